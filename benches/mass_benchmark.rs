@@ -0,0 +1,69 @@
+//! Benchmark for the computation of masses and elemental compositions of
+//! parsed formulas.
+
+use core::hint::black_box;
+use std::str::FromStr;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use molecular_formulas::prelude::*;
+
+/// Representative formulas, from small organics to large mixtures.
+const FORMULAS: [&str; 6] = [
+    "C6H12O6",
+    "PhCOOH",
+    "CuSO4.5H2O",
+    "[Co(NH3)6]Cl3",
+    "C₃₉₀H₄₀₄B₂Br₂ClCs₂F₁₁K₂MnN₂₆Na₂O₁₀₀OsPdS₃W₂³⁻",
+    "C5H9NO.C5H11N.C5H10O2S.3C4H7N.2C4H8O2S.4C3H5NOS.C3H6O3S.C2H5N.C2H4O",
+];
+
+/// Returns the parsed representative formulas.
+fn formulas() -> Vec<(&'static str, ChemicalFormula)> {
+    FORMULAS
+        .into_iter()
+        .map(|formula| (formula, ChemicalFormula::from_str(formula).unwrap()))
+        .collect()
+}
+
+/// Benchmark computing the masses of the formulas.
+fn mass_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mass");
+    group.throughput(Throughput::Elements(1));
+    for (string, formula) in formulas() {
+        group.bench_with_input(BenchmarkId::new("isotopologue", string), &formula, |b, formula| {
+            b.iter(|| black_box(formula).isotopologue_mass());
+        });
+        group.bench_with_input(BenchmarkId::new("molar", string), &formula, |b, formula| {
+            b.iter(|| black_box(formula).molar_mass());
+        });
+        group.bench_with_input(BenchmarkId::new("over charge", string), &formula, |b, formula| {
+            b.iter(|| black_box(formula).isotopologue_mass_over_charge());
+        });
+    }
+    group.finish();
+}
+
+/// Benchmark extracting the elemental compositions of the formulas.
+fn composition_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("composition");
+    group.throughput(Throughput::Elements(1));
+    for (string, formula) in formulas() {
+        group.bench_with_input(BenchmarkId::new("elements", string), &formula, |b, formula| {
+            b.iter(|| black_box(formula).elements().count());
+        });
+        group.bench_with_input(
+            BenchmarkId::new("count of carbon", string),
+            &formula,
+            |b, formula| {
+                b.iter(|| black_box(formula).count_of_element::<u64>(Element::C));
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("diff", string), &formula, |b, formula| {
+            b.iter(|| black_box(formula).diff(black_box(formula)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, mass_benchmark, composition_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+//! Benchmark for the tokenization and the parsing of representative formulas.
+
+use core::hint::black_box;
+use std::str::FromStr;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use molecular_formulas::prelude::*;
+
+/// Representative formulas, grouped by the kind of compound they describe.
+const FORMULAS: [(&str, &str); 12] = [
+    ("small organic", "C6H12O6"),
+    ("small organic", "C8H10N4O2"),
+    ("condensed organic", "CH3COOH"),
+    ("abbreviated organic", "PhCOOH"),
+    ("labelled organic", "[13C]6H12O6"),
+    ("unicode organic", "C₂₇H₄₆O"),
+    ("hydrate", "CuSO4.5H2O"),
+    ("hydrate", "2Fe2O3.3H2O"),
+    ("coordination complex", "[Co(NH3)6]Cl3"),
+    ("coordination complex", "K4[Fe(CN)6]"),
+    ("coordination complex", "[Pt(NH3)2Cl2]"),
+    ("large ion", "C₃₉₀H₄₀₄B₂Br₂ClCs₂F₁₁K₂MnN₂₆Na₂O₁₀₀OsPdS₃W₂³⁻"),
+];
+
+/// Benchmark splitting the formulas into their sub-tokens.
+fn tokenization_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenization");
+    for (kind, formula) in FORMULAS {
+        group.throughput(Throughput::Bytes(formula.len() as u64));
+        group.bench_with_input(BenchmarkId::new(kind, formula), formula, |b, formula| {
+            b.iter(|| {
+                SubTokens::<_, ChemicalFormula, Residual>::from(
+                    black_box(formula).chars().peekable(),
+                )
+                .count()
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Benchmark parsing the formulas into chemical formulas.
+fn parsing_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parsing");
+    for (kind, formula) in FORMULAS {
+        group.throughput(Throughput::Bytes(formula.len() as u64));
+        group.bench_with_input(BenchmarkId::new(kind, formula), formula, |b, formula| {
+            b.iter(|| {
+                let _: ChemicalFormula = ChemicalFormula::from_str(black_box(formula)).unwrap();
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tokenization_benchmark, parsing_benchmark);
+criterion_main!(benches);
@@ -246,12 +246,15 @@ fn main() {
                 fuzz_molecular_formula(&formula);
             }
 
-            // Fuzz ResidualFormula - Has subset of methods
             if let Some(formula) = parse::<ResidualFormula<CountType, ChargeType>>(&data.as_ref()) {
                 round_trip(&data.as_ref(), &formula);
                 fuzz_common_traits(&formula);
+                fuzz_molecular_formula(&formula);
+                fuzz_charged_molecular_formula(&formula);
                 // Specific methods
-                let _ = formula.contains_residuals();
+                if formula.contains_residuals() {
+                    assert_eq!(formula.checked_molar_mass(), None);
+                }
             }
         });
     }
@@ -0,0 +1,39 @@
+/* C interface to the molecular-formulas crate, enabled by the `ffi` feature. */
+#ifndef MOLECULAR_FORMULAS_H
+#define MOLECULAR_FORMULAS_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque handle to a parsed chemical formula. */
+typedef struct ChemicalFormula ChemicalFormula;
+
+/* Parses a NUL-terminated string, returning NULL on failure. */
+ChemicalFormula *molecular_formula_parse(const char *formula);
+
+/* Releases a formula returned by molecular_formula_parse. */
+void molecular_formula_free(ChemicalFormula *formula);
+
+/* Returns the normalized representation of the formula, or NULL. */
+char *molecular_formula_to_string(const ChemicalFormula *formula);
+
+/* Releases a string returned by molecular_formula_to_string. */
+void molecular_formula_string_free(char *string);
+
+/* Returns the monoisotopic mass of the formula, or NaN if it is NULL. */
+double molecular_formula_monoisotopic_mass(const ChemicalFormula *formula);
+
+/* Returns the overall charge of the formula, or NaN if it is NULL. */
+double molecular_formula_charge(const ChemicalFormula *formula);
+
+/* Returns the number of atoms of the given element, or -1 on failure. */
+int64_t molecular_formula_element_count(const ChemicalFormula *formula, const char *symbol);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MOLECULAR_FORMULAS_H */
@@ -0,0 +1,139 @@
+//! Module providing pre-built formulas for very common species, such as
+//! water or the usual adduct ions.
+//!
+//! Each formula is assembled directly from its elemental composition,
+//! without going through the parser, on the first call of its function, and
+//! the same formula is returned by all the following calls, so that the
+//! constants may be used in hot paths. As the formulas are shared across
+//! threads, the module requires the `std` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::str::FromStr;
+//!
+//! use molecular_formulas::{constants, prelude::*};
+//!
+//! let water: &ChemicalFormula = constants::water();
+//! assert_eq!(water, &ChemicalFormula::from_str("H2O").unwrap());
+//! assert_eq!(constants::proton().charge(), 1.0);
+//! ```
+#![cfg(feature = "std")]
+
+use std::sync::OnceLock;
+
+use elements_rs::Element;
+
+use crate::ChemicalFormula;
+
+/// Returns a formula with the provided elemental composition and charge.
+fn formula(composition: &[(Element, u16)], charge: i16) -> ChemicalFormula {
+    let formula = ChemicalFormula::from_element_counts(composition.iter().copied());
+    if charge == 0 {
+        formula
+    } else {
+        formula.charged(charge).expect("A unit charge cannot overflow")
+    }
+}
+
+/// Macro defining a function returning the formula with the provided
+/// elemental composition and charge, built once on its first call.
+macro_rules! constant {
+    ($(#[$doc:meta])* $name:ident, [$(($element:ident, $count:literal)),*], $charge:literal) => {
+        $(#[$doc])*
+        #[must_use]
+        pub fn $name() -> &'static ChemicalFormula {
+            static FORMULA: OnceLock<ChemicalFormula> = OnceLock::new();
+            FORMULA.get_or_init(|| formula(&[$((Element::$element, $count)),*], $charge))
+        }
+    };
+}
+
+constant!(
+    /// Returns water, `H₂O`.
+    water, [(H, 2), (O, 1)], 0
+);
+constant!(
+    /// Returns ammonia, `H₃N`.
+    ammonia, [(H, 3), (N, 1)], 0
+);
+constant!(
+    /// Returns carbon dioxide, `CO₂`.
+    carbon_dioxide, [(C, 1), (O, 2)], 0
+);
+constant!(
+    /// Returns glucose, `C₆H₁₂O₆`.
+    glucose, [(C, 6), (H, 12), (O, 6)], 0
+);
+constant!(
+    /// Returns the proton, `H⁺`.
+    proton, [(H, 1)], 1
+);
+constant!(
+    /// Returns protonated water (hydronium), `H₃O⁺`.
+    hydronium, [(H, 3), (O, 1)], 1
+);
+constant!(
+    /// Returns the ammonium cation, `H₄N⁺`.
+    ammonium, [(H, 4), (N, 1)], 1
+);
+constant!(
+    /// Returns the sodium cation, `Na⁺`.
+    sodium_cation, [(Na, 1)], 1
+);
+constant!(
+    /// Returns the potassium cation, `K⁺`.
+    potassium_cation, [(K, 1)], 1
+);
+constant!(
+    /// Returns the hydroxide anion, `HO⁻`.
+    hydroxide, [(H, 1), (O, 1)], -1
+);
+constant!(
+    /// Returns the chloride anion, `Cl⁻`.
+    chloride, [(Cl, 1)], -1
+);
+constant!(
+    /// Returns the formate anion, `CHO₂⁻`.
+    formate, [(C, 1), (H, 1), (O, 2)], -1
+);
+constant!(
+    /// Returns the acetate anion, `C₂H₃O₂⁻`.
+    acetate, [(C, 2), (H, 3), (O, 2)], -1
+);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_constants_match_parsed_formulas() {
+        let cases: [(&ChemicalFormula, &str); 13] = [
+            (water(), "H2O"),
+            (ammonia(), "H3N"),
+            (carbon_dioxide(), "CO2"),
+            (glucose(), "C6H12O6"),
+            (proton(), "H+"),
+            (hydronium(), "H3O+"),
+            (ammonium(), "H4N+"),
+            (sodium_cation(), "Na+"),
+            (potassium_cation(), "K+"),
+            (hydroxide(), "HO-"),
+            (chloride(), "Cl-"),
+            (formate(), "CHO2-"),
+            (acetate(), "C2H3O2-"),
+        ];
+        for (constant, formula) in cases {
+            assert_eq!(constant, &ChemicalFormula::from_str(formula).unwrap(), "{formula}");
+        }
+    }
+
+    #[test]
+    fn test_constants_are_built_once() {
+        assert!(core::ptr::eq(water(), water()));
+        assert!(core::ptr::eq(proton(), proton()));
+        assert!(!core::ptr::eq(water(), glucose()));
+    }
+}
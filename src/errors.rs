@@ -1,6 +1,8 @@
 //! Submodule defining the error enumeration which might occur when working
 //! with molecular formula.
 
+use alloc::string::String;
+
 use crate::Bracket;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
@@ -46,4 +48,282 @@ pub enum ParserError {
     /// The molecular tree is empty.
     #[error("The molecular tree is empty.")]
     EmptyMolecularTree,
+    /// A charge with an explicit zero magnitude, such as `+0` or `⁰⁺`, was
+    /// found. Neutral species must be written without a charge.
+    #[error("Charges cannot be zero, neutral species must omit the charge.")]
+    ZeroCharge,
+    /// One of the limits set in the parser options was exceeded.
+    #[error("The parser limit on the {0} was exceeded.")]
+    LimitExceeded(ParserLimit),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Enumeration of the limits which can be set in the parser options.
+pub enum ParserLimit {
+    /// The maximal number of tokens.
+    Tokens,
+    /// The maximal nesting depth.
+    Depth,
+    /// The maximal value of a count.
+    Count,
+}
+
+impl core::fmt::Display for ParserLimit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParserLimit::Tokens => write!(f, "number of tokens"),
+            ParserLimit::Depth => write!(f, "nesting depth"),
+            ParserLimit::Count => write!(f, "count magnitude"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when adding atoms to or removing atoms from a formula.
+pub enum CompositionError {
+    /// The formula does not contain enough removable hydrogens.
+    #[error("The formula does not contain enough removable hydrogens.")]
+    InsufficientHydrogens,
+    /// The formula does not contain enough atoms of an element to be
+    /// removed, as when applying a loss.
+    #[error("The formula does not contain enough '{0}' atoms.")]
+    InsufficientAtoms(elements_rs::Element),
+    /// The isotope exchanged for the hydrogens of a formula is not an
+    /// isotope of hydrogen.
+    #[error("The isotope '{0}' is not an isotope of hydrogen.")]
+    NotAHydrogenIsotope(elements_rs::Isotope),
+    /// The number of copies of a formula is zero, as when building a
+    /// multimer of no copy.
+    #[error("The number of copies must be at least one.")]
+    ZeroCopies,
+    /// The repeat unit of a homologous series is charged, while only the
+    /// base formula of the series may carry a charge.
+    #[error("The repeat unit of a homologous series must be neutral.")]
+    ChargedRepeatUnit,
+    /// The resulting formula could not be built, as when it would be empty
+    /// or when a count or its charge overflows.
+    #[error("Parser error: {0}")]
+    Parser(#[from] ParserError),
+}
+
+impl From<NumericError> for CompositionError {
+    fn from(error: NumericError) -> Self {
+        Self::Parser(error.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when building a formula from an element mask and the
+/// counts of its elements.
+pub enum ElementMaskError {
+    /// The element mask does not contain any element.
+    #[error("The element mask is empty.")]
+    EmptyMask,
+    /// The counts provided alongside the element mask do not match its
+    /// elements, either because their numbers differ or because a count is
+    /// zero.
+    #[error("The counts do not match the elements of the element mask.")]
+    MismatchedCounts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when assigning a charge to a mixture component of a
+/// formula.
+pub enum ChargeAssignmentError {
+    /// The formula has no mixture component at the provided index.
+    #[error("The formula has no mixture component at index {0}.")]
+    MissingComponent(usize),
+    /// The charged component could not be built, as when its charge
+    /// overflows.
+    #[error("Parser error: {0}")]
+    Parser(#[from] ParserError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when parsing a formula pattern.
+pub enum FormulaPatternError {
+    /// A term does not start with a known element symbol.
+    #[error("The term '{0}' does not start with an element symbol.")]
+    UnknownElement(String),
+    /// The count or range of counts of a term is malformed.
+    #[error("The term '{0}' has a malformed count.")]
+    MalformedCount(String),
+    /// The minimal count of a term exceeds its maximal count.
+    #[error("The range of counts of the term '{0}' is empty.")]
+    EmptyRange(String),
+    /// An element is constrained by more than one term.
+    #[error("The element '{0}' is constrained more than once.")]
+    DuplicateElement(elements_rs::Element),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when the display of a formula is not parsed back into an
+/// equal formula.
+pub enum RoundtripError {
+    /// The display of the formula cannot be parsed.
+    #[error("The display '{display}' of the formula cannot be parsed: {error}")]
+    Unparsable {
+        /// The display of the formula.
+        display: String,
+        /// The error raised when parsing the display.
+        error: ParserError,
+    },
+    /// The display of the formula is parsed into a different formula.
+    #[error("The display '{0}' of the formula is parsed into a different formula.")]
+    Mismatch(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when a count or charge written in a formula does not
+/// fit in the numeric type it is read into.
+pub enum RangeError {
+    /// A count of the formula exceeds the range of the count type.
+    #[error(
+        "The count {count} at byte {position} of '{formula}' exceeds the range of `{type_name}`."
+    )]
+    Count {
+        /// The formula containing the count.
+        formula: String,
+        /// The byte offset of the count in the formula.
+        position: usize,
+        /// The count written in the formula.
+        count: u64,
+        /// The name of the count type.
+        type_name: &'static str,
+    },
+    /// The charge of the formula exceeds the range of the charge type.
+    #[error(
+        "The charge {charge} at byte {position} of '{formula}' exceeds the range of `{type_name}`."
+    )]
+    Charge {
+        /// The formula containing the charge.
+        formula: String,
+        /// The byte offset of the charge in the formula.
+        position: usize,
+        /// The charge written in the formula.
+        charge: i64,
+        /// The name of the charge type.
+        type_name: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when setting the enrichment of the labelled positions of
+/// a formula.
+pub enum LabelingError {
+    /// The enrichment is not a fraction within zero and one.
+    #[error("The enrichment must be within zero and one.")]
+    InvalidEnrichment,
+    /// The formula has no position labelled with the isotope.
+    #[error("The formula has no position labelled with {0}.")]
+    MissingLabel(elements_rs::Isotope),
+    /// The formula has fewer positions labelled with the isotope than the
+    /// index of the position.
+    #[error("The formula has no position {position} labelled with {isotope}.")]
+    MissingPosition {
+        /// The isotope of the position.
+        isotope: elements_rs::Isotope,
+        /// The index of the position among those labelled with the isotope.
+        position: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when enumerating the formulas obtained by substituting
+/// the residuals of a formula with fragments.
+pub enum EnumerationError {
+    /// The fragment at the index is made of several mixtures, or of a
+    /// mixture with a count other than one.
+    #[error("The fragment at index {0} is not made of a single mixture with a unit count.")]
+    MixtureFragment(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+/// Error enumeration when configuring the valence model used to fill
+/// skeleton compositions with implicit hydrogens.
+pub enum ValenceModelError {
+    /// No valence was provided for the element.
+    #[error("At least one valence of '{0}' is required.")]
+    MissingValences(elements_rs::Element),
+    /// The minimal unsaturation exceeds the maximal one.
+    #[error("The minimal unsaturation {min} exceeds the maximal unsaturation {max}.")]
+    EmptyUnsaturation {
+        /// The minimal unsaturation.
+        min: u32,
+        /// The maximal unsaturation.
+        max: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+/// Error enumeration gathering the errors of the crate by category.
+///
+/// Each of the errors of the crate converts into this error, so that
+/// functions combining several parts of the crate may return it with the
+/// `?` operator. The enumeration is non exhaustive so that adding a
+/// category is not a breaking change: matches on it must provide a
+/// wildcard arm, while the detailed error of each category remains
+/// available through its variant and through
+/// [`source`](core::error::Error::source).
+///
+/// # Examples
+///
+/// ```
+/// use core::{error::Error as _, str::FromStr};
+///
+/// use molecular_formulas::{
+///     errors::{Error, ParserError},
+///     prelude::*,
+/// };
+///
+/// fn parse(formula: &str) -> Result<ChemicalFormula, Error> {
+///     Ok(ChemicalFormula::from_str(formula)?)
+/// }
+///
+/// let error = parse("H2O?").unwrap_err();
+/// assert!(matches!(error, Error::Parser(ParserError::UnexpectedCharacter('?'))));
+/// assert_eq!(
+///     error.source().unwrap().to_string(),
+///     "Character '?' is not allowed in the current molecular formula tree."
+/// );
+/// ```
+pub enum Error {
+    /// A formula could not be parsed, or an operation on it failed.
+    #[error("Parser error: {0}")]
+    Parser(#[from] ParserError),
+    /// A numeric operation overflowed or a number was malformed.
+    #[error("Numeric error: {0}")]
+    Numeric(#[from] NumericError),
+    /// A count or charge does not fit in its numeric type.
+    #[error("Range error: {0}")]
+    Range(#[from] RangeError),
+    /// Atoms could not be added to or removed from a formula.
+    #[error("Composition error: {0}")]
+    Composition(#[from] CompositionError),
+    /// A formula could not be built from an element mask.
+    #[error("Element mask error: {0}")]
+    ElementMask(#[from] ElementMaskError),
+    /// A charge could not be assigned to a mixture component.
+    #[error("Charge assignment error: {0}")]
+    ChargeAssignment(#[from] ChargeAssignmentError),
+    /// A formula pattern could not be parsed.
+    #[error("Formula pattern error: {0}")]
+    FormulaPattern(#[from] FormulaPatternError),
+    /// The display of a formula does not round-trip.
+    #[error("Roundtrip error: {0}")]
+    Roundtrip(#[from] RoundtripError),
+    /// The enrichment of a labelled position could not be set.
+    #[error("Labeling error: {0}")]
+    Labeling(#[from] LabelingError),
+    /// The formulas of a residual formula could not be enumerated.
+    #[error("Enumeration error: {0}")]
+    Enumeration(#[from] EnumerationError),
+    /// A valence model could not be configured.
+    #[error("Valence model error: {0}")]
+    ValenceModel(#[from] ValenceModelError),
+    /// A formula could not be extracted from an SD file.
+    #[cfg(feature = "sdf")]
+    #[error("SD file error: {0}")]
+    Sdf(#[from] crate::sdf::SdfError),
 }
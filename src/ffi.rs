@@ -0,0 +1,183 @@
+//! Module providing a C-compatible interface to the chemical formulas, so that
+//! C and C++ software can embed the parser.
+//!
+//! The matching C declarations are available in the
+//! `include/molecular_formulas.h` header shipped with the crate, whose
+//! declarations are checked against the functions of this module by the
+//! `test_ffi_header` test.
+#![cfg(feature = "ffi")]
+use alloc::{boxed::Box, ffi::CString, string::ToString};
+use core::{
+    ffi::{CStr, c_char},
+    ptr,
+    str::FromStr,
+};
+
+use elements_rs::Element;
+
+use crate::{ChargedMolecularFormula, ChemicalFormula, MolecularFormula};
+
+/// Parses the provided NUL-terminated string into a chemical formula.
+///
+/// Returns a null pointer if the string is null, is not valid UTF-8 or is not
+/// a valid chemical formula. The returned formula must be released with
+/// [`molecular_formula_free`].
+///
+/// # Safety
+///
+/// * `formula` must either be null or point to a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn molecular_formula_parse(formula: *const c_char) -> *mut ChemicalFormula {
+    if formula.is_null() {
+        return ptr::null_mut();
+    }
+    // SAFETY: the caller guarantees that the pointer is a valid C string.
+    let Ok(formula) = unsafe { CStr::from_ptr(formula) }.to_str() else {
+        return ptr::null_mut();
+    };
+    ChemicalFormula::from_str(formula)
+        .map_or(ptr::null_mut(), |formula| Box::into_raw(Box::new(formula)))
+}
+
+/// Releases a chemical formula returned by [`molecular_formula_parse`].
+///
+/// # Safety
+///
+/// * `formula` must either be null or a pointer returned by
+///   [`molecular_formula_parse`] which has not been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn molecular_formula_free(formula: *mut ChemicalFormula) {
+    if !formula.is_null() {
+        // SAFETY: the caller guarantees that the pointer was allocated by us.
+        drop(unsafe { Box::from_raw(formula) });
+    }
+}
+
+/// Returns the normalized string representation of the chemical formula.
+///
+/// Returns a null pointer if the formula is null. The returned string must be
+/// released with [`molecular_formula_string_free`].
+///
+/// # Safety
+///
+/// * `formula` must either be null or a valid pointer returned by
+///   [`molecular_formula_parse`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn molecular_formula_to_string(
+    formula: *const ChemicalFormula,
+) -> *mut c_char {
+    // SAFETY: the caller guarantees that the pointer is valid or null.
+    let Some(formula) = (unsafe { formula.as_ref() }) else {
+        return ptr::null_mut();
+    };
+    CString::new(formula.to_string()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by [`molecular_formula_to_string`].
+///
+/// # Safety
+///
+/// * `string` must either be null or a pointer returned by
+///   [`molecular_formula_to_string`] which has not been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn molecular_formula_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the caller guarantees that the pointer was allocated by us.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Returns the monoisotopic mass of the chemical formula, or `NaN` if the
+/// formula is null.
+///
+/// # Safety
+///
+/// * `formula` must either be null or a valid pointer returned by
+///   [`molecular_formula_parse`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn molecular_formula_monoisotopic_mass(
+    formula: *const ChemicalFormula,
+) -> f64 {
+    // SAFETY: the caller guarantees that the pointer is valid or null.
+    unsafe { formula.as_ref() }.map_or(f64::NAN, ChemicalFormula::isotopologue_mass)
+}
+
+/// Returns the overall charge of the chemical formula, or `NaN` if the formula
+/// is null.
+///
+/// # Safety
+///
+/// * `formula` must either be null or a valid pointer returned by
+///   [`molecular_formula_parse`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn molecular_formula_charge(formula: *const ChemicalFormula) -> f64 {
+    // SAFETY: the caller guarantees that the pointer is valid or null.
+    unsafe { formula.as_ref() }.map_or(f64::NAN, ChemicalFormula::charge)
+}
+
+/// Returns the number of atoms of the element with the provided symbol in the
+/// chemical formula.
+///
+/// Returns `-1` if either pointer is null, if the symbol is not a valid
+/// element symbol or if the count overflows.
+///
+/// # Safety
+///
+/// * `formula` must either be null or a valid pointer returned by
+///   [`molecular_formula_parse`].
+/// * `symbol` must either be null or point to a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn molecular_formula_element_count(
+    formula: *const ChemicalFormula,
+    symbol: *const c_char,
+) -> i64 {
+    if symbol.is_null() {
+        return -1;
+    }
+    // SAFETY: the caller guarantees that the pointer is valid or null.
+    let Some(formula) = (unsafe { formula.as_ref() }) else {
+        return -1;
+    };
+    // SAFETY: the caller guarantees that the pointer is a valid C string.
+    let Some(element) =
+        unsafe { CStr::from_ptr(symbol) }.to_str().ok().and_then(|s| Element::from_str(s).ok())
+    else {
+        return -1;
+    };
+    formula.count_of_element::<i64>(element).unwrap_or(-1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_roundtrip() {
+        let input = CString::new("C6H12O6").unwrap();
+        let carbon = CString::new("C").unwrap();
+        let unknown = CString::new("Xx").unwrap();
+        unsafe {
+            let formula = molecular_formula_parse(input.as_ptr());
+            assert!(!formula.is_null());
+            let string = molecular_formula_to_string(formula);
+            assert_eq!(CStr::from_ptr(string).to_str().unwrap(), "C₆H₁₂O₆");
+            molecular_formula_string_free(string);
+            assert!(molecular_formula_monoisotopic_mass(formula) > 180.0);
+            assert!(molecular_formula_charge(formula).abs() < f64::EPSILON);
+            assert_eq!(molecular_formula_element_count(formula, carbon.as_ptr()), 6);
+            assert_eq!(molecular_formula_element_count(formula, unknown.as_ptr()), -1);
+            molecular_formula_free(formula);
+        }
+    }
+
+    #[test]
+    fn test_ffi_invalid_input() {
+        let input = CString::new("C6H12O6)").unwrap();
+        unsafe {
+            assert!(molecular_formula_parse(input.as_ptr()).is_null());
+            assert!(molecular_formula_parse(ptr::null()).is_null());
+            assert!(molecular_formula_to_string(ptr::null()).is_null());
+            assert!(molecular_formula_monoisotopic_mass(ptr::null()).is_nan());
+        }
+    }
+}
@@ -1,11 +1,18 @@
 //! Module providing fuzzing utilities for molecular formulas.
 #![cfg(feature = "fuzzing")]
-use alloc::string::{String, ToString};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::fmt::Display;
 
 use arbitrary::{Arbitrary, Result, Unstructured};
+use elements_rs::Element;
 
-use crate::{ChargeLike, CountLike, SubToken, Token};
+use crate::{
+    ChargeLike, CountLike, Empty, PolymorphPrefix, SubToken, Token, molecular_tree::hill_sort,
+};
 
 #[derive(Debug, Clone)]
 /// Wrapper struct for fuzzing molecular formulas.
@@ -39,40 +46,183 @@ where
     Extension: Arbitrary<'a>,
 {
     fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
-        let len = u.int_in_range(0..=20)?;
-        let mut formula = String::with_capacity(len * 3);
-        let generate_token = u.arbitrary::<bool>()?;
-        let generate_subtoken = u.arbitrary::<bool>()?;
-        if generate_token {
-            // Generate only tokens, 50% chance
-            for _ in 0..len {
-                let token = u.arbitrary::<Token<Count, Charge, Extension>>()?;
-                formula.push_str(&token.to_string());
+        let formula = match u.int_in_range(0..=7)? {
+            // Generate only tokens, 37.5% chance
+            0..=2 => {
+                let len = u.int_in_range(0..=20)?;
+                let mut formula = String::with_capacity(len * 3);
+                for _ in 0..len {
+                    let token = u.arbitrary::<Token<Count, Charge, Extension>>()?;
+                    formula.push_str(&token.to_string());
+                }
+                formula
             }
-        } else if generate_subtoken {
-            // Generate only subtokens, 25% chance
-            for _ in 0..len {
-                let subtoken = u.arbitrary::<SubToken<Count, Charge, Extension>>()?;
-                formula.push_str(&subtoken.to_string());
+            // Generate only subtokens, 12.5% chance
+            3 => {
+                let len = u.int_in_range(0..=20)?;
+                let mut formula = String::with_capacity(len * 3);
+                for _ in 0..len {
+                    let subtoken = u.arbitrary::<SubToken<Count, Charge, Extension>>()?;
+                    formula.push_str(&subtoken.to_string());
+                }
+                formula
             }
-        } else {
-            // Generate a completely random string of characters, 25% chance
-            for _ in 0..len {
-                formula.push(u.arbitrary::<char>()?);
+            // Generate a completely random string of characters, 12.5% chance
+            4 => {
+                let len = u.int_in_range(0..=20)?;
+                let mut formula = String::with_capacity(len);
+                for _ in 0..len {
+                    formula.push(u.arbitrary::<char>()?);
+                }
+                formula
             }
-        }
+            // Generate a structured mineral formula, 12.5% chance
+            5 => mineral_formula::<Count>(u)?,
+            // Generate a structured formula with residuals, 12.5% chance
+            6 => residual_formula::<Count, Extension>(u)?,
+            // Generate a structured InChI formula, 12.5% chance
+            _ => inchi_formula::<Count>(u)?,
+        };
 
         Ok(FuzzFormula { formula, _marker: core::marker::PhantomData })
     }
 }
 
+/// Returns the string representation of a count, which is usually small and
+/// may be omitted, but occasionally spans the whole range of the count type
+/// so to exercise the overflow checks.
+fn count<'a, Count>(u: &mut Unstructured<'a>) -> Result<String>
+where
+    Count: CountLike + Arbitrary<'a>,
+{
+    Ok(match u.int_in_range(0..=7)? {
+        0..=2 => String::new(),
+        7 => u.arbitrary::<Count>()?.to_string(),
+        _ => u.int_in_range(2_u8..=12)?.to_string(),
+    })
+}
+
+/// Returns a trailing charge, such as `+`, `-2` or `3+`, if any.
+fn charge(u: &mut Unstructured<'_>) -> Result<String> {
+    let sign = if u.arbitrary::<bool>()? { '+' } else { '-' };
+    Ok(match u.int_in_range(0..=5)? {
+        0..=2 => String::new(),
+        3 => sign.to_string(),
+        4 => format!("{sign}{}", u.int_in_range(2_u8..=4)?),
+        _ => format!("{}{sign}", u.int_in_range(2_u8..=4)?),
+    })
+}
+
+/// Returns a group of elements with their counts, possibly including the
+/// provided extension and wrapped in counted brackets.
+fn group<'a, Count, Extension>(
+    u: &mut Unstructured<'a>,
+    extension: Option<&Extension>,
+) -> Result<String>
+where
+    Count: CountLike + Arbitrary<'a>,
+    Extension: Display,
+{
+    let mut group = String::new();
+    for _ in 0..u.int_in_range(1..=4)? {
+        match extension {
+            Some(extension) if u.ratio(1, 3)? => group.push_str(&extension.to_string()),
+            _ => group.push_str(u.arbitrary::<Element>()?.as_ref()),
+        }
+        group.push_str(&count::<Count>(u)?);
+    }
+    if u.ratio(1, 4)? {
+        let (open, close) = if u.arbitrary::<bool>()? { ('(', ')') } else { ('[', ']') };
+        group = format!("{open}{group}{close}{}", count::<Count>(u)?);
+    }
+    Ok(group)
+}
+
+/// Returns a mineral formula with an optional polymorph prefix, such as
+/// `α-Fe2O3` or `2Fe2O3.3H2O`.
+fn mineral_formula<'a, Count>(u: &mut Unstructured<'a>) -> Result<String>
+where
+    Count: CountLike + Arbitrary<'a>,
+{
+    let mut formula = String::new();
+    if u.ratio(3, 4)? {
+        formula.push(char::from(u.arbitrary::<PolymorphPrefix>()?));
+        formula.push(*u.choose(&['-', '\u{2212}', '\u{2013}', '\u{2014}'])?);
+    }
+    for mixture in 0..u.int_in_range(1..=3)? {
+        if mixture > 0 {
+            formula.push('.');
+        }
+        formula.push_str(&count::<Count>(u)?);
+        for _ in 0..u.int_in_range(1..=3)? {
+            formula.push_str(&group::<Count, Empty>(u, None)?);
+        }
+    }
+    formula.push_str(&charge(u)?);
+    Ok(formula)
+}
+
+/// Returns a formula made of several mixtures, each of which may contain
+/// several residual groups, such as `RC(O)OR` or `·[CH2R]2-`.
+fn residual_formula<'a, Count, Extension>(u: &mut Unstructured<'a>) -> Result<String>
+where
+    Count: CountLike + Arbitrary<'a>,
+    Extension: Display + Arbitrary<'a>,
+{
+    let mut formula = String::new();
+    if u.ratio(1, 8)? {
+        formula.push('·');
+    }
+    for mixture in 0..u.int_in_range(1..=3)? {
+        if mixture > 0 {
+            formula.push('.');
+        }
+        formula.push_str(&count::<Count>(u)?);
+        for _ in 0..u.int_in_range(1..=4)? {
+            let extension = u.arbitrary::<Extension>()?;
+            formula.push_str(&group::<Count, Extension>(u, Some(&extension))?);
+        }
+    }
+    formula.push_str(&charge(u)?);
+    Ok(formula)
+}
+
+/// Returns a formula in the InChI notation, made of Hill sorted elements
+/// with ASCII counts and of mixtures with optional leading multipliers, such
+/// as `2C2H6O.ClNa`.
+fn inchi_formula<'a, Count>(u: &mut Unstructured<'a>) -> Result<String>
+where
+    Count: CountLike + Arbitrary<'a>,
+{
+    let mut formula = String::new();
+    for mixture in 0..u.int_in_range(1..=3)? {
+        if mixture > 0 {
+            formula.push('.');
+        }
+        if u.ratio(1, 4)? {
+            formula.push_str(&u.int_in_range(2_u8..=6)?.to_string());
+        }
+        let mut elements = Vec::new();
+        for _ in 0..u.int_in_range(1..=5)? {
+            elements.push(u.arbitrary::<Element>()?);
+        }
+        hill_sort(&mut elements);
+        elements.dedup();
+        for element in elements {
+            formula.push_str(element.as_ref());
+            formula.push_str(&count::<Count>(u)?);
+        }
+    }
+    Ok(formula)
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::string::ToString;
-    use core::marker::PhantomData;
+    use core::{marker::PhantomData, str::FromStr};
 
     use super::*;
-    use crate::{Empty, Residual};
+    use crate::{InChIFormula, MineralFormula, MolecularFormula, Residual, ResidualFormula};
 
     #[test]
     fn test_fuzz_formula_manual_residual() {
@@ -155,4 +305,64 @@ mod tests {
         }
         assert!(hit_non_empty, "Should have generated at least one non-empty formula");
     }
+
+    /// Returns pseudo-random bytes for the structured generators.
+    #[allow(clippy::cast_possible_truncation)]
+    fn pseudo_random_bytes() -> Vec<u8> {
+        (0u32..16384).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect()
+    }
+
+    /// Asserts that most of the strings produced by the provided generator
+    /// from the provided bytes are parsed successfully, and that at least one
+    /// of them satisfies the provided predicate.
+    fn assert_mostly_parsable<'a, M: FromStr>(
+        bytes: &'a [u8],
+        generator: impl Fn(&mut Unstructured<'a>) -> Result<String>,
+        predicate: impl Fn(&str, &M) -> bool,
+    ) {
+        let mut u = Unstructured::new(bytes);
+        let mut generated = 0;
+        let mut parsed = 0;
+        let mut satisfied = false;
+        while !u.is_empty() {
+            let formula = generator(&mut u).unwrap();
+            generated += 1;
+            if let Ok(parsed_formula) = M::from_str(&formula) {
+                parsed += 1;
+                satisfied |= predicate(&formula, &parsed_formula);
+            }
+        }
+        assert!(parsed * 2 > generated, "Only {parsed} of {generated} formulas were parsed");
+        assert!(satisfied, "No parsed formula satisfied the predicate");
+    }
+
+    #[test]
+    fn test_mineral_formula_generator() {
+        assert_mostly_parsable::<MineralFormula<u16, i16>>(
+            &pseudo_random_bytes(),
+            mineral_formula::<u16>,
+            |_, formula| {
+                formula.to_string().starts_with(|c: char| PolymorphPrefix::try_from(c).is_ok())
+                    && formula.number_of_mixtures() > 1
+            },
+        );
+    }
+
+    #[test]
+    fn test_residual_formula_generator() {
+        assert_mostly_parsable::<ResidualFormula<u16, i16>>(
+            &pseudo_random_bytes(),
+            residual_formula::<u16, Residual>,
+            |string, formula| formula.contains_residuals() && string.matches('R').count() > 1,
+        );
+    }
+
+    #[test]
+    fn test_inchi_formula_generator() {
+        assert_mostly_parsable::<InChIFormula<u16>>(
+            &pseudo_random_bytes(),
+            inchi_formula::<u16>,
+            |_, formula| formula.number_of_mixtures() > 1,
+        );
+    }
 }
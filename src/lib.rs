@@ -1,10 +1,11 @@
 #![doc = include_str!("../README.md")]
-#![cfg_attr(not(feature = "fuzzing"), no_std)]
+#![cfg_attr(not(any(feature = "fuzzing", feature = "std")), no_std)]
 
 /// The crate is solely dependent on the alloc crate,
-/// not the standard library.
+/// not the standard library, unless the `std` feature is enabled.
 extern crate alloc;
 
+pub mod constants;
 pub mod errors;
 pub mod molecular_formula;
 pub(crate) mod molecular_tree;
@@ -12,17 +13,28 @@ pub mod nodes;
 pub mod parsable;
 mod serde_impl;
 mod utils;
+pub use errors::Error;
 pub use molecular_formula::*;
 pub use molecular_tree::*;
 pub use nodes::*;
 pub use parsable::*;
-pub(crate) use utils::{display_charge, display_isotope};
+pub(crate) use utils::{
+    WideCount, convert_charge, convert_count, display_charge, display_isotope, number_from_u8,
+    saturating_u64, saturating_usize, wide_count_of_element, wide_count_of_isotope,
+};
+pub mod ffi;
 pub mod fuzzing;
+pub mod modifications;
+pub mod property_testing;
+pub mod sdf;
+pub mod units;
+pub mod validation;
+pub mod wasm;
 
 /// Prelude module re-exporting commonly used items.
 pub mod prelude {
     /// Re-exports from the elements_rs crate.
-    pub use elements_rs::{Element, ElementVariant, Isotope, MassNumber};
+    pub use elements_rs::{Element, ElementMask, ElementVariant, Isotope, MassNumber};
 
     pub use crate::{molecular_formula::*, molecular_tree::*, nodes::*, parsable::*};
 }
@@ -0,0 +1,352 @@
+//! Module providing a library of the chemical modifications commonly
+//! considered in proteomics and metabolomics, expressed as the change of
+//! elemental composition they cause.
+//!
+//! Modifications are applied to and removed from formulas by their
+//! elemental composition, so isotopic labels and the arrangement of the
+//! original formula are not preserved, while its net charge is.
+#![cfg(feature = "modifications")]
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use elements_rs::{Element, RelativeAtomicMass};
+
+use crate::{
+    ChargeLike, ChemicalFormula, CountLike, FormulaDiff, MolecularFormula,
+    errors::{CompositionError, NumericError, ParserError},
+    molecular_formula::fmt_composition_delta,
+    molecular_tree::hill_sort,
+    number_from_u8, wide_count_of_element,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumeration of common chemical modifications, such as post-translational
+/// modifications of proteins and glycan units.
+pub enum Modification {
+    /// Acetylation, `C(2) H(2) O`.
+    Acetylation,
+    /// Methylation, `C H(2)`.
+    Methylation,
+    /// Dimethylation, `C(2) H(4)`.
+    Dimethylation,
+    /// Trimethylation, `C(3) H(6)`.
+    Trimethylation,
+    /// Phosphorylation, `H O(3) P`.
+    Phosphorylation,
+    /// Sulfation, `O(3) S`.
+    Sulfation,
+    /// Oxidation, `O`.
+    Oxidation,
+    /// Deamidation, `H(-1) N(-1) O`.
+    Deamidation,
+    /// C-terminal amidation, `H N O(-1)`.
+    Amidation,
+    /// Carbamidomethylation, `C(2) H(3) N O`.
+    Carbamidomethylation,
+    /// Carbamylation, `C H N O`.
+    Carbamylation,
+    /// Formylation, `C O`.
+    Formylation,
+    /// Dehydration, `H(-2) O(-1)`.
+    Dehydration,
+    /// Hexose unit, such as glucose or mannose, `C(6) H(10) O(5)`.
+    Hexose,
+    /// N-acetylhexosamine unit, such as GlcNAc, `C(8) H(13) N O(5)`.
+    HexNAc,
+    /// Deoxyhexose unit, such as fucose, `C(6) H(10) O(4)`.
+    Deoxyhexose,
+    /// N-acetylneuraminic acid unit, `C(11) H(17) N O(8)`.
+    NeuAc,
+    /// Pentose unit, such as xylose, `C(5) H(8) O(4)`.
+    Pentose,
+    /// Hexuronic acid unit, such as glucuronic acid, `C(6) H(8) O(6)`.
+    HexA,
+}
+
+impl Modification {
+    /// All the modifications in the library.
+    pub const ALL: [Modification; 19] = [
+        Modification::Acetylation,
+        Modification::Methylation,
+        Modification::Dimethylation,
+        Modification::Trimethylation,
+        Modification::Phosphorylation,
+        Modification::Sulfation,
+        Modification::Oxidation,
+        Modification::Deamidation,
+        Modification::Amidation,
+        Modification::Carbamidomethylation,
+        Modification::Carbamylation,
+        Modification::Formylation,
+        Modification::Dehydration,
+        Modification::Hexose,
+        Modification::HexNAc,
+        Modification::Deoxyhexose,
+        Modification::NeuAc,
+        Modification::Pentose,
+        Modification::HexA,
+    ];
+
+    /// Returns the name of the modification in the Unimod database.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::modifications::Modification;
+    ///
+    /// assert_eq!(Modification::Phosphorylation.name(), "Phospho");
+    /// assert_eq!(Modification::Carbamidomethylation.name(), "Carbamidomethyl");
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Modification::Acetylation => "Acetyl",
+            Modification::Methylation => "Methyl",
+            Modification::Dimethylation => "Dimethyl",
+            Modification::Trimethylation => "Trimethyl",
+            Modification::Phosphorylation => "Phospho",
+            Modification::Sulfation => "Sulfo",
+            Modification::Oxidation => "Oxidation",
+            Modification::Deamidation => "Deamidated",
+            Modification::Amidation => "Amidated",
+            Modification::Carbamidomethylation => "Carbamidomethyl",
+            Modification::Carbamylation => "Carbamyl",
+            Modification::Formylation => "Formyl",
+            Modification::Dehydration => "Dehydrated",
+            Modification::Hexose => "Hex",
+            Modification::HexNAc => "HexNAc",
+            Modification::Deoxyhexose => "dHex",
+            Modification::NeuAc => "NeuAc",
+            Modification::Pentose => "Pent",
+            Modification::HexA => "HexA",
+        }
+    }
+
+    /// Returns the change of elemental composition caused by the
+    /// modification, in Hill order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::{modifications::Modification, prelude::*};
+    ///
+    /// assert_eq!(
+    ///     Modification::Deamidation.composition(),
+    ///     &[(Element::H, -1), (Element::N, -1), (Element::O, 1)]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn composition(&self) -> &'static [(Element, i8)] {
+        match self {
+            Modification::Acetylation => &[(Element::C, 2), (Element::H, 2), (Element::O, 1)],
+            Modification::Methylation => &[(Element::C, 1), (Element::H, 2)],
+            Modification::Dimethylation => &[(Element::C, 2), (Element::H, 4)],
+            Modification::Trimethylation => &[(Element::C, 3), (Element::H, 6)],
+            Modification::Phosphorylation => &[(Element::H, 1), (Element::O, 3), (Element::P, 1)],
+            Modification::Sulfation => &[(Element::O, 3), (Element::S, 1)],
+            Modification::Oxidation => &[(Element::O, 1)],
+            Modification::Deamidation => &[(Element::H, -1), (Element::N, -1), (Element::O, 1)],
+            Modification::Amidation => &[(Element::H, 1), (Element::N, 1), (Element::O, -1)],
+            Modification::Carbamidomethylation => {
+                &[(Element::C, 2), (Element::H, 3), (Element::N, 1), (Element::O, 1)]
+            }
+            Modification::Carbamylation => {
+                &[(Element::C, 1), (Element::H, 1), (Element::N, 1), (Element::O, 1)]
+            }
+            Modification::Formylation => &[(Element::C, 1), (Element::O, 1)],
+            Modification::Dehydration => &[(Element::H, -2), (Element::O, -1)],
+            Modification::Hexose => &[(Element::C, 6), (Element::H, 10), (Element::O, 5)],
+            Modification::HexNAc => {
+                &[(Element::C, 8), (Element::H, 13), (Element::N, 1), (Element::O, 5)]
+            }
+            Modification::Deoxyhexose => &[(Element::C, 6), (Element::H, 10), (Element::O, 4)],
+            Modification::NeuAc => {
+                &[(Element::C, 11), (Element::H, 17), (Element::N, 1), (Element::O, 8)]
+            }
+            Modification::Pentose => &[(Element::C, 5), (Element::H, 8), (Element::O, 4)],
+            Modification::HexA => &[(Element::C, 6), (Element::H, 8), (Element::O, 6)],
+        }
+    }
+
+    /// Returns the monoisotopic mass shift caused by the modification, which
+    /// is negative for modifications removing more mass than they add.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::modifications::Modification;
+    ///
+    /// assert!((Modification::Phosphorylation.isotopologue_mass() - 79.966331).abs() < 1e-6);
+    /// assert!((Modification::Dehydration.isotopologue_mass() + 18.010565).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    pub fn isotopologue_mass(&self) -> f64 {
+        self.composition()
+            .iter()
+            .map(|(element, count)| f64::from(*count) * element.relative_atomic_mass())
+            .sum()
+    }
+
+    /// Returns the formula obtained by applying the modification to the
+    /// provided formula.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{modifications::Modification, prelude::*};
+    ///
+    /// let serine: ChemicalFormula = ChemicalFormula::from_str("C3H7NO3").unwrap();
+    /// let phosphoserine = Modification::Phosphorylation.apply(&serine).unwrap();
+    /// assert_eq!(phosphoserine.to_string(), "C₃H₈NO₆P");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * If the formula does not contain enough atoms for the atoms removed by
+    ///   the modification.
+    /// * If the modified formula would not contain any atom.
+    /// * If any of the counts or the charge overflows.
+    pub fn apply<Count: CountLike, Charge: ChargeLike>(
+        &self,
+        formula: &ChemicalFormula<Count, Charge>,
+    ) -> Result<ChemicalFormula<Count, Charge>, CompositionError> {
+        self.shift(formula, true)
+    }
+
+    /// Returns the formula obtained by removing the modification from the
+    /// provided formula, i.e. the formula it was applied to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{modifications::Modification, prelude::*};
+    ///
+    /// let acetyllysine: ChemicalFormula = ChemicalFormula::from_str("C8H16N2O3").unwrap();
+    /// let lysine = Modification::Acetylation.remove(&acetyllysine).unwrap();
+    /// assert_eq!(lysine.to_string(), "C₆H₁₄N₂O₂");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * If the formula does not contain enough atoms for the atoms added by
+    ///   the modification.
+    /// * If the unmodified formula would not contain any atom.
+    /// * If any of the counts or the charge overflows.
+    pub fn remove<Count: CountLike, Charge: ChargeLike>(
+        &self,
+        formula: &ChemicalFormula<Count, Charge>,
+    ) -> Result<ChemicalFormula<Count, Charge>, CompositionError> {
+        self.shift(formula, false)
+    }
+
+    /// Returns whether the provided difference between two formulas
+    /// corresponds to the modification, i.e. whether it has the same change
+    /// of elemental composition and no change of charge.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{modifications::Modification, prelude::*};
+    ///
+    /// let glucose: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    /// let maltose: ChemicalFormula = ChemicalFormula::from_str("C12H22O11").unwrap();
+    /// assert!(Modification::Hexose.matches(&maltose.diff(&glucose)));
+    /// assert!(!Modification::Deoxyhexose.matches(&maltose.diff(&glucose)));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, diff: &FormulaDiff) -> bool {
+        diff.charge().abs() < f64::EPSILON
+            && diff
+                .elements()
+                .eq(self.composition().iter().map(|&(element, count)| (element, i64::from(count))))
+    }
+
+    /// Returns the formula shifted by the composition of the modification,
+    /// added when `gain` is set and subtracted otherwise.
+    fn shift<Count: CountLike, Charge: ChargeLike>(
+        self,
+        formula: &ChemicalFormula<Count, Charge>,
+        gain: bool,
+    ) -> Result<ChemicalFormula<Count, Charge>, CompositionError> {
+        let overflow = CompositionError::from(NumericError::PositiveOverflow);
+        let mut elements: Vec<Element> = formula
+            .element_mask()
+            .into_iter()
+            .chain(self.composition().iter().map(|(element, _)| *element))
+            .collect();
+        hill_sort(&mut elements);
+        elements.dedup();
+
+        let mut counts: Vec<(Element, Count)> = Vec::with_capacity(elements.len());
+        for element in elements {
+            let count: Count = u64::try_from(wide_count_of_element(formula, element))
+                .ok()
+                .and_then(|count| Count::try_from(count).ok())
+                .ok_or(overflow)?;
+            let delta = self
+                .composition()
+                .iter()
+                .find(|(e, _)| *e == element)
+                .map_or(0, |(_, delta)| if gain { *delta } else { -*delta });
+            let magnitude: Count = number_from_u8(delta.unsigned_abs());
+            let count = if delta < 0 {
+                count.checked_sub(&magnitude).ok_or(CompositionError::InsufficientAtoms(element))?
+            } else {
+                count.checked_add(&magnitude).ok_or(overflow)?
+            };
+            counts.push((element, count));
+        }
+        if counts.iter().all(|(_, count)| count.is_zero()) {
+            return Err(ParserError::EmptyMolecularTree.into());
+        }
+        let charge = formula.net_charge()?;
+        let modified = ChemicalFormula::from_element_counts(counts);
+        if charge.is_zero() { Ok(modified) } else { Ok(modified.charged(charge)?) }
+    }
+}
+
+impl Display for Modification {
+    /// Writes the change of elemental composition in the notation of the
+    /// Unimod database, such as `H O(3) P`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_composition_delta(
+            self.composition().iter().map(|&(element, count)| (element, i64::from(count))),
+            f,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_compositions_are_hill_sorted() {
+        for modification in Modification::ALL {
+            let mut elements: Vec<Element> =
+                modification.composition().iter().map(|(element, _)| *element).collect();
+            hill_sort(&mut elements);
+            assert!(
+                modification.composition().iter().map(|(element, _)| *element).eq(elements),
+                "{modification:?} is not Hill sorted"
+            );
+            assert!(modification.composition().iter().all(|(_, count)| *count != 0));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Modification::Phosphorylation.to_string(), "H O(3) P");
+        assert_eq!(Modification::Dehydration.to_string(), "H(-2) O(-1)");
+        assert_eq!(Modification::Oxidation.to_string(), "O");
+    }
+}
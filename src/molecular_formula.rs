@@ -1,19 +1,82 @@
 //! Properties that can be computed from molecular formulas.
 
-use core::{fmt::Display, iter::repeat_n};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{cmp::Ordering, fmt::Display, iter::repeat_n, str::FromStr};
 
-use crate::{ChargeLike, ChargedMolecularTree, CountLike, MolecularTree, prelude::Element};
+use crate::{
+    ChargeLike, ChargedMolecularTree, CountLike, MolecularTree, WideCount,
+    errors::{NumericError, ParserError, RoundtripError},
+    molecular_tree::hill_sort,
+    nodes::ELECTRON_MASS,
+    prelude::Element,
+    saturating_usize, wide_count_of_element,
+};
 
+mod adduct;
+mod annotated;
+mod by_mass;
 mod chemical_formula;
+#[cfg(feature = "arena")]
+mod chemical_formula_ref;
+mod display_style;
+mod display_width;
+mod element_bounds;
+mod feature_spec;
+mod formula_diff;
+mod formula_index;
+mod formula_pattern;
+mod halogen_signature;
+mod homologous_series;
 mod inchi_formula;
+mod labeled_formula;
+mod mass_context;
 mod mineral_formula;
+mod neutral_loss;
+mod numeric_conversions;
+mod prefixed;
+#[cfg(feature = "rational")]
+mod rational_formula;
 mod residual_formula;
+mod type_tagged;
+pub use adduct::{Adduct, AdductTerm};
+pub use annotated::Annotated;
+pub use by_mass::{ByMass, MassSortKey};
 pub use chemical_formula::*;
-use elements_rs::Isotope;
+#[cfg(feature = "arena")]
+pub use chemical_formula_ref::ChemicalFormulaRef;
+pub use display_style::{
+    BracketStyle, ChargeStyle, DisplayStyle, IsotopeStyle, MixtureSeparator, NumberStyle, Styled,
+    StyledDisplay,
+};
+pub use element_bounds::ElementBounds;
+use elements_rs::{BondsNumber, ElementMask, Isotope, MassNumber};
+pub use feature_spec::FeatureSpec;
+pub use formula_diff::FormulaDiff;
+#[cfg(feature = "modifications")]
+pub(crate) use formula_diff::fmt_composition_delta;
+pub use formula_index::{FormulaIndex, MassTolerance};
+pub use formula_pattern::FormulaPattern;
+pub use halogen_signature::HalogenSignature;
+pub use homologous_series::{Homolog, HomologousSeries};
 pub use inchi_formula::*;
+pub use labeled_formula::LabeledFormula;
+pub use mass_context::MassContext;
 pub use mineral_formula::*;
-use num_traits::{CheckedAdd, CheckedMul, ConstZero};
+pub use neutral_loss::NeutralLoss;
+use num_traits::{AsPrimitive, CheckedAdd, CheckedMul, ConstOne, ConstZero, Zero};
+pub use prefixed::Prefixed;
+#[cfg(feature = "rational")]
+pub use rational_formula::RationalFormula;
 pub use residual_formula::*;
+pub use type_tagged::{NumericTypes, TypeTagged};
+
+/// The length of the element histograms returned by
+/// [`MolecularFormula::element_histogram`], i.e. one more than the largest
+/// atomic number, so that they may be indexed by atomic number.
+pub const ELEMENT_HISTOGRAM_LENGTH: usize = 119;
 
 /// Trait defining metadata associated with a molecular formula.
 pub trait MolecularFormulaMetadata: Sized {
@@ -80,13 +143,19 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
 
     /// Iterates over the mixtures in the molecular formula, repeating them
     /// according to their counts.
+    ///
+    /// Counts which cannot be represented by `usize`, which may only happen
+    /// on targets where `usize` is narrower than the count type, are
+    /// saturated at `usize::MAX`.
     fn mixtures(&self) -> impl Iterator<Item = &Self::Tree> {
-        self.counted_mixtures().flat_map(|(count, tree)| {
-            repeat_n(tree, count.try_into().ok().expect("Count type cannot be converted to usize - do you have an extremely large mixture count?"))
-        })
+        self.counted_mixtures().flat_map(|(count, tree)| repeat_n(tree, saturating_usize(count)))
     }
 
-    /// Returns the number of mixtures in the molecular formula.
+    /// Returns the number of mixtures in the molecular formula, saturating at
+    /// `usize::MAX`.
+    ///
+    /// See [`try_number_of_mixtures`](Self::try_number_of_mixtures) for a
+    /// checked variant.
     ///
     /// # Example
     ///
@@ -100,16 +169,42 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
     /// ```
     fn number_of_mixtures(&self) -> usize {
         self.counted_mixtures()
-            .map(|(count, _)| {
-                let count: usize =
-                    count.try_into().ok().expect("Count type cannot be converted to usize - do you have an extremely large mixture count?");
-                count
-            })
-            .sum()
+            .fold(0, |total: usize, (count, _)| total.saturating_add(saturating_usize(count)))
+    }
+
+    /// Returns the number of mixtures in the molecular formula, as the
+    /// provided data type.
+    ///
+    /// # Errors
+    ///
+    /// * If the provided data type C cannot represent the count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{errors::NumericError, prelude::*};
+    ///
+    /// let formula = ChemicalFormula::<u8, i8>::from_str("200H2O.100NaCl").unwrap();
+    /// assert_eq!(formula.try_number_of_mixtures::<u8>(), Err(NumericError::PositiveOverflow));
+    /// assert_eq!(formula.try_number_of_mixtures::<u16>(), Ok(300));
+    /// ```
+    fn try_number_of_mixtures<C>(&self) -> Result<C, NumericError>
+    where
+        C: From<Self::Count> + CheckedAdd + ConstZero,
+    {
+        self.counted_mixtures()
+            .try_fold(C::ZERO, |total, (count, _)| total.checked_add(&C::from(count)))
+            .ok_or(NumericError::PositiveOverflow)
     }
 
     /// Returns the number of elements present in the molecular formula,
-    /// counting repeating units according to their counts.
+    /// counting repeating units according to their counts and saturating at
+    /// `usize::MAX`.
+    ///
+    /// See [`try_number_of_elements`](Self::try_number_of_elements) for a
+    /// checked variant.
     ///
     /// # Example
     ///
@@ -122,13 +217,163 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
     /// assert_eq!(formula.number_of_elements(), 24);
     /// ```
     fn number_of_elements(&self) -> usize {
+        self.counted_mixtures().fold(0, |total: usize, (count, tree)| {
+            total.saturating_add(saturating_usize(count).saturating_mul(tree.number_of_elements()))
+        })
+    }
+
+    /// Returns the number of elements present in the molecular formula,
+    /// counting repeating units according to their counts, as the provided
+    /// data type.
+    ///
+    /// Unlike [`number_of_elements`](Self::number_of_elements), this method
+    /// never saturates, so that wide data types such as `u128` may be used to
+    /// count the atoms of pathological formulas, such as large polymers.
+    ///
+    /// # Errors
+    ///
+    /// * If the provided data type C cannot represent the count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{errors::NumericError, prelude::*};
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    /// assert_eq!(formula.try_number_of_elements::<u32>(), Ok(24));
+    ///
+    /// let polymer = ChemicalFormula::<u32, i32>::from_str("((C2H4)4000000000)4000000000").unwrap();
+    /// assert_eq!(polymer.try_number_of_elements::<u64>(), Err(NumericError::PositiveOverflow));
+    /// assert_eq!(polymer.try_number_of_elements::<u128>(), Ok(96_000_000_000_000_000_000));
+    /// ```
+    fn try_number_of_elements<C>(&self) -> Result<C, NumericError>
+    where
+        C: From<Self::Count> + CheckedAdd + CheckedMul + ConstZero + ConstOne,
+    {
+        let mut total: C = C::zero();
+        for (count, tree) in self.counted_mixtures() {
+            total = tree
+                .try_number_of_elements::<C>()
+                .and_then(|elements| C::from(count).checked_mul(&elements))
+                .and_then(|elements| total.checked_add(&elements))
+                .ok_or(NumericError::PositiveOverflow)?;
+        }
+        Ok(total)
+    }
+
+    /// Returns the number of atoms present in the molecular formula,
+    /// counting repeating units and mixtures according to their counts, and
+    /// saturating at `u64::MAX`.
+    ///
+    /// The atoms are counted independently of the count type of the
+    /// formula, so that formulas with more atoms than their count type can
+    /// hold, such as `C200H200` with `u8` counts, are counted exactly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("CuSO4.5H2O").unwrap();
+    /// assert_eq!(formula.number_of_atoms(), 21);
+    /// ```
+    fn number_of_atoms(&self) -> u64 {
+        // The atoms are counted in a wide count rather than in the count type
+        // of the formula, which may not hold the total number of atoms.
+        self.counted_mixtures()
+            .try_fold(WideCount(0), |total, (count, tree)| {
+                tree.try_number_of_elements::<WideCount>()?
+                    .checked_mul(&WideCount::from(count))
+                    .and_then(|atoms| total.checked_add(&atoms))
+            })
+            .map_or(u64::MAX, |total| u64::try_from(total.0).unwrap_or(u64::MAX))
+    }
+
+    /// Returns the elemental composition of each mixture component of the
+    /// molecular formula, alongside the count of the component.
+    ///
+    /// Unlike [`count_of_element`](Self::count_of_element), the counts are
+    /// not merged over the whole mixture: each composition lists the number
+    /// of atoms of each element in a single copy of its component, in Hill
+    /// order, with isotopes counted as their element. Counts are computed
+    /// independently of the count type of the formula, and saturate at
+    /// `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("C20H24N2O2.2C2H6O").unwrap();
+    /// let compositions = formula.component_compositions();
+    /// assert_eq!(compositions.len(), 2);
+    /// assert_eq!(
+    ///     compositions[0],
+    ///     (1, vec![(Element::C, 20), (Element::H, 24), (Element::N, 2), (Element::O, 2)])
+    /// );
+    /// assert_eq!(compositions[1], (2, vec![(Element::C, 2), (Element::H, 6), (Element::O, 1)]));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn component_compositions(&self) -> Vec<(Self::Count, Vec<(Element, u64)>)> {
         self.counted_mixtures()
             .map(|(count, tree)| {
-                let count: usize =
-                    count.try_into().ok().expect("Count type cannot be converted to usize - do you have an extremely large mixture count?");
-                count * tree.number_of_elements()
+                let mut elements: Vec<Element> = tree.element_mask().into_iter().collect();
+                hill_sort(&mut elements);
+                let composition = elements
+                    .into_iter()
+                    .map(|element| {
+                        let atoms = tree
+                            .count_of_element::<WideCount>(element)
+                            .map_or(u64::MAX, |atoms| u64::try_from(atoms.0).unwrap_or(u64::MAX));
+                        (element, atoms)
+                    })
+                    .collect();
+                (count, composition)
             })
-            .sum()
+            .collect()
+    }
+
+    /// Returns the number of atoms of each element of the molecular formula
+    /// as an array indexed by atomic number, with isotopes counted as their
+    /// element.
+    ///
+    /// The array has the same length for every formula, with a zero at
+    /// index zero and for every absent element, so that the histograms of
+    /// many formulas may be stacked into the rows of a feature matrix.
+    /// Returns None if the count of any element cannot be represented by a
+    /// `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("CuSO4.5H2O").unwrap();
+    /// let histogram = formula.element_histogram().unwrap();
+    /// assert_eq!(histogram.len(), ELEMENT_HISTOGRAM_LENGTH);
+    /// assert_eq!(histogram[usize::from(u8::from(Element::H))], 10);
+    /// assert_eq!(histogram[8], 9);
+    /// assert_eq!(histogram[16], 1);
+    /// assert_eq!(histogram[29], 1);
+    /// assert_eq!(histogram.iter().sum::<u64>(), 21);
+    /// ```
+    fn element_histogram(&self) -> Option<[u64; ELEMENT_HISTOGRAM_LENGTH]>
+    where
+        u64: From<Self::Count>,
+    {
+        let mut histogram = [0; ELEMENT_HISTOGRAM_LENGTH];
+        for element in self.element_mask() {
+            histogram[usize::from(u8::from(element))] = self.count_of_element(element)?;
+        }
+        Some(histogram)
     }
 
     /// Returns the number of non-hydrogen elements present in the molecular
@@ -149,7 +394,8 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
     where
         usize: From<Self::Count>,
     {
-        self.number_of_elements() - self.count_of_element::<usize>(Element::H).unwrap_or(0)
+        self.number_of_elements()
+            .saturating_sub(self.count_of_element::<usize>(Element::H).unwrap_or(0))
     }
 
     /// Iterates over the elements in the molecular formula.
@@ -168,7 +414,7 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
     /// ```
     fn elements(&self) -> impl Iterator<Item = Element> {
         self.counted_mixtures().flat_map(|(count, tree)| {
-            repeat_n(tree, count.try_into().ok().expect("Count type cannot be converted to usize - do you have an extremely large mixture count?")).flat_map(MolecularTree::elements)
+            repeat_n(tree, saturating_usize(count)).flat_map(MolecularTree::elements)
         })
     }
 
@@ -188,7 +434,7 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
     /// ```
     fn non_hydrogens(&self) -> impl Iterator<Item = Element> {
         self.counted_mixtures().flat_map(|(count, tree)| {
-            repeat_n(tree, count.try_into().ok().expect("Count type cannot be converted to usize - do you have an extremely large mixture count?")).flat_map(MolecularTree::non_hydrogens)
+            repeat_n(tree, saturating_usize(count)).flat_map(MolecularTree::non_hydrogens)
         })
     }
 
@@ -245,6 +491,49 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
         self.counted_mixtures().any(|(_, tree)| tree.contains_element(element))
     }
 
+    /// Returns the mask of the elements present in the molecular formula,
+    /// including the elements of its isotopes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("[13C]H3OH").unwrap();
+    /// let elements: Vec<Element> = formula.element_mask().into_iter().collect();
+    /// assert_eq!(elements, [Element::H, Element::C, Element::O]);
+    /// ```
+    fn element_mask(&self) -> ElementMask {
+        self.counted_mixtures().flat_map(|(_, tree)| tree.element_mask()).collect()
+    }
+
+    /// Returns whether the molecular formula has at least one element in
+    /// common with the provided one.
+    ///
+    /// The check only compares the element masks of the formulas, so that
+    /// it can be used to cheaply discard candidates in search loops before
+    /// comparing their counts.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let water: ChemicalFormula = ChemicalFormula::from_str("H2O").unwrap();
+    /// let methane: ChemicalFormula = ChemicalFormula::from_str("CH4").unwrap();
+    /// let salt: ChemicalFormula = ChemicalFormula::from_str("NaCl").unwrap();
+    /// assert!(water.shares_elements_with(&methane));
+    /// assert!(!water.shares_elements_with(&salt));
+    /// ```
+    fn shares_elements_with<M: MolecularFormula>(&self, other: &M) -> bool {
+        let other = other.element_mask();
+        self.element_mask().into_iter().any(|element| other.contains(element))
+    }
+
     /// Returns whether the molecular formula contains any isotopes.
     ///
     /// # Example
@@ -342,6 +631,148 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
         Some(total)
     }
 
+    /// Returns the total number of nucleons of the molecular formula, i.e.
+    /// the sum of the mass numbers of its atoms.
+    ///
+    /// Explicit isotopes contribute their own mass number, while the other
+    /// atoms contribute the mass number of the most abundant isotope of
+    /// their element. Returns None if the sum cannot be represented by a
+    /// `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let water: ChemicalFormula = ChemicalFormula::from_str("H2O").unwrap();
+    /// assert_eq!(water.mass_number_sum(), Some(18));
+    /// let heavy_water: ChemicalFormula = ChemicalFormula::from_str("D2O").unwrap();
+    /// assert_eq!(heavy_water.mass_number_sum(), Some(20));
+    /// let labeled: ChemicalFormula = ChemicalFormula::from_str("[13C]H3[18O]H").unwrap();
+    /// assert_eq!(labeled.mass_number_sum(), Some(35));
+    /// ```
+    fn mass_number_sum(&self) -> Option<u64>
+    where
+        u64: From<Self::Count>,
+    {
+        let mut total: u64 = 0;
+        for element in self.element_mask() {
+            let mut remaining: u64 = self.count_of_element(element)?;
+            for isotope in element.isotopes() {
+                let count: u64 = self.count_of_isotope(*isotope)?;
+                remaining = remaining.checked_sub(count)?;
+                let mass_number: u64 = isotope.mass_number().into();
+                total = total.checked_add(count.checked_mul(mass_number)?)?;
+            }
+            let mass_number: u64 = element.most_abundant_isotope().mass_number().into();
+            total = total.checked_add(remaining.checked_mul(mass_number)?)?;
+        }
+        Some(total)
+    }
+
+    /// Returns the heteroatom class of the molecular formula, as used in
+    /// petroleomics, i.e. the counts of its elements other than carbon and
+    /// hydrogen in Hill order, such as `N1O2S1`, or `HC` for hydrocarbons.
+    ///
+    /// Isotopes are counted as their element, and every count is written
+    /// explicitly. Returns None if the count of any heteroatom cannot be
+    /// represented by the count type of the formula.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("C20H15NO2S").unwrap();
+    /// assert_eq!(formula.heteroatom_class().as_deref(), Some("N1O2S1"));
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("C30H50").unwrap();
+    /// assert_eq!(formula.heteroatom_class().as_deref(), Some("HC"));
+    /// ```
+    fn heteroatom_class(&self) -> Option<String> {
+        let mut heteroatoms: Vec<Element> = self
+            .element_mask()
+            .into_iter()
+            .filter(|element| !matches!(element, Element::C | Element::H))
+            .collect();
+        if heteroatoms.is_empty() {
+            return Some(String::from("HC"));
+        }
+        hill_sort(&mut heteroatoms);
+        let mut class = String::new();
+        for element in heteroatoms {
+            let count = u64::try_from(wide_count_of_element(self, element)).ok()?;
+            let count = Self::Count::try_from(count).ok()?;
+            class.push_str(element.as_ref());
+            class.push_str(&count.to_string());
+        }
+        Some(class)
+    }
+
+    /// Returns the double bond equivalent of the molecular formula, i.e. its
+    /// number of rings and double bonds, as computed from the lowest valence
+    /// of each element with `1 + Σ nᵢ (vᵢ - 2) / 2`.
+    ///
+    /// The charge is not considered, so that the result may be a half
+    /// integer for ions and radicals, and the components of mixtures are
+    /// counted together as a single formula.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let benzene: ChemicalFormula = ChemicalFormula::from_str("C6H6").unwrap();
+    /// assert_eq!(benzene.double_bond_equivalent(), 4.0);
+    /// let pyridine: ChemicalFormula = ChemicalFormula::from_str("C5H5N").unwrap();
+    /// assert_eq!(pyridine.double_bond_equivalent(), 4.0);
+    /// let chloroform: ChemicalFormula = ChemicalFormula::from_str("CHCl3").unwrap();
+    /// assert_eq!(chloroform.double_bond_equivalent(), 0.0);
+    /// ```
+    fn double_bond_equivalent(&self) -> f64 {
+        let mut total = 0.0;
+        for element in self.element_mask() {
+            let count: f64 = wide_count_of_element(self, element).as_();
+            total += count * (f64::from(element.number_of_bonds().0) - 2.0);
+        }
+        1.0 + total / 2.0
+    }
+
+    /// Returns whether the molecular formula satisfies the provided element
+    /// constraints, i.e. whether all of its elements are allowed and the
+    /// counts of the bounded elements are within their ranges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use elements_rs::Element;
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let bounds = ElementBounds::<u16>::new()
+    ///     .with_bounds(Element::C, 1, 6)
+    ///     .with_allowed_elements([Element::H, Element::O]);
+    /// let glucose: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    /// let water: ChemicalFormula = ChemicalFormula::from_str("H2O").unwrap();
+    /// let salt: ChemicalFormula = ChemicalFormula::from_str("CH3COONa").unwrap();
+    /// assert!(glucose.satisfies(&bounds));
+    /// assert!(!water.satisfies(&bounds));
+    /// assert!(!salt.satisfies(&bounds));
+    /// ```
+    fn satisfies(&self, bounds: &ElementBounds<Self::Count>) -> bool {
+        self.element_mask().into_iter().all(|element| bounds.is_allowed(element))
+            && bounds.iter().all(|(element, min, max)| {
+                let count = wide_count_of_element(self, element);
+                min.into() <= count && count <= max.into()
+            })
+    }
+
     /// Returns the isotopologue mass of the molecular formula without
     /// considering any charge.
     ///
@@ -359,12 +790,138 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
     fn isotopologue_mass(&self) -> f64 {
         let mut total_mass = 0.0;
         for (count, tree) in self.counted_mixtures() {
-            let count: f64 = count.into();
+            let count: f64 = count.as_();
             total_mass += count * tree.isotopologue_mass();
         }
         total_mass
     }
 
+    /// Returns the isotopologue mass of the molecular formula without
+    /// considering any charge, using the isotope masses and isotopic
+    /// compositions of the provided context.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let deuterium = Isotope::try_from((Element::H, 2_u16)).unwrap();
+    /// let context = MassContext::new().with_isotope_mass(deuterium, 2.0141);
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("CH3[2H]").unwrap();
+    /// let mass = formula.isotopologue_mass_with(&context);
+    /// assert!((formula.isotopologue_mass() - mass).abs() < 1e-5);
+    /// assert!(
+    ///     (formula.isotopologue_mass_with(&MassContext::new()) - formula.isotopologue_mass()).abs()
+    ///         < 1e-9
+    /// );
+    /// ```
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        self.counted_mixtures()
+            .map(|(count, tree)| {
+                let count: f64 = count.as_();
+                count * tree.isotopologue_mass_with(context)
+            })
+            .sum()
+    }
+
+    /// Returns the abundance-weighted mass of the A+n isotopologue peak of the
+    /// molecular formula, i.e. of its isotopologues weighing `n` nominal
+    /// mass units more than its lightest one, or `None` if there is no such
+    /// isotopologue or if the count of any element does not fit in a `u64`.
+    ///
+    /// Atoms which are not explicit isotopes have the natural isotopic
+    /// composition of their element. See
+    /// [`LabeledFormula::a_plus_n_mass`] for partially enriched isotopes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("CH2Br2").unwrap();
+    /// let mass = formula.a_plus_n_mass(2).unwrap();
+    /// assert!((mass - formula.isotopologue_mass() - 1.998).abs() < 1e-3);
+    /// ```
+    fn a_plus_n_mass(&self, n: u64) -> Option<f64>
+    where
+        u64: From<Self::Count>,
+    {
+        LabeledFormula::new(self.clone()).ok()?.a_plus_n_mass(n)
+    }
+
+    /// Returns the intensity of the A+n isotopologue peak of the molecular
+    /// formula relative to its A peak, as defined in
+    /// [`a_plus_n_mass`](Self::a_plus_n_mass), or zero if the count of any
+    /// element does not fit in a `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// // Dibromomethane shows the 1:2:1 triplet of its two bromine atoms.
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("CH2Br2").unwrap();
+    /// assert!((formula.a_plus_n_abundance(2) - 1.95).abs() < 0.01);
+    /// assert!((formula.a_plus_n_abundance(4) - 0.95).abs() < 0.01);
+    /// ```
+    fn a_plus_n_abundance(&self, n: u64) -> f64
+    where
+        u64: From<Self::Count>,
+    {
+        LabeledFormula::new(self.clone()).map_or(0.0, |labeled| labeled.a_plus_n_abundance(n))
+    }
+
+    /// Returns the expected A+2 and A+4 isotope peaks of the molecular
+    /// formula, alongside the contributions of its chlorine, bromine and
+    /// sulfur atoms, or `None` if the count of any element does not fit in a
+    /// `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let methionine: ChemicalFormula = ChemicalFormula::from_str("C5H11NO2S").unwrap();
+    /// let signature = methionine.halogen_signature().unwrap();
+    /// assert!(signature.is_present());
+    /// assert!((signature.contribution(Element::S) - 0.045).abs() < 0.001);
+    /// assert!(signature.contribution(Element::Cl).abs() < f64::EPSILON);
+    /// ```
+    fn halogen_signature(&self) -> Option<HalogenSignature>
+    where
+        u64: From<Self::Count>,
+    {
+        HalogenSignature::new(self)
+    }
+
+    /// Compares the molecular formula with another one by isotopologue mass,
+    /// breaking ties by Hill notation and then by string representation, as
+    /// done by [`ByMass`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::{cmp::Ordering, str::FromStr};
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let water: ChemicalFormula = ChemicalFormula::from_str("H2O").unwrap();
+    /// let ammonia: ChemicalFormula = ChemicalFormula::from_str("NH3").unwrap();
+    /// assert_eq!(ammonia.cmp_by_mass(&water), Ordering::Less);
+    /// assert_eq!(water.cmp_by_mass(&water.clone()), Ordering::Equal);
+    /// ```
+    fn cmp_by_mass(&self, other: &Self) -> Ordering {
+        by_mass::cmp_by_mass(self, other)
+    }
+
     /// Returns whether the molecular formula is a noble gas compound.
     ///
     /// # Example
@@ -528,6 +1085,91 @@ pub trait MolecularFormula: MolecularFormulaMetadata + Display + From<Element> +
         }
         formula
     }
+
+    /// Checks that the display of the formula is parsed back into an equal
+    /// formula.
+    ///
+    /// The formulas of this crate guarantee this round trip: both the parsed
+    /// formulas and the ones built or edited through the methods of this
+    /// crate, such as by charging, neutralizing or exchanging their
+    /// hydrogens, display to strings which are parsed back into equal
+    /// formulas. This method is meant as a debugging utility to check this
+    /// guarantee.
+    ///
+    /// # Errors
+    ///
+    /// * If the display of the formula cannot be parsed.
+    /// * If the display of the formula is parsed into a different formula.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("•CH3+").unwrap();
+    /// assert_eq!(formula.verify_roundtrip(), Ok(()));
+    /// assert_eq!(formula.neutralize().verify_roundtrip(), Ok(()));
+    /// assert_eq!(formula.deprotonate(1).unwrap().verify_roundtrip(), Ok(()));
+    /// ```
+    fn verify_roundtrip(&self) -> Result<(), RoundtripError>
+    where
+        Self: FromStr<Err = ParserError> + PartialEq,
+    {
+        let display = self.to_string();
+        match Self::from_str(&display) {
+            Ok(parsed) if parsed == *self => Ok(()),
+            Ok(_) => Err(RoundtripError::Mismatch(display)),
+            Err(error) => Err(RoundtripError::Unparsable { display, error }),
+        }
+    }
+
+    /// Returns the width of the display of the molecular formula, i.e. its
+    /// number of characters.
+    ///
+    /// All the characters written when displaying formulas, including the
+    /// subscript and superscript digits and signs, are one column wide, so
+    /// that this is the number of columns the display takes in a terminal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    /// assert_eq!(formula.to_string(), "C₆H₁₂O₆");
+    /// assert_eq!(formula.display_width(), 7);
+    /// ```
+    fn display_width(&self) -> usize {
+        self.to_string().chars().count()
+    }
+
+    /// Returns the display of the molecular formula, truncated with an
+    /// ellipsis so that it is at most `max_width` columns wide.
+    ///
+    /// The display is truncated at the last token boundary leaving room for
+    /// the ellipsis, so that no element symbol, count, mass number or charge
+    /// is split. Displays which fit in the width are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("C6H12O6.NaCl.H2O").unwrap();
+    /// assert_eq!(formula.truncate_display(16), "C₆H₁₂O₆.NaCl.H₂O");
+    /// assert_eq!(formula.truncate_display(12), "C₆H₁₂O₆.Na…");
+    /// // The ellipsis never splits the count of hydrogens.
+    /// assert_eq!(formula.truncate_display(4), "C₆…");
+    /// ```
+    fn truncate_display(&self, max_width: usize) -> String {
+        display_width::truncate_display(&self.to_string(), max_width)
+    }
 }
 
 /// A molecular formula that can hold a charge.
@@ -541,6 +1183,30 @@ pub trait ChargedMolecularFormula:
     MolecularFormula<Tree: ChargedMolecularTree<Self::Count, Self::Charge>>
     + ChargedMolecularFormulaMetadata
 {
+    /// Returns the difference between the molecular formula and the provided
+    /// reference formula, with per-element count differences and the
+    /// differences of charge and mass.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use elements_rs::Element;
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let measured: ChemicalFormula = ChemicalFormula::from_str("C6H10O5").unwrap();
+    /// let reference: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    /// let diff = measured.diff(&reference);
+    /// assert_eq!(diff.count_of_element(Element::H), -2);
+    /// assert_eq!(diff.count_of_element(Element::O), -1);
+    /// assert_eq!(diff.count_of_element(Element::C), 0);
+    /// assert!((diff.isotopologue_mass() + 18.010565).abs() < 1e-6);
+    /// ```
+    fn diff(&self, reference: &Self) -> FormulaDiff {
+        FormulaDiff::new(self, reference)
+    }
+
     /// Returns the overall charge of the molecular formula.
     ///
     /// # Example
@@ -558,12 +1224,36 @@ pub trait ChargedMolecularFormula:
     fn charge(&self) -> f64 {
         self.counted_mixtures()
             .map(|(count, tree)| {
-                let count: f64 = count.into();
+                let count: f64 = count.as_();
                 count * tree.charge()
             })
             .sum()
     }
 
+    /// Returns the overall charge of the molecular formula as an integer,
+    /// which unlike [`charge`](Self::charge) may be compared and hashed
+    /// exactly.
+    ///
+    /// Returns `None` if the charge cannot be represented by an `i64`.
+    /// Residuals are neutral, so that the charge of a formula containing
+    /// them is the one written in it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let citrate: ChemicalFormula = ChemicalFormula::from_str("C6H5O7-3.3Na+").unwrap();
+    /// assert_eq!(citrate.net_charge_integer(), Some(0));
+    /// let sulfate: ChemicalFormula = ChemicalFormula::from_str("2SO4-2").unwrap();
+    /// assert_eq!(sulfate.net_charge_integer(), Some(-4));
+    /// ```
+    fn net_charge_integer(&self) -> Option<i64> {
+        net_charge_of_mixtures::<Self::Count, Self::Charge, _>(self.counted_mixtures())
+    }
+
     /// Returns the isotopologue mass with charge considered.
     ///
     /// # Example
@@ -582,7 +1272,7 @@ pub trait ChargedMolecularFormula:
     fn isotopologue_mass_with_charge(&self) -> f64 {
         self.counted_mixtures()
             .map(|(count, tree)| {
-                let count: f64 = count.into();
+                let count: f64 = count.as_();
                 count * tree.isotopologue_mass_with_charge()
             })
             .sum()
@@ -606,6 +1296,138 @@ pub trait ChargedMolecularFormula:
         self.isotopologue_mass_with_charge() / self.charge()
     }
 
+    /// Returns the mass of the electrons lost or gained by the molecular
+    /// formula because of its charge, i.e. the difference between
+    /// [`isotopologue_mass_with_charge`](Self::isotopologue_mass_with_charge)
+    /// and [`isotopologue_mass`](MolecularFormula::isotopologue_mass).
+    ///
+    /// The correction is negative for cations, which lost electrons, and
+    /// positive for anions, which gained them: `SO4-2` weighs two electron
+    /// masses more than `SO4`. Some legacy tools do not apply it, and their
+    /// values may be reproduced by subtracting it from the charged mass.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let sulfate: ChemicalFormula = ChemicalFormula::from_str("SO4-2").unwrap();
+    /// assert!((sulfate.electron_mass_correction() - 2.0 * 0.000548579909).abs() < 1e-12);
+    ///
+    /// let uncorrected = sulfate.isotopologue_mass_with_charge() - sulfate.electron_mass_correction();
+    /// assert!((uncorrected - sulfate.isotopologue_mass()).abs() < 1e-12);
+    /// ```
+    fn electron_mass_correction(&self) -> f64 {
+        -self.charge() * ELECTRON_MASS
+    }
+
+    /// Returns the number of electrons of the molecular formula, i.e. the sum
+    /// of the atomic numbers of its atoms minus its net charge.
+    ///
+    /// Returns None if the number cannot be represented by a `u64`, or if
+    /// the charge exceeds the number of protons.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let water: ChemicalFormula = ChemicalFormula::from_str("H2O").unwrap();
+    /// assert_eq!(water.electron_count(), Some(10));
+    /// let hydronium: ChemicalFormula = ChemicalFormula::from_str("H3O+").unwrap();
+    /// assert_eq!(hydronium.electron_count(), Some(10));
+    /// let proton: ChemicalFormula = ChemicalFormula::from_str("H+2").unwrap();
+    /// assert_eq!(proton.electron_count(), None);
+    /// ```
+    fn electron_count(&self) -> Option<u64>
+    where
+        u64: From<Self::Count>,
+    {
+        let mut protons: u64 = 0;
+        for element in self.element_mask() {
+            let count: u64 = self.count_of_element(element)?;
+            let atomic_number: u64 = u8::from(element).into();
+            protons = protons.checked_add(count.checked_mul(atomic_number)?)?;
+        }
+        let electrons = i128::from(protons) - i128::from(self.net_charge_integer()?);
+        u64::try_from(electrons).ok()
+    }
+
+    /// Returns whether the molecular formula has an even number of
+    /// electrons, as closed-shell molecules and most ions observed in mass
+    /// spectrometry do, or None if its number of electrons cannot be
+    /// computed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let protonated: ChemicalFormula = ChemicalFormula::from_str("C6H13O6+").unwrap();
+    /// assert_eq!(protonated.is_even_electron(), Some(true));
+    /// let radical_cation: ChemicalFormula = ChemicalFormula::from_str("C6H12O6+").unwrap();
+    /// assert_eq!(radical_cation.is_even_electron(), Some(false));
+    /// ```
+    fn is_even_electron(&self) -> Option<bool>
+    where
+        u64: From<Self::Count>,
+    {
+        Some(self.electron_count()? % 2 == 0)
+    }
+
+    /// Returns the mass over charge ratio of the molecular formula, or None
+    /// if the formula is neutral, as its ratio would be infinite or NaN.
+    ///
+    /// When `polarity_aware` is true, the ratio is divided by the signed
+    /// charge as in
+    /// [`isotopologue_mass_over_charge`](Self::isotopologue_mass_over_charge),
+    /// so that anions have a negative ratio. Otherwise, the ratio is divided
+    /// by the absolute value of the charge, as reported by mass spectrometers
+    /// in either polarity.
+    ///
+    /// When `electron_correction` is true, the mass is the
+    /// [`isotopologue_mass_with_charge`](Self::isotopologue_mass_with_charge),
+    /// so that anions are heavier than the neutral formula by the mass of
+    /// the electrons they gained. Otherwise, it is the
+    /// [`isotopologue_mass`](MolecularFormula::isotopologue_mass), as
+    /// reported by the legacy tools which do not apply the
+    /// [`electron_mass_correction`](Self::electron_mass_correction).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let sulfate: ChemicalFormula = ChemicalFormula::from_str("SO4-2").unwrap();
+    /// assert!((sulfate.mz(false, true).unwrap() - 47.976413).abs() < 1e-6);
+    /// assert!((sulfate.mz(true, true).unwrap() + 47.976413).abs() < 1e-6);
+    /// assert!((sulfate.mz(false, false).unwrap() - 47.975865).abs() < 1e-6);
+    ///
+    /// let water: ChemicalFormula = ChemicalFormula::from_str("H2O").unwrap();
+    /// assert_eq!(water.mz(false, true), None);
+    /// ```
+    fn mz(&self, polarity_aware: bool, electron_correction: bool) -> Option<f64> {
+        let charge = self.charge();
+        if charge.is_zero() {
+            return None;
+        }
+        let charge = if polarity_aware { charge } else { charge.abs() };
+        let mass = if electron_correction {
+            self.isotopologue_mass_with_charge()
+        } else {
+            self.isotopologue_mass()
+        };
+        Some(mass / charge)
+    }
+
     /// Returns the molar mass.
     ///
     /// # Example
@@ -623,11 +1445,141 @@ pub trait ChargedMolecularFormula:
     fn molar_mass(&self) -> f64 {
         self.counted_mixtures()
             .map(|(count, tree)| {
-                let count: f64 = count.into();
+                let count: f64 = count.as_();
                 count * tree.molar_mass()
             })
             .sum()
     }
+
+    /// Returns the molar mass, using the isotope masses and isotopic
+    /// compositions of the provided context, e.g. for enriched reagents.
+    ///
+    /// Unlabelled atoms weigh as the average mass of their element under
+    /// the isotopic composition of the context, which is the same table
+    /// used by [`LabeledFormula`] for isotope patterns. Unlike
+    /// [`molar_mass`](Self::molar_mass), explicit isotopes weigh as their
+    /// isotope rather than as their element.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let boron_10 = Isotope::try_from((Element::B, 10_u16)).unwrap();
+    /// let boron_11 = Isotope::try_from((Element::B, 11_u16)).unwrap();
+    /// let enriched = MassContext::new()
+    ///     .with_isotopic_composition(Element::B, [(boron_10, 0.96), (boron_11, 0.04)]);
+    ///
+    /// let boric_acid: ChemicalFormula = ChemicalFormula::from_str("H3BO3").unwrap();
+    /// let natural = boric_acid.molar_mass_with(&MassContext::new());
+    /// assert!((natural - boric_acid.molar_mass()).abs() < 1e-9);
+    /// assert!((natural - boric_acid.molar_mass_with(&enriched) - 0.76).abs() < 0.01);
+    /// ```
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        self.counted_mixtures()
+            .map(|(count, tree)| {
+                let count: f64 = count.as_();
+                count * tree.molar_mass_with(context)
+            })
+            .sum()
+    }
+
+    /// Returns, for each mixture component, its count, the molar mass of a
+    /// single copy of the component and the fraction of the molar mass of
+    /// the formula contributed by all of its copies.
+    ///
+    /// The weight fractions sum to one, and directly report quantities such
+    /// as the water content of hydrates or the base content of salts.
+    /// Repeated components are not merged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("CuSO4.5H2O").unwrap();
+    /// let masses = formula.component_masses();
+    /// assert_eq!(masses.len(), 2);
+    /// let (count, water, water_fraction) = masses[1];
+    /// assert_eq!(count, 5);
+    /// assert!((water - 18.015).abs() < 1e-3);
+    /// // Copper sulfate pentahydrate is about 36% water by weight.
+    /// assert!((water_fraction - 0.3608).abs() < 1e-4);
+    /// ```
+    fn component_masses(&self) -> Vec<(Self::Count, f64, f64)> {
+        let total = self.molar_mass();
+        self.counted_mixtures()
+            .map(|(count, tree)| {
+                let copies: f64 = count.as_();
+                let mass = tree.molar_mass();
+                (count, mass, copies * mass / total)
+            })
+            .collect()
+    }
+
+    /// Returns, for each mixture component, its count and the net charge of
+    /// a single copy of the component.
+    ///
+    /// Each component may carry its own charge, as the hydronium and
+    /// chloride ions of `H3O+.Cl-`, and the overall
+    /// [`net_charge_integer`](Self::net_charge_integer) is the sum of the
+    /// charges of the components weighted by their counts. The charges of
+    /// the groups within a component, as in `(H3O)+(Cl)-`, are summed into
+    /// the charge of the component. Repeated components are not merged.
+    ///
+    /// Returns `None` if the charge of any component cannot be represented by
+    /// an `i64`, as for [`net_charge_integer`](Self::net_charge_integer).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("2Na+.SO4-2.10H2O").unwrap();
+    /// assert_eq!(formula.component_charges(), Some(vec![(2, 1), (1, -2), (10, 0)]));
+    /// assert_eq!(formula.net_charge_integer(), Some(0));
+    /// ```
+    fn component_charges(&self) -> Option<Vec<(Self::Count, i64)>> {
+        self.counted_mixtures()
+            .map(|(count, tree)| Some((count, tree.net_charge_integer()?)))
+            .collect()
+    }
+
+    /// Returns the dense feature vector of the molecular formula, with the
+    /// features of the provided specification in its documented order.
+    ///
+    /// Counts which cannot be represented by the count type of the formula
+    /// are featurized as infinite.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let spec = FeatureSpec::new().with_elements([Element::C, Element::H, Element::N]);
+    /// let formula: ChemicalFormula = ChemicalFormula::from_str("C5H5N").unwrap();
+    /// let features = formula.to_feature_vector(&spec);
+    /// assert_eq!(features.len(), spec.len());
+    /// assert_eq!(&features[..3], &[5.0, 5.0, 1.0]);
+    /// // The mass, the double bond equivalent and the charge follow.
+    /// assert!((features[3] - 79.042_2).abs() < 1e-3);
+    /// assert!((features[4] - 4.0).abs() < f32::EPSILON);
+    /// assert!(features[5].abs() < f32::EPSILON);
+    /// // Then the ratios of H, N, O, P and S to carbon.
+    /// assert!((features[6] - 1.0).abs() < f32::EPSILON);
+    /// assert!((features[7] - 0.2).abs() < f32::EPSILON);
+    /// ```
+    fn to_feature_vector(&self, spec: &FeatureSpec) -> Vec<f32> {
+        spec.features(self)
+    }
 }
 
 impl<M> ChargedMolecularFormula for M where
@@ -635,3 +1587,17 @@ impl<M> ChargedMolecularFormula for M where
         + ChargedMolecularFormulaMetadata
 {
 }
+
+/// Returns the net integer charge of the provided mixtures with their
+/// multipliers, or `None` if it cannot be represented by an `i64`.
+pub(crate) fn net_charge_of_mixtures<'a, Count: CountLike, Charge, T>(
+    mixtures: impl Iterator<Item = (Count, &'a T)>,
+) -> Option<i64>
+where
+    T: ChargedMolecularTree<Count, Charge> + 'a,
+{
+    mixtures.into_iter().try_fold(0_i64, |total, (count, tree)| {
+        let count = i64::try_from(count.into()).ok()?;
+        total.checked_add(tree.net_charge_integer()?.checked_mul(count)?)
+    })
+}
@@ -0,0 +1,354 @@
+//! Submodule providing the adduct ions in the notation mandated by the PSI
+//! standards such as mzTab-M, e.g. `[M+H]1+` or `[2M+Na]1+`.
+
+use alloc::vec::Vec;
+use core::{fmt::Display, str::FromStr};
+
+use elements_rs::{Element, Isotope};
+
+use crate::{
+    BaselineDigit, ChargeLike, ChemicalFormula, CountLike, DisplayStyle, MolecularFormula,
+    NumberStyle, StyledDisplay,
+    errors::{CompositionError, NumericError, ParserError},
+    molecular_tree::hill_sort,
+    nodes::ELECTRON_MASS,
+    signed_charge, try_fold_number,
+};
+
+/// Abbreviations of the solvents and modifiers commonly found in adducts,
+/// alongside the formulas they stand for.
+const ABBREVIATIONS: [(&str, &str); 8] = [
+    ("ACN", "C2H3N"),
+    ("DMSO", "C2H6OS"),
+    ("FA", "CH2O2"),
+    ("Hac", "C2H4O2"),
+    ("HAc", "C2H4O2"),
+    ("IsoProp", "C3H8O"),
+    ("MeOH", "CH4O"),
+    ("TFA", "C2HF3O2"),
+];
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+/// A species gained or lost by the molecule in an adduct, such as the `+Na`
+/// in `[M+Na]1+` or the `-H2O` in `[M+H-H2O]1+`.
+pub struct AdductTerm<Count: CountLike = u16, Charge: ChargeLike = i16> {
+    /// Whether the species is lost rather than gained.
+    loss: bool,
+    /// The number of copies of the species.
+    count: Count,
+    /// The formula of the species.
+    formula: ChemicalFormula<Count, Charge>,
+    /// The abbreviation the species was written with, if any.
+    abbreviation: Option<&'static str>,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> AdductTerm<Count, Charge> {
+    /// Returns whether the species is lost rather than gained.
+    #[must_use]
+    pub fn is_loss(&self) -> bool {
+        self.loss
+    }
+
+    /// Returns the number of copies of the species.
+    #[must_use]
+    pub fn count(&self) -> Count {
+        self.count
+    }
+
+    /// Returns the formula of the species.
+    #[must_use]
+    pub fn formula(&self) -> &ChemicalFormula<Count, Charge> {
+        &self.formula
+    }
+
+    /// Returns the monoisotopic mass of the term, negative for losses.
+    fn isotopologue_mass(&self) -> f64 {
+        let count: f64 = self.count.as_();
+        let mass = count * self.formula.isotopologue_mass();
+        if self.loss { -mass } else { mass }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+/// An adduct ion in the notation used by the PSI standards such as mzTab-M,
+/// describing how an ion is formed from a number of copies of a molecule
+/// `M`, the species it gains or loses and its overall charge.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let adduct = Adduct::<u32, i32>::from_str("[2M+Na]+").unwrap();
+/// assert_eq!(adduct.multimer(), 2);
+/// assert_eq!(adduct.charge(), 1);
+/// assert_eq!(adduct.to_string(), "[2M+Na]1+");
+///
+/// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+/// let ion = Adduct::<u32, i32>::from_str("[M+H-H2O]1+").unwrap().ion_formula(&glucose).unwrap();
+/// assert_eq!(ion.to_string(), "C₆H₁₁O₅⁺");
+/// ```
+pub struct Adduct<Count: CountLike = u16, Charge: ChargeLike = i16> {
+    /// The number of copies of the molecule.
+    multimer: Count,
+    /// The species gained or lost by the molecule.
+    terms: Vec<AdductTerm<Count, Charge>>,
+    /// The overall charge of the ion.
+    charge: Charge,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Adduct<Count, Charge> {
+    /// Returns the number of copies of the molecule in the ion.
+    #[must_use]
+    pub fn multimer(&self) -> Count {
+        self.multimer
+    }
+
+    /// Returns the adduct with the provided number of copies of the
+    /// molecule, replacing the previous one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let adduct = Adduct::<u32, i32>::from_str("[M+Na]1+").unwrap();
+    /// assert_eq!(adduct.with_multimer(3).to_string(), "[3M+Na]1+");
+    /// ```
+    #[must_use]
+    pub fn with_multimer(self, multimer: Count) -> Self {
+        Self { multimer, ..self }
+    }
+
+    /// Returns the overall charge of the ion.
+    #[must_use]
+    pub fn charge(&self) -> Charge {
+        self.charge
+    }
+
+    /// Iterates over the species gained or lost by the molecule.
+    pub fn terms(&self) -> impl Iterator<Item = &AdductTerm<Count, Charge>> {
+        self.terms.iter()
+    }
+
+    /// Returns the mass over charge ratio of the ion formed from a molecule
+    /// with the provided neutral monoisotopic mass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    /// let adduct = Adduct::<u32, i32>::from_str("[M+Na]1+").unwrap();
+    /// let mz = adduct.ion_mass_over_charge(glucose.isotopologue_mass());
+    /// assert!((mz - 203.052604).abs() < 1e-5);
+    /// ```
+    #[must_use]
+    pub fn ion_mass_over_charge(&self, molecule_mass: f64) -> f64 {
+        let multimer: f64 = self.multimer.as_();
+        let charge: f64 = self.charge.as_();
+        let mass = multimer * molecule_mass
+            + self.terms.iter().map(AdductTerm::isotopologue_mass).sum::<f64>()
+            - charge * ELECTRON_MASS;
+        mass / charge.abs()
+    }
+
+    /// Returns the formula of the ion formed from the provided molecule, as
+    /// the elemental composition in Hill order of the copies of the molecule
+    /// with the gained species added and the lost ones removed, carrying the
+    /// charge of the adduct.
+    ///
+    /// # Errors
+    ///
+    /// * If the molecule does not contain enough atoms for the losses.
+    /// * If the ion would not contain any atom.
+    /// * If any of the counts overflows.
+    pub fn ion_formula(
+        &self,
+        molecule: &ChemicalFormula<Count, Charge>,
+    ) -> Result<ChemicalFormula<Count, Charge>, CompositionError> {
+        let overflow = CompositionError::from(NumericError::PositiveOverflow);
+        let mut elements: Vec<Element> = molecule
+            .element_mask()
+            .into_iter()
+            .chain(self.terms.iter().flat_map(|term| term.formula.element_mask()))
+            .collect();
+        hill_sort(&mut elements);
+        elements.dedup();
+
+        let mut counts: Vec<(Element, Count)> = Vec::with_capacity(elements.len());
+        for element in elements {
+            let mut count = molecule
+                .count_of_element::<Count>(element)
+                .and_then(|count| count.checked_mul(&self.multimer))
+                .ok_or(overflow)?;
+            // Gains are applied first, so that losses may remove gained atoms.
+            for term in self.terms.iter().filter(|term| !term.loss) {
+                count = term
+                    .formula
+                    .count_of_element::<Count>(element)
+                    .and_then(|gained| gained.checked_mul(&term.count))
+                    .and_then(|gained| count.checked_add(&gained))
+                    .ok_or(overflow)?;
+            }
+            for term in self.terms.iter().filter(|term| term.loss) {
+                let lost = term
+                    .formula
+                    .count_of_element::<Count>(element)
+                    .and_then(|lost| lost.checked_mul(&term.count))
+                    .ok_or(overflow)?;
+                count =
+                    count.checked_sub(&lost).ok_or(CompositionError::InsufficientAtoms(element))?;
+            }
+            counts.push((element, count));
+        }
+        if counts.iter().all(|(_, count)| count.is_zero()) {
+            return Err(ParserError::EmptyMolecularTree.into());
+        }
+        Ok(ChemicalFormula::from_element_counts(counts).charged(self.charge)?)
+    }
+}
+
+/// Parses the species of an adduct term, either from its abbreviation or as
+/// a chemical formula.
+fn parse_species<Count, Charge>(
+    species: &str,
+) -> Result<(ChemicalFormula<Count, Charge>, Option<&'static str>), ParserError>
+where
+    Count: CountLike,
+    Charge: ChargeLike + TryFrom<Count>,
+    Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+{
+    if let Some((abbreviation, formula)) =
+        ABBREVIATIONS.iter().find(|(abbreviation, _)| *abbreviation == species)
+    {
+        return Ok((ChemicalFormula::from_str(formula)?, Some(abbreviation)));
+    }
+    Ok((ChemicalFormula::from_str(species)?, None))
+}
+
+impl<Count: CountLike, Charge: ChargeLike> FromStr for Adduct<Count, Charge>
+where
+    Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let inner = s.strip_prefix('[').ok_or_else(|| unexpected_start(s))?;
+        let (inner, charge) = inner
+            .rsplit_once(']')
+            .ok_or(ParserError::MissingClosingBracket(crate::Bracket::Square))?;
+
+        let mut chars = inner.chars().peekable();
+        let multimer = try_fold_number::<Count, BaselineDigit, _>(&mut chars)
+            .transpose()?
+            .unwrap_or(Count::ONE);
+        match chars.next() {
+            Some('M') => {}
+            Some(c) => return Err(ParserError::UnexpectedCharacter(c)),
+            None => return Err(ParserError::UnexpectedEndOfInput),
+        }
+
+        let mut terms = Vec::new();
+        while let Some(sign) = chars.next() {
+            let loss = match sign {
+                '+' => false,
+                '-' => true,
+                c => return Err(ParserError::UnexpectedCharacter(c)),
+            };
+            let count = try_fold_number::<Count, BaselineDigit, _>(&mut chars)
+                .transpose()?
+                .unwrap_or(Count::ONE);
+            let mut species = alloc::string::String::new();
+            while let Some(c) = chars.next_if(|&c| c != '+' && c != '-') {
+                species.push(c);
+            }
+            if species.is_empty() {
+                return Err(chars
+                    .next()
+                    .map_or(ParserError::UnexpectedEndOfInput, ParserError::UnexpectedCharacter));
+            }
+            let (formula, abbreviation) = parse_species(&species)?;
+            terms.push(AdductTerm { loss, count, formula, abbreviation });
+        }
+
+        Ok(Self { multimer, terms, charge: parse_adduct_charge(charge)? })
+    }
+}
+
+/// Returns the error for an adduct which does not start with a bracket.
+fn unexpected_start(s: &str) -> ParserError {
+    s.chars().next().map_or(ParserError::UnexpectedEndOfInput, ParserError::UnexpectedCharacter)
+}
+
+/// Parses the charge following the closing bracket of an adduct, written as
+/// `1+` in the mzTab-M notation, or more leniently as `+`, `++` or `+2`.
+fn parse_adduct_charge<Charge: ChargeLike>(charge: &str) -> Result<Charge, ParserError> {
+    let mut chars = charge.chars().peekable();
+    let leading =
+        try_fold_number::<u64, BaselineDigit, _>(&mut chars).transpose().map_err(charge_error)?;
+    let positive = match chars.next() {
+        Some('+') => true,
+        Some('-') => false,
+        Some(c) => return Err(ParserError::UnexpectedCharacter(c)),
+        None => return Err(ParserError::UnexpectedEndOfInput),
+    };
+    let sign = if positive { '+' } else { '-' };
+    let magnitude = if let Some(leading) = leading {
+        leading
+    } else {
+        let mut repeated: u64 = 1;
+        while chars.next_if_eq(&sign).is_some() {
+            repeated = repeated.saturating_add(1);
+        }
+        if repeated == 1
+            && let Some(trailing) = try_fold_number::<u64, BaselineDigit, _>(&mut chars)
+        {
+            trailing.map_err(charge_error)?
+        } else {
+            repeated
+        }
+    };
+    if let Some(c) = chars.next() {
+        return Err(ParserError::UnexpectedCharacter(c));
+    }
+    Ok(signed_charge(magnitude, positive)?)
+}
+
+/// Converts an error raised while parsing the magnitude of a charge, where a
+/// leading zero denotes an explicit zero charge.
+fn charge_error(error: NumericError) -> ParserError {
+    if error == NumericError::LeadingZero { ParserError::ZeroCharge } else { error.into() }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Display for Adduct<Count, Charge> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let style = DisplayStyle::new().with_number_style(NumberStyle::Ascii);
+        write!(f, "[")?;
+        if !self.multimer.is_one() {
+            write!(f, "{}", self.multimer)?;
+        }
+        write!(f, "M")?;
+        for term in &self.terms {
+            write!(f, "{}", if term.loss { '-' } else { '+' })?;
+            if !term.count.is_one() {
+                write!(f, "{}", term.count)?;
+            }
+            match term.abbreviation {
+                Some(abbreviation) => write!(f, "{abbreviation}")?,
+                None => term.formula.fmt_with(&style, f)?,
+            }
+        }
+        let charge: i128 = self.charge.into();
+        write!(f, "]{}{}", charge.unsigned_abs(), if charge < 0 { '-' } else { '+' })
+    }
+}
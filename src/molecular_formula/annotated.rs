@@ -0,0 +1,155 @@
+//! Submodule providing the `Annotated` wrapper, which attaches arbitrary
+//! user metadata such as identifiers or retention times to any molecular
+//! formula type.
+
+use core::fmt::Display;
+
+use elements_rs::Element;
+
+use crate::{
+    ChargedMolecularFormulaMetadata, DisplayStyle, MolecularFormula, MolecularFormulaMetadata,
+    StyledDisplay,
+};
+
+#[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash, Default)]
+/// Molecular formula of any type, carrying an annotation of any type.
+///
+/// Pipelines processing formulas often need to keep track of where each of
+/// them comes from, e.g. the identifier of a compound or the retention time
+/// of a feature: the wrapper carries such an annotation alongside the
+/// formula, so that no parallel collection has to be kept in sync. It
+/// implements [`MolecularFormula`] and
+/// [`ChargedMolecularFormula`](crate::ChargedMolecularFormula) by delegating to
+/// the wrapped formula, and is displayed as the wrapped formula alone.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+/// let feature = Annotated::new(glucose, ("feature-42", 3.25_f64));
+/// assert_eq!(feature.annotation(), &("feature-42", 3.25));
+/// assert_eq!(feature.count_of_element::<u32>(Element::C), Some(6));
+/// assert!((feature.isotopologue_mass() - 180.063_388).abs() < 1e-6);
+/// assert_eq!(feature.to_string(), "C₆H₁₂O₆");
+/// ```
+pub struct Annotated<M, T> {
+    /// The annotated formula.
+    formula: M,
+    /// The annotation of the formula.
+    annotation: T,
+}
+
+impl<M, T> Annotated<M, T> {
+    /// Creates a new formula carrying the provided annotation.
+    #[must_use]
+    pub const fn new(formula: M, annotation: T) -> Self {
+        Self { formula, annotation }
+    }
+
+    /// Returns the annotated formula.
+    #[must_use]
+    pub const fn formula(&self) -> &M {
+        &self.formula
+    }
+
+    /// Returns the annotation of the formula.
+    #[must_use]
+    pub const fn annotation(&self) -> &T {
+        &self.annotation
+    }
+
+    /// Returns a mutable reference to the annotation of the formula.
+    #[must_use]
+    pub const fn annotation_mut(&mut self) -> &mut T {
+        &mut self.annotation
+    }
+
+    /// Returns the formula obtained by applying the provided function to
+    /// the annotated formula, preserving the annotation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let ion = ChemicalFormula::<u32, i32>::from_str("C6H13O6+").unwrap();
+    /// let neutral = Annotated::new(ion, 7_u32).map(|formula| formula.neutralize());
+    /// assert_eq!((neutral.to_string().as_str(), *neutral.annotation()), ("C₆H₁₃O₆", 7));
+    /// ```
+    #[must_use]
+    pub fn map<N>(self, f: impl FnOnce(M) -> N) -> Annotated<N, T> {
+        Annotated { formula: f(self.formula), annotation: self.annotation }
+    }
+
+    /// Returns the formula carrying the annotation obtained by applying the
+    /// provided function to the current annotation.
+    #[must_use]
+    pub fn map_annotation<U>(self, f: impl FnOnce(T) -> U) -> Annotated<M, U> {
+        Annotated { formula: self.formula, annotation: f(self.annotation) }
+    }
+
+    /// Consumes the formula and returns the annotated formula and its
+    /// annotation.
+    #[must_use]
+    pub fn into_parts(self) -> (M, T) {
+        (self.formula, self.annotation)
+    }
+}
+
+impl<M, T> AsRef<M> for Annotated<M, T> {
+    fn as_ref(&self) -> &M {
+        &self.formula
+    }
+}
+
+impl<M: MolecularFormulaMetadata, T> MolecularFormulaMetadata for Annotated<M, T> {
+    type Count = M::Count;
+}
+
+impl<M: MolecularFormula, T: Default + Clone> MolecularFormula for Annotated<M, T> {
+    type Tree = M::Tree;
+
+    fn counted_mixtures(&self) -> impl Iterator<Item = (Self::Count, &Self::Tree)> {
+        self.formula.counted_mixtures()
+    }
+
+    fn counted_mixtures_mut(&mut self) -> impl Iterator<Item = (Self::Count, &mut Self::Tree)> {
+        self.formula.counted_mixtures_mut()
+    }
+
+    fn into_counted_mixtures(self) -> impl Iterator<Item = (Self::Count, Self::Tree)> {
+        self.formula.into_counted_mixtures()
+    }
+}
+
+impl<M: ChargedMolecularFormulaMetadata, T> ChargedMolecularFormulaMetadata for Annotated<M, T> {
+    type Charge = M::Charge;
+}
+
+impl<M: From<Element>, T: Default> From<Element> for Annotated<M, T> {
+    fn from(element: Element) -> Self {
+        Self { formula: M::from(element), annotation: T::default() }
+    }
+}
+
+impl<M: StyledDisplay, T> StyledDisplay for Annotated<M, T> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        self.formula.fmt_with(style, f)
+    }
+}
+
+impl<M: Display, T> Display for Annotated<M, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.formula.fmt(f)
+    }
+}
@@ -0,0 +1,184 @@
+//! Submodule providing the ordering of molecular formulas by mass, as used
+//! to sort candidate lists for presentation and to search them by mass.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{cmp::Ordering, fmt::Display};
+
+use elements_rs::Element;
+
+use crate::{MolecularFormula, molecular_tree::hill_sort, wide_count_of_element};
+
+#[derive(Debug, Clone)]
+/// Wrapper ordering molecular formulas by increasing isotopologue mass,
+/// rounded to the microdalton so that isomers have the same mass.
+///
+/// Formulas with the same mass are ordered by their Hill notation, and then
+/// by their string representation, so that the ordering is total and
+/// deterministic. Two wrapped formulas are equal when they have the same
+/// mass and the same string representation. Large lists are sorted faster
+/// with the [`MassSortKey`] of each formula, which is computed only once.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let mut candidates: Vec<ByMass<ChemicalFormula<u32, i32>>> =
+///     ["C2H6O", "CH4", "CH3CH2OH", "H2O"]
+///         .into_iter()
+///         .map(|formula| ByMass(ChemicalFormula::from_str(formula).unwrap()))
+///         .collect();
+/// candidates.sort();
+/// let sorted: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+/// assert_eq!(sorted, ["CH₄", "H₂O", "CH₃CH₂OH", "C₂H₆O"]);
+///
+/// // The first candidate heavier than 17 Da.
+/// let index = candidates.partition_point(|candidate| candidate.mass() < 17.0);
+/// assert_eq!(candidates[index].to_string(), "H₂O");
+/// ```
+pub struct ByMass<M>(pub M);
+
+impl<M: MolecularFormula> ByMass<M> {
+    /// Returns the isotopologue mass of the wrapped formula, without
+    /// considering any charge.
+    #[must_use]
+    pub fn mass(&self) -> f64 {
+        self.0.isotopologue_mass()
+    }
+
+    /// Consumes the wrapper and returns the wrapped formula.
+    #[must_use]
+    pub fn into_inner(self) -> M {
+        self.0
+    }
+}
+
+impl<M> AsRef<M> for ByMass<M> {
+    fn as_ref(&self) -> &M {
+        &self.0
+    }
+}
+
+impl<M: MolecularFormula> PartialEq for ByMass<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<M: MolecularFormula> Eq for ByMass<M> {}
+
+impl<M: MolecularFormula> PartialOrd for ByMass<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M: MolecularFormula> Ord for ByMass<M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_by_mass(&other.0)
+    }
+}
+
+impl<M: Display> Display for ByMass<M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Compares the provided formulas by isotopologue mass, then by Hill
+/// notation and finally by string representation.
+pub(crate) fn cmp_by_mass<M: MolecularFormula>(left: &M, right: &M) -> Ordering {
+    rounded_mass(left)
+        .total_cmp(&rounded_mass(right))
+        .then_with(|| hill_notation(left).cmp(&hill_notation(right)))
+        .then_with(|| left.to_string().cmp(&right.to_string()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Key ordering molecular formulas as [`ByMass`] does, computed once per
+/// formula so that sorting large candidate lists with
+/// [`sort_by_cached_key`](slice::sort_by_cached_key) does not recompute the
+/// Hill notation and the string representation in every comparison.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let mut candidates: Vec<ChemicalFormula<u32, i32>> = ["C2H6O", "CH4", "CH3CH2OH", "H2O"]
+///     .into_iter()
+///     .map(|formula| ChemicalFormula::from_str(formula).unwrap())
+///     .collect();
+/// candidates.sort_by_cached_key(MassSortKey::new);
+/// let sorted: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+/// assert_eq!(sorted, ["CH₄", "H₂O", "CH₃CH₂OH", "C₂H₆O"]);
+/// ```
+pub struct MassSortKey {
+    /// The isotopologue mass of the formula in microdaltons.
+    mass: f64,
+    /// The elemental composition of the formula in Hill notation.
+    hill_notation: String,
+    /// The string representation of the formula.
+    display: String,
+}
+
+impl MassSortKey {
+    /// Returns the key ordering the provided formula by mass.
+    #[must_use]
+    pub fn new<M: MolecularFormula>(formula: &M) -> Self {
+        Self {
+            mass: rounded_mass(formula),
+            hill_notation: hill_notation(formula),
+            display: formula.to_string(),
+        }
+    }
+}
+
+impl Eq for MassSortKey {}
+
+impl PartialOrd for MassSortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MassSortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.mass
+            .total_cmp(&other.mass)
+            .then_with(|| self.hill_notation.cmp(&other.hill_notation))
+            .then_with(|| self.display.cmp(&other.display))
+    }
+}
+
+/// Returns the isotopologue mass of the provided formula in microdaltons,
+/// rounded so that the accumulated floating point errors, which depend on
+/// the structure of the formula, do not affect the ordering.
+fn rounded_mass<M: MolecularFormula>(formula: &M) -> f64 {
+    (formula.isotopologue_mass() * 1e6).round()
+}
+
+/// Returns the elemental composition of the provided formula in Hill
+/// notation with ASCII counts, such as `C2H6O`, with isotopes counted as
+/// their element.
+fn hill_notation<M: MolecularFormula>(formula: &M) -> String {
+    let mut elements: Vec<Element> = formula.element_mask().into_iter().collect();
+    hill_sort(&mut elements);
+
+    let mut notation = String::new();
+    for element in elements {
+        notation.push_str(element.as_ref());
+        let count = wide_count_of_element(formula, element);
+        if count != 1 {
+            notation.push_str(&count.to_string());
+        }
+    }
+    notation
+}
@@ -2,22 +2,55 @@
 //! as found in resources such as PubChem. This is a more permissive format
 //! than InChI, allowing for a wider variety of notations.
 
-use alloc::vec::Vec;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::{
+    borrow::Borrow,
     fmt::Display,
-    ops::{Add, AddAssign},
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, MulAssign},
 };
 
-use elements_rs::{Element, Isotope};
+use elements_rs::{Element, ElementMask, Isotope};
 
 use crate::{
-    ChargeLike, ChargedMolecularFormulaMetadata, CountLike, Empty, InChIFormula, MolecularFormula,
-    MolecularFormulaMetadata, ParsableFormula, SequenceNode, prelude::ChemicalTree,
+    ChargeLike, ChargedMolecularFormula, ChargedMolecularFormulaMetadata, CountLike, DeepSizeOf,
+    DisplayStyle, Empty, InChIFormula, InChITree, MolecularFormula, MolecularFormulaMetadata,
+    MolecularTree, ParsableFormula, PrefixHandling, RepeatNode, SequenceNode, StyledDisplay,
+    convert_count,
+    errors::{CompositionError, ElementMaskError, NumericError, ParserError},
+    molecular_formula::{net_charge_of_mixtures, prefixed::read_prefix},
+    molecular_tree::{hill_sort, mixtures_size_of},
+    prelude::ChemicalTree,
 };
 
+mod charge_assignment;
+mod charge_sites;
+mod hydrates;
+mod hydrogen_exchange;
+mod hydrogen_filling;
+mod into_elements;
+mod multimers;
+mod normalization;
+mod sub_compositions;
+pub use charge_assignment::SaltSplit;
+pub use hydrogen_exchange::ExchangePolicy;
+pub use hydrogen_filling::ValenceModel;
+pub use into_elements::IntoElements;
+pub use normalization::NormalizationLevel;
+pub use sub_compositions::SubCompositions;
+
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
 /// A chemical formula representing molecular formulas
 ///
+/// The display of any chemical formula, whether parsed or built by the
+/// methods of this crate, is parsed back into an equal formula, as checked
+/// by [`verify_roundtrip`](MolecularFormula::verify_roundtrip). The only
+/// exception is the [`empty`](Self::empty) formula, which displays as the
+/// empty string.
+///
 /// # Examples
 ///
 /// ```
@@ -36,6 +69,62 @@ pub struct ChemicalFormula<Count: CountLike = u16, Charge: ChargeLike = i16> {
 }
 
 impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Returns the empty formula, which contains no mixture.
+    ///
+    /// The empty formula is the identity of the addition of formulas, so
+    /// that it may be used as the initial value when folding compositions.
+    /// It has no atoms, a mass and a charge of zero, and displays as the
+    /// empty string. As the parser rejects empty strings, it is the only
+    /// formula whose display is not parsed back, although it round-trips
+    /// through serde, which deserializes the empty string as the empty
+    /// formula.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let empty = ChemicalFormula::<u32, i32>::empty();
+    /// assert!(empty.is_empty());
+    /// assert_eq!(empty, ChemicalFormula::default());
+    /// assert_eq!(empty.to_string(), "");
+    /// assert_eq!(empty.isotopologue_mass(), 0.0);
+    /// assert_eq!(empty.charge(), 0.0);
+    ///
+    /// let fragments = ["CH3", "CH2", "OH"].map(|f| ChemicalFormula::from_str(f).unwrap());
+    /// let mixture = fragments.into_iter().fold(empty, |total, fragment| total + fragment);
+    /// assert_eq!(mixture.to_string(), "CH₃.CH₂.OH");
+    ///
+    /// // The sum keeps the fragments as mixture components, which the total
+    /// // composition merges into ethanol.
+    /// let ethanol = ChemicalFormula::total_composition([mixture]).unwrap();
+    /// assert_eq!(ethanol.to_string(), "C₂H₆O");
+    /// ```
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self { mixtures: Vec::new() }
+    }
+
+    /// Returns whether the formula is the empty formula, i.e. whether it
+    /// contains no mixture.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert!(ChemicalFormula::<u32, i32>::empty().is_empty());
+    /// assert!(!ChemicalFormula::<u32, i32>::from_str("H2O").unwrap().is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mixtures.is_empty()
+    }
+
     /// Iterates on the sub-formulas in the InChI formula, repeating them
     /// according to their counts.
     ///
@@ -56,6 +145,528 @@ impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
     pub fn subformulas(&self) -> impl Iterator<Item = Self> {
         self.mixtures().cloned().map(Into::into)
     }
+
+    /// Returns a single copy of the largest mixture component of the
+    /// formula, i.e. of its parent compound, or `None` if the formula is
+    /// empty.
+    ///
+    /// This drops the counter-ions, solvents and waters of crystallization
+    /// of salts and hydrates at the formula level, as the largest fragment
+    /// of a structure would. Components are compared by their number of
+    /// non-hydrogen atoms, so that a heavy counter-ion such as iodide is not
+    /// taken for the parent compound of a light amine, and then by their
+    /// isotopologue mass. The first of equally large components is
+    /// returned. The charge of the component is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let salt = ChemicalFormula::<u32, i32>::from_str("C17H19NO3.HCl.3H2O").unwrap();
+    /// assert_eq!(salt.largest_component().unwrap().to_string(), "C₁₇H₁₉NO₃");
+    ///
+    /// let iodide = ChemicalFormula::<u32, i32>::from_str("I-.C2H8N+").unwrap();
+    /// assert_eq!(iodide.largest_component().unwrap().to_string(), "C₂H₈N⁺");
+    ///
+    /// assert_eq!(ChemicalFormula::<u32, i32>::empty().largest_component(), None);
+    /// ```
+    #[must_use]
+    pub fn largest_component(&self) -> Option<Self> {
+        let mut largest: Option<(u64, f64, Self)> = None;
+        for ((_, composition), (_, tree)) in
+            self.component_compositions().into_iter().zip(&self.mixtures)
+        {
+            let heavy_atoms = composition
+                .into_iter()
+                .filter(|(element, _)| *element != Element::H)
+                .fold(0_u64, |total, (_, atoms)| total.saturating_add(atoms));
+            let component = Self { mixtures: alloc::vec![(Count::one(), tree.clone())] };
+            let mass = component.isotopologue_mass();
+            if largest.as_ref().is_none_or(|(largest_heavy_atoms, largest_mass, _)| {
+                (heavy_atoms, mass) > (*largest_heavy_atoms, *largest_mass)
+            }) {
+                largest = Some((heavy_atoms, mass, component));
+            }
+        }
+        largest.map(|(_, _, component)| component)
+    }
+
+    /// Iterates on the non-empty elemental sub-compositions of the formula,
+    /// optionally bounded by their number of atoms, alongside their
+    /// monoisotopic masses.
+    ///
+    /// Isotopic labels, charges and mixture boundaries are not preserved, as
+    /// the sub-compositions are built from the elemental composition of the
+    /// formula.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("CH2O").unwrap();
+    /// let sub_compositions: Vec<_> = formula.sub_compositions(None).collect();
+    /// // (1 + 1) * (2 + 1) * (1 + 1) - 1 non-empty sub-compositions.
+    /// assert_eq!(sub_compositions.len(), 11);
+    /// assert_eq!(sub_compositions[0].0.to_string(), "C");
+    /// assert_eq!(sub_compositions.last().unwrap().0.to_string(), "CH₂O");
+    ///
+    /// let bounded: Vec<_> = formula.sub_compositions(Some(1)).collect();
+    /// assert_eq!(bounded.len(), 3);
+    /// ```
+    #[must_use]
+    pub fn sub_compositions(&self, max_atoms: Option<usize>) -> SubCompositions<Count, Charge> {
+        SubCompositions::new(self, max_atoms)
+    }
+
+    /// Returns the formula with the counts of all of its mixtures multiplied
+    /// by the provided factor, or `None` if the factor is zero or if any of
+    /// the counts overflows.
+    ///
+    /// Unlike this method, the `*` operator never fails: a zero factor yields
+    /// the empty formula, and a count that would overflow is split across
+    /// repeated mixtures, as the `+` operator does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u8, i8>::from_str("2H2O.NaCl").unwrap();
+    /// assert_eq!(formula.checked_mul(3).unwrap().to_string(), "6H₂O.3NaCl");
+    /// assert_eq!(formula.checked_mul(0), None);
+    /// assert_eq!(formula.checked_mul(200), None);
+    ///
+    /// assert_eq!((formula.clone() * 3).to_string(), "6H₂O.3NaCl");
+    /// assert!((formula.clone() * 0).is_empty());
+    /// assert_eq!((formula * 200).to_string(), "254H₂O.200NaCl.146H₂O");
+    /// ```
+    #[must_use]
+    pub fn checked_mul(&self, factor: Count) -> Option<Self> {
+        if factor.is_zero() {
+            return None;
+        }
+        Some(Self {
+            mixtures: self
+                .mixtures
+                .iter()
+                .map(|(count, tree)| Some((count.checked_mul(&factor)?, tree.clone())))
+                .collect::<Option<Vec<_>>>()?,
+        })
+    }
+
+    /// Returns the formula with all of its charges removed, leaving the
+    /// elemental composition and the structure of the formula untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let ammonium = ChemicalFormula::<u32, i32>::from_str("NH4+").unwrap();
+    /// assert_eq!(ammonium.neutralize().to_string(), "NH₄");
+    ///
+    /// let salt = ChemicalFormula::<u32, i32>::from_str("K4[Fe(CN)6]").unwrap();
+    /// assert_eq!(salt.neutralize(), salt);
+    ///
+    /// let complex = ChemicalFormula::<u32, i32>::from_str("[Fe(CN)6]-4.4K+").unwrap();
+    /// let neutral = complex.neutralize();
+    /// assert_eq!(neutral.to_string(), "[Fe(CN)₆].4K");
+    /// assert_eq!(neutral.charge(), 0.0);
+    /// ```
+    #[must_use]
+    pub fn neutralize(&self) -> Self {
+        Self {
+            mixtures: self
+                .mixtures
+                .iter()
+                .map(|(count, tree)| (*count, tree.clone().neutralize().canonicalize()))
+                .collect(),
+        }
+    }
+
+    /// Returns the net integer charge of the formula.
+    ///
+    /// # Errors
+    ///
+    /// * If the net charge cannot be represented by the charge type.
+    pub(crate) fn net_charge(&self) -> Result<Charge, NumericError> {
+        let charge = net_charge_of_mixtures::<Count, Charge, _>(self.counted_mixtures())
+            .ok_or(NumericError::PositiveOverflow)?;
+        crate::signed_charge(charge.unsigned_abs(), charge > 0)
+    }
+
+    /// Returns an equivalent formula using the provided count and charge
+    /// types, without going through a string representation.
+    ///
+    /// Conversions to types at least as wide as the current ones never fail,
+    /// while conversions to narrower types fail if any count or charge of the
+    /// formula cannot be represented.
+    ///
+    /// # Errors
+    ///
+    /// * If any count or charge cannot be represented by the new types, with an
+    ///   error matching the sign of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{errors::NumericError, prelude::*};
+    ///
+    /// let formula = ChemicalFormula::<u16, i16>::from_str("[Fe(CN)6]-4").unwrap();
+    /// let wide = formula.convert::<u32, i32>().unwrap();
+    /// assert_eq!(wide.to_string(), "[Fe(CN)₆]⁴⁻");
+    ///
+    /// let large = ChemicalFormula::<u32, i32>::from_str("C300H602").unwrap();
+    /// assert_eq!(large.convert::<u8, i8>(), Err(NumericError::PositiveOverflow));
+    /// ```
+    pub fn convert<NewCount: CountLike, NewCharge: ChargeLike>(
+        &self,
+    ) -> Result<ChemicalFormula<NewCount, NewCharge>, NumericError> {
+        self.clone().try_convert()
+    }
+
+    /// Consumes the formula and returns an equivalent formula using the
+    /// provided count and charge types.
+    ///
+    /// # Errors
+    ///
+    /// * If any count or charge cannot be represented by the new types.
+    pub(crate) fn try_convert<NewCount: CountLike, NewCharge: ChargeLike>(
+        self,
+    ) -> Result<ChemicalFormula<NewCount, NewCharge>, NumericError> {
+        Ok(ChemicalFormula {
+            mixtures: self
+                .mixtures
+                .into_iter()
+                .map(|(count, tree)| Ok((convert_count(count)?, tree.try_convert()?)))
+                .collect::<Result<_, NumericError>>()?,
+        })
+    }
+
+    /// Returns the InChI formula corresponding to the elemental composition
+    /// of each of the mixtures of the formula.
+    ///
+    /// Each mixture is written in Hill order, with isotopes counted as their
+    /// element, while charges and radicals are dropped as they belong to
+    /// other InChI layers. The mixtures are then sorted as the components of
+    /// the formula layer of InChI, identical ones being merged and their
+    /// counts summed, as described in [`InChIFormula::canonical`].
+    ///
+    /// Returns `None` if any of the counts overflows, or if the formula is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("CH3CH2OH.H2O").unwrap();
+    /// let inchi = formula.to_inchi_formula().unwrap();
+    /// assert_eq!(inchi, InChIFormula::from_str("C2H6O.H2O").unwrap());
+    /// ```
+    #[must_use]
+    pub fn to_inchi_formula(&self) -> Option<InChIFormula<Count>> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut components: Vec<(Count, SequenceNode<InChITree<Count>>)> = Vec::new();
+        for (count, tree) in &self.mixtures {
+            let mut elements: Vec<Element> = tree.element_mask().into_iter().collect();
+            hill_sort(&mut elements);
+            let mut component = SequenceNode::empty();
+            for element in elements {
+                let element_count = tree.count_of_element::<Count>(element)?;
+                component.push(RepeatNode::new(element_count, element).into());
+            }
+            components.push((*count, component));
+        }
+        InChIFormula::from_components(components).canonical()
+    }
+
+    /// Returns the formula layer of the InChI corresponding to the formula,
+    /// as described in [`ChemicalFormula::to_inchi_formula`].
+    ///
+    /// Returns `None` if any of the counts overflows, or if the formula is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    /// assert_eq!(formula.to_inchi_formula_layer().unwrap(), "CuO4S.5H2O");
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("[13C]H3COO-.Na+").unwrap();
+    /// assert_eq!(formula.to_inchi_formula_layer().unwrap(), "C2H3O2.Na");
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("HCl.HCl.Ca").unwrap();
+    /// assert_eq!(formula.to_inchi_formula_layer().unwrap(), "Ca.2ClH");
+    /// ```
+    #[must_use]
+    pub fn to_inchi_formula_layer(&self) -> Option<String> {
+        self.to_inchi_formula().map(|formula| formula.to_string())
+    }
+
+    /// Returns the formula with the provided number of protons added to its
+    /// last mixture, increasing its charge accordingly.
+    ///
+    /// When the last mixture is repeated, as in `2H2O`, only one of its
+    /// copies is protonated, yielding `H₂O.H₃O⁺`. The hydrogens are merged into
+    /// the last run of plain hydrogens of the mixture, such as the `H₁₂` in
+    /// `C₆H₁₂O₆`, or appended when there is none.
+    ///
+    /// # Errors
+    ///
+    /// * If the formula is empty.
+    /// * If the hydrogen count or the charge overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    /// assert_eq!(glucose.protonate(1).unwrap().to_string(), "C₆H₁₃O₆⁺");
+    ///
+    /// let ammonia = ChemicalFormula::<u32, i32>::from_str("NH3").unwrap();
+    /// assert_eq!(ammonia.protonate(1).unwrap(), ChemicalFormula::from_str("NH4+").unwrap());
+    ///
+    /// let water = ChemicalFormula::<u32, i32>::from_str("2H2O").unwrap();
+    /// assert_eq!(water.protonate(1).unwrap().to_string(), "H₂O.H₃O⁺");
+    /// ```
+    pub fn protonate(&self, protons: Count) -> Result<Self, CompositionError> {
+        if protons.is_zero() {
+            return Ok(self.clone());
+        }
+        if self.is_empty() {
+            return Err(ParserError::EmptyMolecularTree.into());
+        }
+        let charge = Self::charge_of_protons(protons)?;
+        let mut formula = self.clone();
+        if let Some(tree) = formula.split_last_mixture() {
+            tree.add_hydrogens(protons)?;
+        }
+        Ok(formula.canonicalized().charged(charge)?)
+    }
+
+    /// Returns the formula with the provided number of protons removed from
+    /// its last mixture, decreasing its charge accordingly.
+    ///
+    /// The hydrogens are removed from the runs of plain hydrogens of the
+    /// mixture, starting from the rightmost one, so that `CH3COOH` becomes
+    /// `CH₃COO⁻`. Hydrogens within repeated groups, such as `(CH₃)₂`, are
+    /// not removed. When the last mixture is repeated, as in `2H2O`, only
+    /// one of its copies is deprotonated, yielding `H₂O.HO⁻`.
+    ///
+    /// # Errors
+    ///
+    /// * If the mixture does not contain enough removable hydrogens.
+    /// * If removing the hydrogens would leave the mixture empty.
+    /// * If the charge overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{errors::CompositionError, prelude::*};
+    ///
+    /// let acetic_acid = ChemicalFormula::<u32, i32>::from_str("CH3COOH").unwrap();
+    /// assert_eq!(acetic_acid.deprotonate(1).unwrap().to_string(), "CH₃COO⁻");
+    ///
+    /// let ammonium = ChemicalFormula::<u32, i32>::from_str("NH4+").unwrap();
+    /// assert_eq!(ammonium.deprotonate(1).unwrap(), ChemicalFormula::from_str("NH3").unwrap());
+    ///
+    /// let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+    /// assert_eq!(water.deprotonate(3), Err(CompositionError::InsufficientHydrogens));
+    /// ```
+    pub fn deprotonate(&self, protons: Count) -> Result<Self, CompositionError> {
+        if protons.is_zero() {
+            return Ok(self.clone());
+        }
+        let charge = Self::charge_of_protons(protons)?
+            .checked_neg()
+            .ok_or(NumericError::NegativeOverflow)?;
+        let mut formula = self.clone();
+        let mut remaining = protons;
+        if let Some(tree) = formula.split_last_mixture()
+            && tree.remove_hydrogens(&mut remaining)
+            && remaining.is_zero()
+        {
+            return Err(ParserError::EmptyMolecularTree.into());
+        }
+        if !remaining.is_zero() {
+            return Err(CompositionError::InsufficientHydrogens);
+        }
+        Ok(formula.canonicalized().charged(charge)?)
+    }
+
+    /// Returns the tree of a single copy of the last mixture, splitting it
+    /// off the other copies when the mixture is repeated, so that it may be
+    /// edited alone.
+    fn split_last_mixture(&mut self) -> Option<&mut ChemicalTree<Count, Charge, Empty>> {
+        let (count, tree) = self.mixtures.last_mut()?;
+        if !count.is_one() {
+            *count = *count - Count::ONE;
+            let copy = tree.clone();
+            self.mixtures.push((Count::ONE, copy));
+        }
+        self.mixtures.last_mut().map(|(_, tree)| tree)
+    }
+
+    /// Returns the charge carried by the provided number of protons.
+    fn charge_of_protons(protons: Count) -> Result<Charge, NumericError> {
+        protons
+            .try_into()
+            .ok()
+            .and_then(|protons: usize| i64::try_from(protons).ok())
+            .and_then(|protons| Charge::try_from(protons).ok())
+            .ok_or(NumericError::PositiveOverflow)
+    }
+
+    /// Returns a formula made of the provided mixtures.
+    pub(crate) fn from_mixtures(
+        mixtures: Vec<(Count, ChemicalTree<Count, Charge, Empty>)>,
+    ) -> Self {
+        Self { mixtures }.canonicalized()
+    }
+
+    /// Returns the formula with each of its mixtures replaced by the tree
+    /// the parser would build from its display, so that formulas built by
+    /// editing parsed formulas are parsed back from their display.
+    pub(crate) fn canonicalized(mut self) -> Self {
+        for (_, tree) in &mut self.mixtures {
+            let edited = core::mem::replace(tree, ChemicalTree::Sequence(SequenceNode::empty()));
+            *tree = edited.canonicalize();
+        }
+        self
+    }
+
+    /// Returns the mixtures of the formula.
+    pub(crate) fn into_mixtures(self) -> Vec<(Count, ChemicalTree<Count, Charge, Empty>)> {
+        self.mixtures
+    }
+
+    /// Returns a formula with a single mixture containing the provided
+    /// elements with their counts, in Hill order. Elements with a zero count
+    /// are skipped.
+    ///
+    /// # Panics
+    ///
+    /// * If no element has a non-zero count.
+    pub(crate) fn from_element_counts(counts: impl IntoIterator<Item = (Element, Count)>) -> Self {
+        let mut counts: Vec<(Element, Count)> =
+            counts.into_iter().filter(|(_, count)| !count.is_zero()).collect();
+        assert!(!counts.is_empty(), "At least one element with a non-zero count is required");
+        let mut elements: Vec<Element> = counts.iter().map(|(element, _)| *element).collect();
+        hill_sort(&mut elements);
+        counts.sort_by_key(|(element, _)| elements.iter().position(|e| e == element));
+
+        let mut tree = ChemicalTree::Sequence(SequenceNode::empty());
+        for (element, count) in counts {
+            let node = ChemicalTree::from(element);
+            tree = tree.push(if count.is_one() { node } else { node.repeat(count) });
+        }
+        Self { mixtures: alloc::vec![(Count::one(), tree)] }
+    }
+
+    /// Returns the formula with its last mixture decorated with the provided
+    /// charge, as the parser would do for a trailing charge.
+    ///
+    /// # Errors
+    ///
+    /// * If the charge of the last mixture overflows.
+    pub(crate) fn charged(mut self, charge: Charge) -> Result<Self, ParserError> {
+        if let Some((count, tree)) = self.mixtures.pop() {
+            self.mixtures.push((count, tree.charge(charge)?));
+        }
+        Ok(self)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge>
+where
+    Charge: TryFrom<Count>,
+{
+    /// Returns the total elemental composition of the provided formulas, as
+    /// a single mixture in Hill order carrying their net charge.
+    ///
+    /// Unlike their [sum](Sum), which preserves the mixtures of the
+    /// formulas, the total composition merges the atoms of all of them, with
+    /// isotopes counted as their element. No formulas, or only empty ones,
+    /// yield the [`empty`](Self::empty) formula.
+    ///
+    /// Returns `None` if any count or the net charge overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let fragments: Vec<ChemicalFormula<u32, i32>> = ["CH3", "CH2", "OH", "[13C]O2", "H+"]
+    ///     .into_iter()
+    ///     .map(|fragment| ChemicalFormula::from_str(fragment).unwrap())
+    ///     .collect();
+    /// let total = ChemicalFormula::total_composition(&fragments).unwrap();
+    /// assert_eq!(total.to_string(), "C₃H₇O₃⁺");
+    ///
+    /// let sum: ChemicalFormula<u32, i32> = fragments.iter().sum();
+    /// assert_eq!(sum.to_string(), "CH₃.CH₂.OH.[¹³C]O₂.H⁺");
+    /// ```
+    #[must_use]
+    pub fn total_composition<F: Borrow<Self>>(
+        formulas: impl IntoIterator<Item = F>,
+    ) -> Option<Self> {
+        let mut counts: Vec<(Element, Count)> = Vec::new();
+        let mut charge: i64 = 0;
+        for formula in formulas {
+            let formula = formula.borrow();
+            for element in formula.element_mask() {
+                let count = formula.count_of_element::<Count>(element)?;
+                if let Some((_, total)) = counts.iter_mut().find(|(e, _)| *e == element) {
+                    *total = total.checked_add(&count)?;
+                } else {
+                    counts.push((element, count));
+                }
+            }
+            charge = charge.checked_add(formula.net_charge_integer()?)?;
+        }
+        if counts.is_empty() {
+            return Some(Self::empty());
+        }
+        let total = Self::from_element_counts(counts);
+        if charge == 0 {
+            return Some(total);
+        }
+        total.charged(Charge::try_from(charge).ok()?).ok()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Default for ChemicalFormula<Count, Charge> {
+    fn default() -> Self {
+        Self::empty()
+    }
 }
 
 impl<Count: CountLike, Charge: ChargeLike> From<ChemicalTree<Count, Charge, Empty>>
@@ -78,6 +689,56 @@ impl<Count: CountLike, Charge: ChargeLike> From<Isotope> for ChemicalFormula<Cou
     }
 }
 
+impl<Count: CountLike, Charge: ChargeLike> TryFrom<(ElementMask, &[Count])>
+    for ChemicalFormula<Count, Charge>
+{
+    type Error = ElementMaskError;
+
+    /// Builds a neutral formula from an element mask and the counts of its
+    /// elements, provided by increasing atomic number. The elements are
+    /// written in Hill order.
+    ///
+    /// # Errors
+    ///
+    /// * If the mask is empty.
+    /// * If the number of counts differs from the number of elements in the
+    ///   mask, or if any count is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{errors::ElementMaskError, prelude::*};
+    ///
+    /// let mask: ElementMask = [Element::O, Element::C, Element::H].into_iter().collect();
+    /// let ethanol = ChemicalFormula::<u32, i32>::try_from((mask, &[6, 2, 1][..])).unwrap();
+    /// assert_eq!(ethanol, ChemicalFormula::from_str("C2H6O").unwrap());
+    ///
+    /// let counts: Vec<u32> = ethanol
+    ///     .element_mask()
+    ///     .into_iter()
+    ///     .map(|element| ethanol.count_of_element(element).unwrap())
+    ///     .collect();
+    /// assert_eq!(counts, [6, 2, 1]);
+    ///
+    /// assert_eq!(
+    ///     ChemicalFormula::<u32, i32>::try_from((mask, &[6, 2][..])),
+    ///     Err(ElementMaskError::MismatchedCounts)
+    /// );
+    /// ```
+    fn try_from((mask, counts): (ElementMask, &[Count])) -> Result<Self, Self::Error> {
+        if mask.into_iter().next().is_none() {
+            return Err(ElementMaskError::EmptyMask);
+        }
+        if mask.into_iter().count() != counts.len() || counts.iter().any(num_traits::Zero::is_zero)
+        {
+            return Err(ElementMaskError::MismatchedCounts);
+        }
+        Ok(Self::from_element_counts(mask.into_iter().zip(counts.iter().copied())))
+    }
+}
+
 impl<Count: CountLike, Charge: ChargeLike> From<InChIFormula<Count>>
     for ChemicalFormula<Count, Charge>
 {
@@ -127,6 +788,75 @@ impl<Count: CountLike, Charge: ChargeLike> AddAssign for ChemicalFormula<Count,
     }
 }
 
+impl<Count: CountLike, Charge: ChargeLike> Sum for ChemicalFormula<Count, Charge> {
+    /// Adds all the formulas, starting from the empty formula.
+    fn sum<I: Iterator<Item = Self>>(formulas: I) -> Self {
+        formulas.fold(Self::empty(), Add::add)
+    }
+}
+
+impl<'a, Count: CountLike, Charge: ChargeLike> Sum<&'a Self> for ChemicalFormula<Count, Charge> {
+    /// Adds clones of all the formulas, starting from the empty formula.
+    fn sum<I: Iterator<Item = &'a Self>>(formulas: I) -> Self {
+        formulas.fold(Self::empty(), |mut total, formula| {
+            total += formula.clone();
+            total
+        })
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Mul<Count> for ChemicalFormula<Count, Charge> {
+    type Output = Self;
+
+    /// Multiplies the counts of all mixtures by the provided factor.
+    ///
+    /// A zero factor yields the empty formula, and a count that would
+    /// overflow is split across repeated mixtures, so that the product is
+    /// the same as adding the formula to itself `factor` times.
+    fn mul(mut self, factor: Count) -> Self::Output {
+        self *= factor;
+        self
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> MulAssign<Count> for ChemicalFormula<Count, Charge> {
+    fn mul_assign(&mut self, factor: Count) {
+        if factor.is_zero() {
+            self.mixtures.clear();
+            return;
+        }
+        let mut spilled = Vec::new();
+        for (count, tree) in &mut self.mixtures {
+            if let Some(product) = count.checked_mul(&factor) {
+                *count = product;
+                continue;
+            }
+            // The largest chunk of the factor whose product with the count
+            // still fits, with `low` always fitting and `high` overflowing.
+            let original = *count;
+            let (mut low, mut high) = (Count::ONE, factor);
+            while high - low > Count::ONE {
+                let middle = low + (high - low) / Count::TWO;
+                if original.checked_mul(&middle).is_some() {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            *count = original * low;
+            let mut remaining = factor - low;
+            while remaining >= low {
+                spilled.push((original * low, tree.clone()));
+                remaining = remaining - low;
+            }
+            if !remaining.is_zero() {
+                spilled.push((original * remaining, tree.clone()));
+            }
+        }
+        self.mixtures.extend(spilled);
+    }
+}
+
 impl<Count: CountLike, Charge: ChargeLike> MolecularFormulaMetadata
     for ChemicalFormula<Count, Charge>
 {
@@ -172,35 +902,50 @@ where
     type Tree = ChemicalTree<Count, Charge, Empty>;
 
     fn on_start<J>(
-        _chars: &mut core::iter::Peekable<J>,
+        chars: &mut core::iter::Peekable<J>,
+        options: &crate::ParserOptions,
     ) -> Result<Self::StartOutput, crate::errors::ParserError>
     where
-        J: Iterator<Item = char>,
+        J: Iterator<Item = char> + Clone,
     {
-        Ok(())
+        match options.prefix_handling() {
+            PrefixHandling::Reject => Ok(()),
+            PrefixHandling::Skip => read_prefix(chars).map(drop),
+        }
     }
 
     fn from_parsed(
-        _start_output: Self::StartOutput,
+        (): Self::StartOutput,
         mixtures: Vec<(Count, Self::Tree)>,
     ) -> Result<Self, crate::errors::ParserError> {
         assert!(!mixtures.is_empty(), "At least one mixture is required");
+        debug_assert!(
+            mixtures.iter().all(|(_, tree)| tree.is_canonical()),
+            "The parser must build canonical trees"
+        );
         Ok(Self { mixtures })
     }
 }
 
 impl<Count: CountLike, Charge: ChargeLike> Display for ChemicalFormula<Count, Charge> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        for (i, (count, tree)) in self.mixtures.iter().enumerate() {
-            if i > 0 {
-                write!(f, ".")?;
-            }
-            if !count.is_one() {
-                write!(f, "{count}")?;
-            }
-            write!(f, "{tree}")?;
-        }
-        Ok(())
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> StyledDisplay for ChemicalFormula<Count, Charge> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        style.fmt_mixtures(self.mixtures.iter().map(|(count, tree)| (*count, tree)), f)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> DeepSizeOf for ChemicalFormula<Count, Charge> {
+    fn deep_size_of_children(&self) -> usize {
+        mixtures_size_of(&self.mixtures)
     }
 }
 
@@ -268,6 +1013,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_element_counts() {
+        let formula = ChemicalFormula::<u32, i32>::from_element_counts([
+            (Element::O, 1),
+            (Element::N, 0),
+            (Element::H, 4),
+            (Element::C, 1),
+        ]);
+        assert_eq!(formula.to_string(), "CH₄O");
+        assert_eq!(formula, ChemicalFormula::from_str("CH4O").unwrap());
+    }
+
+    #[test]
+    fn test_sub_compositions_masses() {
+        let formula = ChemicalFormula::<u32, i32>::from_str("C2H5OH").unwrap();
+        let mut number_of_sub_compositions = 0;
+        for (sub_composition, mass) in formula.sub_compositions(None) {
+            assert!((sub_composition.isotopologue_mass() - mass).abs() < 1e-9);
+            number_of_sub_compositions += 1;
+        }
+        assert_eq!(number_of_sub_compositions, 3 * 7 * 2 - 1);
+        let with_isotopes = ChemicalFormula::<u32, i32>::from_str("[13C]H4").unwrap();
+        assert_eq!(with_isotopes.sub_compositions(Some(5)).count(), 2 * 5 - 1);
+    }
+
+    #[test]
+    fn test_mul() {
+        let formula = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+        let trimer = formula.clone() * 3;
+        assert_eq!(trimer.to_string(), "3C₆H₁₂O₆");
+        assert!((trimer.isotopologue_mass() - 3.0 * formula.isotopologue_mass()).abs() < 1e-9);
+
+        let mut cluster = ChemicalFormula::<u32, i32>::from_str("2Na+").unwrap();
+        cluster *= 2;
+        assert_eq!(cluster.to_string(), "4Na⁺");
+    }
+
+    #[test]
+    fn test_mul_overflow_chains() {
+        // 200 * 2 = 400 overflows u8, so the count is split across mixtures
+        let formula = ChemicalFormula::<u8, i8>::from_str("200H2O").unwrap();
+        let product = formula.clone() * 2;
+        assert_eq!(product.to_string(), "200H₂O.200H₂O");
+        assert_eq!(product, formula.clone() + formula.clone());
+        assert_eq!(product.count_of_element::<u16>(Element::H), Some(800));
+
+        let mut zero = formula;
+        zero *= 0;
+        assert!(zero.is_empty());
+    }
+
     #[test]
     fn test_add_overflow_chains() {
         // Use u8 for count to easily trigger overflow
@@ -0,0 +1,298 @@
+//! Submodule providing the assignment of charges to the mixture components
+//! of salts, whose sources often write the charge of the whole mixture at
+//! its end, or omit the charges of the counter-ions altogether, and the
+//! splitting of salts into their counter-ions and their core.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+    ChargeLike, ChargedMolecularFormula, ChemicalFormula, CountLike, MolecularFormula,
+    errors::ChargeAssignmentError,
+};
+
+/// Common counter-ions, as their composition in Hill order, with the charge
+/// they carry in salts.
+const COUNTER_IONS: [(&str, i64); 27] = [
+    ("Li", 1),
+    ("Na", 1),
+    ("K", 1),
+    ("Rb", 1),
+    ("Cs", 1),
+    ("Ag", 1),
+    ("H4N", 1),
+    ("Mg", 2),
+    ("Ca", 2),
+    ("Sr", 2),
+    ("Ba", 2),
+    ("Zn", 2),
+    ("F", -1),
+    ("Cl", -1),
+    ("Br", -1),
+    ("I", -1),
+    ("NO3", -1),
+    ("ClO4", -1),
+    ("BF4", -1),
+    ("F6P", -1),
+    ("HO4S", -1),
+    ("CH3O3S", -1),
+    ("C2F3O2", -1),
+    ("CHO2", -1),
+    ("C2H3O2", -1),
+    ("O4S", -2),
+    ("CO3", -2),
+];
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge>
+where
+    Charge: TryFrom<Count>,
+{
+    /// Returns the formula with each copy of the mixture component at the
+    /// provided index carrying the provided charge, which replaces the
+    /// charges written within the component.
+    ///
+    /// This re-associates a charge written for the whole mixture, as in the
+    /// `C20H18F3N4O8P.Na-` of some sources, with the component it belongs
+    /// to. The charges of the other components are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// * If the formula has no mixture component at the provided index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let salt = ChemicalFormula::<u32, i32>::from_str("C6H5COO.Na-").unwrap();
+    /// let salt = salt.assign_charge_to_component(0, -1).unwrap();
+    /// let salt = salt.assign_charge_to_component(1, 1).unwrap();
+    /// assert_eq!(salt.to_string(), "C₆H₅COO⁻.Na⁺");
+    /// assert_eq!(salt.net_charge_integer(), Some(0));
+    ///
+    /// let neutral = salt.assign_charge_to_component(1, 0).unwrap();
+    /// assert_eq!(neutral.to_string(), "C₆H₅COO⁻.Na");
+    /// ```
+    pub fn assign_charge_to_component(
+        &self,
+        index: usize,
+        charge: Charge,
+    ) -> Result<Self, ChargeAssignmentError> {
+        let mut formula = self.clone();
+        let (_, tree) = formula
+            .mixtures
+            .get_mut(index)
+            .ok_or(ChargeAssignmentError::MissingComponent(index))?;
+        let mut neutral = tree.clone().neutralize().canonicalize();
+        if !charge.is_zero() {
+            neutral = neutral.charge(charge)?;
+        }
+        *tree = neutral;
+        Ok(formula)
+    }
+
+    /// Returns the formula with the charges of its mixture components
+    /// assigned according to the charges of common counter-ions, or `None`
+    /// if they cannot be assigned unambiguously.
+    ///
+    /// Each component matching a common counter-ion, such as `Na`, `NH4`,
+    /// `Cl` or `SO4`, carries its usual charge, whatever the charge written
+    /// for it. The single remaining component carries the charge balancing
+    /// them, so that the net charge of the formula is preserved. Isotopes
+    /// are counted as their element when matching counter-ions.
+    ///
+    /// Returns `None` if the formula contains no counter-ion, if it contains
+    /// more than one other component, or if the balancing charge is not a
+    /// whole charge per copy of the remaining component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let sofosbuvir_sodium = ChemicalFormula::<u32, i32>::from_str("C20H18F3N4O8P.Na").unwrap();
+    /// let assigned = sofosbuvir_sodium.assign_counter_ion_charges().unwrap();
+    /// assert_eq!(assigned.to_string(), "C₂₀H₁₈F₃N₄O₈P⁻.Na⁺");
+    ///
+    /// let sulfate = ChemicalFormula::<u32, i32>::from_str("2C7H15N2O.SO4").unwrap();
+    /// let assigned = sulfate.assign_counter_ion_charges().unwrap();
+    /// assert_eq!(assigned.to_string(), "2C₇H₁₅N₂O⁺.SO₄²⁻");
+    ///
+    /// let ambiguous = ChemicalFormula::<u32, i32>::from_str("C6H12O6.C2H6O").unwrap();
+    /// assert_eq!(ambiguous.assign_counter_ion_charges(), None);
+    /// ```
+    #[must_use]
+    pub fn assign_counter_ion_charges(&self) -> Option<Self> {
+        let (mut charges, counter_ions_charge) = self.counter_ion_charges()?;
+        let balance = self.net_charge_integer()?.checked_sub(counter_ions_charge)?;
+        let mut others = charges.iter().enumerate().filter(|(_, charge)| charge.is_none());
+        match (others.next(), others.next()) {
+            _ if charges.iter().all(Option::is_none) => return None,
+            (None, _) if balance != 0 => return None,
+            (Some((index, _)), None) => {
+                let count = i64::try_from(self.mixtures[index].0.into()).ok()?;
+                if balance % count != 0 {
+                    return None;
+                }
+                charges[index] = Some(balance / count);
+            }
+            (Some(_), Some(_)) => return None,
+            (None, _) => {}
+        }
+
+        let mut formula = self.clone();
+        for (index, charge) in charges.into_iter().enumerate() {
+            let charge = Charge::try_from(charge?).ok()?;
+            formula = formula.assign_charge_to_component(index, charge).ok()?;
+        }
+        Some(formula)
+    }
+
+    /// Splits the formula, which must be neutral, into its common
+    /// counter-ions and its remaining core, or returns `None` if it
+    /// contains no counter-ion or if their charges cannot be balanced.
+    ///
+    /// This is the desalting step applied before annotating salts, such as
+    /// the sodium salt of a drug, by the mass of their active moiety. Each
+    /// component matching a common counter-ion, such as `Na`, `NH4`, `Cl`,
+    /// `SO4` or acetate, is returned as an ion carrying its usual charge,
+    /// together with its multiplier. The core gathers the other components
+    /// and the prefix of the formula, and carries the charge balancing the
+    /// counter-ions: it is neutral when they balance each other, and is
+    /// otherwise the counter-ion of the identified ions. A single remaining
+    /// component is assigned this charge, as by
+    /// [`assign_counter_ion_charges`](Self::assign_counter_ion_charges),
+    /// while several remaining components keep the charges written for
+    /// them, which must add up to it. The core is the empty formula if all
+    /// the components are counter-ions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let salt = ChemicalFormula::<u32, i32>::from_str("C20H18F3N4O8P.Na").unwrap();
+    /// let split = salt.split_salt().unwrap();
+    /// assert_eq!(split.core().to_string(), "C₂₀H₁₈F₃N₄O₈P⁻");
+    /// let ions: Vec<String> = split.ions().iter().map(ToString::to_string).collect();
+    /// assert_eq!(ions, ["Na⁺"]);
+    ///
+    /// let buffered = ChemicalFormula::<u32, i32>::from_str("C6H12O6.2Na.SO4").unwrap();
+    /// let (core, ions) = buffered.split_salt().unwrap().into_parts();
+    /// assert_eq!(core.to_string(), "C₆H₁₂O₆");
+    /// let ions: Vec<String> = ions.iter().map(ToString::to_string).collect();
+    /// assert_eq!(ions, ["2Na⁺", "SO₄²⁻"]);
+    ///
+    /// let charged = ChemicalFormula::<u32, i32>::from_str("C6H5COO.Na+").unwrap();
+    /// assert_eq!(charged.split_salt(), None);
+    /// ```
+    #[must_use]
+    pub fn split_salt(&self) -> Option<SaltSplit<Count, Charge>> {
+        if self.net_charge_integer()? != 0 {
+            return None;
+        }
+        let (charges, counter_ions_charge) = self.counter_ion_charges()?;
+        let others = charges.iter().filter(|charge| charge.is_none()).count();
+        if others == charges.len() {
+            return None;
+        }
+        let formula = if others == 1 {
+            self.assign_counter_ion_charges()?
+        } else {
+            let mut formula = self.clone();
+            let mut others_charge: i64 = 0;
+            for (index, charge) in charges.iter().enumerate() {
+                if let Some(charge) = charge {
+                    let charge = Charge::try_from(*charge).ok()?;
+                    formula = formula.assign_charge_to_component(index, charge).ok()?;
+                } else {
+                    let component = Self::from_mixtures(alloc::vec![self.mixtures[index].clone()]);
+                    others_charge = others_charge.checked_add(component.net_charge_integer()?)?;
+                }
+            }
+            if others_charge.checked_add(counter_ions_charge)? != 0 {
+                return None;
+            }
+            formula
+        };
+
+        let mut core = Self { mixtures: Vec::new() };
+        let mut ions = Vec::new();
+        for (mixture, charge) in formula.mixtures.into_iter().zip(charges) {
+            if charge.is_some() {
+                ions.push(Self::from_mixtures(alloc::vec![mixture]));
+            } else {
+                core.mixtures.push(mixture);
+            }
+        }
+        Some(SaltSplit { core, ions })
+    }
+
+    /// Returns the charge of each mixture component matching a common
+    /// counter-ion, or `None` for the other components, alongside the total
+    /// charge of the counter-ions accounting for their multipliers.
+    fn counter_ion_charges(&self) -> Option<(Vec<Option<i64>>, i64)> {
+        let mut charges: Vec<Option<i64>> = Vec::new();
+        let mut counter_ions_charge: i64 = 0;
+        for (count, composition) in self.component_compositions() {
+            let mut hill = String::new();
+            for (element, atoms) in composition {
+                write!(hill, "{element}").ok()?;
+                if atoms > 1 {
+                    write!(hill, "{atoms}").ok()?;
+                }
+            }
+            let charge =
+                COUNTER_IONS.iter().find_map(|(ion, charge)| (*ion == hill).then_some(*charge));
+            if let Some(charge) = charge {
+                let count = i64::try_from(count.into()).ok()?;
+                counter_ions_charge =
+                    counter_ions_charge.checked_add(charge.checked_mul(count)?)?;
+            }
+            charges.push(charge);
+        }
+        Some((charges, counter_ions_charge))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Salt split into its common counter-ions and its remaining core, as
+/// returned by [`ChemicalFormula::split_salt`].
+pub struct SaltSplit<Count: CountLike = u16, Charge: ChargeLike = i16> {
+    /// The components which are not common counter-ions, carrying the
+    /// charge balancing the counter-ions.
+    core: ChemicalFormula<Count, Charge>,
+    /// The common counter-ions, each with its multiplier and usual charge,
+    /// in the order of the formula.
+    ions: Vec<ChemicalFormula<Count, Charge>>,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> SaltSplit<Count, Charge> {
+    /// Returns the components of the salt which are not common
+    /// counter-ions.
+    #[must_use]
+    pub fn core(&self) -> &ChemicalFormula<Count, Charge> {
+        &self.core
+    }
+
+    /// Returns the common counter-ions of the salt, in the order of the
+    /// formula.
+    #[must_use]
+    pub fn ions(&self) -> &[ChemicalFormula<Count, Charge>] {
+        &self.ions
+    }
+
+    /// Returns the core and the counter-ions of the salt.
+    #[must_use]
+    pub fn into_parts(
+        self,
+    ) -> (ChemicalFormula<Count, Charge>, Vec<ChemicalFormula<Count, Charge>>) {
+        (self.core, self.ions)
+    }
+}
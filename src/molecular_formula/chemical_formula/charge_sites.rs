@@ -0,0 +1,107 @@
+//! Submodule providing the localized charges of formulas, such as the
+//! ammonium and carboxylate sites of the zwitterions of amino acids.
+
+use alloc::vec::Vec;
+
+use crate::{ChargeLike, ChargedMolecularTree, ChemicalFormula, CountLike, saturating_u64};
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Returns each charged site of the formula, i.e. each group written
+    /// with a charge, with its number of copies in the formula and its
+    /// charge, in the order in which they are written.
+    ///
+    /// Charges written within a formula are kept on the groups carrying
+    /// them, so that the sites of zwitterions are preserved even though
+    /// their charges cancel out. The number of copies accounts for the
+    /// counts of the repeated groups and mixtures enclosing each site, and
+    /// saturates at `u64::MAX`.
+    fn charge_sites(&self) -> Vec<(u64, Charge)> {
+        let mut sites = Vec::new();
+        for (count, tree) in &self.mixtures {
+            tree.charge_sites(saturating_u64(*count), &mut sites);
+        }
+        sites
+    }
+
+    /// Returns the positively charged sites of the formula, with their
+    /// number of copies in the formula and their charge, in the order in
+    /// which they are written.
+    ///
+    /// Each group written with a charge is a site, such as the `H₃N⁺` of
+    /// `H₃N⁺CH₂COO⁻`, whose trailing charge is the one of the whole
+    /// component. The number of copies accounts for the counts of the
+    /// repeated groups and mixtures enclosing each site, and saturates at
+    /// `u64::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let lysine = ChemicalFormula::<u32, i32>::from_str("H3N+C5H9(NH3+)COO-").unwrap();
+    /// assert_eq!(lysine.positive_sites(), [(1, 1), (1, 1)]);
+    /// assert_eq!(lysine.negative_sites(), [(1, -1)]);
+    /// assert_eq!(lysine.charge(), 1.0);
+    ///
+    /// let salt = ChemicalFormula::<u32, i32>::from_str("2Na+.SO4-2").unwrap();
+    /// assert_eq!(salt.positive_sites(), [(2, 1)]);
+    /// assert_eq!(salt.negative_sites(), [(1, -2)]);
+    /// ```
+    #[must_use]
+    pub fn positive_sites(&self) -> Vec<(u64, Charge)> {
+        let mut sites = self.charge_sites();
+        sites.retain(|(_, charge)| *charge > Charge::ZERO);
+        sites
+    }
+
+    /// Returns the negatively charged sites of the formula, with their
+    /// number of copies in the formula and their charge, in the order in
+    /// which they are written.
+    ///
+    /// See [`positive_sites`](Self::positive_sites) for how the sites are
+    /// identified and counted.
+    #[must_use]
+    pub fn negative_sites(&self) -> Vec<(u64, Charge)> {
+        let mut sites = self.charge_sites();
+        sites.retain(|(_, charge)| *charge < Charge::ZERO);
+        sites
+    }
+
+    /// Returns whether the formula is made of neutral mixture components,
+    /// at least one of which carries both positively and negatively charged
+    /// sites, as the zwitterions of amino acids and their hydrates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let glycine = ChemicalFormula::<u32, i32>::from_str("H3N+CH2COO-").unwrap();
+    /// assert!(glycine.is_zwitterion());
+    /// assert_eq!(glycine.charge(), 0.0);
+    /// assert!(ChemicalFormula::<u32, i32>::from_str("H3N+CH2COO-.H2O").unwrap().is_zwitterion());
+    ///
+    /// // Salts are neutral but their charges are carried by distinct components.
+    /// assert!(!ChemicalFormula::<u32, i32>::from_str("Na+.Cl-").unwrap().is_zwitterion());
+    /// // Cations are not neutral.
+    /// assert!(!ChemicalFormula::<u32, i32>::from_str("H3N+CH2COOH").unwrap().is_zwitterion());
+    /// ```
+    #[must_use]
+    pub fn is_zwitterion(&self) -> bool {
+        let mut has_both = false;
+        for (_, tree) in &self.mixtures {
+            if ChargedMolecularTree::<Count, Charge>::net_charge_integer(tree) != Some(0) {
+                return false;
+            }
+            let mut sites = Vec::new();
+            tree.charge_sites(1, &mut sites);
+            has_both |= sites.iter().any(|(_, charge)| *charge > Charge::ZERO)
+                && sites.iter().any(|(_, charge)| *charge < Charge::ZERO);
+        }
+        has_both
+    }
+}
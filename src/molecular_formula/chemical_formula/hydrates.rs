@@ -0,0 +1,110 @@
+//! Submodule providing the removal of the waters of crystallization and
+//! solvents of formulas, as needed to deduplicate the entries of compound
+//! registries recording the same compound in several solvation states.
+
+use alloc::vec::Vec;
+
+use elements_rs::Element;
+
+use crate::{ChargeLike, ChargedMolecularTree, ChemicalFormula, CountLike, MolecularFormula};
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Returns the formula without its mixture components equal to water or
+    /// to any of the provided solvents, alongside the number of removed
+    /// copies of these components.
+    ///
+    /// Components are compared by their elemental composition, with
+    /// isotopes counted as their element so that heavy water is stripped as
+    /// well, and only neutral components are removed. Each component of
+    /// the provided solvent formulas is a solvent. The prefix of the
+    /// formula is preserved, and the empty formula is returned if all of its
+    /// components are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let hydrate = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    /// let (anhydrous, removed) = hydrate.strip_hydrates(&[]);
+    /// assert_eq!(anhydrous.to_string(), "CuSO₄");
+    /// assert_eq!(removed, 5);
+    ///
+    /// let solvate = ChemicalFormula::<u32, i32>::from_str("C22H19NO4.C2H6O.H2O").unwrap();
+    /// let ethanol = ChemicalFormula::<u32, i32>::from_str("CH3CH2OH").unwrap();
+    /// let (desolvated, removed) = solvate.strip_hydrates(&[ethanol]);
+    /// assert_eq!(desolvated.to_string(), "C₂₂H₁₉NO₄");
+    /// assert_eq!(removed, 2);
+    /// ```
+    #[must_use]
+    pub fn strip_hydrates(&self, solvents: &[Self]) -> (Self, u64) {
+        let water = alloc::vec![(Element::H, 2), (Element::O, 1)];
+        let stripped: Vec<Vec<(Element, u64)>> = core::iter::once(water)
+            .chain(
+                solvents
+                    .iter()
+                    .flat_map(Self::component_compositions)
+                    .map(|(_, composition)| composition),
+            )
+            .collect();
+
+        let mut formula = Self { mixtures: Vec::new() };
+        let mut removed: u64 = 0;
+        for ((count, composition), mixture) in
+            self.component_compositions().into_iter().zip(&self.mixtures)
+        {
+            let neutral =
+                ChargedMolecularTree::<Count, Charge>::net_charge_integer(&mixture.1) == Some(0);
+            if neutral && stripped.contains(&composition) {
+                let count: i128 = count.into();
+                removed = removed.saturating_add(u64::try_from(count).unwrap_or(u64::MAX));
+            } else {
+                formula.mixtures.push(mixture.clone());
+            }
+        }
+        (formula, removed)
+    }
+
+    /// Returns the number of copies of water among the mixture components
+    /// of the formula, i.e. its number of waters of crystallization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let hydrate = ChemicalFormula::<u32, i32>::from_str("K4[Fe(CN)6].3H2O").unwrap();
+    /// assert_eq!(hydrate.water_of_crystallization(), 3);
+    ///
+    /// let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+    /// assert_eq!(water.water_of_crystallization(), 1);
+    /// ```
+    #[must_use]
+    pub fn water_of_crystallization(&self) -> u64 {
+        self.strip_hydrates(&[]).1
+    }
+
+    /// Returns the formula without its waters of crystallization, as used
+    /// to compute masses on an anhydrous basis.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let hydrate = ChemicalFormula::<u32, i32>::from_str("C16H19N3O4S.3H2O").unwrap();
+    /// let anhydrous = hydrate.anhydrous();
+    /// assert_eq!(anhydrous.to_string(), "C₁₆H₁₉N₃O₄S");
+    /// assert!(hydrate.isotopologue_mass() - anhydrous.isotopologue_mass() > 54.0);
+    /// ```
+    #[must_use]
+    pub fn anhydrous(&self) -> Self {
+        self.strip_hydrates(&[]).0
+    }
+}
@@ -0,0 +1,212 @@
+//! Submodule providing the modelling of hydrogen/deuterium exchange, as
+//! observed in hydrogen-deuterium exchange mass spectrometry (HDX-MS), where
+//! the hydrogens bound to heteroatoms are replaced by deuterium.
+
+use alloc::vec::Vec;
+
+use elements_rs::{Element, ElementVariant, Isotope};
+
+use crate::{ChargeLike, ChemicalFormula, CountLike, MolecularFormula, errors::CompositionError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Heuristic estimating the number of exchangeable hydrogens of a formula
+/// from its heteroatoms, as formulas carry no information on bonds.
+///
+/// Each site element is allowed to carry up to a number of exchangeable
+/// hydrogens per atom. The default policy follows the usual hydroxyl,
+/// amine and thiol groups, allowing one hydrogen per oxygen, two per
+/// nitrogen and one per sulfur.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// let policy = ExchangePolicy::default().with_sites(Element::N, 1);
+/// assert_eq!(policy.hydrogens_per_atom(Element::N), 1);
+/// assert_eq!(policy.hydrogens_per_atom(Element::O), 1);
+/// assert_eq!(policy.hydrogens_per_atom(Element::C), 0);
+/// ```
+pub struct ExchangePolicy {
+    /// The site elements with their number of exchangeable hydrogens per
+    /// atom.
+    sites: Vec<(Element, u8)>,
+}
+
+impl Default for ExchangePolicy {
+    fn default() -> Self {
+        Self::new().with_sites(Element::N, 2).with_sites(Element::O, 1).with_sites(Element::S, 1)
+    }
+}
+
+impl ExchangePolicy {
+    /// Creates a policy without any site element, under which no hydrogen
+    /// is exchangeable.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { sites: Vec::new() }
+    }
+
+    /// Sets the number of exchangeable hydrogens carried by each atom of the
+    /// provided element, replacing any previous number. A zero number
+    /// removes the element from the sites.
+    #[must_use]
+    pub fn with_sites(mut self, element: Element, hydrogens_per_atom: u8) -> Self {
+        self.sites.retain(|(site, _)| *site != element);
+        if hydrogens_per_atom > 0 {
+            self.sites.push((element, hydrogens_per_atom));
+        }
+        self
+    }
+
+    /// Returns the number of exchangeable hydrogens carried by each atom of
+    /// the provided element.
+    #[must_use]
+    pub fn hydrogens_per_atom(&self, element: Element) -> u8 {
+        self.sites
+            .iter()
+            .find_map(|(site, hydrogens)| (*site == element).then_some(*hydrogens))
+            .unwrap_or(0)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Returns the number of hydrogens of the formula which are exchangeable
+    /// under the provided policy, i.e. the number of its plain hydrogens
+    /// bounded by the number of hydrogens its site elements may carry.
+    ///
+    /// Hydrogens already specified as isotopes, such as the deuterium of
+    /// `CH3OD`, are not exchangeable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let policy = ExchangePolicy::default();
+    /// let ethanolamine = ChemicalFormula::<u32, i32>::from_str("C2H7NO").unwrap();
+    /// assert_eq!(ethanolamine.exchangeable_hydrogens(&policy), 3);
+    /// let benzene = ChemicalFormula::<u32, i32>::from_str("C6H6").unwrap();
+    /// assert_eq!(benzene.exchangeable_hydrogens(&policy), 0);
+    /// let methanol = ChemicalFormula::<u32, i32>::from_str("CH3OD").unwrap();
+    /// assert_eq!(methanol.exchangeable_hydrogens(&policy), 1);
+    /// ```
+    #[must_use]
+    pub fn exchangeable_hydrogens(&self, policy: &ExchangePolicy) -> u64
+    where
+        u64: From<Count>,
+    {
+        let plain_hydrogens = self
+            .counted_mixtures()
+            .map(|(count, tree)| {
+                tree.number_of_plain_hydrogens::<u64>()
+                    .map_or(u64::MAX, |hydrogens| u64::from(count).saturating_mul(hydrogens))
+            })
+            .fold(0_u64, u64::saturating_add);
+        let sites = policy
+            .sites
+            .iter()
+            .map(|(element, hydrogens)| {
+                self.count_of_element::<u64>(*element).map_or(u64::MAX, |atoms| {
+                    atoms.saturating_mul(<u64 as From<u8>>::from(*hydrogens))
+                })
+            })
+            .fold(0_u64, u64::saturating_add);
+        plain_hydrogens.min(sites)
+    }
+
+    /// Returns the formula with the provided number of its plain hydrogens
+    /// replaced by deuterium, as after the exchange of as many hydrogens.
+    ///
+    /// The hydrogens are exchanged starting from the rightmost ones, which
+    /// are usually the ones bound to heteroatoms in condensed formulas such
+    /// as `CH3COOH`. Repeated groups and mixtures which are only partially
+    /// exchanged are split into their untouched and exchanged copies. The
+    /// mass of the exchanged formula is given by its
+    /// [`isotopologue_mass`](MolecularFormula::isotopologue_mass).
+    ///
+    /// # Errors
+    ///
+    /// * If the formula contains less plain hydrogens than requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{errors::CompositionError, prelude::*};
+    ///
+    /// let acetic_acid = ChemicalFormula::<u32, i32>::from_str("CH3COOH").unwrap();
+    /// let exchanged = acetic_acid.exchange_h_for_d(1).unwrap();
+    /// assert_eq!(exchanged, ChemicalFormula::from_str("CH3COOD").unwrap());
+    /// let shift = exchanged.isotopologue_mass() - acetic_acid.isotopologue_mass();
+    /// assert!((shift - 1.006277).abs() < 1e-6);
+    ///
+    /// let calcium_hydroxide = ChemicalFormula::<u32, i32>::from_str("Ca(OH)2").unwrap();
+    /// assert_eq!(
+    ///     calcium_hydroxide.exchange_h_for_d(1).unwrap(),
+    ///     ChemicalFormula::from_str("Ca(OH)(OD)").unwrap()
+    /// );
+    ///
+    /// let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+    /// assert_eq!(water.exchange_h_for_d(3), Err(CompositionError::InsufficientHydrogens));
+    /// ```
+    pub fn exchange_h_for_d(&self, hydrogens: Count) -> Result<Self, CompositionError> {
+        let deuterium = Isotope::try_from((Element::H, 2_u16))
+            .expect("Deuterium is a known isotope of hydrogen");
+        self.exchange_hydrogens(deuterium, hydrogens)
+    }
+
+    /// Returns the formula with the provided number of its plain hydrogens
+    /// replaced by the provided isotope of hydrogen, starting from the
+    /// rightmost ones, as [`exchange_h_for_d`](Self::exchange_h_for_d) does
+    /// for deuterium.
+    ///
+    /// # Errors
+    ///
+    /// * If the isotope is not an isotope of hydrogen.
+    /// * If the formula contains less plain hydrogens than requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{errors::CompositionError, prelude::*};
+    ///
+    /// let tritium = Isotope::try_from((Element::H, 3_u16)).unwrap();
+    /// let methanol = ChemicalFormula::<u32, i32>::from_str("CH3OH").unwrap();
+    /// assert_eq!(
+    ///     methanol.exchange_hydrogens(tritium, 1).unwrap(),
+    ///     ChemicalFormula::from_str("CH3OT").unwrap()
+    /// );
+    ///
+    /// let carbon13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+    /// assert_eq!(
+    ///     methanol.exchange_hydrogens(carbon13, 1),
+    ///     Err(CompositionError::NotAHydrogenIsotope(carbon13))
+    /// );
+    /// ```
+    pub fn exchange_hydrogens(
+        &self,
+        isotope: Isotope,
+        hydrogens: Count,
+    ) -> Result<Self, CompositionError> {
+        if isotope.element() != Element::H {
+            return Err(CompositionError::NotAHydrogenIsotope(isotope));
+        }
+        let mut remaining = hydrogens;
+        let mut mixtures = Vec::with_capacity(self.mixtures.len());
+        for (count, tree) in self.mixtures.iter().rev() {
+            let parts = tree.exchange_hydrogens_in_copies(*count, isotope, &mut remaining);
+            mixtures.extend(parts.into_iter().rev());
+        }
+        if !remaining.is_zero() {
+            return Err(CompositionError::InsufficientHydrogens);
+        }
+        mixtures.reverse();
+        Ok(Self { mixtures }.canonicalized())
+    }
+}
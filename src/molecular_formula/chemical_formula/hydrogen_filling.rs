@@ -0,0 +1,235 @@
+//! Submodule providing the filling of skeleton compositions with implicit
+//! hydrogens, as needed when working from the heavy atoms of a fragment.
+
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use elements_rs::{BondsNumber, Element};
+
+use crate::{
+    ChargeLike, ChemicalFormula, CountLike, MolecularFormula, errors::ValenceModelError,
+    molecular_tree::hill_sort,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Valences assumed for the atoms of a skeleton composition, alongside the
+/// range of unsaturations, i.e. of rings plus double bonds, its fully
+/// hydrogenated formulas may have.
+///
+/// Each element takes by default the valences between its lowest and
+/// highest number of bonds, e.g. four for carbon and two for oxygen, which
+/// may be replaced to account for hypervalent atoms such as the sulfur of
+/// sulfones. The default unsaturation is any non-negative number.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// let model =
+///     ValenceModel::new().with_valences(Element::S, [2, 4, 6])?.with_unsaturation(0, 2)?;
+/// assert_eq!(model.valences(Element::S), [2, 4, 6]);
+/// assert_eq!(model.valences(Element::C), [4]);
+/// assert_eq!(model.unsaturation(), (0, Some(2)));
+/// # Ok::<(), molecular_formulas::errors::ValenceModelError>(())
+/// ```
+pub struct ValenceModel {
+    /// The elements whose valences replace their default ones.
+    valences: Vec<(Element, Vec<u8>)>,
+    /// The minimal number of rings plus double bonds.
+    min_unsaturation: u32,
+    /// The maximal number of rings plus double bonds, if bounded.
+    max_unsaturation: Option<u32>,
+}
+
+impl Default for ValenceModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValenceModel {
+    /// Creates a model using the default valences of every element, and
+    /// allowing any non-negative unsaturation.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { valences: Vec::new(), min_unsaturation: 0, max_unsaturation: None }
+    }
+
+    /// Sets the valences which each atom of the provided element may take,
+    /// replacing any previous ones.
+    ///
+    /// # Errors
+    ///
+    /// * If no valence is provided.
+    pub fn with_valences(
+        mut self,
+        element: Element,
+        valences: impl IntoIterator<Item = u8>,
+    ) -> Result<Self, ValenceModelError> {
+        let valences: BTreeSet<u8> = valences.into_iter().collect();
+        if valences.is_empty() {
+            return Err(ValenceModelError::MissingValences(element));
+        }
+        self.valences.retain(|(e, _)| *e != element);
+        self.valences.push((element, valences.into_iter().collect()));
+        Ok(self)
+    }
+
+    /// Sets the inclusive range of unsaturations, i.e. of rings plus double
+    /// bonds, of the filled formulas.
+    ///
+    /// # Errors
+    ///
+    /// * If `min` is greater than `max`.
+    pub fn with_unsaturation(mut self, min: u32, max: u32) -> Result<Self, ValenceModelError> {
+        if min > max {
+            return Err(ValenceModelError::EmptyUnsaturation { min, max });
+        }
+        self.min_unsaturation = min;
+        self.max_unsaturation = Some(max);
+        Ok(self)
+    }
+
+    /// Returns the valences which each atom of the provided element may
+    /// take, in increasing order.
+    #[must_use]
+    pub fn valences(&self, element: Element) -> Vec<u8> {
+        self.valences.iter().find(|(e, _)| *e == element).map_or_else(
+            || {
+                let (min, max) = element.number_of_bonds();
+                (min..=max).collect()
+            },
+            |(_, valences)| valences.clone(),
+        )
+    }
+
+    /// Returns the inclusive range of unsaturations of the filled formulas,
+    /// with an unbounded maximum if `None`.
+    #[must_use]
+    pub fn unsaturation(&self) -> (u32, Option<u32>) {
+        (self.min_unsaturation, self.max_unsaturation)
+    }
+
+    /// Returns the lowest and highest numbers of hydrogens which may
+    /// saturate atoms with the provided element counts, alongside the step
+    /// between the reachable numbers, which is zero when only one is.
+    ///
+    /// Each number is the sum of the valences of the atoms minus twice the
+    /// number of bonds of an acyclic skeleton connecting them, so that the
+    /// bounds follow from the lowest and highest valences times the counts,
+    /// and the step from the differences between the valences.
+    fn saturating_hydrogens(&self, counts: &[(Element, u64)]) -> (i128, i128, i128) {
+        let (mut lowest, mut highest, mut step) = (2_i128, 2_i128, 0_u8);
+        for &(element, count) in counts {
+            let valences = self.valences(element);
+            let (Some(&min), Some(&max)) = (valences.first(), valences.last()) else {
+                continue;
+            };
+            let count = i128::from(count);
+            lowest += count * (i128::from(min) - 2);
+            highest += count * (i128::from(max) - 2);
+            if count > 0 {
+                step = valences
+                    .windows(2)
+                    .fold(step, |step, pair| greatest_common_divisor(step, pair[1] - pair[0]));
+            }
+        }
+        (lowest, highest, i128::from(step))
+    }
+}
+
+/// Returns the greatest common divisor of the two numbers, with zero as the
+/// neutral element.
+fn greatest_common_divisor(mut a: u8, mut b: u8) -> u8 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Returns the neutral formulas obtained by saturating the skeleton
+    /// composition of the formula with implicit hydrogens, under the
+    /// provided valence model.
+    ///
+    /// A skeleton whose atoms have valences `vᵢ` and which has `d` rings plus
+    /// double bonds is saturated by `2 + Σ(vᵢ - 2) - 2d` hydrogens, so that
+    /// each combination of valences and each unsaturation allowed by the
+    /// model yields a candidate. The sums of the valences are taken every
+    /// step between their lowest and highest values, which is exact as long
+    /// as the valences of all elements are evenly spaced by a common step,
+    /// as the default ones are. The hydrogens of the skeleton count as
+    /// monovalent atoms, and the added hydrogens are merged with them.
+    ///
+    /// The mixtures of the formula are merged into a single skeleton, with
+    /// isotopes counted as their element and charges dropped. Candidates are
+    /// written in Hill order, without duplicates, from the most to the least
+    /// hydrogenated one. Candidates whose counts cannot be represented by
+    /// the count type are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let skeleton = ChemicalFormula::<u32, i32>::from_str("C6O").unwrap();
+    /// let model = ValenceModel::new().with_unsaturation(0, 4).unwrap();
+    /// let candidates: Vec<String> =
+    ///     skeleton.fill_hydrogens(&model).iter().map(ToString::to_string).collect();
+    /// assert_eq!(candidates, ["C₆H₁₄O", "C₆H₁₂O", "C₆H₁₀O", "C₆H₈O", "C₆H₆O"]);
+    /// ```
+    #[must_use]
+    pub fn fill_hydrogens(&self, model: &ValenceModel) -> Vec<Self> {
+        let mut elements: Vec<Element> = self.element_mask().into_iter().collect();
+        hill_sort(&mut elements);
+        let Some(counts) = elements
+            .iter()
+            .map(|&element| {
+                let count: i128 = self.count_of_element::<Count>(element)?.into();
+                Some((element, u64::try_from(count).ok()?))
+            })
+            .collect::<Option<Vec<(Element, u64)>>>()
+        else {
+            return Vec::new();
+        };
+        let hydrogens = counts
+            .iter()
+            .find_map(|&(element, count)| (element == Element::H).then_some(count))
+            .unwrap_or(0);
+
+        let (min, max) = model.unsaturation();
+        let (min, max) = (i128::from(min), max.map(i128::from));
+        let (lowest, highest, step) = model.saturating_hydrogens(&counts);
+        // A number of added hydrogens is reachable if adding twice an
+        // allowed unsaturation to it yields a reachable saturating number,
+        // which only needs to be checked over one period of the step.
+        let reachable = |filled: i128| {
+            let first = min.max((lowest - filled + 1) / 2);
+            let last = max.map_or((highest - filled) / 2, |max| max.min((highest - filled) / 2));
+            (first..=last.min(first + step.max(1) - 1))
+                .any(|unsaturation| step == 0 || (filled + 2 * unsaturation - lowest) % step == 0)
+        };
+        let most = highest - 2 * min;
+        let least = max.map_or(0, |max| (lowest - 2 * max).max(0));
+
+        (least..=most)
+            .rev()
+            .filter(|&filled| reachable(filled))
+            .filter_map(|filled| {
+                let filled = u64::try_from(filled).ok()?;
+                let hydrogens = Count::try_from(hydrogens.checked_add(filled)?).ok()?;
+                let mut candidate = Vec::with_capacity(counts.len() + 1);
+                for &(element, count) in &counts {
+                    if element != Element::H {
+                        candidate.push((element, Count::try_from(count).ok()?));
+                    }
+                }
+                candidate.push((Element::H, hydrogens));
+                (candidate.iter().any(|(_, count)| !count.is_zero()))
+                    .then(|| Self::from_element_counts(candidate))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,116 @@
+//! Submodule providing the owned iterator over the elements of a chemical
+//! formula, which consumes the formula instead of borrowing it.
+
+use alloc::vec::Vec;
+
+use elements_rs::{Element, ElementVariant};
+
+use crate::{
+    ChargeLike, ChemicalFormula, ChemicalTree, CountLike, Empty, RepeatNode,
+    molecular_tree::into_inner,
+};
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Consumes the formula and iterates over its elements, repeating them
+    /// according to their counts, in the same order as
+    /// [`elements`](crate::MolecularFormula::elements).
+    ///
+    /// Isotopes are yielded as their element, and the prefix, charges and
+    /// radicals of the formula are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("2H2O.[13C]O2").unwrap();
+    /// let elements: Vec<Element> = formula.into_elements().collect();
+    /// assert_eq!(
+    ///     elements,
+    ///     [
+    ///         Element::H,
+    ///         Element::H,
+    ///         Element::O,
+    ///         Element::H,
+    ///         Element::H,
+    ///         Element::O,
+    ///         Element::C,
+    ///         Element::O,
+    ///         Element::O
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn into_elements(self) -> IntoElements<Count, Charge> {
+        let mut stack = self.into_mixtures();
+        stack.reverse();
+        IntoElements { stack }
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> IntoIterator for ChemicalFormula<Count, Charge> {
+    type Item = Element;
+    type IntoIter = IntoElements<Count, Charge>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_elements()
+    }
+}
+
+/// Owned iterator over the elements of a chemical formula, as returned by
+/// [`ChemicalFormula::into_elements`].
+///
+/// The trees of the formula are taken apart as they are visited, so that
+/// only the repeated groups, such as the `CH2` of `(CH2)3`, are cloned for
+/// each of their repetitions but the last one.
+pub struct IntoElements<Count: CountLike, Charge: ChargeLike> {
+    /// The trees left to visit, with the number of times each of them is
+    /// visited, the last tree being visited first.
+    stack: Vec<(Count, ChemicalTree<Count, Charge, Empty>)>,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Iterator for IntoElements<Count, Charge> {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        loop {
+            let (count, tree) = self.stack.pop()?;
+            if count.is_zero() {
+                continue;
+            }
+            let tree = if count.is_one() {
+                tree
+            } else {
+                // We schedule the remaining visits before the current one.
+                let current = tree.clone();
+                self.stack.push((count - Count::ONE, tree));
+                current
+            };
+            match tree {
+                ChemicalTree::Element(element) => return Some(element),
+                ChemicalTree::Isotope(isotope) => return Some(isotope.element()),
+                ChemicalTree::Radical(radical) => {
+                    self.stack.push((Count::ONE, into_inner(radical.into_node())));
+                }
+                ChemicalTree::Charge(charge) => {
+                    self.stack.push((Count::ONE, into_inner(charge.into_tree())));
+                }
+                ChemicalTree::Unit(unit) => {
+                    self.stack.push((Count::ONE, into_inner(unit.into_tree())));
+                }
+                ChemicalTree::Repeat(RepeatNode { count, node }) => {
+                    self.stack.push((count, into_inner(node)));
+                }
+                ChemicalTree::PackedRepeat(node) => {
+                    self.stack.push((node.count(), ChemicalTree::Element(node.element())));
+                }
+                ChemicalTree::Sequence(sequence) => {
+                    self.stack.extend(sequence.into_iter().rev().map(|node| (Count::ONE, node)));
+                }
+                ChemicalTree::Extension(Empty) => {}
+            }
+        }
+    }
+}
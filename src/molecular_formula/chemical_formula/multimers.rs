@@ -0,0 +1,168 @@
+//! Submodule providing the multimers of formulas, i.e. the species `[nM]`
+//! made of several copies of a molecule, and the adduct ions formed from
+//! them, as listed by mass spectrometry software.
+
+use alloc::vec::Vec;
+
+use crate::{
+    Adduct, ChargeLike, ChemicalFormula, CountLike, MolecularFormula,
+    errors::{CompositionError, NumericError, ParserError},
+};
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Returns the formula of the multimer `[nM]` made of the provided
+    /// number of copies of the formula.
+    ///
+    /// The tree of each mixture is wrapped in round brackets and repeated,
+    /// so that its brackets, isotopes and charges are preserved, while a
+    /// single copy leaves the formula untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    /// assert_eq!(glucose.multimer(2).unwrap().to_string(), "(C₆H₁₂O₆)₂");
+    /// let sulfate = ChemicalFormula::<u32, i32>::from_str("[34S]O4-2").unwrap();
+    /// assert_eq!(sulfate.multimer(2).unwrap().to_string(), "([³⁴S]O₄²⁻)₂");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * If the number of copies is zero, as the multimer would be empty.
+    /// * If any of the counts or the charge overflows.
+    /// * If a mixture would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](crate::ParserOptions::MAX_NESTING_DEPTH).
+    pub fn multimer(&self, copies: Count) -> Result<Self, CompositionError> {
+        if copies.is_zero() {
+            return Err(CompositionError::ZeroCopies);
+        }
+        let overflow = CompositionError::from(NumericError::PositiveOverflow);
+        for element in self.element_mask() {
+            self.count_of_element::<Count>(element)
+                .and_then(|count| count.checked_mul(&copies))
+                .ok_or(overflow)?;
+        }
+        let charge: i128 = self.net_charge()?.into();
+        let total = charge.checked_mul(copies.into());
+        if total
+            .and_then(|total| i64::try_from(total).ok())
+            .and_then(|total| Charge::try_from(total).ok())
+            .is_none()
+        {
+            return Err(if charge < 0 {
+                NumericError::NegativeOverflow
+            } else {
+                NumericError::PositiveOverflow
+            }
+            .into());
+        }
+        if copies.is_one() {
+            return Ok(self.clone());
+        }
+
+        let mixtures = self
+            .mixtures
+            .iter()
+            .map(|(count, tree)| {
+                let tree = tree.clone().in_round_brackets()?.repeated(copies)?;
+                Ok((*count, tree))
+            })
+            .collect::<Result<Vec<_>, ParserError>>()?;
+        Ok(Self { mixtures })
+    }
+
+    /// Returns the formulas of the multimers `[M]` to `[nM]` of the formula,
+    /// up to the provided number of copies, the multimer of `i + 1` copies
+    /// being at index `i`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+    /// let clusters: Vec<String> =
+    ///     water.multimers(3).unwrap().iter().map(ToString::to_string).collect();
+    /// assert_eq!(clusters, ["H₂O", "(H₂O)₂", "(H₂O)₃"]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * If any of the counts or the charge of a multimer overflows.
+    /// * If a mixture of a multimer would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](crate::ParserOptions::MAX_NESTING_DEPTH).
+    pub fn multimers(&self, max_copies: Count) -> Result<Vec<Self>, CompositionError> {
+        copies_up_to(max_copies).map(|copies| self.multimer(copies)).collect()
+    }
+
+    /// Returns the adduct ions formed from the multimers `[M]` to `[nM]` of
+    /// the formula with each of the provided adducts, alongside their
+    /// formulas, as the theoretical species list of a compound.
+    ///
+    /// The number of copies of the molecule of each adduct is replaced by
+    /// the one of the multimer, so that adducts are provided for a single
+    /// copy, such as `[M+H]1+`. Charge ladders are obtained by providing
+    /// the adducts of each charge state, such as `[M+H]1+` and `[M+2H]2+`.
+    /// The species are listed by increasing number of copies, and then in
+    /// the order of the adducts. The species whose losses remove more atoms
+    /// than the multimer contains, such as `[M-H2O+H]1+` of sodium
+    /// chloride, are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    /// let adducts: Vec<Adduct<u32, i32>> = ["[M+H]1+", "[M+Na]1+", "[M+2H]2+"]
+    ///     .into_iter()
+    ///     .map(|adduct| Adduct::from_str(adduct).unwrap())
+    ///     .collect();
+    /// let species = glucose.multimer_adducts(2, &adducts).unwrap();
+    /// let names: Vec<String> = species.iter().map(|(adduct, _)| adduct.to_string()).collect();
+    /// assert_eq!(names, ["[M+H]1+", "[M+Na]1+", "[M+2H]2+", "[2M+H]1+", "[2M+Na]1+", "[2M+2H]2+"]);
+    /// assert_eq!(species[4].1.to_string(), "C₁₂H₂₄NaO₁₂⁺");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * If any of the counts or the charge of a species overflows.
+    #[allow(clippy::type_complexity)]
+    pub fn multimer_adducts(
+        &self,
+        max_copies: Count,
+        adducts: &[Adduct<Count, Charge>],
+    ) -> Result<Vec<(Adduct<Count, Charge>, Self)>, CompositionError> {
+        let mut species = Vec::new();
+        for copies in copies_up_to(max_copies) {
+            for adduct in adducts {
+                let adduct = adduct.clone().with_multimer(copies);
+                match adduct.ion_formula(self) {
+                    Ok(ion) => species.push((adduct, ion)),
+                    Err(
+                        CompositionError::InsufficientAtoms(_)
+                        | CompositionError::Parser(ParserError::EmptyMolecularTree),
+                    ) => {}
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+        Ok(species)
+    }
+}
+
+/// Iterates over the numbers of copies from one to the provided maximum.
+fn copies_up_to<Count: CountLike>(max_copies: Count) -> impl Iterator<Item = Count> {
+    core::iter::successors(Some(Count::ONE), move |copies| {
+        copies.checked_add(&Count::ONE).filter(|next| *next <= max_copies)
+    })
+    .take_while(move |copies| *copies <= max_copies)
+}
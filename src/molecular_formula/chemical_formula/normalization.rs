@@ -0,0 +1,153 @@
+//! Submodule providing the explicit normalization levels used to obtain
+//! stable string representations of chemical formulas, as needed to compare
+//! formulas in snapshots and diffs.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    ChargeLike, ChargeStyle, ChemicalFormula, CountLike, DisplayStyle, NumberStyle, StyledDisplay,
+    errors::NumericError,
+};
+
+/// Offset basis of the 64-bit FNV-1a hash used by formula keys.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// Prime of the 64-bit FNV-1a hash used by formula keys.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+/// Characters used to encode formula keys, as in RFC 4648 base32.
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Amount of normalization applied when writing a chemical formula, from
+/// the formula as parsed to its canonical form.
+///
+/// Each level includes the normalizations of the previous ones, except for
+/// [`NormalizationLevel::None`] which writes numbers with ASCII characters.
+pub enum NormalizationLevel {
+    /// The structure of the formula as parsed, with counts, charges and mass
+    /// numbers written with ASCII characters, as in `Ca(OH)2`.
+    None,
+    /// The structure of the formula as parsed, with counts written as
+    /// subscripts and charges and mass numbers as superscripts, as in
+    /// `Ca(OH)₂`. This is the normalization applied by `Display`.
+    #[default]
+    Typography,
+    /// The brackets and repeated groups of each mixture are flattened into
+    /// elements and isotopes with merged counts, in order of first
+    /// appearance and followed by the net charge of the mixture, as in
+    /// `CaO₂H₂`.
+    Structural,
+    /// The flattened mixtures are written in Hill order, and identical
+    /// mixtures are merged summing their counts, as in `CaH₂O₂`.
+    Canonical,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Returns the string representation of the formula with the provided
+    /// level of normalization.
+    ///
+    /// # Errors
+    ///
+    /// * If any merged count or net charge overflows, which may only happen
+    ///   with the structural and canonical levels.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("K4[Fe(CN)6].3H2O.H2O").unwrap();
+    /// assert_eq!(formula.normalize(NormalizationLevel::None).unwrap(), "K4[Fe(CN)6].3H2O.H2O");
+    /// assert_eq!(formula.normalize(NormalizationLevel::Typography).unwrap(), "K₄[Fe(CN)₆].3H₂O.H₂O");
+    /// assert_eq!(formula.normalize(NormalizationLevel::Structural).unwrap(), "K₄FeC₆N₆.3H₂O.H₂O");
+    /// assert_eq!(formula.normalize(NormalizationLevel::Canonical).unwrap(), "C₆FeK₄N₆.4H₂O");
+    /// ```
+    pub fn normalize(&self, level: NormalizationLevel) -> Result<String, NumericError> {
+        Ok(match level {
+            NormalizationLevel::None => {
+                let style = DisplayStyle::new()
+                    .with_number_style(NumberStyle::Ascii)
+                    .with_charge_style(ChargeStyle::SignFirst);
+                self.format_with(&style).to_string()
+            }
+            NormalizationLevel::Typography => self.to_string(),
+            NormalizationLevel::Structural => self.flatten(false)?.to_string(),
+            NormalizationLevel::Canonical => {
+                let flattened = self.flatten(true)?;
+                let mut mixtures: Vec<(Count, _)> = Vec::new();
+                for (count, tree) in flattened.mixtures {
+                    if let Some((existing, _)) = mixtures.iter_mut().find(|(_, t)| *t == tree) {
+                        *existing =
+                            existing.checked_add(&count).ok_or(NumericError::PositiveOverflow)?;
+                    } else {
+                        mixtures.push((count, tree));
+                    }
+                }
+                Self { mixtures }.to_string()
+            }
+        })
+    }
+
+    /// Returns a short fixed-length key of the canonical form of the formula,
+    /// as a compact identifier of its composition and charge.
+    ///
+    /// The key is a 64-bit hash of the formula written with the
+    /// [`NormalizationLevel::Canonical`] normalization, encoded as thirteen
+    /// base32 characters from `A-Z` and `2-7`. Formulas with the same
+    /// canonical form, such as `CH3COOH` and `C2H4O2`, share the same key
+    /// regardless of their count and charge types, and the key
+    /// is stable across platforms and versions of this crate. Distinct
+    /// formulas are very unlikely to share a key, which is nevertheless not
+    /// guaranteed.
+    ///
+    /// # Errors
+    ///
+    /// * If any merged count or net charge overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let acetic_acid = ChemicalFormula::<u32, i32>::from_str("CH3COOH").unwrap();
+    /// let key = acetic_acid.formula_key().unwrap();
+    /// assert_eq!(key.len(), 13);
+    /// assert_eq!(key, ChemicalFormula::<u8, i8>::from_str("C2H4O2").unwrap().formula_key().unwrap());
+    ///
+    /// let acetate = ChemicalFormula::<u32, i32>::from_str("CH3COO-").unwrap();
+    /// assert_ne!(key, acetate.formula_key().unwrap());
+    /// ```
+    pub fn formula_key(&self) -> Result<String, NumericError> {
+        let canonical = self.normalize(NormalizationLevel::Canonical)?;
+        let mut hash = canonical
+            .bytes()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME));
+        // Mixes the bits of the hash, so that formulas with similar canonical
+        // forms have unrelated keys.
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^= hash >> 31;
+        Ok((0..13)
+            .rev()
+            .map(|index| char::from(BASE32_ALPHABET[((hash >> (index * 5)) & 0x1f) as usize]))
+            .collect())
+    }
+
+    /// Returns the formula with each of its mixtures flattened, as described
+    /// in [`NormalizationLevel::Structural`].
+    fn flatten(&self, hill_order: bool) -> Result<Self, NumericError> {
+        Ok(Self {
+            mixtures: self
+                .mixtures
+                .iter()
+                .map(|(count, tree)| Ok((*count, tree.flatten(hill_order)?)))
+                .collect::<Result<_, NumericError>>()?,
+        })
+    }
+}
@@ -0,0 +1,102 @@
+//! Submodule providing the iterator over the elemental sub-compositions of a
+//! chemical formula, as used in MS/MS fragment annotation.
+
+use alloc::vec::Vec;
+
+use elements_rs::{Element, RelativeAtomicMass};
+
+use crate::{
+    ChargeLike, ChemicalFormula, CountLike, MolecularFormula, molecular_tree::hill_sort,
+    wide_count_of_element,
+};
+
+/// Iterator over the non-empty elemental sub-compositions of a chemical
+/// formula, yielding each sub-composition alongside its monoisotopic mass.
+///
+/// Isotopic labels, charges and mixture boundaries are not preserved: the
+/// sub-compositions are built from the elemental composition of the
+/// formula, using the most abundant isotope of each element.
+pub struct SubCompositions<Count: CountLike, Charge: ChargeLike> {
+    /// The distinct elements of the formula, in Hill order.
+    elements: Vec<Element>,
+    /// The count of each element in the formula.
+    maxima: Vec<Count>,
+    /// The count of each element in the current sub-composition.
+    current: Vec<Count>,
+    /// The number of atoms in the current sub-composition.
+    number_of_atoms: usize,
+    /// The maximal number of atoms in a sub-composition, if any.
+    max_atoms: Option<usize>,
+    /// Whether the iteration is complete.
+    exhausted: bool,
+    _charge: core::marker::PhantomData<Charge>,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> SubCompositions<Count, Charge> {
+    /// Creates a new iterator over the sub-compositions of the provided
+    /// formula.
+    pub(crate) fn new(formula: &ChemicalFormula<Count, Charge>, max_atoms: Option<usize>) -> Self {
+        let mut elements: Vec<Element> = formula.element_mask().into_iter().collect();
+        hill_sort(&mut elements);
+        let maxima: Option<Vec<Count>> = elements
+            .iter()
+            .map(|&element| {
+                let count = u64::try_from(wide_count_of_element(formula, element)).ok()?;
+                Count::try_from(count).ok()
+            })
+            .collect();
+        // If the count of any element overflows, we cannot represent the
+        // sub-compositions with the current count type.
+        let exhausted = maxima.is_none() || elements.is_empty();
+        let maxima = maxima.unwrap_or_default();
+        Self {
+            current: alloc::vec![Count::ZERO; maxima.len()],
+            elements,
+            maxima,
+            number_of_atoms: 0,
+            max_atoms,
+            exhausted,
+            _charge: core::marker::PhantomData,
+        }
+    }
+
+    /// Advances the current sub-composition, returning whether a new one was
+    /// found.
+    fn advance(&mut self) -> bool {
+        for (count, maximum) in self.current.iter_mut().zip(self.maxima.iter()) {
+            if *count < *maximum && self.max_atoms.is_none_or(|max| self.number_of_atoms < max) {
+                *count = *count + Count::ONE;
+                self.number_of_atoms += 1;
+                return true;
+            }
+            // We reset the digit and carry over to the next one.
+            self.number_of_atoms -= (*count).try_into().unwrap_or(0);
+            *count = Count::ZERO;
+        }
+        false
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Iterator for SubCompositions<Count, Charge> {
+    type Item = (ChemicalFormula<Count, Charge>, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted || !self.advance() {
+            self.exhausted = true;
+            return None;
+        }
+        let mass = self
+            .elements
+            .iter()
+            .zip(self.current.iter())
+            .map(|(element, &count)| {
+                let count: f64 = count.as_();
+                count * element.relative_atomic_mass()
+            })
+            .sum();
+        let formula = ChemicalFormula::from_element_counts(
+            self.elements.iter().copied().zip(self.current.iter().copied()),
+        );
+        Some((formula, mass))
+    }
+}
@@ -0,0 +1,60 @@
+//! Submodule providing the arena-allocated counterpart of the chemical
+//! formulas, as returned by [`parse_in`](crate::parse_in).
+
+use bumpalo::collections::Vec;
+use elements_rs::Isotope;
+
+use crate::{ChargeLike, ChemicalFormula, ChemicalTreeRef, CountLike, Empty, ParsableFormula};
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+/// A chemical formula whose mixtures and nodes are allocated in a
+/// [`Bump`](bumpalo::Bump) arena.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// let arena = Bump::new();
+/// let hydrate = parse_in::<u16, i16>(&arena, "CuSO4.5H2O").unwrap();
+/// assert_eq!(hydrate.mixtures().len(), 2);
+/// assert_eq!(hydrate.mixtures()[1].0, 5);
+/// assert_eq!(hydrate.to_formula().to_string(), "CuSO₄.5H₂O");
+/// ```
+pub struct ChemicalFormulaRef<'a, Count: CountLike = u16, Charge: ChargeLike = i16> {
+    /// The mixtures of the formula with their multipliers.
+    mixtures: Vec<'a, (Count, ChemicalTreeRef<'a, Count, Charge, Empty>)>,
+}
+
+impl<'a, Count: CountLike, Charge: ChargeLike> ChemicalFormulaRef<'a, Count, Charge> {
+    /// Creates a new formula from the provided mixtures.
+    pub(crate) fn new(
+        mixtures: Vec<'a, (Count, ChemicalTreeRef<'a, Count, Charge, Empty>)>,
+    ) -> Self {
+        Self { mixtures }
+    }
+
+    /// Returns the mixtures of the formula with their multipliers.
+    #[must_use]
+    pub fn mixtures(&self) -> &[(Count, ChemicalTreeRef<'a, Count, Charge, Empty>)] {
+        &self.mixtures
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormulaRef<'_, Count, Charge>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    /// Returns the owned chemical formula equivalent to the arena-allocated
+    /// formula, which is equal to the formula parsed from the same string
+    /// with the same options.
+    #[must_use]
+    pub fn to_formula(&self) -> ChemicalFormula<Count, Charge> {
+        ChemicalFormula::from_parsed(
+            (),
+            self.mixtures.iter().map(|(count, tree)| (*count, tree.to_tree())).collect(),
+        )
+        .unwrap_or_else(|_| unreachable!("Parsed formulas hold at least one mixture"))
+    }
+}
@@ -0,0 +1,585 @@
+//! Submodule providing the configuration used to display molecular formulas
+//! according to the conventions of different journals and tools.
+
+use core::fmt::{Display, Formatter, Result};
+
+use elements_rs::{Element, ElementVariant, Isotope, MassNumber};
+
+use crate::{
+    BaselineMinus, BaselinePlus, Bracket, CharacterMarker, CountLike, Dot, SuperscriptMinus,
+    SuperscriptPlus, subscript_digits_ltr, superscript_digits_ltr,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Placement and form of the charges of a formula.
+pub enum ChargeStyle {
+    /// The magnitude precedes the sign, as in `Ca²⁺`.
+    #[default]
+    MagnitudeFirst,
+    /// The sign precedes the magnitude, as in `Ca⁺²`.
+    SignFirst,
+    /// The sign is repeated as many times as the magnitude, as in `Ca⁺⁺`.
+    Repeated,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Brackets used to display the groups of a formula.
+pub enum BracketStyle {
+    /// The brackets are displayed as they were written.
+    #[default]
+    Preserve,
+    /// All groups are displayed within round brackets, as in `Ca(OH)₂`.
+    Round,
+    /// All groups are displayed within square brackets, as in `Ca[OH]₂`.
+    Square,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Form of the counts, charges and mass numbers of a formula.
+pub enum NumberStyle {
+    /// Counts are displayed as subscripts and charges and mass numbers as
+    /// superscripts, as in `[¹³C]H₄`.
+    #[default]
+    Scripts,
+    /// All numbers and signs are displayed using ASCII characters, as in
+    /// `[13C]H4`.
+    Ascii,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Form of the isotopes of a formula.
+///
+/// Only the bracketed form, with either number style, and the hydrogen
+/// letters are guaranteed to be parsed back into the same formula.
+pub enum IsotopeStyle {
+    /// The mass number precedes the element within square brackets, as in
+    /// `[¹³C]` or, with ASCII numbers, `[13C]`.
+    #[default]
+    Bracketed,
+    /// The mass number precedes the element as a superscript, without
+    /// brackets, as in `¹³C`, irrespectively of the number style.
+    Superscript,
+    /// The mass number follows the element, as in the `C13` used by
+    /// spreadsheet column headers, irrespectively of the number style.
+    Suffixed,
+    /// Deuterium and tritium are displayed as `D` and `T`, while the other
+    /// isotopes are bracketed.
+    HydrogenLetters,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Separator displayed between the mixtures of a formula.
+pub enum MixtureSeparator {
+    /// The full stop, as in `CuSO₄.5H₂O`.
+    #[default]
+    Dot,
+    /// The middle dot, as in `CuSO₄·5H₂O`.
+    ///
+    /// Since the middle dot is also used to denote radicals, formulas
+    /// displayed with this separator are not meant to be parsed back.
+    MiddleDot,
+}
+
+impl MixtureSeparator {
+    /// Returns the character used as separator.
+    #[must_use]
+    pub const fn character(&self) -> char {
+        match self {
+            Self::Dot => Dot::CANONICAL,
+            Self::MiddleDot => '·',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Vertical position of the numbers written in scientific notation.
+enum Position {
+    /// Baseline digits, as for mixture multipliers and ASCII numbers.
+    Baseline,
+    /// Subscript digits, as for counts.
+    Subscript,
+    /// Superscript digits, as for charges.
+    Superscript,
+}
+
+impl Position {
+    /// Returns the character separating the mantissa from the exponent.
+    const fn exponent_marker(self) -> char {
+        match self {
+            Self::Baseline => 'e',
+            Self::Subscript => 'ₑ',
+            Self::Superscript => 'ᵉ',
+        }
+    }
+
+    /// Writes the provided non-negative number with the digits of the
+    /// position.
+    fn fmt_digits(self, number: i128, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Baseline => write!(f, "{number}"),
+            Self::Subscript => {
+                subscript_digits_ltr(number).try_for_each(|digit| write!(f, "{digit}"))
+            }
+            Self::Superscript => {
+                superscript_digits_ltr(number).try_for_each(|digit| write!(f, "{digit}"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+/// Configuration of how molecular formulas are displayed.
+///
+/// The default style is the one used by the `Display` implementations of
+/// the formulas. Note that some styles, such as ASCII numbers with the
+/// magnitude before the sign (`Ca2+`), are not guaranteed to be parsed back
+/// into the same formula.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let formula = ChemicalFormula::<u32, i32>::from_str("[Ca(H2O)6]+2.2Cl-").unwrap();
+/// let style = DisplayStyle::new()
+///     .with_charge_style(ChargeStyle::SignFirst)
+///     .with_number_style(NumberStyle::Ascii);
+/// assert_eq!(formula.format_with(&style).to_string(), "[Ca(H2O)6]+2.2Cl-");
+/// ```
+pub struct DisplayStyle {
+    /// The placement and form of the charges.
+    charge: ChargeStyle,
+    /// The brackets used for groups.
+    brackets: BracketStyle,
+    /// The form of the numbers.
+    numbers: NumberStyle,
+    /// The separator between mixtures.
+    separator: MixtureSeparator,
+    /// The form of the isotopes.
+    isotopes: IsotopeStyle,
+    /// Whether giant counts and charges are written in scientific notation.
+    compact: bool,
+}
+
+impl DisplayStyle {
+    /// Creates the default display style.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            charge: ChargeStyle::MagnitudeFirst,
+            brackets: BracketStyle::Preserve,
+            numbers: NumberStyle::Scripts,
+            separator: MixtureSeparator::Dot,
+            isotopes: IsotopeStyle::Bracketed,
+            compact: false,
+        }
+    }
+
+    /// Smallest magnitude of the counts and charges written in scientific
+    /// notation by the compact styles.
+    pub const COMPACT_THRESHOLD: u128 = 1_000_000;
+
+    /// Largest number of significant digits of the counts and charges
+    /// written in scientific notation by the compact styles.
+    pub const COMPACT_DIGITS: u32 = 3;
+
+    /// Sets the placement and form of the charges.
+    #[must_use]
+    pub const fn with_charge_style(mut self, charge: ChargeStyle) -> Self {
+        self.charge = charge;
+        self
+    }
+
+    /// Sets the brackets used for groups.
+    #[must_use]
+    pub const fn with_bracket_style(mut self, brackets: BracketStyle) -> Self {
+        self.brackets = brackets;
+        self
+    }
+
+    /// Sets the form of the numbers.
+    #[must_use]
+    pub const fn with_number_style(mut self, numbers: NumberStyle) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Sets the separator between mixtures.
+    #[must_use]
+    pub const fn with_mixture_separator(mut self, separator: MixtureSeparator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the form of the isotopes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("[13C]H3OD").unwrap();
+    /// let style = DisplayStyle::new().with_isotope_style(IsotopeStyle::Suffixed);
+    /// assert_eq!(formula.format_with(&style).to_string(), "C13H₃OH2");
+    /// let style = DisplayStyle::new().with_isotope_style(IsotopeStyle::HydrogenLetters);
+    /// assert_eq!(formula.format_with(&style).to_string(), "[¹³C]H₃OD");
+    /// ```
+    #[must_use]
+    pub const fn with_isotope_style(mut self, isotopes: IsotopeStyle) -> Self {
+        self.isotopes = isotopes;
+        self
+    }
+
+    /// Sets whether the counts, mixture multipliers and charges of at least
+    /// [`COMPACT_THRESHOLD`](Self::COMPACT_THRESHOLD) in magnitude are written
+    /// in scientific notation, as the `(C₂H₄)₁ₑ₆` of a polymer, so that
+    /// formulas built programmatically with giant counts are displayed in a
+    /// bounded width.
+    ///
+    /// The mantissa is an integer of at most
+    /// [`COMPACT_DIGITS`](Self::COMPACT_DIGITS) significant digits, and the
+    /// numbers needing more digits are truncated and marked with a leading
+    /// `~`. The giant charges of the repeated charge style are written with
+    /// their magnitude rather than with as many signs.
+    ///
+    /// Compact displays are meant to be read, not parsed back: the parser
+    /// rejects the exponents and the approximation marks, so that a compact
+    /// display is never silently read as another formula. The default display
+    /// must be used to round-trip formulas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let polymer = ChemicalFormula::<u32, i32>::from_str("(C2H4)2500000").unwrap();
+    /// let style = DisplayStyle::new().with_compact_numbers(true);
+    /// assert_eq!(polymer.format_with(&style).to_string(), "(C₂H₄)₂₅ₑ₅");
+    /// let style = style.with_number_style(NumberStyle::Ascii);
+    /// assert_eq!(polymer.format_with(&style).to_string(), "(C2H4)25e5");
+    ///
+    /// let polymer = ChemicalFormula::<u32, i32>::from_str("(C2H4)1234567").unwrap();
+    /// assert_eq!(polymer.format_with(&style).to_string(), "(C2H4)~123e4");
+    /// assert!(ChemicalFormula::<u32, i32>::from_str("(C2H4)~123e4").is_err());
+    /// ```
+    #[must_use]
+    pub const fn with_compact_numbers(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Returns the placement and form of the charges.
+    #[must_use]
+    pub const fn charge_style(&self) -> ChargeStyle {
+        self.charge
+    }
+
+    /// Returns the brackets used for groups.
+    #[must_use]
+    pub const fn bracket_style(&self) -> BracketStyle {
+        self.brackets
+    }
+
+    /// Returns the form of the numbers.
+    #[must_use]
+    pub const fn number_style(&self) -> NumberStyle {
+        self.numbers
+    }
+
+    /// Returns the separator between mixtures.
+    #[must_use]
+    pub const fn mixture_separator(&self) -> MixtureSeparator {
+        self.separator
+    }
+
+    /// Returns the form of the isotopes.
+    #[must_use]
+    pub const fn isotope_style(&self) -> IsotopeStyle {
+        self.isotopes
+    }
+
+    /// Returns whether giant counts and charges are written in scientific
+    /// notation.
+    #[must_use]
+    pub const fn compact_numbers(&self) -> bool {
+        self.compact
+    }
+
+    /// Returns the integer mantissa and the exponent of the scientific
+    /// notation of the provided magnitude, and whether they are exact, or
+    /// `None` if the magnitude is not written in scientific notation.
+    fn scientific_notation(&self, magnitude: u128) -> Option<(u128, u32, bool)> {
+        if !self.compact || magnitude < Self::COMPACT_THRESHOLD {
+            return None;
+        }
+        let (mut mantissa, mut exponent, mut exact) = (magnitude, 0, true);
+        while mantissa % 10 == 0 {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        while mantissa >= 10u128.pow(Self::COMPACT_DIGITS) {
+            exact = false;
+            mantissa /= 10;
+            exponent += 1;
+        }
+        Some((mantissa, exponent, exact))
+    }
+
+    /// Writes the provided magnitude in scientific notation if it is giant,
+    /// in the provided position, and returns whether it was written.
+    fn fmt_scientific(
+        &self,
+        magnitude: u128,
+        position: Position,
+        f: &mut Formatter<'_>,
+    ) -> core::result::Result<bool, core::fmt::Error> {
+        let Some((mantissa, exponent, exact)) = self.scientific_notation(magnitude) else {
+            return Ok(false);
+        };
+        let position =
+            if self.numbers == NumberStyle::Ascii { Position::Baseline } else { position };
+        if !exact {
+            write!(f, "~")?;
+        }
+        let mantissa = i128::try_from(mantissa)
+            .unwrap_or_else(|_| unreachable!("Mantissas have at most three digits"));
+        position.fmt_digits(mantissa, f)?;
+        write!(f, "{}", position.exponent_marker())?;
+        position.fmt_digits(i128::from(exponent), f)?;
+        Ok(true)
+    }
+
+    /// Writes the provided mixture multiplier on the baseline.
+    fn fmt_baseline<C: CountLike>(&self, count: C, f: &mut Formatter<'_>) -> Result {
+        if !self.fmt_scientific(Into::<i128>::into(count).unsigned_abs(), Position::Baseline, f)? {
+            write!(f, "{count}")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the bracket to display in place of the provided one.
+    pub(crate) fn bracket(&self, bracket: Bracket) -> Bracket {
+        match self.brackets {
+            BracketStyle::Preserve => bracket,
+            BracketStyle::Round => Bracket::Round,
+            BracketStyle::Square => Bracket::Square,
+        }
+    }
+
+    /// Writes the provided count as a subscript.
+    pub(crate) fn fmt_subscript<C: CountLike>(&self, count: C, f: &mut Formatter<'_>) -> Result {
+        if self.fmt_scientific(Into::<i128>::into(count).unsigned_abs(), Position::Subscript, f)? {
+            return Ok(());
+        }
+        match self.numbers {
+            NumberStyle::Scripts => {
+                for digit in subscript_digits_ltr(count) {
+                    write!(f, "{digit}")?;
+                }
+                Ok(())
+            }
+            NumberStyle::Ascii => write!(f, "{count}"),
+        }
+    }
+
+    /// Writes the provided number as a superscript.
+    fn fmt_superscript(&self, number: i128, f: &mut Formatter<'_>) -> Result {
+        if self.fmt_scientific(number.unsigned_abs(), Position::Superscript, f)? {
+            return Ok(());
+        }
+        match self.numbers {
+            NumberStyle::Scripts => {
+                for digit in superscript_digits_ltr(number) {
+                    write!(f, "{digit}")?;
+                }
+                Ok(())
+            }
+            NumberStyle::Ascii => write!(f, "{}", number.unsigned_abs()),
+        }
+    }
+
+    /// Writes the provided charge.
+    pub(crate) fn fmt_charge<C: Into<i128>>(&self, charge: C, f: &mut Formatter<'_>) -> Result {
+        // We convert the charge into i128 to avoid potential overflows when
+        // executing the `abs` method on the most negative value of any
+        // supported integer type.
+        let charge: i128 = charge.into();
+        let sign = match (self.numbers, charge < 0) {
+            (NumberStyle::Scripts, true) => SuperscriptMinus::CANONICAL,
+            (NumberStyle::Scripts, false) => SuperscriptPlus::CANONICAL,
+            (NumberStyle::Ascii, true) => BaselineMinus::CANONICAL,
+            (NumberStyle::Ascii, false) => BaselinePlus::CANONICAL,
+        };
+        match self.charge {
+            ChargeStyle::MagnitudeFirst | ChargeStyle::Repeated
+                if self.scientific_notation(charge.unsigned_abs()).is_some() =>
+            {
+                self.fmt_superscript(charge, f)?;
+                write!(f, "{sign}")
+            }
+            ChargeStyle::MagnitudeFirst => {
+                if charge.abs() > 1 {
+                    self.fmt_superscript(charge, f)?;
+                }
+                write!(f, "{sign}")
+            }
+            ChargeStyle::SignFirst => {
+                write!(f, "{sign}")?;
+                if charge.abs() > 1 {
+                    self.fmt_superscript(charge, f)?;
+                }
+                Ok(())
+            }
+            ChargeStyle::Repeated => {
+                for _ in 0..charge.unsigned_abs() {
+                    write!(f, "{sign}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the provided isotope.
+    pub(crate) fn fmt_isotope(&self, isotope: Isotope, f: &mut Formatter<'_>) -> Result {
+        let mass_number = isotope.mass_number();
+        match self.isotopes {
+            IsotopeStyle::Superscript => {
+                for digit in superscript_digits_ltr(mass_number) {
+                    write!(f, "{digit}")?;
+                }
+                write!(f, "{}", isotope.element())
+            }
+            IsotopeStyle::Suffixed => write!(f, "{}{mass_number}", isotope.element()),
+            IsotopeStyle::HydrogenLetters
+                if isotope.element() == Element::H && mass_number == 2 =>
+            {
+                write!(f, "D")
+            }
+            IsotopeStyle::HydrogenLetters
+                if isotope.element() == Element::H && mass_number == 3 =>
+            {
+                write!(f, "T")
+            }
+            IsotopeStyle::Bracketed | IsotopeStyle::HydrogenLetters => {
+                write!(f, "[")?;
+                self.fmt_superscript(i128::from(mass_number), f)?;
+                write!(f, "{}]", isotope.element())
+            }
+        }
+    }
+
+    /// Writes the provided mixtures, with their counts, separated by the
+    /// mixture separator.
+    pub(crate) fn fmt_mixtures<'a, C: CountLike, T: StyledDisplay + 'a>(
+        &self,
+        mixtures: impl Iterator<Item = (C, &'a T)>,
+        f: &mut Formatter<'_>,
+    ) -> Result {
+        for (i, (count, tree)) in mixtures.enumerate() {
+            if i > 0 {
+                write!(f, "{}", self.separator.character())?;
+            }
+            if !count.is_one() {
+                self.fmt_baseline(count, f)?;
+            }
+            tree.fmt_with(self, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Trait for types which can be displayed according to a [`DisplayStyle`].
+pub trait StyledDisplay {
+    /// Writes the value according to the provided style.
+    ///
+    /// # Errors
+    ///
+    /// * If writing to the formatter fails.
+    fn fmt_with(&self, style: &DisplayStyle, f: &mut Formatter<'_>) -> Result;
+
+    /// Returns a value implementing `Display` which writes the value
+    /// according to the provided style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    /// let style = DisplayStyle::new().with_mixture_separator(MixtureSeparator::MiddleDot);
+    /// assert_eq!(formula.format_with(&style).to_string(), "CuSO₄·5H₂O");
+    /// ```
+    fn format_with<'a>(&'a self, style: &'a DisplayStyle) -> Styled<'a, Self> {
+        Styled { value: self, style }
+    }
+
+    /// Returns a value implementing `Display` which writes the value with
+    /// the default style, except for its giant counts and charges which are
+    /// written in scientific notation, as described in
+    /// [`DisplayStyle::with_compact_numbers`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let polyethylene = ChemicalFormula::<u32, i32>::from_str("(C2H4)1000000").unwrap();
+    /// assert_eq!(polyethylene.display_compact().to_string(), "(C₂H₄)₁ₑ₆");
+    /// assert_eq!(polyethylene.to_string(), "(C₂H₄)₁₀₀₀₀₀₀");
+    ///
+    /// let polyanion = ChemicalFormula::<u32, i32>::from_str("(C3H3O2)50000000-50000000").unwrap();
+    /// assert_eq!(polyanion.display_compact().to_string(), "(C₃H₃O₂)₅ₑ₇⁵ᵉ⁷⁻");
+    ///
+    /// let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+    /// assert_eq!(water.display_compact().to_string(), water.to_string());
+    /// ```
+    fn display_compact(&self) -> Styled<'_, Self> {
+        self.format_with(&COMPACT_STYLE)
+    }
+}
+
+/// Style used by [`StyledDisplay::display_compact`].
+static COMPACT_STYLE: DisplayStyle = DisplayStyle::new().with_compact_numbers(true);
+
+/// Value displayed according to a [`DisplayStyle`], as returned by
+/// [`StyledDisplay::format_with`].
+pub struct Styled<'a, T: ?Sized> {
+    /// The value to display.
+    value: &'a T,
+    /// The style to display the value with.
+    style: &'a DisplayStyle,
+}
+
+impl<T: StyledDisplay + ?Sized> Display for Styled<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.value.fmt_with(self.style, f)
+    }
+}
+
+impl<T: StyledDisplay + ?Sized> StyledDisplay for crate::Subtree<T> {
+    fn fmt_with(&self, style: &DisplayStyle, f: &mut Formatter<'_>) -> Result {
+        self.as_ref().fmt_with(style, f)
+    }
+}
+
+impl StyledDisplay for Element {
+    fn fmt_with(&self, _style: &DisplayStyle, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{self}")
+    }
+}
+
+impl StyledDisplay for Isotope {
+    fn fmt_with(&self, style: &DisplayStyle, f: &mut Formatter<'_>) -> Result {
+        style.fmt_isotope(*self, f)
+    }
+}
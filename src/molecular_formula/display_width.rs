@@ -0,0 +1,73 @@
+//! Submodule providing the width of formula displays and their truncation
+//! at token boundaries, as needed to render long mixtures in tables.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    BaselineDigit, BaselineMinus, BaselinePlus, CharacterMarker, Dot, SubscriptDigit,
+    SuperscriptDigit, SuperscriptMinus, SuperscriptPlus,
+};
+
+/// Character appended to truncated displays.
+const ELLIPSIS: char = '…';
+
+/// Returns whether the provided character is a digit of the provided form.
+fn is_digit<D: TryFrom<char>>(character: char) -> bool {
+    D::try_from(character).is_ok()
+}
+
+/// Returns whether the provided character is the sign of a charge.
+fn is_sign(character: char) -> bool {
+    BaselinePlus::matches(character)
+        || BaselineMinus::matches(character)
+        || SuperscriptPlus::matches(character)
+        || SuperscriptMinus::matches(character)
+}
+
+/// Returns whether a token of the provided formula display may end before
+/// the character at the provided index.
+///
+/// Element symbols, counts and charges are never split, and neither are
+/// opening brackets, the mass numbers of isotopes and the multipliers of
+/// mixtures from the token they precede.
+fn is_token_boundary(characters: &[char], index: usize) -> bool {
+    let (previous, next) = (characters[index - 1], characters[index]);
+    let baseline = is_digit::<BaselineDigit>;
+    let superscript = is_digit::<SuperscriptDigit>;
+    let charge_part =
+        |character: char| is_sign(character) || baseline(character) || superscript(character);
+    if matches!(previous, '(' | '[')
+        || next.is_ascii_lowercase()
+        || is_digit::<SubscriptDigit>(next)
+        || (baseline(next) && !Dot::matches(previous))
+        || (superscript(previous) && superscript(next))
+        || (superscript(previous) && next.is_ascii_uppercase())
+        || (charge_part(previous) && is_sign(next))
+        || (is_sign(previous) && charge_part(next))
+    {
+        return false;
+    }
+    if baseline(previous) {
+        // A run of baseline digits at the start of the display or of one of
+        // its mixtures is a multiplier, which belongs to the following token.
+        let start = characters[..index].iter().rposition(|c| !baseline(*c));
+        return start.is_some_and(|start| !Dot::matches(characters[start]));
+    }
+    true
+}
+
+/// Returns the provided display truncated to at most the provided width, at
+/// the last token boundary leaving room for an ellipsis.
+pub(crate) fn truncate_display(display: &str, max_width: usize) -> String {
+    let characters: Vec<char> = display.chars().collect();
+    if characters.len() <= max_width {
+        return String::from(display);
+    }
+    let Some(available) = max_width.checked_sub(1) else {
+        return String::new();
+    };
+    let end = (1..=available).rev().find(|&end| is_token_boundary(&characters, end)).unwrap_or(0);
+    let mut truncated: String = characters[..end].iter().collect();
+    truncated.push(ELLIPSIS);
+    truncated
+}
@@ -0,0 +1,147 @@
+//! Submodule providing the element constraints shared by the generation of
+//! candidate formulas and the validation of existing ones.
+
+use alloc::vec::Vec;
+
+use elements_rs::Element;
+
+use crate::{ChargeLike, ChemicalFormula, CountLike};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+/// Constraints on the elements of a formula, made of an inclusive range of
+/// counts for some elements and an optional set of allowed elements.
+///
+/// Elements with bounds are always allowed, while elements without bounds
+/// are allowed in any number when no allowed set is provided, or when they
+/// belong to it.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let bounds = ElementBounds::<u32>::new()
+///     .with_bounds(Element::C, 1, 10)
+///     .with_bounds(Element::O, 0, 4)
+///     .with_allowed_elements([Element::H]);
+///
+/// let ethanol = ChemicalFormula::<u32, i32>::from_str("C2H6O").unwrap();
+/// let urea = ChemicalFormula::<u32, i32>::from_str("CH4N2O").unwrap();
+/// assert!(ethanol.satisfies(&bounds));
+/// assert!(!urea.satisfies(&bounds));
+/// ```
+pub struct ElementBounds<Count: CountLike = u16> {
+    /// The inclusive range of counts of the bounded elements.
+    bounds: Vec<(Element, Count, Count)>,
+    /// The elements allowed without bounds, if restricted.
+    allowed: Option<Vec<Element>>,
+}
+
+impl<Count: CountLike> ElementBounds<Count> {
+    /// Creates constraints allowing any element in any number.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { bounds: Vec::new(), allowed: None }
+    }
+
+    /// Sets the inclusive range of counts of the provided element, replacing
+    /// any previous range.
+    ///
+    /// # Panics
+    ///
+    /// * If `min` is greater than `max`.
+    #[must_use]
+    pub fn with_bounds(mut self, element: Element, min: Count, max: Count) -> Self {
+        assert!(min <= max, "The minimum count of {element} must not exceed its maximum");
+        if let Some(bound) = self.bounds.iter_mut().find(|(e, _, _)| *e == element) {
+            *bound = (element, min, max);
+        } else {
+            self.bounds.push((element, min, max));
+        }
+        self
+    }
+
+    /// Restricts the elements allowed without bounds to the provided ones,
+    /// in addition to any previously allowed elements.
+    #[must_use]
+    pub fn with_allowed_elements(mut self, elements: impl IntoIterator<Item = Element>) -> Self {
+        let allowed = self.allowed.get_or_insert_with(Vec::new);
+        for element in elements {
+            if !allowed.contains(&element) {
+                allowed.push(element);
+            }
+        }
+        self
+    }
+
+    /// Returns the inclusive range of counts of the provided element, if it
+    /// is bounded.
+    #[must_use]
+    pub fn bounds(&self, element: Element) -> Option<(Count, Count)> {
+        self.bounds.iter().find(|(e, _, _)| *e == element).map(|&(_, min, max)| (min, max))
+    }
+
+    /// Iterates over the bounded elements with their inclusive range of
+    /// counts.
+    pub fn iter(&self) -> impl Iterator<Item = (Element, Count, Count)> + '_ {
+        self.bounds.iter().copied()
+    }
+
+    /// Returns whether the provided element may appear in a formula.
+    #[must_use]
+    pub fn is_allowed(&self, element: Element) -> bool {
+        self.bounds.iter().any(|(e, _, _)| *e == element)
+            || self.allowed.as_ref().is_none_or(|allowed| allowed.contains(&element))
+    }
+
+    /// Iterates over the neutral formulas made of the bounded elements, with
+    /// every combination of counts within their bounds.
+    ///
+    /// Elements which are allowed without bounds are not generated, and the
+    /// combination where all counts are zero is skipped. Counts vary fastest
+    /// for the element bounded last, and every generated formula is written
+    /// in Hill order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let bounds =
+    ///     ElementBounds::<u32>::new().with_bounds(Element::C, 1, 2).with_bounds(Element::O, 0, 1);
+    /// let formulas: Vec<String> =
+    ///     bounds.formulas::<i32>().map(|formula| formula.to_string()).collect();
+    /// assert_eq!(formulas, ["C", "CO", "C₂", "C₂O"]);
+    /// ```
+    pub fn formulas<Charge: ChargeLike>(
+        &self,
+    ) -> impl Iterator<Item = ChemicalFormula<Count, Charge>> + '_ {
+        let mut counts: Option<Vec<Count>> =
+            Some(self.bounds.iter().map(|&(_, min, _)| min).collect());
+        core::iter::from_fn(move || {
+            loop {
+                let current = counts.clone()?;
+                counts = self.next_counts(current.clone());
+                if current.iter().any(|count| !count.is_zero()) {
+                    return Some(ChemicalFormula::from_element_counts(
+                        self.bounds.iter().map(|&(element, _, _)| element).zip(current),
+                    ));
+                }
+            }
+        })
+    }
+
+    /// Returns the combination of counts following the provided one, if any.
+    fn next_counts(&self, mut counts: Vec<Count>) -> Option<Vec<Count>> {
+        for (count, &(_, min, max)) in counts.iter_mut().zip(self.bounds.iter()).rev() {
+            if *count < max {
+                *count = *count + Count::ONE;
+                return Some(counts);
+            }
+            *count = min;
+        }
+        None
+    }
+}
@@ -0,0 +1,251 @@
+//! Submodule providing the specification of the dense feature vectors of
+//! formulas, as used to embed formulas in machine learning pipelines.
+
+use alloc::{format, string::String, vec::Vec};
+
+use elements_rs::Element;
+
+use crate::ChargedMolecularFormula;
+
+/// Elements whose counts are featurized by default.
+const DEFAULT_ELEMENTS: [Element; 10] = [
+    Element::C,
+    Element::H,
+    Element::N,
+    Element::O,
+    Element::P,
+    Element::S,
+    Element::F,
+    Element::Cl,
+    Element::Br,
+    Element::I,
+];
+
+/// Elements whose ratios to carbon are featurized by default.
+const DEFAULT_RATIOS: [Element; 5] = [Element::H, Element::N, Element::O, Element::P, Element::S];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Specification of the features of the dense vectors returned by
+/// [`to_feature_vector`](crate::ChargedMolecularFormula::to_feature_vector).
+///
+/// The features are always written in the following order, each group
+/// being omitted when disabled:
+///
+/// 1. The number of atoms of each of the [`elements`](Self::elements), in the
+///    order in which they were provided, with isotopes counted as their
+///    element. The default elements are `C`, `H`, `N`, `O`, `P`, `S`, `F`,
+///    `Cl`, `Br` and `I`.
+/// 2. The number of atoms of any other element, if enabled.
+/// 3. The isotopologue mass of the formula, without considering its charge.
+/// 4. The double bond equivalent of the formula.
+/// 5. The charge of the formula.
+/// 6. The ratio of the number of atoms of each of the [`ratios`](Self::ratios)
+///    elements to the number of carbon atoms, or zero for formulas without
+///    carbon. The default ratios are `H/C`, `N/C`, `O/C`, `P/C` and `S/C`.
+///
+/// The [`feature_names`](Self::feature_names) follow the same order, so
+/// that vectors of formulas featurized with equal specifications may be
+/// compared and stacked.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// let spec = FeatureSpec::new()
+///     .with_elements([Element::C, Element::H, Element::O])
+///     .with_other_elements(true)
+///     .with_double_bond_equivalent(false)
+///     .with_ratios([Element::O]);
+/// assert_eq!(spec.feature_names(), ["C", "H", "O", "other", "mass", "charge", "O/C"]);
+/// assert_eq!(spec.len(), 7);
+/// ```
+pub struct FeatureSpec {
+    /// The elements whose counts are featurized.
+    elements: Vec<Element>,
+    /// Whether the number of atoms of the other elements is featurized.
+    other_elements: bool,
+    /// Whether the isotopologue mass is featurized.
+    mass: bool,
+    /// Whether the double bond equivalent is featurized.
+    double_bond_equivalent: bool,
+    /// Whether the charge is featurized.
+    charge: bool,
+    /// The elements whose ratios to carbon are featurized.
+    ratios: Vec<Element>,
+}
+
+impl Default for FeatureSpec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureSpec {
+    /// Creates the default specification, featurizing the counts of the
+    /// common elements of organic compounds, the mass, the double bond
+    /// equivalent, the charge and the ratios of the common heteroatoms to
+    /// carbon.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            elements: DEFAULT_ELEMENTS.to_vec(),
+            other_elements: false,
+            mass: true,
+            double_bond_equivalent: true,
+            charge: true,
+            ratios: DEFAULT_RATIOS.to_vec(),
+        }
+    }
+
+    /// Sets the elements whose counts are featurized, in order, replacing
+    /// the previous ones.
+    ///
+    /// # Panics
+    ///
+    /// * If an element is provided more than once.
+    #[must_use]
+    pub fn with_elements(mut self, elements: impl IntoIterator<Item = Element>) -> Self {
+        self.elements = distinct(elements);
+        self
+    }
+
+    /// Sets whether the number of atoms of the elements other than the
+    /// featurized ones is featurized.
+    #[must_use]
+    pub fn with_other_elements(mut self, other_elements: bool) -> Self {
+        self.other_elements = other_elements;
+        self
+    }
+
+    /// Sets whether the isotopologue mass is featurized.
+    #[must_use]
+    pub fn with_mass(mut self, mass: bool) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets whether the double bond equivalent is featurized.
+    #[must_use]
+    pub fn with_double_bond_equivalent(mut self, double_bond_equivalent: bool) -> Self {
+        self.double_bond_equivalent = double_bond_equivalent;
+        self
+    }
+
+    /// Sets whether the charge is featurized.
+    #[must_use]
+    pub fn with_charge(mut self, charge: bool) -> Self {
+        self.charge = charge;
+        self
+    }
+
+    /// Sets the elements whose ratios to carbon are featurized, in order,
+    /// replacing the previous ones.
+    ///
+    /// # Panics
+    ///
+    /// * If an element is provided more than once.
+    #[must_use]
+    pub fn with_ratios(mut self, ratios: impl IntoIterator<Item = Element>) -> Self {
+        self.ratios = distinct(ratios);
+        self
+    }
+
+    /// Returns the elements whose counts are featurized, in order.
+    #[must_use]
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Returns the elements whose ratios to carbon are featurized, in order.
+    #[must_use]
+    pub fn ratios(&self) -> &[Element] {
+        &self.ratios
+    }
+
+    /// Returns the number of features of the vectors.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+            + usize::from(self.other_elements)
+            + usize::from(self.mass)
+            + usize::from(self.double_bond_equivalent)
+            + usize::from(self.charge)
+            + self.ratios.len()
+    }
+
+    /// Returns whether the vectors have no features.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the names of the features of the vectors, in order: the
+    /// element symbols, `other`, `mass`, `dbe`, `charge` and the ratios
+    /// such as `H/C`.
+    #[must_use]
+    pub fn feature_names(&self) -> Vec<String> {
+        let mut names = Vec::with_capacity(self.len());
+        names.extend(self.elements.iter().map(|element| format!("{element}")));
+        for (enabled, name) in [
+            (self.other_elements, "other"),
+            (self.mass, "mass"),
+            (self.double_bond_equivalent, "dbe"),
+            (self.charge, "charge"),
+        ] {
+            if enabled {
+                names.push(String::from(name));
+            }
+        }
+        names.extend(self.ratios.iter().map(|element| format!("{element}/C")));
+        names
+    }
+
+    /// Returns the features of the provided formula, in the order of the
+    /// specification.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub(crate) fn features<M: ChargedMolecularFormula>(&self, formula: &M) -> Vec<f32> {
+        let count = |element: Element| -> f64 {
+            formula
+                .count_of_element::<M::Count>(element)
+                .map_or(f64::INFINITY, |count| count.into() as f64)
+        };
+        let mut features = Vec::with_capacity(self.len());
+        features.extend(self.elements.iter().map(|element| count(*element) as f32));
+        if self.other_elements {
+            let other: f64 = formula
+                .element_mask()
+                .into_iter()
+                .filter(|element| !self.elements.contains(element))
+                .map(count)
+                .sum();
+            features.push(other as f32);
+        }
+        if self.mass {
+            features.push(formula.isotopologue_mass() as f32);
+        }
+        if self.double_bond_equivalent {
+            features.push(formula.double_bond_equivalent() as f32);
+        }
+        if self.charge {
+            features.push(formula.charge() as f32);
+        }
+        let carbons = count(Element::C);
+        features.extend(
+            self.ratios.iter().map(|element| {
+                if carbons > 0.0 { (count(*element) / carbons) as f32 } else { 0.0 }
+            }),
+        );
+        features
+    }
+}
+
+/// Collects the provided elements, ensuring that none is repeated.
+fn distinct(elements: impl IntoIterator<Item = Element>) -> Vec<Element> {
+    let mut distinct: Vec<Element> = Vec::new();
+    for element in elements {
+        assert!(!distinct.contains(&element), "The element {element} is provided more than once");
+        distinct.push(element);
+    }
+    distinct
+}
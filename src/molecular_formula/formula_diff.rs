@@ -0,0 +1,283 @@
+//! Submodule providing the structured difference between two molecular
+//! formulas, as used to explain mismatches against a reference formula or to
+//! compute the composition of a modification.
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use elements_rs::{Element, RelativeAtomicMass};
+
+use crate::{
+    ChargedMolecularFormula, MassTolerance, molecular_tree::hill_sort, wide_count_of_element,
+};
+
+/// Elements other than hydrogen of the compositions suggested for mass
+/// shifts, with the inclusive range of their count differences.
+const SHIFT_ELEMENTS: [(Element, i64, i64); 5] = [
+    (Element::C, -10, 15),
+    (Element::N, -4, 6),
+    (Element::O, -8, 10),
+    (Element::S, -2, 2),
+    (Element::P, -1, 2),
+];
+
+/// The inclusive range of the hydrogen count differences of the
+/// compositions suggested for mass shifts.
+const SHIFT_HYDROGENS: (i64, i64) = (-20, 30);
+
+#[derive(Debug, Clone, PartialEq)]
+/// Difference between two molecular formulas, obtained by subtracting a
+/// reference formula from another one.
+///
+/// Positive element counts denote atoms which are only present in the
+/// compared formula, while negative counts denote atoms which are only
+/// present in the reference one.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let serine = ChemicalFormula::<u16, i16>::from_str("C3H7NO3").unwrap();
+/// let phosphoserine = ChemicalFormula::<u16, i16>::from_str("C3H8NO6P").unwrap();
+/// let diff = phosphoserine.diff(&serine);
+/// assert_eq!(diff.count_of_element(Element::P), 1);
+/// assert_eq!(diff.count_of_element(Element::O), 3);
+/// assert_eq!(diff.to_string(), "H O(3) P");
+/// assert!((diff.isotopologue_mass() - 79.966331).abs() < 1e-6);
+/// ```
+pub struct FormulaDiff {
+    /// The non-zero differences of the element counts, in Hill order.
+    elements: Vec<(Element, i64)>,
+    /// The difference of the charges.
+    charge: f64,
+    /// The difference of the isotopologue masses, without charges.
+    isotopologue_mass: f64,
+    /// The difference of the molar masses.
+    molar_mass: f64,
+}
+
+impl FormulaDiff {
+    /// Returns the difference between the provided formula and the
+    /// reference formula.
+    pub(crate) fn new<M: ChargedMolecularFormula>(formula: &M, reference: &M) -> Self {
+        let mut elements: Vec<Element> =
+            formula.element_mask().into_iter().chain(reference.element_mask()).collect();
+        hill_sort(&mut elements);
+        elements.dedup();
+        let totals = elements.into_iter().map(|element| {
+            // The counts are summed in i128, as they may not fit in the count
+            // type, and saturate beyond its range.
+            (
+                element,
+                wide_count_of_element(formula, element) - wide_count_of_element(reference, element),
+            )
+        });
+
+        Self {
+            elements: totals
+                .filter(|(_, difference)| *difference != 0)
+                .map(|(element, difference)| {
+                    // Differences beyond the range of i64 saturate.
+                    let difference = i64::try_from(difference).unwrap_or(if difference > 0 {
+                        i64::MAX
+                    } else {
+                        i64::MIN
+                    });
+                    (element, difference)
+                })
+                .collect(),
+            charge: formula.charge() - reference.charge(),
+            isotopologue_mass: formula.isotopologue_mass() - reference.isotopologue_mass(),
+            molar_mass: formula.molar_mass() - reference.molar_mass(),
+        }
+    }
+
+    /// Returns the neutral difference made of the provided element count
+    /// differences, which must be non-zero and in Hill order.
+    fn from_element_counts(elements: Vec<(Element, i64)>) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let (isotopologue_mass, molar_mass) = elements.iter().fold(
+            (0.0, 0.0),
+            |(isotopologue_mass, molar_mass), (element, count)| {
+                let count = *count as f64;
+                (
+                    isotopologue_mass + count * element.relative_atomic_mass(),
+                    molar_mass + count * element.standard_atomic_weight(),
+                )
+            },
+        );
+        Self { elements, charge: 0.0, isotopologue_mass, molar_mass }
+    }
+
+    /// Returns the elemental compositions whose isotopologue mass matches
+    /// the provided mass shift within the provided tolerance, as candidate
+    /// explanations of the shift observed between a modified and an
+    /// unmodified species.
+    ///
+    /// The candidates are the small neutral differences gaining or losing
+    /// at most 10 to 15 carbons, 4 to 6 nitrogens, 8 to 10 oxygens, two
+    /// sulfurs, one to two phosphorus atoms and 20 to 30 hydrogens, such as
+    /// `+CH2`, `+O` or `-H2O`. Hydrogen is solved for each combination of
+    /// the other elements rather than enumerated, and the differences which
+    /// would change the parity of the number of electrons, i.e. whose total
+    /// count of hydrogen, nitrogen and phosphorus atoms is odd, are skipped.
+    /// Relative tolerances are taken relative to the shift. The candidates
+    /// are sorted by increasing mass error, then by increasing number of
+    /// gained or lost atoms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let methylation = FormulaDiff::delta_mass_to_candidates(14.01565, MassTolerance::Dalton(1e-4));
+    /// assert_eq!(methylation[0].to_string(), "C H(2)");
+    ///
+    /// let dehydration =
+    ///     FormulaDiff::delta_mass_to_candidates(-18.010565, MassTolerance::Dalton(1e-4));
+    /// assert_eq!(dehydration[0].to_string(), "H(-2) O(-1)");
+    ///
+    /// let phosphorylation =
+    ///     FormulaDiff::delta_mass_to_candidates(79.966331, MassTolerance::Dalton(1e-4));
+    /// assert_eq!(phosphorylation[0].to_string(), "H O(3) P");
+    /// ```
+    #[must_use]
+    pub fn delta_mass_to_candidates(delta: f64, tolerance: MassTolerance) -> Vec<Self> {
+        let (low, high) = tolerance.window(delta);
+        let hydrogen_mass = Element::H.relative_atomic_mass();
+        let mut candidates: Vec<(f64, i64, Self)> = Vec::new();
+        let mut counts: Vec<i64> = SHIFT_ELEMENTS.iter().map(|&(_, min, _)| min).collect();
+        loop {
+            #[allow(clippy::cast_precision_loss)]
+            let heavy_mass: f64 = SHIFT_ELEMENTS
+                .iter()
+                .zip(&counts)
+                .map(|((element, _, _), count)| *count as f64 * element.relative_atomic_mass())
+                .sum();
+            #[allow(clippy::cast_possible_truncation)]
+            let (min_hydrogens, max_hydrogens) = (
+                (((low - heavy_mass) / hydrogen_mass).ceil() as i64).max(SHIFT_HYDROGENS.0),
+                (((high - heavy_mass) / hydrogen_mass).floor() as i64).min(SHIFT_HYDROGENS.1),
+            );
+            for hydrogens in min_hydrogens..=max_hydrogens {
+                let odd_valences = hydrogens + counts[1] + counts[4];
+                if odd_valences % 2 != 0 {
+                    continue;
+                }
+                let mut elements: Vec<(Element, i64)> = SHIFT_ELEMENTS
+                    .iter()
+                    .zip(&counts)
+                    .map(|((element, _, _), count)| (*element, *count))
+                    .chain(core::iter::once((Element::H, hydrogens)))
+                    .filter(|(_, count)| *count != 0)
+                    .collect();
+                if elements.is_empty() {
+                    continue;
+                }
+                let mut order: Vec<Element> =
+                    elements.iter().map(|(element, _)| *element).collect();
+                hill_sort(&mut order);
+                elements.sort_by_key(|(element, _)| order.iter().position(|e| e == element));
+                let atoms = elements.iter().map(|(_, count)| count.abs()).sum();
+                let candidate = Self::from_element_counts(elements);
+                if (low..=high).contains(&candidate.isotopologue_mass) {
+                    candidates.push((
+                        (candidate.isotopologue_mass - delta).abs(),
+                        atoms,
+                        candidate,
+                    ));
+                }
+            }
+
+            let Some(index) =
+                SHIFT_ELEMENTS.iter().zip(&counts).rposition(|((_, _, max), count)| count < max)
+            else {
+                break;
+            };
+            counts[index] += 1;
+            for (count, (_, min, _)) in counts.iter_mut().zip(&SHIFT_ELEMENTS).skip(index + 1) {
+                *count = *min;
+            }
+        }
+        candidates.sort_by(|(left_error, left_atoms, _), (right_error, right_atoms, _)| {
+            left_error.total_cmp(right_error).then(left_atoms.cmp(right_atoms))
+        });
+        candidates.into_iter().map(|(_, _, candidate)| candidate).collect()
+    }
+
+    /// Iterates over the elements whose counts differ, in Hill order, with
+    /// the difference of their counts.
+    pub fn elements(&self) -> impl Iterator<Item = (Element, i64)> + '_ {
+        self.elements.iter().copied()
+    }
+
+    /// Returns the difference of the counts of the provided element.
+    #[must_use]
+    pub fn count_of_element(&self, element: Element) -> i64 {
+        self.elements.iter().find(|(e, _)| *e == element).map_or(0, |(_, count)| *count)
+    }
+
+    /// Returns the difference of the charges.
+    #[must_use]
+    pub fn charge(&self) -> f64 {
+        self.charge
+    }
+
+    /// Returns the difference of the isotopologue masses, without
+    /// considering the charges.
+    #[must_use]
+    pub fn isotopologue_mass(&self) -> f64 {
+        self.isotopologue_mass
+    }
+
+    /// Returns the difference of the molar masses.
+    #[must_use]
+    pub fn molar_mass(&self) -> f64 {
+        self.molar_mass
+    }
+
+    /// Returns whether the two formulas have the same elemental composition
+    /// and charge.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty() && self.charge.abs() < f64::EPSILON
+    }
+}
+
+/// Writes the provided element count differences in the notation of the
+/// Unimod database, such as `H(-2) C(2) O`.
+pub(crate) fn fmt_composition_delta(
+    elements: impl Iterator<Item = (Element, i64)>,
+    f: &mut core::fmt::Formatter<'_>,
+) -> core::fmt::Result {
+    for (i, (element, count)) in elements.enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        if count == 1 {
+            write!(f, "{element}")?;
+        } else {
+            write!(f, "{element}({count})")?;
+        }
+    }
+    Ok(())
+}
+
+impl Display for FormulaDiff {
+    /// Writes the element differences in the notation of the Unimod
+    /// database, such as `H(-2) C(2) O`, followed by the charge difference
+    /// if any.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_composition_delta(self.elements(), f)?;
+        if self.charge.abs() >= f64::EPSILON {
+            if !self.elements.is_empty() {
+                write!(f, " ")?;
+            }
+            write!(f, "charge({})", self.charge)?;
+        }
+        Ok(())
+    }
+}
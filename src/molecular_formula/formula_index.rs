@@ -0,0 +1,220 @@
+//! Submodule providing a collection of molecular formulas sorted by mass,
+//! which can be queried for the formulas within a mass window in
+//! logarithmic time, as needed by annotation engines.
+
+use alloc::vec::Vec;
+
+use crate::{MassSortKey, MolecularFormula};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Tolerance of a mass query, defining a window centered on the target mass.
+pub enum MassTolerance {
+    /// Absolute tolerance, in daltons.
+    Dalton(f64),
+    /// Relative tolerance, in parts per million of the target mass.
+    Ppm(f64),
+}
+
+impl MassTolerance {
+    /// Returns the inclusive bounds of the mass window centered on the
+    /// provided target mass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let (low, high) = MassTolerance::Ppm(5.0).window(200.0);
+    /// assert!((low - 199.999).abs() < 1e-9);
+    /// assert!((high - 200.001).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn window(&self, target: f64) -> (f64, f64) {
+        let delta = match self {
+            Self::Dalton(delta) => *delta,
+            Self::Ppm(ppm) => target.abs() * ppm / 1e6,
+        };
+        (target - delta, target + delta)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Collection of molecular formulas sorted by their precomputed
+/// isotopologue mass, without considering any charge.
+///
+/// Formulas with the same mass are sorted as by [`ByMass`](crate::ByMass),
+/// so that the order of the collection does not depend on the insertion
+/// order.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let index: FormulaIndex<ChemicalFormula<u32, i32>> =
+///     ["C6H12O6", "C9H11NO3", "C8H10N4O2", "C7H6O2"]
+///         .into_iter()
+///         .map(|formula| ChemicalFormula::from_str(formula).unwrap())
+///         .collect();
+///
+/// let matches: Vec<String> = index
+///     .query_mass(180.0634, MassTolerance::Ppm(5.0))
+///     .map(|(_, formula)| formula.to_string())
+///     .collect();
+/// assert_eq!(matches, ["C₆H₁₂O₆"]);
+///
+/// let matches = index.query_mass(181.0, MassTolerance::Dalton(1.0));
+/// assert_eq!(matches.len(), 2);
+/// ```
+pub struct FormulaIndex<M> {
+    /// The formulas with their mass, sorted by mass.
+    entries: Vec<(f64, M)>,
+}
+
+impl<M> Default for FormulaIndex<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> FormulaIndex<M> {
+    /// Creates an empty index.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Returns the number of formulas in the index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the index contains no formula.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the formulas of the index with their mass, by
+    /// increasing mass.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (f64, &M)> + '_ {
+        self.entries.iter().map(|(mass, formula)| (*mass, formula))
+    }
+
+    /// Iterates over the formulas whose mass is within the inclusive window
+    /// of the provided tolerance around the target mass, with their mass, by
+    /// increasing mass.
+    ///
+    /// The window is found by binary search, in logarithmic time. Relative
+    /// tolerances are computed from the target mass, not from the mass of
+    /// the candidates.
+    #[must_use]
+    pub fn query_mass(
+        &self,
+        target: f64,
+        tolerance: MassTolerance,
+    ) -> impl ExactSizeIterator<Item = (f64, &M)> + '_ {
+        let (low, high) = tolerance.window(target);
+        let start = self.entries.partition_point(|(mass, _)| *mass < low);
+        let end = self.entries.partition_point(|(mass, _)| *mass <= high).max(start);
+        self.entries[start..end].iter().map(|(mass, formula)| (*mass, formula))
+    }
+
+    /// Returns the formula whose mass is closest to the target mass, with
+    /// its mass, if the index is not empty.
+    #[must_use]
+    pub fn nearest(&self, target: f64) -> Option<(f64, &M)> {
+        let index = self.entries.partition_point(|(mass, _)| *mass < target);
+        let before = index.checked_sub(1).and_then(|index| self.entries.get(index));
+        let after = self.entries.get(index);
+        match (before, after) {
+            (Some(before), Some(after)) if target - before.0 <= after.0 - target => Some(before),
+            (_, Some(after)) => Some(after),
+            (before, None) => before,
+        }
+        .map(|(mass, formula)| (*mass, formula))
+    }
+}
+
+impl<M: MolecularFormula> FormulaIndex<M> {
+    /// Inserts the provided formula into the index, in linear time.
+    ///
+    /// To insert many formulas at once, prefer `extend` or `collect`,
+    /// which sort the index only once.
+    pub fn insert(&mut self, formula: M) {
+        let mass = formula.isotopologue_mass();
+        let position = self.entries.partition_point(|(other_mass, other)| {
+            other_mass.total_cmp(&mass).then_with(|| other.cmp_by_mass(&formula)).is_le()
+        });
+        self.entries.insert(position, (mass, formula));
+    }
+
+    /// Sorts the entries of the index by mass.
+    fn sort(&mut self) {
+        self.entries.sort_by_cached_key(|(_, formula)| MassSortKey::new(formula));
+        self.entries.sort_by(|(left_mass, _), (right_mass, _)| left_mass.total_cmp(right_mass));
+    }
+}
+
+impl<M: MolecularFormula> Extend<M> for FormulaIndex<M> {
+    fn extend<I: IntoIterator<Item = M>>(&mut self, formulas: I) {
+        self.entries
+            .extend(formulas.into_iter().map(|formula| (formula.isotopologue_mass(), formula)));
+        self.sort();
+    }
+}
+
+impl<M: MolecularFormula> FromIterator<M> for FormulaIndex<M> {
+    fn from_iter<I: IntoIterator<Item = M>>(formulas: I) -> Self {
+        let mut index = Self::new();
+        index.extend(formulas);
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::str::FromStr;
+
+    use super::*;
+    use crate::ChemicalFormula;
+
+    fn index(formulas: &[&str]) -> FormulaIndex<ChemicalFormula<u32, i32>> {
+        formulas.iter().map(|formula| ChemicalFormula::from_str(formula).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_insert_matches_collect() {
+        let formulas = ["C2H6O", "CH4", "CH3CH2OH", "H2O", "CO", "N2", "C2H4"];
+        let collected = index(&formulas);
+        let mut inserted = FormulaIndex::new();
+        for formula in formulas.iter().rev() {
+            inserted.insert(ChemicalFormula::<u32, i32>::from_str(formula).unwrap());
+        }
+        assert!(collected.iter().map(|(_, f)| f).eq(inserted.iter().map(|(_, f)| f)));
+        assert!(collected.iter().zip(collected.iter().skip(1)).all(|(a, b)| a.0 <= b.0));
+    }
+
+    #[test]
+    fn test_window_bounds_are_inclusive() {
+        let index = index(&["H2O", "CH4", "NH3"]);
+        let water = index.iter().find(|(_, f)| f.to_string() == "H₂O").unwrap().0;
+        assert_eq!(index.query_mass(water, MassTolerance::Dalton(0.0)).len(), 1);
+        assert_eq!(index.query_mass(water, MassTolerance::Dalton(-1.0)).len(), 0);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let index = index(&["H2O", "CH4", "NH3"]);
+        assert_eq!(index.nearest(0.0).unwrap().1.to_string(), "CH₄");
+        assert_eq!(index.nearest(17.2).unwrap().1.to_string(), "NH₃");
+        assert_eq!(index.nearest(17.8).unwrap().1.to_string(), "H₂O");
+        assert_eq!(index.nearest(1000.0).unwrap().1.to_string(), "H₂O");
+        assert!(FormulaIndex::<ChemicalFormula>::new().nearest(1.0).is_none());
+    }
+}
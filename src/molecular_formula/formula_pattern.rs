@@ -0,0 +1,201 @@
+//! Submodule providing a compact pattern language constraining the element
+//! counts of formulas, as used to filter large lists of candidates.
+
+use alloc::string::{String, ToString};
+use core::{fmt::Display, str::FromStr};
+
+use elements_rs::Element;
+
+use crate::{ElementBounds, MolecularFormula, errors::FormulaPatternError};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+/// Pattern constraining the element counts of a formula, such as
+/// `C6-10 H* O2-4 !N`.
+///
+/// A pattern is made of whitespace-separated terms, each made of an element
+/// symbol followed by its allowed counts:
+///
+/// * `C` allows exactly one atom, and `C6` exactly six atoms.
+/// * `C6-10` allows between six and ten atoms, inclusive.
+/// * `C6+` allows six atoms or more.
+/// * `C*` allows any number of atoms, including none.
+/// * `!N` forbids the element.
+///
+/// Elements which do not appear in the pattern are allowed in any number,
+/// and isotopes are counted as their element.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let pattern = FormulaPattern::from_str("C6-10 H* O2-4 !N").unwrap();
+/// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+/// let toluene = ChemicalFormula::<u32, i32>::from_str("C7H8").unwrap();
+/// let vanillin = ChemicalFormula::<u32, i32>::from_str("C8H8O3").unwrap();
+/// let tyrosine = ChemicalFormula::<u32, i32>::from_str("C9H11NO3").unwrap();
+/// assert!(!pattern.matches(&glucose));
+/// assert!(!pattern.matches(&toluene));
+/// assert!(pattern.matches(&vanillin));
+/// assert!(!pattern.matches(&tyrosine));
+/// ```
+pub struct FormulaPattern {
+    /// The inclusive range of counts of the constrained elements.
+    bounds: ElementBounds<u64>,
+}
+
+impl FormulaPattern {
+    /// Returns the element constraints of the pattern.
+    #[must_use]
+    pub fn bounds(&self) -> &ElementBounds<u64> {
+        &self.bounds
+    }
+
+    /// Returns whether the provided formula satisfies every term of the
+    /// pattern.
+    pub fn matches<M: MolecularFormula>(&self, formula: &M) -> bool
+    where
+        u64: From<M::Count>,
+    {
+        self.bounds.iter().all(|(element, min, max)| {
+            formula
+                .count_of_element::<u64>(element)
+                .is_some_and(|count| min <= count && count <= max)
+        })
+    }
+}
+
+/// Parses the counts of a term, following its element symbol, into an
+/// inclusive range.
+fn parse_counts(counts: &str) -> Option<(u64, u64)> {
+    if counts.is_empty() {
+        return Some((1, 1));
+    }
+    if counts == "*" {
+        return Some((0, u64::MAX));
+    }
+    if let Some(min) = counts.strip_suffix('+') {
+        return Some((parse_count(min)?, u64::MAX));
+    }
+    if let Some((min, max)) = counts.split_once('-') {
+        return Some((parse_count(min)?, parse_count(max)?));
+    }
+    let count = parse_count(counts)?;
+    Some((count, count))
+}
+
+/// Parses a count made only of ASCII digits.
+fn parse_count(count: &str) -> Option<u64> {
+    if count.is_empty() || !count.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    count.parse().ok()
+}
+
+impl FromStr for FormulaPattern {
+    type Err = FormulaPatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bounds = ElementBounds::new();
+        for term in s.split_whitespace() {
+            let (forbidden, rest) = match term.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, term),
+            };
+            let symbol_length = rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !c.is_ascii_lowercase())
+                .map_or(rest.len(), |(index, _)| index);
+            let (symbol, counts) = rest.split_at(symbol_length);
+            let element = Element::from_str(symbol)
+                .map_err(|_| FormulaPatternError::UnknownElement(term.to_string()))?;
+            if bounds.bounds(element).is_some() {
+                return Err(FormulaPatternError::DuplicateElement(element));
+            }
+            let (min, max) = if forbidden {
+                if !counts.is_empty() {
+                    return Err(FormulaPatternError::MalformedCount(term.to_string()));
+                }
+                (0, 0)
+            } else {
+                parse_counts(counts)
+                    .ok_or_else(|| FormulaPatternError::MalformedCount(term.to_string()))?
+            };
+            if min > max {
+                return Err(FormulaPatternError::EmptyRange(term.to_string()));
+            }
+            bounds = bounds.with_bounds(element, min, max);
+        }
+        Ok(Self { bounds })
+    }
+}
+
+impl TryFrom<&str> for FormulaPattern {
+    type Error = FormulaPatternError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl TryFrom<String> for FormulaPattern {
+    type Error = FormulaPatternError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+impl Display for FormulaPattern {
+    /// Writes the terms of the pattern in their canonical notation.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, (element, min, max)) in self.bounds.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match (min, max) {
+                (0, 0) => write!(f, "!{element}")?,
+                (0, u64::MAX) => write!(f, "{element}*")?,
+                (1, 1) => write!(f, "{element}")?,
+                (min, u64::MAX) => write!(f, "{element}{min}+")?,
+                (min, max) if min == max => write!(f, "{element}{min}")?,
+                (min, max) => write!(f, "{element}{min}-{max}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_roundtrip() {
+        for pattern in ["C6-10 H* O2-4 !N", "C Cl2 Na1+", "", "Br0-3 S"] {
+            let parsed = FormulaPattern::from_str(pattern).unwrap();
+            assert_eq!(parsed.to_string(), pattern);
+        }
+        assert_eq!(FormulaPattern::from_str("  C1  N0 ").unwrap().to_string(), "C !N");
+    }
+
+    #[test]
+    fn test_errors() {
+        for (pattern, expected) in [
+            ("Xy2", FormulaPatternError::UnknownElement("Xy2".to_string())),
+            ("6C", FormulaPatternError::UnknownElement("6C".to_string())),
+            ("c6", FormulaPatternError::UnknownElement("c6".to_string())),
+            ("C6-", FormulaPatternError::MalformedCount("C6-".to_string())),
+            ("C-6", FormulaPatternError::MalformedCount("C-6".to_string())),
+            ("C**", FormulaPatternError::MalformedCount("C**".to_string())),
+            ("!N2", FormulaPatternError::MalformedCount("!N2".to_string())),
+            ("C10-6", FormulaPatternError::EmptyRange("C10-6".to_string())),
+            ("C6 H* !C", FormulaPatternError::DuplicateElement(Element::C)),
+        ] {
+            assert_eq!(FormulaPattern::from_str(pattern), Err(expected), "{pattern}");
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Submodule providing the A+2 isotope signature of chlorine, bromine and
+//! sulfur, as used in screening workflows to detect these elements from the
+//! isotope pattern of a mass spectrum.
+
+use elements_rs::{Element, Isotope, MassNumber};
+
+use crate::{LabeledFormula, MassContext, MolecularFormula};
+
+/// Elements whose heavy isotope two mass units above their lightest one
+/// leaves a distinctive A+2 peak.
+const SIGNATURE_ELEMENTS: [Element; 3] = [Element::Cl, Element::Br, Element::S];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Expected A+2 and A+4 isotope peaks of a formula, relative to its A peak,
+/// alongside the contributions of its chlorine, bromine and sulfur atoms.
+///
+/// Each chlorine atom adds about 32% of the A peak to the A+2 peak, each
+/// bromine atom about 97% and each sulfur atom about 4.5%, far more than
+/// the fraction of a percent added by each carbon or oxygen atom. These
+/// ratios are a quick diagnostic of the presence of these elements, which
+/// does not require the full isotope pattern.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let dichlorobenzene = ChemicalFormula::<u32, i32>::from_str("C6H4Cl2").unwrap();
+/// let signature = dichlorobenzene.halogen_signature().unwrap();
+/// assert_eq!(signature.number_of_atoms(Element::Cl), 2);
+/// assert!((signature.contribution(Element::Cl) - 0.64).abs() < 0.01);
+/// // The A+2 peak also accounts for the other elements, such as carbon.
+/// assert!(signature.a_plus_2() > signature.contribution(Element::Cl));
+/// assert!((signature.a_plus_2() - 0.64).abs() < 0.01);
+/// assert!((signature.a_plus_4() - 0.10).abs() < 0.01);
+/// ```
+pub struct HalogenSignature {
+    /// The number of atoms of chlorine, bromine and sulfur with natural
+    /// isotopic composition.
+    atoms: [u64; 3],
+    /// The contributions of chlorine, bromine and sulfur to the A+2 peak,
+    /// relative to the A peak.
+    contributions: [f64; 3],
+    /// The A+2 peak relative to the A peak.
+    a_plus_2: f64,
+    /// The A+4 peak relative to the A peak.
+    a_plus_4: f64,
+}
+
+impl HalogenSignature {
+    /// Returns the signature of the provided formula, or `None` if the count
+    /// of any of its elements does not fit in a `u64`.
+    pub(crate) fn new<M: MolecularFormula>(formula: &M) -> Option<Self>
+    where
+        u64: From<M::Count>,
+    {
+        let context = MassContext::new();
+        let mut atoms = [0; 3];
+        let mut contributions = [0.0; 3];
+        for (index, element) in SIGNATURE_ELEMENTS.into_iter().enumerate() {
+            let mut count: u64 = formula.count_of_element(element)?;
+            for isotope in element.isotopes() {
+                count -= formula.count_of_isotope::<u64>(*isotope)?;
+            }
+            let (lightest, _) = context
+                .isotopic_composition(element)
+                .min_by_key(|(isotope, _)| isotope.mass_number())?;
+            let heavy = Isotope::try_from((element, lightest.mass_number() + 2)).ok()?;
+            atoms[index] = count;
+            #[allow(clippy::cast_precision_loss)]
+            let atoms = count as f64;
+            contributions[index] = atoms * context.abundance(heavy) / context.abundance(lightest);
+        }
+
+        let labeled = LabeledFormula::new(formula.clone()).ok()?;
+        Some(Self {
+            atoms,
+            contributions,
+            a_plus_2: labeled.a_plus_n_abundance(2),
+            a_plus_4: labeled.a_plus_n_abundance(4),
+        })
+    }
+
+    /// Returns the number of atoms of the provided element with natural
+    /// isotopic composition, which is zero for elements other than chlorine,
+    /// bromine and sulfur.
+    #[must_use]
+    pub fn number_of_atoms(&self, element: Element) -> u64 {
+        SIGNATURE_ELEMENTS.iter().position(|e| *e == element).map_or(0, |index| self.atoms[index])
+    }
+
+    /// Returns the contribution of the atoms of the provided element to the
+    /// A+2 peak, relative to the A peak, which is zero for elements other
+    /// than chlorine, bromine and sulfur.
+    #[must_use]
+    pub fn contribution(&self, element: Element) -> f64 {
+        SIGNATURE_ELEMENTS
+            .iter()
+            .position(|e| *e == element)
+            .map_or(0.0, |index| self.contributions[index])
+    }
+
+    /// Returns whether the formula contains any chlorine, bromine or sulfur
+    /// atom with natural isotopic composition.
+    #[must_use]
+    pub fn is_present(&self) -> bool {
+        self.atoms.iter().any(|atoms| *atoms > 0)
+    }
+
+    /// Returns the expected intensity of the A+2 peak, relative to the A
+    /// peak, accounting for all the elements of the formula.
+    #[must_use]
+    pub fn a_plus_2(&self) -> f64 {
+        self.a_plus_2
+    }
+
+    /// Returns the expected intensity of the A+4 peak, relative to the A
+    /// peak, accounting for all the elements of the formula.
+    #[must_use]
+    pub fn a_plus_4(&self) -> f64 {
+        self.a_plus_4
+    }
+}
@@ -0,0 +1,157 @@
+//! Submodule providing the generation of homologous series, i.e. formulas
+//! differing by a repeated unit such as `CH2`, as screened in lipidomics and
+//! for surfactants.
+
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::{
+    ChargeLike, ChemicalFormula, CountLike, MolecularFormula,
+    errors::{CompositionError, NumericError, ParserError},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Homologous series of formulas, obtained by adding a repeat unit such as
+/// `CH2` any number of times to a base formula.
+///
+/// The members are the [total composition](ChemicalFormula::total_composition)
+/// of the base formula and of the repeated unit, so they are written in Hill
+/// order as a single mixture, and carry the net charge of the base formula.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let acetic_acid = ChemicalFormula::<u32, i32>::from_str("C2H4O2").unwrap();
+/// let methylene = ChemicalFormula::<u32, i32>::from_str("CH2").unwrap();
+/// let fatty_acids = HomologousSeries::new(acetic_acid, methylene).unwrap();
+///
+/// let palmitic_acid = fatty_acids.member(14).unwrap();
+/// assert_eq!(palmitic_acid.to_string(), "C₁₆H₃₂O₂");
+///
+/// let members = fatty_acids.members(12..=14).unwrap();
+/// let formulas: Vec<String> = members.iter().map(|member| member.formula().to_string()).collect();
+/// assert_eq!(formulas, ["C₁₄H₂₈O₂", "C₁₅H₃₀O₂", "C₁₆H₃₂O₂"]);
+/// assert!((members[2].isotopologue_mass() - 256.240_230).abs() < 1e-6);
+/// ```
+pub struct HomologousSeries<Count: CountLike, Charge: ChargeLike> {
+    /// The formula of the first member of the series.
+    base: ChemicalFormula<Count, Charge>,
+    /// The neutral unit repeated along the series.
+    repeat_unit: ChemicalFormula<Count, Charge>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Member of a [`HomologousSeries`], with the number of repeat units added
+/// to the base formula and its isotopologue mass.
+pub struct Homolog<Count: CountLike, Charge: ChargeLike> {
+    /// The number of repeat units added to the base formula.
+    repeats: u64,
+    /// The formula of the member.
+    formula: ChemicalFormula<Count, Charge>,
+    /// The isotopologue mass of the member, without considering its charge.
+    isotopologue_mass: f64,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Homolog<Count, Charge> {
+    /// Returns the number of repeat units added to the base formula.
+    #[must_use]
+    pub fn repeats(&self) -> u64 {
+        self.repeats
+    }
+
+    /// Returns the formula of the member.
+    #[must_use]
+    pub fn formula(&self) -> &ChemicalFormula<Count, Charge> {
+        &self.formula
+    }
+
+    /// Returns the isotopologue mass of the member, without considering its
+    /// charge.
+    #[must_use]
+    pub fn isotopologue_mass(&self) -> f64 {
+        self.isotopologue_mass
+    }
+
+    /// Consumes the member and returns its formula.
+    #[must_use]
+    pub fn into_formula(self) -> ChemicalFormula<Count, Charge> {
+        self.formula
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> HomologousSeries<Count, Charge> {
+    /// Creates the homologous series starting from the provided base formula
+    /// and growing by the provided repeat unit.
+    ///
+    /// # Errors
+    ///
+    /// * If the repeat unit is charged.
+    /// * If the net charge of the repeat unit overflows.
+    pub fn new(
+        base: ChemicalFormula<Count, Charge>,
+        repeat_unit: ChemicalFormula<Count, Charge>,
+    ) -> Result<Self, CompositionError> {
+        if !repeat_unit.net_charge()?.is_zero() {
+            return Err(CompositionError::ChargedRepeatUnit);
+        }
+        Ok(Self { base, repeat_unit })
+    }
+
+    /// Returns the formula of the first member of the series.
+    #[must_use]
+    pub fn base(&self) -> &ChemicalFormula<Count, Charge> {
+        &self.base
+    }
+
+    /// Returns the unit repeated along the series.
+    #[must_use]
+    pub fn repeat_unit(&self) -> &ChemicalFormula<Count, Charge> {
+        &self.repeat_unit
+    }
+
+    /// Returns the formula obtained by adding the provided number of repeat
+    /// units to the base formula.
+    ///
+    /// # Errors
+    ///
+    /// * If any element count or the charge overflows.
+    pub fn member(&self, repeats: u64) -> Result<ChemicalFormula<Count, Charge>, ParserError>
+    where
+        Charge: TryFrom<Count>,
+    {
+        let overflow = ParserError::from(NumericError::PositiveOverflow);
+        let repeats = Count::try_from(repeats).map_err(|_| overflow)?;
+        let added = if repeats.is_zero() {
+            ChemicalFormula::empty()
+        } else {
+            self.repeat_unit.checked_mul(repeats).ok_or(overflow)?
+        };
+        ChemicalFormula::total_composition([&self.base, &added]).ok_or(overflow)
+    }
+
+    /// Returns the members of the series obtained by adding each of the
+    /// provided numbers of repeat units to the base formula, in order.
+    ///
+    /// # Errors
+    ///
+    /// * If any element count or the charge of a member overflows.
+    pub fn members(
+        &self,
+        repeats: RangeInclusive<u64>,
+    ) -> Result<Vec<Homolog<Count, Charge>>, ParserError>
+    where
+        Charge: TryFrom<Count>,
+    {
+        repeats
+            .map(|repeats| {
+                let formula = self.member(repeats)?;
+                let isotopologue_mass = formula.isotopologue_mass();
+                Ok(Homolog { repeats, formula, isotopologue_mass })
+            })
+            .collect()
+    }
+}
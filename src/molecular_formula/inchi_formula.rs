@@ -3,13 +3,14 @@
 //! crate.
 
 use alloc::vec::Vec;
-use core::fmt::Display;
+use core::{cmp::Ordering, fmt::Display};
 
 use elements_rs::Element;
 
 use crate::{
-    CountLike, InChITree, MolecularFormula, MolecularFormulaMetadata, ParsableFormula,
-    prelude::SequenceNode,
+    CountLike, DeepSizeOf, DisplayStyle, InChITree, MolecularFormula, MolecularFormulaMetadata,
+    MolecularTree, ParsableFormula, StyledDisplay, convert_count, errors::NumericError,
+    molecular_tree::mixtures_size_of, prelude::SequenceNode,
 };
 
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
@@ -26,6 +27,11 @@ use crate::{
 /// let formula = InChIFormula::<u32>::from_str("C2H6O").unwrap();
 /// assert_eq!(formula.to_string(), "C2H6O");
 /// ```
+///
+/// The formula is displayed with its components in the order in which they
+/// were parsed: the [`canonical`](InChIFormula::canonical) formula is
+/// displayed byte for byte as the formula layer of the InChI of the same
+/// species.
 pub struct InChIFormula<Count: CountLike = u16> {
     mixtures: Vec<(Count, SequenceNode<InChITree<Count>>)>,
 }
@@ -53,6 +59,160 @@ impl<Count: CountLike> InChIFormula<Count> {
     }
 }
 
+impl<Count: CountLike> InChIFormula<Count> {
+    /// Returns the formula with its components in the order of the formula
+    /// layer of InChI, identical components being merged and their counts
+    /// summed, or `None` if any of the merged counts overflows.
+    ///
+    /// As in the InChI technical manual, the components are compared by
+    /// their Hill formulas without hydrogen, element by element: carbon comes
+    /// before any other element and the other elements are sorted
+    /// alphabetically, the larger count coming first for the same element,
+    /// and the formula with more elements coming first when one is the start
+    /// of the other. The components with the same heavy atoms are then
+    /// sorted by decreasing number of hydrogens. Within each component, the
+    /// elements are in Hill order, hydrogen being written alphabetically
+    /// when there is no carbon.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = InChIFormula::<u32>::from_str("5H2O.H2O4S.Cu").unwrap();
+    /// assert_eq!(formula.canonical().unwrap().to_string(), "Cu.H2O4S.5H2O");
+    ///
+    /// let formula = InChIFormula::<u32>::from_str("ClH.Ca.ClH").unwrap();
+    /// assert_eq!(formula.canonical().unwrap().to_string(), "Ca.2ClH");
+    /// ```
+    #[must_use]
+    pub fn canonical(&self) -> Option<Self> {
+        let mut components = Vec::with_capacity(self.mixtures.len());
+        for (count, tree) in &self.mixtures {
+            components.push((component_key(tree)?, *count, tree));
+        }
+        components.sort_by(|(left, ..), (right, ..)| compare_components(left, right));
+
+        let mut mixtures: Vec<(Count, SequenceNode<InChITree<Count>>)> =
+            Vec::with_capacity(components.len());
+        for (_, count, tree) in components {
+            match mixtures.last_mut() {
+                Some((existing, last)) if last == tree => {
+                    *existing = existing.checked_add(&count)?;
+                }
+                _ => mixtures.push((count, tree.clone())),
+            }
+        }
+        Some(Self { mixtures })
+    }
+
+    /// Returns whether the components of the formula are in the order of
+    /// the formula layer of InChI, with identical components merged, as
+    /// described in [`InChIFormula::canonical`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert!(InChIFormula::<u32>::from_str("C2H4O2.Na").unwrap().is_canonical());
+    /// assert!(!InChIFormula::<u32>::from_str("Na.C2H4O2").unwrap().is_canonical());
+    /// assert!(!InChIFormula::<u32>::from_str("H2O.H2O").unwrap().is_canonical());
+    /// ```
+    #[must_use]
+    pub fn is_canonical(&self) -> bool {
+        self.canonical().is_some_and(|canonical| canonical == *self)
+    }
+
+    /// Consumes the formula and returns an equivalent formula using the
+    /// provided count type.
+    ///
+    /// # Errors
+    ///
+    /// * If any count cannot be represented by the new type.
+    pub(crate) fn try_convert<NewCount: CountLike>(
+        self,
+    ) -> Result<InChIFormula<NewCount>, NumericError> {
+        Ok(InChIFormula {
+            mixtures: self
+                .mixtures
+                .into_iter()
+                .map(|(count, sequence)| {
+                    Ok((convert_count(count)?, sequence.try_map(InChITree::try_convert)?))
+                })
+                .collect::<Result<_, NumericError>>()?,
+        })
+    }
+
+    /// Creates a new InChI formula from the provided counted components,
+    /// which are expected to be Hill sorted.
+    ///
+    /// # Panics
+    ///
+    /// * If no component is provided.
+    pub(crate) fn from_components(mixtures: Vec<(Count, SequenceNode<InChITree<Count>>)>) -> Self {
+        assert!(!mixtures.is_empty(), "At least one mixture is required");
+        Self { mixtures }
+    }
+}
+
+/// The key by which the components of the formula layer of InChI are
+/// sorted: the counts of the heavy atoms in Hill order, and the number of
+/// hydrogens.
+type ComponentKey<Count> = (Vec<(Element, Count)>, Count);
+
+/// Returns the key by which the provided component is sorted, or `None` if
+/// any of its counts overflows.
+fn component_key<Count: CountLike>(
+    tree: &SequenceNode<InChITree<Count>>,
+) -> Option<ComponentKey<Count>> {
+    let mut heavy_atoms: Vec<(Element, Count)> = Vec::new();
+    let mut hydrogens = Count::zero();
+    for element in tree.elements() {
+        if element == Element::H {
+            hydrogens = tree.count_of_element::<Count>(element)?;
+        } else if heavy_atoms.iter().all(|(other, _)| *other != element) {
+            heavy_atoms.push((element, tree.count_of_element::<Count>(element)?));
+        }
+    }
+    Some((heavy_atoms, hydrogens))
+}
+
+/// Compares two elements of Hill formulas without hydrogen, carbon coming
+/// before any other element and the others being sorted alphabetically.
+fn compare_elements(left: Element, right: Element) -> Ordering {
+    match (left, right) {
+        _ if left == right => Ordering::Equal,
+        (Element::C, _) => Ordering::Less,
+        (_, Element::C) => Ordering::Greater,
+        _ => {
+            let left: &str = left.as_ref();
+            let right: &str = right.as_ref();
+            left.cmp(right)
+        }
+    }
+}
+
+/// Compares the keys of two components of the formula layer of InChI.
+fn compare_components<Count: CountLike>(
+    (left, left_hydrogens): &ComponentKey<Count>,
+    (right, right_hydrogens): &ComponentKey<Count>,
+) -> Ordering {
+    left.iter()
+        .zip(right)
+        .map(|((left, left_count), (right, right_count))| {
+            compare_elements(*left, *right)
+                .then_with(|| right_count.partial_cmp(left_count).unwrap_or(Ordering::Equal))
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| right.len().cmp(&left.len()))
+        .then_with(|| right_hydrogens.partial_cmp(left_hydrogens).unwrap_or(Ordering::Equal))
+}
+
 impl<Count: CountLike> From<SequenceNode<InChITree<Count>>> for InChIFormula<Count> {
     fn from(tree: SequenceNode<InChITree<Count>>) -> Self {
         Self { mixtures: alloc::vec![(Count::one(), tree)] }
@@ -93,9 +253,10 @@ impl<Count: CountLike> ParsableFormula for InChIFormula<Count> {
 
     fn on_start<J>(
         _chars: &mut core::iter::Peekable<J>,
+        _options: &crate::ParserOptions,
     ) -> Result<Self::StartOutput, crate::errors::ParserError>
     where
-        J: Iterator<Item = char>,
+        J: Iterator<Item = char> + Clone,
     {
         Ok(())
     }
@@ -115,18 +276,24 @@ impl<Count: CountLike> ParsableFormula for InChIFormula<Count> {
     }
 }
 
+impl<Count: CountLike> StyledDisplay for InChIFormula<Count> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        style.fmt_mixtures(self.mixtures.iter().map(|(count, tree)| (*count, tree)), f)
+    }
+}
+
 impl<Count: CountLike> Display for InChIFormula<Count> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        for (i, (count, tree)) in self.mixtures.iter().enumerate() {
-            if i > 0 {
-                write!(f, ".")?;
-            }
-            if !count.is_one() {
-                write!(f, "{count}")?;
-            }
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
 
-            write!(f, "{tree}")?;
-        }
-        Ok(())
+impl<Count: CountLike> DeepSizeOf for InChIFormula<Count> {
+    fn deep_size_of_children(&self) -> usize {
+        mixtures_size_of(&self.mixtures)
     }
 }
@@ -0,0 +1,534 @@
+//! Submodule providing formulas whose explicit isotopes are only partially
+//! enriched, as is the case of labelled compounds, together with their
+//! average mass and isotope pattern.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use elements_rs::{Element, ElementVariant, Isotope, MassNumber};
+
+use crate::{
+    MassContext, MolecularFormula,
+    errors::{LabelingError, NumericError},
+    wide_count_of_element, wide_count_of_isotope,
+};
+
+/// Isotope distribution of an atom or of a group of atoms, mapping the sum of
+/// the mass numbers to the abundance and to the abundance-weighted mass.
+type Distribution = BTreeMap<u64, (f64, f64)>;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Molecular formula whose explicit isotopes denote labelled positions,
+/// each enriched in its isotope by its own fraction.
+///
+/// In a plain molecular formula, an explicit isotope such as `[13C]` denotes
+/// a position occupied by that isotope only. In a labelled compound, the
+/// position is instead occupied by the isotope with the enrichment of the
+/// reagent, such as 99%, and by the other isotopes of the element for the
+/// remaining fraction, in their natural proportions. Atoms which are not
+/// labelled have the natural isotopic composition of their element.
+///
+/// The positions labelled with an isotope are numbered from zero in the order
+/// they appear in the formula, and are fully enriched until their enrichment
+/// is set, either for all of them at once or for each of them.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+/// let glucose = ChemicalFormula::<u32, i32>::from_str("[13C]C5H12O6").unwrap();
+/// let labeled = LabeledFormula::new(glucose).unwrap().with_enrichment(carbon_13, 0.99).unwrap();
+/// assert_eq!(labeled.enrichment(carbon_13, 0), Some(0.99));
+/// assert!((labeled.average_mass() - 181.14).abs() < 0.005);
+///
+/// // The most intense peak is the one of the labelled isotopologue.
+/// let pattern = labeled.isotope_pattern(1e-4);
+/// let (mass, abundance) = pattern.iter().copied().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+/// assert!((mass - 181.066743).abs() < 1e-3);
+/// assert!(abundance > 0.9 * 0.99 && abundance < 0.99);
+/// ```
+pub struct LabeledFormula<M> {
+    /// The labelled molecular formula.
+    formula: M,
+    /// The number of unlabelled atoms of each element.
+    atoms: Vec<(Element, u64)>,
+    /// The positions labelled with each isotope.
+    labels: Vec<Label>,
+    /// The isotope masses and natural isotopic compositions.
+    context: MassContext,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Positions of a formula labelled with the same isotope.
+struct Label {
+    /// The isotope of the positions.
+    isotope: Isotope,
+    /// The number of positions labelled with the isotope.
+    count: u64,
+    /// The enrichment of the positions without an enrichment of their own.
+    enrichment: f64,
+    /// The enrichments set for individual positions, keyed by their index.
+    positions: BTreeMap<u64, f64>,
+}
+
+impl Label {
+    /// Returns the groups of positions sharing the same enrichment, as pairs
+    /// of number of positions and enrichment.
+    fn groups(&self) -> impl Iterator<Item = (u64, f64)> + '_ {
+        let shared = self.count - self.positions.len() as u64;
+        core::iter::once((shared, self.enrichment))
+            .filter(|(count, _)| *count > 0)
+            .chain(self.positions.values().map(|enrichment| (1, *enrichment)))
+    }
+}
+
+impl<M: MolecularFormula> LabeledFormula<M> {
+    /// Creates a labelled formula whose labelled positions, i.e. its explicit
+    /// isotopes, are fully enriched.
+    ///
+    /// # Errors
+    ///
+    /// * If the count of any element of the formula does not fit in a `u64`.
+    pub fn new(formula: M) -> Result<Self, NumericError> {
+        let to_u64 = |count: i128| u64::try_from(count).map_err(|_| NumericError::PositiveOverflow);
+
+        let mut atoms = Vec::new();
+        let mut labels = Vec::new();
+        for element in formula.element_mask() {
+            let mut unlabelled = to_u64(wide_count_of_element(&formula, element))?;
+            for isotope in element.isotopes() {
+                let labelled = to_u64(wide_count_of_isotope(&formula, *isotope))?;
+                if labelled > 0 {
+                    unlabelled -= labelled;
+                    labels.push(Label {
+                        isotope: *isotope,
+                        count: labelled,
+                        enrichment: 1.0,
+                        positions: BTreeMap::new(),
+                    });
+                }
+            }
+            if unlabelled > 0 {
+                atoms.push((element, unlabelled));
+            }
+        }
+
+        Ok(Self { formula, atoms, labels, context: MassContext::new() })
+    }
+}
+
+impl<M> LabeledFormula<M> {
+    /// Sets the enrichment of all the positions labelled with the provided
+    /// isotope, as the fraction of these positions occupied by the isotope.
+    ///
+    /// # Errors
+    ///
+    /// * If the enrichment is not within zero and one.
+    /// * If the formula has no position labelled with the isotope.
+    pub fn with_enrichment(
+        mut self,
+        isotope: Isotope,
+        enrichment: f64,
+    ) -> Result<Self, LabelingError> {
+        if !(0.0..=1.0).contains(&enrichment) {
+            return Err(LabelingError::InvalidEnrichment);
+        }
+        let label = self
+            .labels
+            .iter_mut()
+            .find(|label| label.isotope == isotope)
+            .ok_or(LabelingError::MissingLabel(isotope))?;
+        label.enrichment = enrichment;
+        label.positions.clear();
+        Ok(self)
+    }
+
+    /// Sets the enrichment of the position with the provided index among
+    /// those labelled with the provided isotope, as the fraction of the
+    /// molecules in which the position is occupied by the isotope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// // Ethanol labelled on both carbons, with reagents of different purity.
+    /// let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+    /// let ethanol = ChemicalFormula::<u32, i32>::from_str("[13C]H3[13C]H2OH").unwrap();
+    /// let labeled = LabeledFormula::new(ethanol)
+    ///     .unwrap()
+    ///     .with_position_enrichment(carbon_13, 0, 0.99)
+    ///     .unwrap()
+    ///     .with_position_enrichment(carbon_13, 1, 0.5)
+    ///     .unwrap();
+    /// assert_eq!(labeled.enrichment(carbon_13, 0), Some(0.99));
+    /// assert_eq!(labeled.enrichment(carbon_13, 1), Some(0.5));
+    /// assert!(labeled.with_position_enrichment(carbon_13, 2, 0.5).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * If the enrichment is not within zero and one.
+    /// * If the formula has no such position labelled with the isotope.
+    pub fn with_position_enrichment(
+        mut self,
+        isotope: Isotope,
+        position: u64,
+        enrichment: f64,
+    ) -> Result<Self, LabelingError> {
+        if !(0.0..=1.0).contains(&enrichment) {
+            return Err(LabelingError::InvalidEnrichment);
+        }
+        let label = self
+            .labels
+            .iter_mut()
+            .find(|label| label.isotope == isotope && position < label.count)
+            .ok_or(LabelingError::MissingPosition { isotope, position })?;
+        label.positions.insert(position, enrichment);
+        Ok(self)
+    }
+
+    /// Sets the isotope masses and the natural isotopic compositions used for
+    /// the average mass and the isotope pattern.
+    #[must_use]
+    pub fn with_mass_context(mut self, context: MassContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Returns the labelled molecular formula.
+    #[must_use]
+    pub fn formula(&self) -> &M {
+        &self.formula
+    }
+
+    /// Returns the enrichment of the position with the provided index among
+    /// those labelled with the provided isotope, if the formula has such a
+    /// position.
+    #[must_use]
+    pub fn enrichment(&self, isotope: Isotope, position: u64) -> Option<f64> {
+        self.labels
+            .iter()
+            .find(|label| label.isotope == isotope && position < label.count)
+            .map(|label| label.positions.get(&position).copied().unwrap_or(label.enrichment))
+    }
+
+    /// Returns the average mass of the labelled formula, without
+    /// considering any charge.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_mass(&self) -> f64 {
+        let unlabelled: f64 = self
+            .atoms
+            .iter()
+            .map(|(element, count)| *count as f64 * self.context.average_mass(*element))
+            .sum();
+        let labelled: f64 = self
+            .labels
+            .iter()
+            .flat_map(|label| label.groups().map(move |group| (label.isotope, group)))
+            .map(|(isotope, (count, enrichment))| {
+                let (abundance, mass) = self
+                    .labelled_distribution(isotope, enrichment)
+                    .into_values()
+                    .fold((0.0, 0.0), |(a, m), (abundance, mass)| (a + abundance, m + mass));
+                count as f64 * mass / abundance
+            })
+            .sum();
+        unlabelled + labelled
+    }
+
+    /// Returns the isotope pattern of the labelled formula, without
+    /// considering any charge, as pairs of mass and abundance sorted by mass.
+    ///
+    /// Isotopologues are grouped by their nominal mass, so that each peak has
+    /// the abundance-weighted mass of its isotopologues. The abundances are
+    /// fractions of the whole population of molecules: peaks whose abundance
+    /// is below the provided threshold are discarded, as are, approximately,
+    /// their contributions to the other peaks.
+    #[must_use]
+    pub fn isotope_pattern(&self, min_abundance: f64) -> Vec<(f64, f64)> {
+        self.pattern(|distribution| distribution, min_abundance, u64::MAX)
+            .into_values()
+            .map(|(abundance, mass)| (mass / abundance, abundance))
+            .collect()
+    }
+
+    /// Returns the abundance-weighted mass of the A+n peak of the labelled
+    /// formula, i.e. of its isotopologues weighing `n` nominal mass units
+    /// more than its lightest isotopologue, the A peak, or `None` if there
+    /// is no such isotopologue.
+    ///
+    /// Unlike [`isotope_pattern`](Self::isotope_pattern), only the
+    /// isotopologues up to the A+n peak are computed. For formulas made of
+    /// elements whose lightest isotope is also their most abundant one, as
+    /// most organic compounds, the A peak is the monoisotopic one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let chloromethane = ChemicalFormula::<u32, i32>::from_str("CH3Cl").unwrap();
+    /// let labeled = LabeledFormula::new(chloromethane.clone()).unwrap();
+    /// let lightest = labeled.a_plus_n_mass(0).unwrap();
+    /// assert!((lightest - chloromethane.isotopologue_mass()).abs() < 1e-9);
+    /// let chlorine_37 = labeled.a_plus_n_mass(2).unwrap();
+    /// assert!((chlorine_37 - 51.989).abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn a_plus_n_mass(&self, n: u64) -> Option<f64> {
+        self.lightest_peaks(n)
+            .get(&n)
+            .filter(|(abundance, _)| *abundance > 0.0)
+            .map(|(abundance, mass)| mass / abundance)
+    }
+
+    /// Returns the intensity of the A+n peak of the labelled formula relative
+    /// to its A peak, as defined in [`a_plus_n_mass`](Self::a_plus_n_mass).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// // The A+1 peak of an organic compound is mostly due to its carbon-13,
+    /// // contributing about 1.1% of the A peak per carbon atom.
+    /// let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    /// let labeled = LabeledFormula::new(glucose).unwrap();
+    /// assert!((labeled.a_plus_n_abundance(1) - 0.0686).abs() < 1e-3);
+    /// assert_eq!(labeled.a_plus_n_abundance(0), 1.0);
+    /// ```
+    #[must_use]
+    pub fn a_plus_n_abundance(&self, n: u64) -> f64 {
+        let peaks = self.lightest_peaks(n);
+        let lightest = peaks.get(&0).map_or(0.0, |(abundance, _)| *abundance);
+        peaks.get(&n).map_or(0.0, |(abundance, _)| abundance / lightest)
+    }
+
+    /// Returns the isotope distribution of the labelled formula up to the
+    /// provided number of nominal mass units above its lightest
+    /// isotopologue, keyed by their offset from it.
+    fn lightest_peaks(&self, n: u64) -> Distribution {
+        self.pattern(
+            |mut distribution| {
+                distribution.retain(|_, (abundance, _)| *abundance > 0.0);
+                let lightest = distribution.keys().next().copied().unwrap_or(0);
+                distribution.into_iter().map(|(key, value)| (key - lightest, value)).collect()
+            },
+            0.0,
+            n,
+        )
+    }
+
+    /// Returns the isotope distribution of the labelled formula, with the
+    /// distribution of each of its atoms first remapped by the provided
+    /// function, discarding the entries below the provided abundance or above
+    /// the provided key.
+    fn pattern(
+        &self,
+        remap: impl Fn(Distribution) -> Distribution,
+        min_abundance: f64,
+        max_key: u64,
+    ) -> Distribution {
+        let mut pattern: Distribution = BTreeMap::from([(0, (1.0, 0.0))]);
+        for (element, count) in &self.atoms {
+            let distribution = remap(self.natural_distribution(*element));
+            let distribution = power(&distribution, *count, min_abundance, max_key);
+            pattern = convolve(&pattern, &distribution, min_abundance, max_key);
+        }
+        for label in &self.labels {
+            for (count, enrichment) in label.groups() {
+                let distribution = remap(self.labelled_distribution(label.isotope, enrichment));
+                let distribution = power(&distribution, count, min_abundance, max_key);
+                pattern = convolve(&pattern, &distribution, min_abundance, max_key);
+            }
+        }
+        pattern
+    }
+
+    /// Returns the isotope distribution of an unlabelled atom of the provided
+    /// element.
+    fn natural_distribution(&self, element: Element) -> Distribution {
+        let mut distribution: Distribution = self
+            .context
+            .isotopic_composition(element)
+            .map(|(isotope, abundance)| {
+                (
+                    u64::from(isotope.mass_number()),
+                    (abundance, abundance * self.context.isotope_mass(isotope)),
+                )
+            })
+            .collect();
+        if distribution.is_empty() {
+            // Elements without natural isotopic composition are assumed to
+            // consist of their most abundant isotope.
+            let isotope = self.context.most_abundant_isotope(element);
+            distribution.insert(
+                u64::from(isotope.mass_number()),
+                (1.0, self.context.isotope_mass(isotope)),
+            );
+        }
+        distribution
+    }
+
+    /// Returns the isotope distribution of a position labelled with the
+    /// provided isotope and enrichment.
+    fn labelled_distribution(&self, isotope: Isotope, enrichment: f64) -> Distribution {
+        let others: Vec<(Isotope, f64)> = self
+            .context
+            .isotopic_composition(isotope.element())
+            .filter(|(i, _)| *i != isotope)
+            .collect();
+        let total: f64 = others.iter().map(|(_, abundance)| abundance).sum();
+        let mut distribution: Distribution = BTreeMap::new();
+        if total > 0.0 && enrichment < 1.0 {
+            for (other, abundance) in others {
+                let abundance = (1.0 - enrichment) * abundance / total;
+                distribution.insert(
+                    u64::from(other.mass_number()),
+                    (abundance, abundance * self.context.isotope_mass(other)),
+                );
+            }
+        }
+        let abundance = if total > 0.0 { enrichment } else { 1.0 };
+        distribution.insert(
+            u64::from(isotope.mass_number()),
+            (abundance, abundance * self.context.isotope_mass(isotope)),
+        );
+        distribution
+    }
+}
+
+/// Returns the distribution of the union of two independent groups of atoms,
+/// discarding the entries below the provided abundance or above the provided
+/// key.
+fn convolve(
+    left: &Distribution,
+    right: &Distribution,
+    min_abundance: f64,
+    max_key: u64,
+) -> Distribution {
+    let mut distribution = Distribution::new();
+    for (left_key, (left_abundance, left_mass)) in left {
+        for (right_key, (right_abundance, right_mass)) in right {
+            let key = left_key + right_key;
+            if key > max_key {
+                break;
+            }
+            let entry = distribution.entry(key).or_insert((0.0, 0.0));
+            entry.0 += left_abundance * right_abundance;
+            entry.1 += left_abundance * right_mass + right_abundance * left_mass;
+        }
+    }
+    distribution.retain(|_, (abundance, _)| *abundance >= min_abundance);
+    distribution
+}
+
+/// Returns the distribution of the provided number of independent atoms
+/// sharing the same distribution, by repeated squaring.
+fn power(
+    distribution: &Distribution,
+    mut count: u64,
+    min_abundance: f64,
+    max_key: u64,
+) -> Distribution {
+    let mut result: Distribution = BTreeMap::from([(0, (1.0, 0.0))]);
+    let mut base = distribution.clone();
+    while count > 0 {
+        if count & 1 == 1 {
+            result = convolve(&result, &base, min_abundance, max_key);
+        }
+        count >>= 1;
+        if count > 0 {
+            base = convolve(&base, &base, min_abundance, max_key);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use elements_rs::RelativeAtomicMass;
+
+    use super::*;
+    use crate::ChemicalFormula;
+
+    #[test]
+    fn test_unlabelled_formula() {
+        let formula = ChemicalFormula::<u32, i32>::from_str("CH4").unwrap();
+        let labeled = LabeledFormula::new(formula.clone()).unwrap();
+        let expected =
+            Element::C.standard_atomic_weight() + 4.0 * Element::H.standard_atomic_weight();
+        assert!((labeled.average_mass() - expected).abs() < 1e-9);
+
+        let pattern = labeled.isotope_pattern(0.0);
+        let total: f64 = pattern.iter().map(|(_, abundance)| abundance).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!((pattern[0].0 - formula.isotopologue_mass()).abs() < 1e-9);
+        assert!((pattern[1].1 / pattern[0].1 - 0.0113).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_natural_enrichment() {
+        let deuterium = Isotope::try_from((Element::H, 2_u16)).unwrap();
+        let natural = MassContext::new().abundance(deuterium);
+        let formula = ChemicalFormula::<u32, i32>::from_str("[2H]2O").unwrap();
+        let labeled =
+            LabeledFormula::new(formula).unwrap().with_enrichment(deuterium, natural).unwrap();
+        let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+        let expected = LabeledFormula::new(water).unwrap().average_mass();
+        // The standard atomic weight of hydrogen is a conventional value.
+        assert!((labeled.average_mass() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_full_enrichment() {
+        let formula = ChemicalFormula::<u32, i32>::from_str("[13C]2").unwrap();
+        let labeled = LabeledFormula::new(formula).unwrap();
+        let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+        assert_eq!(labeled.enrichment(carbon_13, 1), Some(1.0));
+        assert_eq!(labeled.enrichment(carbon_13, 2), None);
+        assert!((labeled.average_mass() - 2.0 * carbon_13.relative_atomic_mass()).abs() < 1e-9);
+        assert_eq!(labeled.isotope_pattern(0.0).len(), 1);
+    }
+
+    #[test]
+    fn test_missing_label() {
+        let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+        let formula = ChemicalFormula::<u32, i32>::from_str("CH4").unwrap();
+        let labeled = LabeledFormula::new(formula).unwrap();
+        assert_eq!(
+            labeled.clone().with_enrichment(carbon_13, 0.99),
+            Err(LabelingError::MissingLabel(carbon_13))
+        );
+        assert_eq!(
+            labeled.with_position_enrichment(carbon_13, 0, 0.99),
+            Err(LabelingError::MissingPosition { isotope: carbon_13, position: 0 })
+        );
+    }
+
+    #[test]
+    fn test_invalid_enrichment() {
+        let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+        let formula = ChemicalFormula::<u32, i32>::from_str("[13C]H4").unwrap();
+        let labeled = LabeledFormula::new(formula).unwrap();
+        for enrichment in [-0.1, 1.1, f64::NAN] {
+            assert_eq!(
+                labeled.clone().with_enrichment(carbon_13, enrichment),
+                Err(LabelingError::InvalidEnrichment)
+            );
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Submodule providing the tables of isotope masses and isotopic
+//! compositions used by mass computations, which may override the defaults
+//! provided by `elements_rs` for a given computation.
+
+use alloc::vec::Vec;
+
+use elements_rs::{Element, ElementVariant, Isotope, IsotopicComposition, RelativeAtomicMass};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Isotope masses and isotopic compositions used by mass computations.
+///
+/// Any isotope mass or isotopic composition which is not overridden is taken
+/// from `elements_rs`. Since a context is an explicit value rather than a
+/// global setting, computations with different assumptions, such as an
+/// enriched reagent lot and natural material, may coexist.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let boron_10 = Isotope::try_from((Element::B, 10_u16)).unwrap();
+/// let boron_11 = Isotope::try_from((Element::B, 11_u16)).unwrap();
+/// let enriched = MassContext::new()
+///     .with_isotopic_composition(Element::B, [(boron_10, 0.96), (boron_11, 0.04)]);
+/// assert_eq!(enriched.most_abundant_isotope(Element::B), boron_10);
+///
+/// let borane = ChemicalFormula::<u32, i32>::from_str("BH3").unwrap();
+/// let natural_mass = borane.isotopologue_mass();
+/// let enriched_mass = borane.isotopologue_mass_with(&enriched);
+/// assert!((natural_mass - enriched_mass - 0.996368).abs() < 1e-6);
+/// ```
+pub struct MassContext {
+    /// The isotope masses overriding the default ones.
+    masses: Vec<(Isotope, f64)>,
+    /// The normalized isotopic compositions overriding the natural ones.
+    compositions: Vec<(Element, Vec<(Isotope, f64)>)>,
+}
+
+impl MassContext {
+    /// Creates a context using the default isotope masses and natural
+    /// isotopic compositions.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { masses: Vec::new(), compositions: Vec::new() }
+    }
+
+    /// Sets the mass of the provided isotope, replacing any previous mass.
+    ///
+    /// # Panics
+    ///
+    /// * If the mass is not a positive finite number.
+    #[must_use]
+    pub fn with_isotope_mass(mut self, isotope: Isotope, mass: f64) -> Self {
+        assert!(mass.is_finite() && mass > 0.0, "The mass of {isotope} must be positive");
+        if let Some(entry) = self.masses.iter_mut().find(|(i, _)| *i == isotope) {
+            entry.1 = mass;
+        } else {
+            self.masses.push((isotope, mass));
+        }
+        self
+    }
+
+    /// Sets the isotopic composition of the provided element, replacing its
+    /// natural composition and any previous one.
+    ///
+    /// The abundances are normalized, so they may be provided as fractions,
+    /// percentages or relative intensities. Isotopes of the element which are
+    /// not listed have a null abundance.
+    ///
+    /// # Panics
+    ///
+    /// * If any isotope does not belong to the element.
+    /// * If any abundance is negative or not finite.
+    /// * If all the abundances are null.
+    #[must_use]
+    pub fn with_isotopic_composition(
+        mut self,
+        element: Element,
+        abundances: impl IntoIterator<Item = (Isotope, f64)>,
+    ) -> Self {
+        let mut composition: Vec<(Isotope, f64)> = Vec::new();
+        for (isotope, abundance) in abundances {
+            assert!(isotope.element() == element, "{isotope} is not an isotope of {element}");
+            assert!(
+                abundance.is_finite() && abundance >= 0.0,
+                "The abundance of {isotope} must be a non-negative finite number"
+            );
+            if let Some(entry) = composition.iter_mut().find(|(i, _)| *i == isotope) {
+                entry.1 = abundance;
+            } else {
+                composition.push((isotope, abundance));
+            }
+        }
+        let total: f64 = composition.iter().map(|(_, abundance)| abundance).sum();
+        assert!(total > 0.0, "At least one isotope of {element} must be abundant");
+        for (_, abundance) in &mut composition {
+            *abundance /= total;
+        }
+        if let Some(entry) = self.compositions.iter_mut().find(|(e, _)| *e == element) {
+            entry.1 = composition;
+        } else {
+            self.compositions.push((element, composition));
+        }
+        self
+    }
+
+    /// Returns the mass of the provided isotope.
+    #[must_use]
+    pub fn isotope_mass(&self, isotope: Isotope) -> f64 {
+        self.masses
+            .iter()
+            .find(|(i, _)| *i == isotope)
+            .map_or_else(|| isotope.relative_atomic_mass(), |(_, mass)| *mass)
+    }
+
+    /// Returns the abundance of the provided isotope, as a fraction, which is
+    /// zero for isotopes without natural occurrence.
+    #[must_use]
+    pub fn abundance(&self, isotope: Isotope) -> f64 {
+        match self.compositions.iter().find(|(element, _)| *element == isotope.element()) {
+            Some((_, composition)) => {
+                composition
+                    .iter()
+                    .find(|(i, _)| *i == isotope)
+                    .map_or(0.0, |(_, abundance)| *abundance)
+            }
+            None => isotope.isotopic_composition().unwrap_or(0.0),
+        }
+    }
+
+    /// Iterates over the isotopes of the provided element with a non-zero
+    /// abundance, with their abundance as a fraction.
+    pub fn isotopic_composition(
+        &self,
+        element: Element,
+    ) -> impl Iterator<Item = (Isotope, f64)> + '_ {
+        element
+            .isotopes()
+            .iter()
+            .map(|isotope| (*isotope, self.abundance(*isotope)))
+            .filter(|(_, abundance)| *abundance > 0.0)
+    }
+
+    /// Returns the most abundant isotope of the provided element, which is
+    /// used for the isotopologue mass of unlabelled atoms.
+    #[must_use]
+    pub fn most_abundant_isotope(&self, element: Element) -> Isotope {
+        self.compositions
+            .iter()
+            .find(|(e, _)| *e == element)
+            .and_then(|(_, composition)| {
+                composition.iter().max_by(|(_, first), (_, second)| first.total_cmp(second))
+            })
+            .map_or_else(|| element.most_abundant_isotope(), |(isotope, _)| *isotope)
+    }
+
+    /// Returns the mass of an unlabelled atom of the provided element in an
+    /// isotopologue, i.e. the mass of its most abundant isotope.
+    #[must_use]
+    pub fn element_mass(&self, element: Element) -> f64 {
+        self.isotope_mass(self.most_abundant_isotope(element))
+    }
+
+    /// Returns the average mass of the provided element, weighted by the
+    /// abundances of its isotopes.
+    ///
+    /// Unless any of its isotope masses or its isotopic composition is
+    /// overridden, this is the standard atomic weight of the element.
+    #[must_use]
+    pub fn average_mass(&self, element: Element) -> f64 {
+        let overridden = self.compositions.iter().any(|(e, _)| *e == element)
+            || self.masses.iter().any(|(isotope, _)| isotope.element() == element);
+        let (mass, abundance) = self.isotopic_composition(element).fold(
+            (0.0, 0.0),
+            |(mass, total), (isotope, abundance)| {
+                (mass + abundance * self.isotope_mass(isotope), total + abundance)
+            },
+        );
+        if overridden && abundance > 0.0 {
+            mass / abundance
+        } else {
+            element.standard_atomic_weight()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let context = MassContext::new();
+        for element in [Element::H, Element::C, Element::Cl, Element::Fe, Element::U] {
+            assert!((context.element_mass(element) - element.relative_atomic_mass()).abs() < 1e-12);
+            assert!(
+                (context.average_mass(element) - element.standard_atomic_weight()).abs() < 1e-12
+            );
+        }
+        let total: f64 = context.isotopic_composition(Element::Cl).map(|(_, a)| a).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_isotope_mass_override() {
+        let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+        let context = MassContext::new().with_isotope_mass(carbon_13, 13.0);
+        assert!((context.isotope_mass(carbon_13) - 13.0).abs() < f64::EPSILON);
+        assert!((context.element_mass(Element::C) - 12.0).abs() < f64::EPSILON);
+        assert!(context.average_mass(Element::C) < Element::C.standard_atomic_weight());
+    }
+
+    #[test]
+    fn test_isotopic_composition_override() {
+        let chlorine_35 = Isotope::try_from((Element::Cl, 35_u16)).unwrap();
+        let chlorine_37 = Isotope::try_from((Element::Cl, 37_u16)).unwrap();
+        let context = MassContext::new()
+            .with_isotopic_composition(Element::Cl, [(chlorine_35, 1.0), (chlorine_37, 3.0)]);
+        assert!((context.abundance(chlorine_37) - 0.75).abs() < f64::EPSILON);
+        assert_eq!(context.most_abundant_isotope(Element::Cl), chlorine_37);
+        let expected =
+            0.25 * chlorine_35.relative_atomic_mass() + 0.75 * chlorine_37.relative_atomic_mass();
+        assert!((context.average_mass(Element::Cl) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_foreign_isotope() {
+        let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+        let _ = MassContext::new().with_isotopic_composition(Element::N, [(carbon_13, 1.0)]);
+    }
+}
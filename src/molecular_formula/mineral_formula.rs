@@ -7,13 +7,16 @@ use core::{fmt::Display, iter::Peekable};
 use elements_rs::{Element, Isotope};
 
 use crate::{
-    BaselineMinus, ChargeLike, ChargedMolecularFormulaMetadata, ChemicalTree, CountLike, Empty,
-    MolecularFormula, MolecularFormulaMetadata, ParsableFormula, errors::ParserError,
-    parsable::CharacterMarker, prelude::ChemicalFormula,
+    ChargeLike, ChargedMolecularFormulaMetadata, ChemicalTree, CountLike, DeepSizeOf, DisplayStyle,
+    Empty, MolecularFormula, MolecularFormulaMetadata, ParsableFormula, Prefixed, StyledDisplay,
+    errors::{NumericError, ParserError},
+    molecular_formula::prefixed::{polymorph_prefix, read_prefix},
+    prelude::ChemicalFormula,
 };
 
 #[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 /// Represents a greek letter in a molecular formula.
 ///
 /// These are not ALL of the greek letters, but only those which are used in
@@ -85,6 +88,10 @@ impl TryFrom<char> for PolymorphPrefix {
 /// Struct representing a mineral formula, potentially with a greek letter
 /// prefix.
 ///
+/// The prefixes are read as by [`Prefixed`], and only polymorph prefixes are
+/// kept, so that the `α-D-` prefix of `α-D-C6H12O6` is discarded rather
+/// than read as a polymorph prefix followed by deuterium.
+///
 /// # Examples
 ///
 /// ```
@@ -96,10 +103,77 @@ impl TryFrom<char> for PolymorphPrefix {
 /// assert_eq!(quartz.to_string(), "α-SiO₂");
 /// ```
 pub struct MineralFormula<Count: CountLike = u16, Charge: ChargeLike = i16> {
-    /// Optional greek letter prefix for the mineral formula.
-    polymorph_prefix: Option<PolymorphPrefix>,
-    /// The rest of the chemical formula.
-    formula: ChemicalFormula<Count, Charge>,
+    /// The chemical formula, with its optional polymorph prefix.
+    formula: Prefixed<ChemicalFormula<Count, Charge>>,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> MineralFormula<Count, Charge> {
+    /// Returns the formula with all of its charges removed, preserving the
+    /// polymorph prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = MineralFormula::<u32, i32>::from_str("α-SiO4-4").unwrap();
+    /// assert_eq!(formula.neutralize().to_string(), "α-SiO₄");
+    /// ```
+    #[must_use]
+    pub fn neutralize(&self) -> Self {
+        Self { formula: self.formula.clone().map(|formula| formula.neutralize()) }
+    }
+
+    /// Returns the number of copies of water among the mixture components
+    /// of the formula, i.e. its number of waters of crystallization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let gypsum = MineralFormula::<u32, i32>::from_str("CaSO4.2H2O").unwrap();
+    /// assert_eq!(gypsum.water_of_crystallization(), 2);
+    /// ```
+    #[must_use]
+    pub fn water_of_crystallization(&self) -> u64 {
+        self.formula.formula().water_of_crystallization()
+    }
+
+    /// Returns the formula without its waters of crystallization, preserving
+    /// the polymorph prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let hemihydrate = MineralFormula::<u32, i32>::from_str("β-2CaSO4.H2O").unwrap();
+    /// assert_eq!(hemihydrate.anhydrous().to_string(), "β-2CaSO₄");
+    /// ```
+    #[must_use]
+    pub fn anhydrous(&self) -> Self {
+        Self { formula: self.formula.clone().map(|formula| formula.anhydrous()) }
+    }
+
+    /// Consumes the formula and returns an equivalent formula using the
+    /// provided count and charge types.
+    ///
+    /// # Errors
+    ///
+    /// * If any count or charge cannot be represented by the new types.
+    pub(crate) fn try_convert<NewCount: CountLike, NewCharge: ChargeLike>(
+        self,
+    ) -> Result<MineralFormula<NewCount, NewCharge>, NumericError> {
+        let (prefix, formula) = self.formula.into_parts();
+        Ok(MineralFormula { formula: Prefixed::from_parts(prefix, formula.try_convert()?) })
+    }
 }
 
 impl<Count: CountLike, Charge: ChargeLike> MolecularFormulaMetadata
@@ -126,13 +200,33 @@ impl<Count: CountLike, Charge: ChargeLike> MolecularFormula for MineralFormula<C
 
 impl<Count: CountLike, Charge: ChargeLike> From<Element> for MineralFormula<Count, Charge> {
     fn from(element: Element) -> Self {
-        Self { polymorph_prefix: None, formula: ChemicalFormula::from(element) }
+        Self { formula: Prefixed::new(None, ChemicalFormula::from(element)) }
     }
 }
 
 impl<Count: CountLike, Charge: ChargeLike> From<Isotope> for MineralFormula<Count, Charge> {
     fn from(isotope: Isotope) -> Self {
-        Self { polymorph_prefix: None, formula: ChemicalFormula::from(isotope) }
+        Self { formula: Prefixed::new(None, ChemicalFormula::from(isotope)) }
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> From<MineralFormula<Count, Charge>>
+    for Prefixed<ChemicalFormula<Count, Charge>>
+{
+    fn from(mineral: MineralFormula<Count, Charge>) -> Self {
+        mineral.formula
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> From<Prefixed<ChemicalFormula<Count, Charge>>>
+    for MineralFormula<Count, Charge>
+{
+    /// Returns the mineral formula of the prefixed formula, keeping its
+    /// prefix only if it is a polymorph prefix, as mineral formulas carry
+    /// no other prefix.
+    fn from(prefixed: Prefixed<ChemicalFormula<Count, Charge>>) -> Self {
+        let polymorph_prefix = prefixed.polymorph_prefix();
+        Self { formula: prefixed.with_prefix(polymorph_prefix) }
     }
 }
 
@@ -154,25 +248,16 @@ where
 
     fn on_start<J>(
         chars: &mut Peekable<J>,
+        _options: &crate::ParserOptions,
     ) -> Result<<MineralFormula<Count, Charge> as crate::ParsableFormula>::StartOutput, ParserError>
     where
-        J: Iterator<Item = char>,
+        J: Iterator<Item = char> + Clone,
     {
-        let first_character = chars.peek().ok_or(ParserError::UnexpectedEndOfInput)?;
-        if let Ok(polymorph_prefix) = PolymorphPrefix::try_from(*first_character) {
-            chars.next();
-            // The polymorph prefix was found, but it must be followed by either an
-            // hyphen or something which an OCR would mistake for a hyphen. To have
-            // OCR-resilient parsing, we accept anything that looks like a "minus" sign.
-            let next_character = chars.next().ok_or(ParserError::UnexpectedEndOfInput)?;
-            if BaselineMinus::matches(next_character) {
-                Ok(Some(polymorph_prefix))
-            } else {
-                Err(ParserError::UnexpectedCharacter(next_character))
-            }
-        } else {
-            Ok(None)
-        }
+        chars.peek().ok_or(ParserError::UnexpectedEndOfInput)?;
+        // The prefix is read as by the `Prefixed` wrapper, so that both agree
+        // on where the formula starts, and only a polymorph prefix is kept,
+        // as when converting a prefixed formula into a mineral formula.
+        Ok(read_prefix(chars)?.as_deref().and_then(polymorph_prefix))
     }
 
     fn from_parsed(
@@ -180,17 +265,29 @@ where
         mixtures: Vec<(Count, Self::Tree)>,
     ) -> Result<Self, crate::errors::ParserError> {
         let formula = ChemicalFormula::from_parsed((), mixtures)?;
-        Ok(MineralFormula { polymorph_prefix: start_output, formula })
+        Ok(MineralFormula { formula: Prefixed::new(start_output, formula) })
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> StyledDisplay for MineralFormula<Count, Charge> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        self.formula.fmt_with(style, f)
     }
 }
 
 impl<Count: CountLike, Charge: ChargeLike> Display for MineralFormula<Count, Charge> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        if let Some(prefix) = &self.polymorph_prefix {
-            write!(f, "{prefix}")?;
-            write!(f, "-")?;
-        }
-        write!(f, "{}", self.formula)
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> DeepSizeOf for MineralFormula<Count, Charge> {
+    fn deep_size_of_children(&self) -> usize {
+        self.formula.deep_size_of_children()
     }
 }
 
@@ -225,7 +322,11 @@ mod tests {
             let input = format!("{char_representation}-{formula_part}");
             let parsed = MineralFormula::<u32, i32>::from_str(&input).expect("Should parse");
 
-            assert_eq!(parsed.polymorph_prefix, Some(prefix), "Prefix mismatch for {input}");
+            assert_eq!(
+                parsed.formula.polymorph_prefix(),
+                Some(prefix),
+                "Prefix mismatch for {input}"
+            );
             assert_eq!(parsed.to_string(), input, "Roundtrip mismatch for {input}");
         }
     }
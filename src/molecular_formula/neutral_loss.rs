@@ -0,0 +1,232 @@
+//! Submodule providing a small library of the neutral losses commonly
+//! observed in tandem mass spectrometry.
+
+use core::fmt::Display;
+
+use elements_rs::{Element, RelativeAtomicMass};
+
+use crate::{ChargeLike, ChemicalFormula, CountLike, MolecularFormula, number_from_u8};
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Enumeration of the neutral losses commonly observed in tandem mass
+/// spectrometry.
+pub enum NeutralLoss {
+    /// Water, `H₂O`.
+    Water,
+    /// Ammonia, `H₃N`.
+    Ammonia,
+    /// Carbon monoxide, `CO`.
+    CarbonMonoxide,
+    /// Carbon dioxide, `CO₂`.
+    CarbonDioxide,
+    /// Formic acid, `CH₂O₂`.
+    FormicAcid,
+    /// Formaldehyde, `CH₂O`.
+    Formaldehyde,
+    /// Methanol, `CH₄O`.
+    Methanol,
+    /// Acetic acid, `C₂H₄O₂`.
+    AceticAcid,
+    /// Hydrogen cyanide, `CHN`.
+    HydrogenCyanide,
+    /// Hydrogen sulfide, `H₂S`.
+    HydrogenSulfide,
+    /// Hydrogen chloride, `ClH`.
+    HydrogenChloride,
+    /// Hydrogen fluoride, `FH`.
+    HydrogenFluoride,
+    /// Sulfur trioxide, `O₃S`.
+    SulfurTrioxide,
+    /// Phosphoric acid, `H₃O₄P`.
+    PhosphoricAcid,
+}
+
+impl NeutralLoss {
+    /// All the neutral losses in the library.
+    pub const ALL: [NeutralLoss; 14] = [
+        NeutralLoss::Water,
+        NeutralLoss::Ammonia,
+        NeutralLoss::CarbonMonoxide,
+        NeutralLoss::CarbonDioxide,
+        NeutralLoss::FormicAcid,
+        NeutralLoss::Formaldehyde,
+        NeutralLoss::Methanol,
+        NeutralLoss::AceticAcid,
+        NeutralLoss::HydrogenCyanide,
+        NeutralLoss::HydrogenSulfide,
+        NeutralLoss::HydrogenChloride,
+        NeutralLoss::HydrogenFluoride,
+        NeutralLoss::SulfurTrioxide,
+        NeutralLoss::PhosphoricAcid,
+    ];
+
+    /// Returns the common name of the neutral loss.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert_eq!(NeutralLoss::Water.name(), "water");
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            NeutralLoss::Water => "water",
+            NeutralLoss::Ammonia => "ammonia",
+            NeutralLoss::CarbonMonoxide => "carbon monoxide",
+            NeutralLoss::CarbonDioxide => "carbon dioxide",
+            NeutralLoss::FormicAcid => "formic acid",
+            NeutralLoss::Formaldehyde => "formaldehyde",
+            NeutralLoss::Methanol => "methanol",
+            NeutralLoss::AceticAcid => "acetic acid",
+            NeutralLoss::HydrogenCyanide => "hydrogen cyanide",
+            NeutralLoss::HydrogenSulfide => "hydrogen sulfide",
+            NeutralLoss::HydrogenChloride => "hydrogen chloride",
+            NeutralLoss::HydrogenFluoride => "hydrogen fluoride",
+            NeutralLoss::SulfurTrioxide => "sulfur trioxide",
+            NeutralLoss::PhosphoricAcid => "phosphoric acid",
+        }
+    }
+
+    /// Returns the elemental composition of the neutral loss, in Hill order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert_eq!(NeutralLoss::Water.composition(), &[(Element::H, 2), (Element::O, 1)]);
+    /// ```
+    #[must_use]
+    pub fn composition(&self) -> &'static [(Element, u8)] {
+        match self {
+            NeutralLoss::Water => &[(Element::H, 2), (Element::O, 1)],
+            NeutralLoss::Ammonia => &[(Element::H, 3), (Element::N, 1)],
+            NeutralLoss::CarbonMonoxide => &[(Element::C, 1), (Element::O, 1)],
+            NeutralLoss::CarbonDioxide => &[(Element::C, 1), (Element::O, 2)],
+            NeutralLoss::FormicAcid => &[(Element::C, 1), (Element::H, 2), (Element::O, 2)],
+            NeutralLoss::Formaldehyde => &[(Element::C, 1), (Element::H, 2), (Element::O, 1)],
+            NeutralLoss::Methanol => &[(Element::C, 1), (Element::H, 4), (Element::O, 1)],
+            NeutralLoss::AceticAcid => &[(Element::C, 2), (Element::H, 4), (Element::O, 2)],
+            NeutralLoss::HydrogenCyanide => &[(Element::C, 1), (Element::H, 1), (Element::N, 1)],
+            NeutralLoss::HydrogenSulfide => &[(Element::H, 2), (Element::S, 1)],
+            NeutralLoss::HydrogenChloride => &[(Element::Cl, 1), (Element::H, 1)],
+            NeutralLoss::HydrogenFluoride => &[(Element::F, 1), (Element::H, 1)],
+            NeutralLoss::SulfurTrioxide => &[(Element::O, 3), (Element::S, 1)],
+            NeutralLoss::PhosphoricAcid => &[(Element::H, 3), (Element::O, 4), (Element::P, 1)],
+        }
+    }
+
+    /// Returns the monoisotopic mass of the neutral loss.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let mass = NeutralLoss::Water.isotopologue_mass();
+    /// assert!((mass - 18.010565).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    pub fn isotopologue_mass(&self) -> f64 {
+        self.composition()
+            .iter()
+            .map(|(element, count)| f64::from(*count) * element.relative_atomic_mass())
+            .sum()
+    }
+
+    /// Returns the neutral loss as a chemical formula.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula: ChemicalFormula = NeutralLoss::FormicAcid.formula();
+    /// assert_eq!(formula.to_string(), "CH₂O₂");
+    /// ```
+    #[must_use]
+    pub fn formula<Count: CountLike, Charge: ChargeLike>(&self) -> ChemicalFormula<Count, Charge> {
+        ChemicalFormula::from_element_counts(
+            self.composition().iter().map(|&(element, count)| (element, number_from_u8(count))),
+        )
+    }
+
+    /// Returns whether the neutral loss can be subtracted from the provided
+    /// formula, i.e. whether the formula contains at least as many atoms of
+    /// each element as the neutral loss.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let glycine: ChemicalFormula = ChemicalFormula::from_str("C2H5NO2").unwrap();
+    /// assert!(NeutralLoss::Water.is_subtractable_from(&glycine));
+    /// assert!(!NeutralLoss::HydrogenSulfide.is_subtractable_from(&glycine));
+    /// ```
+    #[must_use]
+    pub fn is_subtractable_from<M: MolecularFormula>(&self, formula: &M) -> bool
+    where
+        u64: From<M::Count>,
+    {
+        self.composition().iter().all(|&(element, count)| {
+            formula
+                .count_of_element::<u64>(element)
+                .is_some_and(|total| total >= <u64 as From<u8>>::from(count))
+        })
+    }
+}
+
+impl Display for NeutralLoss {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.formula::<u8, i8>())
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalFormula<Count, Charge> {
+    /// Iterates on the neutral losses of the built-in library which can be
+    /// subtracted from the formula.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let glycine: ChemicalFormula = ChemicalFormula::from_str("C2H5NO2").unwrap();
+    /// let losses: Vec<NeutralLoss> = glycine.common_losses().collect();
+    /// assert!(losses.contains(&NeutralLoss::Water));
+    /// assert!(losses.contains(&NeutralLoss::Ammonia));
+    /// assert!(losses.contains(&NeutralLoss::FormicAcid));
+    /// assert!(!losses.contains(&NeutralLoss::HydrogenChloride));
+    /// ```
+    pub fn common_losses(&self) -> impl Iterator<Item = NeutralLoss> + '_
+    where
+        u64: From<Count>,
+    {
+        NeutralLoss::ALL.into_iter().filter(|loss| loss.is_subtractable_from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_formulas_match_compositions() {
+        for loss in NeutralLoss::ALL {
+            let formula: ChemicalFormula<u8, i8> = loss.formula();
+            assert!(formula.is_hill_sorted(), "{loss:?} is not Hill sorted");
+            assert!((formula.isotopologue_mass() - loss.isotopologue_mass()).abs() < 1e-9);
+            assert_eq!(formula.to_string(), loss.to_string());
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Submodule implementing the conversions between formulas parametrized with
+//! different count and charge types.
+//!
+//! Since a blanket implementation over any pair of parametrizations would
+//! conflict with the reflexive `TryFrom` implementation of the standard
+//! library, the conversions are implemented for each pair of distinct
+//! primitive parametrizations.
+
+use crate::{ChemicalFormula, InChIFormula, MineralFormula, ResidualFormula, errors::NumericError};
+
+/// Implements `TryFrom` in both directions between each pair of distinct
+/// parametrizations of the provided formula type.
+macro_rules! impl_numeric_conversions {
+    ($formula:ident;) => {};
+    ($formula:ident; $head:tt $(, $tail:tt)*) => {
+        $(
+            impl_numeric_conversions!(@impl $formula; $head => $tail);
+            impl_numeric_conversions!(@impl $formula; $tail => $head);
+        )*
+        impl_numeric_conversions!($formula; $($tail),*);
+    };
+    (@impl $formula:ident; ($from_count:ty, $from_charge:ty) => ($to_count:ty, $to_charge:ty)) => {
+        impl TryFrom<$formula<$from_count, $from_charge>> for $formula<$to_count, $to_charge> {
+            type Error = NumericError;
+
+            fn try_from(formula: $formula<$from_count, $from_charge>) -> Result<Self, Self::Error> {
+                formula.try_convert()
+            }
+        }
+    };
+    (@impl $formula:ident; $from_count:ty => $to_count:ty) => {
+        impl TryFrom<$formula<$from_count>> for $formula<$to_count> {
+            type Error = NumericError;
+
+            fn try_from(formula: $formula<$from_count>) -> Result<Self, Self::Error> {
+                formula.try_convert()
+            }
+        }
+    };
+}
+
+/// Implements the conversions for each of the charged formula types, between
+/// all combinations of the primitive count and charge types.
+macro_rules! impl_charged_numeric_conversions {
+    ($($formula:ident),*) => {
+        $(
+            impl_numeric_conversions!(
+                $formula;
+                (u8, i8), (u8, i16), (u8, i32), (u8, i64),
+                (u16, i8), (u16, i16), (u16, i32), (u16, i64),
+                (u32, i8), (u32, i16), (u32, i32), (u32, i64),
+                (u64, i8), (u64, i16), (u64, i32), (u64, i64)
+            );
+        )*
+    };
+}
+
+impl_charged_numeric_conversions!(ChemicalFormula, MineralFormula, ResidualFormula);
+impl_numeric_conversions!(InChIFormula; u8, u16, u32, u64);
@@ -0,0 +1,284 @@
+//! Submodule providing the `Prefixed` wrapper, which adds a labeled prefix
+//! such as the `α-` polymorph prefix, the `α-D-` anomer prefix or the `Δ9-`
+//! descriptor to any molecular formula type.
+
+use alloc::string::{String, ToString};
+use core::{fmt::Display, iter::Peekable};
+
+use elements_rs::Element;
+
+use crate::{
+    BaselineMinus, ChargedMolecularFormulaMetadata, DeepSizeOf, DisplayStyle, FromStrWithOptions,
+    MolecularFormula, MolecularFormulaMetadata, ParserOptions, PolymorphPrefix, StyledDisplay,
+    SuperscriptDigit, errors::ParserError, parsable::CharacterMarker,
+};
+
+#[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
+/// Molecular formula of any type, potentially with a labeled prefix.
+///
+/// The polymorph prefixes of [`MineralFormula`](crate::MineralFormula), which
+/// wraps its formula into this type, also denote the anomers of sugars and
+/// the phases of polymers, and are extended by configuration descriptors or
+/// by locants as in the `α-D-` and `Δ9-` prefixes. The wrapper parses the
+/// prefix and its hyphens, as described in
+/// [`PrefixHandling`](crate::PrefixHandling), parses the rest of the string
+/// as the wrapped formula, and writes the prefix back before the formula so
+/// that it round-trips. It is the only representation of the prefixes of
+/// formulas, which the [`ChemicalFormula`](crate::ChemicalFormula) parser
+/// otherwise rejects or skips.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let anomer = Prefixed::<ChemicalFormula<u32, i32>>::from_str("β-C6H12O6").unwrap();
+/// assert_eq!(anomer.polymorph_prefix(), Some(PolymorphPrefix::Beta));
+/// assert_eq!(anomer.formula().to_string(), "C₆H₁₂O₆");
+/// assert_eq!(anomer.to_string(), "β-C₆H₁₂O₆");
+///
+/// let linoleic = Prefixed::<ChemicalFormula<u32, i32>>::from_str("Δ9,12-C18H32O2").unwrap();
+/// assert_eq!(linoleic.prefix(), Some("Δ9,12"));
+/// assert_eq!(linoleic.polymorph_prefix(), None);
+///
+/// let inchi = Prefixed::<InChIFormula<u32>>::from_str("γ-C3H7NO2").unwrap();
+/// assert_eq!(inchi.to_string(), "γ-C3H7NO2");
+///
+/// let unprefixed = Prefixed::<ChemicalFormula<u32, i32>>::from_str("H2O").unwrap();
+/// assert_eq!(unprefixed.prefix(), None);
+/// ```
+pub struct Prefixed<M> {
+    /// The labeled prefix of the formula without its trailing hyphen, if
+    /// any.
+    prefix: Option<String>,
+    /// The prefixed formula.
+    formula: M,
+}
+
+impl<M> Prefixed<M> {
+    /// Creates a new formula with the provided optional polymorph prefix.
+    #[must_use]
+    pub fn new(prefix: Option<PolymorphPrefix>, formula: M) -> Self {
+        Self { prefix: prefix.map(|prefix| prefix.to_string()), formula }
+    }
+
+    /// Returns the labeled prefix of the formula without its trailing
+    /// hyphen, if any.
+    #[must_use]
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Returns the prefix of the formula if it is a single polymorph prefix.
+    #[must_use]
+    pub fn polymorph_prefix(&self) -> Option<PolymorphPrefix> {
+        self.prefix().and_then(polymorph_prefix)
+    }
+
+    /// Returns the prefixed formula.
+    #[must_use]
+    pub const fn formula(&self) -> &M {
+        &self.formula
+    }
+
+    /// Returns the formula with the provided optional polymorph prefix,
+    /// replacing the previous prefix.
+    #[must_use]
+    pub fn with_prefix(self, prefix: Option<PolymorphPrefix>) -> Self {
+        Self::new(prefix, self.formula)
+    }
+
+    /// Returns the formula obtained by applying the provided function to
+    /// the prefixed formula, preserving the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let ion = Prefixed::<ChemicalFormula<u32, i32>>::from_str("α-SiO4-4").unwrap();
+    /// assert_eq!(ion.map(|formula| formula.neutralize()).to_string(), "α-SiO₄");
+    /// ```
+    #[must_use]
+    pub fn map<N>(self, f: impl FnOnce(M) -> N) -> Prefixed<N> {
+        Prefixed { prefix: self.prefix, formula: f(self.formula) }
+    }
+
+    /// Returns the formula with the provided labeled prefix, as returned by
+    /// [`Prefixed::into_parts`].
+    pub(crate) fn from_parts(prefix: Option<String>, formula: M) -> Self {
+        Self { prefix, formula }
+    }
+
+    /// Consumes the formula and returns its labeled prefix and the prefixed
+    /// formula.
+    #[must_use]
+    pub fn into_parts(self) -> (Option<String>, M) {
+        (self.prefix, self.formula)
+    }
+}
+
+impl<M: MolecularFormulaMetadata> MolecularFormulaMetadata for Prefixed<M> {
+    type Count = M::Count;
+}
+
+impl<M: MolecularFormula> MolecularFormula for Prefixed<M> {
+    type Tree = M::Tree;
+
+    fn counted_mixtures(&self) -> impl Iterator<Item = (Self::Count, &Self::Tree)> {
+        self.formula.counted_mixtures()
+    }
+
+    fn counted_mixtures_mut(&mut self) -> impl Iterator<Item = (Self::Count, &mut Self::Tree)> {
+        self.formula.counted_mixtures_mut()
+    }
+
+    fn into_counted_mixtures(self) -> impl Iterator<Item = (Self::Count, Self::Tree)> {
+        self.formula.into_counted_mixtures()
+    }
+}
+
+impl<M: ChargedMolecularFormulaMetadata> ChargedMolecularFormulaMetadata for Prefixed<M> {
+    type Charge = M::Charge;
+}
+
+impl<M: From<Element>> From<Element> for Prefixed<M> {
+    fn from(element: Element) -> Self {
+        Self { prefix: None, formula: M::from(element) }
+    }
+}
+
+impl<M: FromStrWithOptions> FromStrWithOptions for Prefixed<M> {
+    fn from_str_with_options(s: &str, options: &ParserOptions) -> Result<Self, ParserError> {
+        let mut chars = s.chars().peekable();
+        let Some(prefix) = read_prefix(&mut chars)? else {
+            return Ok(Self { prefix: None, formula: M::from_str_with_options(s, options)? });
+        };
+        let formula: String = chars.collect();
+        Ok(Self { prefix: Some(prefix), formula: M::from_str_with_options(&formula, options)? })
+    }
+}
+
+impl<M: StyledDisplay> StyledDisplay for Prefixed<M> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        if let Some(prefix) = &self.prefix {
+            write!(f, "{prefix}-")?;
+        }
+        self.formula.fmt_with(style, f)
+    }
+}
+
+impl<M: Display> Display for Prefixed<M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(prefix) = &self.prefix {
+            write!(f, "{prefix}-")?;
+        }
+        write!(f, "{}", self.formula)
+    }
+}
+
+impl<M: DeepSizeOf> DeepSizeOf for Prefixed<M> {
+    fn deep_size_of_children(&self) -> usize {
+        self.prefix.deep_size_of_children() + self.formula.deep_size_of_children()
+    }
+}
+
+/// Returns whether the provided character is a greek letter, either
+/// uppercase or lowercase.
+fn is_greek_letter(c: char) -> bool {
+    matches!(c, '\u{0391}'..='\u{03a9}' | '\u{03b1}'..='\u{03c9}')
+}
+
+/// Returns the provided labeled prefix if it is a single polymorph prefix.
+pub(crate) fn polymorph_prefix(prefix: &str) -> Option<PolymorphPrefix> {
+    let mut chars = prefix.chars();
+    let prefix = PolymorphPrefix::try_from(chars.next()?).ok()?;
+    chars.next().is_none().then_some(prefix)
+}
+
+/// Returns whether the provided characters start with a label of a prefix,
+/// i.e. with a greek letter, or with a `D` or `L` configuration descriptor
+/// followed by a hyphen, so that formulas starting with these letters, such
+/// as the `La2O3` of `α-La2O3`, are not mistaken for descriptors.
+fn starts_with_label<J>(chars: &Peekable<J>) -> bool
+where
+    J: Iterator<Item = char> + Clone,
+{
+    let mut lookahead = chars.clone();
+    match lookahead.next() {
+        Some(c) if is_greek_letter(c) => true,
+        Some('D' | 'L') => lookahead.next().is_some_and(BaselineMinus::matches),
+        _ => false,
+    }
+}
+
+/// Reads the labeled prefix at the start of the provided characters, if
+/// any, returning its labels joined by hyphens.
+///
+/// # Errors
+///
+/// * If a label is not followed by a hyphen.
+pub(crate) fn read_prefix<J>(chars: &mut Peekable<J>) -> Result<Option<String>, ParserError>
+where
+    J: Iterator<Item = char> + Clone,
+{
+    if !chars.peek().copied().is_some_and(is_greek_letter) {
+        return Ok(None);
+    }
+
+    let mut prefix = String::new();
+    while starts_with_label(chars)
+        && let Some(first) = chars.next()
+    {
+        if !prefix.is_empty() {
+            prefix.push('-');
+        }
+        prefix.push(first);
+        if is_greek_letter(first) {
+            while let Some(c) = chars.next_if(|c| {
+                c.is_ascii_digit() || *c == ',' || SuperscriptDigit::try_from(*c).is_ok()
+            }) {
+                prefix.push(c);
+            }
+        }
+        // As for polymorph prefixes, anything looking like a hyphen is
+        // accepted as the end of the label.
+        let hyphen = chars.next().ok_or(ParserError::UnexpectedEndOfInput)?;
+        if !BaselineMinus::matches(hyphen) {
+            return Err(ParserError::UnexpectedCharacter(hyphen));
+        }
+    }
+    Ok(Some(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(input: &str) -> Result<Option<String>, ParserError> {
+        read_prefix(&mut input.chars().peekable())
+    }
+
+    #[test]
+    fn test_read_prefix() {
+        assert_eq!(read("H2O"), Ok(None));
+        assert_eq!(read("D2O"), Ok(None));
+        assert_eq!(read("α-C6H12O6").unwrap().as_deref(), Some("α"));
+        assert_eq!(read("α-D-C6H12O6").unwrap().as_deref(), Some("α-D"));
+        assert_eq!(read("Δ9,11-C18H30O2").unwrap().as_deref(), Some("Δ9,11"));
+        assert_eq!(read("η⁵-C5H5").unwrap().as_deref(), Some("η⁵"));
+        assert_eq!(read("α"), Err(ParserError::UnexpectedEndOfInput));
+        assert_eq!(read("αC"), Err(ParserError::UnexpectedCharacter('C')));
+        assert_eq!(read("α-D2O").unwrap().as_deref(), Some("α"));
+        assert_eq!(read("α-La2O3").unwrap().as_deref(), Some("α"));
+        assert_eq!(read("α-L-Dy2O3").unwrap().as_deref(), Some("α-L"));
+    }
+}
@@ -0,0 +1,328 @@
+//! Submodule providing formulas whose mixtures may be repeated a rational
+//! number of times, as the hemihydrates and sesquihydrates found in
+//! regulatory documents, e.g. `CaSO4.1/2H2O` or `Na2CO3.3/2H2O2`.
+//!
+//! The rational amounts are kept as multipliers of the mixtures rather than
+//! as a rational [`CountLike`] type, since the counts within the trees are
+//! converted exactly into integers to be displayed, balanced and compared,
+//! which fractional counts could not honour.
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Display, str::FromStr};
+
+use elements_rs::{Element, Isotope};
+use num_integer::Integer;
+use num_rational::Ratio;
+use num_traits::{CheckedAdd, CheckedMul, One};
+
+use crate::{
+    BaselineDigit, ChargeLike, ChargedMolecularTree, ChemicalFormula, ChemicalTree, CountLike,
+    DeepSizeOf, Dot, Empty, MolecularTree,
+    errors::{NumericError, ParserError},
+    molecular_tree::mixtures_size_of,
+    parsable::{CharacterMarker, DEFAULT_PARSER_OPTIONS, FromStrWithOptions, ParserOptions},
+    try_fold_number,
+};
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+/// Chemical formula whose mixtures are repeated a rational number of times,
+/// such as the half water molecule of the hemihydrate `CaSO4.1/2H2O`.
+///
+/// The counts within each mixture remain integral, so that the mixtures are
+/// plain chemical trees, while their multipliers are kept as exact ratios.
+/// Element counts are therefore computed exactly, and the formula may be
+/// scaled to the smallest integral formula with
+/// [`to_integral`](Self::to_integral).
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+/// use num_rational::Ratio;
+///
+/// let options = ParserOptions::new().with_fractional_multipliers(true);
+/// let plaster =
+///     RationalFormula::<u32, i32>::from_str_with_options("CaSO4.1/2H2O", &options).unwrap();
+/// assert_eq!(plaster.count_of_element(Element::H), Some(Ratio::from_integer(1)));
+/// assert_eq!(plaster.count_of_element(Element::O), Some(Ratio::new(9, 2)));
+/// assert!((plaster.isotopologue_mass() - 144.919603).abs() < 1e-6);
+///
+/// let (factor, formula) = plaster.to_integral().unwrap();
+/// assert_eq!(factor, 2);
+/// assert_eq!(formula, ChemicalFormula::from_str("2CaSO4.H2O").unwrap());
+/// ```
+pub struct RationalFormula<Count: CountLike + Integer = u16, Charge: ChargeLike = i16> {
+    /// The mixtures of the formula, with their rational multipliers.
+    mixtures: Vec<(Ratio<Count>, ChemicalTree<Count, Charge, Empty>)>,
+}
+
+impl<Count: CountLike + Integer, Charge: ChargeLike> RationalFormula<Count, Charge> {
+    /// Iterates over the mixtures of the formula, with their rational
+    /// multipliers.
+    pub fn counted_mixtures(
+        &self,
+    ) -> impl Iterator<Item = (Ratio<Count>, &ChemicalTree<Count, Charge, Empty>)> {
+        self.mixtures.iter().map(|(count, tree)| (*count, tree))
+    }
+
+    /// Returns the exact number of atoms of the provided element in the
+    /// formula, including its isotopes.
+    ///
+    /// Returns `None` if the count overflows the count type.
+    #[must_use]
+    pub fn count_of_element(&self, element: Element) -> Option<Ratio<Count>> {
+        let mut total = Ratio::from_integer(Count::ZERO);
+        for (count, tree) in &self.mixtures {
+            let atoms = Ratio::from_integer(tree.count_of_element::<Count>(element)?);
+            total = total.checked_add(&count.checked_mul(&atoms)?)?;
+        }
+        Some(total)
+    }
+
+    /// Returns the isotopologue mass of the formula, without considering its
+    /// charge.
+    #[must_use]
+    pub fn isotopologue_mass(&self) -> f64 {
+        self.weighted_sum(MolecularTree::isotopologue_mass)
+    }
+
+    /// Returns the isotopologue mass of the formula, accounting for the
+    /// electrons gained or lost by its charge.
+    #[must_use]
+    pub fn isotopologue_mass_with_charge(&self) -> f64 {
+        self.weighted_sum(ChargedMolecularTree::isotopologue_mass_with_charge)
+    }
+
+    /// Returns the molar mass of the formula.
+    #[must_use]
+    pub fn molar_mass(&self) -> f64 {
+        self.weighted_sum(ChargedMolecularTree::molar_mass)
+    }
+
+    /// Returns the overall charge of the formula.
+    #[must_use]
+    pub fn charge(&self) -> f64 {
+        self.weighted_sum(ChargedMolecularTree::charge)
+    }
+
+    /// Returns the sum of the provided property of the mixtures, weighted by
+    /// their multipliers.
+    fn weighted_sum(&self, property: impl Fn(&ChemicalTree<Count, Charge, Empty>) -> f64) -> f64 {
+        self.mixtures
+            .iter()
+            .map(|(count, tree)| {
+                let numerator: f64 = count.numer().as_();
+                let denominator: f64 = count.denom().as_();
+                numerator * property(tree) / denominator
+            })
+            .sum()
+    }
+
+    /// Returns the smallest integral multiple of the formula, alongside the
+    /// factor it was multiplied by, i.e. the least common multiple of the
+    /// denominators of the multipliers.
+    ///
+    /// Returns `None` if any of the scaled multipliers overflows the count
+    /// type.
+    #[must_use]
+    pub fn to_integral(&self) -> Option<(Count, ChemicalFormula<Count, Charge>)> {
+        let factor =
+            self.mixtures.iter().fold(Count::ONE, |factor, (count, _)| factor.lcm(count.denom()));
+        let mixtures = self
+            .mixtures
+            .iter()
+            .map(|(count, tree)| {
+                let scaled = count.checked_mul(&Ratio::from_integer(factor))?;
+                Some((scaled.to_integer(), tree.clone()))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((factor, ChemicalFormula::from_mixtures(mixtures)))
+    }
+}
+
+impl<Count: CountLike + Integer, Charge: ChargeLike> From<ChemicalFormula<Count, Charge>>
+    for RationalFormula<Count, Charge>
+{
+    fn from(formula: ChemicalFormula<Count, Charge>) -> Self {
+        Self {
+            mixtures: formula
+                .into_mixtures()
+                .into_iter()
+                .map(|(count, tree)| (Ratio::from_integer(count), tree))
+                .collect(),
+        }
+    }
+}
+
+impl<Count: CountLike + Integer, Charge: ChargeLike> FromStr for RationalFormula<Count, Charge>
+where
+    Charge: TryFrom<Count>,
+    Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+{
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
+    }
+}
+
+impl<Count: CountLike + Integer, Charge: ChargeLike> FromStrWithOptions
+    for RationalFormula<Count, Charge>
+where
+    Charge: TryFrom<Count>,
+    Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+{
+    /// Parses a formula whose mixtures may be prefixed by a fraction, such
+    /// as the `3/2` of `2Na2CO3.3/2H2O2`, written with either a slash or the
+    /// fraction slash `⁄`, if the options allow
+    /// [fractional multipliers](ParserOptions::with_fractional_multipliers),
+    /// or by a decimal number written with a comma, such as the `2,5` of
+    /// `CaSO4.2,5H2O`, if the options read the comma as a decimal separator.
+    /// Mixtures without a fraction are parsed as by [`ChemicalFormula`],
+    /// with the provided options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_decimal_comma(true);
+    /// let hydrate =
+    ///     RationalFormula::<u32, i32>::from_str_with_options("CaSO4.2,5H2O", &options).unwrap();
+    /// let fractional = ParserOptions::new().with_fractional_multipliers(true);
+    /// assert_eq!(
+    ///     hydrate,
+    ///     RationalFormula::from_str_with_options("CaSO4.5/2H2O", &fractional).unwrap()
+    /// );
+    /// assert_eq!(hydrate.to_string(), "CaSO₄.5/2H₂O");
+    /// assert!(RationalFormula::<u32, i32>::from_str("CaSO4.2,5H2O").is_err());
+    /// assert!(RationalFormula::<u32, i32>::from_str("CaSO4.5/2H2O").is_err());
+    /// ```
+    fn from_str_with_options(s: &str, options: &ParserOptions) -> Result<Self, ParserError> {
+        let separated = options.separate_middle_dots(s);
+        let mut mixtures = Vec::new();
+        for component in separated.as_deref().unwrap_or(s).split(Dot::matches) {
+            let (fraction, remainder) = match split_decimal_comma(component) {
+                Some((integer, decimals, remainder)) if options.decimal_comma() => {
+                    (decimal_ratio(integer, decimals, remainder)?, remainder)
+                }
+                _ if options.fractional_multipliers() => split_fraction(component)?,
+                _ => (Ratio::from_integer(Count::ONE), component),
+            };
+            let formula =
+                ChemicalFormula::<Count, Charge>::from_str_with_options(remainder, options)?;
+            for (count, tree) in formula.into_mixtures() {
+                let count = fraction
+                    .checked_mul(&Ratio::from_integer(count))
+                    .ok_or(NumericError::PositiveOverflow)?;
+                mixtures.push((count, tree));
+            }
+        }
+        Ok(Self { mixtures })
+    }
+}
+
+/// Splits the fraction prefixing the provided mixture component, such as
+/// the `3/2` of `3/2H2O2`, from the rest of the component, with a fraction
+/// of one if the component is not prefixed by a fraction.
+fn split_fraction<Count: CountLike + Integer>(
+    component: &str,
+) -> Result<(Ratio<Count>, &str), ParserError> {
+    let mut chars = component.chars().peekable();
+    let numerator = try_fold_number::<Count, BaselineDigit, _>(&mut chars).transpose()?;
+    let Some(numerator) =
+        numerator.filter(|_| chars.next_if(|&c| matches!(c, '/' | '⁄')).is_some())
+    else {
+        return Ok((Ratio::from_integer(Count::ONE), component));
+    };
+    let denominator =
+        try_fold_number::<Count, BaselineDigit, _>(&mut chars).transpose()?.ok_or_else(|| {
+            chars
+                .peek()
+                .map_or(ParserError::UnexpectedEndOfInput, |&c| ParserError::UnexpectedCharacter(c))
+        })?;
+    let remainder = chars.collect::<String>();
+    Ok((Ratio::new(numerator, denominator), &component[component.len() - remainder.len()..]))
+}
+
+/// Splits the decimal number written with a comma prefixing the provided
+/// mixture component, such as the `2,5` of `2,5H2O`, into its integer part,
+/// its decimals and the rest of the component, or returns `None` if the
+/// component is not prefixed by a comma, possibly preceded by digits.
+fn split_decimal_comma(component: &str) -> Option<(&str, &str, &str)> {
+    let after_integer = component.trim_start_matches(|c: char| c.is_ascii_digit());
+    let integer = &component[..component.len() - after_integer.len()];
+    let after_comma = after_integer.strip_prefix(',')?;
+    let remainder = after_comma.trim_start_matches(|c: char| c.is_ascii_digit());
+    Some((integer, &after_comma[..after_comma.len() - remainder.len()], remainder))
+}
+
+/// Returns the exact ratio of the decimal number with the provided integer
+/// part and decimals, such as five halves for `2` and `5`, followed by the
+/// provided remainder of its mixture component.
+///
+/// # Errors
+///
+/// * If either the integer part or the decimals are missing.
+/// * If the integer part has a leading zero, or if the number is zero.
+/// * If the numerator or the denominator of the ratio overflow the count type.
+fn decimal_ratio<Count: CountLike + Integer>(
+    integer: &str,
+    decimals: &str,
+    remainder: &str,
+) -> Result<Ratio<Count>, ParserError> {
+    if integer.is_empty() {
+        return Err(ParserError::UnexpectedCharacter(','));
+    }
+    if decimals.is_empty() {
+        return Err(remainder
+            .chars()
+            .next()
+            .map_or(ParserError::UnexpectedEndOfInput, ParserError::UnexpectedCharacter));
+    }
+    if integer.len() > 1 && integer.starts_with('0') {
+        return Err(NumericError::LeadingZero.into());
+    }
+    let mut numerator: u64 = 0;
+    for digit in integer.bytes().chain(decimals.bytes()) {
+        numerator = numerator
+            .checked_mul(10)
+            .and_then(|numerator| numerator.checked_add(u64::from(digit - b'0')))
+            .ok_or(NumericError::PositiveOverflow)?;
+    }
+    if numerator == 0 {
+        return Err(NumericError::LeadingZero.into());
+    }
+    let denominator = u32::try_from(decimals.len())
+        .ok()
+        .and_then(|exponent| 10_u64.checked_pow(exponent))
+        .ok_or(NumericError::PositiveOverflow)?;
+    let numerator = Count::try_from(numerator).map_err(|_| NumericError::PositiveOverflow)?;
+    let denominator = Count::try_from(denominator).map_err(|_| NumericError::PositiveOverflow)?;
+    Ok(Ratio::new(numerator, denominator))
+}
+
+impl<Count: CountLike + Integer, Charge: ChargeLike> Display for RationalFormula<Count, Charge> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, (count, tree)) in self.mixtures.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            if !count.is_one() {
+                write!(f, "{count}")?;
+            }
+            write!(f, "{tree}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<Count: CountLike + Integer, Charge: ChargeLike> DeepSizeOf for RationalFormula<Count, Charge> {
+    fn deep_size_of_children(&self) -> usize {
+        mixtures_size_of(&self.mixtures)
+    }
+}
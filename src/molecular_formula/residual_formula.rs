@@ -7,13 +7,24 @@ use alloc::vec::Vec;
 use elements_rs::{Element, Isotope};
 
 use crate::{
-    ChargeLike, ChargedMolecularFormulaMetadata, ChemicalTree, CountLike, MolecularFormulaMetadata,
-    ParsableFormula, Residual,
+    ChargeLike, ChargedMolecularFormula, ChargedMolecularFormulaMetadata, ChemicalTree, CountLike,
+    DeepSizeOf, DisplayStyle, MolecularFormula, MolecularFormulaMetadata, ParsableFormula,
+    Residual, StyledDisplay, convert_count, errors::NumericError, molecular_tree::mixtures_size_of,
 };
 
+mod enumeration;
+pub use enumeration::EnumeratedFormulas;
+
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
 /// A chemical formula which can contain residual notations.
 ///
+/// Residuals stand for unspecified groups of atoms: they are neutral, so
+/// that the charge of a residual formula is the one written in it, while
+/// their mass is unknown. The masses computed through the
+/// [`MolecularFormula`] and [`ChargedMolecularFormula`] traits only account
+/// for the atoms of the formula, and the `checked_` mass methods return
+/// `None` for formulas containing residuals.
+///
 /// # Examples
 ///
 /// ```
@@ -23,6 +34,10 @@ use crate::{
 ///
 /// let formula = ResidualFormula::<u32, i32>::from_str("C6H5R").unwrap();
 /// assert!(formula.contains_residuals());
+///
+/// let ammonium = ResidualFormula::<u32, i32>::from_str("RNH3+").unwrap();
+/// assert_eq!(ammonium.net_charge_integer(), Some(1));
+/// assert_eq!(ammonium.checked_molar_mass(), None);
 /// ```
 pub struct ResidualFormula<Count: CountLike = u16, Charge: ChargeLike = i16> {
     mixtures: Vec<(Count, ChemicalTree<Count, Charge, Residual>)>,
@@ -39,6 +54,88 @@ impl<Count: CountLike, Charge: ChargeLike> ResidualFormula<Count, Charge> {
         }
         false
     }
+
+    /// Returns the formula with all of its charges removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ResidualFormula::<u32, i32>::from_str("RNH3+").unwrap();
+    /// assert_eq!(formula.neutralize().to_string(), "RNH₃");
+    /// ```
+    #[must_use]
+    pub fn neutralize(&self) -> Self {
+        Self {
+            mixtures: self
+                .mixtures
+                .iter()
+                .map(|(count, tree)| (*count, tree.clone().neutralize().canonicalize()))
+                .collect(),
+        }
+    }
+
+    /// Returns the isotopologue mass of the formula, without considering its
+    /// charge, or `None` if it contains residuals, whose mass is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let formula = ResidualFormula::<u32, i32>::from_str("CH3COOH").unwrap();
+    /// assert!((formula.checked_isotopologue_mass().unwrap() - 60.021129).abs() < 1e-6);
+    /// let formula = ResidualFormula::<u32, i32>::from_str("RCOOH").unwrap();
+    /// assert_eq!(formula.checked_isotopologue_mass(), None);
+    /// ```
+    #[must_use]
+    pub fn checked_isotopologue_mass(&self) -> Option<f64> {
+        (!self.contains_residuals()).then(|| self.isotopologue_mass())
+    }
+
+    /// Returns the isotopologue mass of the formula, accounting for the
+    /// electrons gained or lost by its charge, or `None` if it contains
+    /// residuals, whose mass is unknown.
+    #[must_use]
+    pub fn checked_isotopologue_mass_with_charge(&self) -> Option<f64>
+    where
+        Charge: TryFrom<Count>,
+    {
+        (!self.contains_residuals()).then(|| self.isotopologue_mass_with_charge())
+    }
+
+    /// Returns the molar mass of the formula, or `None` if it contains
+    /// residuals, whose mass is unknown.
+    #[must_use]
+    pub fn checked_molar_mass(&self) -> Option<f64>
+    where
+        Charge: TryFrom<Count>,
+    {
+        (!self.contains_residuals()).then(|| self.molar_mass())
+    }
+
+    /// Consumes the formula and returns an equivalent formula using the
+    /// provided count and charge types.
+    ///
+    /// # Errors
+    ///
+    /// * If any count or charge cannot be represented by the new types.
+    pub(crate) fn try_convert<NewCount: CountLike, NewCharge: ChargeLike>(
+        self,
+    ) -> Result<ResidualFormula<NewCount, NewCharge>, NumericError> {
+        Ok(ResidualFormula {
+            mixtures: self
+                .mixtures
+                .into_iter()
+                .map(|(count, tree)| Ok((convert_count(count)?, tree.try_convert()?)))
+                .collect::<Result<_, NumericError>>()?,
+        })
+    }
 }
 
 impl<Count: CountLike, Charge: ChargeLike> From<Element> for ResidualFormula<Count, Charge> {
@@ -59,6 +156,22 @@ impl<Count: CountLike, Charge: ChargeLike> MolecularFormulaMetadata
     type Count = Count;
 }
 
+impl<Count: CountLike, Charge: ChargeLike> MolecularFormula for ResidualFormula<Count, Charge> {
+    type Tree = ChemicalTree<Count, Charge, Residual>;
+
+    fn counted_mixtures(&self) -> impl Iterator<Item = (Self::Count, &Self::Tree)> {
+        self.mixtures.iter().map(|(count, tree)| (*count, tree))
+    }
+
+    fn counted_mixtures_mut(&mut self) -> impl Iterator<Item = (Self::Count, &mut Self::Tree)> {
+        self.mixtures.iter_mut().map(|(count, tree)| (*count, tree))
+    }
+
+    fn into_counted_mixtures(self) -> impl Iterator<Item = (Self::Count, Self::Tree)> {
+        self.mixtures.into_iter()
+    }
+}
+
 impl<Count: CountLike, Charge: ChargeLike> ChargedMolecularFormulaMetadata
     for ResidualFormula<Count, Charge>
 where
@@ -77,9 +190,10 @@ where
 
     fn on_start<J>(
         _chars: &mut core::iter::Peekable<J>,
+        _options: &crate::ParserOptions,
     ) -> Result<Self::StartOutput, crate::errors::ParserError>
     where
-        J: Iterator<Item = char>,
+        J: Iterator<Item = char> + Clone,
     {
         Ok(())
     }
@@ -89,21 +203,32 @@ where
         mixtures: Vec<(Count, Self::Tree)>,
     ) -> Result<Self, crate::errors::ParserError> {
         assert!(!mixtures.is_empty(), "At least one mixture is required");
+        debug_assert!(
+            mixtures.iter().all(|(_, tree)| tree.is_canonical()),
+            "The parser must build canonical trees"
+        );
         Ok(Self { mixtures })
     }
 }
 
+impl<Count: CountLike, Charge: ChargeLike> StyledDisplay for ResidualFormula<Count, Charge> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        style.fmt_mixtures(self.mixtures.iter().map(|(count, tree)| (*count, tree)), f)
+    }
+}
+
 impl<Count: CountLike, Charge: ChargeLike> core::fmt::Display for ResidualFormula<Count, Charge> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        for (i, (fraction, tree)) in self.mixtures.iter().enumerate() {
-            if i > 0 {
-                write!(f, ".")?;
-            }
-            if *fraction != Count::one() {
-                write!(f, "{fraction}")?;
-            }
-            write!(f, "{tree}")?;
-        }
-        Ok(())
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> DeepSizeOf for ResidualFormula<Count, Charge> {
+    fn deep_size_of_children(&self) -> usize {
+        mixtures_size_of(&self.mixtures)
     }
 }
@@ -0,0 +1,173 @@
+//! Submodule providing the enumeration of the chemical formulas obtained by
+//! substituting the residuals of a residual formula with fragments from a
+//! library, as used to expand generic structures into concrete candidates.
+
+use alloc::vec::Vec;
+
+use crate::{
+    ChargeLike, ChemicalFormula, ChemicalTree, CountLike, MolecularFormula, ResidualFormula,
+    errors::EnumerationError,
+};
+
+impl<Count: CountLike, Charge: ChargeLike> ResidualFormula<Count, Charge> {
+    /// The maximal number of formulas yielded by
+    /// [`enumerate_with`](Self::enumerate_with).
+    pub const DEFAULT_ENUMERATION_LIMIT: usize = 10_000;
+
+    /// Iterates over the chemical formulas obtained by substituting each
+    /// residual of the formula with each of the provided fragments, yielding
+    /// at most [`DEFAULT_ENUMERATION_LIMIT`](Self::DEFAULT_ENUMERATION_LIMIT)
+    /// formulas.
+    ///
+    /// See [`enumerate_with_limit`](Self::enumerate_with_limit) for details.
+    ///
+    /// # Errors
+    ///
+    /// * If any fragment is made of several mixtures, or of a mixture with a
+    ///   count other than one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let amine = ResidualFormula::<u32, i32>::from_str("RNHR").unwrap();
+    /// let fragments: Vec<ChemicalFormula<u32, i32>> =
+    ///     ["CH3", "C2H5"].into_iter().map(|f| ChemicalFormula::from_str(f).unwrap()).collect();
+    /// let formulas: Vec<String> =
+    ///     amine.enumerate_with(&fragments).unwrap().map(|f| f.to_string()).collect();
+    /// assert_eq!(formulas, ["CH₃NHCH₃", "CH₃NHC₂H₅", "C₂H₅NHCH₃", "C₂H₅NHC₂H₅"]);
+    /// ```
+    pub fn enumerate_with<'a>(
+        &'a self,
+        fragments: &'a [ChemicalFormula<Count, Charge>],
+    ) -> Result<EnumeratedFormulas<'a, Count, Charge>, EnumerationError> {
+        self.enumerate_with_limit(fragments, Self::DEFAULT_ENUMERATION_LIMIT)
+    }
+
+    /// Iterates over the chemical formulas obtained by substituting each
+    /// residual of the formula with each of the provided fragments, yielding
+    /// at most the provided number of formulas.
+    ///
+    /// Each residual is substituted independently, so that a formula with
+    /// `n` residuals and `k` fragments yields `k` to the power of `n`
+    /// formulas, in lexicographic order of the fragments substituted from
+    /// the first residual to the last one. A repeated residual, such as the
+    /// one of `NR3`, counts as a single residual substituted with the same
+    /// fragment in each repetition. A formula without residuals yields
+    /// itself, while no formula is yielded if residuals are present but no
+    /// fragment is provided.
+    ///
+    /// The formulas are built lazily, so that only the formulas which are
+    /// consumed are allocated.
+    ///
+    /// # Errors
+    ///
+    /// * If any fragment is made of several mixtures, or of a mixture with a
+    ///   count other than one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let amine = ResidualFormula::<u32, i32>::from_str("NR3").unwrap();
+    /// let fragments: Vec<ChemicalFormula<u32, i32>> =
+    ///     ["H", "CH3"].into_iter().map(|f| ChemicalFormula::from_str(f).unwrap()).collect();
+    /// let formulas: Vec<String> =
+    ///     amine.enumerate_with_limit(&fragments, 1).unwrap().map(|f| f.to_string()).collect();
+    /// assert_eq!(formulas, ["NH₃"]);
+    ///
+    /// let formulas: Vec<String> =
+    ///     amine.enumerate_with_limit(&fragments, 10).unwrap().map(|f| f.to_string()).collect();
+    /// assert_eq!(formulas, ["NH₃", "N(CH₃)₃"]);
+    /// ```
+    pub fn enumerate_with_limit<'a>(
+        &'a self,
+        fragments: &'a [ChemicalFormula<Count, Charge>],
+        limit: usize,
+    ) -> Result<EnumeratedFormulas<'a, Count, Charge>, EnumerationError> {
+        let fragments: Vec<&ChemicalTree<Count, Charge, crate::Empty>> = fragments
+            .iter()
+            .enumerate()
+            .map(|(index, fragment)| {
+                let mut mixtures = fragment.counted_mixtures();
+                match (mixtures.next(), mixtures.next()) {
+                    (Some((count, tree)), None) if count.is_one() => Ok(tree),
+                    _ => Err(EnumerationError::MixtureFragment(index)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        let number_of_residuals: usize =
+            self.mixtures.iter().map(|(_, tree)| tree.number_of_extensions()).sum();
+        Ok(EnumeratedFormulas {
+            formula: self,
+            exhausted: fragments.is_empty() && number_of_residuals > 0,
+            fragments,
+            current: alloc::vec![0; number_of_residuals],
+            remaining: limit,
+        })
+    }
+}
+
+/// Iterator over the chemical formulas obtained by substituting the
+/// residuals of a residual formula with fragments, as returned by
+/// [`ResidualFormula::enumerate_with`].
+pub struct EnumeratedFormulas<'a, Count: CountLike, Charge: ChargeLike> {
+    /// The residual formula whose residuals are substituted.
+    formula: &'a ResidualFormula<Count, Charge>,
+    /// The trees of the fragments substituted to the residuals.
+    fragments: Vec<&'a ChemicalTree<Count, Charge, crate::Empty>>,
+    /// The index of the fragment substituted to each residual.
+    current: Vec<usize>,
+    /// The number of formulas which may still be yielded.
+    remaining: usize,
+    /// Whether the iteration is complete.
+    exhausted: bool,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> EnumeratedFormulas<'_, Count, Charge> {
+    /// Advances to the next combination of fragments, returning whether a new
+    /// one was found.
+    fn advance(&mut self) -> bool {
+        for index in self.current.iter_mut().rev() {
+            if *index + 1 < self.fragments.len() {
+                *index += 1;
+                return true;
+            }
+            // We reset the digit and carry over to the previous one.
+            *index = 0;
+        }
+        false
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Iterator for EnumeratedFormulas<'_, Count, Charge> {
+    type Item = ChemicalFormula<Count, Charge>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted || self.remaining == 0 {
+            return None;
+        }
+        let mut indices = self.current.iter();
+        let mixtures = self
+            .formula
+            .mixtures
+            .iter()
+            .map(|(count, tree)| {
+                let tree = tree.clone().substitute_extensions(&mut |_| {
+                    let index = indices.next().expect("Each residual has a fragment index");
+                    self.fragments[*index].clone()
+                });
+                (*count, tree)
+            })
+            .collect();
+        self.remaining -= 1;
+        self.exhausted = !self.advance();
+        Some(ChemicalFormula::from_mixtures(mixtures))
+    }
+}
@@ -0,0 +1,258 @@
+//! Submodule providing the `TypeTagged` wrapper, which serializes any
+//! molecular formula alongside the names of its count and charge types, and
+//! the `NumericTypes` trait validating that the counts and charges written in
+//! a formula fit in those types.
+
+use alloc::string::ToString;
+use core::any::type_name;
+
+use crate::{
+    Annotated, ChargeLike, ChemicalFormula, CountLike, InChIFormula, InchiToken, MineralFormula,
+    MolecularFormulaMetadata, Prefixed, ResidualFormula, Token, errors::RangeError, tokenize,
+};
+
+#[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash, Default)]
+/// Molecular formula of any type, serialized in a self-describing mode
+/// alongside the names of its count and charge types.
+///
+/// Formulas are serialized as their display, which does not record the
+/// numeric types they were built with: a formula serialized with `u32`
+/// counts may not fit in the `u16` counts it is deserialized into. The
+/// wrapper serializes the formula as a `count`, `charge` and `formula`
+/// struct, and when deserializing validates that the counts and charges of
+/// the formula fit in the target types, naming the tags and the offending
+/// count in the error otherwise. The tags are written by the serializer and
+/// only used to explain the errors, so that formulas fitting in the target
+/// types are deserialized whatever the types they were serialized with. The
+/// `charge` tag is `null` for formulas without charge, such as
+/// [`InChIFormula`].
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let polymer = ChemicalFormula::<u32, i32>::from_str("C70000H140002").unwrap();
+/// let serialized = serde_json::to_string(&TypeTagged::new(polymer)).unwrap();
+/// assert_eq!(
+///     serialized,
+///     r#"{"count":"u32","charge":"i32","formula":"C₇₀₀₀₀H₁₄₀₀₀₂"}"#
+/// );
+///
+/// let error = serde_json::from_str::<TypeTagged<ChemicalFormula<u16, i16>>>(&serialized)
+///     .unwrap_err()
+///     .to_string();
+/// assert!(error.starts_with(
+///     "The formula serialized with `u32` counts and `i32` charges cannot be deserialized with \
+///      `u16` counts and `i16` charges: The count 70000 at byte 1 of 'C₇₀₀₀₀H₁₄₀₀₀₂' exceeds the \
+///      range of `u16`."
+/// ));
+///
+/// let small = r#"{"count":"u32","charge":"i32","formula":"C₆H₁₂O₆"}"#;
+/// let glucose: TypeTagged<ChemicalFormula<u16, i16>> = serde_json::from_str(small).unwrap();
+/// assert_eq!(glucose.formula().to_string(), "C₆H₁₂O₆");
+/// ```
+pub struct TypeTagged<M> {
+    /// The tagged formula.
+    formula: M,
+}
+
+impl<M> TypeTagged<M> {
+    /// Creates a new formula serialized alongside its numeric types.
+    #[must_use]
+    pub const fn new(formula: M) -> Self {
+        Self { formula }
+    }
+
+    /// Returns the tagged formula.
+    #[must_use]
+    pub const fn formula(&self) -> &M {
+        &self.formula
+    }
+
+    /// Consumes the wrapper and returns the tagged formula.
+    #[must_use]
+    pub fn into_inner(self) -> M {
+        self.formula
+    }
+}
+
+impl<M> From<M> for TypeTagged<M> {
+    fn from(formula: M) -> Self {
+        Self::new(formula)
+    }
+}
+
+impl<M> AsRef<M> for TypeTagged<M> {
+    fn as_ref(&self) -> &M {
+        &self.formula
+    }
+}
+
+/// Trait providing the names of the numeric types of a formula, and the
+/// validation that the counts and charges written in a formula fit in them.
+pub trait NumericTypes: MolecularFormulaMetadata {
+    /// Returns the name of the count type, e.g. `u32`.
+    #[must_use]
+    fn count_type_name() -> &'static str {
+        type_name::<Self::Count>()
+    }
+
+    /// Returns the name of the charge type, e.g. `i32`, or `None` if the
+    /// formula carries no charge.
+    #[must_use]
+    fn charge_type_name() -> Option<&'static str>;
+
+    /// Returns the error naming the first count or charge written in the
+    /// provided formula which does not fit in the numeric types, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let error = ChemicalFormula::<u16, i8>::range_error("SO4-200").unwrap();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "The charge -200 at byte 3 of 'SO4-200' exceeds the range of `i8`."
+    /// );
+    /// assert_eq!(ChemicalFormula::<u16, i8>::range_error("C60000"), None);
+    /// ```
+    #[must_use]
+    fn range_error(formula: &str) -> Option<RangeError>;
+}
+
+impl<Count: CountLike, Charge: ChargeLike> NumericTypes for ChemicalFormula<Count, Charge> {
+    fn charge_type_name() -> Option<&'static str> {
+        Some(type_name::<Charge>())
+    }
+
+    fn range_error(formula: &str) -> Option<RangeError> {
+        first_out_of_range::<Count, Charge>(formula)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> NumericTypes for MineralFormula<Count, Charge> {
+    fn charge_type_name() -> Option<&'static str> {
+        Some(type_name::<Charge>())
+    }
+
+    fn range_error(formula: &str) -> Option<RangeError> {
+        first_out_of_range::<Count, Charge>(formula)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> NumericTypes for ResidualFormula<Count, Charge> {
+    fn charge_type_name() -> Option<&'static str> {
+        Some(type_name::<Charge>())
+    }
+
+    fn range_error(formula: &str) -> Option<RangeError> {
+        first_out_of_range::<Count, Charge>(formula)
+    }
+}
+
+impl<Count: CountLike> NumericTypes for InChIFormula<Count> {
+    fn charge_type_name() -> Option<&'static str> {
+        None
+    }
+
+    fn range_error(formula: &str) -> Option<RangeError> {
+        first_out_of_range::<Count, i64>(formula)
+    }
+}
+
+impl<M: NumericTypes> NumericTypes for Prefixed<M> {
+    fn charge_type_name() -> Option<&'static str> {
+        M::charge_type_name()
+    }
+
+    fn range_error(formula: &str) -> Option<RangeError> {
+        M::range_error(formula)
+    }
+}
+
+impl<M: NumericTypes, T> NumericTypes for Annotated<M, T> {
+    fn charge_type_name() -> Option<&'static str> {
+        M::charge_type_name()
+    }
+
+    fn range_error(formula: &str) -> Option<RangeError> {
+        M::range_error(formula)
+    }
+}
+
+/// Returns the error naming the first count or charge of the provided
+/// formula which does not fit in the provided types, if any.
+///
+/// The formula is read with the widest count and charge types. The
+/// characters which cannot be read, such as prefixes, are skipped, so that
+/// the counts written after them are validated as well.
+fn first_out_of_range<Count: CountLike, Charge: ChargeLike>(formula: &str) -> Option<RangeError> {
+    let mut offset = 0;
+    while offset < formula.len() {
+        let rest = &formula[offset..];
+        let mut skipped = None;
+        for token in tokenize::<u64, i64>(rest) {
+            match token {
+                Ok(token) => {
+                    let position = offset + token.span().start;
+                    match *token.value() {
+                        Token::Inchi(InchiToken::Count(count))
+                            if Count::try_from(count).is_err() =>
+                        {
+                            return Some(RangeError::Count {
+                                formula: formula.to_string(),
+                                position,
+                                count,
+                                type_name: type_name::<Count>(),
+                            });
+                        }
+                        Token::Charge(charge) if Charge::try_from(charge).is_err() => {
+                            return Some(RangeError::Charge {
+                                formula: formula.to_string(),
+                                position,
+                                charge,
+                                type_name: type_name::<Charge>(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                Err(error) => skipped = Some(error.span().end),
+            }
+        }
+        let skipped = skipped?;
+        // The failing token is skipped, advancing at least by a character.
+        let next = rest.chars().next().map_or(1, char::len_utf8);
+        offset += skipped.max(next);
+        while !formula.is_char_boundary(offset) {
+            offset += 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_out_of_range() {
+        assert_eq!(first_out_of_range::<u16, i16>("C6H12O6"), None);
+        assert!(matches!(
+            first_out_of_range::<u16, i16>("α-D-C6H12O70000"),
+            Some(RangeError::Count { position: 11, count: 70000, .. })
+        ));
+        assert!(matches!(
+            first_out_of_range::<u8, i8>("(Mg,Fe)2SiO300"),
+            Some(RangeError::Count { position: 11, count: 300, .. })
+        ));
+        assert!(matches!(
+            first_out_of_range::<u8, i8>("C₃₀₀"),
+            Some(RangeError::Count { position: 1, count: 300, .. })
+        ));
+    }
+}
@@ -1,13 +1,22 @@
 //! Properties that can be computed from trees of molecular nodes.
 
-use crate::prelude::Element;
+use crate::{MassContext, prelude::Element};
 mod blankets;
 mod chemical_tree;
+#[cfg(feature = "arena")]
+mod chemical_tree_ref;
+mod deep_size;
 mod inchi_tree;
+mod subtree;
 
-pub(crate) use chemical_tree::ChemicalTree;
+pub use chemical_tree::ChemicalTree;
+#[cfg(feature = "arena")]
+pub use chemical_tree_ref::ChemicalTreeRef;
+pub use deep_size::DeepSizeOf;
+pub(crate) use deep_size::mixtures_size_of;
 pub(crate) use inchi_tree::InChITree;
 use num_traits::{CheckedAdd, CheckedMul, ConstOne, ConstZero};
+pub(crate) use subtree::{Subtree, into_inner, make_mut};
 
 /// Helper to check if two elements are in Hill order.
 #[must_use]
@@ -32,6 +41,20 @@ pub fn is_hill_sorted_pair(prev: Element, next: Element, has_carbon: bool) -> bo
     }
 }
 
+/// Sorts the provided elements according to the Hill system.
+pub(crate) fn hill_sort(elements: &mut [Element]) {
+    let has_carbon = elements.contains(&Element::C);
+    elements.sort_unstable_by(|&prev, &next| {
+        if prev == next {
+            core::cmp::Ordering::Equal
+        } else if is_hill_sorted_pair(prev, next, has_carbon) {
+            core::cmp::Ordering::Less
+        } else {
+            core::cmp::Ordering::Greater
+        }
+    });
+}
+
 /// Trait for computing various molecular properties.
 pub trait MolecularTree<Count>: Sized {
     /// Type of the element iterator.
@@ -90,9 +113,22 @@ pub trait MolecularTree<Count>: Sized {
         C: From<Count> + CheckedAdd + CheckedMul + ConstZero + ConstOne;
     /// Returns the total number of elements in the molecular tree.
     fn number_of_elements(&self) -> usize;
+    /// Returns the total number of elements in the molecular tree.
+    ///
+    /// Returns None if the provided data type C cannot represent the count.
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count> + CheckedAdd + CheckedMul + ConstZero + ConstOne;
+    /// Returns the mask of the elements present in the molecular tree,
+    /// including the elements of its isotopes.
+    fn element_mask(&self) -> elements_rs::ElementMask;
     /// Returns the isotopologue mass of the molecular tree without considering
     /// any charge.
     fn isotopologue_mass(&self) -> f64;
+    /// Returns the isotopologue mass of the molecular tree without considering
+    /// any charge, using the isotope masses and compositions of the provided
+    /// context.
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64;
 
     /// Returns whether the molecular tree is a noble gas compound.
     fn is_noble_gas_compound(&self) -> bool;
@@ -132,11 +168,21 @@ pub trait ChargedMolecularTree<Count, Charge>: MolecularTree<Count> {
     /// Returns the charge of the molecular tree.
     fn charge(&self) -> f64;
 
+    /// Returns the charge of the molecular tree as an integer, or `None` if
+    /// it cannot be represented by an `i64`. Extensions, such as residuals,
+    /// are neutral.
+    fn net_charge_integer(&self) -> Option<i64>;
+
     /// Returns the isotopologue mass with charge considered.
     fn isotopologue_mass_with_charge(&self) -> f64;
 
     /// Returns the molar mass.
     fn molar_mass(&self) -> f64;
+
+    /// Returns the molar mass, using the isotope masses and isotopic
+    /// compositions of the provided context. Explicit isotopes weigh as
+    /// their isotope rather than as their element.
+    fn molar_mass_with(&self, context: &MassContext) -> f64;
 }
 
 #[cfg(test)]
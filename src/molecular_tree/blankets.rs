@@ -1,10 +1,8 @@
 //! Blanket implementations for molecular trees.
 
-use alloc::boxed::Box;
+use crate::{ChargeLike, ChargedMolecularTree, CountLike, MassContext, MolecularTree, Subtree};
 
-use crate::{ChargeLike, ChargedMolecularTree, CountLike, MolecularTree};
-
-impl<T: MolecularTree<Count>, Count: CountLike> MolecularTree<Count> for Box<T> {
+impl<T: MolecularTree<Count>, Count: CountLike> MolecularTree<Count> for Subtree<T> {
     type ElementIter<'a>
         = T::ElementIter<'a>
     where
@@ -55,6 +53,23 @@ impl<T: MolecularTree<Count>, Count: CountLike> MolecularTree<Count> for Box<T>
         (**self).number_of_elements()
     }
 
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        (**self).try_number_of_elements::<C>()
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        (**self).element_mask()
+    }
+
     #[inline]
     fn count_of_element<C>(&self, element: elements_rs::Element) -> Option<C>
     where
@@ -83,12 +98,16 @@ impl<T: MolecularTree<Count>, Count: CountLike> MolecularTree<Count> for Box<T>
         (**self).isotopologue_mass()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        (**self).isotopologue_mass_with(context)
+    }
+
     fn is_noble_gas_compound(&self) -> bool {
         (**self).is_noble_gas_compound()
     }
 
     fn isotopic_normalization(&self) -> Self {
-        Box::new((**self).isotopic_normalization())
+        Subtree::new((**self).isotopic_normalization())
     }
 
     fn check_hill_ordering(
@@ -101,12 +120,16 @@ impl<T: MolecularTree<Count>, Count: CountLike> MolecularTree<Count> for Box<T>
 }
 
 impl<T: ChargedMolecularTree<Count, Charge>, Count: CountLike, Charge: ChargeLike>
-    ChargedMolecularTree<Count, Charge> for Box<T>
+    ChargedMolecularTree<Count, Charge> for Subtree<T>
 {
     fn charge(&self) -> f64 {
         (**self).charge()
     }
 
+    fn net_charge_integer(&self) -> Option<i64> {
+        (**self).net_charge_integer()
+    }
+
     fn isotopologue_mass_with_charge(&self) -> f64 {
         (**self).isotopologue_mass_with_charge()
     }
@@ -114,4 +137,8 @@ impl<T: ChargedMolecularTree<Count, Charge>, Count: CountLike, Charge: ChargeLik
     fn molar_mass(&self) -> f64 {
         (**self).molar_mass()
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        (**self).molar_mass_with(context)
+    }
 }
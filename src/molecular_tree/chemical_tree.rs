@@ -1,14 +1,20 @@
 //! General enumeration for chemical tree nodes.
 
-use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::fmt::Display;
 
-use elements_rs::ElementVariant;
+use elements_rs::{ElementVariant, MassNumber};
 
 use crate::{
-    ChargeLike, ChargedMolecularTree, Complex, CountLike, MolecularTree, display_isotope,
-    errors::{NumericError, ParserError},
-    prelude::{BracketNode, ChargeNode, Element, Isotope, RadicalNode, RepeatNode, SequenceNode},
+    ChargeLike, ChargedMolecularTree, Complex, CountLike, DisplayStyle, Empty, MassContext,
+    MolecularTree, ParserOptions, StyledDisplay, Subtree, convert_charge, convert_count,
+    errors::{NumericError, ParserError, ParserLimit},
+    molecular_tree::{hill_sort, into_inner, make_mut},
+    prelude::{
+        BracketNode, ChargeNode, Element, Isotope, PackedRepeatNode, RadicalNode, RepeatNode,
+        SequenceNode,
+    },
+    saturating_u64,
 };
 
 mod chemical_tree_element_iter;
@@ -17,21 +23,34 @@ use chemical_tree_element_iter::{ChemicalTreeElementIter, ChemicalTreeNonHydroge
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Enumeration of chemical tree nodes.
+///
+/// The size of the nodes is that of their largest variant, the vector of
+/// the sequences, and does not depend on the count and charge types up to
+/// 64 bits. The memory used by large collections of formulas is instead
+/// dominated by the allocation of the subtrees, so that repeats of a single
+/// element at most 255 times, by far the most common ones, are packed
+/// inline in a [`PackedRepeat`](Self::PackedRepeat) node, while larger
+/// repeats fall back to a [`Repeat`](Self::Repeat) node whatever the count
+/// type. The trees built by the parser and by the crate always pack the
+/// repeats which can be packed, and their memory usage can be measured with
+/// [`deep_size_of`](crate::DeepSizeOf::deep_size_of).
 pub enum ChemicalTree<Count: CountLike, Charge: ChargeLike, Extension> {
     /// An atom (element)
     Element(Element),
     /// An isotope (element with mass number)
     Isotope(Isotope),
     /// A left-hand side radical.
-    Radical(RadicalNode<Box<Self>>),
+    Radical(RadicalNode<Subtree<Self>>),
     /// An ion (element or molecule with charge)
-    Charge(ChargeNode<Charge, Box<Self>>),
+    Charge(ChargeNode<Charge, Subtree<Self>>),
     /// Number of molecules
-    Repeat(RepeatNode<Count, Box<Self>>),
+    Repeat(RepeatNode<Count, Subtree<Self>>),
+    /// Number of atoms of a single element, packed inline
+    PackedRepeat(PackedRepeatNode),
     /// A sequence of molecular formulas
     Sequence(SequenceNode<Self>),
     /// A repeating unit wrapped in round brackets
-    Unit(BracketNode<Box<Self>>),
+    Unit(BracketNode<Subtree<Self>>),
     /// An extension node for arbitrary extensions
     Extension(Extension),
 }
@@ -53,28 +72,61 @@ impl<Count: CountLike, Charge: ChargeLike, Extension> From<Isotope>
 }
 
 impl<Count: CountLike, Charge: ChargeLike, Extension> ChemicalTree<Count, Charge, Extension> {
+    /// Consumes the chemical tree and returns it repeated the provided number
+    /// of times, packing the repeats of a single element which fit in a
+    /// [`PackedRepeatNode`].
+    pub(crate) fn into_repeat(self, count: Count) -> Self {
+        if let Self::Element(element) = self
+            && let Some(packed) = PackedRepeatNode::new(element, count)
+        {
+            return Self::PackedRepeat(packed);
+        }
+        Self::Repeat(RepeatNode::new(count, Subtree::new(self)))
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike, Extension: Clone>
+    ChemicalTree<Count, Charge, Extension>
+{
     /// Consumes the chemical tree and returns a version decorated with a
     /// left-hand side radical.
+    ///
+    /// As two consecutive radical dots cannot be parsed, a tree already
+    /// decorated with a left-hand side radical is first wrapped in round
+    /// brackets.
     pub(crate) fn left_radical(self) -> Self {
-        Self::Radical(RadicalNode::left(Box::new(self)))
+        let tree = if self.is_left_radical() { self.round() } else { self };
+        Self::Radical(RadicalNode::left(Subtree::new(tree)))
     }
 
     /// Consumes the chemical tree and returns a version decorated with a
     /// right-hand side radical.
+    ///
+    /// As two consecutive radical dots cannot be parsed, a tree already
+    /// decorated with a right-hand side radical is first wrapped in round
+    /// brackets.
     pub(crate) fn right_radical(self) -> Self {
-        Self::Radical(RadicalNode::right(Box::new(self)))
+        match self {
+            Self::Radical(node) if node.is_left() => {
+                Self::Radical(node.map(|n| Subtree::new(into_inner(n).right_radical())))
+            }
+            tree @ Self::Radical(_) => {
+                Self::Radical(RadicalNode::right(Subtree::new(tree.round())))
+            }
+            tree => Self::Radical(RadicalNode::right(Subtree::new(tree))),
+        }
     }
 
     #[inline]
     /// Wraps the chemical tree into square brackets.
     pub(crate) fn square(self) -> Self {
-        if self.is_leaf() { self } else { Self::Unit(BracketNode::square(Box::new(self))) }
+        if self.is_leaf() { self } else { Self::Unit(BracketNode::square(Subtree::new(self))) }
     }
 
     #[inline]
     /// Wraps the chemical tree into round brackets.
     pub(crate) fn round(self) -> Self {
-        if self.is_leaf() { self } else { Self::Unit(BracketNode::round(Box::new(self))) }
+        if self.is_leaf() { self } else { Self::Unit(BracketNode::round(Subtree::new(self))) }
     }
 
     /// Consumes the chemical tree and returns a version decorated with an
@@ -92,6 +144,16 @@ impl<Count: CountLike, Charge: ChargeLike, Extension> ChemicalTree<Count, Charge
             return Err(ParserError::EmptyMolecularTree);
         }
 
+        // A leading radical decorates all of the unit following it, including
+        // its trailing charge.
+        if let Self::Radical(node) = self {
+            return Ok(if node.is_left() {
+                Self::Radical(node.try_map(|n| into_inner(n).charge(charge).map(Subtree::new))?)
+            } else {
+                Self::Charge(ChargeNode::new(charge, Subtree::new(Self::Radical(node))))
+            });
+        }
+
         Ok(if let Self::Charge(charge_node) = self {
             charge = charge_node.charge.checked_add(&charge).ok_or(
                 if charge_node.charge > Charge::ZERO && charge > Charge::ZERO {
@@ -101,25 +163,260 @@ impl<Count: CountLike, Charge: ChargeLike, Extension> ChemicalTree<Count, Charge
                 },
             )?;
             if charge.is_zero() {
-                *charge_node.into_tree()
+                into_inner(charge_node.into_tree())
             } else {
                 Self::Charge(ChargeNode::new(charge, charge_node.into_tree()))
             }
         } else {
-            Self::Charge(ChargeNode::new(charge, Box::new(self)))
+            Self::Charge(ChargeNode::new(charge, Subtree::new(self)))
         })
     }
 
     /// Consumes the chemical tree and returns a version decorated with a
     /// repeat specifier.
     pub(crate) fn repeat(self, count: Count) -> Self {
-        if let Self::Sequence(mut sequence) = self {
-            assert!(!sequence.is_empty());
-            let last = sequence.pop().unwrap().repeat(count);
-            sequence.push(last);
-            Self::Sequence(sequence)
-        } else {
-            Self::Repeat(RepeatNode::new(count, Box::new(self)))
+        match self {
+            Self::Sequence(mut sequence) => {
+                assert!(!sequence.is_empty());
+                let last = sequence.pop().unwrap().repeat(count);
+                sequence.push(last);
+                Self::Sequence(sequence)
+            }
+            Self::Radical(node) if node.is_left() => {
+                Self::Radical(node.map(|n| Subtree::new(into_inner(n).repeat(count))))
+            }
+            tree => tree.into_repeat(count),
+        }
+    }
+
+    /// Returns the number of brackets, charges and radicals enclosing the
+    /// most nested atom of the tree, which the parser and the public
+    /// builders keep within the
+    /// [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    pub(crate) fn nesting_depth(&self) -> usize {
+        match self {
+            Self::Element(_) | Self::Isotope(_) | Self::PackedRepeat(_) | Self::Extension(_) => 0,
+            Self::Radical(node) => 1 + node.as_ref().nesting_depth(),
+            Self::Charge(node) => 1 + node.as_ref().nesting_depth(),
+            Self::Unit(node) => 1 + node.as_ref().nesting_depth(),
+            Self::Repeat(node) => node.as_ref().nesting_depth(),
+            Self::Sequence(sequence) => {
+                sequence.iter().map(Self::nesting_depth).max().unwrap_or_default()
+            }
+        }
+    }
+
+    /// Returns the tree unchanged if it is nested within the
+    /// [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    fn within_nesting_limit(self) -> Result<Self, ParserError> {
+        if self.nesting_depth() > ParserOptions::MAX_NESTING_DEPTH {
+            return Err(ParserError::LimitExceeded(ParserLimit::Depth));
+        }
+        Ok(self)
+    }
+
+    /// Returns the tree followed by the provided node, as the parser would
+    /// build it when reading the display of the node right after the display
+    /// of the tree.
+    ///
+    /// A node following a leading radical is pushed within it, as the
+    /// radical decorates all of the unit following it.
+    ///
+    /// # Errors
+    ///
+    /// * If the resulting tree would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let hydroxide =
+    ///     ChemicalTree::<u32, i32, Empty>::from(Element::O).followed_by(Element::H.into()).unwrap();
+    /// assert_eq!(ChemicalFormula::from(hydroxide).to_string(), "OH");
+    /// ```
+    pub fn followed_by(self, node: Self) -> Result<Self, ParserError> {
+        // The nesting of the tree is unchanged, so that only the nesting of
+        // the node, possibly within a leading radical, needs to be checked.
+        let enclosing = usize::from(self.is_left_radical());
+        if node.nesting_depth() + enclosing > ParserOptions::MAX_NESTING_DEPTH {
+            return Err(ParserError::LimitExceeded(ParserLimit::Depth));
+        }
+        Ok(self.push(node))
+    }
+
+    /// Returns the tree wrapped into round brackets, or the tree itself if
+    /// it is a single atom.
+    ///
+    /// # Errors
+    ///
+    /// * If the resulting tree would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use molecular_formulas::{
+    ///     errors::{ParserError, ParserLimit},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let mut tree = ChemicalTree::<u32, i32, Empty>::from(Element::H);
+    /// let error = loop {
+    ///     match tree.followed_by(Element::C.into()).and_then(ChemicalTree::in_round_brackets) {
+    ///         Ok(nested) => tree = nested,
+    ///         Err(error) => break error,
+    ///     }
+    /// };
+    /// assert_eq!(error, ParserError::LimitExceeded(ParserLimit::Depth));
+    /// ```
+    pub fn in_round_brackets(self) -> Result<Self, ParserError> {
+        self.round().within_nesting_limit()
+    }
+
+    /// Returns the tree wrapped into square brackets, or the tree itself if
+    /// it is a single atom.
+    ///
+    /// # Errors
+    ///
+    /// * If the resulting tree would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    pub fn in_square_brackets(self) -> Result<Self, ParserError> {
+        self.square().within_nesting_limit()
+    }
+
+    /// Returns the tree decorated with a leading radical, as in `·CH3`.
+    ///
+    /// As two consecutive radical dots cannot be parsed, a tree already
+    /// decorated with a leading radical is first wrapped in round brackets.
+    ///
+    /// # Errors
+    ///
+    /// * If the resulting tree would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    pub fn with_left_radical(self) -> Result<Self, ParserError> {
+        self.left_radical().within_nesting_limit()
+    }
+
+    /// Returns the tree decorated with a trailing radical, as in `CH3·`.
+    ///
+    /// As two consecutive radical dots cannot be parsed, a tree already
+    /// decorated with a trailing radical is first wrapped in round brackets.
+    ///
+    /// # Errors
+    ///
+    /// * If the resulting tree would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    pub fn with_right_radical(self) -> Result<Self, ParserError> {
+        self.right_radical().within_nesting_limit()
+    }
+
+    /// Returns the tree decorated with the provided charge, as the parser
+    /// would decorate it when reading the charge right after its display.
+    ///
+    /// A charge added to a charged tree is merged with its charge, and the
+    /// charge is dropped if they cancel out. A charge added to a tree with a
+    /// leading radical decorates the unit following the radical.
+    ///
+    /// # Errors
+    ///
+    /// * If the charge is zero, as neutral species must omit the charge.
+    /// * If the tree is an empty sequence.
+    /// * If the merged charge cannot be represented by the charge type.
+    /// * If the resulting tree would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let cyanide =
+    ///     ChemicalTree::<u32, i32, Empty>::from(Element::C).followed_by(Element::N.into()).unwrap();
+    /// let ferrocyanide = ChemicalTree::from(Element::Fe)
+    ///     .followed_by(cyanide.in_round_brackets().unwrap().repeated(6).unwrap())
+    ///     .and_then(ChemicalTree::in_square_brackets)
+    ///     .and_then(|tree| tree.charged(-4))
+    ///     .unwrap();
+    /// assert_eq!(ChemicalFormula::from(ferrocyanide).to_string(), "[Fe(CN)₆]⁴⁻");
+    ///
+    /// let ammonium = ChemicalTree::<u32, i32, Empty>::from(Element::N).charged(1).unwrap();
+    /// assert_eq!(ammonium.charged(-1).unwrap(), ChemicalTree::from(Element::N));
+    /// assert_eq!(
+    ///     ChemicalTree::<u32, i32, Empty>::from(Element::N).charged(0),
+    ///     Err(molecular_formulas::errors::ParserError::ZeroCharge)
+    /// );
+    /// ```
+    pub fn charged(self, charge: Charge) -> Result<Self, ParserError> {
+        if charge.is_zero() {
+            return Err(ParserError::ZeroCharge);
+        }
+        self.charge(charge)?.within_nesting_limit()
+    }
+
+    /// Returns the tree with its last unit repeated the provided number of
+    /// times, as the parser would repeat it when reading the count right
+    /// after its display.
+    ///
+    /// A count of one leaves the tree untouched, and a unit which is
+    /// already repeated is first wrapped in round brackets, as in `(H2)3`.
+    /// To repeat a whole sequence, first wrap it in brackets with
+    /// [`in_round_brackets`](Self::in_round_brackets) or
+    /// [`in_square_brackets`](Self::in_square_brackets).
+    ///
+    /// # Errors
+    ///
+    /// * If the tree is an empty sequence, or if the count is zero, as absent
+    ///   groups must be omitted.
+    /// * If the resulting tree would be nested beyond the
+    ///   [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let methyl = ChemicalTree::<u32, i32, Empty>::from(Element::C)
+    ///     .followed_by(ChemicalTree::from(Element::H).repeated(3).unwrap())
+    ///     .unwrap();
+    /// let trimethylamine = methyl
+    ///     .in_round_brackets()
+    ///     .and_then(|tree| tree.repeated(3))
+    ///     .and_then(|tree| tree.followed_by(Element::N.into()))
+    ///     .unwrap();
+    /// assert_eq!(ChemicalFormula::from(trimethylamine).to_string(), "(CH₃)₃N");
+    /// ```
+    pub fn repeated(self, count: Count) -> Result<Self, ParserError> {
+        if count.is_zero() || self.is_empty_sequence() {
+            return Err(ParserError::EmptyMolecularTree);
+        }
+        if count.is_one() {
+            return Ok(self);
+        }
+        self.repeat_bracketed(count).within_nesting_limit()
+    }
+
+    /// Returns whether the tree is an empty sequence.
+    fn is_empty_sequence(&self) -> bool {
+        matches!(self, Self::Sequence(sequence) if sequence.is_empty())
+    }
+
+    /// Repeats the last unit of the tree, as [`repeat`](Self::repeat) does,
+    /// first wrapping it in round brackets if it is already repeated so that
+    /// the two counts are not displayed as a single one.
+    fn repeat_bracketed(self, count: Count) -> Self {
+        match self {
+            Self::Sequence(mut sequence) => {
+                let last = sequence.pop().unwrap().repeat_bracketed(count);
+                sequence.push(last);
+                Self::Sequence(sequence)
+            }
+            Self::Radical(node) if node.is_left() => {
+                Self::Radical(node.map(|n| Subtree::new(into_inner(n).repeat_bracketed(count))))
+            }
+            tree @ (Self::Repeat(_) | Self::PackedRepeat(_)) => tree.round().repeat(count),
+            tree => tree.repeat(count),
         }
     }
 
@@ -132,7 +429,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension> ChemicalTree<Count, Charge
     /// Returns whether the chemical tree contains an extension node.
     pub(crate) fn contains_extension(&self) -> bool {
         match self {
-            Self::Element(_) | Self::Isotope(_) => false,
+            Self::Element(_) | Self::Isotope(_) | Self::PackedRepeat(_) => false,
             Self::Radical(r) => r.as_ref().contains_extension(),
             Self::Charge(c) => c.as_ref().contains_extension(),
             Self::Repeat(r) => r.as_ref().contains_extension(),
@@ -142,97 +439,261 @@ impl<Count: CountLike, Charge: ChargeLike, Extension> ChemicalTree<Count, Charge
         }
     }
 
+    /// Returns the number of extension nodes of the chemical tree.
+    pub(crate) fn number_of_extensions(&self) -> usize {
+        match self {
+            Self::Element(_) | Self::Isotope(_) | Self::PackedRepeat(_) => 0,
+            Self::Radical(r) => r.as_ref().number_of_extensions(),
+            Self::Charge(c) => c.as_ref().number_of_extensions(),
+            Self::Repeat(r) => r.as_ref().number_of_extensions(),
+            Self::Sequence(s) => s.iter().map(Self::number_of_extensions).sum(),
+            Self::Unit(b) => b.as_ref().number_of_extensions(),
+            Self::Extension(_) => 1,
+        }
+    }
+
+    /// Consumes the chemical tree and returns it with each of its extension
+    /// nodes replaced by the tree returned by the provided function, which
+    /// is called on the extension nodes in order of appearance.
+    ///
+    /// Substituted sequences are spliced into the enclosing sequence, and
+    /// wrapped in round brackets when the extension node is repeated.
+    pub(crate) fn substitute_extensions<NewExtension: Clone>(
+        self,
+        substitute: &mut impl FnMut(Extension) -> ChemicalTree<Count, Charge, NewExtension>,
+    ) -> ChemicalTree<Count, Charge, NewExtension> {
+        match self {
+            Self::Element(element) => ChemicalTree::Element(element),
+            Self::Isotope(isotope) => ChemicalTree::Isotope(isotope),
+            Self::Radical(node) => {
+                ChemicalTree::Radical(
+                    node.map(|n| Subtree::new(into_inner(n).substitute_extensions(substitute))),
+                )
+            }
+            Self::Charge(node) => {
+                let charge = node.charge;
+                ChemicalTree::Charge(ChargeNode::new(
+                    charge,
+                    Subtree::new(into_inner(node.into_tree()).substitute_extensions(substitute)),
+                ))
+            }
+            Self::Repeat(node) => {
+                let repeats_extension = matches!(*node.node, Self::Extension(_));
+                let mut substituted = into_inner(node.node).substitute_extensions(substitute);
+                if repeats_extension {
+                    substituted = substituted.round();
+                }
+                substituted.into_repeat(node.count)
+            }
+            Self::PackedRepeat(node) => ChemicalTree::PackedRepeat(node),
+            Self::Sequence(node) => {
+                let mut sequence = SequenceNode::empty();
+                for child in node.into_iter() {
+                    match child.substitute_extensions(substitute) {
+                        ChemicalTree::Sequence(inner) => {
+                            for grandchild in inner.into_iter() {
+                                sequence.push(grandchild);
+                            }
+                        }
+                        substituted => sequence.push(substituted),
+                    }
+                }
+                ChemicalTree::Sequence(sequence)
+            }
+            Self::Unit(node) => {
+                ChemicalTree::Unit(
+                    node.map(|n| Subtree::new(into_inner(n).substitute_extensions(substitute))),
+                )
+            }
+            Self::Extension(extension) => substitute(extension),
+        }
+    }
+
     /// Consumes the chemical tree and returns a version decorated with a
     /// complex specifier.
     pub(crate) fn complex(self, complex: Complex) -> Self {
-        match complex {
-            Complex::Benzyl => {
-                let mut sequence: SequenceNode<Self> = SequenceNode::empty();
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::SEVEN,
-                    Box::new(Self::Element(Element::C)),
-                )));
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::SEVEN,
-                    Box::new(Self::Element(Element::H)),
-                )));
-                self.push(Self::Sequence(sequence).round())
-            }
-            Complex::Butyl => {
-                let mut sequence: SequenceNode<Self> = SequenceNode::empty();
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::FOUR,
-                    Box::new(Self::Element(Element::C)),
-                )));
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::NINE,
-                    Box::new(Self::Element(Element::H)),
-                )));
-                self.push(Self::Sequence(sequence).round())
-            }
-            Complex::Phenyl => {
-                let mut sequence: SequenceNode<Self> = SequenceNode::empty();
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::SIX,
-                    Box::new(Self::Element(Element::C)),
-                )));
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::FIVE,
-                    Box::new(Self::Element(Element::H)),
-                )));
-                self.push(Self::Sequence(sequence).round())
-            }
-            Complex::Cyclohexyl => {
-                let mut sequence: SequenceNode<Self> = SequenceNode::empty();
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::SIX,
-                    Box::new(Self::Element(Element::C)),
-                )));
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::ELEVEN,
-                    Box::new(Self::Element(Element::H)),
-                )));
-                self.push(Self::Sequence(sequence).round())
-            }
-            Complex::Ethyl => {
-                let mut sequence: SequenceNode<Self> = SequenceNode::empty();
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::TWO,
-                    Box::new(Self::Element(Element::C)),
-                )));
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::FIVE,
-                    Box::new(Self::Element(Element::H)),
-                )));
-                self.push(Self::Sequence(sequence).round())
-            }
-            Complex::Methyl => {
-                let mut sequence: SequenceNode<Self> = SequenceNode::empty();
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::ONE,
-                    Box::new(Self::Element(Element::C)),
-                )));
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::THREE,
-                    Box::new(Self::Element(Element::H)),
-                )));
-                self.push(Self::Sequence(sequence).round())
-            }
-            Complex::Cyclopentadienyl => {
-                let mut sequence: SequenceNode<Self> = SequenceNode::empty();
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::FIVE,
-                    Box::new(Self::Element(Element::C)),
-                )));
-                sequence.push(Self::Repeat(RepeatNode::new(
-                    Count::FIVE,
-                    Box::new(Self::Element(Element::H)),
-                )));
-                Self::Charge(ChargeNode::new(
-                    -Charge::ONE,
-                    Box::new(self.push(Self::Sequence(sequence).round())),
+        let mut sequence: SequenceNode<Self> = SequenceNode::empty();
+        for &(element, count) in complex.composition() {
+            let atom = Self::Element(element);
+            sequence.push(if count == 1 {
+                atom
+            } else {
+                atom.repeat(
+                    Count::try_from(u64::from(count))
+                        .unwrap_or_else(|_| unreachable!("Complex counts fit any count type")),
+                )
+            });
+        }
+        self.push(Self::Sequence(sequence).round())
+    }
+
+    /// Consumes the chemical tree and returns a version without any charge
+    /// node, keeping the rest of the structure untouched.
+    pub(crate) fn neutralize(self) -> Self {
+        match self {
+            Self::Charge(node) => into_inner(node.into_tree()).neutralize(),
+            Self::Radical(node) => {
+                Self::Radical(node.map(|n| Subtree::new(into_inner(n).neutralize())))
+            }
+            Self::Repeat(node) => into_inner(node.node).neutralize().into_repeat(node.count),
+            Self::Sequence(node) => Self::Sequence(node.map(Self::neutralize)),
+            Self::Unit(node) => Self::Unit(node.map(|n| Subtree::new(into_inner(n).neutralize()))),
+            leaf @ (Self::Element(_)
+            | Self::Isotope(_)
+            | Self::PackedRepeat(_)
+            | Self::Extension(_)) => leaf,
+        }
+    }
+
+    /// Consumes the chemical tree and returns an equivalent tree using the
+    /// provided count and charge types.
+    ///
+    /// # Errors
+    ///
+    /// * If any count or charge cannot be represented by the new types.
+    pub(crate) fn try_convert<NewCount: CountLike, NewCharge: ChargeLike>(
+        self,
+    ) -> Result<ChemicalTree<NewCount, NewCharge, Extension>, NumericError> {
+        Ok(match self {
+            Self::Element(element) => ChemicalTree::Element(element),
+            Self::Isotope(isotope) => ChemicalTree::Isotope(isotope),
+            Self::Radical(node) => {
+                ChemicalTree::Radical(
+                    node.try_map(|n| Ok(Subtree::new(into_inner(n).try_convert()?)))?,
+                )
+            }
+            Self::Charge(node) => {
+                let charge = convert_charge(node.charge)?;
+                ChemicalTree::Charge(ChargeNode::new(
+                    charge,
+                    Subtree::new(into_inner(node.into_tree()).try_convert()?),
                 ))
             }
+            Self::Repeat(node) => {
+                let count = convert_count(node.count)?;
+                into_inner(node.node).try_convert()?.into_repeat(count)
+            }
+            Self::PackedRepeat(node) => {
+                ChemicalTree::Element(node.element())
+                    .into_repeat(convert_count(node.count::<Count>())?)
+            }
+            Self::Sequence(node) => ChemicalTree::Sequence(node.try_map(Self::try_convert)?),
+            Self::Unit(node) => {
+                ChemicalTree::Unit(
+                    node.try_map(|n| Ok(Subtree::new(into_inner(n).try_convert()?)))?,
+                )
+            }
+            Self::Extension(extension) => ChemicalTree::Extension(extension),
+        })
+    }
+
+    /// Pushes each charged node of the tree to the provided sites, with its
+    /// number of copies when the tree is itself repeated the provided
+    /// number of times, saturating at `u64::MAX`, and its charge.
+    pub(crate) fn charge_sites(&self, copies: u64, sites: &mut Vec<(u64, Charge)>) {
+        match self {
+            Self::Element(_) | Self::Isotope(_) | Self::PackedRepeat(_) | Self::Extension(_) => {}
+            Self::Radical(node) => node.as_ref().charge_sites(copies, sites),
+            Self::Charge(node) => {
+                sites.push((copies, node.charge));
+                node.as_ref().charge_sites(copies, sites);
+            }
+            Self::Repeat(node) => {
+                node.node.charge_sites(copies.saturating_mul(saturating_u64(node.count)), sites);
+            }
+            Self::Sequence(node) => {
+                for node in node.iter() {
+                    node.charge_sites(copies, sites);
+                }
+            }
+            Self::Unit(node) => node.as_ref().charge_sites(copies, sites),
+        }
+    }
+
+    /// Returns the number of hydrogens in the node if it is a run of plain
+    /// hydrogens, such as `H` or `H₃`.
+    fn hydrogen_run(&self) -> Option<Count> {
+        match self {
+            Self::Element(Element::H) => Some(Count::ONE),
+            Self::Repeat(repeat) if matches!(repeat.node.as_ref(), Self::Element(Element::H)) => {
+                Some(repeat.count)
+            }
+            Self::PackedRepeat(repeat) if repeat.element() == Element::H => Some(repeat.count()),
+            _ => None,
+        }
+    }
+
+    /// Returns a run of plain hydrogens of the provided non-zero length.
+    fn hydrogens(count: Count) -> Self {
+        let hydrogen = Self::Element(Element::H);
+        if count.is_one() { hydrogen } else { hydrogen.repeat(count) }
+    }
+
+    /// Adds the provided number of hydrogens to the last top-level run of
+    /// plain hydrogens of the tree, or appends a new run if there is none.
+    /// Outer charges are preserved.
+    pub(crate) fn add_hydrogens(&mut self, count: Count) -> Result<(), NumericError> {
+        if let Some(run) = self.hydrogen_run() {
+            *self = Self::hydrogens(run.checked_add(&count).ok_or(NumericError::PositiveOverflow)?);
+            return Ok(());
+        }
+        match self {
+            Self::Charge(node) => make_mut(node.as_mut()).add_hydrogens(count),
+            Self::Sequence(sequence) => {
+                if let Some(node) =
+                    sequence.iter_mut().rev().find(|node| node.hydrogen_run().is_some())
+                {
+                    node.add_hydrogens(count)
+                } else {
+                    sequence.push(Self::hydrogens(count));
+                    Ok(())
+                }
+            }
+            _ => {
+                let tree = core::mem::replace(self, Self::Sequence(SequenceNode::empty()));
+                *self = tree.push(Self::hydrogens(count));
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes up to the provided number of hydrogens from the runs of plain
+    /// hydrogens of the tree, starting from the rightmost one and decreasing
+    /// `remaining` accordingly. Hydrogens within repeated groups, such as
+    /// `(CH₃)₂`, are left untouched.
+    ///
+    /// Returns whether the tree is left empty.
+    pub(crate) fn remove_hydrogens(&mut self, remaining: &mut Count) -> bool {
+        if remaining.is_zero() {
+            return false;
+        }
+        if let Some(run) = self.hydrogen_run() {
+            let removed = if run < *remaining { run } else { *remaining };
+            *remaining = *remaining - removed;
+            if run == removed {
+                return true;
+            }
+            *self = Self::hydrogens(run - removed);
+            return false;
+        }
+        match self {
+            Self::Charge(node) => make_mut(node.as_mut()).remove_hydrogens(remaining),
+            Self::Radical(node) => make_mut(node.as_mut()).remove_hydrogens(remaining),
+            Self::Unit(node) => make_mut(node.as_mut()).remove_hydrogens(remaining),
+            Self::Sequence(sequence) => {
+                for index in (0..sequence.len()).rev() {
+                    if sequence.get_mut(index).is_some_and(|node| node.remove_hydrogens(remaining))
+                    {
+                        sequence.remove(index);
+                    }
+                }
+                if sequence.len() == 1 {
+                    *self = sequence.pop().unwrap();
+                    return false;
+                }
+                sequence.is_empty()
+            }
+            _ => false,
         }
     }
 
@@ -242,21 +703,350 @@ impl<Count: CountLike, Charge: ChargeLike, Extension> ChemicalTree<Count, Charge
     }
 
     /// Pushes a new node onto a sequence, converting the tree into a sequence
-    /// if necessary.
-    pub(crate) fn push(mut self, node: Self) -> Self {
-        if let ChemicalTree::Sequence(ref mut sequence) = self {
-            if sequence.is_empty() {
-                node
-            } else {
+    /// if necessary. A node pushed onto a leading radical is pushed within
+    /// it, as the radical decorates all of the unit following it.
+    pub(crate) fn push(self, node: Self) -> Self {
+        match self {
+            Self::Radical(radical) if radical.is_left() => {
+                Self::Radical(radical.map(|n| Subtree::new(into_inner(n).push(node))))
+            }
+            Self::Sequence(mut sequence) => {
+                if sequence.is_empty() {
+                    node
+                } else {
+                    sequence.push(node);
+                    Self::Sequence(sequence)
+                }
+            }
+            tree => {
+                let mut sequence = SequenceNode::empty();
+                sequence.push(tree);
                 sequence.push(node);
-                self
+                Self::Sequence(sequence)
             }
+        }
+    }
+
+    /// Consumes the chemical tree and returns the tree which the parser
+    /// would build from its display, so that trees built by editing parsed
+    /// trees display to strings which are parsed back into equal trees.
+    ///
+    /// Nested sequences are spliced, charges and radicals wrap the unit
+    /// preceding them and groups collapse to their atom when bracketing a
+    /// single atom, as when parsing. Repeated groups whose display would
+    /// otherwise be read with a different composition, such as a repeated
+    /// sequence or a repeated charged group following other atoms, are
+    /// wrapped in round brackets.
+    pub(crate) fn canonicalize(self) -> Self {
+        self.replay(Self::Sequence(SequenceNode::empty()))
+    }
+
+    /// Returns whether the chemical tree is equal to its
+    /// [`canonicalize`](Self::canonicalize)d version.
+    pub(crate) fn is_canonical(&self) -> bool
+    where
+        Self: PartialEq,
+    {
+        self.clone().canonicalize() == *self
+    }
+
+    /// Appends the chemical tree to the provided unit as the parser would
+    /// when reading its display right after the one of the unit.
+    fn replay(self, unit: Self) -> Self {
+        match self {
+            leaf @ (Self::Element(_) | Self::Isotope(_) | Self::Extension(_)) => unit.push(leaf),
+            Self::Sequence(node) => node.into_iter().fold(unit, |unit, child| child.replay(unit)),
+            Self::Unit(node) => {
+                let node = node.map(|n| Subtree::new(into_inner(n).canonicalize()));
+                unit.push(if node.as_ref().is_leaf() {
+                    into_inner(node.into_tree())
+                } else {
+                    Self::Unit(node)
+                })
+            }
+            Self::Charge(node) => {
+                let charge = node.charge;
+                let unit = into_inner(node.into_tree()).replay(unit);
+                if charge.is_zero() {
+                    return unit;
+                }
+                // Charges whose sum overflows cannot be merged as the parser
+                // would, and are kept as nested charges.
+                let overflows = matches!(
+                    &unit,
+                    Self::Charge(existing) if existing.charge.checked_add(&charge).is_none()
+                );
+                if overflows || unit.is_empty_unit() {
+                    Self::Charge(ChargeNode::new(charge, Subtree::new(unit)))
+                } else {
+                    unit.charge(charge).expect("The charge was checked not to overflow")
+                }
+            }
+            Self::Radical(node) if node.is_left() => {
+                let tree = into_inner(node.into_node());
+                if unit.is_empty_unit() {
+                    tree.canonicalize().left_radical()
+                } else {
+                    // A radical between two groups is read as decorating the
+                    // preceding unit.
+                    tree.replay(unit.right_radical())
+                }
+            }
+            Self::Radical(node) => into_inner(node.into_node()).replay(unit).right_radical(),
+            Self::PackedRepeat(node) => {
+                unit.push(Self::Element(node.element())).repeat(node.count())
+            }
+            Self::Repeat(node) => {
+                let count = node.count;
+                let tree = into_inner(node.node).canonicalize();
+                match tree {
+                    Self::Element(_) | Self::Isotope(_) | Self::Extension(_) | Self::Unit(_) => {
+                        unit.push(tree).repeat(count)
+                    }
+                    Self::Charge(_) | Self::Radical(_)
+                        if unit.is_empty_unit() && !tree.is_left_radical() =>
+                    {
+                        tree.repeat(count)
+                    }
+                    tree => {
+                        unit.push(Self::Unit(BracketNode::round(Subtree::new(tree)))).repeat(count)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns whether the chemical tree is an empty sequence, as the unit
+    /// from which the parser starts.
+    fn is_empty_unit(&self) -> bool {
+        matches!(self, Self::Sequence(sequence) if sequence.is_empty())
+    }
+
+    /// Returns whether the chemical tree is decorated with a left-hand side
+    /// radical.
+    fn is_left_radical(&self) -> bool {
+        matches!(self, Self::Radical(node) if node.is_left())
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> ChemicalTree<Count, Charge, Empty> {
+    /// Returns the number of plain hydrogens of the tree, i.e. of hydrogens
+    /// not specified as isotopes, or None if the provided data type cannot
+    /// represent it.
+    pub(crate) fn number_of_plain_hydrogens<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        match self {
+            Self::Element(Element::H) => Some(C::ONE),
+            Self::Element(_) | Self::Isotope(_) | Self::Extension(_) => Some(C::ZERO),
+            Self::Radical(node) => node.as_ref().number_of_plain_hydrogens(),
+            Self::Charge(node) => node.as_ref().number_of_plain_hydrogens(),
+            Self::Unit(node) => node.as_ref().number_of_plain_hydrogens(),
+            Self::Repeat(node) => {
+                C::from(node.count).checked_mul(&node.node.number_of_plain_hydrogens()?)
+            }
+            Self::PackedRepeat(node) => {
+                Some(if node.element() == Element::H { C::from(node.count()) } else { C::ZERO })
+            }
+            Self::Sequence(sequence) => {
+                sequence.iter().try_fold(C::ZERO, |total, node| {
+                    total.checked_add(&node.number_of_plain_hydrogens()?)
+                })
+            }
+        }
+    }
+
+    /// Replaces up to the provided number of plain hydrogens of the tree
+    /// with the provided isotope, starting from the rightmost ones and
+    /// decreasing `remaining` accordingly. Repeated groups which are only
+    /// partially exchanged, such as `(OH)₃` becoming `(OH)(OD)₂`, are split
+    /// into their untouched and exchanged repetitions.
+    pub(crate) fn exchange_hydrogens(&mut self, isotope: Isotope, remaining: &mut Count) {
+        if remaining.is_zero() {
+            return;
+        }
+        if let Some(run) = self.hydrogen_run() {
+            let exchanged = if run < *remaining { run } else { *remaining };
+            *remaining = *remaining - exchanged;
+            let labeled = Self::Isotope(isotope);
+            let labeled = if exchanged.is_one() { labeled } else { labeled.repeat(exchanged) };
+            *self = if run == exchanged {
+                labeled
+            } else {
+                Self::hydrogens(run - exchanged).push(labeled)
+            };
+            return;
+        }
+        match self {
+            Self::Charge(node) => make_mut(node.as_mut()).exchange_hydrogens(isotope, remaining),
+            Self::Radical(node) => make_mut(node.as_mut()).exchange_hydrogens(isotope, remaining),
+            Self::Unit(node) => make_mut(node.as_mut()).exchange_hydrogens(isotope, remaining),
+            Self::Sequence(sequence) => {
+                for index in (0..sequence.len()).rev() {
+                    let Some(node) = sequence.get_mut(index) else {
+                        continue;
+                    };
+                    node.exchange_hydrogens(isotope, remaining);
+                    // Split runs are spliced into the sequence, so that the
+                    // tree matches the one parsed from its display.
+                    if let Self::Sequence(_) = node {
+                        let Self::Sequence(inner) = sequence.remove(index) else {
+                            unreachable!("The node was just checked to be a sequence");
+                        };
+                        for (offset, node) in inner.into_iter().enumerate() {
+                            sequence.insert(index + offset, node);
+                        }
+                    }
+                }
+            }
+            Self::Repeat(node) => {
+                let mut sequence = SequenceNode::empty();
+                for (count, copy) in
+                    node.node.exchange_hydrogens_in_copies(node.count, isotope, remaining)
+                {
+                    sequence.push(if count.is_one() { copy } else { copy.into_repeat(count) });
+                }
+                *self = if sequence.len() == 1 {
+                    sequence.pop().unwrap()
+                } else {
+                    Self::Sequence(sequence)
+                };
+            }
+            Self::Element(_) | Self::Isotope(_) | Self::PackedRepeat(_) | Self::Extension(_) => {}
+        }
+    }
+
+    /// Returns the provided number of copies of the tree with up to
+    /// `remaining` of their plain hydrogens replaced with the provided
+    /// isotope, as runs of identical copies from left to right. The rightmost
+    /// copies are exchanged first, and at most one copy is partially
+    /// exchanged.
+    pub(crate) fn exchange_hydrogens_in_copies(
+        &self,
+        copies: Count,
+        isotope: Isotope,
+        remaining: &mut Count,
+    ) -> Vec<(Count, Self)> {
+        let (whole, per_copy) = match self.number_of_plain_hydrogens::<Count>() {
+            Some(per_copy) if per_copy.is_zero() => return alloc::vec![(copies, self.clone())],
+            Some(per_copy) => {
+                let whole = *remaining / per_copy;
+                (if whole < copies { whole } else { copies }, per_copy)
+            }
+            // The hydrogens of a single copy cannot be counted, so that no
+            // copy can be entirely exchanged.
+            None => (Count::ZERO, Count::ZERO),
+        };
+        *remaining = *remaining - whole * per_copy;
+        let partial = (!remaining.is_zero() && whole < copies).then(|| {
+            let mut partial = self.clone();
+            partial.exchange_hydrogens(isotope, remaining);
+            partial
+        });
+        let untouched = copies - whole - if partial.is_some() { Count::ONE } else { Count::ZERO };
+
+        let mut parts = Vec::new();
+        if !untouched.is_zero() {
+            parts.push((untouched, self.clone()));
+        }
+        parts.extend(partial.map(|partial| (Count::ONE, partial)));
+        if !whole.is_zero() {
+            let mut exchanged = self.clone();
+            exchanged.exchange_hydrogens(isotope, &mut per_copy.clone());
+            parts.push((whole, exchanged));
+        }
+        parts
+    }
+
+    /// Returns the tree with its brackets and repeated groups flattened into
+    /// a sequence of elements and isotopes with merged counts, followed by
+    /// the net charge of the tree and by a radical if the tree has any.
+    ///
+    /// Elements and isotopes are written in order of first appearance, or in
+    /// Hill order if requested, with the isotopes of an element following it
+    /// by increasing mass number.
+    ///
+    /// # Errors
+    ///
+    /// * If any merged count or the net charge overflows.
+    pub(crate) fn flatten(&self, hill_order: bool) -> Result<Self, NumericError> {
+        let mut atoms: Vec<(Element, Option<Isotope>, Count)> = Vec::new();
+        let radical = self.collect_atoms(Count::ONE, &mut atoms)?;
+        if hill_order {
+            let mut elements: Vec<Element> = atoms.iter().map(|(element, _, _)| *element).collect();
+            hill_sort(&mut elements);
+            elements.dedup();
+            atoms.sort_by_key(|(element, isotope, _)| {
+                (elements.iter().position(|e| e == element), isotope.map(|i| i.mass_number()))
+            });
+        }
+
+        let mut tree = Self::Sequence(SequenceNode::empty());
+        for (element, isotope, count) in atoms {
+            let node = isotope.map_or(Self::Element(element), Self::Isotope);
+            tree = tree.push(if count.is_one() { node } else { node.repeat(count) });
+        }
+        if radical {
+            tree = tree.right_radical();
+        }
+        let charge = ChargedMolecularTree::<Count, Charge>::net_charge_integer(self)
+            .ok_or(NumericError::PositiveOverflow)?;
+        if charge != 0 {
+            let charge = crate::signed_charge(charge.unsigned_abs(), charge > 0)?;
+            tree = Self::Charge(ChargeNode::new(charge, Subtree::new(tree)));
+        }
+        Ok(tree)
+    }
+
+    /// Adds the elements and isotopes of the tree, repeated by the provided
+    /// multiplier, to the provided counts, and returns whether the tree
+    /// contains any radical.
+    fn collect_atoms(
+        &self,
+        multiplier: Count,
+        atoms: &mut Vec<(Element, Option<Isotope>, Count)>,
+    ) -> Result<bool, NumericError> {
+        let (element, isotope) = match self {
+            Self::Element(element) => (*element, None),
+            Self::Isotope(isotope) => (isotope.element(), Some(*isotope)),
+            Self::Radical(node) => {
+                node.as_ref().collect_atoms(multiplier, atoms)?;
+                return Ok(true);
+            }
+            Self::Charge(node) => return node.as_ref().collect_atoms(multiplier, atoms),
+            Self::Repeat(node) => {
+                let multiplier =
+                    multiplier.checked_mul(&node.count).ok_or(NumericError::PositiveOverflow)?;
+                return node.node.collect_atoms(multiplier, atoms);
+            }
+            Self::PackedRepeat(node) => {
+                let multiplier =
+                    multiplier.checked_mul(&node.count()).ok_or(NumericError::PositiveOverflow)?;
+                return Self::Element(node.element()).collect_atoms(multiplier, atoms);
+            }
+            Self::Sequence(node) => {
+                let mut radical = false;
+                for node in node.iter() {
+                    radical |= node.collect_atoms(multiplier, atoms)?;
+                }
+                return Ok(radical);
+            }
+            Self::Unit(node) => return node.as_ref().collect_atoms(multiplier, atoms),
+            Self::Extension(_) => return Ok(false),
+        };
+        if let Some((_, _, count)) =
+            atoms.iter_mut().find(|(e, i, _)| *e == element && *i == isotope)
+        {
+            *count = count.checked_add(&multiplier).ok_or(NumericError::PositiveOverflow)?;
         } else {
-            let mut sequence = SequenceNode::empty();
-            sequence.push(self);
-            sequence.push(node);
-            ChemicalTree::Sequence(sequence)
+            atoms.push((element, isotope, multiplier));
         }
+        Ok(false)
     }
 }
 
@@ -288,6 +1078,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.contains_elements(),
             Self::Charge(c) => c.contains_elements(),
             Self::Repeat(r) => r.contains_elements(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().contains_elements(),
             Self::Sequence(s) => s.contains_elements(),
             Self::Unit(b) => b.contains_elements(),
             Self::Extension(_) => false, // Empty node has no elements
@@ -301,6 +1092,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.contains_non_hydrogens(),
             Self::Charge(c) => c.contains_non_hydrogens(),
             Self::Repeat(r) => r.contains_non_hydrogens(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().contains_non_hydrogens(),
             Self::Sequence(s) => s.contains_non_hydrogens(),
             Self::Unit(b) => b.contains_non_hydrogens(),
             Self::Extension(_) => false, // Empty node has no elements
@@ -314,11 +1106,45 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.number_of_elements(),
             Self::Charge(c) => c.number_of_elements(),
             Self::Repeat(r) => r.number_of_elements(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().number_of_elements(),
             Self::Sequence(s) => s.number_of_elements(),
             Self::Unit(b) => b.number_of_elements(),
             Self::Extension(_) => 0, // Empty node has no elements
         }
     }
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        match self {
+            Self::Element(e) => <Element as MolecularTree<Count>>::try_number_of_elements::<C>(e),
+            Self::Isotope(i) => <Isotope as MolecularTree<Count>>::try_number_of_elements::<C>(i),
+            Self::Radical(r) => r.try_number_of_elements::<C>(),
+            Self::Charge(c) => c.try_number_of_elements::<C>(),
+            Self::Repeat(r) => r.try_number_of_elements::<C>(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().try_number_of_elements::<C>(),
+            Self::Sequence(s) => s.try_number_of_elements::<C>(),
+            Self::Unit(b) => b.try_number_of_elements::<C>(),
+            Self::Extension(_) => Some(C::ZERO),
+        }
+    }
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        match self {
+            Self::Element(e) => <Element as MolecularTree<Count>>::element_mask(e),
+            Self::Isotope(i) => <Isotope as MolecularTree<Count>>::element_mask(i),
+            Self::Radical(r) => r.element_mask(),
+            Self::Charge(c) => c.element_mask(),
+            Self::Repeat(r) => r.element_mask(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().element_mask(),
+            Self::Sequence(s) => s.element_mask(),
+            Self::Unit(b) => b.element_mask(),
+            Self::Extension(_) => elements_rs::ElementMask::default(),
+        }
+    }
 
     fn contains_isotopes(&self) -> bool {
         match self {
@@ -327,6 +1153,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.contains_isotopes(),
             Self::Charge(c) => c.contains_isotopes(),
             Self::Repeat(r) => r.contains_isotopes(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().contains_isotopes(),
             Self::Sequence(s) => s.contains_isotopes(),
             Self::Unit(b) => b.contains_isotopes(),
             Self::Extension(_) => false, // Empty node has no isotopes
@@ -340,6 +1167,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.contains_element(element),
             Self::Charge(c) => c.contains_element(element),
             Self::Repeat(r) => r.contains_element(element),
+            Self::PackedRepeat(r) => r.unpack::<Count>().contains_element(element),
             Self::Sequence(s) => s.contains_element(element),
             Self::Unit(b) => b.contains_element(element),
             Self::Extension(_) => false, // Empty node has no elements
@@ -353,6 +1181,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.contains_isotope(isotope),
             Self::Charge(c) => c.contains_isotope(isotope),
             Self::Repeat(r) => r.contains_isotope(isotope),
+            Self::PackedRepeat(r) => r.unpack::<Count>().contains_isotope(isotope),
             Self::Sequence(s) => s.contains_isotope(isotope),
             Self::Unit(b) => b.contains_isotope(isotope),
             Self::Extension(_) => false, // Empty node has no isotopes
@@ -378,6 +1207,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.count_of_element::<C>(element),
             Self::Charge(c) => c.count_of_element::<C>(element),
             Self::Repeat(r) => r.count_of_element::<C>(element),
+            Self::PackedRepeat(r) => r.unpack::<Count>().count_of_element::<C>(element),
             Self::Sequence(s) => s.count_of_element::<C>(element),
             Self::Unit(b) => b.count_of_element::<C>(element),
             Self::Extension(_) => None,
@@ -403,6 +1233,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.count_of_isotope::<C>(isotope),
             Self::Charge(c) => c.count_of_isotope::<C>(isotope),
             Self::Repeat(r) => r.count_of_isotope::<C>(isotope),
+            Self::PackedRepeat(r) => r.unpack::<Count>().count_of_isotope::<C>(isotope),
             Self::Sequence(s) => s.count_of_isotope::<C>(isotope),
             Self::Unit(b) => b.count_of_isotope::<C>(isotope),
             Self::Extension(_) => None,
@@ -416,12 +1247,31 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.isotopologue_mass(),
             Self::Charge(c) => c.isotopologue_mass(),
             Self::Repeat(r) => r.isotopologue_mass(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().isotopologue_mass(),
             Self::Sequence(s) => s.isotopologue_mass(),
             Self::Unit(b) => b.isotopologue_mass(),
             Self::Extension(_) => 0.0,
         }
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        match self {
+            Self::Element(e) => {
+                <Element as MolecularTree<Count>>::isotopologue_mass_with(e, context)
+            }
+            Self::Isotope(i) => {
+                <Isotope as MolecularTree<Count>>::isotopologue_mass_with(i, context)
+            }
+            Self::Radical(r) => r.isotopologue_mass_with(context),
+            Self::Charge(c) => c.isotopologue_mass_with(context),
+            Self::Repeat(r) => r.isotopologue_mass_with(context),
+            Self::PackedRepeat(r) => r.unpack::<Count>().isotopologue_mass_with(context),
+            Self::Sequence(s) => s.isotopologue_mass_with(context),
+            Self::Unit(b) => b.isotopologue_mass_with(context),
+            Self::Extension(_) => 0.0,
+        }
+    }
+
     fn is_noble_gas_compound(&self) -> bool {
         match self {
             Self::Element(e) => <Element as MolecularTree<Count>>::is_noble_gas_compound(e),
@@ -429,6 +1279,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.is_noble_gas_compound(),
             Self::Charge(c) => c.is_noble_gas_compound(),
             Self::Repeat(r) => r.is_noble_gas_compound(),
+            Self::PackedRepeat(r) => r.unpack::<Count>().is_noble_gas_compound(),
             Self::Sequence(s) => s.is_noble_gas_compound(),
             Self::Unit(b) => b.is_noble_gas_compound(),
             Self::Extension(_) => false, // Empty node has no noble gas compounds
@@ -442,6 +1293,7 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => Self::Radical(r.isotopic_normalization()),
             Self::Charge(c) => Self::Charge(c.isotopic_normalization()),
             Self::Repeat(r) => Self::Repeat(r.isotopic_normalization()),
+            Self::PackedRepeat(r) => Self::PackedRepeat(*r),
             Self::Sequence(s) => Self::Sequence(s.isotopic_normalization()),
             Self::Unit(b) => Self::Unit(b.isotopic_normalization()),
             Self::Extension(_) => self.clone(),
@@ -463,6 +1315,9 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> MolecularTree<Count
             Self::Radical(r) => r.check_hill_ordering(predecessor, has_carbon),
             Self::Charge(c) => c.check_hill_ordering(predecessor, has_carbon),
             Self::Repeat(r) => r.check_hill_ordering(predecessor, has_carbon),
+            Self::PackedRepeat(r) => {
+                r.unpack::<Count>().check_hill_ordering(predecessor, has_carbon)
+            }
             Self::Sequence(s) => s.check_hill_ordering(predecessor, has_carbon),
             Self::Unit(b) => b.check_hill_ordering(predecessor, has_carbon),
             Self::Extension(_) => Ok(predecessor),
@@ -474,14 +1329,27 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Display> Display
     for ChemicalTree<Count, Charge, Extension>
 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike, Extension: Display> StyledDisplay
+    for ChemicalTree<Count, Charge, Extension>
+{
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
         match self {
-            Self::Element(e) => write!(f, "{e}"),
-            Self::Isotope(i) => display_isotope(*i, f),
-            Self::Radical(r) => write!(f, "{r}"),
-            Self::Charge(c) => write!(f, "{c}"),
-            Self::Repeat(r) => write!(f, "{r}"),
-            Self::Sequence(s) => write!(f, "{s}"),
-            Self::Unit(b) => write!(f, "{b}"),
+            Self::Element(e) => e.fmt_with(style, f),
+            Self::Isotope(i) => i.fmt_with(style, f),
+            Self::Radical(r) => r.fmt_with(style, f),
+            Self::Charge(c) => c.fmt_with(style, f),
+            Self::Repeat(r) => r.fmt_with(style, f),
+            Self::PackedRepeat(r) => r.fmt_with(style, f),
+            Self::Sequence(s) => s.fmt_with(style, f),
+            Self::Unit(b) => b.fmt_with(style, f),
             Self::Extension(e) => write!(f, "{e}"),
         }
     }
@@ -499,7 +1367,24 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> ChargedMolecularTre
             Self::Repeat(r) => r.charge(),
             Self::Sequence(s) => s.charge(),
             Self::Unit(b) => b.charge(),
-            Self::Extension(_) => 0.0,
+            Self::PackedRepeat(_) | Self::Extension(_) => 0.0,
+        }
+    }
+
+    fn net_charge_integer(&self) -> Option<i64> {
+        match self {
+            Self::Element(e) => {
+                <Element as ChargedMolecularTree<Count, Charge>>::net_charge_integer(e)
+            }
+            Self::Isotope(i) => {
+                <Isotope as ChargedMolecularTree<Count, Charge>>::net_charge_integer(i)
+            }
+            Self::Radical(r) => r.net_charge_integer(),
+            Self::Charge(c) => c.net_charge_integer(),
+            Self::Repeat(r) => r.net_charge_integer(),
+            Self::Sequence(s) => s.net_charge_integer(),
+            Self::Unit(b) => b.net_charge_integer(),
+            Self::PackedRepeat(_) | Self::Extension(_) => Some(0),
         }
     }
 
@@ -514,6 +1399,10 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> ChargedMolecularTre
             Self::Radical(r) => r.isotopologue_mass_with_charge(),
             Self::Charge(c) => c.isotopologue_mass_with_charge(),
             Self::Repeat(r) => r.isotopologue_mass_with_charge(),
+            Self::PackedRepeat(r) => <RepeatNode<Count, Element> as ChargedMolecularTree<
+                Count,
+                Charge,
+            >>::isotopologue_mass_with_charge(&r.unpack()),
             Self::Sequence(s) => s.isotopologue_mass_with_charge(),
             Self::Unit(b) => b.isotopologue_mass_with_charge(),
             Self::Extension(_) => 0.0,
@@ -527,9 +1416,37 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Clone> ChargedMolecularTre
             Self::Radical(r) => r.molar_mass(),
             Self::Charge(c) => c.molar_mass(),
             Self::Repeat(r) => r.molar_mass(),
+            Self::PackedRepeat(r) => {
+                <RepeatNode<Count, Element> as ChargedMolecularTree<Count, Charge>>::molar_mass(
+                    &r.unpack(),
+                )
+            }
             Self::Sequence(s) => s.molar_mass(),
             Self::Unit(b) => b.molar_mass(),
             Self::Extension(_) => 0.0,
         }
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        match self {
+            Self::Element(e) => {
+                <Element as ChargedMolecularTree<Count, Charge>>::molar_mass_with(e, context)
+            }
+            Self::Isotope(i) => {
+                <Isotope as ChargedMolecularTree<Count, Charge>>::molar_mass_with(i, context)
+            }
+            Self::Radical(r) => r.molar_mass_with(context),
+            Self::Charge(c) => c.molar_mass_with(context),
+            Self::Repeat(r) => r.molar_mass_with(context),
+            Self::PackedRepeat(r) => {
+                <RepeatNode<Count, Element> as ChargedMolecularTree<Count, Charge>>::molar_mass_with(
+                    &r.unpack(),
+                    context,
+                )
+            }
+            Self::Sequence(s) => s.molar_mass_with(context),
+            Self::Unit(b) => b.molar_mass_with(context),
+            Self::Extension(_) => 0.0,
+        }
+    }
 }
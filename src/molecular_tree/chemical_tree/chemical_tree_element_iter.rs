@@ -8,7 +8,7 @@ use elements_rs::{Element, Isotope};
 
 use crate::{
     BracketNode, ChargeLike, ChargeNode, ChemicalTree, CountLike, RadicalNode, RepeatNode,
-    SequenceNode, molecular_tree::MolecularTree,
+    SequenceNode, Subtree, molecular_tree::MolecularTree,
 };
 
 #[allow(clippy::type_complexity)]
@@ -23,15 +23,17 @@ pub enum ChemicalTreeElementIter<
     /// An isotope (element with mass number)
     Isotope(<Isotope as MolecularTree<Count>>::ElementIter<'a>),
     /// A left-hand side radical.
-    Radical(Box<<RadicalNode<Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
+    Radical(Box<<RadicalNode<Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
     /// An ion (element or molecule with charge)
-    Charge(Box<<ChargeNode<Charge, Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
+    Charge(Box<<ChargeNode<Charge, Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
+    /// Number of atoms of a single element, packed inline
+    PackedRepeat(core::iter::RepeatN<Element>),
     /// Number of molecules
-    Repeat(Box<<RepeatNode<Count, Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
+    Repeat(Box<<RepeatNode<Count, Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
     /// A sequence of molecular formulas
     Sequence(Box<<SequenceNode<ChemicalTree<Count, Charge, Extension>> as MolecularTree<Count>>::ElementIter<'a>>),
     /// A repeating unit wrapped in round brackets
-    Unit(Box<<BracketNode<Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
+    Unit(Box<<BracketNode<Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::ElementIter<'a>>),
     /// An extension node for arbitrary extensions
     Extension(Empty<Element>),
 }
@@ -53,6 +55,12 @@ impl<'a, Count: CountLike + 'a, Charge: ChargeLike + 'a, Extension: Clone>
             ChemicalTree::Radical(r) => ChemicalTreeElementIter::Radical(Box::new(r.elements())),
             ChemicalTree::Charge(c) => ChemicalTreeElementIter::Charge(Box::new(c.elements())),
             ChemicalTree::Repeat(r) => ChemicalTreeElementIter::Repeat(Box::new(r.elements())),
+            ChemicalTree::PackedRepeat(r) => {
+                ChemicalTreeElementIter::PackedRepeat(core::iter::repeat_n(
+                    r.element(),
+                    crate::saturating_usize(r.count::<Count>()),
+                ))
+            }
             ChemicalTree::Sequence(s) => ChemicalTreeElementIter::Sequence(Box::new(s.elements())),
             ChemicalTree::Unit(b) => ChemicalTreeElementIter::Unit(Box::new(b.elements())),
             ChemicalTree::Extension(_) => ChemicalTreeElementIter::Extension(core::iter::empty()),
@@ -74,6 +82,7 @@ impl<'a, Count: CountLike + 'a, Charge: ChargeLike + 'a, Extension: Clone + 'a>
             | ChemicalTreeElementIter::Charge(iter)
             | ChemicalTreeElementIter::Unit(iter) => iter.next(),
             ChemicalTreeElementIter::Repeat(iter) => iter.next(),
+            ChemicalTreeElementIter::PackedRepeat(iter) => iter.next(),
             ChemicalTreeElementIter::Sequence(iter) => iter.next(),
             ChemicalTreeElementIter::Extension(iter) => iter.next(),
         }
@@ -92,15 +101,17 @@ pub enum ChemicalTreeNonHydrogenElementIter<
     /// An isotope (element with mass number)
     Isotope(<Isotope as MolecularTree<Count>>::NonHydrogenElementIter<'a>),
     /// A left-hand side radical.
-    Radical(Box<<RadicalNode<Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
+    Radical(Box<<RadicalNode<Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
     /// An ion (element or molecule with charge)
-    Charge(Box<<ChargeNode<Charge, Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
+    Charge(Box<<ChargeNode<Charge, Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
+    /// Number of atoms of a single element, packed inline
+    PackedRepeat(core::iter::RepeatN<Element>),
     /// Number of molecules
-    Repeat(Box<<RepeatNode<Count, Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
+    Repeat(Box<<RepeatNode<Count, Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
     /// A sequence of molecular formulas
     Sequence(Box<<SequenceNode<ChemicalTree<Count, Charge, Extension>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
     /// A repeating unit wrapped in round brackets
-    Unit(Box<<BracketNode<Box<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
+    Unit(Box<<BracketNode<Subtree<ChemicalTree<Count, Charge, Extension>>> as MolecularTree<Count>>::NonHydrogenElementIter<'a>>),
     /// An extension node for arbitrary extensions
     Extension(Empty<Element>),
 }
@@ -132,6 +143,17 @@ impl<'a, Count: CountLike + 'a, Charge: ChargeLike + 'a, Extension: Clone>
             ChemicalTree::Repeat(r) => {
                 ChemicalTreeNonHydrogenElementIter::Repeat(Box::new(r.non_hydrogens()))
             }
+            ChemicalTree::PackedRepeat(r) => {
+                let count = if r.element() == Element::H {
+                    0
+                } else {
+                    crate::saturating_usize(r.count::<Count>())
+                };
+                ChemicalTreeNonHydrogenElementIter::PackedRepeat(core::iter::repeat_n(
+                    r.element(),
+                    count,
+                ))
+            }
             ChemicalTree::Sequence(s) => {
                 ChemicalTreeNonHydrogenElementIter::Sequence(Box::new(s.non_hydrogens()))
             }
@@ -158,6 +180,7 @@ impl<'a, Count: CountLike + 'a, Charge: ChargeLike + 'a, Extension: Clone + 'a>
             | ChemicalTreeNonHydrogenElementIter::Charge(iter)
             | ChemicalTreeNonHydrogenElementIter::Unit(iter) => iter.next(),
             ChemicalTreeNonHydrogenElementIter::Repeat(iter) => iter.next(),
+            ChemicalTreeNonHydrogenElementIter::PackedRepeat(iter) => iter.next(),
             ChemicalTreeNonHydrogenElementIter::Sequence(iter) => iter.next(),
             ChemicalTreeNonHydrogenElementIter::Extension(iter) => iter.next(),
         }
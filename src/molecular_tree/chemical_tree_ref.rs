@@ -0,0 +1,322 @@
+//! Submodule providing the arena-allocated counterpart of the chemical trees,
+//! whose nodes are allocated in a [`Bump`] arena rather than boxed one by one.
+
+use bumpalo::{Bump, boxed::Box, collections::Vec};
+
+use crate::{
+    Bracket, ChargeLike, ChemicalTree, Complex, CountLike, Empty, Subtree,
+    errors::{NumericError, ParserError},
+    parsable::{ChemicalTreeBuilder, TreeBuilder},
+    prelude::{BracketNode, ChargeNode, Element, Isotope, RadicalNode, RepeatNode, SequenceNode},
+};
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+/// Enumeration of chemical tree nodes allocated in a [`Bump`] arena.
+///
+/// The trees are built by [`parse_in`](crate::parse_in), which allocates all
+/// of their nodes in the provided arena, so that parsing large batches of
+/// formulas does not allocate each node separately and the whole batch is
+/// freed at once when the arena is dropped or reset. The trees can be
+/// converted into the equivalent owned [`ChemicalTree`] with
+/// [`to_tree`](Self::to_tree).
+pub enum ChemicalTreeRef<'a, Count: CountLike, Charge: ChargeLike, Extension = Empty> {
+    /// An atom (element)
+    Element(Element),
+    /// An isotope (element with mass number)
+    Isotope(Isotope),
+    /// A left-hand side radical.
+    Radical(RadicalNode<Box<'a, Self>>),
+    /// An ion (element or molecule with charge)
+    Charge(ChargeNode<Charge, Box<'a, Self>>),
+    /// Number of molecules
+    Repeat(RepeatNode<Count, Box<'a, Self>>),
+    /// A sequence of molecular formulas
+    Sequence(Vec<'a, Self>),
+    /// A repeating unit wrapped in round brackets
+    Unit(BracketNode<Box<'a, Self>>),
+    /// An extension node for arbitrary extensions
+    Extension(Extension),
+}
+
+impl<Count: CountLike, Charge: ChargeLike, Extension: Clone>
+    ChemicalTreeRef<'_, Count, Charge, Extension>
+{
+    /// Returns the owned chemical tree equivalent to the arena-allocated
+    /// tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let arena = Bump::new();
+    /// let formula = parse_in::<u16, i16>(&arena, "[Fe(CN)6]-4").unwrap();
+    /// let (_, tree) = &formula.mixtures()[0];
+    /// assert_eq!(tree.to_tree().to_string(), "[Fe(CN)₆]⁴⁻");
+    /// ```
+    #[must_use]
+    pub fn to_tree(&self) -> ChemicalTree<Count, Charge, Extension> {
+        match self {
+            Self::Element(element) => ChemicalTree::Element(*element),
+            Self::Isotope(isotope) => ChemicalTree::Isotope(*isotope),
+            Self::Radical(node) => {
+                let tree = Subtree::new(node.as_ref().to_tree());
+                ChemicalTree::Radical(if node.is_left() {
+                    RadicalNode::left(tree)
+                } else {
+                    RadicalNode::right(tree)
+                })
+            }
+            Self::Charge(node) => {
+                ChemicalTree::Charge(ChargeNode::new(
+                    node.charge,
+                    Subtree::new(node.as_ref().to_tree()),
+                ))
+            }
+            Self::Repeat(node) => node.node.to_tree().into_repeat(node.count),
+            Self::Sequence(nodes) => {
+                let mut sequence = SequenceNode::empty();
+                for node in nodes {
+                    sequence.push(node.to_tree());
+                }
+                ChemicalTree::Sequence(sequence)
+            }
+            Self::Unit(node) => {
+                let tree = Subtree::new(node.as_ref().to_tree());
+                ChemicalTree::Unit(match node.bracket() {
+                    Bracket::Round => BracketNode::round(tree),
+                    Bracket::Square => BracketNode::square(tree),
+                })
+            }
+            Self::Extension(extension) => ChemicalTree::Extension(extension.clone()),
+        }
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike, Extension>
+    ChemicalTreeRef<'_, Count, Charge, Extension>
+{
+    /// Returns whether the tree is a single atom.
+    fn is_leaf(&self) -> bool {
+        matches!(self, Self::Element(_) | Self::Isotope(_))
+    }
+
+    /// Returns whether the tree is decorated with a left-hand side radical.
+    fn is_left_radical(&self) -> bool {
+        matches!(self, Self::Radical(node) if node.is_left())
+    }
+}
+
+impl<'a, Count: CountLike, Charge: ChargeLike, Extension>
+    TreeBuilder<ChemicalTreeRef<'a, Count, Charge, Extension>> for &'a Bump
+{
+    fn empty(self) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        ChemicalTreeRef::Sequence(Vec::new_in(self))
+    }
+
+    fn is_empty(self, tree: &ChemicalTreeRef<'a, Count, Charge, Extension>) -> bool {
+        matches!(tree, ChemicalTreeRef::Sequence(sequence) if sequence.is_empty())
+    }
+}
+
+impl<'a, Count: CountLike, Charge: ChargeLike, Extension>
+    ChemicalTreeBuilder<ChemicalTreeRef<'a, Count, Charge, Extension>> for &'a Bump
+{
+    type Count = Count;
+    type Charge = Charge;
+    type Extension = Extension;
+
+    /// Pushes a new node onto a sequence, as
+    /// [`ChemicalTree::push`](ChemicalTree::push) does.
+    fn push(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+        node: ChemicalTreeRef<'a, Count, Charge, Extension>,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        match tree {
+            ChemicalTreeRef::Radical(radical) if radical.is_left() => {
+                ChemicalTreeRef::Radical(
+                    radical.map(|n| Box::new_in(self.push(Box::into_inner(n), node), self)),
+                )
+            }
+            ChemicalTreeRef::Sequence(mut sequence) => {
+                if sequence.is_empty() {
+                    node
+                } else {
+                    sequence.push(node);
+                    ChemicalTreeRef::Sequence(sequence)
+                }
+            }
+            tree => {
+                let mut sequence = Vec::with_capacity_in(2, self);
+                sequence.push(tree);
+                sequence.push(node);
+                ChemicalTreeRef::Sequence(sequence)
+            }
+        }
+    }
+
+    fn element(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+        element: Element,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        self.push(tree, ChemicalTreeRef::Element(element))
+    }
+
+    fn isotope(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+        isotope: Isotope,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        self.push(tree, ChemicalTreeRef::Isotope(isotope))
+    }
+
+    fn extension(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+        extension: Extension,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        self.push(tree, ChemicalTreeRef::Extension(extension))
+    }
+
+    fn round(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        if tree.is_leaf() {
+            tree
+        } else {
+            ChemicalTreeRef::Unit(BracketNode::round(Box::new_in(tree, self)))
+        }
+    }
+
+    fn square(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        if tree.is_leaf() {
+            tree
+        } else {
+            ChemicalTreeRef::Unit(BracketNode::square(Box::new_in(tree, self)))
+        }
+    }
+
+    /// Decorates the tree with a left-hand side radical, as
+    /// [`ChemicalTree::left_radical`](ChemicalTree::left_radical) does.
+    fn left_radical(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        let tree = if tree.is_left_radical() { self.round(tree) } else { tree };
+        ChemicalTreeRef::Radical(RadicalNode::left(Box::new_in(tree, self)))
+    }
+
+    /// Decorates the tree with a right-hand side radical, as
+    /// [`ChemicalTree::right_radical`](ChemicalTree::right_radical) does.
+    fn right_radical(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        match tree {
+            ChemicalTreeRef::Radical(node) if node.is_left() => {
+                ChemicalTreeRef::Radical(
+                    node.map(|n| Box::new_in(self.right_radical(Box::into_inner(n)), self)),
+                )
+            }
+            tree @ ChemicalTreeRef::Radical(_) => {
+                ChemicalTreeRef::Radical(RadicalNode::right(Box::new_in(self.round(tree), self)))
+            }
+            tree => ChemicalTreeRef::Radical(RadicalNode::right(Box::new_in(tree, self))),
+        }
+    }
+
+    /// Decorates the tree with a charge, merging it with the charge of an
+    /// already charged tree, as
+    /// [`ChemicalTree::charge`](ChemicalTree::charge) does.
+    fn charge(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+        mut charge: Charge,
+    ) -> Result<ChemicalTreeRef<'a, Count, Charge, Extension>, ParserError> {
+        if self.is_empty(&tree) {
+            return Err(ParserError::EmptyMolecularTree);
+        }
+
+        // A leading radical decorates all of the unit following it, including
+        // its trailing charge.
+        if let ChemicalTreeRef::Radical(node) = tree {
+            return Ok(if node.is_left() {
+                ChemicalTreeRef::Radical(node.try_map(|n| {
+                    self.charge(Box::into_inner(n), charge).map(|tree| Box::new_in(tree, self))
+                })?)
+            } else {
+                ChemicalTreeRef::Charge(ChargeNode::new(
+                    charge,
+                    Box::new_in(ChemicalTreeRef::Radical(node), self),
+                ))
+            });
+        }
+
+        Ok(if let ChemicalTreeRef::Charge(charge_node) = tree {
+            charge = charge_node.charge.checked_add(&charge).ok_or(
+                if charge_node.charge > Charge::ZERO && charge > Charge::ZERO {
+                    NumericError::PositiveOverflow
+                } else {
+                    NumericError::NegativeOverflow
+                },
+            )?;
+            if charge.is_zero() {
+                Box::into_inner(charge_node.into_tree())
+            } else {
+                ChemicalTreeRef::Charge(ChargeNode::new(charge, charge_node.into_tree()))
+            }
+        } else {
+            ChemicalTreeRef::Charge(ChargeNode::new(charge, Box::new_in(tree, self)))
+        })
+    }
+
+    /// Decorates the last unit of the tree with a repeat count, as
+    /// [`ChemicalTree::repeat`](ChemicalTree::repeat) does.
+    fn repeat(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+        count: Count,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        match tree {
+            ChemicalTreeRef::Sequence(mut sequence) => {
+                let last = self.repeat(sequence.pop().unwrap(), count);
+                sequence.push(last);
+                ChemicalTreeRef::Sequence(sequence)
+            }
+            ChemicalTreeRef::Radical(node) if node.is_left() => {
+                ChemicalTreeRef::Radical(
+                    node.map(|n| Box::new_in(self.repeat(Box::into_inner(n), count), self)),
+                )
+            }
+            tree => ChemicalTreeRef::Repeat(RepeatNode::new(count, Box::new_in(tree, self))),
+        }
+    }
+
+    fn complex(
+        self,
+        tree: ChemicalTreeRef<'a, Count, Charge, Extension>,
+        complex: Complex,
+    ) -> ChemicalTreeRef<'a, Count, Charge, Extension> {
+        let composition = complex.composition();
+        let mut sequence = Vec::with_capacity_in(composition.len(), self);
+        for &(element, count) in composition {
+            let atom = ChemicalTreeRef::Element(element);
+            sequence.push(if count == 1 {
+                atom
+            } else {
+                self.repeat(
+                    atom,
+                    Count::try_from(u64::from(count))
+                        .unwrap_or_else(|_| unreachable!("Complex counts fit any count type")),
+                )
+            });
+        }
+        self.push(tree, self.round(ChemicalTreeRef::Sequence(sequence)))
+    }
+}
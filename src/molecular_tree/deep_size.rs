@@ -0,0 +1,156 @@
+//! Submodule providing the `DeepSizeOf` trait, which measures the memory
+//! used by formulas and trees including the heap allocations they own.
+
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+
+use elements_rs::{Element, Isotope};
+
+use crate::{
+    BracketNode, ChargeLike, ChargeNode, ChemicalTree, CountLike, Empty, InChITree, RadicalNode,
+    RepeatNode, Residual, SequenceNode,
+};
+
+/// Trait providing the number of bytes used by a value, including the heap
+/// allocations it owns, so as to plan the capacity of in-memory formula
+/// databases and to catch regressions in the size of the nodes.
+///
+/// The sizes account for the capacity of the vectors and strings rather
+/// than their length. With the `shared-tree` feature, the subtrees shared
+/// between clones are counted in each of the trees owning them, so that the
+/// sum of the sizes of several clones overestimates their memory usage.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let water = ChemicalFormula::<u16, i16>::from_str("H2O").unwrap();
+/// let slaked_lime = ChemicalFormula::<u16, i16>::from_str("Ca(OH)2").unwrap();
+/// assert!(water.deep_size_of() >= size_of::<ChemicalFormula<u16, i16>>());
+/// assert!(slaked_lime.deep_size_of() > water.deep_size_of());
+/// ```
+pub trait DeepSizeOf {
+    /// Returns the number of bytes used by the value, including the heap
+    /// allocations it owns.
+    #[must_use]
+    fn deep_size_of(&self) -> usize {
+        size_of_val(self) + self.deep_size_of_children()
+    }
+
+    /// Returns the number of bytes of the heap allocations owned by the
+    /// value, excluding the value itself.
+    #[must_use]
+    fn deep_size_of_children(&self) -> usize;
+}
+
+/// Implements `DeepSizeOf` for types owning no heap allocation.
+macro_rules! impl_deep_size_of_leaf {
+    ($($leaf:ty),*) => {
+        $(
+            impl DeepSizeOf for $leaf {
+                #[inline]
+                fn deep_size_of_children(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_deep_size_of_leaf!(Element, Isotope, Empty, Residual);
+
+impl<T: DeepSizeOf> DeepSizeOf for Box<T> {
+    fn deep_size_of_children(&self) -> usize {
+        (**self).deep_size_of()
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for Arc<T> {
+    fn deep_size_of_children(&self) -> usize {
+        // The allocation holds the strong and weak counters before the value.
+        2 * size_of::<usize>() + (**self).deep_size_of()
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for Vec<T> {
+    fn deep_size_of_children(&self) -> usize {
+        self.capacity() * size_of::<T>()
+            + self.iter().map(DeepSizeOf::deep_size_of_children).sum::<usize>()
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for Option<T> {
+    fn deep_size_of_children(&self) -> usize {
+        self.as_ref().map_or(0, DeepSizeOf::deep_size_of_children)
+    }
+}
+
+impl DeepSizeOf for String {
+    fn deep_size_of_children(&self) -> usize {
+        self.capacity()
+    }
+}
+
+/// Returns the number of bytes of the heap allocations owned by the
+/// provided counted mixtures of a formula, excluding the vector itself.
+pub(crate) fn mixtures_size_of<Count, Tree: DeepSizeOf>(mixtures: &Vec<(Count, Tree)>) -> usize {
+    mixtures.capacity() * size_of::<(Count, Tree)>()
+        + mixtures.iter().map(|(_, tree)| tree.deep_size_of_children()).sum::<usize>()
+}
+
+impl<N: DeepSizeOf> DeepSizeOf for SequenceNode<N> {
+    fn deep_size_of_children(&self) -> usize {
+        self.capacity() * size_of::<N>()
+            + self.iter().map(DeepSizeOf::deep_size_of_children).sum::<usize>()
+    }
+}
+
+impl<Count, T: DeepSizeOf> DeepSizeOf for RepeatNode<Count, T> {
+    fn deep_size_of_children(&self) -> usize {
+        self.as_ref().deep_size_of_children()
+    }
+}
+
+impl<C, T: DeepSizeOf> DeepSizeOf for ChargeNode<C, T> {
+    fn deep_size_of_children(&self) -> usize {
+        self.as_ref().deep_size_of_children()
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for RadicalNode<T> {
+    fn deep_size_of_children(&self) -> usize {
+        self.as_ref().deep_size_of_children()
+    }
+}
+
+impl<T: DeepSizeOf> DeepSizeOf for BracketNode<T> {
+    fn deep_size_of_children(&self) -> usize {
+        self.as_ref().deep_size_of_children()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike, Extension: DeepSizeOf> DeepSizeOf
+    for ChemicalTree<Count, Charge, Extension>
+{
+    fn deep_size_of_children(&self) -> usize {
+        match self {
+            Self::Element(element) => element.deep_size_of_children(),
+            Self::Isotope(isotope) => isotope.deep_size_of_children(),
+            Self::Radical(radical) => radical.deep_size_of_children(),
+            Self::Charge(charge) => charge.deep_size_of_children(),
+            Self::Repeat(repeat) => repeat.deep_size_of_children(),
+            Self::PackedRepeat(_) => 0,
+            Self::Sequence(sequence) => sequence.deep_size_of_children(),
+            Self::Unit(unit) => unit.deep_size_of_children(),
+            Self::Extension(extension) => extension.deep_size_of_children(),
+        }
+    }
+}
+
+impl<Count> DeepSizeOf for InChITree<Count> {
+    fn deep_size_of_children(&self) -> usize {
+        0
+    }
+}
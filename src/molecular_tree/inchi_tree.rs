@@ -3,7 +3,9 @@
 use core::fmt::Display;
 
 use crate::{
-    ChargeLike, ChemicalTree, CountLike, MolecularTree,
+    ChargeLike, ChemicalTree, CountLike, DisplayStyle, MassContext, MolecularTree, StyledDisplay,
+    convert_count,
+    errors::NumericError,
     prelude::{Element, RepeatNode},
 };
 
@@ -14,6 +16,20 @@ pub struct InChITree<Count> {
     node: RepeatNode<Count, Element>,
 }
 
+impl<Count: CountLike> InChITree<Count> {
+    /// Consumes the tree and returns an equivalent tree using the provided
+    /// count type.
+    ///
+    /// # Errors
+    ///
+    /// * If the count cannot be represented by the new type.
+    pub(crate) fn try_convert<NewCount: CountLike>(
+        self,
+    ) -> Result<InChITree<NewCount>, NumericError> {
+        Ok(InChITree { node: RepeatNode::new(convert_count(self.node.count)?, self.node.node) })
+    }
+}
+
 impl<Count> From<InChITree<Count>> for RepeatNode<Count, Element> {
     fn from(tree: InChITree<Count>) -> Self {
         tree.node
@@ -80,6 +96,23 @@ impl<Count: CountLike> MolecularTree<Count> for InChITree<Count> {
         self.node.number_of_elements()
     }
 
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        self.node.try_number_of_elements::<C>()
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        self.node.element_mask()
+    }
+
     #[inline]
     fn count_of_element<C>(&self, element: Element) -> Option<C>
     where
@@ -108,6 +141,10 @@ impl<Count: CountLike> MolecularTree<Count> for InChITree<Count> {
         self.node.isotopologue_mass()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        self.node.isotopologue_mass_with(context)
+    }
+
     fn is_noble_gas_compound(&self) -> bool {
         self.node.is_noble_gas_compound()
     }
@@ -137,6 +174,17 @@ impl<Count> From<RepeatNode<Count, Element>> for InChITree<Count> {
     }
 }
 
+impl<Count: CountLike> StyledDisplay for InChITree<Count> {
+    fn fmt_with(
+        &self,
+        _style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        // InChI formulas always use baseline counts, regardless of the style.
+        write!(f, "{self}")
+    }
+}
+
 impl<Count: CountLike> Display for InChITree<Count> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if self.node.count().is_one() {
@@ -0,0 +1,49 @@
+//! Submodule providing the pointer owning the subtrees of chemical trees,
+//! which is shared between clones when the `shared-tree` feature is enabled.
+
+#[cfg(not(feature = "shared-tree"))]
+/// Pointer owning the subtrees of the bracket, charge, radical and repeat
+/// nodes of chemical trees.
+///
+/// Without the `shared-tree` feature, each subtree is boxed and cloning a
+/// tree deeply copies all of its subtrees.
+pub(crate) type Subtree<T> = alloc::boxed::Box<T>;
+
+#[cfg(feature = "shared-tree")]
+/// Pointer owning the subtrees of the bracket, charge, radical and repeat
+/// nodes of chemical trees.
+///
+/// With the `shared-tree` feature, each subtree is reference-counted, so
+/// that cloning a tree only copies its outermost sequence while its
+/// subtrees are shared between the clones. Shared subtrees are copied on
+/// write, i.e. only when one of the clones is edited.
+pub(crate) type Subtree<T> = alloc::sync::Arc<T>;
+
+/// Returns the tree owned by the provided subtree, cloning it only if it is
+/// shared with other trees.
+#[inline]
+#[cfg_attr(not(feature = "shared-tree"), allow(clippy::boxed_local))]
+pub(crate) fn into_inner<T: Clone>(subtree: Subtree<T>) -> T {
+    #[cfg(not(feature = "shared-tree"))]
+    {
+        *subtree
+    }
+    #[cfg(feature = "shared-tree")]
+    {
+        alloc::sync::Arc::unwrap_or_clone(subtree)
+    }
+}
+
+/// Returns a mutable reference to the tree owned by the provided subtree,
+/// first cloning it if it is shared with other trees.
+#[inline]
+pub(crate) fn make_mut<T: Clone>(subtree: &mut Subtree<T>) -> &mut T {
+    #[cfg(not(feature = "shared-tree"))]
+    {
+        subtree
+    }
+    #[cfg(feature = "shared-tree")]
+    {
+        alloc::sync::Arc::make_mut(subtree)
+    }
+}
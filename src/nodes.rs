@@ -8,14 +8,16 @@ mod charge_node;
 mod element_node;
 mod empty_node;
 mod isotope_node;
+mod packed_repeat_node;
 mod radical_node;
 mod repeat_node;
 mod residual_node;
 mod sequence_node;
 
 pub(crate) use bracket_node::BracketNode;
-pub(crate) use charge_node::ChargeNode;
-pub(crate) use empty_node::Empty;
+pub(crate) use charge_node::{ChargeNode, ELECTRON_MASS};
+pub use empty_node::Empty;
+pub(crate) use packed_repeat_node::PackedRepeatNode;
 pub(crate) use radical_node::{Radical, RadicalNode};
 pub(crate) use repeat_node::RepeatNode;
 pub use residual_node::Residual;
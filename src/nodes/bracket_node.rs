@@ -5,7 +5,9 @@
 
 use core::fmt::Display;
 
-use crate::{Bracket, ChargeLike, ChargedMolecularTree, CountLike};
+use crate::{
+    Bracket, ChargeLike, ChargedMolecularTree, CountLike, DisplayStyle, MassContext, StyledDisplay,
+};
 
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -23,6 +25,12 @@ impl<T> AsRef<T> for BracketNode<T> {
     }
 }
 
+impl<T> AsMut<T> for BracketNode<T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.tree
+    }
+}
+
 impl<T> BracketNode<T> {
     /// Creates a new `BracketNode` wrapping the given tree with round brackets
     /// `()`.
@@ -35,6 +43,30 @@ impl<T> BracketNode<T> {
     pub fn square(tree: T) -> Self {
         Self { tree, bracket: Bracket::Square }
     }
+
+    /// Returns the type of bracket used.
+    #[cfg(feature = "arena")]
+    pub(crate) fn bracket(&self) -> Bracket {
+        self.bracket
+    }
+
+    /// Converts the node into the underlying tree, consuming the brackets.
+    pub(crate) fn into_tree(self) -> T {
+        self.tree
+    }
+
+    /// Maps the underlying tree, preserving the bracket type.
+    pub(crate) fn map<U>(self, f: impl FnOnce(T) -> U) -> BracketNode<U> {
+        BracketNode { tree: f(self.tree), bracket: self.bracket }
+    }
+
+    /// Fallibly maps the underlying tree, preserving the bracket type.
+    pub(crate) fn try_map<U, E>(
+        self,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> Result<BracketNode<U>, E> {
+        Ok(BracketNode { tree: f(self.tree)?, bracket: self.bracket })
+    }
 }
 
 impl<Count, T: crate::MolecularTree<Count>> crate::MolecularTree<Count> for BracketNode<T> {
@@ -83,6 +115,23 @@ impl<Count, T: crate::MolecularTree<Count>> crate::MolecularTree<Count> for Brac
         self.tree.number_of_elements()
     }
 
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        self.tree.try_number_of_elements::<C>()
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        self.tree.element_mask()
+    }
+
     #[inline]
     fn contains_isotope(&self, isotope: elements_rs::Isotope) -> bool {
         self.tree.contains_isotope(isotope)
@@ -116,6 +165,10 @@ impl<Count, T: crate::MolecularTree<Count>> crate::MolecularTree<Count> for Brac
         self.tree.isotopologue_mass()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        self.tree.isotopologue_mass_with(context)
+    }
+
     fn is_noble_gas_compound(&self) -> bool {
         self.tree.is_noble_gas_compound()
     }
@@ -133,9 +186,22 @@ impl<Count, T: crate::MolecularTree<Count>> crate::MolecularTree<Count> for Brac
     }
 }
 
-impl<T: Display> Display for BracketNode<T> {
+impl<T: StyledDisplay> Display for BracketNode<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}{}{}", self.bracket.opening(), self.tree, self.bracket.closing())
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<T: StyledDisplay> StyledDisplay for BracketNode<T> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        let bracket = style.bracket(self.bracket);
+        write!(f, "{}", bracket.opening())?;
+        self.tree.fmt_with(style, f)?;
+        write!(f, "{}", bracket.closing())
     }
 }
 
@@ -146,6 +212,10 @@ impl<Count: CountLike, Charge: ChargeLike, T: ChargedMolecularTree<Count, Charge
         self.tree.charge()
     }
 
+    fn net_charge_integer(&self) -> Option<i64> {
+        self.tree.net_charge_integer()
+    }
+
     fn isotopologue_mass_with_charge(&self) -> f64 {
         self.tree.isotopologue_mass_with_charge()
     }
@@ -153,4 +223,8 @@ impl<Count: CountLike, Charge: ChargeLike, T: ChargedMolecularTree<Count, Charge
     fn molar_mass(&self) -> f64 {
         self.tree.molar_mass()
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        self.tree.molar_mass_with(context)
+    }
 }
@@ -1,9 +1,13 @@
 //! Submodule providing a struct and implementation of the `ExtensionTree` trait
 //! for molecular formulas that can contain charges.
 
-use crate::{ChargeLike, ChargedMolecularTree, CountLike, MolecularTree, display_charge};
+use crate::{
+    ChargeLike, ChargedMolecularTree, CountLike, DisplayStyle, MassContext, MolecularTree,
+    StyledDisplay,
+};
 
-const ELECTRON_MASS: f64 = 0.000548579909065;
+/// Mass of the electron, in daltons.
+pub(crate) const ELECTRON_MASS: f64 = 0.000548579909065;
 
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -21,6 +25,12 @@ impl<C, T> AsRef<T> for ChargeNode<C, T> {
     }
 }
 
+impl<C, T> AsMut<T> for ChargeNode<C, T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.tree
+    }
+}
+
 impl<C: ChargeLike, T> ChargeNode<C, T> {
     /// Creates a new `ChargeNode` with the given charge and underlying tree.
     pub fn new(charge: C, tree: T) -> Self {
@@ -33,10 +43,20 @@ impl<C: ChargeLike, T> ChargeNode<C, T> {
     }
 }
 
-impl<C: ChargeLike, T: core::fmt::Display> core::fmt::Display for ChargeNode<C, T> {
+impl<C: ChargeLike, T: StyledDisplay> core::fmt::Display for ChargeNode<C, T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.tree)?;
-        display_charge(self.charge, f)
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<C: ChargeLike, T: StyledDisplay> StyledDisplay for ChargeNode<C, T> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        self.tree.fmt_with(style, f)?;
+        style.fmt_charge(self.charge, f)
     }
 }
 
@@ -83,6 +103,23 @@ impl<Count: CountLike, Charge: ChargeLike, T: MolecularTree<Count>> MolecularTre
         self.tree.number_of_elements()
     }
 
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        self.tree.try_number_of_elements::<C>()
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        self.tree.element_mask()
+    }
+
     #[inline]
     fn contains_element(&self, element: elements_rs::Element) -> bool {
         self.tree.contains_element(element)
@@ -121,6 +158,10 @@ impl<Count: CountLike, Charge: ChargeLike, T: MolecularTree<Count>> MolecularTre
         self.tree.isotopologue_mass()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        self.tree.isotopologue_mass_with(context)
+    }
+
     fn is_noble_gas_compound(&self) -> bool {
         self.tree.is_noble_gas_compound()
     }
@@ -142,16 +183,26 @@ impl<Count: CountLike, Charge: ChargeLike, T: ChargedMolecularTree<Count, Charge
     ChargedMolecularTree<Count, Charge> for ChargeNode<Charge, T>
 {
     fn charge(&self) -> f64 {
-        self.charge.into()
+        let charge: f64 = self.charge.as_();
+        charge + self.tree.charge()
+    }
+
+    fn net_charge_integer(&self) -> Option<i64> {
+        i64::try_from(self.charge.into()).ok()?.checked_add(self.tree.net_charge_integer()?)
     }
 
     fn isotopologue_mass_with_charge(&self) -> f64 {
-        let charge: f64 = self.charge.into();
+        let charge: f64 = self.charge.as_();
         self.tree.isotopologue_mass_with_charge() - charge * ELECTRON_MASS
     }
 
     fn molar_mass(&self) -> f64 {
-        let charge: f64 = self.charge.into();
+        let charge: f64 = self.charge.as_();
         self.tree.molar_mass() - charge * ELECTRON_MASS
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        let charge: f64 = self.charge.as_();
+        self.tree.molar_mass_with(context) - charge * ELECTRON_MASS
+    }
 }
@@ -4,7 +4,7 @@
 
 use elements_rs::{BondsNumber, Element, RelativeAtomicMass};
 
-use crate::{ChargedMolecularTree, MolecularTree};
+use crate::{ChargedMolecularTree, MassContext, MolecularTree};
 
 impl<Count> MolecularTree<Count> for Element {
     type ElementIter<'a>
@@ -59,6 +59,23 @@ impl<Count> MolecularTree<Count> for Element {
         1
     }
 
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        Some(C::ONE)
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        elements_rs::ElementMask::from(*self)
+    }
+
     #[inline]
     fn count_of_element<C>(&self, element: Element) -> Option<C>
     where
@@ -87,6 +104,10 @@ impl<Count> MolecularTree<Count> for Element {
         self.relative_atomic_mass()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        context.element_mass(*self)
+    }
+
     fn is_noble_gas_compound(&self) -> bool {
         self.is_noble_gas()
     }
@@ -114,6 +135,10 @@ impl<Count, Charge> ChargedMolecularTree<Count, Charge> for Element {
         0.0
     }
 
+    fn net_charge_integer(&self) -> Option<i64> {
+        Some(0)
+    }
+
     fn isotopologue_mass_with_charge(&self) -> f64 {
         self.relative_atomic_mass()
     }
@@ -121,4 +146,8 @@ impl<Count, Charge> ChargedMolecularTree<Count, Charge> for Element {
     fn molar_mass(&self) -> f64 {
         self.standard_atomic_weight()
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        context.average_mass(*self)
+    }
 }
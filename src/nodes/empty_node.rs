@@ -5,7 +5,8 @@ use core::fmt::Display;
 #[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
-/// Marker type for an empty node.
+/// Marker type for an empty node, used as the extension of the formulas
+/// which do not allow any extension, such as chemical formulas.
 pub struct Empty;
 
 impl Display for Empty {
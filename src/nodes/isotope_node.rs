@@ -3,7 +3,7 @@
 
 use elements_rs::{BondsNumber, Element, ElementVariant, Isotope, RelativeAtomicMass};
 
-use crate::{ChargedMolecularTree, MolecularTree};
+use crate::{ChargedMolecularTree, MassContext, MolecularTree};
 
 impl<Count> MolecularTree<Count> for Isotope {
     type ElementIter<'a>
@@ -56,6 +56,23 @@ impl<Count> MolecularTree<Count> for Isotope {
         1
     }
 
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        Some(C::ONE)
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        elements_rs::ElementMask::from(self.element())
+    }
+
     #[inline]
     fn count_of_element<C>(&self, element: Element) -> Option<C>
     where
@@ -84,6 +101,10 @@ impl<Count> MolecularTree<Count> for Isotope {
         self.relative_atomic_mass()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        context.isotope_mass(*self)
+    }
+
     #[inline]
     fn is_noble_gas_compound(&self) -> bool {
         self.is_noble_gas()
@@ -113,6 +134,10 @@ impl<Count, Charge> ChargedMolecularTree<Count, Charge> for Isotope {
         0.0
     }
 
+    fn net_charge_integer(&self) -> Option<i64> {
+        Some(0)
+    }
+
     fn isotopologue_mass_with_charge(&self) -> f64 {
         self.relative_atomic_mass()
     }
@@ -120,4 +145,8 @@ impl<Count, Charge> ChargedMolecularTree<Count, Charge> for Isotope {
     fn molar_mass(&self) -> f64 {
         self.element().standard_atomic_weight()
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        context.isotope_mass(*self)
+    }
 }
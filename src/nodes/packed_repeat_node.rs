@@ -0,0 +1,65 @@
+//! A repeat node packing a small number of repetitions of a single element
+//! inline, so that the most common repeats, such as the `C6` of `C6H12O6`,
+//! do not allocate the node being repeated.
+
+use core::{fmt::Display, num::NonZeroU8};
+
+use elements_rs::Element;
+
+use crate::{CountLike, DisplayStyle, RepeatNode, StyledDisplay};
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackedRepeatNode {
+    /// The element being repeated.
+    element: Element,
+    /// The number of repetitions.
+    count: NonZeroU8,
+}
+
+impl PackedRepeatNode {
+    /// Returns the packed repetitions of the provided element, or None if
+    /// the count is zero or does not fit in a byte, in which case the
+    /// repetitions are stored in a [`RepeatNode`].
+    pub(crate) fn new<Count: CountLike>(element: Element, count: Count) -> Option<Self> {
+        let count: i128 = count.into();
+        Some(Self { element, count: NonZeroU8::new(u8::try_from(count).ok()?)? })
+    }
+
+    /// Get the element being repeated.
+    pub fn element(self) -> Element {
+        self.element
+    }
+
+    /// Get the count of the repeat node.
+    ///
+    /// # Panics
+    ///
+    /// * If the count does not fit in the provided count type, which cannot
+    ///   happen for nodes built from counts of that type.
+    pub fn count<Count: CountLike>(self) -> Count {
+        Count::try_from(u64::from(self.count.get()))
+            .unwrap_or_else(|_| unreachable!("Packed counts were built from the count type"))
+    }
+
+    /// Returns the repetitions as a [`RepeatNode`] of the element.
+    pub(crate) fn unpack<Count: CountLike>(self) -> RepeatNode<Count, Element> {
+        RepeatNode::new(self.count(), self.element)
+    }
+}
+
+impl Display for PackedRepeatNode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl StyledDisplay for PackedRepeatNode {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        self.unpack::<u8>().fmt_with(style, f)
+    }
+}
@@ -3,7 +3,10 @@
 
 use core::fmt::Display;
 
-use crate::{Baseline, CharacterMarker, ChargedMolecularTree, MolecularTree};
+use crate::{
+    Baseline, CharacterMarker, ChargedMolecularTree, DisplayStyle, MassContext, MolecularTree,
+    StyledDisplay,
+};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -41,6 +44,12 @@ impl<T> AsRef<T> for RadicalNode<T> {
     }
 }
 
+impl<T> AsMut<T> for RadicalNode<T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.node
+    }
+}
+
 impl<T> RadicalNode<T> {
     /// Creates a new left-hand side radical node.
     pub fn left(node: T) -> Self {
@@ -51,6 +60,29 @@ impl<T> RadicalNode<T> {
     pub fn right(node: T) -> Self {
         Self { node, left_side: false }
     }
+
+    /// Returns whether the radical is on the left-hand side of the node.
+    pub(crate) fn is_left(&self) -> bool {
+        self.left_side
+    }
+
+    /// Converts the node into the underlying node, consuming the radical.
+    pub(crate) fn into_node(self) -> T {
+        self.node
+    }
+
+    /// Maps the underlying node, preserving the side of the radical.
+    pub(crate) fn map<U>(self, f: impl FnOnce(T) -> U) -> RadicalNode<U> {
+        RadicalNode { node: f(self.node), left_side: self.left_side }
+    }
+
+    /// Fallibly maps the underlying node, preserving the side of the radical.
+    pub(crate) fn try_map<U, E>(
+        self,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> Result<RadicalNode<U>, E> {
+        Ok(RadicalNode { node: f(self.node)?, left_side: self.left_side })
+    }
 }
 
 impl<Count, T: MolecularTree<Count>> MolecularTree<Count> for RadicalNode<T> {
@@ -84,6 +116,23 @@ impl<Count, T: MolecularTree<Count>> MolecularTree<Count> for RadicalNode<T> {
         self.node.number_of_elements()
     }
 
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        self.node.try_number_of_elements::<C>()
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        self.node.element_mask()
+    }
+
     #[inline]
     fn contains_non_hydrogens(&self) -> bool {
         self.node.contains_non_hydrogens()
@@ -132,6 +181,10 @@ impl<Count, T: MolecularTree<Count>> MolecularTree<Count> for RadicalNode<T> {
         self.node.isotopologue_mass()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        self.node.isotopologue_mass_with(context)
+    }
+
     #[inline]
     fn is_noble_gas_compound(&self) -> bool {
         self.node.is_noble_gas_compound()
@@ -151,12 +204,24 @@ impl<Count, T: MolecularTree<Count>> MolecularTree<Count> for RadicalNode<T> {
     }
 }
 
-impl<T: Display> Display for RadicalNode<T> {
+impl<T: StyledDisplay> Display for RadicalNode<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<T: StyledDisplay> StyledDisplay for RadicalNode<T> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
         if self.left_side {
-            write!(f, "{Radical}{}", self.node)
+            write!(f, "{Radical}")?;
+            self.node.fmt_with(style, f)
         } else {
-            write!(f, "{}{Radical}", self.node)
+            self.node.fmt_with(style, f)?;
+            write!(f, "{Radical}")
         }
     }
 }
@@ -168,6 +233,10 @@ impl<T: ChargedMolecularTree<Count, Charge>, Count, Charge> ChargedMolecularTree
         self.node.charge()
     }
 
+    fn net_charge_integer(&self) -> Option<i64> {
+        self.node.net_charge_integer()
+    }
+
     fn isotopologue_mass_with_charge(&self) -> f64 {
         self.node.isotopologue_mass_with_charge()
     }
@@ -175,4 +244,8 @@ impl<T: ChargedMolecularTree<Count, Charge>, Count, Charge> ChargedMolecularTree
     fn molar_mass(&self) -> f64 {
         self.node.molar_mass()
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        self.node.molar_mass_with(context)
+    }
 }
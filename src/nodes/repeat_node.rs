@@ -4,7 +4,8 @@
 use core::fmt::Display;
 
 use crate::{
-    ChargeLike, ChargedMolecularTree, ChemicalTree, CountLike, MolecularTree, subscript_digits_ltr,
+    ChargeLike, ChargedMolecularTree, ChemicalTree, CountLike, DisplayStyle, MassContext,
+    MolecularTree, StyledDisplay, saturating_usize,
 };
 
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
@@ -31,10 +32,7 @@ where
         if repeat.count == Count::ONE {
             return ChemicalTree::from(repeat.node);
         }
-        ChemicalTree::Repeat(RepeatNode {
-            count: repeat.count,
-            node: alloc::boxed::Box::new(repeat.node.into()),
-        })
+        ChemicalTree::from(repeat.node).into_repeat(repeat.count)
     }
 }
 
@@ -59,22 +57,12 @@ impl<Count: CountLike, T: MolecularTree<Count>> MolecularTree<Count> for RepeatN
 
     #[inline]
     fn elements(&self) -> Self::ElementIter<'_> {
-        let count: usize = self
-            .count
-            .try_into()
-            .ok()
-            .expect("Count too large for usize - do you have an extremely large repeat count?");
-        core::iter::repeat_n(&self.node, count).flat_map(T::elements)
+        core::iter::repeat_n(&self.node, saturating_usize(self.count)).flat_map(T::elements)
     }
 
     #[inline]
     fn non_hydrogens(&self) -> Self::NonHydrogenElementIter<'_> {
-        let count: usize = self
-            .count
-            .try_into()
-            .ok()
-            .expect("Count too large for usize - do you have an extremely large repeat count?");
-        core::iter::repeat_n(&self.node, count).flat_map(T::non_hydrogens)
+        core::iter::repeat_n(&self.node, saturating_usize(self.count)).flat_map(T::non_hydrogens)
     }
 
     #[inline]
@@ -104,8 +92,24 @@ impl<Count: CountLike, T: MolecularTree<Count>> MolecularTree<Count> for RepeatN
 
     #[inline]
     fn number_of_elements(&self) -> usize {
-        let count: usize = self.count.try_into().ok().expect("Count too large for usize");
-        count * self.node.number_of_elements()
+        saturating_usize(self.count).saturating_mul(self.node.number_of_elements())
+    }
+
+    #[inline]
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        self.node.try_number_of_elements::<C>()?.checked_mul(&C::from(self.count))
+    }
+
+    #[inline]
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        self.node.element_mask()
     }
 
     #[inline]
@@ -138,10 +142,16 @@ impl<Count: CountLike, T: MolecularTree<Count>> MolecularTree<Count> for RepeatN
 
     #[inline]
     fn isotopologue_mass(&self) -> f64 {
-        let count: f64 = self.count.into();
+        let count: f64 = self.count.as_();
         self.node.isotopologue_mass() * count
     }
 
+    #[inline]
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        let count: f64 = self.count.as_();
+        self.node.isotopologue_mass_with(context) * count
+    }
+
     #[inline]
     fn is_noble_gas_compound(&self) -> bool {
         self.node.is_noble_gas_compound()
@@ -160,13 +170,20 @@ impl<Count: CountLike, T: MolecularTree<Count>> MolecularTree<Count> for RepeatN
     }
 }
 
-impl<Count: CountLike, T: Display> Display for RepeatNode<Count, T> {
+impl<Count: CountLike, T: StyledDisplay> Display for RepeatNode<Count, T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.node)?;
-        for digit in subscript_digits_ltr(self.count) {
-            write!(f, "{digit}")?;
-        }
-        Ok(())
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<Count: CountLike, T: StyledDisplay> StyledDisplay for RepeatNode<Count, T> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        self.node.fmt_with(style, f)?;
+        style.fmt_subscript(self.count, f)
     }
 }
 
@@ -192,17 +209,27 @@ impl<Count: CountLike, Charge, T: ChargedMolecularTree<Count, Charge>>
     ChargedMolecularTree<Count, Charge> for RepeatNode<Count, T>
 {
     fn charge(&self) -> f64 {
-        let count: f64 = self.count.into();
+        let count: f64 = self.count.as_();
         self.node.charge() * count
     }
 
+    fn net_charge_integer(&self) -> Option<i64> {
+        let count = i64::try_from(self.count.into()).ok()?;
+        self.node.net_charge_integer()?.checked_mul(count)
+    }
+
     fn isotopologue_mass_with_charge(&self) -> f64 {
-        let count: f64 = self.count.into();
+        let count: f64 = self.count.as_();
         self.node.isotopologue_mass_with_charge() * count
     }
 
     fn molar_mass(&self) -> f64 {
-        let count: f64 = self.count.into();
+        let count: f64 = self.count.as_();
         self.node.molar_mass() * count
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        let count: f64 = self.count.as_();
+        self.node.molar_mass_with(context) * count
+    }
 }
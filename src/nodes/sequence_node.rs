@@ -6,7 +6,10 @@ use alloc::vec::Vec;
 use core::fmt::Display;
 
 use super::{Node, Supports};
-use crate::{ChargeLike, ChargedMolecularTree, CountLike, MolecularTree};
+use crate::{
+    ChargeLike, ChargedMolecularTree, CountLike, DisplayStyle, MassContext, MolecularTree,
+    StyledDisplay,
+};
 
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -41,10 +44,55 @@ impl<N> SequenceNode<N> {
         self.nodes.iter()
     }
 
+    /// Returns a mutable iterator over the nodes in the sequence.
+    pub(crate) fn iter_mut(&mut self) -> core::slice::IterMut<'_, N> {
+        self.nodes.iter_mut()
+    }
+
+    /// Returns the number of nodes the sequence can hold without
+    /// reallocating.
+    pub(crate) fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Returns the number of nodes in the sequence.
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns a mutable reference to the node at the provided position.
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut N> {
+        self.nodes.get_mut(index)
+    }
+
+    /// Removes and returns the node at the provided position.
+    pub(crate) fn remove(&mut self, index: usize) -> N {
+        self.nodes.remove(index)
+    }
+
+    /// Inserts a node at the provided position, shifting the following ones.
+    pub(crate) fn insert(&mut self, index: usize, node: N) {
+        self.nodes.insert(index, node);
+    }
+
     /// Returns an into iterator over the nodes in the sequence.
     pub(crate) fn into_iter(self) -> alloc::vec::IntoIter<N> {
         self.nodes.into_iter()
     }
+
+    /// Maps each of the nodes in the sequence.
+    pub(crate) fn map<U>(self, f: impl FnMut(N) -> U) -> SequenceNode<U> {
+        SequenceNode { nodes: self.nodes.into_iter().map(f).collect() }
+    }
+
+    /// Fallibly maps each of the nodes in the sequence, stopping at the first
+    /// error.
+    pub(crate) fn try_map<U, E>(
+        self,
+        f: impl FnMut(N) -> Result<U, E>,
+    ) -> Result<SequenceNode<U>, E> {
+        Ok(SequenceNode { nodes: self.nodes.into_iter().map(f).collect::<Result<_, E>>()? })
+    }
 }
 
 impl<M, N> Supports<M> for SequenceNode<N>
@@ -54,10 +102,20 @@ where
 {
 }
 
-impl<N: Display> Display for SequenceNode<N> {
+impl<N: StyledDisplay> Display for SequenceNode<N> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with(&DisplayStyle::new(), f)
+    }
+}
+
+impl<N: StyledDisplay> StyledDisplay for SequenceNode<N> {
+    fn fmt_with(
+        &self,
+        style: &DisplayStyle,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
         for node in &self.nodes {
-            write!(f, "{node}")?;
+            node.fmt_with(style, f)?;
         }
         Ok(())
     }
@@ -111,7 +169,26 @@ impl<Count, T: MolecularTree<Count>> MolecularTree<Count> for SequenceNode<T> {
     }
 
     fn number_of_elements(&self) -> usize {
-        self.nodes.iter().map(|node: &T| node.number_of_elements()).sum()
+        self.nodes.iter().fold(0, |total, node: &T| total.saturating_add(node.number_of_elements()))
+    }
+
+    fn try_number_of_elements<C>(&self) -> Option<C>
+    where
+        C: From<Count>
+            + num_traits::CheckedAdd
+            + num_traits::CheckedMul
+            + num_traits::ConstZero
+            + num_traits::ConstOne,
+    {
+        let mut total = C::ZERO;
+        for node in &self.nodes {
+            total = total.checked_add(&node.try_number_of_elements::<C>()?)?;
+        }
+        Some(total)
+    }
+
+    fn element_mask(&self) -> elements_rs::ElementMask {
+        self.nodes.iter().flat_map(|node: &T| node.element_mask()).collect()
     }
 
     fn count_of_element<C>(&self, element: elements_rs::Element) -> Option<C>
@@ -148,6 +225,10 @@ impl<Count, T: MolecularTree<Count>> MolecularTree<Count> for SequenceNode<T> {
         self.nodes.iter().map(MolecularTree::isotopologue_mass).sum()
     }
 
+    fn isotopologue_mass_with(&self, context: &MassContext) -> f64 {
+        self.nodes.iter().map(|node| node.isotopologue_mass_with(context)).sum()
+    }
+
     fn is_noble_gas_compound(&self) -> bool {
         self.nodes.iter().all(MolecularTree::is_noble_gas_compound)
     }
@@ -175,6 +256,12 @@ impl<Count: CountLike, Charge: ChargeLike, T: ChargedMolecularTree<Count, Charge
         self.nodes.iter().map(ChargedMolecularTree::charge).sum()
     }
 
+    fn net_charge_integer(&self) -> Option<i64> {
+        self.nodes
+            .iter()
+            .try_fold(0_i64, |total, node| total.checked_add(node.net_charge_integer()?))
+    }
+
     fn isotopologue_mass_with_charge(&self) -> f64 {
         self.nodes.iter().map(ChargedMolecularTree::isotopologue_mass_with_charge).sum()
     }
@@ -182,4 +269,8 @@ impl<Count: CountLike, Charge: ChargeLike, T: ChargedMolecularTree<Count, Charge
     fn molar_mass(&self) -> f64 {
         self.nodes.iter().map(ChargedMolecularTree::molar_mass).sum()
     }
+
+    fn molar_mass_with(&self, context: &MassContext) -> f64 {
+        self.nodes.iter().map(|node| node.molar_mass_with(context)).sum()
+    }
 }
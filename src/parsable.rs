@@ -1,18 +1,45 @@
 //! Submodule defining a parsable entity.
 
+mod byte_decoding;
+mod case_folding;
+mod dialect;
+mod formula_cache;
 mod from_str_impls;
+mod incremental_parser;
 mod molecule_parser;
 mod parsable_formula;
 mod parsable_molecular_tree;
+mod parser_options;
+mod suggestions;
 mod tokens;
+mod tree_builder;
+mod validation_profiles;
 
 use core::fmt::Debug;
 
+#[cfg(feature = "arena")]
+pub use bumpalo::Bump;
+pub use case_folding::{CaseAmbiguity, CaseFolding};
+pub use dialect::Dialect;
+pub use formula_cache::FormulaCache;
+pub use incremental_parser::{ExpectedToken, IncrementalParser, ValidationState};
+#[cfg(feature = "arena")]
+pub use molecule_parser::{parse_in, parse_in_with_options};
 pub(crate) use parsable_formula::ParsableFormula;
+pub(crate) use parser_options::DEFAULT_PARSER_OPTIONS;
+pub use parser_options::{
+    ChargeNotation, FromStrWithOptions, HapticityHandling, MiddleDotHandling, ParserOptions,
+    PrefixHandling, ZeroCountHandling,
+};
+pub use suggestions::{MAX_TWO_EDITS_LENGTH, suggest};
 pub use tokens::*;
+pub use validation_profiles::{ProfileViolation, ValidationProfile};
 
 use crate::parsable::molecule_parser::MoleculeParser;
-pub(crate) use crate::parsable::parsable_molecular_tree::ParsableMolecularTree;
+pub(crate) use crate::parsable::{
+    parsable_molecular_tree::ParsableMolecularTree,
+    tree_builder::{ChemicalTreeBuilder, OwnedTrees, TreeBuilder},
+};
 
 /// Trait for tokens used in parsing molecular formulas.
 pub(crate) trait TokenLike: Copy + Eq + Sized + Debug {
@@ -31,6 +58,10 @@ pub(crate) trait TokenLike: Copy + Eq + Sized + Debug {
     /// Returns the element associated with this token if it represents an
     /// element.
     fn as_element(&self) -> Option<elements_rs::Element>;
+
+    /// Returns whether this token wraps the unit preceding it into a new
+    /// node, as trailing charges and radicals do.
+    fn wraps_unit(&self) -> bool;
 }
 
 #[cfg(test)]
@@ -0,0 +1,45 @@
+//! Submodule providing the decoding of formulas read as raw bytes, such as
+//! the lines of legacy instrument exports, which may be encoded either in
+//! UTF-8 or in Latin-1.
+
+use alloc::{borrow::Cow, string::String};
+
+/// Returns the string encoded by the provided bytes, decoding them as UTF-8
+/// and falling back to Latin-1 for each byte which is not part of a valid
+/// UTF-8 sequence.
+///
+/// Valid UTF-8 is borrowed without copying, so that only the inputs with
+/// invalid sequences are transcoded. Since Latin-1 maps each byte to the
+/// code point of the same value, the middle dot encoded as `0xB7` in
+/// Latin-1 is decoded as `·`, as it is when encoded as `0xC2 0xB7` in UTF-8.
+pub(super) fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    let mut chunks = bytes.utf8_chunks();
+    let Some(first) = chunks.next() else {
+        return Cow::Borrowed("");
+    };
+    if first.invalid().is_empty() {
+        return Cow::Borrowed(first.valid());
+    }
+    let mut decoded = String::with_capacity(bytes.len() + first.invalid().len());
+    for chunk in core::iter::once(first).chain(chunks) {
+        decoded.push_str(chunk.valid());
+        decoded.extend(chunk.invalid().iter().copied().map(char::from));
+    }
+    Cow::Owned(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert!(matches!(decode(b"C6H12O6"), Cow::Borrowed("C6H12O6")));
+        assert!(matches!(decode("CuSO4·5H2O".as_bytes()), Cow::Borrowed("CuSO4·5H2O")));
+        assert!(matches!(decode(b""), Cow::Borrowed("")));
+        assert_eq!(decode(b"CuSO4\xB75H2O"), "CuSO4·5H2O");
+        assert_eq!(decode(b"\xB7CH3"), "·CH3");
+        assert_eq!(decode(b"CH3\xB7"), "CH3·");
+        assert_eq!(decode(b"\xE9\xB7"), "é·");
+    }
+}
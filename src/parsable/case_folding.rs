@@ -0,0 +1,227 @@
+//! Submodule providing the case folding of formulas written in uppercase,
+//! as stored by many legacy databases, e.g. `NACL` for `NaCl`.
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use elements_rs::Element;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Run of uppercase letters which may be read as several sequences of
+/// element symbols, such as `CO`, which is either cobalt or carbon and
+/// oxygen.
+pub struct CaseAmbiguity {
+    /// The run of uppercase letters, as written.
+    run: String,
+    /// The element symbols chosen for the run.
+    reading: String,
+    /// The number of sequences of element symbols spelling the run.
+    number_of_readings: usize,
+}
+
+impl CaseAmbiguity {
+    /// Returns the run of uppercase letters, as written.
+    #[must_use]
+    pub fn run(&self) -> &str {
+        &self.run
+    }
+
+    /// Returns the element symbols chosen for the run, e.g. `Co` for `CO`.
+    #[must_use]
+    pub fn reading(&self) -> &str {
+        &self.reading
+    }
+
+    /// Returns the number of sequences of element symbols spelling the run,
+    /// which is at least two.
+    #[must_use]
+    pub fn number_of_readings(&self) -> usize {
+        self.number_of_readings
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Formula whose runs of uppercase letters were rewritten as element
+/// symbols, alongside the runs which could be read in several ways.
+///
+/// Each run of two or more uppercase letters is split into the element
+/// symbols spelling it, case-insensitively, preferring at each position the
+/// two-letter symbol over the one-letter one unless the rest of the run
+/// could then not be spelled. Runs containing lowercase letters are kept as
+/// written, as are the runs which cannot be spelled with element symbols,
+/// so that the parser reports them. This is the folding applied by
+/// [`ParserOptions::with_case_folding`](crate::ParserOptions::with_case_folding),
+/// whose runs with several readings are reported here.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// let salt = CaseFolding::new("NACL");
+/// assert_eq!(salt.folded(), "NaCl");
+/// assert!(!salt.is_ambiguous());
+///
+/// let fluoride = CaseFolding::new("63F6BR.N");
+/// assert_eq!(fluoride.folded(), "63F6Br.N");
+///
+/// let monoxide = CaseFolding::new("CO");
+/// assert_eq!(monoxide.folded(), "Co");
+/// let ambiguity = &monoxide.ambiguities()[0];
+/// assert_eq!((ambiguity.run(), ambiguity.reading()), ("CO", "Co"));
+/// assert_eq!(ambiguity.number_of_readings(), 2);
+/// ```
+pub struct CaseFolding {
+    /// The formula with its runs of uppercase letters folded.
+    folded: String,
+    /// The runs which could be read in several ways.
+    ambiguities: Vec<CaseAmbiguity>,
+}
+
+impl CaseFolding {
+    /// Folds the runs of uppercase letters of the provided formula.
+    #[must_use]
+    pub fn new(formula: &str) -> Self {
+        let mut folded = String::with_capacity(formula.len());
+        let mut ambiguities = Vec::new();
+        let mut rest = formula;
+        while !rest.is_empty() {
+            let letters =
+                rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_alphabetic()).len();
+            if letters == 0 {
+                let c =
+                    rest.chars().next().unwrap_or_else(|| unreachable!("The rest is not empty"));
+                folded.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+            let (run, tail) = rest.split_at(letters);
+            match spell(run) {
+                Some((reading, number_of_readings)) => {
+                    if number_of_readings > 1 {
+                        ambiguities.push(CaseAmbiguity {
+                            run: run.to_string(),
+                            reading: reading.clone(),
+                            number_of_readings,
+                        });
+                    }
+                    folded.push_str(&reading);
+                }
+                None => folded.push_str(run),
+            }
+            rest = tail;
+        }
+        Self { folded, ambiguities }
+    }
+
+    /// Returns the formula with its runs of uppercase letters folded.
+    #[must_use]
+    pub fn folded(&self) -> &str {
+        &self.folded
+    }
+
+    /// Returns the runs of uppercase letters which could be read in several
+    /// ways, in the order in which they appear.
+    #[must_use]
+    pub fn ambiguities(&self) -> &[CaseAmbiguity] {
+        &self.ambiguities
+    }
+
+    /// Returns whether any run of uppercase letters could be read in
+    /// several ways.
+    #[must_use]
+    pub fn is_ambiguous(&self) -> bool {
+        !self.ambiguities.is_empty()
+    }
+}
+
+/// Returns the provided formula with its runs of uppercase letters folded,
+/// or `None` if no run was changed.
+pub(super) fn fold(formula: &str) -> Option<String> {
+    let folded = CaseFolding::new(formula).folded;
+    (folded != formula).then_some(folded)
+}
+
+/// Returns whether the provided uppercase letters spell an element symbol
+/// or a hydrogen isotope.
+fn is_symbol(letters: &[u8]) -> bool {
+    match *letters {
+        [b'D' | b'T'] => true,
+        [first] => Element::try_from(char::from(first)).is_ok(),
+        [first, second] => {
+            Element::try_from([char::from(first), char::from(second.to_ascii_lowercase())]).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Returns the element symbols spelling the provided run of letters,
+/// preferring the longest symbol at each position, alongside the number of
+/// ways in which the run may be spelled, or `None` if the run is a single
+/// letter, contains a lowercase letter or cannot be spelled.
+fn spell(run: &str) -> Option<(String, usize)> {
+    let letters = run.as_bytes();
+    if letters.len() < 2 || !letters.iter().all(u8::is_ascii_uppercase) {
+        return None;
+    }
+    // The number of ways in which each suffix of the run may be spelled.
+    let mut readings = vec![0_usize; letters.len() + 1];
+    readings[letters.len()] = 1;
+    for start in (0..letters.len()).rev() {
+        readings[start] = [1, 2]
+            .into_iter()
+            .filter(|length| start + length <= letters.len())
+            .filter(|length| is_symbol(&letters[start..start + length]))
+            .map(|length| readings[start + length])
+            .fold(0, usize::saturating_add);
+    }
+    if readings[0] == 0 {
+        return None;
+    }
+    let mut reading = String::with_capacity(letters.len());
+    let mut start = 0;
+    while start < letters.len() {
+        let length = if start + 2 <= letters.len()
+            && is_symbol(&letters[start..start + 2])
+            && readings[start + 2] > 0
+        {
+            2
+        } else {
+            1
+        };
+        reading.push(char::from(letters[start]));
+        if length == 2 {
+            reading.push(char::from(letters[start + 1].to_ascii_lowercase()));
+        }
+        start += length;
+    }
+    Some((reading, readings[0]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell() {
+        assert_eq!(spell("NACL"), Some(("NaCl".to_string(), 1)));
+        assert_eq!(spell("CO"), Some(("Co".to_string(), 2)));
+        assert_eq!(spell("CHO"), Some(("CHo".to_string(), 2)));
+        assert_eq!(spell("BR"), Some(("Br".to_string(), 1)));
+        assert_eq!(spell("CD"), Some(("Cd".to_string(), 2)));
+        assert_eq!(spell("QX"), None);
+        assert_eq!(spell("Cl"), None);
+        assert_eq!(spell("C"), None);
+    }
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold("NACL").as_deref(), Some("NaCl"));
+        assert_eq!(fold("NaCl"), None);
+        assert_eq!(fold("C6H12O6"), None);
+        assert_eq!(fold("CUSO4.5H2O").as_deref(), Some("CuSO4.5H2O"));
+    }
+}
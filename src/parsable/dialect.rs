@@ -0,0 +1,102 @@
+//! Submodule providing the dialects of the databases distributing molecular
+//! formulas, each bundling the parser options needed to read the formulas
+//! as they are written by its source.
+
+use crate::{
+    errors::ParserError,
+    parsable::{ChargeNotation, FromStrWithOptions, ParserOptions},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Writing conventions of the molecular formulas distributed by a database.
+///
+/// Each dialect provides the [`ParserOptions`] reading the formulas of its
+/// source. The formulas of sources using residuals, such as the `R` groups
+/// of ChEBI and KEGG, should be parsed into a
+/// [`ResidualFormula`](crate::ResidualFormula), while the formulas of
+/// InChI should be parsed into an [`InChIFormula`](crate::InChIFormula).
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// let sulfate: ChemicalFormula<u32, i32> = Dialect::Chebi.parse("SO4(2-)").unwrap();
+/// assert_eq!(sulfate, Dialect::PubChem.parse("SO4-2").unwrap());
+///
+/// let morphine_sulfate: ChemicalFormula<u32, i32> =
+///     Dialect::Kegg.parse("(C17H19NO3)2. H2SO4. 5H2O").unwrap();
+/// assert_eq!(morphine_sulfate.count_of_element::<u32>(Element::S), Some(1));
+///
+/// let glycine: ResidualFormula<u32, i32> = Dialect::Kegg.parse("C2H4NO2R").unwrap();
+/// assert_eq!(glycine.to_string(), "C₂H₄NO₂R");
+/// ```
+pub enum Dialect {
+    /// The molecular formulas of PubChem: elements in Hill order, mixture
+    /// components separated by full stops, and charges written after each
+    /// component with the sign first, as in `C6H5O7-3.3Na+`.
+    PubChem,
+    /// The molecular formulas of ChEBI, which may contain `R` groups and
+    /// write charges with the magnitude first within round brackets, as in
+    /// `SO4(2-)`. Other charges are read with the sign first, so that the
+    /// ambiguous `SO42-` is never read as a sulfate.
+    Chebi,
+    /// The molecular formulas of KEGG, which may contain `R` groups and
+    /// separate the mixture components of drugs with a full stop followed by
+    /// a space, as in `(C17H19NO3)2. H2SO4. 5H2O`.
+    Kegg,
+    /// The formula layer of InChI: elements in Hill order, neutral mixture
+    /// components separated by full stops, and component multipliers, as in
+    /// `2ClH.Fe`.
+    InChI,
+}
+
+impl Dialect {
+    /// Returns the name of the dialect.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert_eq!(Dialect::Chebi.name(), "ChEBI");
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PubChem => "PubChem",
+            Self::Chebi => "ChEBI",
+            Self::Kegg => "KEGG",
+            Self::InChI => "InChI",
+        }
+    }
+
+    /// Returns the parser options reading the formulas of the dialect.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert_eq!(Dialect::PubChem.parser_options(), ParserOptions::new());
+    /// assert!(Dialect::Kegg.parser_options().spaced_separators());
+    /// ```
+    #[must_use]
+    pub fn parser_options(&self) -> ParserOptions {
+        match self {
+            Self::PubChem | Self::InChI => ParserOptions::new(),
+            Self::Chebi => ParserOptions::new().with_charge_notation(ChargeNotation::Bracketed),
+            Self::Kegg => ParserOptions::new().with_spaced_separators(true),
+        }
+    }
+
+    /// Parses the provided formula written in the dialect.
+    ///
+    /// # Errors
+    ///
+    /// * If the string is not a valid formula of the dialect.
+    pub fn parse<M: FromStrWithOptions>(&self, formula: &str) -> Result<M, ParserError> {
+        M::from_str_with_options(formula, &self.parser_options())
+    }
+}
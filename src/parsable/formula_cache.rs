@@ -0,0 +1,165 @@
+//! Submodule providing a cache of parsed formulas, which parses each
+//! distinct string only once when reading datasets with many duplicated
+//! formulas.
+
+use alloc::{collections::BTreeMap, string::String, sync::Arc};
+
+use crate::{
+    ChemicalFormula,
+    errors::ParserError,
+    parsable::{FromStrWithOptions, ParserOptions},
+};
+
+#[derive(Debug, Clone)]
+/// Cache of parsed formulas, sharing a single parsed formula between all
+/// the occurrences of the same string.
+///
+/// Datasets such as PubChem contain the same formulas millions of times,
+/// e.g. `C6H12O6`: the cache parses each distinct string once and returns
+/// the same [`Arc`] for all of its occurrences. The cache holds at most
+/// [`capacity`](Self::capacity) formulas, evicting the least recently used
+/// one when full. Strings which cannot be parsed are not cached.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let mut cache: FormulaCache<ChemicalFormula<u32, i32>> = FormulaCache::new(2);
+/// let glucose = cache.parse("C6H12O6").unwrap();
+/// assert!(Arc::ptr_eq(&glucose, &cache.parse("C6H12O6").unwrap()));
+/// assert_eq!(glucose.to_string(), "C₆H₁₂O₆");
+///
+/// cache.parse("H2O").unwrap();
+/// cache.parse("NaCl").unwrap();
+/// assert_eq!(cache.len(), 2);
+/// assert!(!cache.contains("C6H12O6"));
+/// assert_eq!((cache.hits(), cache.misses()), (1, 3));
+/// ```
+pub struct FormulaCache<M = ChemicalFormula> {
+    /// The maximal number of cached formulas.
+    capacity: usize,
+    /// The options used to parse the formulas.
+    options: ParserOptions,
+    /// The cached formulas, with the tick of their last use.
+    formulas: BTreeMap<String, (Arc<M>, u64)>,
+    /// The strings of the cached formulas, sorted by the tick of their last
+    /// use.
+    recency: BTreeMap<u64, String>,
+    /// The tick of the next use of the cache.
+    tick: u64,
+    /// The number of parses answered by the cache.
+    hits: u64,
+    /// The number of parses which were not answered by the cache.
+    misses: u64,
+}
+
+impl<M> FormulaCache<M> {
+    /// Returns a new empty cache holding at most the provided number of
+    /// formulas, parsed with the default options.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self::with_options(capacity, ParserOptions::new())
+    }
+
+    /// Returns a new empty cache holding at most the provided number of
+    /// formulas, parsed with the provided options.
+    #[must_use]
+    pub fn with_options(capacity: usize, options: ParserOptions) -> Self {
+        Self {
+            capacity,
+            options,
+            formulas: BTreeMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the maximal number of cached formulas.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the options used to parse the formulas.
+    #[must_use]
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// Returns the number of cached formulas.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.formulas.len()
+    }
+
+    /// Returns whether the cache holds no formulas.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.formulas.is_empty()
+    }
+
+    /// Returns whether the formula parsed from the provided string is
+    /// cached, without counting as a use of the cache.
+    #[must_use]
+    pub fn contains(&self, formula: &str) -> bool {
+        self.formulas.contains_key(formula)
+    }
+
+    /// Returns the number of parses answered by the cache.
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of parses which were not answered by the cache,
+    /// including the ones of strings which cannot be parsed.
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Removes all the cached formulas, preserving the hit and miss counts.
+    pub fn clear(&mut self) {
+        self.formulas.clear();
+        self.recency.clear();
+    }
+}
+
+impl<M: FromStrWithOptions> FormulaCache<M> {
+    /// Returns the formula parsed from the provided string, parsing it only
+    /// if it is not cached already.
+    ///
+    /// # Errors
+    ///
+    /// * If the string is not a valid formula, or if it exceeds any of the
+    ///   limits set in the options of the cache.
+    pub fn parse(&mut self, formula: &str) -> Result<Arc<M>, ParserError> {
+        let tick = self.tick;
+        self.tick += 1;
+        if let Some((parsed, last_use)) = self.formulas.get_mut(formula) {
+            self.hits += 1;
+            let key = self.recency.remove(last_use).expect("Cached formulas have a last use");
+            self.recency.insert(tick, key);
+            *last_use = tick;
+            return Ok(Arc::clone(parsed));
+        }
+        self.misses += 1;
+        let parsed = Arc::new(M::from_str_with_options(formula, &self.options)?);
+        if self.capacity == 0 {
+            return Ok(parsed);
+        }
+        if self.formulas.len() == self.capacity
+            && let Some((_, evicted)) = self.recency.pop_first()
+        {
+            self.formulas.remove(&evicted);
+        }
+        self.formulas.insert(String::from(formula), (Arc::clone(&parsed), tick));
+        self.recency.insert(tick, String::from(formula));
+        Ok(parsed)
+    }
+}
@@ -6,15 +6,17 @@ use core::str::FromStr;
 use elements_rs::Isotope;
 
 use crate::{
-    ChargeLike, ChemicalFormula, CountLike, InChIFormula, MineralFormula, ResidualFormula,
-    errors::ParserError, parsable::MoleculeParser,
+    ChargeLike, ChemicalFormula, CountLike, InChIFormula, MineralFormula, Prefixed,
+    ResidualFormula,
+    errors::ParserError,
+    parsable::{DEFAULT_PARSER_OPTIONS, FromStrWithOptions, MoleculeParser, ParserOptions},
 };
 
 impl<Count: CountLike> FromStr for InChIFormula<Count> {
     type Err = ParserError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        MoleculeParser::new(s.chars())?.parse_formula()
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
     }
 }
 
@@ -26,7 +28,7 @@ where
     type Error = ParserError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        MoleculeParser::new(s.chars())?.parse_formula()
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
     }
 }
 
@@ -38,7 +40,7 @@ where
     type Err = ParserError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        MoleculeParser::new(s.chars())?.parse_formula()
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
     }
 }
 
@@ -50,7 +52,7 @@ where
     type Error = ParserError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        MoleculeParser::new(s.chars())?.parse_formula()
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
     }
 }
 
@@ -62,7 +64,7 @@ where
     type Err = ParserError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        MoleculeParser::new(s.chars())?.parse_formula()
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
     }
 }
 
@@ -74,7 +76,7 @@ where
     type Error = ParserError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        MoleculeParser::new(s.chars())?.parse_formula()
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
     }
 }
 
@@ -86,7 +88,61 @@ where
     type Err = ParserError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        MoleculeParser::new(s.chars())?.parse_formula()
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
+    }
+}
+
+impl<M: FromStrWithOptions> TryFrom<&str> for Prefixed<M> {
+    type Error = ParserError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
+    }
+}
+
+impl<M: FromStrWithOptions> FromStr for Prefixed<M> {
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_options(s, &DEFAULT_PARSER_OPTIONS)
+    }
+}
+
+impl<Count: CountLike> FromStrWithOptions for InChIFormula<Count> {
+    fn from_str_with_options(s: &str, options: &ParserOptions) -> Result<Self, ParserError> {
+        let separated = options.separate_middle_dots(s);
+        MoleculeParser::with_options(separated.as_deref().unwrap_or(s).chars(), options)?
+            .parse_formula()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> FromStrWithOptions for ChemicalFormula<Count, Charge>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    fn from_str_with_options(s: &str, options: &ParserOptions) -> Result<Self, ParserError> {
+        MoleculeParser::with_options(options.preprocess(s)?.chars(), options)?.parse_formula()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> FromStrWithOptions for MineralFormula<Count, Charge>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    fn from_str_with_options(s: &str, options: &ParserOptions) -> Result<Self, ParserError> {
+        MoleculeParser::with_options(options.preprocess(s)?.chars(), options)?.parse_formula()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> FromStrWithOptions for ResidualFormula<Count, Charge>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    fn from_str_with_options(s: &str, options: &ParserOptions) -> Result<Self, ParserError> {
+        MoleculeParser::with_options(options.preprocess(s)?.chars(), options)?.parse_formula()
     }
 }
 
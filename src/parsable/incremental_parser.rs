@@ -0,0 +1,254 @@
+//! Submodule providing an incremental parser, which validates a chemical
+//! formula while it is being typed, as needed by live form validation.
+
+use alloc::{string::String, vec::Vec};
+use core::{marker::PhantomData, str::FromStr};
+
+use elements_rs::{Element, Isotope};
+
+use crate::{
+    Bracket, ChargeLike, ChemicalFormula, CountLike, Spanned, Token, errors::ParserError, tokenize,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Token expected to continue an incomplete formula.
+pub enum ExpectedToken {
+    /// An element, possibly completing the partial element symbol at the end
+    /// of the input.
+    Element,
+    /// The closing bracket matching the innermost open bracket.
+    ClosingBracket(Bracket),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Validation state of the input of an [`IncrementalParser`].
+pub enum ValidationState {
+    /// The input is a valid formula, which may still be extended.
+    Complete,
+    /// The input is not a formula yet, but it is the prefix of at least one.
+    Incomplete(ExpectedToken),
+    /// The input is not the prefix of any formula, with the byte range of the
+    /// token where the error was detected.
+    Invalid(Spanned<ParserError>),
+}
+
+#[derive(Debug, Clone)]
+/// Parser validating a chemical formula one character at a time.
+///
+/// After each character, the parser reports whether the input is a valid
+/// formula, the prefix of a valid formula or neither, and it suggests the
+/// element symbols which may be typed next. The input is validated again
+/// as a whole after each change, which is fast for formulas typed by hand.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// let mut parser = IncrementalParser::<u32, i32>::new();
+/// assert_eq!(parser.push_char('['), ValidationState::Incomplete(ExpectedToken::Element));
+/// assert_eq!(parser.push_char('1'), ValidationState::Incomplete(ExpectedToken::Element));
+/// assert_eq!(parser.push_char('3'), ValidationState::Incomplete(ExpectedToken::Element));
+/// assert!(parser.suggestions().contains(&Element::C));
+/// assert!(!parser.suggestions().contains(&Element::H));
+/// assert_eq!(
+///     parser.push_char('C'),
+///     ValidationState::Incomplete(ExpectedToken::ClosingBracket(Bracket::Square))
+/// );
+/// assert_eq!(parser.push_char(']'), ValidationState::Complete);
+/// assert_eq!(parser.push_char('H'), ValidationState::Complete);
+/// assert_eq!(
+///     parser.suggestions(),
+///     [Element::H, Element::He, Element::Ho, Element::Hf, Element::Hg, Element::Hs]
+/// );
+/// assert!(matches!(parser.push_char(')'), ValidationState::Invalid(_)));
+/// assert_eq!(parser.pop_char(), Some(')'));
+/// assert_eq!(parser.state(), &ValidationState::Complete);
+/// ```
+pub struct IncrementalParser<Count: CountLike = u16, Charge: ChargeLike = i16> {
+    /// The characters pushed so far.
+    input: String,
+    /// The validation state of the input.
+    state: ValidationState,
+    /// Phantom data for the type of the parsed formula.
+    _formula: PhantomData<ChemicalFormula<Count, Charge>>,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Default for IncrementalParser<Count, Charge>
+where
+    Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> IncrementalParser<Count, Charge>
+where
+    Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    /// Creates a parser with an empty input.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            input: String::new(),
+            state: ValidationState::Incomplete(ExpectedToken::Element),
+            _formula: PhantomData,
+        }
+    }
+
+    /// Returns the characters pushed so far.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the validation state of the input.
+    #[must_use]
+    pub fn state(&self) -> &ValidationState {
+        &self.state
+    }
+
+    /// Appends the provided character to the input, and returns the new
+    /// validation state of the input.
+    pub fn push_char(&mut self, c: char) -> ValidationState {
+        self.input.push(c);
+        self.state = Self::validate(&self.input);
+        self.state.clone()
+    }
+
+    /// Removes the last character of the input, if any, and validates the
+    /// input again.
+    pub fn pop_char(&mut self) -> Option<char> {
+        let c = self.input.pop()?;
+        self.state = Self::validate(&self.input);
+        Some(c)
+    }
+
+    /// Empties the input.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the elements which may be typed next, by increasing atomic
+    /// number.
+    ///
+    /// When the input ends with a partial element symbol, such as the `C` of
+    /// `NaC`, only the elements completing it are returned, including the
+    /// element of the partial symbol itself.
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Element> {
+        if matches!(self.state, ValidationState::Invalid(_)) {
+            return Vec::new();
+        }
+        let (stem, partial) = split_partial_symbol(&self.input);
+        if partial.is_empty() {
+            Self::candidates(&self.input, "").collect()
+        } else {
+            Self::candidates(stem, partial).collect()
+        }
+    }
+
+    /// Returns the validation state of the provided input.
+    fn validate(input: &str) -> ValidationState {
+        if input.is_empty() {
+            return ValidationState::Incomplete(ExpectedToken::Element);
+        }
+        match Self::check(input) {
+            Ok(None) => ValidationState::Complete,
+            Ok(Some(bracket)) => {
+                ValidationState::Incomplete(ExpectedToken::ClosingBracket(bracket))
+            }
+            Err(error) => {
+                let (stem, partial) = split_partial_symbol(input);
+                if !partial.is_empty() && Self::candidates(stem, partial).next().is_some()
+                    || Self::candidates(input, "").next().is_some()
+                {
+                    ValidationState::Incomplete(ExpectedToken::Element)
+                } else {
+                    ValidationState::Invalid(error)
+                }
+            }
+        }
+    }
+
+    /// Returns whether the provided input is a formula, or the innermost
+    /// bracket to close when it only becomes a formula once its open
+    /// brackets are closed.
+    fn check(input: &str) -> Result<Option<Bracket>, Spanned<ParserError>> {
+        let mut open = Vec::new();
+        let mut last_span = 0..input.len();
+        for token in tokenize::<Count, Charge>(input) {
+            let token = token?;
+            match token.value() {
+                Token::OpenBracket(bracket) => open.push(*bracket),
+                Token::CloseBracket(_) => {
+                    open.pop();
+                }
+                _ => {}
+            }
+            last_span = token.span();
+        }
+
+        let Err(error) = ChemicalFormula::<Count, Charge>::from_str(input) else {
+            return Ok(None);
+        };
+        if let Some(innermost) = open.last().copied() {
+            let mut closed = String::from(input);
+            closed.extend(open.iter().rev().map(Bracket::closing));
+            if ChemicalFormula::<Count, Charge>::from_str(&closed).is_ok() {
+                return Ok(Some(innermost));
+            }
+        }
+        Err(Spanned::new(error, last_span))
+    }
+
+    /// Returns the elements whose symbol starts with the provided prefix and
+    /// which, appended to the provided stem, form a formula or the prefix of
+    /// a formula missing only its closing brackets.
+    fn candidates<'a>(stem: &'a str, prefix: &'a str) -> impl Iterator<Item = Element> + 'a {
+        (1..=118u8).filter_map(|atomic_number| Element::try_from(atomic_number).ok()).filter(
+            move |element| {
+                let symbol: &str = element.as_ref();
+                symbol.starts_with(prefix) && {
+                    let mut candidate = String::from(stem);
+                    candidate.push_str(symbol);
+                    Self::check(&candidate).is_ok()
+                }
+            },
+        )
+    }
+}
+
+/// Splits the provided input before the partial element symbol it ends
+/// with, made of an uppercase letter optionally followed by a lowercase one.
+fn split_partial_symbol(input: &str) -> (&str, &str) {
+    let mut chars = input.char_indices().rev();
+    match (chars.next(), chars.next()) {
+        (Some((_, last)), Some((index, first)))
+            if last.is_ascii_lowercase() && first.is_ascii_uppercase() =>
+        {
+            input.split_at(index)
+        }
+        (Some((index, last)), _) if last.is_ascii_uppercase() => input.split_at(index),
+        _ => (input, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_partial_symbol() {
+        assert_eq!(split_partial_symbol(""), ("", ""));
+        assert_eq!(split_partial_symbol("H2"), ("H2", ""));
+        assert_eq!(split_partial_symbol("NaC"), ("Na", "C"));
+        assert_eq!(split_partial_symbol("NaCl"), ("Na", "Cl"));
+        assert_eq!(split_partial_symbol("Me"), ("", "Me"));
+        assert_eq!(split_partial_symbol("C₆H"), ("C₆", "H"));
+        assert_eq!(split_partial_symbol("ab"), ("ab", ""));
+    }
+}
@@ -7,56 +7,118 @@ use core::iter::Peekable;
 use num_traits::ConstOne;
 
 use crate::{
-    TokenLike,
-    errors::ParserError,
-    parsable::{parsable_formula::ParsableFormula, parsable_molecular_tree::ParsableMolecularTree},
+    MolecularFormulaMetadata, TokenLike,
+    errors::{ParserError, ParserLimit},
+    parsable::{
+        OwnedTrees, ParserOptions, TreeBuilder, parsable_formula::ParsableFormula,
+        parsable_molecular_tree::ParsableMolecularTree,
+    },
 };
 
+#[cfg(feature = "arena")]
+mod arena_tree_parser;
 mod chemical_tree_parser;
 mod inchi_tree_parser;
 mod mineral_formula_parser;
 mod residual_tree_parser;
 
-/// Trait for parsable molecular trees.
-pub(crate) trait MolecularTreeParser<Count, T: ParsableMolecularTree<Count>> {
+#[cfg(feature = "arena")]
+pub use arena_tree_parser::{parse_in, parse_in_with_options};
+
+/// The tokens read by the parser of the provided formula type.
+type ParserToken<M> = <<M as ParsableFormula>::Tree as ParsableMolecularTree<
+    <M as MolecularFormulaMetadata>::Count,
+>>::Token;
+
+/// Trait for parsable molecular trees, whose nodes are built by the provided
+/// tree builder.
+pub(crate) trait MolecularTreeParser<Token, Tree, B: TreeBuilder<Tree>> {
     /// Extends the provided sequence with a new token.
     fn extend_tree(
         &mut self,
-        sequence: T,
-        terminator: <T as ParsableMolecularTree<Count>>::Token,
-        token: <T as ParsableMolecularTree<Count>>::Token,
-    ) -> Result<T, ParserError>;
+        builder: B,
+        sequence: Tree,
+        terminator: Token,
+        token: Token,
+    ) -> Result<Tree, ParserError>;
 }
 
-pub(super) struct MoleculeParser<I: Iterator<Item = char>, M: ParsableFormula> {
-    tokens: Peekable<<M::Tree as ParsableMolecularTree<M::Count>>::Tokens<I>>,
-    start_output: M::StartOutput,
+/// Iterator adapter enforcing the token-level limits of the parser options.
+struct LimitedTokens<T> {
+    /// The underlying tokens.
+    tokens: T,
+    /// The number of tokens read so far.
+    consumed: usize,
+    /// The maximal number of tokens which may be read.
+    max_tokens: Option<usize>,
+    /// The maximal value of any count token.
+    max_count: Option<u64>,
 }
 
-impl<I: Iterator<Item = char>, M: ParsableFormula> MoleculeParser<I, M>
+impl<T, Token> Iterator for LimitedTokens<T>
 where
-    Self: MolecularTreeParser<M::Count, M::Tree>,
+    T: Iterator<Item = Result<Token, ParserError>>,
+    Token: TokenLike,
 {
-    pub(crate) fn new(chars: I) -> Result<Self, ParserError> {
+    type Item = Result<Token, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.tokens.next()?;
+        self.consumed += 1;
+        if self.max_tokens.is_some_and(|max_tokens| self.consumed > max_tokens) {
+            return Some(Err(ParserError::LimitExceeded(ParserLimit::Tokens)));
+        }
+        if let (Some(max_count), Ok(Some(count))) =
+            (self.max_count, token.as_ref().map(TokenLike::as_count))
+        {
+            let count: i128 = count.into();
+            if u64::try_from(count).map_or(true, |count| count > max_count) {
+                return Some(Err(ParserError::LimitExceeded(ParserLimit::Count)));
+            }
+        }
+        Some(token)
+    }
+}
+
+pub(super) struct MoleculeParser<'o, I: Iterator<Item = char>, M: ParsableFormula> {
+    #[allow(clippy::type_complexity)]
+    tokens: Peekable<LimitedTokens<<M::Tree as ParsableMolecularTree<M::Count>>::Tokens<I>>>,
+    start_output: M::StartOutput,
+    /// The options of the parser.
+    options: &'o ParserOptions,
+    /// The current nesting depth.
+    depth: usize,
+    /// The number of nodes currently wrapped by trailing charges and
+    /// radicals, which nest the tree without increasing its nesting depth.
+    wrappers: usize,
+}
+
+impl<'o, I: Iterator<Item = char>, M: ParsableFormula> MoleculeParser<'o, I, M> {
+    pub(crate) fn with_options(chars: I, options: &'o ParserOptions) -> Result<Self, ParserError>
+    where
+        I: Clone,
+    {
         let mut peekable_chars = chars.peekable();
-        let start_output = M::on_start(&mut peekable_chars)?;
+        let start_output = M::on_start(&mut peekable_chars, options)?;
         Ok(Self {
-            tokens: <M::Tree as ParsableMolecularTree<M::Count>>::Tokens::from(peekable_chars)
-                .peekable(),
+            tokens: LimitedTokens {
+                tokens: <M::Tree as ParsableMolecularTree<M::Count>>::Tokens::from(peekable_chars),
+                consumed: 0,
+                max_tokens: options.max_tokens(),
+                max_count: options.max_count(),
+            }
+            .peekable(),
             start_output,
+            options,
+            depth: 0,
+            wrappers: 0,
         })
     }
 }
 
-impl<I: Iterator<Item = char>, M: ParsableFormula> MoleculeParser<I, M>
-where
-    Self: MolecularTreeParser<M::Count, M::Tree>,
-{
+impl<I: Iterator<Item = char>, M: ParsableFormula> MoleculeParser<'_, I, M> {
     /// Peeks at the next token without consuming it.
-    #[allow(clippy::type_complexity)]
-    fn peek_token(
-        &mut self,
-    ) -> Result<Option<<M::Tree as ParsableMolecularTree<M::Count>>::Token>, ParserError> {
+    fn peek_token(&mut self) -> Result<Option<ParserToken<M>>, ParserError> {
         match self.tokens.peek().copied() {
             Some(Ok(token)) => Ok(Some(token)),
             Some(Err(e)) => Err(e),
@@ -65,9 +127,7 @@ where
     }
 
     /// Consumes and returns the next token.
-    fn consume_token(
-        &mut self,
-    ) -> Result<<M::Tree as ParsableMolecularTree<M::Count>>::Token, ParserError> {
+    fn consume_token(&mut self) -> Result<ParserToken<M>, ParserError> {
         match self.tokens.next() {
             Some(Ok(token)) => Ok(token),
             Some(Err(e)) => Err(e),
@@ -107,39 +167,97 @@ where
         }
     }
 
-    pub(super) fn parse_formula(mut self) -> Result<M, ParserError> {
-        // Next, we start to parse the mixtures, which are separated by dots.
+    pub(super) fn parse_formula(mut self) -> Result<M, ParserError>
+    where
+        Self: MolecularTreeParser<ParserToken<M>, M::Tree, OwnedTrees>,
+        OwnedTrees: TreeBuilder<M::Tree>,
+    {
         let mut mixtures: Vec<(M::Count, M::Tree)> = Vec::new();
+        self.parse_mixtures(OwnedTrees, &mut mixtures)?;
+        <M as ParsableFormula>::from_parsed(self.start_output, mixtures)
+    }
+
+    /// Parses the mixtures of the formula, which are separated by dots, with
+    /// the provided tree builder, and extends the provided collection with
+    /// each of them and its multiplier.
+    ///
+    /// # Errors
+    ///
+    /// * If the formula is empty, or if any of its mixtures cannot be parsed.
+    fn parse_mixtures<Tree, B: TreeBuilder<Tree>>(
+        &mut self,
+        builder: B,
+        mixtures: &mut impl Extend<(M::Count, Tree)>,
+    ) -> Result<(), ParserError>
+    where
+        Self: MolecularTreeParser<ParserToken<M>, Tree, B>,
+    {
+        if self.tokens.peek().is_none() {
+            return Err(ParserError::EmptyMolecularTree);
+        }
 
         while self.tokens.peek().is_some() {
             // If there is a mixture multiplier, it appears at the beginning
             // of the mixture
             let mixture_multiplier = self.consume_count()?.unwrap_or(<M::Count as ConstOne>::ONE);
 
-            let sequence: M::Tree = self.parse_sequence(<<M::Tree as ParsableMolecularTree<M::Count>>::Token as TokenLike>::mixture_separator(), None)?;
+            let sequence =
+                self.parse_sequence(builder, ParserToken::<M>::mixture_separator(), None)?;
 
             if !self.consume_mixture_separator()? {
                 // if there is no mixture separator, we are in an illegal state.
                 todo!("raise an error here");
             }
 
-            mixtures.push((mixture_multiplier, sequence));
+            mixtures.extend([(mixture_multiplier, sequence)]);
         }
 
-        if mixtures.is_empty() {
-            return Err(ParserError::EmptyMolecularTree);
+        Ok(())
+    }
+
+    fn parse_sequence<Tree, B: TreeBuilder<Tree>>(
+        &mut self,
+        builder: B,
+        terminator: ParserToken<M>,
+        initial_token: Option<ParserToken<M>>,
+    ) -> Result<Tree, ParserError>
+    where
+        Self: MolecularTreeParser<ParserToken<M>, Tree, B>,
+    {
+        if self.options.max_depth().is_some_and(|max_depth| self.depth > max_depth) {
+            return Err(ParserError::LimitExceeded(ParserLimit::Depth));
         }
+        self.depth += 1;
+        self.check_tree_depth()?;
+        let sequence = self.parse_unit(builder, terminator, initial_token);
+        self.depth -= 1;
+        sequence
+    }
 
-        <M as ParsableFormula>::from_parsed(self.start_output, mixtures)
+    /// Returns an error if the tree being parsed is nested beyond
+    /// [`MAX_NESTING_DEPTH`](ParserOptions::MAX_NESTING_DEPTH), which would
+    /// risk overflowing the stack of the recursive tree traversals.
+    fn check_tree_depth(&self) -> Result<(), ParserError> {
+        if self.depth + self.wrappers > ParserOptions::MAX_NESTING_DEPTH {
+            return Err(ParserError::LimitExceeded(ParserLimit::Depth));
+        }
+        Ok(())
     }
 
-    fn parse_sequence(
+    fn parse_unit<Tree, B: TreeBuilder<Tree>>(
         &mut self,
-        terminator: <M::Tree as ParsableMolecularTree<M::Count>>::Token,
-        mut initial_token: Option<<M::Tree as ParsableMolecularTree<M::Count>>::Token>,
-    ) -> Result<M::Tree, ParserError> {
+        builder: B,
+        terminator: ParserToken<M>,
+        mut initial_token: Option<ParserToken<M>>,
+    ) -> Result<Tree, ParserError>
+    where
+        Self: MolecularTreeParser<ParserToken<M>, Tree, B>,
+    {
         // We initialize an empty tree
-        let mut sequence: M::Tree = M::Tree::empty();
+        let mut sequence = builder.empty();
+        // Trailing charges and radicals wrap the unit parsed so far into a new
+        // node, so that chaining them nests the tree as brackets would.
+        let mut wrappers = 0;
 
         'unit: loop {
             let next_token = if let Some(pending_token) = initial_token.take() {
@@ -157,10 +275,17 @@ where
                 self.tokens.next().transpose()?.ok_or(ParserError::UnexpectedEndOfInput)?
             };
 
-            sequence = self.extend_tree(sequence, terminator, next_token)?;
+            if next_token.wraps_unit() && !builder.is_empty(&sequence) {
+                wrappers += 1;
+                self.wrappers += 1;
+                self.check_tree_depth()?;
+            }
+
+            sequence = self.extend_tree(builder, sequence, terminator, next_token)?;
         }
+        self.wrappers -= wrappers;
 
-        if sequence.is_empty() {
+        if builder.is_empty(&sequence) {
             return Err(ParserError::EmptyMolecularTree);
         }
 
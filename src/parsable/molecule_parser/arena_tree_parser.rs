@@ -0,0 +1,91 @@
+//! Submodule providing the parsing of chemical formulas into trees allocated
+//! in a [`Bump`] arena, following the same grammar as the owned parser.
+
+use bumpalo::{Bump, collections::Vec};
+use elements_rs::Isotope;
+
+use crate::{
+    ChargeLike, ChemicalFormula, ChemicalFormulaRef, CountLike,
+    errors::ParserError,
+    parsable::{DEFAULT_PARSER_OPTIONS, MoleculeParser, ParserOptions},
+};
+
+/// Parses the provided chemical formula into a formula whose nodes are all
+/// allocated in the provided arena, with the default parser options.
+///
+/// Parsing large batches of formulas into the same arena avoids the
+/// allocation of each node of their trees, and frees the whole batch at once
+/// when the arena is dropped or reset. The parsed formulas can be converted
+/// into owned formulas with [`to_formula`](ChemicalFormulaRef::to_formula)
+/// when needed.
+///
+/// # Errors
+///
+/// * If the formula cannot be parsed, as for
+///   [`ChemicalFormula::from_str`](core::str::FromStr::from_str).
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::prelude::*;
+///
+/// let mut arena = Bump::new();
+/// for formula in ["C6H12O6", "[Cu(NH3)4]+2", "Me3N.BF3"] {
+///     let parsed = parse_in::<u16, i16>(&arena, formula).unwrap();
+///     assert_eq!(parsed.to_formula(), ChemicalFormula::from_str(formula).unwrap());
+/// }
+/// arena.reset();
+/// ```
+pub fn parse_in<'a, Count: CountLike, Charge>(
+    arena: &'a Bump,
+    s: &str,
+) -> Result<ChemicalFormulaRef<'a, Count, Charge>, ParserError>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: ChargeLike + TryFrom<Count>,
+{
+    parse_in_with_options(arena, s, &DEFAULT_PARSER_OPTIONS)
+}
+
+/// Parses the provided chemical formula into a formula whose nodes are all
+/// allocated in the provided arena, with the provided parser options.
+///
+/// # Errors
+///
+/// * If the formula cannot be parsed with the provided options, as for
+///   [`FromStrWithOptions::from_str_with_options`](crate::FromStrWithOptions::from_str_with_options).
+pub fn parse_in_with_options<'a, Count: CountLike, Charge>(
+    arena: &'a Bump,
+    s: &str,
+    options: &ParserOptions,
+) -> Result<ChemicalFormulaRef<'a, Count, Charge>, ParserError>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: ChargeLike + TryFrom<Count>,
+{
+    MoleculeParser::<_, ChemicalFormula<Count, Charge>>::with_options(
+        options.preprocess(s)?.chars(),
+        options,
+    )?
+    .parse_formula_in(arena)
+}
+
+impl<I: Iterator<Item = char>, Count: CountLike, Charge: ChargeLike>
+    MoleculeParser<'_, I, ChemicalFormula<Count, Charge>>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    /// Parses the formula into the provided arena, as
+    /// [`parse_formula`](MoleculeParser::parse_formula) does.
+    fn parse_formula_in(
+        mut self,
+        arena: &Bump,
+    ) -> Result<ChemicalFormulaRef<'_, Count, Charge>, ParserError> {
+        let mut mixtures = Vec::new_in(arena);
+        self.parse_mixtures(arena, &mut mixtures)?;
+        Ok(ChemicalFormulaRef::new(mixtures))
+    }
+}
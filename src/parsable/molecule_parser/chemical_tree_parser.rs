@@ -10,23 +10,26 @@ use crate::{
     Empty, InchiToken, MolecularFormulaMetadata, ParsableFormula, Token, TokenLike,
     errors::ParserError,
     parsable::{
-        MoleculeParser, molecule_parser::MolecularTreeParser,
+        ChemicalTreeBuilder, MoleculeParser, molecule_parser::MolecularTreeParser,
         parsable_molecular_tree::ParsableMolecularTree,
     },
 };
 
-impl<I: Iterator<Item = char>, M> MoleculeParser<I, M>
+impl<I: Iterator<Item = char>, M> MoleculeParser<'_, I, M>
 where
     M: ParsableFormula + ChargedMolecularFormulaMetadata,
     M::Charge: TryFrom<M::Count>,
     Isotope: TryFrom<(elements_rs::Element, M::Count), Error = elements_rs::errors::Error>,
 {
-    pub(crate) fn extend_generic_tree<Count: CountLike, Charge: ChargeLike, Extension>(
+    /// Extends the provided chemical tree with a new token, building its
+    /// nodes with the provided tree builder.
+    pub(crate) fn extend_generic_tree<Count: CountLike, Charge: ChargeLike, Extension, Tree, B>(
         &mut self,
-        mut tree: ChemicalTree<Count, Charge, Extension>,
+        builder: B,
+        tree: Tree,
         terminator: Token<Count, Charge, Extension>,
         token: Token<Count, Charge, Extension>,
-    ) -> Result<ChemicalTree<Count, Charge, Extension>, ParserError>
+    ) -> Result<Tree, ParserError>
     where
         Extension: Debug + Copy + Eq,
         M: MolecularFormulaMetadata<Count = Count>
@@ -34,70 +37,52 @@ where
         M: ParsableFormula<Tree = ChemicalTree<Count, Charge, Extension>>,
         ChemicalTree<Count, Charge, Extension>:
             ParsableMolecularTree<Count, Token = Token<Count, Charge, Extension>>,
-        Self: MolecularTreeParser<Count, ChemicalTree<Count, Charge, Extension>>,
+        B: ChemicalTreeBuilder<Tree, Count = Count, Charge = Charge, Extension = Extension>,
+        Self: MolecularTreeParser<Token<Count, Charge, Extension>, Tree, B>,
     {
         Ok(match token {
             Token::Inchi(InchiToken::Element(element)) => {
-                if self.peek_token()? == Some(Token::OpenBracket(Bracket::Square)) {
-                    // This might be an isotope specifier, or an unrelated
-                    // square bracket group.
-                    self.consume_token()?; // Consume the opening square bracket
-
-                    // If the next_token is a count, this could be an isotope
-                    // specifier. We need to further check whether the next token
-                    // is a closing square bracket.
-                    if let Some(mass_number) = self.consume_count()? {
-                        if self.peek_token()? == Some(Token::CloseBracket(Bracket::Square)) {
-                            tree = tree.isotope(Isotope::try_from((element, mass_number))?);
-                            self.consume_token()?; // Consume the closing square bracket
-                            tree
-                        } else {
-                            tree = tree.element(element);
-
-                            // Otherwise, we are parsing a new square bracket group, from which we
-                            // have already consumed the opening square bracket and the first token.
-                            let new_tree = self.parse_sequence(
-                                Token::CloseBracket(Bracket::Square),
-                                Some(Token::Inchi(InchiToken::Count(mass_number))),
-                            )?;
-
-                            // And we consume the closing square bracket
-                            if self.consume_token()? != Token::CloseBracket(Bracket::Square) {
-                                return Err(ParserError::MissingClosingBracket(Bracket::Square));
-                            }
-
-                            tree.push(if new_tree.is_leaf() { new_tree } else { new_tree.square() })
-                        }
-                    } else {
-                        tree = tree.element(element);
+                if self.peek_token()? != Some(Token::OpenBracket(Bracket::Square)) {
+                    // No isotope specifier.
+                    return Ok(builder.element(tree, element));
+                }
+                // This might be an isotope specifier, or an unrelated square
+                // bracket group.
+                self.consume_token()?; // Consume the opening square bracket
 
+                // If the next_token is a count, this could be an isotope
+                // specifier. We need to further check whether the next token
+                // is a closing square bracket.
+                match self.consume_count()? {
+                    Some(mass_number)
+                        if self.peek_token()? == Some(Token::CloseBracket(Bracket::Square)) =>
+                    {
+                        self.consume_token()?; // Consume the closing square bracket
+                        builder.isotope(tree, Isotope::try_from((element, mass_number))?)
+                    }
+                    mass_number => {
                         // Otherwise, we are parsing a new square bracket group, from which we
-                        // have already consumed the opening square bracket and the first token.
-                        let new_tree =
-                            self.parse_sequence(Token::CloseBracket(Bracket::Square), None)?;
-
-                        // And we consume the closing square bracket
-                        if self.consume_token()? != Token::CloseBracket(Bracket::Square) {
-                            return Err(ParserError::MissingClosingBracket(Bracket::Square));
-                        }
-
-                        tree.push(if new_tree.is_leaf() { new_tree } else { new_tree.square() })
+                        // have already consumed the opening square bracket and possibly the
+                        // first token.
+                        let tree = builder.element(tree, element);
+                        self.push_square_group(
+                            builder,
+                            tree,
+                            mass_number.map(|count| Token::Inchi(InchiToken::Count(count))),
+                        )?
                     }
-                } else {
-                    // No isotope specifier.
-                    tree.element(element)
                 }
             }
             Token::Inchi(InchiToken::Count(count)) => {
                 // A repeat always decorates the previous unit. Only at the start
                 // of a new mixture can there be a repeat without a preceding unit
-                // which is handled in `Self::parse_mixture`. The only exception is
+                // which is handled in `Self::parse_mixtures`. The only exception is
                 // when this repeat is followed by an `Element`,
                 // in which case it is an isotope specifier.
-                if !tree.is_empty() {
-                    tree.repeat(count)
+                if !builder.is_empty(&tree) {
+                    builder.repeat(tree, count)
                 } else if let Some(element) = self.consume_token()?.as_element() {
-                    tree.isotope(Isotope::try_from((element, count))?)
+                    builder.isotope(tree, Isotope::try_from((element, count))?)
                 } else {
                     return Err(ParserError::UnprocessableNumber);
                 }
@@ -106,31 +91,33 @@ where
                 // A radical at the beginning of a unit decorates the entire unit
                 // that follows it, while it wraps up the entire unit if it is at
                 // some point inside the unit.
-                if tree.is_empty() {
+                if builder.is_empty(&tree) {
                     // If the unit is empty, we parse the following unit
                     // and then decorate it with the radical.
-                    self.parse_sequence(terminator, None)?.left_radical()
+                    let unit = self.parse_sequence(builder, terminator, None)?;
+                    builder.left_radical(unit)
                 } else {
                     // If the unit is not empty, we decorate the entire
                     // unit with the radical.
-                    tree.right_radical()
+                    builder.right_radical(tree)
                 }
             }
-            Token::Isotope(isotope) => tree.isotope(isotope),
-            Token::Complex(complex) => tree.complex(complex),
-            Token::Charge(charge) => tree.charge(charge)?,
+            Token::Isotope(isotope) => builder.isotope(tree, isotope),
+            Token::Complex(complex) => builder.complex(tree, complex),
+            Token::Charge(charge) => builder.charge(tree, charge)?,
             Token::OpenBracket(bracket) => {
-                let new_tree = self.parse_sequence(Token::CloseBracket(bracket), None)?;
+                let new_tree = self.parse_sequence(builder, Token::CloseBracket(bracket), None)?;
 
                 // There must be a closing bracket here
                 if self.consume_token()? != Token::CloseBracket(bracket) {
                     return Err(ParserError::MissingClosingBracket(bracket));
                 }
 
-                tree.push(match bracket {
-                    Bracket::Round => new_tree.round(),
-                    Bracket::Square => new_tree.square(),
-                })
+                let group = match bracket {
+                    Bracket::Round => builder.round(new_tree),
+                    Bracket::Square => builder.square(new_tree),
+                };
+                builder.push(tree, group)
             }
             Token::CloseBracket(bracket) => {
                 return Err(ParserError::UnexpectedCharacter(bracket.closing()));
@@ -138,24 +125,56 @@ where
             Token::Inchi(InchiToken::Dot) => {
                 return Err(ParserError::UnexpectedCharacter('.'));
             }
-            Token::Extension(extension) => tree.extension(extension),
+            Token::Extension(extension) => builder.extension(tree, extension),
         })
     }
+
+    /// Parses a square bracketed group whose opening bracket was consumed,
+    /// starting from the provided token if any, and pushes it onto the
+    /// provided tree.
+    fn push_square_group<Count: CountLike, Charge: ChargeLike, Extension, Tree, B>(
+        &mut self,
+        builder: B,
+        tree: Tree,
+        initial_token: Option<Token<Count, Charge, Extension>>,
+    ) -> Result<Tree, ParserError>
+    where
+        Extension: Debug + Copy + Eq,
+        M: MolecularFormulaMetadata<Count = Count>
+            + ChargedMolecularFormulaMetadata<Charge = Charge>,
+        M: ParsableFormula<Tree = ChemicalTree<Count, Charge, Extension>>,
+        ChemicalTree<Count, Charge, Extension>:
+            ParsableMolecularTree<Count, Token = Token<Count, Charge, Extension>>,
+        B: ChemicalTreeBuilder<Tree, Count = Count, Charge = Charge, Extension = Extension>,
+        Self: MolecularTreeParser<Token<Count, Charge, Extension>, Tree, B>,
+    {
+        let new_tree =
+            self.parse_sequence(builder, Token::CloseBracket(Bracket::Square), initial_token)?;
+
+        // And we consume the closing square bracket
+        if self.consume_token()? != Token::CloseBracket(Bracket::Square) {
+            return Err(ParserError::MissingClosingBracket(Bracket::Square));
+        }
+
+        Ok(builder.push(tree, builder.square(new_tree)))
+    }
 }
 
-impl<I: Iterator<Item = char>, Count: CountLike, Charge: ChargeLike>
-    MolecularTreeParser<Count, ChemicalTree<Count, Charge, Empty>>
-    for MoleculeParser<I, ChemicalFormula<Count, Charge>>
+impl<I: Iterator<Item = char>, Count: CountLike, Charge: ChargeLike, Tree, B>
+    MolecularTreeParser<Token<Count, Charge, Empty>, Tree, B>
+    for MoleculeParser<'_, I, ChemicalFormula<Count, Charge>>
 where
     Charge: TryFrom<Count>,
     Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    B: ChemicalTreeBuilder<Tree, Count = Count, Charge = Charge, Extension = Empty>,
 {
     fn extend_tree(
         &mut self,
-        tree: ChemicalTree<Count, Charge, Empty>,
+        builder: B,
+        tree: Tree,
         terminator: Token<Count, Charge, Empty>,
         token: Token<Count, Charge, Empty>,
-    ) -> Result<ChemicalTree<Count, Charge, Empty>, ParserError> {
-        self.extend_generic_tree(tree, terminator, token)
+    ) -> Result<Tree, ParserError> {
+        self.extend_generic_tree(builder, tree, terminator, token)
     }
 }
@@ -5,17 +5,18 @@ use crate::{
     CountLike, InChIFormula, InChITree, InchiToken, RepeatNode, SequenceNode,
     errors::ParserError,
     parsable::{
-        MoleculeParser, molecule_parser::MolecularTreeParser,
+        MoleculeParser, OwnedTrees, molecule_parser::MolecularTreeParser,
         parsable_molecular_tree::ParsableMolecularTree,
     },
 };
 
 impl<I: Iterator<Item = char>, Count: CountLike>
-    MolecularTreeParser<Count, SequenceNode<InChITree<Count>>>
-    for MoleculeParser<I, InChIFormula<Count>>
+    MolecularTreeParser<InchiToken<Count>, SequenceNode<InChITree<Count>>, OwnedTrees>
+    for MoleculeParser<'_, I, InChIFormula<Count>>
 {
     fn extend_tree(
         &mut self,
+        _builder: OwnedTrees,
         mut tree: SequenceNode<InChITree<Count>>,
         _terminator: InchiToken<Count>,
         token: InchiToken<Count>,
@@ -49,18 +50,22 @@ mod tests {
     use elements_rs::Element;
 
     use super::*;
+    use crate::parsable::DEFAULT_PARSER_OPTIONS;
 
     #[test]
     #[should_panic(expected = "Counts should be handled at a higher level than sequence extension")]
     fn test_extend_tree_panics_on_count() {
-        let mut parser =
-            MoleculeParser::<core::str::Chars, InChIFormula<u32>>::new("".chars()).unwrap();
+        let mut parser = MoleculeParser::<'_, core::str::Chars, InChIFormula<u32>>::with_options(
+            "".chars(),
+            &DEFAULT_PARSER_OPTIONS,
+        )
+        .unwrap();
         let tree = SequenceNode::empty();
         let terminator = InchiToken::Element(Element::H);
         let token = InchiToken::Count(2);
 
         // This should panic
-        let _ = parser.extend_tree(tree, terminator, token);
+        let _ = parser.extend_tree(OwnedTrees, tree, terminator, token);
     }
 
     #[test]
@@ -68,13 +73,16 @@ mod tests {
         expected = "Mixture separators should be handled at a higher level than sequence extension"
     )]
     fn test_extend_tree_panics_on_dot() {
-        let mut parser =
-            MoleculeParser::<core::str::Chars, InChIFormula<u32>>::new("".chars()).unwrap();
+        let mut parser = MoleculeParser::<'_, core::str::Chars, InChIFormula<u32>>::with_options(
+            "".chars(),
+            &DEFAULT_PARSER_OPTIONS,
+        )
+        .unwrap();
         let tree = SequenceNode::empty();
         let terminator = InchiToken::Element(Element::H);
         let token = InchiToken::Dot;
 
         // This should panic
-        let _ = parser.extend_tree(tree, terminator, token);
+        let _ = parser.extend_tree(OwnedTrees, tree, terminator, token);
     }
 }
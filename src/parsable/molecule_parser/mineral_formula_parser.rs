@@ -5,24 +5,26 @@
 use elements_rs::Isotope;
 
 use crate::{
-    ChargeLike, ChemicalTree, CountLike, Empty, MineralFormula, Token,
+    ChargeLike, CountLike, Empty, MineralFormula, Token,
     errors::ParserError,
-    parsable::{MoleculeParser, molecule_parser::MolecularTreeParser},
+    parsable::{ChemicalTreeBuilder, MoleculeParser, molecule_parser::MolecularTreeParser},
 };
 
-impl<I: Iterator<Item = char>, Count: CountLike, Charge: ChargeLike>
-    MolecularTreeParser<Count, ChemicalTree<Count, Charge, Empty>>
-    for MoleculeParser<I, MineralFormula<Count, Charge>>
+impl<I: Iterator<Item = char>, Count: CountLike, Charge: ChargeLike, Tree, B>
+    MolecularTreeParser<Token<Count, Charge, Empty>, Tree, B>
+    for MoleculeParser<'_, I, MineralFormula<Count, Charge>>
 where
     Charge: TryFrom<Count>,
     Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    B: ChemicalTreeBuilder<Tree, Count = Count, Charge = Charge, Extension = Empty>,
 {
     fn extend_tree(
         &mut self,
-        tree: ChemicalTree<Count, Charge, Empty>,
+        builder: B,
+        tree: Tree,
         terminator: Token<Count, Charge, Empty>,
         token: Token<Count, Charge, Empty>,
-    ) -> Result<ChemicalTree<Count, Charge, Empty>, ParserError> {
-        self.extend_generic_tree(tree, terminator, token)
+    ) -> Result<Tree, ParserError> {
+        self.extend_generic_tree(builder, tree, terminator, token)
     }
 }
@@ -4,24 +4,26 @@
 use elements_rs::Isotope;
 
 use crate::{
-    ChargeLike, ChemicalTree, CountLike, Residual, ResidualFormula, Token,
+    ChargeLike, CountLike, Residual, ResidualFormula, Token,
     errors::ParserError,
-    parsable::{MoleculeParser, molecule_parser::MolecularTreeParser},
+    parsable::{ChemicalTreeBuilder, MoleculeParser, molecule_parser::MolecularTreeParser},
 };
 
-impl<I: Iterator<Item = char>, Count: CountLike, Charge: ChargeLike>
-    MolecularTreeParser<Count, ChemicalTree<Count, Charge, Residual>>
-    for MoleculeParser<I, ResidualFormula<Count, Charge>>
+impl<I: Iterator<Item = char>, Count: CountLike, Charge: ChargeLike, Tree, B>
+    MolecularTreeParser<Token<Count, Charge, Residual>, Tree, B>
+    for MoleculeParser<'_, I, ResidualFormula<Count, Charge>>
 where
     Charge: TryFrom<Count>,
     Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    B: ChemicalTreeBuilder<Tree, Count = Count, Charge = Charge, Extension = Residual>,
 {
     fn extend_tree(
         &mut self,
-        tree: ChemicalTree<Count, Charge, Residual>,
+        builder: B,
+        tree: Tree,
         terminator: Token<Count, Charge, Residual>,
         token: Token<Count, Charge, Residual>,
-    ) -> Result<ChemicalTree<Count, Charge, Residual>, ParserError> {
-        self.extend_generic_tree(tree, terminator, token)
+    ) -> Result<Tree, ParserError> {
+        self.extend_generic_tree(builder, tree, terminator, token)
     }
 }
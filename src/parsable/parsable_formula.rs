@@ -3,7 +3,9 @@
 use alloc::vec::Vec;
 use core::iter::Peekable;
 
-use crate::{MolecularFormulaMetadata, errors::ParserError, parsable::ParsableMolecularTree};
+use crate::{
+    MolecularFormulaMetadata, ParserOptions, errors::ParserError, parsable::ParsableMolecularTree,
+};
 
 /// Trait defining a parsable chemical formula.
 pub(crate) trait ParsableFormula: MolecularFormulaMetadata {
@@ -18,8 +20,12 @@ pub(crate) trait ParsableFormula: MolecularFormulaMetadata {
         mixtures: Vec<(Self::Count, Self::Tree)>,
     ) -> Result<Self, ParserError>;
 
-    /// Hook called at the start of the parsing process.
-    fn on_start<J>(chars: &mut Peekable<J>) -> Result<Self::StartOutput, ParserError>
+    /// Hook called at the start of the parsing process, with the options of
+    /// the parser.
+    fn on_start<J>(
+        chars: &mut Peekable<J>,
+        options: &ParserOptions,
+    ) -> Result<Self::StartOutput, ParserError>
     where
-        J: Iterator<Item = char>;
+        J: Iterator<Item = char> + Clone;
 }
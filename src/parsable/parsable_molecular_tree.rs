@@ -16,12 +16,6 @@ pub(crate) trait ParsableMolecularTree<Count> {
     where
         I: Iterator<Item = char>;
 
-    /// Returns an empty molecular tree.
-    fn empty() -> Self;
-
-    /// Returns whether the molecular tree is empty.
-    fn is_empty(&self) -> bool;
-
     /// Adds a new element to the molecular tree.
     fn element(self, element: Element) -> Self;
 }
@@ -0,0 +1,963 @@
+//! Submodule defining the options which can be provided to the parser, such
+//! as the limits bounding the resources used while parsing untrusted input.
+
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+mod charge_notation;
+mod middle_dot_handling;
+mod zero_count_handling;
+
+pub use charge_notation::ChargeNotation;
+pub use middle_dot_handling::MiddleDotHandling;
+pub use zero_count_handling::ZeroCountHandling;
+
+use crate::{
+    BaselineMinus, ChargeLike, ChemicalFormula, CountLike, Dot, MolecularFormula, Radical,
+    SuperscriptDigit,
+    errors::ParserError,
+    parsable::{CharacterMarker, byte_decoding, case_folding},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// How the parser handles a labeled prefix at the start of a chemical formula,
+/// such as the `Δ9-` descriptor or the `α-D-` anomer prefix.
+///
+/// A prefix is made of labels, each followed by a hyphen. The first label
+/// starts with a greek letter, optionally followed by digits, superscript
+/// digits and commas, as in `α`, `Δ9,11` or `η⁵`. It may be followed by
+/// further greek labels and by the `D` and `L` configuration descriptors.
+/// Since a `D` or an `L` following a prefix is always read as a descriptor,
+/// it must be followed by a hyphen.
+///
+/// Prefixes are preserved by parsing the formula as a
+/// [`Prefixed`](crate::Prefixed) formula, which always reads them.
+pub enum PrefixHandling {
+    /// Prefixes are rejected as unexpected characters.
+    #[default]
+    Reject,
+    /// Prefixes are read and discarded.
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// How the parser handles the hapticity descriptors of organometallic
+/// formulas, such as the `η⁵-` of `Fe(η⁵-C5H5)2`.
+///
+/// A descriptor is made of the greek letter `η`, followed by baseline or
+/// superscript digits and by a hyphen. Since descriptors describe how a
+/// ligand is bound rather than its composition, they do not affect the
+/// parsed formula.
+pub enum HapticityHandling {
+    /// Descriptors are rejected as unexpected characters.
+    #[default]
+    Reject,
+    /// Descriptors are read and discarded wherever they appear.
+    Skip,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+/// Options customizing the behaviour of the molecular formula parser.
+///
+/// By default, no limits are enforced, prefixes are rejected and only the
+/// built-in abbreviations, such as `Me` or `Ph`, are recognized, while
+/// solvent abbreviations are off.
+///
+/// # Example
+///
+/// ```rust
+/// use molecular_formulas::{
+///     errors::{ParserError, ParserLimit},
+///     prelude::*,
+/// };
+///
+/// let options = ParserOptions::new().with_max_tokens(4);
+/// let water = ChemicalFormula::<u16, i16>::from_str_with_options("H2O", &options);
+/// assert!(water.is_ok());
+/// let glucose = ChemicalFormula::<u16, i16>::from_str_with_options("C6H12O6", &options);
+/// assert_eq!(glucose, Err(ParserError::LimitExceeded(ParserLimit::Tokens)));
+/// ```
+#[allow(clippy::struct_field_names)]
+pub struct ParserOptions {
+    /// The maximal number of tokens which may be read.
+    max_tokens: Option<usize>,
+    /// The maximal nesting depth of brackets and radical-decorated units.
+    max_depth: Option<usize>,
+    /// The maximal value of any count, including mixture multipliers.
+    max_count: Option<u64>,
+    /// How labeled prefixes of chemical formulas are handled.
+    prefix_handling: PrefixHandling,
+    /// How hapticity descriptors are handled.
+    hapticity_handling: HapticityHandling,
+    /// How the digits preceding a trailing charge sign are read.
+    charge_notation: ChargeNotation,
+    /// How the middle dots are read.
+    middle_dot_handling: MiddleDotHandling,
+    /// How zero counts and leading zeros are handled.
+    zero_count_handling: ZeroCountHandling,
+    /// Whether whitespace around the mixture separators is ignored.
+    spaced_separators: bool,
+    /// Whether a comma within a mixture multiplier is a decimal separator.
+    decimal_comma: bool,
+    /// Whether mixture multipliers may be written as fractions.
+    fractional_multipliers: bool,
+    /// Whether the runs of uppercase letters are folded into element
+    /// symbols.
+    case_folding: bool,
+    /// The user-defined abbreviations with their bracketed expansions,
+    /// sorted by decreasing length of their names.
+    abbreviations: Vec<(String, String)>,
+    /// The solvent abbreviations with their bracketed expansions, sorted by
+    /// decreasing length of their names.
+    solvents: Vec<(String, String)>,
+}
+
+impl ParserOptions {
+    /// The maximal nesting depth of the parsed trees, which is always
+    /// enforced regardless of the options.
+    ///
+    /// The trees are traversed recursively when computing their masses,
+    /// counts and displays, so that deeper trees could overflow the stack.
+    /// Each bracket group and each radical-decorated unit adds a level of
+    /// nesting, as does each trailing charge or radical wrapping the unit
+    /// preceding it, as in `H+H+H+`. Deeper formulas are rejected with
+    /// [`ParserError::LimitExceeded`](crate::errors::ParserError::LimitExceeded).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::{
+    ///     errors::{ParserError, ParserLimit},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let nested = format!("{}H{}", "(".repeat(10_000), ")".repeat(10_000));
+    /// assert_eq!(
+    ///     ChemicalFormula::<u16, i16>::from_str(&nested),
+    ///     Err(ParserError::LimitExceeded(ParserLimit::Depth))
+    /// );
+    /// ```
+    pub const MAX_NESTING_DEPTH: usize = 256;
+
+    /// Returns the default parser options, which enforce no limits besides
+    /// the [`MAX_NESTING_DEPTH`](Self::MAX_NESTING_DEPTH) and reject
+    /// prefixes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_tokens: None,
+            max_depth: None,
+            max_count: None,
+            prefix_handling: PrefixHandling::Reject,
+            hapticity_handling: HapticityHandling::Reject,
+            charge_notation: ChargeNotation::SignFirst,
+            middle_dot_handling: MiddleDotHandling::Contextual,
+            zero_count_handling: ZeroCountHandling::Reject,
+            spaced_separators: false,
+            decimal_comma: false,
+            fractional_multipliers: false,
+            case_folding: false,
+            abbreviations: Vec::new(),
+            solvents: Vec::new(),
+        }
+    }
+
+    /// Sets the maximal number of tokens which may be read.
+    #[must_use]
+    pub const fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Sets the maximal nesting depth, where a formula without brackets has
+    /// depth zero.
+    ///
+    /// A depth beyond the [`MAX_NESTING_DEPTH`](Self::MAX_NESTING_DEPTH) is
+    /// rejected regardless of this option.
+    #[must_use]
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the maximal value of any count, including mixture multipliers.
+    #[must_use]
+    pub const fn with_max_count(mut self, max_count: u64) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    /// Sets how labeled prefixes of chemical formulas are handled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_prefix_handling(PrefixHandling::Skip);
+    /// let thc = ChemicalFormula::<u32, i32>::from_str_with_options("Δ9-C21H30O2", &options).unwrap();
+    /// assert_eq!(thc.to_string(), "C₂₁H₃₀O₂");
+    ///
+    /// assert!(
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("Δ9-C21H30O2", &ParserOptions::new())
+    ///         .is_err()
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn with_prefix_handling(mut self, prefix_handling: PrefixHandling) -> Self {
+        self.prefix_handling = prefix_handling;
+        self
+    }
+
+    /// Sets how hapticity descriptors are handled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_hapticity_handling(HapticityHandling::Skip);
+    /// let ferrocene =
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("Fe(η⁵-C5H5)2", &options).unwrap();
+    /// assert_eq!(ferrocene, ChemicalFormula::from_str("Fe(C5H5)2").unwrap());
+    /// assert_eq!(ferrocene.charge(), 0.0);
+    ///
+    /// assert!(ChemicalFormula::<u32, i32>::from_str("Fe(η⁵-C5H5)2").is_err());
+    /// ```
+    #[must_use]
+    pub const fn with_hapticity_handling(mut self, hapticity_handling: HapticityHandling) -> Self {
+        self.hapticity_handling = hapticity_handling;
+        self
+    }
+
+    /// Sets how the digits preceding a trailing charge sign are read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_charge_notation(ChargeNotation::MagnitudeFirst);
+    /// let ferrous = ChemicalFormula::<u32, i32>::from_str_with_options("Fe2+", &options).unwrap();
+    /// assert_eq!(ferrous, ChemicalFormula::from_str("Fe+2").unwrap());
+    /// let ammonium = ChemicalFormula::<u32, i32>::from_str_with_options("NH4+", &options).unwrap();
+    /// assert_eq!(ammonium, ChemicalFormula::from_str("NH4+").unwrap());
+    ///
+    /// let iron = ChemicalFormula::<u32, i32>::from_str("Fe2+").unwrap();
+    /// assert_eq!(iron.count_of_element::<u32>(Element::Fe), Some(2));
+    ///
+    /// let options = ParserOptions::new().with_charge_notation(ChargeNotation::Bracketed);
+    /// let sulfate = ChemicalFormula::<u32, i32>::from_str_with_options("SO4(2-)", &options).unwrap();
+    /// assert_eq!(sulfate, ChemicalFormula::from_str("SO4-2").unwrap());
+    /// ```
+    #[must_use]
+    pub const fn with_charge_notation(mut self, charge_notation: ChargeNotation) -> Self {
+        self.charge_notation = charge_notation;
+        self
+    }
+
+    /// Sets how the middle dots `·` and `∙` are read, which may be either
+    /// mixture separators or radicals.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let hydrate = ChemicalFormula::<u32, i32>::from_str("CuSO4·5H2O").unwrap();
+    /// assert_eq!(hydrate, ChemicalFormula::from_str("CuSO4.5H2O").unwrap());
+    /// let methyl = ChemicalFormula::<u32, i32>::from_str("·CH3").unwrap();
+    /// assert_eq!(methyl, ChemicalFormula::from_str("•CH3").unwrap());
+    ///
+    /// let options = ParserOptions::new().with_middle_dot_handling(MiddleDotHandling::Radical);
+    /// let ethane = ChemicalFormula::<u32, i32>::from_str_with_options("CH3·CH3", &options).unwrap();
+    /// assert_eq!(ethane.number_of_mixtures(), 1);
+    /// ```
+    #[must_use]
+    pub const fn with_middle_dot_handling(
+        mut self,
+        middle_dot_handling: MiddleDotHandling,
+    ) -> Self {
+        self.middle_dot_handling = middle_dot_handling;
+        self
+    }
+
+    /// Sets how counts of zero and counts with leading zeros are handled, as
+    /// in the `N0` placeholders emitted by some programs.
+    ///
+    /// Since InChI formulas are strict, they are always parsed as if zero
+    /// counts were rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_zero_count_handling(ZeroCountHandling::Drop);
+    /// let methane = ChemicalFormula::<u32, i32>::from_str_with_options("CH4N0", &options).unwrap();
+    /// assert_eq!(methane, ChemicalFormula::from_str("CH4").unwrap());
+    /// let glucose =
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("C06H12O06", &options).unwrap();
+    /// assert_eq!(glucose, ChemicalFormula::from_str("C6H12O6").unwrap());
+    ///
+    /// assert!(ChemicalFormula::<u32, i32>::from_str("CH4N0").is_err());
+    /// ```
+    #[must_use]
+    pub const fn with_zero_count_handling(
+        mut self,
+        zero_count_handling: ZeroCountHandling,
+    ) -> Self {
+        self.zero_count_handling = zero_count_handling;
+        self
+    }
+
+    /// Sets whether whitespace around the mixture separators is ignored, as
+    /// in the `(C17H19NO3)2. H2SO4. 5H2O` of KEGG DRUG.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_spaced_separators(true);
+    /// let morphine_sulfate =
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("(C17H19NO3)2. H2SO4. 5H2O", &options)
+    ///         .unwrap();
+    /// assert_eq!(morphine_sulfate, ChemicalFormula::from_str("(C17H19NO3)2.H2SO4.5H2O").unwrap());
+    /// assert!(ChemicalFormula::<u32, i32>::from_str("(C17H19NO3)2. H2SO4. 5H2O").is_err());
+    /// ```
+    #[must_use]
+    pub const fn with_spaced_separators(mut self, spaced_separators: bool) -> Self {
+        self.spaced_separators = spaced_separators;
+        self
+    }
+
+    /// Sets whether a comma between the digits of a mixture multiplier is
+    /// read as a decimal separator, as in the `CaSO4.2,5H2O` exported by
+    /// laboratory software set to European locales.
+    ///
+    /// Only the formulas whose mixtures may be repeated a fractional number
+    /// of times, such as the `RationalFormula` of the `rational` feature,
+    /// read decimal multipliers. Since the full stop separates the mixtures,
+    /// the decimal point itself is never read as a decimal separator.
+    #[must_use]
+    pub const fn with_decimal_comma(mut self, decimal_comma: bool) -> Self {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+
+    /// Sets whether a mixture multiplier may be written as a fraction, such
+    /// as the `1/2` of the hemihydrate `CaSO4.1/2H2O` found in regulatory
+    /// documents, with either a slash or the fraction slash `⁄`.
+    ///
+    /// As for decimal multipliers, only the formulas whose mixtures may be
+    /// repeated a fractional number of times, such as the `RationalFormula`
+    /// of the `rational` feature, read fractional multipliers, and only when
+    /// this option is set.
+    #[must_use]
+    pub const fn with_fractional_multipliers(mut self, fractional_multipliers: bool) -> Self {
+        self.fractional_multipliers = fractional_multipliers;
+        self
+    }
+
+    /// Sets whether the runs of uppercase letters are read
+    /// case-insensitively as element symbols, as in the `NACL` stored by
+    /// legacy databases.
+    ///
+    /// Since some runs may be read in several ways, such as `CO`, which is
+    /// read as cobalt rather than carbon and oxygen, the two-letter symbols
+    /// are preferred unless the rest of the run could then not be read. The
+    /// runs with several readings of a formula are reported by
+    /// [`CaseFolding`](crate::CaseFolding), which describes the folding in
+    /// detail. Runs containing lowercase letters are read as usual.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_case_folding(true);
+    /// let salt = ChemicalFormula::<u32, i32>::from_str_with_options("NACL", &options).unwrap();
+    /// assert_eq!(salt, ChemicalFormula::from_str("NaCl").unwrap());
+    /// let bromide = ChemicalFormula::<u32, i32>::from_str_with_options("C6H5BR", &options).unwrap();
+    /// assert_eq!(bromide.count_of_element::<u32>(Element::Br), Some(1));
+    ///
+    /// assert!(ChemicalFormula::<u32, i32>::from_str("NACL").is_err());
+    /// ```
+    #[must_use]
+    pub const fn with_case_folding(mut self, case_folding: bool) -> Self {
+        self.case_folding = case_folding;
+        self
+    }
+
+    /// Registers an abbreviation standing for the provided formula, such as
+    /// the `Ac` acetyl group, replacing any previous abbreviation with
+    /// the same name.
+    ///
+    /// Abbreviations are recognized wherever an element symbol may start,
+    /// and behave as the bracketed formula they stand for, so that they may
+    /// be repeated as in `Ac2O`. When several abbreviations match, the
+    /// longest one is used. User-defined abbreviations take precedence over
+    /// element symbols and built-in abbreviations, so that registering `Ac`
+    /// for the acetyl group shadows actinium. Since abbreviations are
+    /// expanded before parsing, the expanded formula counts towards the
+    /// token limit.
+    ///
+    /// # Errors
+    ///
+    /// * If the name is empty, does not start with an uppercase letter or
+    ///   contains characters other than letters.
+    /// * If the formula is empty, is made of several mixtures, or of a mixture
+    ///   with a count other than one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let acetyl = ChemicalFormula::<u32, i32>::from_str("C2H3O").unwrap();
+    /// let options = ParserOptions::new().with_abbreviation("Ac", &acetyl).unwrap();
+    /// let glycine =
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("AcNHCH2COOH", &options).unwrap();
+    /// assert_eq!(glycine.count_of_element::<u32>(Element::C), Some(4));
+    /// assert_eq!(glycine.count_of_element::<u32>(Element::H), Some(7));
+    /// let actinium = ChemicalFormula::<u32, i32>::from_str("AcNHCH2COOH").unwrap();
+    /// assert_eq!(actinium.count_of_element::<u32>(Element::Ac), Some(1));
+    /// ```
+    pub fn with_abbreviation<Count: CountLike, Charge: ChargeLike>(
+        mut self,
+        name: &str,
+        formula: &ChemicalFormula<Count, Charge>,
+    ) -> Result<Self, ParserError> {
+        let mut chars = name.chars();
+        match chars.next() {
+            None => return Err(ParserError::UnexpectedEndOfInput),
+            Some(first) if !first.is_uppercase() => {
+                return Err(ParserError::UnexpectedCharacter(first));
+            }
+            Some(_) => {}
+        }
+        if let Some(invalid) = chars.find(|c| !c.is_alphabetic()) {
+            return Err(ParserError::UnexpectedCharacter(invalid));
+        }
+        register(&mut self.abbreviations, name, bracketed(formula)?);
+        Ok(self)
+    }
+
+    /// Registers a solvent abbreviation standing for the provided formula,
+    /// such as `DMSO`, replacing any previous solvent with the same name.
+    ///
+    /// Unlike other abbreviations, solvents are only recognized as whole
+    /// mixture components, optionally preceded by their count, as in the
+    /// solvate `C20H24N2O2.2EtOH`. A component is delimited by the mixture
+    /// separators and by the middle dots commonly used for solvates, so that
+    /// solvent names may freely contain element symbols.
+    ///
+    /// Solvents may be labeled with the number of their hydrogens replaced
+    /// by deuterium, as in the `d6-DMSO` or `DMSO-d6` of NMR solvents, which
+    /// are read as the solvent with as many deuterium atoms, i.e. `C2D6OS`.
+    /// Labels exceeding the hydrogens of the solvent are left unexpanded, and
+    /// are therefore rejected by the parser.
+    ///
+    /// # Panics
+    ///
+    /// * If the name is empty, starts with a digit or contains a mixture
+    ///   separator or a middle dot.
+    /// * If the formula is made of several mixtures, or of a mixture with a
+    ///   count other than one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let dioxane = ChemicalFormula::<u32, i32>::from_str("C4H8O2").unwrap();
+    /// let options = ParserOptions::new().with_solvent("Diox", &dioxane);
+    /// let solvate =
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("C6H6O.Diox", &options).unwrap();
+    /// assert_eq!(solvate.to_string(), "C₆H₆O.(C₄H₈O₂)");
+    ///
+    /// let labeled = ChemicalFormula::<u32, i32>::from_str_with_options("d8-Diox", &options).unwrap();
+    /// assert_eq!(labeled, ChemicalFormula::from_str("(C4D8O2)").unwrap());
+    /// ```
+    #[must_use]
+    pub fn with_solvent<Count: CountLike, Charge: ChargeLike>(
+        mut self,
+        name: &str,
+        formula: &ChemicalFormula<Count, Charge>,
+    ) -> Self {
+        assert!(
+            name.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                && !name.chars().any(is_component_boundary),
+            "Solvents must not start with a digit nor contain component separators"
+        );
+        let expansion =
+            bracketed(formula).expect("Solvents must stand for a single mixture with a unit count");
+        register(&mut self.solvents, name, expansion);
+        self
+    }
+
+    /// Registers the abbreviations of the common solvents, which are off by
+    /// default: `MeOH`, `EtOH`, `iPrOH`, `AcOH`, `TFA`, `Et2O`, `THF`,
+    /// `EtOAc`, `DCM`, `MeCN`, `DMF`, `DMAc`, `DMSO`, `NMP` and `Py`.
+    ///
+    /// See [`with_solvent`](Self::with_solvent) for how solvents are
+    /// recognized, and to register further solvents.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let options = ParserOptions::new().with_common_solvents();
+    /// let solvate =
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("CuCl2.2DMSO", &options).unwrap();
+    /// assert_eq!(solvate.to_string(), "CuCl₂.2(C₂H₆OS)");
+    /// assert_eq!(solvate.count_of_element::<u32>(Element::S), Some(2));
+    ///
+    /// assert!(ChemicalFormula::<u32, i32>::from_str("CuCl2.2DMSO").is_err());
+    ///
+    /// // NMR solvents are written with their deuteration labels.
+    /// let dmso = ChemicalFormula::<u32, i32>::from_str_with_options("d6-DMSO", &options).unwrap();
+    /// assert_eq!(dmso, ChemicalFormula::from_str("(C2D6OS)").unwrap());
+    /// assert_eq!(
+    ///     ChemicalFormula::<u32, i32>::from_str_with_options("THF-d8", &options).unwrap(),
+    ///     ChemicalFormula::from_str("(C4D8O)").unwrap()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_common_solvents(mut self) -> Self {
+        for (name, formula) in COMMON_SOLVENTS {
+            register(&mut self.solvents, name, format!("({formula})"));
+        }
+        self
+    }
+
+    /// Returns the formula standing for the provided user-defined
+    /// abbreviation, as the bracketed formula it expands to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let tosyl = ChemicalFormula::<u32, i32>::from_str("C7H7SO2").unwrap();
+    /// let options = ParserOptions::new().with_abbreviation("Ts", &tosyl).unwrap();
+    /// assert_eq!(options.abbreviation("Ts"), Some("(C₇H₇SO₂)"));
+    /// assert_eq!(options.abbreviation("Me"), None);
+    /// ```
+    #[must_use]
+    pub fn abbreviation(&self, name: &str) -> Option<&str> {
+        self.abbreviations
+            .iter()
+            .find_map(|(existing, expansion)| (existing == name).then_some(expansion.as_str()))
+    }
+
+    /// Returns the formula standing for the provided solvent abbreviation,
+    /// as the bracketed formula it expands to.
+    #[must_use]
+    pub fn solvent(&self, name: &str) -> Option<&str> {
+        self.solvents
+            .iter()
+            .find_map(|(existing, expansion)| (existing == name).then_some(expansion.as_str()))
+    }
+
+    /// Returns the provided string with its runs of uppercase letters folded
+    /// if requested, the middle dots read as mixture
+    /// separators replaced by full stops, the spacing around the mixture
+    /// separators removed and the magnitude-first charges rewritten if
+    /// requested, the user-defined abbreviations and solvents
+    /// replaced by their bracketed expansions, if requested with the
+    /// hapticity descriptors removed and, if requested, with the units
+    /// counted zero times and the leading zeros of counts removed.
+    ///
+    /// # Errors
+    ///
+    /// * If a magnitude-first charge is ambiguous, as detailed in
+    ///   [`ChargeNotation::MagnitudeFirst`].
+    pub(crate) fn preprocess<'s>(&self, s: &'s str) -> Result<Cow<'s, str>, ParserError> {
+        let mut s = Cow::Borrowed(s);
+        if self.case_folding
+            && let Some(folded) = case_folding::fold(&s)
+        {
+            s = Cow::Owned(folded);
+        }
+        if let Some(rewritten) = self.separate_middle_dots(&s) {
+            s = Cow::Owned(rewritten);
+        }
+        if self.spaced_separators
+            && let Some(stripped) = strip_separator_spacing(&s)
+        {
+            s = Cow::Owned(stripped);
+        }
+        if self.charge_notation != ChargeNotation::SignFirst
+            && let Some(rewritten) = charge_notation::to_sign_first(&s, self.charge_notation)?
+        {
+            s = Cow::Owned(rewritten);
+        }
+        let skip_hapticity = self.hapticity_handling == HapticityHandling::Skip;
+        if !self.abbreviations.is_empty()
+            || !self.solvents.is_empty()
+            || (skip_hapticity && s.contains('η'))
+        {
+            s = Cow::Owned(self.expand(&s, skip_hapticity));
+        }
+        if self.zero_count_handling == ZeroCountHandling::Drop
+            && let Some(rewritten) = zero_count_handling::drop_zero_counts(&s)
+        {
+            s = Cow::Owned(rewritten);
+        }
+        Ok(s)
+    }
+
+    /// Returns the provided string with the middle dots read as mixture
+    /// separators replaced by full stops, or `None` if there is no such
+    /// middle dot.
+    pub(crate) fn separate_middle_dots(&self, s: &str) -> Option<String> {
+        middle_dot_handling::to_full_stops(s, self.middle_dot_handling)
+    }
+
+    /// Returns the provided string with the user-defined abbreviations and
+    /// solvents replaced by their bracketed expansions and, if requested,
+    /// with the hapticity descriptors removed.
+    fn expand(&self, s: &str, skip_hapticity: bool) -> String {
+        let mut expanded = String::with_capacity(s.len());
+        let mut rest = s;
+        let mut component_start = true;
+        while let Some(c) = rest.chars().next() {
+            if core::mem::take(&mut component_start) {
+                let component = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+                if let Some((expansion, length)) = self.solvent_at(component) {
+                    expanded.push_str(&rest[..rest.len() - component.len()]);
+                    expanded.push_str(&expansion);
+                    rest = &component[length..];
+                    continue;
+                }
+            }
+            if let Some(length) = hapticity_length(rest).filter(|_| skip_hapticity) {
+                rest = &rest[length..];
+                continue;
+            }
+            let abbreviation = c
+                .is_uppercase()
+                .then(|| {
+                    self.abbreviations.iter().find(|(name, _)| rest.starts_with(name.as_str()))
+                })
+                .flatten();
+            if let Some((name, expansion)) = abbreviation {
+                expanded.push_str(expansion);
+                rest = &rest[name.len()..];
+            } else {
+                expanded.push(c);
+                rest = &rest[c.len_utf8()..];
+                component_start = is_component_boundary(c);
+            }
+        }
+        expanded
+    }
+
+    /// Returns the expansion of the solvent making up the provided mixture
+    /// component, deuterated if it is labeled as such, alongside the length
+    /// of the solvent name and of its label.
+    fn solvent_at(&self, component: &str) -> Option<(Cow<'_, str>, usize)> {
+        let (prefix_label, labeled) = match deuteration_prefix(component) {
+            Some((deuterium, labeled)) => (Some(deuterium), labeled),
+            None => (None, component),
+        };
+        self.solvents.iter().find_map(|(name, expansion)| {
+            let tail = labeled.strip_prefix(name.as_str())?;
+            let (label, tail) = match (prefix_label, deuteration_suffix(tail)) {
+                (None, Some((deuterium, tail))) => (Some(deuterium), tail),
+                (label, _) => (label, tail),
+            };
+            if !tail.chars().next().is_none_or(is_component_boundary) {
+                return None;
+            }
+            let expansion = match label {
+                Some(deuterium) => Cow::Owned(deuterated(expansion, deuterium)?),
+                None => Cow::Borrowed(expansion.as_str()),
+            };
+            Some((expansion, component.len() - tail.len()))
+        })
+    }
+
+    /// Returns how the digits preceding a trailing charge sign are read.
+    #[must_use]
+    pub const fn charge_notation(&self) -> ChargeNotation {
+        self.charge_notation
+    }
+
+    /// Returns how the middle dots are read.
+    #[must_use]
+    pub const fn middle_dot_handling(&self) -> MiddleDotHandling {
+        self.middle_dot_handling
+    }
+
+    /// Returns how zero counts and leading zeros are handled.
+    #[must_use]
+    pub const fn zero_count_handling(&self) -> ZeroCountHandling {
+        self.zero_count_handling
+    }
+
+    /// Returns whether whitespace around the mixture separators is ignored.
+    #[must_use]
+    pub const fn spaced_separators(&self) -> bool {
+        self.spaced_separators
+    }
+
+    /// Returns whether a comma within a mixture multiplier is a decimal
+    /// separator.
+    #[must_use]
+    pub const fn decimal_comma(&self) -> bool {
+        self.decimal_comma
+    }
+
+    /// Returns whether mixture multipliers may be written as fractions.
+    #[must_use]
+    pub const fn fractional_multipliers(&self) -> bool {
+        self.fractional_multipliers
+    }
+
+    /// Returns whether the runs of uppercase letters are folded into element
+    /// symbols.
+    #[must_use]
+    pub const fn case_folding(&self) -> bool {
+        self.case_folding
+    }
+
+    /// Returns how hapticity descriptors are handled.
+    #[must_use]
+    pub const fn hapticity_handling(&self) -> HapticityHandling {
+        self.hapticity_handling
+    }
+
+    /// Returns the maximal number of tokens which may be read, if any.
+    #[must_use]
+    pub const fn max_tokens(&self) -> Option<usize> {
+        self.max_tokens
+    }
+
+    /// Returns the maximal nesting depth, if any.
+    #[must_use]
+    pub const fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns the maximal value of any count, if any.
+    #[must_use]
+    pub const fn max_count(&self) -> Option<u64> {
+        self.max_count
+    }
+
+    /// Returns how labeled prefixes of chemical formulas are handled.
+    #[must_use]
+    pub const fn prefix_handling(&self) -> PrefixHandling {
+        self.prefix_handling
+    }
+}
+
+/// The common solvents registered by
+/// [`with_common_solvents`](ParserOptions::with_common_solvents), with their
+/// formulas.
+const COMMON_SOLVENTS: [(&str, &str); 15] = [
+    ("MeOH", "CH4O"),
+    ("EtOH", "C2H6O"),
+    ("iPrOH", "C3H8O"),
+    ("AcOH", "C2H4O2"),
+    ("TFA", "C2HF3O2"),
+    ("Et2O", "C4H10O"),
+    ("THF", "C4H8O"),
+    ("EtOAc", "C4H8O2"),
+    ("DCM", "CH2Cl2"),
+    ("MeCN", "C2H3N"),
+    ("DMF", "C3H7NO"),
+    ("DMAc", "C4H9NO"),
+    ("DMSO", "C2H6OS"),
+    ("NMP", "C5H9NO"),
+    ("Py", "C5H5N"),
+];
+
+/// Returns the provided string without the whitespace around its mixture
+/// components, or `None` if there is no such whitespace.
+fn strip_separator_spacing(s: &str) -> Option<String> {
+    let mut stripped = String::with_capacity(s.len());
+    let mut rest = s;
+    loop {
+        let end = rest.find(Dot::matches).unwrap_or(rest.len());
+        let (component, tail) = rest.split_at(end);
+        stripped.push_str(component.trim());
+        let Some(separator) = tail.chars().next() else {
+            break;
+        };
+        stripped.push(separator);
+        rest = &tail[separator.len_utf8()..];
+    }
+    (stripped.len() != s.len()).then_some(stripped)
+}
+
+/// Returns whether the provided character delimits the mixture components
+/// in which solvents are recognized.
+fn is_component_boundary(c: char) -> bool {
+    Dot::matches(c) || Radical::matches(c)
+}
+
+/// Returns the bracketed expansion of the provided formula.
+///
+/// # Errors
+///
+/// * If the formula is empty, is made of several mixtures, or of a mixture with
+///   a count other than one.
+fn bracketed<Count: CountLike, Charge: ChargeLike>(
+    formula: &ChemicalFormula<Count, Charge>,
+) -> Result<String, ParserError> {
+    let mut mixtures = formula.counted_mixtures();
+    match (mixtures.next(), mixtures.next()) {
+        (Some((count, tree)), None) if count.is_one() => Ok(format!("({tree})")),
+        (None, _) => Err(ParserError::EmptyMolecularTree),
+        // The mixture separator, or the leading digit of the count, is the
+        // character which may not appear in an abbreviated group.
+        (Some(_), Some(_)) => Err(ParserError::UnexpectedCharacter('.')),
+        (Some((count, _)), None) => {
+            Err(ParserError::UnexpectedCharacter(count.to_string().chars().next().unwrap_or('0')))
+        }
+    }
+}
+
+/// Registers the provided expansion in the provided table, replacing any
+/// previous expansion with the same name and keeping the table sorted by
+/// decreasing length of the names, so that the longest match is found first.
+fn register(table: &mut Vec<(String, String)>, name: &str, expansion: String) {
+    table.retain(|(existing, _)| existing != name);
+    let position =
+        table.iter().position(|(existing, _)| existing.len() < name.len()).unwrap_or(table.len());
+    table.insert(position, (String::from(name), expansion));
+}
+
+/// Returns the number of deuterium atoms of the deuteration label, as the
+/// `d6-` of `d6-DMSO`, at the start of the provided string and the rest of
+/// the string following it.
+fn deuteration_prefix(s: &str) -> Option<(u32, &str)> {
+    let (deuterium, rest) = deuteration_count(s.strip_prefix('d')?)?;
+    let hyphen = rest.chars().next().filter(|c| BaselineMinus::matches(*c))?;
+    Some((deuterium, &rest[hyphen.len_utf8()..]))
+}
+
+/// Returns the number of deuterium atoms of the deuteration label, as the
+/// `-d6` of `DMSO-d6`, at the start of the provided string and the rest of
+/// the string following it.
+fn deuteration_suffix(s: &str) -> Option<(u32, &str)> {
+    let hyphen = s.chars().next().filter(|c| BaselineMinus::matches(*c))?;
+    deuteration_count(s[hyphen.len_utf8()..].strip_prefix('d')?)
+}
+
+/// Returns the positive number of deuterium atoms at the start of the
+/// provided string and the rest of the string following it.
+fn deuteration_count(s: &str) -> Option<(u32, &str)> {
+    let rest = s.trim_start_matches(|c: char| c.is_ascii_digit());
+    let deuterium: u32 = s[..s.len() - rest.len()].parse().ok()?;
+    (deuterium > 0).then_some((deuterium, rest))
+}
+
+/// Returns the bracketed expansion of the provided solvent expansion with
+/// the provided number of its hydrogens replaced by deuterium, or `None` if
+/// the solvent has fewer hydrogens.
+fn deuterated(expansion: &str, deuterium: u32) -> Option<String> {
+    let formula = expansion.strip_prefix('(')?.strip_suffix(')')?;
+    let formula: ChemicalFormula<u32, i32> = formula.parse().ok()?;
+    bracketed(&formula.exchange_h_for_d(deuterium).ok()?).ok()
+}
+
+/// Returns the length in bytes of the hapticity descriptor at the start of
+/// the provided string, including its trailing hyphen, if any.
+fn hapticity_length(s: &str) -> Option<usize> {
+    let digits = s.strip_prefix('η')?;
+    let rest = digits
+        .trim_start_matches(|c: char| c.is_ascii_digit() || SuperscriptDigit::try_from(c).is_ok());
+    if rest.len() == digits.len() {
+        return None;
+    }
+    let hyphen = rest.chars().next().filter(|c| BaselineMinus::matches(*c))?;
+    Some(s.len() - rest.len() + hyphen.len_utf8())
+}
+
+/// The default parser options, used by the `FromStr` implementations.
+pub(crate) static DEFAULT_PARSER_OPTIONS: ParserOptions = ParserOptions::new();
+
+/// Trait for formulas which can be parsed with custom [`ParserOptions`].
+pub trait FromStrWithOptions: Sized {
+    /// Parses the provided string using the provided options.
+    ///
+    /// # Errors
+    ///
+    /// * If the string is not a valid formula, or if it exceeds any of the
+    ///   limits set in the options.
+    fn from_str_with_options(s: &str, options: &ParserOptions) -> Result<Self, ParserError>;
+
+    /// Parses the provided bytes using the default options, decoding them
+    /// as UTF-8 and falling back to Latin-1 for the bytes which are not part
+    /// of a valid UTF-8 sequence.
+    ///
+    /// Legacy instrument exports often encode the middle dot of hydrates as
+    /// the Latin-1 byte `0xB7`, which is decoded as `·`. Valid UTF-8 input
+    /// is parsed without being copied.
+    ///
+    /// # Errors
+    ///
+    /// * If the decoded string is not a valid formula.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    ///
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let latin1 = ChemicalFormula::<u32, i32>::parse_bytes(b"CuSO4\xB75H2O").unwrap();
+    /// let utf8 = ChemicalFormula::<u32, i32>::parse_bytes("CuSO4·5H2O".as_bytes()).unwrap();
+    /// assert_eq!(latin1, utf8);
+    /// assert_eq!(latin1, ChemicalFormula::from_str("CuSO4.5H2O").unwrap());
+    /// ```
+    fn parse_bytes(bytes: &[u8]) -> Result<Self, ParserError> {
+        Self::parse_bytes_with_options(bytes, &DEFAULT_PARSER_OPTIONS)
+    }
+
+    /// Parses the provided bytes using the provided options, decoding them
+    /// as [`parse_bytes`](Self::parse_bytes) does.
+    ///
+    /// # Errors
+    ///
+    /// * If the decoded string is not a valid formula, or if it exceeds any of
+    ///   the limits set in the options.
+    fn parse_bytes_with_options(
+        bytes: &[u8],
+        options: &ParserOptions,
+    ) -> Result<Self, ParserError> {
+        Self::from_str_with_options(&byte_decoding::decode(bytes), options)
+    }
+}
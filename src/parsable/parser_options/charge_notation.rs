@@ -0,0 +1,216 @@
+//! Submodule providing the notations in which the charges of formulas may be
+//! written, and the rewriting of charges whose magnitude precedes their sign,
+//! as in `Fe2+`, into the sign-first notation read by the parser.
+
+use alloc::string::String;
+
+use crate::{BaselineMinus, BaselinePlus, Dot, errors::ParserError, parsable::CharacterMarker};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// How the parser reads the baseline digits preceding a trailing charge
+/// sign, as in `Fe2+`, which may be either a count or a charge magnitude.
+pub enum ChargeNotation {
+    /// The digits preceding a sign are counts, so that `Fe2+` is read as two
+    /// iron atoms with a single positive charge. Magnitudes follow the sign,
+    /// as in the `Fe+2` used by PubChem, or are written as superscripts,
+    /// either before or after the sign as in `Fe³⁺` and `Fe⁺³`.
+    #[default]
+    SignFirst,
+    /// The digits preceding a sign at the end of a mixture component may be
+    /// a charge magnitude, as in the `Fe2+` and `SO42-` of textbooks or the
+    /// `SO4(2-)` of ChEBI. The following rules are applied in order:
+    ///
+    /// 1. A sign within round brackets, optionally preceded by digits as in
+    ///    `SO4(2-)` or `NH4(+)`, is a charge.
+    /// 2. Digits which cannot end with a magnitude are a count: magnitudes are
+    ///    never `0` or `1`, and counts are never `1`, so that the digits of
+    ///    `C60+`, `C12H22O11+` and `C27H43O12-` are counts.
+    /// 3. A single digit following a lone element symbol or a closing square
+    ///    bracket, as in `Fe2+` or `[Fe(CN)6]4-`, is a magnitude.
+    /// 4. Any other single digit, as in `NH4+`, is a count.
+    /// 5. Of two or more digits following a lone element symbol or a closing
+    ///    bracket, as in `Hg22+`, the last one is the magnitude and the
+    ///    preceding ones are a count.
+    /// 6. Any other run of two or more digits, as in `SO42-`, is ambiguous and
+    ///    rejected with [`ParserError::UnprocessableNumber`]: such charges must
+    ///    be written within round brackets, as in `SO4(2-)`, or with the sign
+    ///    first.
+    ///
+    /// Formulas in the sign-first notation are read as under
+    /// [`SignFirst`](Self::SignFirst), except for the ones matching the
+    /// rules above.
+    MagnitudeFirst,
+    /// Only the charges written within round brackets at the end of a
+    /// mixture component, as in the `SO4(2-)` or `NH4(+)` of ChEBI, are read
+    /// with the magnitude first, which is never ambiguous. Any other digits
+    /// preceding a sign are counts, as under [`SignFirst`](Self::SignFirst),
+    /// so that `C12H22O11+` is read as a sucrose cation.
+    Bracketed,
+}
+
+/// Returns the provided string with the charges of its mixture components
+/// rewritten from the provided magnitude-first notation into the sign-first
+/// one, according to the rules of [`ChargeNotation::MagnitudeFirst`] or of
+/// [`ChargeNotation::Bracketed`], or `None` if no charge needs to be
+/// rewritten.
+///
+/// # Errors
+///
+/// * If a mixture component ends with an ambiguous run of digits preceding its
+///   charge sign, as in `SO42-`.
+pub(super) fn to_sign_first(
+    s: &str,
+    notation: ChargeNotation,
+) -> Result<Option<String>, ParserError> {
+    let mut rewritten = String::with_capacity(s.len());
+    let mut changed = false;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let end = rest.find(Dot::matches).unwrap_or(rest.len());
+        let (component, tail) = rest.split_at(end);
+        if let Some((head, count, sign, magnitude)) = split_charge(component, notation)? {
+            rewritten.push_str(head);
+            rewritten.push_str(count);
+            rewritten.push(sign);
+            rewritten.push_str(magnitude);
+            changed = true;
+        } else {
+            rewritten.push_str(component);
+        }
+        let separator = tail.chars().next();
+        if let Some(separator) = separator {
+            rewritten.push(separator);
+        }
+        rest = &tail[separator.map_or(0, char::len_utf8)..];
+    }
+    Ok(changed.then_some(rewritten))
+}
+
+/// Returns whether the provided character is a baseline charge sign.
+fn is_sign(c: char) -> bool {
+    BaselinePlus::matches(c) || BaselineMinus::matches(c)
+}
+
+/// Returns whether the provided string is a lone element symbol, possibly
+/// preceded by the count of its mixture.
+fn is_lone_element(s: &str) -> bool {
+    let mut chars = s.trim_start_matches(|c: char| c.is_ascii_digit()).chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.next().is_none_or(|c| c.is_ascii_lowercase())
+        && chars.next().is_none()
+}
+
+/// The head, count, sign and magnitude of a mixture component ending with a
+/// magnitude-first charge.
+type SplitCharge<'a> = (&'a str, &'a str, char, &'a str);
+
+/// Splits the provided mixture component ending with a charge written in the
+/// provided magnitude-first notation into its head, the count preceding the
+/// charge, the sign and the magnitude, or returns `None` if the component
+/// does not end with such a charge.
+///
+/// # Errors
+///
+/// * If the digits preceding the charge sign may be read both as a count and as
+///   a count followed by a magnitude, as in `SO42-`.
+fn split_charge(
+    component: &str,
+    notation: ChargeNotation,
+) -> Result<Option<SplitCharge<'_>>, ParserError> {
+    // ChEBI-style charges, such as `SO4(2-)`, are always magnitudes.
+    if let Some(body) = component.strip_suffix(')') {
+        let Some(sign) = body.chars().next_back().filter(|c| is_sign(*c)) else {
+            return Ok(None);
+        };
+        let body = &body[..body.len() - sign.len_utf8()];
+        let digits = body.trim_end_matches(|c: char| c.is_ascii_digit());
+        let magnitude = &body[digits.len()..];
+        return Ok(digits.strip_suffix('(').map(|head| (head, "", sign, magnitude)));
+    }
+    if notation != ChargeNotation::MagnitudeFirst {
+        return Ok(None);
+    }
+
+    let Some(sign) = component.chars().next_back().filter(|c| is_sign(*c)) else {
+        return Ok(None);
+    };
+    let body = &component[..component.len() - sign.len_utf8()];
+    let head = body.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = &body[head.len()..];
+    let Some(length) = digits.len().checked_sub(1) else {
+        return Ok(None);
+    };
+    let (count, magnitude) = digits.split_at(length);
+    if matches!(magnitude, "0" | "1") || count == "1" || count.starts_with('0') {
+        return Ok(None);
+    }
+    if count.is_empty() {
+        return Ok((head.ends_with(']') || is_lone_element(head))
+            .then_some((head, count, sign, magnitude)));
+    }
+    if head.ends_with([']', ')']) || is_lone_element(head) {
+        return Ok(Some((head, count, sign, magnitude)));
+    }
+    Err(ParserError::UnprocessableNumber)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_sign_first() {
+        assert_eq!(to_sign_first("Fe2+", ChargeNotation::MagnitudeFirst), Ok(Some("Fe+2".into())));
+        assert_eq!(
+            to_sign_first("SO4(2-)", ChargeNotation::MagnitudeFirst),
+            Ok(Some("SO4-2".into()))
+        );
+        assert_eq!(
+            to_sign_first("NH4(+)", ChargeNotation::MagnitudeFirst),
+            Ok(Some("NH4+".into()))
+        );
+        assert_eq!(
+            to_sign_first("[Fe(CN)6]4-", ChargeNotation::MagnitudeFirst),
+            Ok(Some("[Fe(CN)6]-4".into()))
+        );
+        assert_eq!(
+            to_sign_first("2Fe3+.3SO4(2-)", ChargeNotation::MagnitudeFirst),
+            Ok(Some("2Fe+3.3SO4-2".into()))
+        );
+        assert_eq!(
+            to_sign_first("Hg22+", ChargeNotation::MagnitudeFirst),
+            Ok(Some("Hg2+2".into()))
+        );
+        assert_eq!(
+            to_sign_first("[Fe(CN)6]32-", ChargeNotation::MagnitudeFirst),
+            Ok(Some("[Fe(CN)6]3-2".into()))
+        );
+        assert_eq!(to_sign_first("NH4+", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("Fe+2", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("C6H12O6", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("Ca(OH)2", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("CH3(OH)2", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("C12H22O11+", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("C27H43O12-", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("C60+", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(to_sign_first("Fe0+", ChargeNotation::MagnitudeFirst), Ok(None));
+        assert_eq!(
+            to_sign_first("SO42-", ChargeNotation::MagnitudeFirst),
+            Err(ParserError::UnprocessableNumber)
+        );
+        assert_eq!(
+            to_sign_first("Na+.C6H5O73-", ChargeNotation::MagnitudeFirst),
+            Err(ParserError::UnprocessableNumber)
+        );
+    }
+
+    #[test]
+    fn test_bracketed_to_sign_first() {
+        let bracketed = |s| to_sign_first(s, ChargeNotation::Bracketed);
+        assert_eq!(bracketed("SO4(2-)"), Ok(Some("SO4-2".into())));
+        assert_eq!(bracketed("2Na(+).SO4(2-)"), Ok(Some("2Na+.SO4-2".into())));
+        assert_eq!(bracketed("Fe2+"), Ok(None));
+        assert_eq!(bracketed("SO42-"), Ok(None));
+        assert_eq!(bracketed("C12H22O11+"), Ok(None));
+    }
+}
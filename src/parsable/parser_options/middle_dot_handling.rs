@@ -0,0 +1,118 @@
+//! Submodule providing how the middle dots of formulas, which may be either
+//! mixture separators as in `CuSO4·5H2O` or radicals as in `·CH3`, are read,
+//! and the rewriting of the middle dots read as separators into full stops.
+
+use alloc::string::String;
+
+use crate::{
+    BaselineMinus, BaselinePlus, Dot, Radical, SuperscriptDigit, SuperscriptMinus, SuperscriptPlus,
+    parsable::CharacterMarker,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// How the parser reads the middle dot `·` and the bullet operator `∙`,
+/// which are written both as mixture separators, as in `CuSO4·5H2O`, and as
+/// radicals, as in `·CH3`.
+///
+/// Whatever the handling, the full stop `.` is always a mixture separator
+/// and the bullet `•` is always a radical, so that these characters should
+/// be preferred when writing formulas.
+pub enum MiddleDotHandling {
+    /// A middle dot is a radical at the start or at the end of a unit, and a
+    /// mixture separator otherwise. The following rules are applied:
+    ///
+    /// 1. A middle dot at the start of the formula or of a mixture component,
+    ///    or following an opening bracket, as in `·CH3` or `(·CH2)`, is a
+    ///    radical.
+    /// 2. A middle dot at the end of the formula or of a mixture component, or
+    ///    preceding a closing bracket, a charge or another middle dot, as in
+    ///    `Cl·`, `(CH3·)2` or `CH4·+`, is a radical.
+    /// 3. Any other middle dot, as in `CuSO4·5H2O`, is a mixture separator.
+    #[default]
+    Contextual,
+    /// Middle dots are always radicals, so that `CH3·CH3` is read as a single
+    /// mixture component.
+    Radical,
+    /// Middle dots are always mixture separators.
+    Separator,
+}
+
+/// Returns whether the provided character is a middle dot, whose reading
+/// depends on the [`MiddleDotHandling`].
+fn is_middle_dot(c: char) -> bool {
+    matches!(c, '·' | '\u{2219}')
+}
+
+/// Returns whether a middle dot following the provided character is at the
+/// start of a unit.
+fn starts_unit(previous: Option<char>) -> bool {
+    previous.is_none_or(|c| Dot::matches(c) || Radical::matches(c) || matches!(c, '(' | '['))
+}
+
+/// Returns whether a middle dot preceding the provided character is at the
+/// end of a unit.
+fn ends_unit(next: Option<char>) -> bool {
+    next.is_none_or(|c| {
+        Dot::matches(c)
+            || is_middle_dot(c)
+            || matches!(c, ')' | ']')
+            || BaselinePlus::matches(c)
+            || BaselineMinus::matches(c)
+            || SuperscriptPlus::matches(c)
+            || SuperscriptMinus::matches(c)
+            || SuperscriptDigit::try_from(c).is_ok()
+    })
+}
+
+/// Returns the provided string with the middle dots read as mixture
+/// separators under the provided handling replaced by full stops, or `None`
+/// if no middle dot needs to be replaced.
+pub(super) fn to_full_stops(s: &str, handling: MiddleDotHandling) -> Option<String> {
+    if handling == MiddleDotHandling::Radical || !s.contains(is_middle_dot) {
+        return None;
+    }
+    let mut rewritten = String::with_capacity(s.len());
+    let mut changed = false;
+    let mut previous = None;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let separator = is_middle_dot(c)
+            && (handling == MiddleDotHandling::Separator
+                || !(starts_unit(previous) || ends_unit(chars.peek().copied())));
+        if separator {
+            rewritten.push('.');
+            changed = true;
+        } else {
+            rewritten.push(c);
+        }
+        previous = Some(c);
+    }
+    changed.then_some(rewritten)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_full_stops() {
+        let contextual = |s| to_full_stops(s, MiddleDotHandling::Contextual);
+        assert_eq!(contextual("CuSO4·5H2O").as_deref(), Some("CuSO4.5H2O"));
+        assert_eq!(contextual("CH3·CH3").as_deref(), Some("CH3.CH3"));
+        assert_eq!(contextual("Na+·Cl-").as_deref(), Some("Na+.Cl-"));
+        assert_eq!(contextual("H2O·∙OH").as_deref(), None);
+        assert_eq!(contextual("·CH3"), None);
+        assert_eq!(contextual("CH3·"), None);
+        assert_eq!(contextual("(CH3·)2"), None);
+        assert_eq!(contextual("[·CH2]"), None);
+        assert_eq!(contextual("CH4·+"), None);
+        assert_eq!(contextual("CH4·²⁺"), None);
+        assert_eq!(contextual("H2O.·OH"), None);
+        assert_eq!(contextual("H2O··"), None);
+        assert_eq!(contextual("CH3•CH3"), None);
+
+        let separator = |s| to_full_stops(s, MiddleDotHandling::Separator);
+        assert_eq!(separator("·CH3∙H2O").as_deref(), Some(".CH3.H2O"));
+        assert_eq!(to_full_stops("CuSO4·5H2O", MiddleDotHandling::Radical), None);
+    }
+}
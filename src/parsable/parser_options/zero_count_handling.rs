@@ -0,0 +1,119 @@
+//! Submodule providing how the parser handles the zero counts emitted by
+//! some programs as placeholders, as in `C0H4`, and the rewriting of the
+//! formulas containing them into formulas without zero counts.
+
+use alloc::{string::String, vec::Vec};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// How the parser handles counts of zero and counts with leading zeros, as
+/// in the `C0H4` or `C06H12O06` emitted by some programs.
+pub enum ZeroCountHandling {
+    /// Zero counts and leading zeros are rejected.
+    #[default]
+    Reject,
+    /// Elements and bracketed groups with a count of zero are dropped, as
+    /// in `CH4N0` read as `CH4`, and the leading zeros of the other counts
+    /// are ignored, as in `C06H12O06` read as `C6H12O6`. Both baseline and
+    /// subscript counts are normalized, while the superscript numbers of
+    /// isotopes and charges, the charge magnitudes and the mixture
+    /// multipliers are read as usual.
+    Drop,
+}
+
+/// Returns whether the provided character is a subscript digit.
+fn is_subscript_digit(c: char) -> bool {
+    ('₀'..='₉').contains(&c)
+}
+
+/// Returns whether the provided character is the zero of the baseline or
+/// subscript digits.
+fn is_zero(c: char) -> bool {
+    c == '0' || c == '₀'
+}
+
+/// Returns the provided string with its elements and bracketed groups
+/// counted zero times removed and the leading zeros of its other counts
+/// stripped, or `None` if no count needs to be rewritten.
+pub(super) fn drop_zero_counts(s: &str) -> Option<String> {
+    let mut rewritten = String::with_capacity(s.len());
+    let mut changed = false;
+    // The offsets in the rewritten string of the open brackets.
+    let mut open_brackets: Vec<usize> = Vec::new();
+    // The offset in the rewritten string of the last closed group.
+    let mut last_group = None;
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let follows_unit = rewritten
+            .chars()
+            .next_back()
+            .is_some_and(|last| last.is_ascii_alphabetic() || matches!(last, ')' | ']'));
+        if follows_unit && (c.is_ascii_digit() || is_subscript_digit(c)) {
+            let subscript = is_subscript_digit(c);
+            let length = rest
+                .find(
+                    |c: char| {
+                        if subscript { !is_subscript_digit(c) } else { !c.is_ascii_digit() }
+                    },
+                )
+                .unwrap_or(rest.len());
+            let (digits, tail) = rest.split_at(length);
+            let significant = digits.trim_start_matches(is_zero);
+            if significant.is_empty() {
+                drop_unit(&mut rewritten, last_group);
+            } else {
+                rewritten.push_str(significant);
+            }
+            changed |= significant.len() != digits.len();
+            last_group = None;
+            rest = tail;
+            continue;
+        }
+        match c {
+            '(' | '[' => open_brackets.push(rewritten.len()),
+            ')' | ']' => last_group = open_brackets.pop(),
+            _ => {}
+        }
+        if !matches!(c, ')' | ']') {
+            last_group = None;
+        }
+        rewritten.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    changed.then_some(rewritten)
+}
+
+/// Removes from the end of the provided string the unit counted zero
+/// times, which is either the bracketed group starting at the provided
+/// offset or the element symbol preceding the count.
+fn drop_unit(rewritten: &mut String, last_group: Option<usize>) {
+    if let Some(start) = last_group {
+        rewritten.truncate(start);
+        return;
+    }
+    let lowercase =
+        rewritten.len() - rewritten.trim_end_matches(|c: char| c.is_ascii_lowercase()).len();
+    rewritten.truncate(rewritten.len() - lowercase);
+    if rewritten.ends_with(|c: char| c.is_ascii_uppercase()) {
+        rewritten.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_zero_counts() {
+        assert_eq!(drop_zero_counts("C0H4").as_deref(), Some("H4"));
+        assert_eq!(drop_zero_counts("CH4N0").as_deref(), Some("CH4"));
+        assert_eq!(drop_zero_counts("C06H12O06").as_deref(), Some("C6H12O6"));
+        assert_eq!(drop_zero_counts("C₆H₁₂Cl₀O₆").as_deref(), Some("C₆H₁₂O₆"));
+        assert_eq!(drop_zero_counts("CH3(CH2)0CH3").as_deref(), Some("CH3CH3"));
+        assert_eq!(drop_zero_counts("[Fe(CN)6]0K4").as_deref(), Some("K4"));
+        assert_eq!(drop_zero_counts("Na(Cl0)2").as_deref(), Some("Na()2"));
+        assert_eq!(drop_zero_counts("C10H20"), None);
+        assert_eq!(drop_zero_counts("[13C]H4"), None);
+        assert_eq!(drop_zero_counts("2H2O.Fe+2"), None);
+        assert_eq!(drop_zero_counts("H2¹⁸O"), None);
+    }
+}
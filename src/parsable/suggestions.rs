@@ -0,0 +1,149 @@
+//! Submodule providing suggestions of valid formulas close to a formula
+//! which could not be parsed, as shown in did-you-mean messages.
+
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+
+use elements_rs::Isotope;
+
+use crate::{ChargeLike, ChemicalFormula, CountLike};
+
+/// Maximal number of edits separating a suggestion from the input.
+const MAX_EDITS: usize = 2;
+
+/// Maximal length, in characters, of the formulas for which [`suggest`]
+/// considers suggestions two edits away.
+///
+/// The number of candidates two edits away grows with the square of the
+/// length of the formula, and each of them is parsed, so that longer
+/// formulas only receive the suggestions a single edit away.
+pub const MAX_TWO_EDITS_LENGTH: usize = 32;
+
+/// Returns the valid chemical formulas which are at most two edits away from
+/// the provided formula, by increasing number of edits and then
+/// alphabetically.
+///
+/// Formulas longer than [`MAX_TWO_EDITS_LENGTH`] characters only receive
+/// the suggestions a single edit away, so that the number of candidates
+/// parsed grows linearly with the length of the formula.
+///
+/// The edits considered are the ones correcting the most common typing
+/// mistakes:
+///
+/// * changing the case of a letter, as in `CL` instead of `Cl`;
+/// * swapping a zero and the letter `O`, as in `C02` instead of `CO2`;
+/// * removing a bracket or swapping its kind, as in `Ca(OH]2`;
+/// * inserting a closing bracket, as in `Ca(OH2`.
+///
+/// Whether a candidate is valid is decided by the parser of
+/// [`ChemicalFormula`], so that only formulas which can be parsed are
+/// suggested. No suggestion is returned for valid formulas.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// assert_eq!(suggest::<u32, i32>("NaCL"), ["NaCl"]);
+/// assert_eq!(suggest::<u32, i32>("C02"), ["CO2", "Co2"]);
+/// assert!(suggest::<u32, i32>("Ca(OH2").contains(&"Ca(OH)2".to_string()));
+/// assert!(suggest::<u32, i32>("NaCl").is_empty());
+/// ```
+#[must_use]
+pub fn suggest<Count, Charge>(formula: &str) -> Vec<String>
+where
+    Count: CountLike,
+    Charge: ChargeLike + TryFrom<Count>,
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+{
+    let is_valid = |candidate: &str| ChemicalFormula::<Count, Charge>::from_str(candidate).is_ok();
+    if is_valid(formula) {
+        return Vec::new();
+    }
+
+    let mut visited = BTreeSet::from([formula.to_string()]);
+    let mut frontier = alloc::vec![formula.to_string()];
+    let mut suggestions = Vec::new();
+    let max_edits = if formula.chars().count() > MAX_TWO_EDITS_LENGTH { 1 } else { MAX_EDITS };
+    for _ in 0..max_edits {
+        let mut next = Vec::new();
+        for candidate in &frontier {
+            for edited in edits(candidate) {
+                if visited.insert(edited.clone()) {
+                    next.push(edited);
+                }
+            }
+        }
+        let mut valid: Vec<String> =
+            next.iter().filter(|candidate| is_valid(candidate)).cloned().collect();
+        valid.sort_unstable();
+        suggestions.extend(valid);
+        frontier = next;
+    }
+    suggestions
+}
+
+/// Returns the formulas obtained by applying a single edit to the provided
+/// formula.
+fn edits(formula: &str) -> Vec<String> {
+    let mut edits = Vec::new();
+    for (index, c) in formula.char_indices() {
+        let (before, after) = (&formula[..index], &formula[index + c.len_utf8()..]);
+        let mut substitute = |replacement: Option<char>| {
+            let mut edited = String::with_capacity(formula.len() + 1);
+            edited.push_str(before);
+            edited.extend(replacement);
+            edited.push_str(after);
+            edits.push(edited);
+        };
+        if c.is_ascii_uppercase() {
+            substitute(Some(c.to_ascii_lowercase()));
+        } else if c.is_ascii_lowercase() {
+            substitute(Some(c.to_ascii_uppercase()));
+        }
+        match c {
+            '0' => substitute(Some('O')),
+            'O' | 'o' => substitute(Some('0')),
+            '(' => substitute(Some('[')),
+            '[' => substitute(Some('(')),
+            ')' => substitute(Some(']')),
+            ']' => substitute(Some(')')),
+            _ => {}
+        }
+        if matches!(c, '(' | '[' | ')' | ']') {
+            substitute(None);
+        }
+    }
+
+    for (opening, closing) in [('(', ')'), ('[', ']')] {
+        if !formula.contains(opening) {
+            continue;
+        }
+        for index in formula.char_indices().map(|(index, _)| index).skip(1).chain([formula.len()]) {
+            let mut edited = String::with_capacity(formula.len() + 1);
+            edited.push_str(&formula[..index]);
+            edited.push(closing);
+            edited.push_str(&formula[index..]);
+            edits.push(edited);
+        }
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits() {
+        let edits = edits("C(O");
+        for expected in ["c(O", "C[O", "CO", "C(o", "C(0", "C)(O", "C()O", "C(O)"] {
+            assert!(edits.iter().any(|edit| edit == expected), "{expected}");
+        }
+        assert_eq!(edits.len(), 8);
+    }
+}
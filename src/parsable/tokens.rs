@@ -9,10 +9,12 @@ mod subtokens;
 pub use subtokens::*;
 mod inchi_tokens;
 pub use inchi_tokens::InchiToken;
+mod spanned_tokens;
+pub use spanned_tokens::{Spanned, SpannedTokens, tokenize};
 
 use crate::{
-    ChargedMolecularFormulaMetadata, ChemicalFormula, ChemicalTree, SequenceNode, TokenLike,
-    display_charge, display_isotope, errors::ParserError, parsable::ParsableMolecularTree,
+    ChargedMolecularFormulaMetadata, ChemicalFormula, ChemicalTree, TokenLike, display_charge,
+    display_isotope, errors::ParserError, parsable::ParsableMolecularTree,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -82,6 +84,10 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Debug + Eq + Copy> TokenLi
             _ => None,
         }
     }
+
+    fn wraps_unit(&self) -> bool {
+        matches!(self, Token::Charge(_) | Token::Radical)
+    }
 }
 
 impl<Count: CountLike, Charge: ChargeLike, Extension: Debug + Eq + Copy> From<HydrogenIsotope>
@@ -93,12 +99,27 @@ impl<Count: CountLike, Charge: ChargeLike, Extension: Debug + Eq + Copy> From<Hy
 }
 
 /// Iterator over the `Token`s found in a provided string.
-pub(crate) struct Tokens<I: Iterator<Item = char>, M: ChargedMolecularFormulaMetadata, Extension>
+///
+/// See [`tokenize`] for an entry point yielding the tokens together with
+/// the byte ranges they were read from.
+pub struct Tokens<I: Iterator<Item = char>, M: ChargedMolecularFormulaMetadata, Extension>
 where
     Extension: TryFrom<char> + Debug,
 {
-    /// A peekable iterator over the allowed characters.
-    stream: core::iter::Peekable<SubTokens<I, M, Extension>>,
+    /// The iterator over the subtokens.
+    stream: SubTokens<I, M, Extension>,
+}
+
+impl<I: Iterator<Item = char>, M: ChargedMolecularFormulaMetadata, Extension>
+    Tokens<I, M, Extension>
+where
+    Extension: TryFrom<char> + Debug,
+{
+    /// Returns the length in bytes of the character read ahead of the last
+    /// token, if any.
+    pub(crate) fn lookahead_len(&mut self) -> usize {
+        self.stream.lookahead_len()
+    }
 }
 
 impl<I: Iterator<Item = char>, M: ChargedMolecularFormulaMetadata, Extension> From<Peekable<I>>
@@ -107,7 +128,7 @@ where
     Extension: TryFrom<char> + Debug,
 {
     fn from(iter: Peekable<I>) -> Self {
-        Self { stream: SubTokens::from(iter).peekable() }
+        Self { stream: SubTokens::from(iter) }
     }
 }
 
@@ -172,19 +193,6 @@ where
     where
         I: Iterator<Item = char>;
 
-    #[inline]
-    fn empty() -> Self {
-        ChemicalTree::Sequence(SequenceNode::empty())
-    }
-
-    #[inline]
-    fn is_empty(&self) -> bool {
-        match self {
-            ChemicalTree::Sequence(sequence) => sequence.is_empty(),
-            _ => false,
-        }
-    }
-
     #[inline]
     fn element(self, element: elements_rs::Element) -> Self {
         self.push(Self::Element(element))
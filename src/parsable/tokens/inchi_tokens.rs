@@ -60,6 +60,10 @@ impl<Count: CountLike> TokenLike for InchiToken<Count> {
             _ => None,
         }
     }
+
+    fn wraps_unit(&self) -> bool {
+        false
+    }
 }
 
 pub(crate) struct InchiTokens<I: Iterator<Item = char>, Count> {
@@ -106,14 +110,6 @@ impl<Count: CountLike> ParsableMolecularTree<Count> for SequenceNode<InChITree<C
     where
         I: Iterator<Item = char>;
 
-    fn empty() -> Self {
-        SequenceNode::empty()
-    }
-
-    fn is_empty(&self) -> bool {
-        SequenceNode::is_empty(self)
-    }
-
     fn element(mut self, element: Element) -> Self {
         self.push(element.into());
         self
@@ -0,0 +1,183 @@
+//! Submodule providing the public entry point of the tokenizer, which yields
+//! the tokens of a formula together with the byte ranges they were read
+//! from, as needed by syntax highlighters and linters of formula inputs.
+
+use alloc::sync::Arc;
+use core::{
+    fmt::Debug,
+    ops::Range,
+    str::Chars,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use elements_rs::Isotope;
+
+use crate::{ChargeLike, ChemicalFormula, CountLike, Empty, Token, Tokens, errors::ParserError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Value read from a formula, with the byte range of the characters it was
+/// read from.
+pub struct Spanned<T> {
+    /// The value read.
+    value: T,
+    /// The byte range of the characters the value was read from.
+    span: Range<usize>,
+}
+
+impl<T> Spanned<T> {
+    /// Creates a spanned value from the value read and its byte range.
+    pub(crate) fn new(value: T, span: Range<usize>) -> Self {
+        Self { value, span }
+    }
+
+    /// Returns the value read.
+    #[must_use]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes the spanned value and returns the value read.
+    #[must_use]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Returns the byte range of the characters the value was read from.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Iterator over the characters of a string, counting the bytes read.
+struct CountedChars<'a> {
+    /// The characters of the string.
+    chars: Chars<'a>,
+    /// The number of bytes read, shared with the spanned tokens. The counter
+    /// is atomic only so that the spanned tokens are `Send` and `Sync`, as it
+    /// is never accessed concurrently.
+    consumed: Arc<AtomicUsize>,
+}
+
+impl Iterator for CountedChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.consumed.fetch_add(c.len_utf8(), Ordering::Relaxed);
+        Some(c)
+    }
+}
+
+/// Iterator over the tokens of a formula with the byte ranges they were read
+/// from, as returned by [`tokenize`].
+///
+/// Formulas allowing extensions, such as the residuals of
+/// [`ResidualFormula`](crate::ResidualFormula), are tokenized by providing
+/// the extension type, as in `SpannedTokens::<u32, i32, Residual>::new`.
+///
+/// The iteration stops after the first error, since the tokens following an
+/// invalid character cannot be reliably delimited.
+pub struct SpannedTokens<'a, Count: CountLike, Charge: ChargeLike, Extension = Empty>
+where
+    Charge: TryFrom<Count>,
+    Extension: TryFrom<char> + Debug,
+{
+    /// The tokens of the formula.
+    tokens: Tokens<CountedChars<'a>, ChemicalFormula<Count, Charge>, Extension>,
+    /// The number of bytes read by the tokens, including lookahead.
+    consumed: Arc<AtomicUsize>,
+    /// The byte offset where the next token starts.
+    start: usize,
+    /// Whether an error was returned.
+    failed: bool,
+}
+
+impl<'a, Count: CountLike, Charge: ChargeLike, Extension>
+    SpannedTokens<'a, Count, Charge, Extension>
+where
+    Charge: TryFrom<Count>,
+    Extension: TryFrom<char> + Debug,
+{
+    /// Creates the iterator over the tokens of the provided formula.
+    #[must_use]
+    pub fn new(formula: &'a str) -> Self {
+        let consumed = Arc::new(AtomicUsize::new(0));
+        let chars = CountedChars { chars: formula.chars(), consumed: Arc::clone(&consumed) };
+        Self { tokens: Tokens::from(chars.peekable()), consumed, start: 0, failed: false }
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike, Extension: Debug + Copy + Eq> Iterator
+    for SpannedTokens<'_, Count, Charge, Extension>
+where
+    Isotope: TryFrom<(elements_rs::Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+    Extension: TryFrom<char>,
+{
+    type Item = Result<Spanned<Token<Count, Charge, Extension>>, Spanned<ParserError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let token = self.tokens.next()?;
+        // The lookahead is peeked first, as peeking may read a character.
+        let lookahead = self.tokens.lookahead_len();
+        let end = self.consumed.load(Ordering::Relaxed) - lookahead;
+        let span = self.start..end;
+        self.start = end;
+        Some(match token {
+            Ok(value) => Ok(Spanned::new(value, span)),
+            Err(value) => {
+                self.failed = true;
+                Err(Spanned::new(value, span))
+            }
+        })
+    }
+}
+
+/// Returns an iterator over the tokens of the provided chemical formula,
+/// with the byte ranges they were read from.
+///
+/// The tokens are the ones read by the parser of
+/// [`ChemicalFormula`](crate::ChemicalFormula), so that they can be reused
+/// to highlight or lint formula inputs consistently with the parser. Note
+/// that a sequence of valid tokens does not necessarily form a valid
+/// formula, for instance when its brackets are unbalanced.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::{errors::ParserError, prelude::*};
+///
+/// let tokens: Vec<_> = tokenize::<u32, i32>("Ca(OH)₂")
+///     .map(|token| {
+///         let token = token.unwrap();
+///         (token.value().to_string(), token.span())
+///     })
+///     .collect();
+/// assert_eq!(
+///     tokens,
+///     [
+///         ("Ca".to_string(), 0..2),
+///         ("(".to_string(), 2..3),
+///         ("O".to_string(), 3..4),
+///         ("H".to_string(), 4..5),
+///         (")".to_string(), 5..6),
+///         ("2".to_string(), 6..9),
+///     ]
+/// );
+///
+/// let error = tokenize::<u32, i32>("H2O?").find_map(Result::err).unwrap();
+/// assert_eq!(error.value(), &ParserError::UnexpectedCharacter('?'));
+/// assert_eq!(error.span(), 3..4);
+/// ```
+#[must_use]
+pub fn tokenize<Count, Charge>(formula: &str) -> SpannedTokens<'_, Count, Charge>
+where
+    Count: CountLike,
+    Charge: ChargeLike + TryFrom<Count>,
+{
+    SpannedTokens::new(formula)
+}
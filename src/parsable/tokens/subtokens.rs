@@ -7,10 +7,12 @@ use core::{
 };
 
 use elements_rs::{Element, isotopes::HydrogenIsotope};
-use num_traits::{CheckedAdd, CheckedNeg, ConstOne, One, Signed};
+use num_traits::{CheckedNeg, Signed};
 
+mod compatibility;
 mod complex;
 pub use complex::Complex;
+mod names;
 mod typesetting;
 pub use typesetting::{Baseline, Subscript, Superscript, TypeSetting};
 
@@ -22,8 +24,33 @@ use crate::{
 };
 
 /// Marker trait for typesettings that support charge notation.
-pub trait ChargeLike: NumberLike + Signed + CheckedNeg + Into<i32> + TryFrom<i64> {}
-impl<T> ChargeLike for T where T: NumberLike + Signed + CheckedNeg + Into<i32> + TryFrom<i64> {}
+pub trait ChargeLike: NumberLike + Signed + CheckedNeg + TryFrom<i64> {}
+impl<T> ChargeLike for T where T: NumberLike + Signed + CheckedNeg + TryFrom<i64> {}
+
+/// Returns the charge with the provided magnitude and sign.
+///
+/// Since the magnitude is parsed separately from the sign, the most negative
+/// value of the charge type is accepted even though its magnitude exceeds the
+/// largest positive value.
+///
+/// # Errors
+///
+/// * If the charge cannot be represented by the charge type, with an error
+///   matching its sign.
+pub(crate) fn signed_charge<Charge: ChargeLike>(
+    magnitude: u64,
+    positive: bool,
+) -> Result<Charge, NumericError> {
+    let overflow =
+        if positive { NumericError::PositiveOverflow } else { NumericError::NegativeOverflow };
+    let charge = if positive {
+        i64::try_from(magnitude).ok()
+    } else {
+        0_i64.checked_sub_unsigned(magnitude)
+    }
+    .ok_or(overflow)?;
+    Charge::try_from(charge).map_err(|_| overflow)
+}
 
 mod brackets;
 mod digits;
@@ -155,43 +182,60 @@ impl<I: Iterator<Item = char>, M, Extension> From<Peekable<I>> for SubTokens<I,
 impl<I: Iterator<Item = char>, M: ChargedMolecularFormulaMetadata, Extension>
     SubTokens<I, M, Extension>
 {
+    /// Returns the length in bytes of the character read ahead of the last
+    /// subtoken, if any.
+    pub(crate) fn lookahead_len(&mut self) -> usize {
+        self.stream.peek().map_or(0, |c| c.len_utf8())
+    }
+
     /// Parses a charge.
     fn parse_charge<CS: SignMarker>(&mut self) -> Result<M::Charge, NumericError>
     where
-        M::Charge: From<CS::Digit>,
+        u64: From<CS::Digit>,
     {
         // There might be one of more signs in some notations.
-        let mut sign_count: M::Charge = <M::Charge as ConstOne>::ONE;
+        let mut magnitude: u64 = 1;
         while self.stream.peek().copied().is_some_and(|c| CS::matches(c)) {
-            sign_count = sign_count
-                .checked_add(&<M::Charge as ConstOne>::ONE)
-                .ok_or(NumericError::PositiveOverflow)?;
+            magnitude = magnitude.checked_add(1).ok_or(if CS::POSITIVE {
+                NumericError::PositiveOverflow
+            } else {
+                NumericError::NegativeOverflow
+            })?;
             self.stream.next();
         }
 
-        // If the sign count is, in absolute value, equal to one, it may be followed
-        // by an optional number.
-        if sign_count.abs().is_one()
-            && let Some(count) = try_fold_number::<M::Charge, CS::Digit, _>(&mut self.stream)
+        // If there is a single sign, it may be followed by an optional number.
+        if magnitude == 1
+            && let Some(count) = try_fold_number::<u64, CS::Digit, _>(&mut self.stream)
         {
-            sign_count = count?;
-        }
-
-        // We adjust the sign of the charge according to the sign marker.
-        if !CS::POSITIVE {
-            sign_count = sign_count.checked_neg().ok_or(NumericError::NegativeOverflow)?;
+            magnitude = count.map_err(|error| {
+                if error == NumericError::PositiveOverflow && !CS::POSITIVE {
+                    NumericError::NegativeOverflow
+                } else {
+                    error
+                }
+            })?;
         }
 
-        Ok(sign_count)
+        signed_charge(magnitude, CS::POSITIVE)
     }
 
     fn parse_charge_token<CS: SignMarker>(
         &mut self,
     ) -> Result<SubToken<M::Count, M::Charge, Extension>, ParserError>
     where
-        M::Charge: From<CS::Digit>,
+        u64: From<CS::Digit>,
     {
-        let charge = self.parse_charge::<CS>()?;
+        let charge = match self.parse_charge::<CS>() {
+            // A sign followed by a lone zero, such as `+0`, is an explicit zero
+            // charge rather than a malformed number.
+            Err(NumericError::LeadingZero)
+                if self.stream.peek().copied().is_none_or(|c| CS::Digit::try_from(c).is_err()) =>
+            {
+                return Err(ParserError::ZeroCharge);
+            }
+            charge => charge?,
+        };
         // Charges cannot be immediately followed by another charge or digit.
         if self.parse_any_illegal_charge_successor() {
             return Err(ParserError::UnexpectedCharacter(self.stream.next().unwrap()));
@@ -199,6 +243,103 @@ impl<I: Iterator<Item = char>, M: ChargedMolecularFormulaMetadata, Extension>
         Ok(SubToken::Charge(charge))
     }
 
+    #[cfg(feature = "names")]
+    #[allow(clippy::type_complexity)]
+    /// Parses a full element name starting with the provided character, such
+    /// as `Sodium` or `deuterium`.
+    ///
+    /// Since element symbols are at most two letters long, runs of at most
+    /// two letters are handled as symbols, while longer runs of lowercase
+    /// letters must spell out a name.
+    fn parse_element_name(
+        &mut self,
+        first: char,
+    ) -> Option<Result<SubToken<M::Count, M::Charge, Extension>, ParserError>> {
+        if !first.is_ascii_alphabetic()
+            || !self
+                .stream
+                .peek()
+                .copied()
+                .map(compatibility::compatibility_fold)
+                .is_some_and(|c| c.is_ascii_lowercase())
+        {
+            return None;
+        }
+
+        let mut name = alloc::string::String::from(first);
+        while let Some(c) = self
+            .stream
+            .peek()
+            .copied()
+            .map(compatibility::compatibility_fold)
+            .filter(char::is_ascii_lowercase)
+        {
+            name.push(c);
+            self.stream.next();
+        }
+
+        if let Ok(complex) = Complex::try_from(name.as_str()) {
+            return Some(Ok(complex.into()));
+        }
+
+        if let [first, second] = name.chars().collect::<alloc::vec::Vec<_>>()[..] {
+            if let Ok(element) = Element::try_from([first, second]) {
+                return Some(Ok(element.into()));
+            }
+            let unexpected = if Element::try_from(first).is_ok() { second } else { first };
+            return Some(Err(ParserError::UnexpectedCharacter(unexpected)));
+        }
+
+        Some(names::sub_token_from_name(&name).ok_or(ParserError::UnexpectedCharacter(first)))
+    }
+
+    #[allow(clippy::type_complexity)]
+    /// Parses a complex group whose symbol starts with the provided
+    /// character, such as `Me` or `Fmoc`, returning `None` if no symbol
+    /// starts with it and the following character.
+    ///
+    /// The characters are consumed for as long as they may still spell a
+    /// symbol. Since `Fm` is the only element symbol starting a complex
+    /// symbol, two characters spelling no complex are read as an element.
+    fn parse_complex(
+        &mut self,
+        first: char,
+    ) -> Option<Result<SubToken<M::Count, M::Charge, Extension>, ParserError>> {
+        let mut symbol = [first; Complex::MAX_SYMBOL_LENGTH];
+        let mut length = 1;
+        while length < Complex::MAX_SYMBOL_LENGTH
+            && let Some(c) = self.stream.peek().copied().map(compatibility::compatibility_fold)
+        {
+            symbol[length] = c;
+            if !Complex::any_symbol_starts_with(&symbol[..=length]) {
+                break;
+            }
+            self.stream.next();
+            length += 1;
+        }
+
+        if length == 1 {
+            return None;
+        }
+        if let Some(complex) = Complex::from_symbol(&symbol[..length]) {
+            return Some(Ok(complex.into()));
+        }
+        if length == 2 {
+            let [first, second, ..] = symbol;
+            return Some(match Element::try_from([first, second]) {
+                Ok(element) => Ok(element.into()),
+                Err(_) if Element::try_from(first).is_ok() => {
+                    Err(ParserError::UnexpectedCharacter(second))
+                }
+                Err(_) => Err(ParserError::UnexpectedCharacter(first)),
+            });
+        }
+        Some(Err(self
+            .stream
+            .next()
+            .map_or(ParserError::UnexpectedEndOfInput, ParserError::UnexpectedCharacter)))
+    }
+
     /// Returns whether any charge or superscript digit can be parsed next.
     fn parse_any_illegal_charge_successor(&mut self) -> bool {
         if let Some(c) = self.stream.peek().copied() {
@@ -222,6 +363,12 @@ where
 
     #[allow(clippy::too_many_lines)]
     fn next(&mut self) -> Option<Self::Item> {
+        // Combining marks carry no chemical meaning, and are dropped as in
+        // the NFKD normalization.
+        while self.stream.peek().copied().is_some_and(compatibility::is_combining_mark) {
+            self.stream.next();
+        }
+
         if let Some(count) = try_fold_number::<M::Count, BaselineDigit, _>(&mut self.stream) {
             // If we have found a baseline number, we return it as a count token.
             // But first, we check that it is not further followed by a subscript digit,
@@ -242,58 +389,64 @@ where
 
             return Some(count.map(|c| InchiToken::Count(c).into()).map_err(Into::into));
         }
-        if let Some(count) = try_fold_number::<M::Count, SuperscriptDigit, _>(&mut self.stream) {
-            let count = match count {
-                Ok(c) => c,
-                Err(e) => return Some(Err(e.into())),
-            };
-            return Some(match self.stream.peek().copied() {
-                Some(c) if SuperscriptMinus::matches(c) => {
-                    self.stream.next();
-
-                    // We check that no further charge or digit follows.
-                    if self.parse_any_illegal_charge_successor() {
-                        return Some(Err(ParserError::UnexpectedCharacter(
-                            self.stream.next().unwrap(),
-                        )));
-                    }
-
-                    let mut padded_count: i64 = count.into();
-                    // Should not be possible to overflow here.
-                    padded_count = -padded_count;
-                    M::Charge::try_from(padded_count)
-                        .map_err(|_| NumericError::NegativeOverflow.into())
-                        .map(|ch| SubToken::Charge(ch))
+        // Superscript numbers are folded into the widest count type, since
+        // whether they are a mass number or the magnitude of a charge only
+        // becomes known once the following character is read.
+        if let Some(number) = try_fold_number::<u64, SuperscriptDigit, _>(&mut self.stream) {
+            let superscript_sign = self.stream.peek().copied().and_then(|c| {
+                if SuperscriptMinus::matches(c) {
+                    Some(false)
+                } else if SuperscriptPlus::matches(c) {
+                    Some(true)
+                } else {
+                    None
                 }
-                Some(c) if SuperscriptPlus::matches(c) => {
-                    self.stream.next();
-
-                    // We check that no further charge or digit follows.
-                    if self.parse_any_illegal_charge_successor() {
-                        return Some(Err(ParserError::UnexpectedCharacter(
-                            self.stream.next().unwrap(),
-                        )));
-                    }
-
-                    M::Charge::try_from(count)
-                        .map_err(|_| NumericError::PositiveOverflow.into())
-                        .map(|ch| SubToken::Charge(ch))
-                }
-                _ => Ok(SubToken::SuperscriptDigit(count)),
             });
+            let number = match (number, superscript_sign) {
+                (Ok(number), _) => number,
+                // A lone superscript zero followed by a sign, such as `⁰⁺`, is an
+                // explicit zero charge.
+                (Err(NumericError::LeadingZero), Some(_)) => {
+                    return Some(Err(ParserError::ZeroCharge));
+                }
+                (Err(NumericError::PositiveOverflow), Some(false)) => {
+                    return Some(Err(NumericError::NegativeOverflow.into()));
+                }
+                (Err(e), _) => return Some(Err(e.into())),
+            };
+            let Some(positive) = superscript_sign else {
+                return Some(
+                    M::Count::try_from(number)
+                        .map(SubToken::SuperscriptDigit)
+                        .map_err(|_| NumericError::PositiveOverflow.into()),
+                );
+            };
+            self.stream.next();
+
+            // We check that no further charge or digit follows.
+            if self.parse_any_illegal_charge_successor() {
+                return Some(Err(ParserError::UnexpectedCharacter(self.stream.next().unwrap())));
+            }
+
+            return Some(signed_charge(number, positive).map(SubToken::Charge).map_err(Into::into));
         }
 
-        let next_char = self.stream.next()?;
+        let next_char = compatibility::compatibility_fold(self.stream.next()?);
 
-        if let Some(peaked) = self.stream.peek().copied() {
-            if let Ok(complex) = Complex::try_from([next_char, peaked]) {
-                self.stream.next();
-                return Some(Ok(complex.into()));
-            }
-            if let Ok(element) = Element::try_from([next_char, peaked]) {
-                self.stream.next();
-                return Some(Ok(element.into()));
-            }
+        #[cfg(feature = "names")]
+        if let Some(token) = self.parse_element_name(next_char) {
+            return Some(token);
+        }
+
+        if let Some(token) = self.parse_complex(next_char) {
+            return Some(token);
+        }
+
+        if let Some(peaked) = self.stream.peek().copied().map(compatibility::compatibility_fold)
+            && let Ok(element) = Element::try_from([next_char, peaked])
+        {
+            self.stream.next();
+            return Some(Ok(element.into()));
         }
 
         if let Ok(element) = Element::try_from(next_char) {
@@ -0,0 +1,65 @@
+//! Submodule normalizing compatibility forms of characters, such as the
+//! full-width forms commonly found in formulas copied from PDFs, into
+//! their canonical counterparts.
+
+/// Offset between the full-width forms and their ASCII counterparts.
+const FULL_WIDTH_OFFSET: u32 = 0xFEE0;
+
+/// Returns the canonical form of the provided character, mapping the
+/// full-width ASCII variants (U+FF01 to U+FF5E) to ASCII, as the NFKC
+/// normalization would.
+pub(crate) fn compatibility_fold(c: char) -> char {
+    if ('\u{FF01}'..='\u{FF5E}').contains(&c) {
+        char::from_u32(u32::from(c) - FULL_WIDTH_OFFSET).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+/// Returns the value of the provided character if it is a compatibility form
+/// of a baseline digit, i.e. a full-width digit or a mathematical
+/// alphanumeric digit (bold, double-struck, sans-serif, monospace).
+pub(crate) fn compatibility_digit(c: char) -> Option<u32> {
+    match c {
+        '\u{FF10}'..='\u{FF19}' => Some(u32::from(c) - 0xFF10),
+        '\u{1D7CE}'..='\u{1D7FF}' => Some((u32::from(c) - 0x1D7CE) % 10),
+        _ => None,
+    }
+}
+
+/// Returns whether the provided character is a combining mark, which is
+/// dropped by the NFKD normalization followed by mark stripping.
+pub(crate) fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{20D0}'..='\u{20FF}' | '\u{FE20}'..='\u{FE2F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compatibility_fold() {
+        assert_eq!(compatibility_fold('Ｃ'), 'C');
+        assert_eq!(compatibility_fold('ｌ'), 'l');
+        assert_eq!(compatibility_fold('（'), '(');
+        assert_eq!(compatibility_fold('C'), 'C');
+        assert_eq!(compatibility_fold('₂'), '₂');
+    }
+
+    #[test]
+    fn test_compatibility_digit() {
+        assert_eq!(compatibility_digit('０'), Some(0));
+        assert_eq!(compatibility_digit('９'), Some(9));
+        assert_eq!(compatibility_digit('\u{1D7CE}'), Some(0));
+        assert_eq!(compatibility_digit('\u{1D7D9}'), Some(1));
+        assert_eq!(compatibility_digit('\u{1D7FF}'), Some(9));
+        assert_eq!(compatibility_digit('9'), None);
+    }
+
+    #[test]
+    fn test_is_combining_mark() {
+        assert!(is_combining_mark('\u{0301}'));
+        assert!(is_combining_mark('\u{20D7}'));
+        assert!(!is_combining_mark('a'));
+    }
+}
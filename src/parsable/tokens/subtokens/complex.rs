@@ -2,10 +2,23 @@
 
 use core::fmt::Display;
 
+use elements_rs::Element;
+
 #[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 /// Represents complex group fragments in molecular formulas.
+///
+/// The common ligands and protecting groups of organic synthesis, such as
+/// `Boc` or `Fmoc`, are provided by the `protecting-groups` feature, which is
+/// enabled by default. Since the variants depend on the enabled features, the
+/// enumeration is non exhaustive, and matches on it must provide a wildcard
+/// arm.
+///
+/// The acetyl (`Ac`) and tosyl (`Ts`) groups are deliberately not provided:
+/// their symbols are those of actinium and tennessine, so that reading them
+/// as groups would change the meaning of valid formulas such as `AcCl₃`.
 pub enum Complex {
     /// Methyl group (Me) - CH3
     Methyl,
@@ -19,13 +32,78 @@ pub enum Complex {
     Benzyl,
     /// Cyclohexyl group (Cy) - C6H11
     Cyclohexyl,
-    /// Cyclopentadienyl group (Cp) - `C5H5-`
+    /// Cyclopentadienyl group (Cp) - C5H5
     Cyclopentadienyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Isopropyl group (iPr) - C3H7
+    Isopropyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Tert-butyl group (tBu) - C4H9
+    TertButyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Benzoyl group (Bz) - C7H5O
+    Benzoyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Mesyl group (Ms) - CH3SO2
+    Mesyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Triflyl group (Tf) - CF3SO2
+    Triflyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Tert-butyloxycarbonyl group (Boc) - C5H9O2
+    TertButyloxycarbonyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Carboxybenzyl group (Cbz) - C8H7O2
+    Carboxybenzyl,
+    #[cfg(feature = "protecting-groups")]
+    /// Fluorenylmethyloxycarbonyl group (Fmoc) - C15H11O2
+    Fluorenylmethyloxycarbonyl,
 }
 
-impl Display for Complex {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let s = match self {
+impl Complex {
+    /// All the complex groups, in the order of their declaration.
+    pub const ALL: &'static [Complex] = &[
+        Complex::Methyl,
+        Complex::Ethyl,
+        Complex::Butyl,
+        Complex::Phenyl,
+        Complex::Benzyl,
+        Complex::Cyclohexyl,
+        Complex::Cyclopentadienyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::Isopropyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::TertButyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::Benzoyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::Mesyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::Triflyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::TertButyloxycarbonyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::Carboxybenzyl,
+        #[cfg(feature = "protecting-groups")]
+        Complex::Fluorenylmethyloxycarbonyl,
+    ];
+
+    /// The number of characters of the longest symbol, `Fmoc`.
+    pub(crate) const MAX_SYMBOL_LENGTH: usize = 4;
+
+    /// Returns the symbol of the complex group, as written in formulas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert_eq!(Complex::Methyl.symbol(), "Me");
+    /// assert_eq!(Complex::Cyclopentadienyl.symbol(), "Cp");
+    /// ```
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        match self {
             Complex::Methyl => "Me",
             Complex::Ethyl => "Et",
             Complex::Butyl => "Bu",
@@ -33,8 +111,82 @@ impl Display for Complex {
             Complex::Benzyl => "Bn",
             Complex::Cyclohexyl => "Cy",
             Complex::Cyclopentadienyl => "Cp",
-        };
-        write!(f, "{s}")
+            #[cfg(feature = "protecting-groups")]
+            Complex::Isopropyl => "iPr",
+            #[cfg(feature = "protecting-groups")]
+            Complex::TertButyl => "tBu",
+            #[cfg(feature = "protecting-groups")]
+            Complex::Benzoyl => "Bz",
+            #[cfg(feature = "protecting-groups")]
+            Complex::Mesyl => "Ms",
+            #[cfg(feature = "protecting-groups")]
+            Complex::Triflyl => "Tf",
+            #[cfg(feature = "protecting-groups")]
+            Complex::TertButyloxycarbonyl => "Boc",
+            #[cfg(feature = "protecting-groups")]
+            Complex::Carboxybenzyl => "Cbz",
+            #[cfg(feature = "protecting-groups")]
+            Complex::Fluorenylmethyloxycarbonyl => "Fmoc",
+        }
+    }
+
+    /// Returns the elements of the complex group and their counts, in the
+    /// order in which they are written.
+    #[must_use]
+    pub const fn composition(self) -> &'static [(Element, u8)] {
+        match self {
+            Complex::Methyl => &[(Element::C, 1), (Element::H, 3)],
+            Complex::Ethyl => &[(Element::C, 2), (Element::H, 5)],
+            Complex::Butyl => &[(Element::C, 4), (Element::H, 9)],
+            Complex::Phenyl => &[(Element::C, 6), (Element::H, 5)],
+            Complex::Benzyl => &[(Element::C, 7), (Element::H, 7)],
+            Complex::Cyclohexyl => &[(Element::C, 6), (Element::H, 11)],
+            Complex::Cyclopentadienyl => &[(Element::C, 5), (Element::H, 5)],
+            #[cfg(feature = "protecting-groups")]
+            Complex::Isopropyl => &[(Element::C, 3), (Element::H, 7)],
+            #[cfg(feature = "protecting-groups")]
+            Complex::TertButyl => &[(Element::C, 4), (Element::H, 9)],
+            #[cfg(feature = "protecting-groups")]
+            Complex::Benzoyl => &[(Element::C, 7), (Element::H, 5), (Element::O, 1)],
+            #[cfg(feature = "protecting-groups")]
+            Complex::Mesyl => &[(Element::C, 1), (Element::H, 3), (Element::S, 1), (Element::O, 2)],
+            #[cfg(feature = "protecting-groups")]
+            Complex::Triflyl => {
+                &[(Element::C, 1), (Element::F, 3), (Element::S, 1), (Element::O, 2)]
+            }
+            #[cfg(feature = "protecting-groups")]
+            Complex::TertButyloxycarbonyl => &[(Element::C, 5), (Element::H, 9), (Element::O, 2)],
+            #[cfg(feature = "protecting-groups")]
+            Complex::Carboxybenzyl => &[(Element::C, 8), (Element::H, 7), (Element::O, 2)],
+            #[cfg(feature = "protecting-groups")]
+            Complex::Fluorenylmethyloxycarbonyl => {
+                &[(Element::C, 15), (Element::H, 11), (Element::O, 2)]
+            }
+        }
+    }
+
+    /// Returns whether the symbol of any complex group starts with the
+    /// provided characters.
+    pub(crate) fn any_symbol_starts_with(prefix: &[char]) -> bool {
+        Self::ALL.iter().any(|complex| {
+            let mut symbol = complex.symbol().chars();
+            prefix.iter().all(|c| symbol.next() == Some(*c))
+        })
+    }
+
+    /// Returns the complex group whose symbol is spelled by the provided
+    /// characters, if any.
+    pub(crate) fn from_symbol(characters: &[char]) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|complex| complex.symbol().chars().eq(characters.iter().copied()))
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.symbol())
     }
 }
 
@@ -42,16 +194,15 @@ impl TryFrom<[char; 2]> for Complex {
     type Error = ();
 
     fn try_from(value: [char; 2]) -> Result<Self, Self::Error> {
-        match value {
-            ['M', 'e'] => Ok(Complex::Methyl),
-            ['E', 't'] => Ok(Complex::Ethyl),
-            ['B', 'u'] => Ok(Complex::Butyl),
-            ['P', 'h'] => Ok(Complex::Phenyl),
-            ['B', 'n'] => Ok(Complex::Benzyl),
-            ['C', 'y'] => Ok(Complex::Cyclohexyl),
-            ['C', 'p'] => Ok(Complex::Cyclopentadienyl),
-            _ => Err(()),
-        }
+        Self::from_symbol(&value).ok_or(())
+    }
+}
+
+impl TryFrom<&str> for Complex {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::ALL.iter().copied().find(|complex| complex.symbol() == value).ok_or(())
     }
 }
 
@@ -87,4 +238,17 @@ mod tests {
         assert_eq!(Complex::Cyclohexyl.to_string(), "Cy");
         assert_eq!(Complex::Cyclopentadienyl.to_string(), "Cp");
     }
+
+    #[test]
+    fn test_symbols_round_trip() {
+        use alloc::string::ToString;
+
+        use super::Complex;
+
+        for complex in Complex::ALL {
+            assert_eq!(Complex::try_from(complex.to_string().as_str()), Ok(*complex));
+            assert!(complex.symbol().chars().count() <= Complex::MAX_SYMBOL_LENGTH);
+        }
+        assert!(Complex::try_from("Fm").is_err());
+    }
 }
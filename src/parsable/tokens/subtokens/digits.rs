@@ -6,8 +6,11 @@ use core::{
     ops::{DivAssign, MulAssign, RemAssign},
 };
 
-use num_traits::{CheckedAdd, CheckedMul, CheckedSub, ConstOne, ConstZero, Num, Unsigned};
+use num_traits::{
+    AsPrimitive, CheckedAdd, CheckedMul, CheckedSub, ConstOne, ConstZero, Num, Unsigned,
+};
 
+use super::compatibility::compatibility_digit;
 use crate::errors::NumericError;
 
 /// Trait for number-like types that can represent 0-10.
@@ -32,8 +35,8 @@ pub trait NumberLike:
     + Hash
     + PartialEq
     + Display
-    + Into<f64>
-    + Into<i64>
+    + AsPrimitive<f64>
+    + Into<i128>
 {
     /// Constant for the `Two` value of the type.
     const TWO: Self;
@@ -58,9 +61,9 @@ pub trait NumberLike:
 }
 
 /// Trait for count-like types.
-pub trait CountLike: NumberLike + Unsigned + TryInto<usize> {}
+pub trait CountLike: NumberLike + Unsigned + TryInto<usize> + TryFrom<u64> {}
 
-impl<T> CountLike for T where T: NumberLike + Unsigned + TryInto<usize> {}
+impl<T> CountLike for T where T: NumberLike + Unsigned + TryInto<usize> + TryFrom<u64> {}
 
 /// Macro to implement the `NumberLike` trait for a given numeric type.
 macro_rules! impl_number_like {
@@ -83,9 +86,11 @@ macro_rules! impl_number_like {
 impl_number_like!(u8);
 impl_number_like!(u16);
 impl_number_like!(u32);
+impl_number_like!(u64);
 impl_number_like!(i8);
 impl_number_like!(i16);
 impl_number_like!(i32);
+impl_number_like!(i64);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -143,7 +148,11 @@ impl TryFrom<char> for BaselineDigit {
             '7' => Ok(BaselineDigit(Digit::Seven)),
             '8' => Ok(BaselineDigit(Digit::Eight)),
             '9' => Ok(BaselineDigit(Digit::Nine)),
-            _ => Err(()),
+            _ => {
+                compatibility_digit(c)
+                    .and_then(|value| char::from_digit(value, 10))
+                    .map_or(Err(()), BaselineDigit::try_from)
+            }
         }
     }
 }
@@ -264,7 +273,7 @@ macro_rules! impl_digit_to_numeric {
     };
 }
 
-impl_digit_to_numeric!(u8, u16, u32, i8, i16, i32);
+impl_digit_to_numeric!(u8, u16, u32, u64, i8, i16, i32, i64);
 
 /// Tries to fold the stream of characters into the provided number type.
 pub fn try_fold_number<D, C, I>(
@@ -335,8 +344,10 @@ where
 /// let digits: Vec<Digit> = digits_ltr(10).collect();
 /// assert_eq!(digits, vec![Digit::One, Digit::Zero]);
 /// ```
-pub fn digits_ltr<D: Into<i64>>(number: D) -> impl Iterator<Item = Digit> {
-    let mut number: i64 = number.into();
+pub fn digits_ltr<D: Into<i128>>(number: D) -> impl Iterator<Item = Digit> {
+    // We convert the number into i128 so that the absolute value of the
+    // extremes of all supported integer types can be represented.
+    let mut number: i128 = number.into();
     number = number.abs();
 
     let mut div = 1;
@@ -393,7 +404,7 @@ pub fn digits_ltr<D: Into<i64>>(number: D) -> impl Iterator<Item = Digit> {
 /// let digits: String = superscript_digits_ltr(105).collect();
 /// assert_eq!(digits, "¹⁰⁵");
 /// ```
-pub fn superscript_digits_ltr<D: Into<i64>>(number: D) -> impl Iterator<Item = char> {
+pub fn superscript_digits_ltr<D: Into<i128>>(number: D) -> impl Iterator<Item = char> {
     digits_ltr(number).map(|d| SuperscriptDigit(d).into())
 }
 
@@ -414,7 +425,7 @@ pub fn superscript_digits_ltr<D: Into<i64>>(number: D) -> impl Iterator<Item = c
 /// let digits: String = subscript_digits_ltr(109).collect();
 /// assert_eq!(digits, "₁₀₉");
 /// ```
-pub fn subscript_digits_ltr<D: Into<i64>>(number: D) -> impl Iterator<Item = char> {
+pub fn subscript_digits_ltr<D: Into<i128>>(number: D) -> impl Iterator<Item = char> {
     digits_ltr(number).map(|d| SubscriptDigit(d).into())
 }
 
@@ -0,0 +1,67 @@
+//! Submodule resolving full element names, such as `Sodium` or `Deuterium`,
+//! into the corresponding sub-tokens.
+#![cfg(feature = "names")]
+
+use elements_rs::{Element, isotopes::HydrogenIsotope};
+
+use super::{ChargeLike, CountLike, SubToken};
+
+/// Alternative spellings of element names which are not returned by
+/// [`Element::name`].
+const ALIASES: &[(&str, Element)] =
+    &[("Aluminum", Element::Al), ("Cesium", Element::Cs), ("Sulphur", Element::S)];
+
+/// Names of the hydrogen isotopes which are commonly spelled out.
+const HYDROGEN_ISOTOPES: &[(&str, HydrogenIsotope)] = &[
+    ("Protium", HydrogenIsotope::H1),
+    ("Deuterium", HydrogenIsotope::D),
+    ("Tritium", HydrogenIsotope::T),
+];
+
+/// Returns the sub-token associated with the provided element or hydrogen
+/// isotope name, compared case-insensitively.
+pub(crate) fn sub_token_from_name<Count: CountLike, Charge: ChargeLike, Extension>(
+    name: &str,
+) -> Option<SubToken<Count, Charge, Extension>> {
+    if let Some((_, isotope)) =
+        HYDROGEN_ISOTOPES.iter().find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+    {
+        return Some((*isotope).into());
+    }
+    if let Some((_, element)) =
+        ALIASES.iter().find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+    {
+        return Some((*element).into());
+    }
+    (1..=118u8)
+        .filter_map(|atomic_number| Element::try_from(atomic_number).ok())
+        .find(|element| element.name().eq_ignore_ascii_case(name))
+        .map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsable::tokens::inchi_tokens::InchiToken;
+
+    #[test]
+    fn test_sub_token_from_name() {
+        assert_eq!(
+            sub_token_from_name::<u16, i16, char>("Sodium"),
+            Some(SubToken::Inchi(InchiToken::Element(Element::Na)))
+        );
+        assert_eq!(
+            sub_token_from_name::<u16, i16, char>("sodium"),
+            Some(SubToken::Inchi(InchiToken::Element(Element::Na)))
+        );
+        assert_eq!(
+            sub_token_from_name::<u16, i16, char>("aluminum"),
+            Some(SubToken::Inchi(InchiToken::Element(Element::Al)))
+        );
+        assert_eq!(
+            sub_token_from_name::<u16, i16, char>("Deuterium"),
+            Some(SubToken::HydrogenIsotope(HydrogenIsotope::D))
+        );
+        assert_eq!(sub_token_from_name::<u16, i16, char>("Sodiu"), None);
+    }
+}
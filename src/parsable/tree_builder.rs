@@ -0,0 +1,202 @@
+//! Submodule providing the traits of the builders of the trees assembled by
+//! the parser, so that the same grammar builds either owned trees or trees
+//! allocated in an arena.
+
+use elements_rs::{Element, Isotope};
+
+use crate::{
+    ChargeLike, ChemicalTree, Complex, CountLike, InChITree, SequenceNode, errors::ParserError,
+};
+
+/// Trait for the builders of the trees assembled by the parser.
+pub(crate) trait TreeBuilder<Tree>: Copy {
+    /// Returns an empty tree.
+    fn empty(self) -> Tree;
+
+    /// Returns whether the provided tree is empty.
+    fn is_empty(self, tree: &Tree) -> bool;
+}
+
+/// Trait for the builders of chemical trees, providing the operations the
+/// parser applies to the tree as it reads the tokens of a unit.
+pub(crate) trait ChemicalTreeBuilder<Tree>: TreeBuilder<Tree> {
+    /// The count type of the built trees.
+    type Count;
+    /// The charge type of the built trees.
+    type Charge;
+    /// The extension type of the built trees.
+    type Extension;
+
+    /// Pushes a new node onto the tree, converting it into a sequence if
+    /// necessary.
+    fn push(self, tree: Tree, node: Tree) -> Tree;
+
+    /// Pushes the provided element onto the tree.
+    fn element(self, tree: Tree, element: Element) -> Tree;
+
+    /// Pushes the provided isotope onto the tree.
+    fn isotope(self, tree: Tree, isotope: Isotope) -> Tree;
+
+    /// Pushes the provided extension onto the tree.
+    fn extension(self, tree: Tree, extension: Self::Extension) -> Tree;
+
+    /// Wraps the tree into round brackets, unless it is a single atom.
+    fn round(self, tree: Tree) -> Tree;
+
+    /// Wraps the tree into square brackets, unless it is a single atom.
+    fn square(self, tree: Tree) -> Tree;
+
+    /// Decorates the tree with a left-hand side radical.
+    fn left_radical(self, tree: Tree) -> Tree;
+
+    /// Decorates the tree with a right-hand side radical.
+    fn right_radical(self, tree: Tree) -> Tree;
+
+    /// Decorates the tree with a charge, merging it with the charge of an
+    /// already charged tree.
+    ///
+    /// # Errors
+    ///
+    /// * If the tree is empty, or if the merged charge overflows.
+    fn charge(self, tree: Tree, charge: Self::Charge) -> Result<Tree, ParserError>;
+
+    /// Decorates the last unit of the tree with a repeat count.
+    fn repeat(self, tree: Tree, count: Self::Count) -> Tree;
+
+    /// Pushes the round bracketed composition of the provided ligand onto
+    /// the tree.
+    fn complex(self, tree: Tree, complex: Complex) -> Tree;
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Builder of owned trees, whose nodes are allocated one by one.
+pub(crate) struct OwnedTrees;
+
+impl<Count: CountLike, Charge: ChargeLike, Extension>
+    TreeBuilder<ChemicalTree<Count, Charge, Extension>> for OwnedTrees
+{
+    #[inline]
+    fn empty(self) -> ChemicalTree<Count, Charge, Extension> {
+        ChemicalTree::Sequence(SequenceNode::empty())
+    }
+
+    #[inline]
+    fn is_empty(self, tree: &ChemicalTree<Count, Charge, Extension>) -> bool {
+        matches!(tree, ChemicalTree::Sequence(sequence) if sequence.is_empty())
+    }
+}
+
+impl<Count: CountLike> TreeBuilder<SequenceNode<InChITree<Count>>> for OwnedTrees {
+    #[inline]
+    fn empty(self) -> SequenceNode<InChITree<Count>> {
+        SequenceNode::empty()
+    }
+
+    #[inline]
+    fn is_empty(self, tree: &SequenceNode<InChITree<Count>>) -> bool {
+        tree.is_empty()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike, Extension: Clone>
+    ChemicalTreeBuilder<ChemicalTree<Count, Charge, Extension>> for OwnedTrees
+{
+    type Count = Count;
+    type Charge = Charge;
+    type Extension = Extension;
+
+    #[inline]
+    fn push(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+        node: ChemicalTree<Count, Charge, Extension>,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.push(node)
+    }
+
+    #[inline]
+    fn element(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+        element: Element,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.push(ChemicalTree::Element(element))
+    }
+
+    #[inline]
+    fn isotope(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+        isotope: Isotope,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.isotope(isotope)
+    }
+
+    #[inline]
+    fn extension(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+        extension: Extension,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.extension(extension)
+    }
+
+    #[inline]
+    fn round(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.round()
+    }
+
+    #[inline]
+    fn square(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.square()
+    }
+
+    #[inline]
+    fn left_radical(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.left_radical()
+    }
+
+    #[inline]
+    fn right_radical(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.right_radical()
+    }
+
+    #[inline]
+    fn charge(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+        charge: Charge,
+    ) -> Result<ChemicalTree<Count, Charge, Extension>, ParserError> {
+        tree.charge(charge)
+    }
+
+    #[inline]
+    fn repeat(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+        count: Count,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.repeat(count)
+    }
+
+    #[inline]
+    fn complex(
+        self,
+        tree: ChemicalTree<Count, Charge, Extension>,
+        complex: Complex,
+    ) -> ChemicalTree<Count, Charge, Extension> {
+        tree.complex(complex)
+    }
+}
@@ -0,0 +1,394 @@
+//! Submodule providing the validation profiles checking whether a formula
+//! string follows the writing conventions of a database or of a naming
+//! authority, as needed to flag nonconforming entries at ingestion time.
+
+use alloc::{vec, vec::Vec};
+use core::{fmt::Display, ops::Range, str::FromStr};
+
+use elements_rs::{Element, Isotope};
+
+use crate::{
+    Bracket, CharacterMarker, ChargeLike, ChemicalFormula, Complex, CountLike, InchiToken,
+    MolecularFormula, MolecularTree, Spanned, SubscriptDigit, SuperscriptDigit, SuperscriptMinus,
+    SuperscriptPlus, Token, errors::ParserError, tokenize,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Named set of conventions a formula string may be checked against.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::prelude::*;
+///
+/// assert!(ValidationProfile::PubChem.conforms::<u32, i32>("C2H4O2"));
+/// let violations = ValidationProfile::PubChem.check::<u32, i32>("CH3COOH");
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].value(), &ProfileViolation::RepeatedElement(Element::C));
+///
+/// assert!(ValidationProfile::IupacStrict.conforms::<u32, i32>("SO₄²⁻"));
+/// assert!(!ValidationProfile::IupacStrict.conforms::<u32, i32>("SO4-2"));
+/// ```
+pub enum ValidationProfile {
+    /// The display rules of the molecular formulas of PubChem: ASCII
+    /// characters, elements in Hill order and written once per component,
+    /// and charges written after the formula with the sign first, as in
+    /// `O4S-2`.
+    PubChem,
+    /// The rules of the molecular formulas of the CAS indexes: ASCII
+    /// characters, elements in Hill order and written once per component,
+    /// and neutral components, as in `C2H4O2.Na`.
+    Cas,
+    /// The typographic rules of the IUPAC recommendations: counts written
+    /// as subscripts, mass numbers as superscripts, and charges as
+    /// superscripts with the magnitude first, as in `[¹³C]O₃²⁻`.
+    IupacStrict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Departure of a formula string from the conventions of a
+/// [`ValidationProfile`].
+pub enum ProfileViolation {
+    /// The formula cannot be parsed.
+    Unparsable(ParserError),
+    /// A character outside of the ASCII range is used, such as a subscript
+    /// digit.
+    NonAsciiCharacter,
+    /// A number is not written with the expected kind of digits, such as
+    /// the baseline count of `H2O` where a subscript count is expected.
+    NumberTypography,
+    /// A count of one is written explicitly, as in `H2O1`.
+    ExplicitUnitCount,
+    /// An abbreviation is used instead of element symbols, as in `MeOH`.
+    Abbreviation(Complex),
+    /// Atoms are grouped within brackets, as in `Ca(OH)2`.
+    Grouping,
+    /// An element is written in several places of a component, as in
+    /// `CH3COOH`.
+    RepeatedElement(Element),
+    /// The elements of a component are not written in Hill order.
+    NotHillOrdered,
+    /// A charge is not written in the expected notation.
+    ChargeNotation,
+    /// A component carries a charge.
+    Charged,
+    /// A radical is marked.
+    Radical,
+}
+
+impl Display for ProfileViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unparsable(error) => write!(f, "The formula cannot be parsed: {error}"),
+            Self::NonAsciiCharacter => write!(f, "Only ASCII characters are allowed."),
+            Self::NumberTypography => {
+                write!(f, "The number is not written with the expected kind of digits.")
+            }
+            Self::ExplicitUnitCount => write!(f, "Counts of one must be omitted."),
+            Self::Abbreviation(complex) => {
+                write!(f, "The abbreviation `{complex}` must be written with element symbols.")
+            }
+            Self::Grouping => write!(f, "Atoms must not be grouped within brackets."),
+            Self::RepeatedElement(element) => {
+                write!(f, "The element {element} is written in several places.")
+            }
+            Self::NotHillOrdered => write!(f, "The elements are not in Hill order."),
+            Self::ChargeNotation => {
+                write!(f, "The charge is not written in the expected notation.")
+            }
+            Self::Charged => write!(f, "Components must be neutral."),
+            Self::Radical => write!(f, "Radicals must not be marked."),
+        }
+    }
+}
+
+/// Role of a count within a formula, which decides how it is typeset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CountRole {
+    /// The multiplier at the start of a component, as in `5H₂O`.
+    Multiplier,
+    /// The mass number of a bracketed isotope, as in `[¹³C]`.
+    MassNumber,
+    /// The count of the preceding element or group, as in `H₂O`.
+    Subscript,
+}
+
+impl ValidationProfile {
+    /// All the validation profiles.
+    pub const ALL: [ValidationProfile; 3] =
+        [ValidationProfile::PubChem, ValidationProfile::Cas, ValidationProfile::IupacStrict];
+
+    /// Returns the name of the validation profile.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// assert_eq!(ValidationProfile::IupacStrict.name(), "IUPAC (strict)");
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PubChem => "PubChem",
+            Self::Cas => "CAS",
+            Self::IupacStrict => "IUPAC (strict)",
+        }
+    }
+
+    /// Returns whether the provided formula follows all the conventions of
+    /// the profile.
+    #[must_use]
+    pub fn conforms<Count, Charge>(&self, formula: &str) -> bool
+    where
+        Count: CountLike,
+        Charge: ChargeLike + TryFrom<Count>,
+        Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+    {
+        self.check::<Count, Charge>(formula).is_empty()
+    }
+
+    /// Returns the violations of the conventions of the profile found in the
+    /// provided formula, by increasing position, with the byte range of the
+    /// token or of the component where each of them was found.
+    ///
+    /// Formulas which cannot be parsed yield a single
+    /// [`ProfileViolation::Unparsable`] violation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::prelude::*;
+    ///
+    /// let violations = ValidationProfile::Cas.check::<u32, i32>("CH3COO-.Na+");
+    /// let violations: Vec<_> =
+    ///     violations.iter().map(|violation| (*violation.value(), violation.span())).collect();
+    /// assert_eq!(
+    ///     violations,
+    ///     [
+    ///         (ProfileViolation::RepeatedElement(Element::C), 0..7),
+    ///         (ProfileViolation::Charged, 6..7),
+    ///         (ProfileViolation::Charged, 10..11)
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn check<Count, Charge>(&self, formula: &str) -> Vec<Spanned<ProfileViolation>>
+    where
+        Count: CountLike,
+        Charge: ChargeLike + TryFrom<Count>,
+        Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+    {
+        let mut tokens = Vec::new();
+        for token in tokenize::<Count, Charge>(formula) {
+            match token {
+                Ok(token) => tokens.push(token),
+                Err(error) => {
+                    let span = error.span();
+                    return vec![Spanned::new(
+                        ProfileViolation::Unparsable(error.into_value()),
+                        span,
+                    )];
+                }
+            }
+        }
+        let parsed = match ChemicalFormula::<Count, Charge>::from_str(formula) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return vec![Spanned::new(ProfileViolation::Unparsable(error), 0..formula.len())];
+            }
+        };
+
+        let mut violations = Vec::new();
+        self.check_tokens(formula, &tokens, &mut violations);
+        self.check_components(formula, &tokens, &parsed, &mut violations);
+        violations.sort_by_key(|violation| violation.span().start);
+        violations
+    }
+
+    /// Checks the conventions applying to the individual tokens of the
+    /// formula.
+    fn check_tokens<Count: CountLike, Charge: ChargeLike>(
+        self,
+        formula: &str,
+        tokens: &[Spanned<Token<Count, Charge, crate::Empty>>],
+        violations: &mut Vec<Spanned<ProfileViolation>>,
+    ) {
+        let ascii = matches!(self, Self::PubChem | Self::Cas);
+        let mut push = |violation, span: Range<usize>| {
+            violations.push(Spanned::new(violation, span));
+        };
+        for (index, token) in tokens.iter().enumerate() {
+            let span = token.span();
+            let text = &formula[span.clone()];
+            if ascii && !text.is_ascii() {
+                push(ProfileViolation::NonAsciiCharacter, span.clone());
+            }
+            let previous = index.checked_sub(1).map(|index| tokens[index].value());
+            match token.value() {
+                Token::Inchi(InchiToken::Count(count)) => {
+                    let role = match previous {
+                        None | Some(Token::Inchi(InchiToken::Dot)) => CountRole::Multiplier,
+                        Some(Token::OpenBracket(Bracket::Square))
+                            if is_isotope_bracket(tokens, index - 1) =>
+                        {
+                            CountRole::MassNumber
+                        }
+                        Some(_) => CountRole::Subscript,
+                    };
+                    if count.is_one() && role != CountRole::MassNumber {
+                        push(ProfileViolation::ExplicitUnitCount, span.clone());
+                    }
+                    if self == Self::IupacStrict && !is_typeset_as(text, role) {
+                        push(ProfileViolation::NumberTypography, span);
+                    }
+                }
+                Token::Isotope(_)
+                    if self == Self::IupacStrict && text.chars().any(|c| c.is_ascii_digit()) =>
+                {
+                    push(ProfileViolation::NumberTypography, span);
+                }
+                Token::Charge(_) => {
+                    if self == Self::Cas {
+                        push(ProfileViolation::Charged, span);
+                    } else if !self.is_charge_notation(text) {
+                        push(ProfileViolation::ChargeNotation, span);
+                    }
+                }
+                Token::Complex(complex) => push(ProfileViolation::Abbreviation(*complex), span),
+                Token::Radical if ascii => push(ProfileViolation::Radical, span),
+                Token::OpenBracket(_) if ascii && !is_isotope_bracket(tokens, index) => {
+                    push(ProfileViolation::Grouping, span);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks the conventions applying to the order of the elements of each
+    /// component of the formula.
+    fn check_components<Count: CountLike, Charge: ChargeLike>(
+        self,
+        formula: &str,
+        tokens: &[Spanned<Token<Count, Charge, crate::Empty>>],
+        parsed: &ChemicalFormula<Count, Charge>,
+        violations: &mut Vec<Spanned<ProfileViolation>>,
+    ) {
+        if self == Self::IupacStrict {
+            return;
+        }
+        let mut spans = Vec::new();
+        let mut start = 0;
+        for token in tokens {
+            if matches!(token.value(), Token::Inchi(InchiToken::Dot)) {
+                spans.push(start..token.span().start);
+                start = token.span().end;
+            }
+        }
+        spans.push(start..formula.len());
+
+        for (span, tree) in spans.into_iter().zip(parsed.mixtures()) {
+            let mut written: Vec<Element> = Vec::new();
+            let mut repeated = None;
+            for element in tree.elements() {
+                if written.last() != Some(&element) {
+                    if written.contains(&element) {
+                        repeated = Some(element);
+                        break;
+                    }
+                    written.push(element);
+                }
+            }
+            if let Some(element) = repeated {
+                violations.push(Spanned::new(ProfileViolation::RepeatedElement(element), span));
+            } else if !tree.is_hill_sorted() {
+                violations.push(Spanned::new(ProfileViolation::NotHillOrdered, span));
+            }
+        }
+    }
+
+    /// Returns whether the provided charge is written in the notation of the
+    /// profile.
+    fn is_charge_notation(self, charge: &str) -> bool {
+        let mut chars = charge.chars();
+        match self {
+            // The sign comes first and is followed by the magnitude, if any.
+            Self::PubChem | Self::Cas => {
+                chars.next().is_some_and(|c| matches!(c, '+' | '-'))
+                    && chars.all(|c| c.is_ascii_digit())
+            }
+            // The superscript magnitude, if any, is followed by a single
+            // superscript sign.
+            Self::IupacStrict => {
+                chars
+                    .next_back()
+                    .is_some_and(|c| SuperscriptPlus::matches(c) || SuperscriptMinus::matches(c))
+                    && chars.all(|c| SuperscriptDigit::try_from(c).is_ok())
+            }
+        }
+    }
+}
+
+/// Returns whether the opening bracket at the provided position encloses a
+/// single isotope, as in `[13C]`, rather than a group of atoms.
+fn is_isotope_bracket<Count: CountLike, Charge: ChargeLike>(
+    tokens: &[Spanned<Token<Count, Charge, crate::Empty>>],
+    index: usize,
+) -> bool {
+    matches!(
+        tokens
+            .get(index..index + 4)
+            .map(|tokens| tokens.iter().map(Spanned::value).collect::<Vec<_>>())
+            .as_deref(),
+        Some([
+            Token::OpenBracket(Bracket::Square),
+            Token::Inchi(InchiToken::Count(_)),
+            Token::Inchi(InchiToken::Element(_)),
+            Token::CloseBracket(Bracket::Square),
+        ])
+    )
+}
+
+/// Returns whether the provided number is typeset as required by its role
+/// by the IUPAC recommendations.
+fn is_typeset_as(number: &str, role: CountRole) -> bool {
+    number.chars().all(|c| {
+        match role {
+            CountRole::Multiplier => c.is_ascii_digit(),
+            CountRole::MassNumber => SuperscriptDigit::try_from(c).is_ok(),
+            CountRole::Subscript => SubscriptDigit::try_from(c).is_ok(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_typeset_as() {
+        assert!(is_typeset_as("12", CountRole::Multiplier));
+        assert!(is_typeset_as("₁₂", CountRole::Subscript));
+        assert!(is_typeset_as("¹³", CountRole::MassNumber));
+        assert!(!is_typeset_as("12", CountRole::Subscript));
+        assert!(!is_typeset_as("₁2", CountRole::Subscript));
+    }
+
+    #[test]
+    fn test_is_charge_notation() {
+        for (profile, charge, expected) in [
+            (ValidationProfile::PubChem, "+", true),
+            (ValidationProfile::PubChem, "-2", true),
+            (ValidationProfile::PubChem, "++", false),
+            (ValidationProfile::PubChem, "²⁻", false),
+            (ValidationProfile::IupacStrict, "⁺", true),
+            (ValidationProfile::IupacStrict, "²⁻", true),
+            (ValidationProfile::IupacStrict, "⁻²", false),
+            (ValidationProfile::IupacStrict, "⁺⁺", false),
+            (ValidationProfile::IupacStrict, "+", false),
+        ] {
+            assert_eq!(profile.is_charge_notation(charge), expected, "{profile:?} {charge}");
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Module providing `proptest` strategies generating molecular formulas
+//! directly as trees, for property tests in downstream crates.
+#![cfg(feature = "proptest")]
+
+use alloc::vec::Vec;
+
+use elements_rs::{Element, Isotope};
+use proptest::{
+    arbitrary::Arbitrary,
+    prelude::{BoxedStrategy, Just, Strategy, any, prop, prop_oneof},
+};
+
+use crate::{
+    ChargeLike, ChemicalFormula, ChemicalTree, CountLike, Empty, MineralFormula, PolymorphPrefix,
+    Prefixed,
+};
+
+/// The largest count generated for the atoms, units and mixtures of the
+/// formulas, small enough to fit any count type.
+const MAX_COUNT: u64 = 12;
+
+/// The largest magnitude generated for the charges of the formulas, small
+/// enough to fit any charge type.
+const MAX_CHARGE: i64 = 3;
+
+/// Returns a strategy generating any element.
+pub fn elements() -> impl Strategy<Value = Element> {
+    (1..=118_u8).prop_filter_map("Atomic numbers map to elements", |atomic_number| {
+        Element::try_from(atomic_number).ok()
+    })
+}
+
+/// Returns a strategy generating any isotope of any element.
+pub fn isotopes() -> impl Strategy<Value = Isotope> {
+    elements()
+        .prop_filter("Elements have isotopes", |element| !element.isotopes().is_empty())
+        .prop_flat_map(|element| prop::sample::select(element.isotopes().to_vec()))
+}
+
+/// Returns a strategy generating counts between the provided minimum and
+/// [`MAX_COUNT`].
+fn counts<Count: CountLike>(min: u64) -> impl Strategy<Value = Count> {
+    (min..=MAX_COUNT).prop_map(|count| {
+        Count::try_from(count).unwrap_or_else(|_| unreachable!("Small counts fit any count type"))
+    })
+}
+
+/// Returns a strategy generating the neutral groups of a mixture
+/// component, i.e. atoms, isotopes and bracketed sequences of groups, each
+/// optionally repeated.
+fn groups<Count: CountLike, Charge: ChargeLike>()
+-> impl Strategy<Value = ChemicalTree<Count, Charge, Empty>> {
+    let atoms = prop_oneof![
+        3 => elements().prop_map(ChemicalTree::Element),
+        1 => isotopes().prop_map(ChemicalTree::Isotope),
+    ];
+    let leaves = (atoms, prop::option::of(counts::<Count>(2)))
+        .prop_map(|(atom, count)| if let Some(count) = count { atom.repeat(count) } else { atom });
+    leaves.prop_recursive(3, 24, 4, |inner| {
+        (prop::collection::vec(inner, 2..=4), any::<bool>(), prop::option::of(counts::<Count>(2)))
+            .prop_map(|(groups, square, count)| {
+                let sequence = sequence(groups);
+                let unit = if square { sequence.square() } else { sequence.round() };
+                if let Some(count) = count { unit.repeat(count) } else { unit }
+            })
+    })
+}
+
+/// Returns the sequence of the provided trees.
+fn sequence<Count: CountLike, Charge: ChargeLike>(
+    trees: Vec<ChemicalTree<Count, Charge, Empty>>,
+) -> ChemicalTree<Count, Charge, Empty> {
+    let mut trees = trees.into_iter();
+    let first = trees.next().unwrap_or_else(|| unreachable!("Sequences are not empty"));
+    trees.fold(first, ChemicalTree::push)
+}
+
+/// Returns a strategy generating the trees of mixture components, i.e.
+/// non-empty sequences of groups, optionally carrying a non-zero charge of
+/// at most three.
+///
+/// The trees are built as the parser would build them, so that their
+/// display is parsed back to an equal tree.
+pub fn chemical_trees<Count: CountLike, Charge: ChargeLike>()
+-> impl Strategy<Value = ChemicalTree<Count, Charge, Empty>> {
+    let charges = prop_oneof![
+        2 => Just(0_i64),
+        1 => (-MAX_CHARGE..=MAX_CHARGE).prop_filter("Charges are not zero", |charge| *charge != 0),
+    ];
+    (prop::collection::vec(groups::<Count, Charge>(), 1..=5), charges).prop_map(
+        |(groups, charge)| {
+            let tree = sequence(groups);
+            if charge == 0 {
+                return tree;
+            }
+            let charge = Charge::try_from(charge)
+                .unwrap_or_else(|_| unreachable!("Small charges fit any charge type"));
+            tree.charge(charge).unwrap_or_else(|_| unreachable!("Sequences are not empty"))
+        },
+    )
+}
+
+/// Returns a strategy generating chemical formulas of one to three mixture
+/// components, each repeated at most [`MAX_COUNT`] times.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::{prelude::*, property_testing::chemical_formulas};
+/// use proptest::{
+///     strategy::{Strategy, ValueTree},
+///     test_runner::TestRunner,
+/// };
+///
+/// let mut runner = TestRunner::deterministic();
+/// let formula = chemical_formulas::<u32, i32>().new_tree(&mut runner).unwrap().current();
+/// assert!(formula.number_of_mixtures() >= 1);
+/// assert_eq!(ChemicalFormula::<u32, i32>::try_from(formula.to_string().as_str()), Ok(formula));
+/// ```
+pub fn chemical_formulas<Count: CountLike, Charge: ChargeLike>()
+-> impl Strategy<Value = ChemicalFormula<Count, Charge>> {
+    prop::collection::vec((counts::<Count>(1), chemical_trees::<Count, Charge>()), 1..=3)
+        .prop_map(ChemicalFormula::from_mixtures)
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Arbitrary for ChemicalFormula<Count, Charge> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        chemical_formulas().boxed()
+    }
+}
+
+/// Returns a strategy generating any polymorph prefix.
+pub fn polymorph_prefixes() -> impl Strategy<Value = PolymorphPrefix> {
+    prop::sample::select(
+        [
+            PolymorphPrefix::Alpha,
+            PolymorphPrefix::Beta,
+            PolymorphPrefix::Gamma,
+            PolymorphPrefix::Delta,
+            PolymorphPrefix::Phi,
+            PolymorphPrefix::Omega,
+            PolymorphPrefix::Lambda,
+            PolymorphPrefix::Mu,
+            PolymorphPrefix::Pi,
+        ]
+        .as_slice(),
+    )
+}
+
+impl Arbitrary for PolymorphPrefix {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        polymorph_prefixes().boxed()
+    }
+}
+
+impl<M: Arbitrary + 'static> Arbitrary for Prefixed<M> {
+    type Parameters = M::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(parameters: Self::Parameters) -> Self::Strategy {
+        (prop::option::of(polymorph_prefixes()), M::arbitrary_with(parameters))
+            .prop_map(|(prefix, formula)| Prefixed::new(prefix, formula))
+            .boxed()
+    }
+}
+
+impl<Count: CountLike, Charge: ChargeLike> Arbitrary for MineralFormula<Count, Charge> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any::<Prefixed<ChemicalFormula<Count, Charge>>>().prop_map(Into::into).boxed()
+    }
+}
@@ -0,0 +1,355 @@
+//! Module providing the extraction of chemical formulas from the records of
+//! SD files, as distributed by compound vendors and databases such as
+//! PubChem.
+//!
+//! The formula of each record is read from its formula property field when
+//! present, and is otherwise computed from the atoms listed in its molfile.
+//! Since molfiles usually leave hydrogens implicit, formulas computed from the
+//! atom block only account for the hydrogens explicitly listed in it.
+#![cfg(feature = "sdf")]
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+
+use elements_rs::{Element, Isotope};
+
+use crate::{
+    ChargeLike, ChemicalFormula, CountLike,
+    errors::{NumericError, ParserError},
+    signed_charge,
+};
+
+/// Names of the property fields which are looked up by default for the
+/// formula of a record.
+pub const DEFAULT_FORMULA_PROPERTIES: [&str; 5] =
+    ["PUBCHEM_MOLECULAR_FORMULA", "MOLECULAR_FORMULA", "FORMULA", "Formula", "MF"];
+
+/// Line terminating each record of an SD file.
+const RECORD_TERMINATOR: &str = "$$$$";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+/// Error enumeration when extracting formulas from an SD file.
+pub enum SdfError {
+    /// The formula of a record could not be parsed or computed.
+    #[error("Record {record}: {error}")]
+    Formula {
+        /// The zero-based index of the record.
+        record: usize,
+        /// The underlying parser error.
+        error: ParserError,
+    },
+    /// The molfile of a record is malformed.
+    #[error("Record {record}: the molfile is malformed.")]
+    MalformedMolfile {
+        /// The zero-based index of the record.
+        record: usize,
+    },
+    /// The molfile of a record contains an atom symbol which is not an
+    /// element, such as a query atom.
+    #[error("Record {record}: the atom symbol '{symbol}' is not an element.")]
+    UnknownAtom {
+        /// The zero-based index of the record.
+        record: usize,
+        /// The unsupported atom symbol.
+        symbol: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Source from which the formula of a record was obtained.
+pub enum FormulaSource {
+    /// The formula was read from a property field of the record.
+    Property,
+    /// The formula was computed from the atom block of the molfile.
+    AtomBlock,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Formula extracted from a record of an SD file.
+pub struct SdfRecord<Count: CountLike = u16, Charge: ChargeLike = i16> {
+    /// The title line of the molfile.
+    name: String,
+    /// The formula of the record.
+    formula: ChemicalFormula<Count, Charge>,
+    /// The source of the formula.
+    source: FormulaSource,
+}
+
+impl<Count: CountLike, Charge: ChargeLike> SdfRecord<Count, Charge> {
+    /// Returns the title line of the molfile of the record.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the formula of the record.
+    #[must_use]
+    pub fn formula(&self) -> &ChemicalFormula<Count, Charge> {
+        &self.formula
+    }
+
+    /// Returns the source from which the formula was obtained.
+    #[must_use]
+    pub fn source(&self) -> FormulaSource {
+        self.source
+    }
+
+    /// Consumes the record and returns its formula.
+    #[must_use]
+    pub fn into_formula(self) -> ChemicalFormula<Count, Charge> {
+        self.formula
+    }
+}
+
+/// Iterator over the formulas of the records of an SD file, read line by
+/// line.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::{prelude::*, sdf::SdfReader};
+///
+/// let sdf = "glucose\n\n\n  0  0  0  0  0  0  0  0  0  0999 V2000\nM  END\n> <PUBCHEM_MOLECULAR_FORMULA>\nC6H12O6\n\n$$$$\n";
+/// let records: Vec<_> = SdfReader::<_, u32, i32>::new(sdf.lines()).collect();
+/// assert_eq!(records.len(), 1);
+/// let record = records[0].as_ref().unwrap();
+/// assert_eq!(record.name(), "glucose");
+/// assert_eq!(record.formula().to_string(), "C₆H₁₂O₆");
+/// ```
+pub struct SdfReader<I, Count: CountLike = u16, Charge: ChargeLike = i16> {
+    /// The lines of the SD file.
+    lines: I,
+    /// The names of the property fields holding the formula.
+    properties: Vec<String>,
+    /// The index of the next record.
+    record: usize,
+    _marker: core::marker::PhantomData<(Count, Charge)>,
+}
+
+impl<I, S, Count: CountLike, Charge: ChargeLike> SdfReader<I, Count, Charge>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    /// Creates a new reader over the provided lines, looking up the
+    /// [`DEFAULT_FORMULA_PROPERTIES`] for the formula of each record.
+    pub fn new<L: IntoIterator<IntoIter = I>>(lines: L) -> Self {
+        Self {
+            lines: lines.into_iter(),
+            properties: DEFAULT_FORMULA_PROPERTIES.iter().map(|&name| name.to_owned()).collect(),
+            record: 0,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Sets the names of the property fields holding the formula, in order
+    /// of preference. When empty, formulas are always computed from the atom
+    /// block.
+    #[must_use]
+    pub fn with_formula_properties<P: Into<String>>(
+        mut self,
+        properties: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.properties = properties.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns the formula held by the first matching property field of the
+    /// record, if any.
+    fn property_formula(
+        &self,
+        lines: &[String],
+    ) -> Option<Result<ChemicalFormula<Count, Charge>, SdfError>>
+    where
+        Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+        Charge: TryFrom<Count>,
+    {
+        let mut values: Vec<(usize, &str)> = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            if !line.starts_with('>') {
+                continue;
+            }
+            let Some(name) = line
+                .split_once('<')
+                .and_then(|(_, rest)| rest.split_once('>'))
+                .map(|(name, _)| name)
+            else {
+                continue;
+            };
+            if let Some(rank) = self.properties.iter().position(|property| property == name)
+                && let Some(value) = lines.get(index + 1)
+            {
+                values.push((rank, value.trim()));
+            }
+        }
+        let (_, value) = values.into_iter().min_by_key(|(rank, _)| *rank)?;
+        Some(
+            ChemicalFormula::from_str(value)
+                .map_err(|error| SdfError::Formula { record: self.record, error }),
+        )
+    }
+
+    /// Computes the formula of the record from the atom block of its
+    /// molfile.
+    fn atom_block_formula(
+        &self,
+        lines: &[String],
+    ) -> Result<ChemicalFormula<Count, Charge>, SdfError> {
+        let record = self.record;
+        let malformed = || SdfError::MalformedMolfile { record };
+        let counts_line = lines.get(3).ok_or_else(malformed)?;
+        let atoms = if counts_line.contains("V3000") {
+            v3000_atoms(lines).ok_or_else(malformed)?
+        } else {
+            v2000_atoms(lines).ok_or_else(malformed)?
+        };
+
+        let mut counts: Vec<(Element, Count)> = Vec::new();
+        let mut total_charge: i64 = 0;
+        for (symbol, charge) in atoms {
+            let element = Element::from_str(symbol)
+                .map_err(|_| SdfError::UnknownAtom { record, symbol: symbol.to_string() })?;
+            if let Some((_, count)) = counts.iter_mut().find(|(e, _)| *e == element) {
+                *count = count.checked_add(&Count::ONE).ok_or(SdfError::Formula {
+                    record,
+                    error: NumericError::PositiveOverflow.into(),
+                })?;
+            } else {
+                counts.push((element, Count::ONE));
+            }
+            total_charge = total_charge.checked_add(charge).ok_or(SdfError::Formula {
+                record,
+                error: if charge > 0 {
+                    NumericError::PositiveOverflow
+                } else {
+                    NumericError::NegativeOverflow
+                }
+                .into(),
+            })?;
+        }
+        if counts.is_empty() {
+            return Err(SdfError::Formula { record, error: ParserError::EmptyMolecularTree });
+        }
+
+        let formula = ChemicalFormula::from_element_counts(counts);
+        if total_charge == 0 {
+            return Ok(formula);
+        }
+        let charge = signed_charge(total_charge.unsigned_abs(), total_charge > 0)
+            .map_err(|error| SdfError::Formula { record, error: error.into() })?;
+        formula.charged(charge).map_err(|error| SdfError::Formula { record, error })
+    }
+}
+
+/// Returns the symbols and charges of the atoms of a V2000 molfile.
+fn v2000_atoms(lines: &[String]) -> Option<Vec<(&str, i64)>> {
+    let number_of_atoms: usize = lines.get(3)?.get(0..3)?.trim().parse().ok()?;
+    let atom_lines = lines.get(4..4 + number_of_atoms)?;
+    let mut atoms: Vec<(&str, i64)> = atom_lines
+        .iter()
+        .map(|line| {
+            let symbol = line.get(31..34)?.trim();
+            // The legacy charge field encodes the charges 3, 2, 1, -1, -2 and -3 as
+            // the codes 1, 2, 3, 5, 6 and 7, while 4 denotes a doublet radical.
+            let charge = match line.get(36..39).map(str::trim) {
+                Some("1") => 3,
+                Some("2") => 2,
+                Some("3") => 1,
+                Some("5") => -1,
+                Some("6") => -2,
+                Some("7") => -3,
+                _ => 0,
+            };
+            Some((symbol, charge))
+        })
+        .collect::<Option<_>>()?;
+
+    // Any charge property line supersedes all of the charges of the atom block.
+    let mut charge_lines = lines.iter().filter(|line| line.starts_with("M  CHG")).peekable();
+    if charge_lines.peek().is_some() {
+        for atom in &mut atoms {
+            atom.1 = 0;
+        }
+    }
+    for line in charge_lines {
+        let values: Vec<i64> = line
+            .get(9..)?
+            .split_whitespace()
+            .map(|value| value.parse().ok())
+            .collect::<Option<_>>()?;
+        for pair in values.chunks_exact(2) {
+            let atom = atoms.get_mut(usize::try_from(pair[0]).ok()?.checked_sub(1)?)?;
+            atom.1 = pair[1];
+        }
+    }
+    Some(atoms)
+}
+
+/// Returns the symbols and charges of the atoms of a V3000 molfile.
+fn v3000_atoms(lines: &[String]) -> Option<Vec<(&str, i64)>> {
+    let start = lines.iter().position(|line| line.trim_end() == "M  V30 BEGIN ATOM")?;
+    let end = lines.iter().position(|line| line.trim_end() == "M  V30 END ATOM")?;
+    lines
+        .get(start + 1..end)?
+        .iter()
+        .map(|line| {
+            let mut fields = line.strip_prefix("M  V30")?.split_whitespace().skip(1);
+            let symbol = fields.next()?;
+            let charge = fields
+                .find_map(|field| field.strip_prefix("CHG="))
+                .map_or(Some(0), |charge| charge.parse().ok())?;
+            Some((symbol, charge))
+        })
+        .collect()
+}
+
+impl<I, S, Count: CountLike, Charge: ChargeLike> Iterator for SdfReader<I, Count, Charge>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+    Isotope: TryFrom<(Element, Count), Error = elements_rs::errors::Error>,
+    Charge: TryFrom<Count>,
+{
+    type Item = Result<SdfRecord<Count, Charge>, SdfError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lines = loop {
+            let mut lines: Vec<String> = Vec::new();
+            let mut terminated = false;
+            for line in self.lines.by_ref() {
+                let line = line.as_ref().trim_end_matches('\r');
+                if line.trim_end() == RECORD_TERMINATOR {
+                    terminated = true;
+                    break;
+                }
+                lines.push(line.to_owned());
+            }
+            // Blank lines between or after records do not form a record.
+            if !lines.iter().all(|line| line.trim().is_empty()) {
+                break lines;
+            }
+            if !terminated {
+                return None;
+            }
+        };
+
+        let result = match self.property_formula(&lines) {
+            Some(formula) => formula.map(|formula| (formula, FormulaSource::Property)),
+            None => {
+                self.atom_block_formula(&lines).map(|formula| (formula, FormulaSource::AtomBlock))
+            }
+        }
+        .map(|(formula, source)| {
+            SdfRecord {
+                name: lines.first().map(|name| name.trim().to_owned()).unwrap_or_default(),
+                formula,
+                source,
+            }
+        });
+        self.record += 1;
+        Some(result)
+    }
+}
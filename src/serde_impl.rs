@@ -1,15 +1,29 @@
 #![cfg(feature = "serde")]
 
 use alloc::string::{String, ToString};
-use core::str::FromStr;
+use core::{fmt::Formatter, marker::PhantomData, str::FromStr};
 
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+};
 
 use crate::{
-    ChargeLike, ChemicalFormula, CountLike, InChIFormula, MineralFormula, ResidualFormula,
-    errors::ParserError,
+    Annotated, ChargeLike, ChemicalFormula, CountLike, InChIFormula, MineralFormula, NumericTypes,
+    Prefixed, ResidualFormula, TypeTagged, errors::ParserError,
 };
 
+/// Returns the deserialization error of a formula which cannot be parsed,
+/// naming the count or charge which does not fit in the numeric types of
+/// the formula when the parsing overflowed.
+fn parse_error<M: NumericTypes, E: serde::de::Error>(formula: &str, error: ParserError) -> E {
+    match (error, M::range_error(formula)) {
+        (ParserError::Numeric(_), Some(range_error)) => E::custom(range_error),
+        _ => E::custom(error),
+    }
+}
+
 impl<Count: CountLike, Charge: ChargeLike> Serialize for ChemicalFormula<Count, Charge> {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -30,7 +44,12 @@ where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        ChemicalFormula::from_str(&s).map_err(serde::de::Error::custom)
+        // The empty formula serializes as the empty string, which the parser
+        // rejects, so that it is deserialized here for it to round-trip.
+        if s.is_empty() {
+            return Ok(ChemicalFormula::empty());
+        }
+        ChemicalFormula::from_str(&s).map_err(|error| parse_error::<Self, _>(&s, error))
     }
 }
 
@@ -52,7 +71,7 @@ where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        MineralFormula::from_str(&s).map_err(serde::de::Error::custom)
+        MineralFormula::from_str(&s).map_err(|error| parse_error::<Self, _>(&s, error))
     }
 }
 
@@ -74,7 +93,222 @@ where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        ResidualFormula::from_str(&s).map_err(serde::de::Error::custom)
+        ResidualFormula::from_str(&s).map_err(|error| parse_error::<Self, _>(&s, error))
+    }
+}
+
+impl<M: core::fmt::Display> Serialize for Prefixed<M> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, M: NumericTypes> Deserialize<'de> for Prefixed<M>
+where
+    Self: FromStr<Err = ParserError>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Prefixed::from_str(&s).map_err(|error| parse_error::<Self, _>(&s, error))
+    }
+}
+
+/// The fields of the serialized annotated formulas.
+const ANNOTATED_FIELDS: &[&str] = &["formula", "annotation"];
+
+impl<M: Serialize, T: Serialize> Serialize for Annotated<M, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Annotated", ANNOTATED_FIELDS.len())?;
+        state.serialize_field("formula", self.formula())?;
+        state.serialize_field("annotation", self.annotation())?;
+        state.end()
+    }
+}
+
+/// Visitor of the serialized annotated formulas, accepting both the maps
+/// of self-describing formats and the sequences of compact ones.
+struct AnnotatedVisitor<M, T>(PhantomData<(M, T)>);
+
+impl<'de, M: Deserialize<'de>, T: Deserialize<'de>> Visitor<'de> for AnnotatedVisitor<M, T> {
+    type Value = Annotated<M, T>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("an annotated molecular formula")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let formula =
+            seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let annotation =
+            seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        Ok(Annotated::new(formula, annotation))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut formula = None;
+        let mut annotation = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "formula" if formula.is_none() => formula = Some(map.next_value()?),
+                "annotation" if annotation.is_none() => annotation = Some(map.next_value()?),
+                "formula" | "annotation" => {
+                    return Err(serde::de::Error::custom(format_args!("duplicate field `{key}`")));
+                }
+                _ => return Err(serde::de::Error::unknown_field(&key, ANNOTATED_FIELDS)),
+            }
+        }
+        let formula = formula.ok_or_else(|| serde::de::Error::missing_field("formula"))?;
+        let annotation = annotation.ok_or_else(|| serde::de::Error::missing_field("annotation"))?;
+        Ok(Annotated::new(formula, annotation))
+    }
+}
+
+impl<'de, M: Deserialize<'de>, T: Deserialize<'de>> Deserialize<'de> for Annotated<M, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "Annotated",
+            ANNOTATED_FIELDS,
+            AnnotatedVisitor(PhantomData),
+        )
+    }
+}
+
+/// The fields of the serialized type-tagged formulas.
+const TYPE_TAGGED_FIELDS: &[&str] = &["count", "charge", "formula"];
+
+impl<M: Serialize + NumericTypes> Serialize for TypeTagged<M> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TypeTagged", TYPE_TAGGED_FIELDS.len())?;
+        state.serialize_field("count", M::count_type_name())?;
+        state.serialize_field("charge", &M::charge_type_name())?;
+        state.serialize_field("formula", self.formula())?;
+        state.end()
+    }
+}
+
+/// Writes the numeric types of a formula, as in "`u32` counts and `i32`
+/// charges".
+struct NumericTypeNames<'a>(&'a str, Option<&'a str>);
+
+impl core::fmt::Display for NumericTypeNames<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "`{}` counts", self.0)?;
+        match self.1 {
+            Some(charge) => write!(f, " and `{charge}` charges"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the error of a formula which cannot be deserialized, naming the
+/// numeric types it was serialized with when they differ from the ones of
+/// the formula type.
+fn tagged_error<M: NumericTypes, E: serde::de::Error>(
+    count: &str,
+    charge: Option<&str>,
+    error: E,
+) -> E {
+    if count == M::count_type_name() && charge == M::charge_type_name() {
+        return error;
+    }
+    E::custom(format_args!(
+        "The formula serialized with {} cannot be deserialized with {}: {error}",
+        NumericTypeNames(count, charge),
+        NumericTypeNames(M::count_type_name(), M::charge_type_name()),
+    ))
+}
+
+/// Visitor of the serialized type-tagged formulas, accepting both the maps
+/// of self-describing formats and the sequences of compact ones.
+struct TypeTaggedVisitor<M>(PhantomData<M>);
+
+impl<'de, M: Deserialize<'de> + NumericTypes> Visitor<'de> for TypeTaggedVisitor<M> {
+    type Value = TypeTagged<M>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a molecular formula tagged with its numeric types")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let count: String =
+            seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let charge: Option<String> =
+            seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        let formula = seq
+            .next_element()
+            .map_err(|error| tagged_error::<M, _>(&count, charge.as_deref(), error))?
+            .ok_or_else(|| serde::de::Error::invalid_length(2, &self))?;
+        Ok(TypeTagged::new(formula))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut count: Option<String> = None;
+        let mut charge: Option<Option<String>> = None;
+        let mut formula = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "count" if count.is_none() => count = Some(map.next_value()?),
+                "charge" if charge.is_none() => charge = Some(map.next_value()?),
+                "formula" if formula.is_none() => {
+                    formula = Some(map.next_value().map_err(|error| {
+                        match (&count, &charge) {
+                            (Some(count), Some(charge)) => {
+                                tagged_error::<M, _>(count, charge.as_deref(), error)
+                            }
+                            _ => error,
+                        }
+                    })?);
+                }
+                "count" | "charge" | "formula" => {
+                    return Err(serde::de::Error::custom(format_args!("duplicate field `{key}`")));
+                }
+                _ => return Err(serde::de::Error::unknown_field(&key, TYPE_TAGGED_FIELDS)),
+            }
+        }
+        count.ok_or_else(|| serde::de::Error::missing_field("count"))?;
+        charge.ok_or_else(|| serde::de::Error::missing_field("charge"))?;
+        let formula = formula.ok_or_else(|| serde::de::Error::missing_field("formula"))?;
+        Ok(TypeTagged::new(formula))
+    }
+}
+
+impl<'de, M: Deserialize<'de> + NumericTypes> Deserialize<'de> for TypeTagged<M> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "TypeTagged",
+            TYPE_TAGGED_FIELDS,
+            TypeTaggedVisitor(PhantomData),
+        )
     }
 }
 
@@ -96,7 +330,7 @@ where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        InChIFormula::from_str(&s).map_err(serde::de::Error::custom)
+        InChIFormula::from_str(&s).map_err(|error| parse_error::<Self, _>(&s, error))
     }
 }
 
@@ -104,7 +338,9 @@ where
 mod tests {
     use core::str::FromStr;
 
-    use crate::{ChemicalFormula, InChIFormula, MineralFormula, ResidualFormula};
+    use crate::{
+        Annotated, ChemicalFormula, InChIFormula, MineralFormula, Prefixed, ResidualFormula,
+    };
 
     #[test]
     fn test_chemical_formula_serde() {
@@ -115,6 +351,15 @@ mod tests {
         assert_eq!(formula, deserialized);
     }
 
+    #[test]
+    fn test_prefixed_chemical_formula_serde() {
+        let formula: Prefixed<ChemicalFormula> = Prefixed::from_str("α-D-C6H12O6").unwrap();
+        let serialized = serde_json::to_string(&formula).unwrap();
+        assert_eq!(serialized, "\"α-D-C₆H₁₂O₆\"");
+        let deserialized: Prefixed<ChemicalFormula> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(formula, deserialized);
+    }
+
     #[test]
     fn test_mineral_formula_serde() {
         let formula = MineralFormula::from_str("SiO2").unwrap();
@@ -133,6 +378,19 @@ mod tests {
         assert_eq!(formula, deserialized);
     }
 
+    #[test]
+    fn test_annotated_formula_serde() {
+        let formula = Annotated::new(ChemicalFormula::from_str("C6H12O6").unwrap(), 42_u32);
+        let serialized = serde_json::to_string(&formula).unwrap();
+        assert_eq!(serialized, "{\"formula\":\"C₆H₁₂O₆\",\"annotation\":42}");
+        let deserialized: Annotated<ChemicalFormula, u32> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(formula, deserialized);
+        let from_sequence: Annotated<ChemicalFormula, u32> =
+            serde_json::from_str("[\"C6H12O6\", 42]").unwrap();
+        assert_eq!(formula, from_sequence);
+    }
+
     #[test]
     fn test_inchi_formula_serde() {
         let s = "C2H6O";
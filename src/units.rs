@@ -0,0 +1,306 @@
+//! Module providing the `Mass` and `Mz` newtypes, which carry masses in
+//! daltons and mass over charge ratios in thomsons, so that the two cannot
+//! be mixed up in client code.
+//!
+//! The mass APIs of the formulas return bare `f64` values, and the
+//! [`TypedMasses`] trait returns the same values wrapped in their unit.
+#![cfg(feature = "typed-units")]
+
+use core::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::ChargedMolecularFormula;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+/// Mass expressed in daltons (Da), i.e. unified atomic mass units.
+///
+/// Masses may be added to and subtracted from each other, scaled by a
+/// factor and divided by a charge into an [`Mz`] ratio.
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::units::Mass;
+///
+/// let water = Mass::from_daltons(18.010_565);
+/// let dimer = water * 2.0;
+/// assert!((dimer.daltons() - 36.021_13).abs() < 1e-9);
+/// assert!(((dimer - water) / water - 1.0).abs() < 1e-12);
+/// assert_eq!(Mass::from_daltons(1.5).to_string(), "1.5 Da");
+/// ```
+pub struct Mass(f64);
+
+impl Mass {
+    /// The mass of zero daltons.
+    pub const ZERO: Self = Self(0.0);
+
+    /// Creates a mass from the provided number of daltons.
+    #[must_use]
+    pub const fn from_daltons(daltons: f64) -> Self {
+        Self(daltons)
+    }
+
+    /// Creates a mass from the provided number of kilodaltons.
+    #[must_use]
+    pub const fn from_kilodaltons(kilodaltons: f64) -> Self {
+        Self(kilodaltons * 1000.0)
+    }
+
+    /// Returns the mass in daltons.
+    #[must_use]
+    pub const fn daltons(self) -> f64 {
+        self.0
+    }
+
+    /// Returns the mass in kilodaltons.
+    #[must_use]
+    pub const fn kilodaltons(self) -> f64 {
+        self.0 / 1000.0
+    }
+
+    /// Returns the mass over charge ratio of an ion of this mass carrying
+    /// the provided charge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::units::Mass;
+    ///
+    /// let mz = Mass::from_daltons(1000.0).over_charge(-2.0);
+    /// assert!((mz.thomsons() + 500.0).abs() < f64::EPSILON);
+    /// ```
+    #[must_use]
+    pub const fn over_charge(self, charge: f64) -> Mz {
+        Mz(self.0 / charge)
+    }
+
+    /// Returns the absolute value of the mass.
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Returns the difference between this mass and the provided reference
+    /// mass, in parts per million of the reference mass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use molecular_formulas::units::Mass;
+    ///
+    /// let observed = Mass::from_daltons(500.001);
+    /// let theoretical = Mass::from_daltons(500.0);
+    /// assert!((observed.ppm_from(theoretical) - 2.0).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    pub fn ppm_from(self, reference: Self) -> f64 {
+        (self.0 - reference.0) / reference.0 * 1e6
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+/// Mass over charge ratio expressed in thomsons (Th), i.e. daltons per
+/// elementary charge.
+///
+/// Ratios may be subtracted from each other, as in the spacing of the peaks
+/// of an isotope pattern, scaled by a factor and multiplied by a charge
+/// back into a [`Mass`].
+///
+/// # Examples
+///
+/// ```
+/// use molecular_formulas::units::Mz;
+///
+/// let ion = Mz::from_thomsons(500.5);
+/// assert!((ion.with_charge(2.0).daltons() - 1001.0).abs() < f64::EPSILON);
+/// assert_eq!(Mz::from_thomsons(9.25).to_string(), "9.25 Th");
+/// ```
+pub struct Mz(f64);
+
+impl Mz {
+    /// The ratio of zero thomsons.
+    pub const ZERO: Self = Self(0.0);
+
+    /// Creates a mass over charge ratio from the provided number of
+    /// thomsons.
+    #[must_use]
+    pub const fn from_thomsons(thomsons: f64) -> Self {
+        Self(thomsons)
+    }
+
+    /// Returns the mass over charge ratio in thomsons.
+    #[must_use]
+    pub const fn thomsons(self) -> f64 {
+        self.0
+    }
+
+    /// Returns the mass of an ion of this ratio carrying the provided
+    /// charge.
+    #[must_use]
+    pub const fn with_charge(self, charge: f64) -> Mass {
+        Mass(self.0 * charge)
+    }
+
+    /// Returns the absolute value of the ratio.
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Returns the difference between this ratio and the provided reference
+    /// ratio, in parts per million of the reference ratio.
+    #[must_use]
+    pub fn ppm_from(self, reference: Self) -> f64 {
+        (self.0 - reference.0) / reference.0 * 1e6
+    }
+}
+
+/// Implements the arithmetic shared by the mass and ratio newtypes.
+macro_rules! impl_unit_arithmetic {
+    ($unit:ident, $symbol:literal) => {
+        impl Add for $unit {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $unit {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $unit {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $unit {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $unit {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $unit {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Mul<$unit> for f64 {
+            type Output = $unit;
+
+            fn mul(self, rhs: $unit) -> $unit {
+                $unit(self * rhs.0)
+            }
+        }
+
+        impl Div<f64> for $unit {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl Div for $unit {
+            type Output = f64;
+
+            fn div(self, rhs: Self) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl Sum for $unit {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|value| value.0).sum())
+            }
+        }
+
+        impl From<$unit> for f64 {
+            fn from(value: $unit) -> f64 {
+                value.0
+            }
+        }
+
+        impl Display for $unit {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Display::fmt(&self.0, f)?;
+                write!(f, " {}", $symbol)
+            }
+        }
+    };
+}
+
+impl_unit_arithmetic!(Mass, "Da");
+impl_unit_arithmetic!(Mz, "Th");
+
+/// Trait providing the masses of the formulas wrapped in their unit, as
+/// returned as bare `f64` values by
+/// [`MolecularFormula`](crate::MolecularFormula)
+/// and [`ChargedMolecularFormula`].
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use molecular_formulas::{
+///     prelude::*,
+///     units::{Mass, TypedMasses},
+/// };
+///
+/// let sulfate: ChemicalFormula = ChemicalFormula::from_str("SO4-2").unwrap();
+/// let mass: Mass = sulfate.typed_isotopologue_mass_with_charge();
+/// let mz = sulfate.typed_mz(false, true).unwrap();
+/// assert!((mz.thomsons() - 47.976_413).abs() < 1e-6);
+/// assert!((mz.with_charge(2.0) - mass).abs() < Mass::from_daltons(1e-9));
+/// ```
+pub trait TypedMasses: ChargedMolecularFormula {
+    /// Returns the isotopologue mass, without considering the charge.
+    fn typed_isotopologue_mass(&self) -> Mass {
+        Mass(self.isotopologue_mass())
+    }
+
+    /// Returns the isotopologue mass with the mass of the electrons lost or
+    /// gained because of the charge considered.
+    fn typed_isotopologue_mass_with_charge(&self) -> Mass {
+        Mass(self.isotopologue_mass_with_charge())
+    }
+
+    /// Returns the molar mass, i.e. the average mass of the formula under
+    /// the natural isotopic abundances.
+    fn typed_molar_mass(&self) -> Mass {
+        Mass(self.molar_mass())
+    }
+
+    /// Returns the mass over charge ratio, or None if the formula is
+    /// neutral, as returned by [`mz`](ChargedMolecularFormula::mz).
+    fn typed_mz(&self, polarity_aware: bool, electron_correction: bool) -> Option<Mz> {
+        self.mz(polarity_aware, electron_correction).map(Mz)
+    }
+}
+
+impl<M: ChargedMolecularFormula> TypedMasses for M {}
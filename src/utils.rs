@@ -1,8 +1,156 @@
 //! Subroutines for displaying tokens.
 
-use elements_rs::{ElementVariant, Isotope, MassNumber};
+use elements_rs::{Element, ElementVariant, Isotope, MassNumber};
+use num_traits::{CheckedAdd, CheckedMul};
 
-use crate::{SuperscriptMinus, SuperscriptPlus, superscript_digits_ltr};
+use crate::{
+    ChargeLike, CountLike, MolecularFormula, MolecularTree, NumberLike, SuperscriptMinus,
+    SuperscriptPlus, errors::NumericError, signed_charge, superscript_digits_ltr,
+};
+
+/// Converts a small constant into the provided number type.
+///
+/// # Arguments
+/// * `value` - The value to convert, which is accumulated by unit steps.
+pub(crate) fn number_from_u8<N: NumberLike>(value: u8) -> N {
+    (0..value).fold(N::ZERO, |total, _| total + N::ONE)
+}
+
+/// Converts a count into a `usize`, saturating at `usize::MAX` on targets
+/// where the count type is wider than `usize`.
+pub(crate) fn saturating_usize<Count: CountLike>(count: Count) -> usize {
+    count.try_into().unwrap_or(usize::MAX)
+}
+
+/// Converts a count into a `u64`, saturating at `u64::MAX` for wider count
+/// types.
+pub(crate) fn saturating_u64<Count: CountLike>(count: Count) -> u64 {
+    let count: i128 = count.into();
+    u64::try_from(count).unwrap_or(u64::MAX)
+}
+
+/// Count wide enough to hold the total count of an element in any formula
+/// built from the supported count types, whatever the count type of the
+/// formula, as used to compare element counts without walking every atom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct WideCount(pub(crate) i128);
+
+impl<Count: Into<i128>> From<Count> for WideCount {
+    fn from(count: Count) -> Self {
+        Self(count.into())
+    }
+}
+
+impl core::ops::Add for WideCount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl core::ops::Mul for WideCount {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 * other.0)
+    }
+}
+
+impl num_traits::CheckedAdd for WideCount {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+}
+
+impl num_traits::CheckedMul for WideCount {
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        self.0.checked_mul(other.0).map(Self)
+    }
+}
+
+impl num_traits::Zero for WideCount {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl num_traits::ConstZero for WideCount {
+    const ZERO: Self = Self(0);
+}
+
+impl num_traits::One for WideCount {
+    fn one() -> Self {
+        Self(1)
+    }
+}
+
+impl num_traits::ConstOne for WideCount {
+    const ONE: Self = Self(1);
+}
+
+/// Returns the total count of the provided element in the provided formula,
+/// counting repeating units and mixtures according to their counts, and
+/// saturating at `i128::MAX`.
+pub(crate) fn wide_count_of_element<M: MolecularFormula>(formula: &M, element: Element) -> i128 {
+    formula
+        .counted_mixtures()
+        .try_fold(WideCount(0), |total, (count, tree)| {
+            tree.count_of_element::<WideCount>(element)?
+                .checked_mul(&WideCount::from(count))
+                .and_then(|count| total.checked_add(&count))
+        })
+        .map_or(i128::MAX, |total| total.0)
+}
+
+/// Returns the total count of the provided isotope in the provided formula,
+/// counting repeating units and mixtures according to their counts, and
+/// saturating at `i128::MAX`.
+pub(crate) fn wide_count_of_isotope<M: MolecularFormula>(formula: &M, isotope: Isotope) -> i128 {
+    formula
+        .counted_mixtures()
+        .try_fold(WideCount(0), |total, (count, tree)| {
+            tree.count_of_isotope::<WideCount>(isotope)?
+                .checked_mul(&WideCount::from(count))
+                .and_then(|count| total.checked_add(&count))
+        })
+        .map_or(i128::MAX, |total| total.0)
+}
+
+/// Converts a count into another count type.
+///
+/// # Errors
+///
+/// * If the count cannot be represented by the target count type.
+pub(crate) fn convert_count<From: CountLike, To: CountLike>(
+    count: From,
+) -> Result<To, NumericError> {
+    let count: i128 = count.into();
+    u64::try_from(count)
+        .ok()
+        .and_then(|count| To::try_from(count).ok())
+        .ok_or(NumericError::PositiveOverflow)
+}
+
+/// Converts a charge into another charge type.
+///
+/// # Errors
+///
+/// * If the charge cannot be represented by the target charge type, with an
+///   error matching its sign.
+pub(crate) fn convert_charge<From: ChargeLike, To: ChargeLike>(
+    charge: From,
+) -> Result<To, NumericError> {
+    let charge: i128 = charge.into();
+    let magnitude = u64::try_from(charge.unsigned_abs()).map_err(|_| {
+        if charge > 0 { NumericError::PositiveOverflow } else { NumericError::NegativeOverflow }
+    })?;
+    signed_charge(magnitude, charge > 0)
+}
 
 /// Displays an isotope in the format `[<superscript_mass><element>]`.
 ///
@@ -27,15 +175,16 @@ pub(crate) fn display_isotope(
 /// The magnitude is displayed only if it is greater than 1.
 ///
 /// # Arguments
-/// * `charge` - The charge value (will be converted to i64).
+/// * `charge` - The charge value (will be converted to i128).
 /// * `f` - The formatter to write to.
-pub(crate) fn display_charge<C: Into<i64>>(
+pub(crate) fn display_charge<C: Into<i128>>(
     charge: C,
     f: &mut core::fmt::Formatter<'_>,
 ) -> core::fmt::Result {
-    // We convert the charge into i64 to avoid potential overflows when
-    // executing the `abs` method on smaller integer types.
-    let charge: i64 = charge.into();
+    // We convert the charge into i128 to avoid potential overflows when
+    // executing the `abs` method on the most negative value of any supported
+    // integer type.
+    let charge: i128 = charge.into();
     if charge.abs() > 1 {
         for digit in superscript_digits_ltr(charge) {
             write!(f, "{digit}")?;
@@ -85,4 +234,11 @@ mod tests {
         assert_eq!(ChargeWrapper(10).to_string(), "¹⁰⁺");
         assert_eq!(ChargeWrapper(-10).to_string(), "¹⁰⁻");
     }
+
+    #[test]
+    fn test_number_from_u8() {
+        assert_eq!(number_from_u8::<u8>(0), 0);
+        assert_eq!(number_from_u8::<u16>(12), 12);
+        assert_eq!(number_from_u8::<i32>(255), 255);
+    }
 }
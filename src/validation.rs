@@ -0,0 +1,401 @@
+//! Module providing a conformance harness comparing the masses computed by
+//! this crate against the masses of a reference dataset, such as the
+//! monoisotopic masses distributed by PubChem.
+//!
+//! The dataset is provided as any iterator of formulas and expected masses,
+//! so that it may be streamed from disk, from the network or from memory.
+#![cfg(feature = "validation")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Display, str::FromStr};
+use std::time::{Duration, Instant};
+
+use num_traits::Zero;
+
+use crate::ChargedMolecularFormula;
+
+/// Mass difference below which a computed mass is considered an exact match.
+const EXACT_MATCH_TOLERANCE: f64 = 1e-10;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Configuration of a validation run against a reference dataset.
+///
+/// # Example
+///
+/// ```rust
+/// use molecular_formulas::{prelude::*, validation::Validator};
+///
+/// let dataset = [("H2O", 18.010565), ("C6H12O6", 180.063388), ("C6H12O7", 180.063388)];
+/// let stats = Validator::new(0.001).validate::<ChemicalFormula<u32, i32>, _>(dataset);
+/// assert_eq!(stats.total(), 3);
+/// assert_eq!(stats.within_tolerance(), 2);
+/// assert_eq!(stats.mismatches(), 1);
+/// assert_eq!(stats.worst_mismatches()[0].formula(), "C6H12O7");
+/// ```
+pub struct Validator {
+    /// The maximal absolute mass difference tolerated.
+    tolerance: f64,
+    /// The number of largest mismatches and of parse failures retained.
+    retained: usize,
+}
+
+impl Validator {
+    /// Creates a validator tolerating the provided absolute mass difference,
+    /// which retains the 30 largest mismatches.
+    ///
+    /// # Panics
+    ///
+    /// * If the tolerance is negative or not finite.
+    #[must_use]
+    pub fn new(tolerance: f64) -> Self {
+        assert!(
+            tolerance.is_finite() && tolerance >= 0.0,
+            "The tolerance must be a non-negative finite number"
+        );
+        Self { tolerance, retained: 30 }
+    }
+
+    /// Sets the number of largest mismatches, and of parse failures, which
+    /// are retained in the report.
+    #[must_use]
+    pub fn with_retained(mut self, retained: usize) -> Self {
+        self.retained = retained;
+        self
+    }
+
+    /// Parses each formula of the provided dataset and compares its
+    /// monoisotopic mass, without considering the charge, to the expected
+    /// mass.
+    pub fn validate<M, S>(&self, dataset: impl IntoIterator<Item = (S, f64)>) -> ValidationStats
+    where
+        M: ChargedMolecularFormula + FromStr,
+        S: AsRef<str>,
+    {
+        self.validate_entries::<M, _, usize>(
+            dataset.into_iter().map(|(formula, expected_mass)| (None, formula, expected_mass)),
+        )
+    }
+
+    /// Validates the provided dataset as [`validate`](Self::validate) does,
+    /// with each entry identified by a key, such as the PubChem compound
+    /// identifier, which the retained mismatches report.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use molecular_formulas::{prelude::*, validation::Validator};
+    ///
+    /// let dataset = [(962, "H2O", 18.010565), (5793, "C6H12O7", 180.063388)];
+    /// let stats = Validator::new(0.001).validate_keyed::<ChemicalFormula<u32, i32>, _, _>(dataset);
+    /// assert_eq!(stats.worst_mismatches()[0].key(), Some("5793"));
+    /// ```
+    pub fn validate_keyed<M, S, K>(
+        &self,
+        dataset: impl IntoIterator<Item = (K, S, f64)>,
+    ) -> ValidationStats
+    where
+        M: ChargedMolecularFormula + FromStr,
+        S: AsRef<str>,
+        K: Display,
+    {
+        self.validate_entries::<M, _, _>(
+            dataset
+                .into_iter()
+                .map(|(key, formula, expected_mass)| (Some(key), formula, expected_mass)),
+        )
+    }
+
+    /// Validates the provided entries, whose keys are only formatted for the
+    /// retained mismatches.
+    fn validate_entries<M, S, K>(
+        &self,
+        dataset: impl Iterator<Item = (Option<K>, S, f64)>,
+    ) -> ValidationStats
+    where
+        M: ChargedMolecularFormula + FromStr,
+        S: AsRef<str>,
+        K: Display,
+    {
+        let start = Instant::now();
+        let mut stats = ValidationStats { tolerance: self.tolerance, ..ValidationStats::default() };
+        for (index, (key, formula, expected_mass)) in dataset.enumerate() {
+            stats.total += 1;
+            let Ok(parsed) = M::from_str(formula.as_ref()) else {
+                stats.parse_failures += 1;
+                if stats.failed_formulas.len() < self.retained {
+                    stats.failed_formulas.push((index, String::from(formula.as_ref())));
+                }
+                continue;
+            };
+            let calculated_mass = parsed.isotopologue_mass();
+            let error = (calculated_mass - expected_mass).abs();
+            if error < EXACT_MATCH_TOLERANCE {
+                stats.exact_matches += 1;
+            } else if error <= self.tolerance {
+                stats.within_tolerance += 1;
+            } else {
+                if parsed.charge().is_zero() {
+                    stats.neutral_mismatches += 1;
+                } else {
+                    stats.ion_mismatches += 1;
+                }
+                self.retain(
+                    &mut stats.worst_mismatches,
+                    Mismatch {
+                        index,
+                        key: key.map(|key| key.to_string()),
+                        formula: String::from(formula.as_ref()),
+                        expected_mass,
+                        calculated_mass,
+                    },
+                );
+            }
+        }
+        stats.elapsed = start.elapsed();
+        stats
+    }
+
+    /// Inserts the provided mismatch among the retained ones, which are kept
+    /// sorted by decreasing error.
+    fn retain(&self, mismatches: &mut Vec<Mismatch>, mismatch: Mismatch) {
+        let position = mismatches.partition_point(|other| other.error() >= mismatch.error());
+        if position < self.retained {
+            mismatches.insert(position, mismatch);
+            mismatches.truncate(self.retained);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Entry of the dataset whose computed mass differs from the expected one by
+/// more than the tolerance.
+pub struct Mismatch {
+    /// The zero-based position of the entry in the dataset.
+    index: usize,
+    /// The key of the entry, if the dataset is keyed.
+    key: Option<String>,
+    /// The formula, as provided in the dataset.
+    formula: String,
+    /// The expected mass.
+    expected_mass: f64,
+    /// The mass computed by this crate.
+    calculated_mass: f64,
+}
+
+impl Mismatch {
+    /// Returns the zero-based position of the entry in the dataset.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the key of the entry, if the dataset is keyed.
+    #[must_use]
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Returns the formula, as provided in the dataset.
+    #[must_use]
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns the expected mass.
+    #[must_use]
+    pub fn expected_mass(&self) -> f64 {
+        self.expected_mass
+    }
+
+    /// Returns the mass computed by this crate.
+    #[must_use]
+    pub fn calculated_mass(&self) -> f64 {
+        self.calculated_mass
+    }
+
+    /// Returns the absolute difference between the computed and the expected
+    /// masses.
+    #[must_use]
+    pub fn error(&self) -> f64 {
+        (self.calculated_mass - self.expected_mass).abs()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Report of a validation run against a reference dataset.
+pub struct ValidationStats {
+    /// The number of entries in the dataset.
+    total: usize,
+    /// The number of formulas which could not be parsed.
+    parse_failures: usize,
+    /// The number of masses matching exactly.
+    exact_matches: usize,
+    /// The number of masses within the tolerance, but not exactly matching.
+    within_tolerance: usize,
+    /// The number of mismatching masses of charged formulas.
+    ion_mismatches: usize,
+    /// The number of mismatching masses of neutral formulas.
+    neutral_mismatches: usize,
+    /// The maximal absolute mass difference tolerated.
+    tolerance: f64,
+    /// The time required by the validation.
+    elapsed: Duration,
+    /// The largest mismatches, by decreasing error.
+    worst_mismatches: Vec<Mismatch>,
+    /// The first formulas which could not be parsed, with their position.
+    failed_formulas: Vec<(usize, String)>,
+}
+
+impl ValidationStats {
+    /// Returns the number of entries in the dataset.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of formulas which could not be parsed.
+    #[must_use]
+    pub fn parse_failures(&self) -> usize {
+        self.parse_failures
+    }
+
+    /// Returns the number of masses matching exactly.
+    #[must_use]
+    pub fn exact_matches(&self) -> usize {
+        self.exact_matches
+    }
+
+    /// Returns the number of masses within the tolerance, but not exactly
+    /// matching.
+    #[must_use]
+    pub fn within_tolerance(&self) -> usize {
+        self.within_tolerance
+    }
+
+    /// Returns the number of masses differing by more than the tolerance.
+    #[must_use]
+    pub fn mismatches(&self) -> usize {
+        self.ion_mismatches + self.neutral_mismatches
+    }
+
+    /// Returns the number of mismatching masses of charged formulas.
+    #[must_use]
+    pub fn ion_mismatches(&self) -> usize {
+        self.ion_mismatches
+    }
+
+    /// Returns the number of mismatching masses of neutral formulas.
+    #[must_use]
+    pub fn neutral_mismatches(&self) -> usize {
+        self.neutral_mismatches
+    }
+
+    /// Returns the maximal absolute mass difference tolerated.
+    #[must_use]
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Returns the time required by the validation.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the percentage of entries whose mass is within the tolerance,
+    /// or zero for an empty dataset.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn accuracy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.exact_matches + self.within_tolerance) as f64 / self.total as f64 * 100.0
+    }
+
+    /// Returns the number of entries processed per second, or zero if the
+    /// validation took no measurable time.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn throughput(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.total as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the retained largest mismatches, by decreasing error.
+    #[must_use]
+    pub fn worst_mismatches(&self) -> &[Mismatch] {
+        &self.worst_mismatches
+    }
+
+    /// Returns the first formulas which could not be parsed, with their
+    /// zero-based position in the dataset.
+    #[must_use]
+    pub fn failed_formulas(&self) -> &[(usize, String)] {
+        &self.failed_formulas
+    }
+}
+
+impl Display for ValidationStats {
+    /// Writes the summary of the validation as a Markdown table.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "| Metric | Value |")?;
+        writeln!(f, "|--------|-------|")?;
+        writeln!(f, "| Total processed | {} |", self.total)?;
+        writeln!(f, "| Total time required | {:.2} s |", self.elapsed.as_secs_f64())?;
+        writeln!(f, "| Processing speed | {:.2} cmp/s |", self.throughput())?;
+        writeln!(f, "| Parse failures | {} |", self.parse_failures)?;
+        writeln!(f, "| Exact matches | {} |", self.exact_matches)?;
+        writeln!(f, "| Within tolerance | {} |", self.within_tolerance)?;
+        writeln!(f, "| Mismatches | {} |", self.mismatches())?;
+        writeln!(f, "|   - Ion mismatches | {} |", self.ion_mismatches)?;
+        writeln!(f, "|   - Neutral mismatches | {} |", self.neutral_mismatches)?;
+        writeln!(f, "| Mass accuracy (within {}) | {:.2}% |", self.tolerance, self.accuracy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChemicalFormula;
+
+    #[test]
+    fn test_retains_largest_mismatches() {
+        let dataset = [("CH4", 10.0), ("H2O", 0.0), ("He", 0.0), ("O2", 40.0), ("X", 0.0)];
+        let stats = Validator::new(0.001)
+            .with_retained(2)
+            .validate::<ChemicalFormula<u16, i16>, _>(dataset);
+        assert_eq!(stats.total(), 5);
+        assert_eq!(stats.parse_failures(), 1);
+        assert_eq!(stats.failed_formulas(), &[(4, String::from("X"))]);
+        assert_eq!(stats.neutral_mismatches(), 4);
+        let formulas: Vec<&str> = stats.worst_mismatches().iter().map(Mismatch::formula).collect();
+        assert_eq!(formulas, ["H2O", "O2"]);
+        assert_eq!(stats.worst_mismatches()[1].index(), 3);
+    }
+
+    #[test]
+    fn test_ion_mismatches_and_accuracy() {
+        let dataset = [("Na+", 0.0), ("Na+", 22.989769), ("Na", 22.989769), ("Na", 22.99)];
+        let stats = Validator::new(0.01).validate::<ChemicalFormula<u16, i16>, _>(dataset);
+        assert_eq!(stats.exact_matches(), 0);
+        assert_eq!(stats.within_tolerance(), 3);
+        assert_eq!(stats.ion_mismatches(), 1);
+        assert_eq!(stats.neutral_mismatches(), 0);
+        assert!((stats.accuracy() - 75.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_empty_dataset() {
+        let stats = Validator::new(0.01).validate::<ChemicalFormula<u16, i16>, &str>([]);
+        assert_eq!(stats.total(), 0);
+        assert!(stats.accuracy().abs() < f64::EPSILON);
+        let stats = ValidationStats::default();
+        assert!(stats.throughput().abs() < f64::EPSILON);
+        assert!(stats.to_string().contains("| Processing speed | 0.00 cmp/s |"));
+    }
+}
@@ -0,0 +1,247 @@
+//! Module providing `wasm-bindgen` wrappers around the molecular formulas, so
+//! that web front-ends can run the same parsing, mass computations and
+//! isotope patterns client-side.
+#![cfg(feature = "wasm")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{ChargedMolecularFormula, ChemicalFormula, LabeledFormula, MolecularFormula};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// JavaScript-facing wrapper around a [`ChemicalFormula`].
+pub struct JsChemicalFormula {
+    /// The wrapped chemical formula.
+    formula: ChemicalFormula,
+}
+
+impl From<ChemicalFormula> for JsChemicalFormula {
+    fn from(formula: ChemicalFormula) -> Self {
+        Self { formula }
+    }
+}
+
+impl From<JsChemicalFormula> for ChemicalFormula {
+    fn from(formula: JsChemicalFormula) -> Self {
+        formula.formula
+    }
+}
+
+#[wasm_bindgen]
+impl JsChemicalFormula {
+    #[wasm_bindgen(constructor)]
+    /// Parses the provided string into a chemical formula.
+    ///
+    /// # Errors
+    ///
+    /// * If the provided string is not a valid chemical formula, the parser
+    ///   error message is returned as a JavaScript error.
+    pub fn new(formula: &str) -> Result<JsChemicalFormula, JsError> {
+        ChemicalFormula::from_str(formula)
+            .map(Self::from)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    /// Returns the normalized string representation of the formula.
+    #[must_use]
+    pub fn to_string(&self) -> String {
+        self.formula.to_string()
+    }
+
+    #[wasm_bindgen(js_name = isotopologueMass)]
+    /// Returns the isotopologue mass of the formula, ignoring the charge.
+    #[must_use]
+    pub fn isotopologue_mass(&self) -> f64 {
+        self.formula.isotopologue_mass()
+    }
+
+    #[wasm_bindgen(js_name = isotopologueMassWithCharge)]
+    /// Returns the isotopologue mass of the formula, accounting for the
+    /// electrons gained or lost by the charge.
+    #[must_use]
+    pub fn isotopologue_mass_with_charge(&self) -> f64 {
+        self.formula.isotopologue_mass_with_charge()
+    }
+
+    #[wasm_bindgen(js_name = isotopologueMassOverCharge)]
+    /// Returns the isotopologue mass over charge ratio of the formula.
+    #[must_use]
+    pub fn isotopologue_mass_over_charge(&self) -> f64 {
+        self.formula.isotopologue_mass_over_charge()
+    }
+
+    #[wasm_bindgen(js_name = electronMassCorrection)]
+    /// Returns the mass of the electrons lost or gained by the charge of the
+    /// formula, negative for cations and positive for anions.
+    #[must_use]
+    pub fn electron_mass_correction(&self) -> f64 {
+        self.formula.electron_mass_correction()
+    }
+
+    /// Returns the mass over charge ratio of the formula, divided by the
+    /// signed charge when `polarity_aware` is true and by its absolute value
+    /// otherwise, and including the mass of the electrons lost or gained
+    /// when `electron_correction` is true. Returns undefined for neutral
+    /// formulas.
+    #[must_use]
+    pub fn mz(&self, polarity_aware: bool, electron_correction: bool) -> Option<f64> {
+        self.formula.mz(polarity_aware, electron_correction)
+    }
+
+    #[wasm_bindgen(js_name = molarMass)]
+    /// Returns the molar mass of the formula.
+    #[must_use]
+    pub fn molar_mass(&self) -> f64 {
+        self.formula.molar_mass()
+    }
+
+    /// Returns the overall charge of the formula.
+    #[must_use]
+    pub fn charge(&self) -> f64 {
+        self.formula.charge()
+    }
+
+    #[wasm_bindgen(js_name = isotopePattern)]
+    /// Returns the isotope pattern of the formula, without considering its
+    /// charge, discarding the peaks whose abundance is below the provided
+    /// threshold, as computed by [`LabeledFormula::isotope_pattern`] with
+    /// the explicit isotopes of the formula fully enriched.
+    ///
+    /// # Errors
+    ///
+    /// * If the count of any element of the formula does not fit in a `u64`,
+    ///   the error message is returned as a JavaScript error.
+    pub fn isotope_pattern(&self, min_abundance: f64) -> Result<JsIsotopePattern, JsError> {
+        LabeledFormula::new(self.formula.clone())
+            .map(|labeled| JsIsotopePattern::from(labeled.isotope_pattern(min_abundance)))
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = aPlusNMass)]
+    /// Returns the abundance-weighted mass of the A+n isotopologue peak of
+    /// the formula, as returned by
+    /// [`a_plus_n_mass`](MolecularFormula::a_plus_n_mass).
+    #[must_use]
+    pub fn a_plus_n_mass(&self, n: u32) -> Option<f64> {
+        self.formula.a_plus_n_mass(u64::from(n))
+    }
+
+    #[wasm_bindgen(js_name = aPlusNAbundance)]
+    /// Returns the intensity of the A+n isotopologue peak of the formula
+    /// relative to its A peak, as returned by
+    /// [`a_plus_n_abundance`](MolecularFormula::a_plus_n_abundance).
+    #[must_use]
+    pub fn a_plus_n_abundance(&self, n: u32) -> f64 {
+        self.formula.a_plus_n_abundance(u64::from(n))
+    }
+
+    #[wasm_bindgen(js_name = isHillSorted)]
+    /// Returns whether the formula is sorted according to the Hill system.
+    #[must_use]
+    pub fn is_hill_sorted(&self) -> bool {
+        self.formula.is_hill_sorted()
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+/// JavaScript-facing isotope pattern, as the masses and abundances of its
+/// peaks sorted by mass.
+pub struct JsIsotopePattern {
+    /// The masses of the peaks, sorted increasingly.
+    masses: Vec<f64>,
+    /// The abundances of the peaks, in the order of their masses.
+    abundances: Vec<f64>,
+}
+
+impl From<Vec<(f64, f64)>> for JsIsotopePattern {
+    fn from(pattern: Vec<(f64, f64)>) -> Self {
+        let (masses, abundances) = pattern.into_iter().unzip();
+        Self { masses, abundances }
+    }
+}
+
+#[wasm_bindgen]
+impl JsIsotopePattern {
+    /// Returns the masses of the peaks, sorted increasingly.
+    #[must_use]
+    pub fn masses(&self) -> Vec<f64> {
+        self.masses.clone()
+    }
+
+    /// Returns the abundances of the peaks, in the order of their masses.
+    #[must_use]
+    pub fn abundances(&self) -> Vec<f64> {
+        self.abundances.clone()
+    }
+
+    /// Returns the number of peaks of the pattern.
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn length(&self) -> usize {
+        self.masses.len()
+    }
+}
+
+#[wasm_bindgen(js_name = normalizeFormula)]
+/// Parses the provided string and returns its normalized representation.
+///
+/// # Errors
+///
+/// * If the provided string is not a valid chemical formula, the parser error
+///   message is returned as a JavaScript error.
+pub fn normalize_formula(formula: &str) -> Result<String, JsError> {
+    JsChemicalFormula::new(formula).map(|formula| formula.to_string())
+}
+
+#[wasm_bindgen(js_name = isValidFormula)]
+/// Returns whether the provided string is a valid chemical formula.
+#[must_use]
+pub fn is_valid_formula(formula: &str) -> bool {
+    ChemicalFormula::<u16, i16>::from_str(formula).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_js_chemical_formula() {
+        let formula: ChemicalFormula = ChemicalFormula::from_str("H₂O").unwrap();
+        let wrapped = JsChemicalFormula::from(formula.clone());
+        assert_eq!(wrapped.to_string(), formula.to_string());
+        assert!((wrapped.isotopologue_mass() - formula.isotopologue_mass()).abs() < f64::EPSILON);
+        assert!((wrapped.molar_mass() - formula.molar_mass()).abs() < f64::EPSILON);
+        assert!(wrapped.charge().abs() < f64::EPSILON);
+        assert_eq!(ChemicalFormula::from(wrapped), formula);
+    }
+
+    #[test]
+    fn test_js_isotope_pattern() {
+        let formula: ChemicalFormula = ChemicalFormula::from_str("CH3Cl").unwrap();
+        let wrapped = JsChemicalFormula::from(formula.clone());
+        let expected = LabeledFormula::new(formula.clone()).unwrap().isotope_pattern(1e-4);
+        let pattern = wrapped.isotope_pattern(1e-4).unwrap();
+        assert_eq!(pattern.length(), expected.len());
+        assert_eq!(pattern.masses(), expected.iter().map(|(mass, _)| *mass).collect::<Vec<_>>());
+        assert_eq!(
+            pattern.abundances(),
+            expected.iter().map(|(_, abundance)| *abundance).collect::<Vec<_>>()
+        );
+        assert_eq!(wrapped.a_plus_n_mass(2), formula.a_plus_n_mass(2));
+        assert!((wrapped.a_plus_n_abundance(2) - formula.a_plus_n_abundance(2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_is_valid_formula() {
+        assert!(is_valid_formula("C6H12O6"));
+        assert!(!is_valid_formula("C6H12O6)"));
+    }
+}
@@ -0,0 +1,100 @@
+//! Submodule testing the user-defined abbreviations registered in the parser
+//! options.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+fn formula(s: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(s).unwrap()
+}
+
+#[test]
+fn test_abbreviation_expansion() {
+    let options = ParserOptions::new().with_abbreviation("Boc", &formula("C5H9O2")).unwrap();
+    let parsed =
+        ChemicalFormula::<u32, i32>::from_str_with_options("BocNHCH2COOH", &options).unwrap();
+    assert_eq!(parsed.to_string(), "(C₅H₉O₂)NHCH₂COOH");
+    assert!((parsed.isotopologue_mass() - formula("C7H13NO4").isotopologue_mass()).abs() < 1e-9);
+
+    let repeated = ChemicalFormula::<u32, i32>::from_str_with_options("Boc2NH", &options).unwrap();
+    assert_eq!(repeated.count_of_element::<u32>(Element::C), Some(10));
+    assert_eq!(repeated.count_of_element::<u32>(Element::N), Some(1));
+}
+
+#[test]
+fn test_longest_abbreviation_wins() {
+    let options = ParserOptions::new()
+        .with_abbreviation("Ac", &formula("C2H3O"))
+        .and_then(|options| options.with_abbreviation("AcO", &formula("C2H3O2")))
+        .unwrap();
+    let parsed = ChemicalFormula::<u32, i32>::from_str_with_options("AcOH", &options).unwrap();
+    assert_eq!(parsed.to_string(), "(C₂H₃O₂)H");
+    let parsed = ChemicalFormula::<u32, i32>::from_str_with_options("AcCl", &options).unwrap();
+    assert_eq!(parsed.count_of_element::<u32>(Element::Ac), Some(0));
+    assert_eq!(parsed.count_of_element::<u32>(Element::O), Some(1));
+}
+
+#[test]
+fn test_abbreviation_replacement_and_builtins() {
+    let options = ParserOptions::new()
+        .with_abbreviation("Ts", &formula("CH3"))
+        .and_then(|options| options.with_abbreviation("Ts", &formula("C7H7SO2")))
+        .unwrap();
+    assert_eq!(options.abbreviation("Ts"), Some("(C₇H₇SO₂)"));
+    let parsed = ChemicalFormula::<u32, i32>::from_str_with_options("TsCl", &options).unwrap();
+    assert_eq!(parsed.count_of_element::<u32>(Element::S), Some(1));
+    assert_eq!(parsed.count_of_element::<u32>(Element::Ts), Some(0));
+
+    // Built-in abbreviations keep being recognized.
+    let parsed = ChemicalFormula::<u32, i32>::from_str_with_options("TsOMe", &options).unwrap();
+    assert_eq!(parsed.count_of_element::<u32>(Element::C), Some(8));
+}
+
+#[test]
+fn test_charged_abbreviation() {
+    let options = ParserOptions::new().with_abbreviation("Tf", &formula("CF3SO3-")).unwrap();
+    let parsed = ChemicalFormula::<u32, i32>::from_str_with_options("NaTf", &options).unwrap();
+    assert_eq!(parsed.count_of_element::<u32>(Element::F), Some(3));
+    assert!((parsed.charge() - -1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_abbreviations_in_other_formulas() {
+    let options = ParserOptions::new().with_abbreviation("Alloc", &formula("C4H5O2")).unwrap();
+    let residual =
+        ResidualFormula::<u32, i32>::from_str_with_options("AllocNHR", &options).unwrap();
+    assert_eq!(residual.to_string(), "(C₄H₅O₂)NHR");
+    assert!(ResidualFormula::<u32, i32>::from_str("AllocNHR").is_err());
+}
+
+#[test]
+fn test_invalid_abbreviation_name() {
+    let boc = formula("C5H9O2");
+    for (name, error) in [
+        ("boc", ParserError::UnexpectedCharacter('b')),
+        ("Bo2", ParserError::UnexpectedCharacter('2')),
+        ("", ParserError::UnexpectedEndOfInput),
+    ] {
+        assert_eq!(ParserOptions::new().with_abbreviation(name, &boc), Err(error), "{name}");
+    }
+}
+
+#[test]
+fn test_invalid_abbreviation_formula() {
+    for (abbreviated, error) in [
+        ("2H2O", ParserError::UnexpectedCharacter('2')),
+        ("H2O.NaCl", ParserError::UnexpectedCharacter('.')),
+    ] {
+        let abbreviated = formula(abbreviated);
+        assert_eq!(
+            ParserOptions::new().with_abbreviation("Hyd", &abbreviated),
+            Err(error),
+            "{abbreviated}"
+        );
+    }
+    assert_eq!(
+        ParserOptions::new().with_abbreviation("Hyd", &ChemicalFormula::<u32, i32>::empty()),
+        Err(ParserError::EmptyMolecularTree)
+    );
+}
@@ -0,0 +1,81 @@
+//! Submodule testing the parsing, display and application of adducts written
+//! in the PSI mzTab-M notation.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{CompositionError, ParserError},
+    prelude::*,
+};
+
+#[test]
+fn test_adduct_round_trip() {
+    for adduct in [
+        "[M+H]1+",
+        "[M-H]1-",
+        "[2M+Na]1+",
+        "[M+2H]2+",
+        "[M+ACN+H]1+",
+        "[M+H-H2O]1+",
+        "[M+NH4]1+",
+        "[M+Cl]1-",
+    ] {
+        let parsed = Adduct::<u32, i32>::from_str(adduct).unwrap();
+        assert_eq!(parsed.to_string(), adduct);
+    }
+}
+
+#[test]
+fn test_adduct_lenient_charges() {
+    for (adduct, expected) in [
+        ("[M+H]+", "[M+H]1+"),
+        ("[M+2H]++", "[M+2H]2+"),
+        ("[M+2H]+2", "[M+2H]2+"),
+        ("[M-H]-", "[M-H]1-"),
+    ] {
+        let parsed = Adduct::<u32, i32>::from_str(adduct).unwrap();
+        assert_eq!(parsed.to_string(), expected);
+    }
+}
+
+#[test]
+fn test_adduct_errors() {
+    assert_eq!(Adduct::<u32, i32>::from_str("[M+H]0+"), Err(ParserError::ZeroCharge));
+    assert!(Adduct::<u32, i32>::from_str("M+H1+").is_err());
+    assert!(Adduct::<u32, i32>::from_str("[M+H").is_err());
+    assert!(Adduct::<u32, i32>::from_str("[M+H]").is_err());
+
+    let adduct = Adduct::<u32, i32>::from_str("[M-H2O+H]1+").unwrap();
+    let molecule = ChemicalFormula::<u32, i32>::from_str("CH4").unwrap();
+    assert_eq!(adduct.ion_formula(&molecule), Err(CompositionError::InsufficientAtoms(Element::O)));
+}
+
+#[test]
+fn test_adduct_ion_formula() {
+    let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    for (adduct, expected) in [
+        ("[M+H]1+", "C₆H₁₃O₆⁺"),
+        ("[M-H]1-", "C₆H₁₁O₆⁻"),
+        ("[2M+Na]1+", "C₁₂H₂₄NaO₁₂⁺"),
+        ("[M+2H]2+", "C₆H₁₄O₆²⁺"),
+        ("[M+H-H2O]1+", "C₆H₁₁O₅⁺"),
+    ] {
+        let adduct = Adduct::<u32, i32>::from_str(adduct).unwrap();
+        let ion = adduct.ion_formula(&glucose).unwrap();
+        assert_eq!(ion.to_string(), expected);
+    }
+}
+
+#[test]
+fn test_adduct_mass_over_charge() {
+    let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    let mass = glucose.isotopologue_mass();
+    for adduct in ["[M+H]1+", "[M-H]1-", "[2M+Na]1+", "[M+2H]2+", "[M+ACN+H]1+"] {
+        let adduct = Adduct::<u32, i32>::from_str(adduct).unwrap();
+        let ion = adduct.ion_formula(&glucose).unwrap();
+        let mz = adduct.ion_mass_over_charge(mass);
+        // The m/z of adducts is reported as a positive value.
+        let expected = ion.isotopologue_mass_over_charge().abs();
+        assert!((mz - expected).abs() < 1e-6, "{adduct}: {mz} != {expected}");
+    }
+}
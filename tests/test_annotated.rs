@@ -0,0 +1,98 @@
+//! Submodule testing the user metadata carried alongside any formula type
+//! through the `Annotated` wrapper.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Feature {
+    id: u32,
+    retention_time: f64,
+}
+
+fn annotated(formula: &str, id: u32) -> Annotated<ChemicalFormula<u32, i32>, Feature> {
+    Annotated::new(
+        ChemicalFormula::from_str(formula).unwrap(),
+        Feature { id, retention_time: f64::from(id) / 10.0 },
+    )
+}
+
+#[test]
+fn test_annotated_formulas_delegate_to_the_formula() {
+    let caffeine = annotated("C8H10N4O2", 3);
+    let formula = caffeine.formula().clone();
+    assert!((caffeine.isotopologue_mass() - formula.isotopologue_mass()).abs() < f64::EPSILON);
+    assert_eq!(caffeine.count_of_element::<u32>(Element::N), Some(4));
+    assert_eq!(caffeine.element_mask(), formula.element_mask());
+    assert_eq!(caffeine.to_string(), formula.to_string());
+
+    let ion = annotated("C6H13O6+", 1);
+    assert_eq!(ion.net_charge_integer(), Some(1));
+    assert!(
+        (ion.mz(true, true).unwrap() - ion.formula().mz(true, true).unwrap()).abs() < f64::EPSILON
+    );
+}
+
+#[test]
+fn test_annotations_are_preserved() {
+    let ion = annotated("C6H13O6+", 5);
+    let neutral = ion.clone().map(|formula| formula.neutralize());
+    assert_eq!(neutral.annotation(), ion.annotation());
+    assert_eq!(neutral.net_charge_integer(), Some(0));
+
+    let relabeled = neutral.map_annotation(|feature| feature.id);
+    assert_eq!(relabeled.annotation(), &5);
+
+    let mut formula = annotated("H2O", 1);
+    formula.annotation_mut().retention_time = 2.5;
+    let (water, feature) = formula.into_parts();
+    assert_eq!(water.to_string(), "H₂O");
+    assert_eq!(feature, Feature { id: 1, retention_time: 2.5 });
+}
+
+#[test]
+fn test_annotated_elements_take_the_default_annotation() {
+    let carbon = Annotated::<ChemicalFormula<u32, i32>, Feature>::from(Element::C);
+    assert_eq!(carbon.annotation(), &Feature::default());
+    assert_eq!(carbon.to_string(), "C");
+}
+
+#[test]
+fn test_annotated_formulas_sort_by_formula_first() {
+    let mut formulas = [annotated("H2O", 2), annotated("CH4", 1)]
+        .map(|formula| formula.map_annotation(|feature| feature.id));
+    formulas.sort();
+    let ids: Vec<u32> = formulas.iter().map(|formula| *formula.annotation()).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(formulas[0].formula() <= formulas[1].formula());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_annotated_formulas_roundtrip_through_serde() {
+    let formula = Annotated::new(
+        ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap(),
+        ("CHEBI:17234".to_string(), 12.5_f64),
+    );
+    let serialized = serde_json::to_string(&formula).unwrap();
+    assert_eq!(serialized, r#"{"formula":"C₆H₁₂O₆","annotation":["CHEBI:17234",12.5]}"#);
+    let deserialized: Annotated<ChemicalFormula<u32, i32>, (String, f64)> =
+        serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, formula);
+
+    let reordered: Annotated<ChemicalFormula<u32, i32>, u32> =
+        serde_json::from_str(r#"{"annotation":7,"formula":"H2O"}"#).unwrap();
+    assert_eq!((reordered.to_string().as_str(), *reordered.annotation()), ("H₂O", 7));
+
+    assert!(
+        serde_json::from_str::<Annotated<ChemicalFormula<u32, i32>, u32>>(r#"{"formula":"H2O"}"#)
+            .is_err()
+    );
+    assert!(
+        serde_json::from_str::<Annotated<ChemicalFormula<u32, i32>, u32>>(
+            r#"{"formula":"H2O","annotation":1,"id":2}"#
+        )
+        .is_err()
+    );
+}
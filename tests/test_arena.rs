@@ -0,0 +1,104 @@
+//! Submodule testing the parsing of formulas into arena-allocated trees,
+//! which must build the same formulas and report the same errors as the
+//! owned parser.
+#![cfg(feature = "arena")]
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+/// Formulas covering the grammar of the default notation.
+const FORMULAS: &[&str] = &[
+    "H2O",
+    "C6H12O6",
+    "CuSO4.5H2O",
+    "2H2O.NaCl",
+    "[Fe(CN)6]-4",
+    "[Cu(NH3)4]+2",
+    "Ca(OH)2",
+    "CH3(CH2)4CH3",
+    "[13C]H4",
+    "13CH4",
+    "C[13C]H6",
+    "H2¹⁸O",
+    "O[2]",
+    "C[2H]3",
+    ".CH3",
+    "CH3.",
+    ".OH-",
+    "NO3-",
+    "SO4-2",
+    "NH4+",
+    "Fe+3",
+    "Fe+2+",
+    "C₆H₅⁻",
+    "Me3N.BF3",
+    "PhCOOH",
+    "D2O",
+];
+
+#[test]
+fn test_arena_formulas_match_owned_formulas() {
+    let arena = Bump::new();
+    for formula in FORMULAS {
+        let owned = ChemicalFormula::<u16, i16>::from_str(formula);
+        let parsed = parse_in::<u16, i16>(&arena, formula);
+        match (owned, parsed) {
+            (Ok(owned), Ok(parsed)) => {
+                assert_eq!(parsed.to_formula(), owned, "{formula}");
+                assert_eq!(parsed.mixtures().len(), owned.counted_mixtures().count(), "{formula}");
+            }
+            (Err(owned), Err(parsed)) => assert_eq!(parsed, owned, "{formula}"),
+            (owned, parsed) => panic!("{formula}: {owned:?} but {parsed:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_arena_errors_match_owned_errors() {
+    let arena = Bump::new();
+    for formula in ["", "H2O)", "(H2O", "C300", "[Fe(CN)6", ".", "H2O..NaCl", "Xy", "-"] {
+        assert_eq!(
+            parse_in::<u8, i8>(&arena, formula).unwrap_err(),
+            ChemicalFormula::<u8, i8>::from_str(formula).unwrap_err(),
+            "{formula}"
+        );
+    }
+}
+
+#[test]
+fn test_arena_options() {
+    let arena = Bump::new();
+    let options = ParserOptions::default().with_prefix_handling(PrefixHandling::Skip);
+    let parsed = parse_in_with_options::<u16, i16>(&arena, "α-D-C6H12O6", &options).unwrap();
+    assert_eq!(
+        parsed.to_formula(),
+        ChemicalFormula::<u16, i16>::from_str_with_options("α-D-C6H12O6", &options).unwrap()
+    );
+    assert!(parse_in::<u16, i16>(&arena, "α-D-C6H12O6").is_err());
+}
+
+#[test]
+fn test_arena_batch() {
+    let mut arena = Bump::new();
+    for _ in 0..3 {
+        let parsed: Vec<_> = FORMULAS
+            .iter()
+            .filter_map(|formula| parse_in::<u32, i32>(&arena, formula).ok())
+            .collect();
+        assert!(parsed.iter().all(|formula| !formula.mixtures().is_empty()));
+        drop(parsed);
+        arena.reset();
+    }
+}
+
+#[test]
+fn test_arena_tree_structure() {
+    let arena = Bump::new();
+    let parsed = parse_in::<u16, i16>(&arena, "Ca(OH)2").unwrap();
+    let [(1, ChemicalTreeRef::Sequence(nodes))] = parsed.mixtures() else {
+        panic!("Expected a single sequence");
+    };
+    assert!(matches!(nodes[0], ChemicalTreeRef::Element(Element::Ca)));
+    assert!(matches!(nodes[1], ChemicalTreeRef::Repeat(_)));
+}
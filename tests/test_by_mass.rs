@@ -0,0 +1,69 @@
+//! Submodule testing the ordering of molecular formulas by mass.
+
+use std::{cmp::Ordering, str::FromStr};
+
+use molecular_formulas::prelude::*;
+
+fn by_mass(formula: &str) -> ByMass<ChemicalFormula<u32, i32>> {
+    ByMass(ChemicalFormula::from_str(formula).unwrap())
+}
+
+#[test]
+fn test_sorting_is_deterministic() {
+    let formulas = ["C6H12O6", "C2H6O", "H2O", "CH3CH2OH", "NH3", "CO", "N2", "C2H4", "CH4"];
+    let mut sorted: Vec<_> = formulas.iter().map(|formula| by_mass(formula)).collect();
+    sorted.sort();
+    let mut reversed: Vec<_> = formulas.iter().rev().map(|formula| by_mass(formula)).collect();
+    reversed.sort();
+    assert_eq!(sorted, reversed);
+    assert!(sorted.windows(2).all(|pair| pair[0].mass() <= pair[1].mass() + 1e-9));
+    let names: Vec<String> = sorted.iter().map(ToString::to_string).collect();
+    assert_eq!(names, ["CH₄", "NH₃", "H₂O", "CO", "N₂", "C₂H₄", "CH₃CH₂OH", "C₂H₆O", "C₆H₁₂O₆"]);
+}
+
+#[test]
+fn test_ties_are_broken_by_hill_notation() {
+    // Formulas with the same mass but different compositions.
+    let left = ChemicalFormula::<u32, i32>::from_str("C2H6O").unwrap();
+    let right = ChemicalFormula::<u32, i32>::from_str("H6C2O").unwrap();
+    assert!((left.isotopologue_mass() - right.isotopologue_mass()).abs() < 1e-9);
+    assert_ne!(left.cmp_by_mass(&right), Ordering::Equal);
+    assert_eq!(left.cmp_by_mass(&right), right.cmp_by_mass(&left).reverse());
+}
+
+#[test]
+fn test_binary_search_by_mass() {
+    let mut candidates: Vec<_> =
+        ["C8H10N4O2", "C6H12O6", "C9H11NO3", "C7H6O2", "C10H16N5O13P3"].map(by_mass).to_vec();
+    candidates.sort();
+    let start = candidates.partition_point(|candidate| candidate.mass() < 180.0);
+    let end = candidates.partition_point(|candidate| candidate.mass() < 182.0);
+    let found: Vec<String> =
+        candidates[start..end].iter().map(|candidate| candidate.as_ref().to_string()).collect();
+    assert_eq!(found, ["C₆H₁₂O₆", "C₉H₁₁NO₃"]);
+    assert_eq!(candidates[0].clone().into_inner().to_string(), "C₇H₆O₂");
+}
+
+#[test]
+fn test_sort_keys_agree_with_by_mass() {
+    let formulas = ["C6H12O6", "C2H6O", "H2O", "CH3CH2OH", "NH3", "CO", "N2", "C2H4", "CH4"];
+    let mut by_key: Vec<ChemicalFormula<u32, i32>> =
+        formulas.iter().map(|formula| ChemicalFormula::from_str(formula).unwrap()).collect();
+    by_key.sort_by_cached_key(MassSortKey::new);
+    let mut wrapped: Vec<_> = formulas.iter().map(|formula| by_mass(formula)).collect();
+    wrapped.sort();
+    let wrapped: Vec<_> = wrapped.into_iter().map(ByMass::into_inner).collect();
+    assert_eq!(by_key, wrapped);
+}
+
+#[test]
+fn test_sort_keys_of_large_counts() {
+    // The Hill notation is built from the element counts, and not by
+    // walking every atom of the formula.
+    let mut formulas: Vec<ChemicalFormula<u32, i32>> = ["C4000000000", "(CH2)4000000000"]
+        .into_iter()
+        .map(|formula| ChemicalFormula::from_str(formula).unwrap())
+        .collect();
+    formulas.sort_by_cached_key(MassSortKey::new);
+    assert_eq!(formulas[0].to_string(), "C₄₀₀₀₀₀₀₀₀₀");
+}
@@ -0,0 +1,64 @@
+//! Submodule testing the case-insensitive reading of formulas written in
+//! uppercase, as stored by legacy databases.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn folded(formula: &str) -> ChemicalFormula<u32, i32> {
+    let options = ParserOptions::new().with_case_folding(true);
+    ChemicalFormula::from_str_with_options(formula, &options).unwrap()
+}
+
+#[test]
+fn test_uppercase_formulas() {
+    for (uppercase, expected) in [
+        ("NACL", "NaCl"),
+        ("63F6BR.N", "63F6Br.N"),
+        ("C6H5BR", "C6H5Br"),
+        ("CACL2.2H2O", "CaCl2.2H2O"),
+        ("C2H5CL", "C2H5Cl"),
+        ("MG(OH)2", "Mg(OH)2"),
+        ("[13C]H3CL", "[13C]H3Cl"),
+        ("C6H12O6", "C6H12O6"),
+    ] {
+        assert_eq!(folded(uppercase), ChemicalFormula::from_str(expected).unwrap(), "{uppercase}");
+    }
+}
+
+#[test]
+fn test_mixed_case_formulas_are_unchanged() {
+    for formula in ["NaCl", "CoO", "CuSO4.5H2O", "C6H5Br"] {
+        assert_eq!(folded(formula), ChemicalFormula::from_str(formula).unwrap(), "{formula}");
+    }
+    let options = ParserOptions::new().with_case_folding(true);
+    assert!(options.case_folding());
+    assert!(!ParserOptions::new().case_folding());
+}
+
+#[test]
+fn test_ambiguous_runs_prefer_two_letter_symbols() {
+    assert_eq!(folded("CO"), ChemicalFormula::from_str("Co").unwrap());
+    assert_eq!(folded("NO"), ChemicalFormula::from_str("No").unwrap());
+    // The one-letter symbols are used when the rest of the run requires it.
+    assert_eq!(folded("CLI"), ChemicalFormula::from_str("ClI").unwrap());
+    assert_eq!(folded("NAC"), ChemicalFormula::from_str("NaC").unwrap());
+
+    let folding = CaseFolding::new("CH3COOH.NACL");
+    assert_eq!(folding.folded(), "CH3CoOH.NaCl");
+    assert!(folding.is_ambiguous());
+    let runs: Vec<(&str, &str, usize)> = folding
+        .ambiguities()
+        .iter()
+        .map(|ambiguity| (ambiguity.run(), ambiguity.reading(), ambiguity.number_of_readings()))
+        .collect();
+    assert_eq!(runs, [("COOH", "CoOH", 2)]);
+}
+
+#[test]
+fn test_unspellable_runs_are_reported_by_the_parser() {
+    let options = ParserOptions::new().with_case_folding(true);
+    assert!(ChemicalFormula::<u32, i32>::from_str_with_options("QX2", &options).is_err());
+    assert_eq!(CaseFolding::new("QX2").folded(), "QX2");
+    assert!(!CaseFolding::new("QX2").is_ambiguous());
+}
@@ -0,0 +1,67 @@
+//! Submodule testing the assignment of charges to the mixture components of
+//! salts.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ChargeAssignmentError, prelude::*};
+
+/// Parses the provided formula.
+fn parse(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_assign_charge_to_component() {
+    let mixture = parse("C20H18F3N4O8P.Na-");
+    let moved = mixture.assign_charge_to_component(1, 0).unwrap();
+    let moved = moved.assign_charge_to_component(0, -1).unwrap();
+    assert_eq!(moved, parse("C20H18F3N4O8P-.Na"));
+    assert_eq!(moved.net_charge_integer(), mixture.net_charge_integer());
+
+    // The charge replaces the nested charges of the component.
+    let complex = parse("[Fe(CN)6]-4.K+");
+    assert_eq!(complex.assign_charge_to_component(0, -3).unwrap(), parse("[Fe(CN)6]-3.K+"));
+    assert_eq!(parse("(H3O)+(Cl)-").assign_charge_to_component(0, 0).unwrap(), parse("(H3O)(Cl)"));
+
+    // The charge is carried by each copy of the component.
+    let repeated = parse("2NH4.SO4-2").assign_charge_to_component(0, 1).unwrap();
+    assert_eq!(repeated.to_string(), "2NH₄⁺.SO₄²⁻");
+    assert_eq!(repeated.net_charge_integer(), Some(0));
+    assert!(repeated.verify_roundtrip().is_ok());
+
+    assert_eq!(
+        mixture.assign_charge_to_component(2, 1),
+        Err(ChargeAssignmentError::MissingComponent(2))
+    );
+}
+
+#[test]
+fn test_assign_counter_ion_charges() {
+    for (formula, expected) in [
+        ("C20H18F3N4O8P.Na", "C20H18F3N4O8P-.Na+"),
+        ("C20H18F3N4O8P-.Na", "C20H18F3N4O8P-2.Na+"),
+        ("C20H18F3N4O8P.[23Na]", "C20H18F3N4O8P-.[23Na]+"),
+        ("C17H20NO3.Cl", "C17H20NO3+.Cl-"),
+        ("2C7H15N2O.SO4", "2C7H15N2O+.SO4-2"),
+        ("C12H22O14.Ca", "C12H22O14-2.Ca+2"),
+        ("C8H8O3.2NH4", "C8H8O3-2.2NH4+"),
+        ("Na.Cl", "Na+.Cl-"),
+    ] {
+        let assigned = parse(formula).assign_counter_ion_charges();
+        assert_eq!(assigned, Some(parse(expected)), "{formula}");
+        assert_eq!(
+            assigned.unwrap().net_charge_integer(),
+            parse(formula).net_charge_integer(),
+            "{formula}"
+        );
+    }
+}
+
+#[test]
+fn test_ambiguous_counter_ion_charges() {
+    for formula in
+        ["C6H12O6", "C6H12O6.C2H6O", "C6H12O6.C2H6O.Na", "2C7H15N2O.Na", "Na.Na", "C17H19NO3.HCl"]
+    {
+        assert_eq!(parse(formula).assign_counter_ion_charges(), None, "{formula}");
+    }
+}
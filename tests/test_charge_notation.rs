@@ -0,0 +1,86 @@
+//! Submodule testing the magnitude-first charge notation, as in `Fe2+`,
+//! which may be enabled in the parser options.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+fn magnitude_first(formula: &str) -> ChemicalFormula<u32, i32> {
+    let options = ParserOptions::new().with_charge_notation(ChargeNotation::MagnitudeFirst);
+    ChemicalFormula::from_str_with_options(formula, &options).unwrap()
+}
+
+fn sign_first(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_sign_first_is_the_default() {
+    assert_eq!(ParserOptions::new().charge_notation(), ChargeNotation::SignFirst);
+    let iron = sign_first("Fe2+");
+    assert_eq!(iron.count_of_element::<u32>(Element::Fe), Some(2));
+    assert!((iron.charge() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_textbook_style() {
+    for (magnitude_first_formula, sign_first_formula) in [
+        ("Fe2+", "Fe+2"),
+        ("Al3+", "Al+3"),
+        ("O2-", "O-2"),
+        ("Hg22+", "Hg2+2"),
+        ("[Fe(CN)6]4-", "[Fe(CN)6]-4"),
+        ("[Cu(NH3)4]2+", "[Cu(NH3)4]+2"),
+        ("NH4+", "NH4+"),
+        ("NO3-", "NO3-"),
+        ("H2PO4-", "H2PO4-"),
+        ("Fe3+.3Cl-", "Fe+3.3Cl-"),
+        ("2Na+.SO4(2-)", "2Na+.SO4-2"),
+        ("[Fe(CN)6]32-", "[Fe(CN)6]3-2"),
+    ] {
+        assert_eq!(
+            magnitude_first(magnitude_first_formula),
+            sign_first(sign_first_formula),
+            "Unexpected parse of {magnitude_first_formula}"
+        );
+    }
+}
+
+#[test]
+fn test_chebi_style() {
+    assert_eq!(magnitude_first("SO4(2-)"), sign_first("SO4-2"));
+    assert_eq!(magnitude_first("Fe(3+)"), sign_first("Fe+3"));
+    assert_eq!(magnitude_first("NH4(+)"), sign_first("NH4+"));
+    assert!((magnitude_first("C6H5O7(3-)").charge() + 3.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_pubchem_style_is_unaffected() {
+    for formula in ["Fe+2", "O4S-2", "H4N+", "C6H5O7-3", "C2H3O2-", "Cl-", "C6H12O6", "Ca(OH)2"] {
+        assert_eq!(magnitude_first(formula), sign_first(formula), "Unexpected parse of {formula}");
+    }
+}
+
+#[test]
+fn test_counts_preceding_a_sign_are_not_magnitudes() {
+    // Magnitudes are never zero or one, and counts are never one, so that
+    // the digits of these ions are counts.
+    for formula in ["C12H22O11+", "C27H43O12-", "C60+", "C20+", "H30+", "C6H5O71-"] {
+        assert_eq!(magnitude_first(formula), sign_first(formula), "Unexpected parse of {formula}");
+    }
+    let sucrose = magnitude_first("C12H22O11+");
+    assert_eq!(sucrose.count_of_element::<u32>(Element::O), Some(11));
+    assert!((sucrose.charge() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_ambiguous_magnitudes_are_rejected() {
+    let options = ParserOptions::new().with_charge_notation(ChargeNotation::MagnitudeFirst);
+    for formula in ["SO42-", "PO43-", "Cr2O72-", "2Na+.SO42-", "C10H12N52+"] {
+        assert_eq!(
+            ChemicalFormula::<u32, i32>::from_str_with_options(formula, &options),
+            Err(ParserError::UnprocessableNumber),
+            "Unexpected parse of {formula}"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Submodule testing the localized charged sites of formulas, as carried
+//! by zwitterions.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn formula(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_sites_of_zwitterions_are_preserved() {
+    for zwitterion in ["H3N+CH2COO-", "[NH3+]CH2COO-", "(H3N+)CH2(COO-)", "C5H11N+O2-"] {
+        let parsed = formula(zwitterion);
+        assert_eq!(parsed.positive_sites(), [(1, 1)], "{zwitterion}");
+        assert_eq!(parsed.negative_sites(), [(1, -1)], "{zwitterion}");
+        assert_eq!(parsed.net_charge_integer(), Some(0), "{zwitterion}");
+        assert!(parsed.is_zwitterion(), "{zwitterion}");
+        let reparsed = formula(&parsed.to_string());
+        assert_eq!(reparsed.positive_sites(), [(1, 1)], "{zwitterion}");
+        assert!(reparsed.is_zwitterion(), "{zwitterion}");
+    }
+}
+
+#[test]
+fn test_site_copies_account_for_counts() {
+    let dimer = formula("2H3N+CH2COO-");
+    assert_eq!(dimer.positive_sites(), [(2, 1)]);
+    assert_eq!(dimer.negative_sites(), [(2, -1)]);
+
+    let polyanion = formula("C6H8(OPO3-2)3(NH3+)2");
+    assert_eq!(polyanion.negative_sites(), [(3, -2)]);
+    assert_eq!(polyanion.positive_sites(), [(2, 1)]);
+    assert_eq!(polyanion.net_charge_integer(), Some(-4));
+    assert!(!polyanion.is_zwitterion());
+}
+
+#[test]
+fn test_formulas_without_localized_charges() {
+    let glucose = formula("C6H12O6");
+    assert!(glucose.positive_sites().is_empty());
+    assert!(glucose.negative_sites().is_empty());
+    assert!(!glucose.is_zwitterion());
+
+    let ammonium = formula("NH4+");
+    assert_eq!(ammonium.positive_sites(), [(1, 1)]);
+    assert!(ammonium.negative_sites().is_empty());
+    assert!(!ammonium.is_zwitterion());
+
+    let salt = formula("Ca+2.2Cl-");
+    assert_eq!(salt.positive_sites(), [(1, 2)]);
+    assert_eq!(salt.negative_sites(), [(2, -1)]);
+    assert!(!salt.is_zwitterion());
+}
+
+#[test]
+fn test_hydrated_zwitterions() {
+    let hydrate = formula("H3N+CH2COO-.2H2O");
+    assert!(hydrate.is_zwitterion());
+    let salt_of_zwitterion = formula("H3N+CH2COO-.Na+");
+    assert!(!salt_of_zwitterion.is_zwitterion());
+    assert_eq!(salt_of_zwitterion.positive_sites(), [(1, 1), (1, 1)]);
+}
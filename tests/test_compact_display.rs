@@ -0,0 +1,51 @@
+//! Submodule testing the compact display of formulas with giant counts and
+//! charges, and that compact displays are never parsed back silently.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn compact(formula: &str) -> String {
+    ChemicalFormula::<u64, i64>::from_str(formula).unwrap().display_compact().to_string()
+}
+
+#[test]
+fn test_compact_counts() {
+    assert_eq!(compact("(C2H4)999999"), "(C₂H₄)₉₉₉₉₉₉");
+    assert_eq!(compact("(C2H4)1000000"), "(C₂H₄)₁ₑ₆");
+    assert_eq!(compact("C1200000H2400002"), "C₁₂ₑ₅H~₂₄₀ₑ₄");
+    assert_eq!(compact("C18446744073709551615"), "C~₁₈₄ₑ₁₇");
+    assert_eq!(compact("3000000H2O.NaCl"), "3e6H₂O.NaCl");
+}
+
+#[test]
+fn test_compact_charges() {
+    assert_eq!(compact("Fe+3"), "Fe³⁺");
+    assert_eq!(compact("(C3H3O2)2000000-2000000"), "(C₃H₃O₂)₂ₑ₆²ᵉ⁶⁻");
+
+    let formula = ChemicalFormula::<u64, i64>::from_str("(C3H3O2)2000000-2000000").unwrap();
+    let style = DisplayStyle::new().with_compact_numbers(true);
+    let repeated = style.clone().with_charge_style(ChargeStyle::Repeated);
+    assert_eq!(formula.format_with(&repeated).to_string(), "(C₃H₃O₂)₂ₑ₆²ᵉ⁶⁻");
+    let sign_first =
+        style.with_charge_style(ChargeStyle::SignFirst).with_number_style(NumberStyle::Ascii);
+    assert_eq!(formula.format_with(&sign_first).to_string(), "(C3H3O2)2e6-2e6");
+}
+
+#[test]
+fn test_compact_displays_are_not_parsed_back() {
+    for formula in ["(C2H4)1000000", "C1234567H2", "(C3H3O2)2000000-2000000", "3000000H2O"] {
+        let parsed = ChemicalFormula::<u64, i64>::from_str(formula).unwrap();
+        let ascii =
+            DisplayStyle::new().with_compact_numbers(true).with_number_style(NumberStyle::Ascii);
+        for display in
+            [parsed.display_compact().to_string(), parsed.format_with(&ascii).to_string()]
+        {
+            assert!(
+                ChemicalFormula::<u64, i64>::from_str(&display).is_err(),
+                "{display} was parsed back"
+            );
+        }
+        assert_eq!(ChemicalFormula::from_str(&parsed.to_string()), Ok(parsed));
+    }
+}
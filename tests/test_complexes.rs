@@ -75,6 +75,111 @@ fn test_cyclopentadienyl() {
     assert_eq!(ferrocene.count_of_element(elements_rs::Element::H), Some(10));
     assert_eq!(ferrocene.count_of_element(elements_rs::Element::Fe), Some(1));
 
-    // Check charge: 2 * Cp(-1) + Fe(0) = -2
-    assert!((ferrocene.charge() - -2.0).abs() < f64::EPSILON);
+    // Cp is a neutral ligand, so ferrocene is neutral overall
+    assert!(ferrocene.charge().abs() < f64::EPSILON);
+}
+
+#[test]
+/// Test that the cyclopentadienide anion requires an explicit charge
+fn test_cyclopentadienide() {
+    let cyclopentadienide: ChemicalFormula = ChemicalFormula::from_str("Cp-").unwrap();
+    assert_eq!(cyclopentadienide.to_string(), "(C₅H₅)⁻");
+    assert!((cyclopentadienide.charge() - -1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+/// Test the hapticity descriptors of organometallic formulas
+fn test_hapticity_descriptors() {
+    let options = ParserOptions::new().with_hapticity_handling(HapticityHandling::Skip);
+    let ferrocene: ChemicalFormula = ChemicalFormula::from_str("Fe(C5H5)2").unwrap();
+    for descriptor in ["Fe(η⁵-C5H5)2", "Fe(η5-C5H5)2", "(η⁵-Cp)2Fe", "η⁵-Cp2Fe"] {
+        let parsed: ChemicalFormula =
+            ChemicalFormula::from_str_with_options(descriptor, &options).unwrap();
+        assert_eq!(
+            parsed.count_of_element::<u16>(elements_rs::Element::C),
+            ferrocene.count_of_element::<u16>(elements_rs::Element::C),
+            "Unexpected composition for {descriptor}"
+        );
+        assert!(parsed.charge().abs() < f64::EPSILON, "Unexpected charge for {descriptor}");
+        assert!(ChemicalFormula::<u16, i16>::from_str(descriptor).is_err());
+    }
+
+    let benzene_chromium: ChemicalFormula =
+        ChemicalFormula::from_str_with_options("(η⁶-C6H6)Cr(CO)3", &options).unwrap();
+    assert_eq!(benzene_chromium.to_string(), "(C₆H₆)Cr(CO)₃");
+
+    // An incomplete descriptor is still rejected.
+    assert!(ChemicalFormula::<u16, i16>::from_str_with_options("Fe(η-C5H5)2", &options).is_err());
+    assert!(ChemicalFormula::<u16, i16>::from_str_with_options("Fe(η⁵C5H5)2", &options).is_err());
+}
+
+#[test]
+#[cfg(feature = "protecting-groups")]
+/// Test the compositions of the protecting groups and ligands
+fn test_protecting_groups() {
+    for (abbreviated, expanded) in [
+        ("iPrOH", "C3H7OH"),
+        ("tBuOK", "C4H9OK"),
+        ("BzCl", "C7H5OCl"),
+        ("MsCl", "CH3SO2Cl"),
+        ("TfOH", "CF3SO2OH"),
+        ("BocNHCH2COOH", "C5H9O2NHCH2COOH"),
+        ("CbzCl", "C8H7O2Cl"),
+        ("FmocCl", "C15H11O2Cl"),
+        ("Boc2O", "C10H18O5"),
+        ("BnOBz", "C14H12O2"),
+    ] {
+        let abbreviated: ChemicalFormula<u32, i32> =
+            ChemicalFormula::from_str(abbreviated).unwrap();
+        let expanded: ChemicalFormula<u32, i32> = ChemicalFormula::from_str(expanded).unwrap();
+        for element in [
+            elements_rs::Element::C,
+            elements_rs::Element::H,
+            elements_rs::Element::O,
+            elements_rs::Element::S,
+            elements_rs::Element::F,
+        ] {
+            assert_eq!(
+                abbreviated.count_of_element::<u32>(element),
+                expanded.count_of_element::<u32>(element),
+                "Unexpected {element} count for {abbreviated}"
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "protecting-groups")]
+/// Test that the element symbols sharing letters with the complexes are
+/// still read as elements
+fn test_protecting_groups_leave_elements_untouched() {
+    let fermium: ChemicalFormula = ChemicalFormula::from_str("FmCl3").unwrap();
+    assert_eq!(fermium.count_of_element(elements_rs::Element::Fm), Some(1));
+    // The acetyl and tosyl groups are not provided, as `Ac` and `Ts` are
+    // the symbols of actinium and tennessine.
+    let actinium: ChemicalFormula = ChemicalFormula::from_str("AcCl3").unwrap();
+    assert_eq!(actinium.count_of_element(elements_rs::Element::Ac), Some(1));
+    let tennessine: ChemicalFormula = ChemicalFormula::from_str("TsF").unwrap();
+    assert_eq!(tennessine.count_of_element(elements_rs::Element::Ts), Some(1));
+    assert!(Complex::try_from("Ac").is_err() && Complex::try_from("Ts").is_err());
+    for element in ["AcCl3", "TsF", "BCl3", "CuBr", "Pr2O3", "MgSO4"] {
+        let formula: ChemicalFormula = ChemicalFormula::from_str(element).unwrap();
+        assert!(!formula.to_string().contains('('), "Unexpected complex in {formula}");
+    }
+    for incomplete in ["Fmo", "Bo", "Cb", "iP", "tB", "Fmox"] {
+        assert!(ChemicalFormula::<u32, i32>::from_str(incomplete).is_err(), "{incomplete}");
+    }
+}
+
+#[test]
+#[cfg(feature = "protecting-groups")]
+/// Test that the symbols of the complexes round-trip through their display
+fn test_complex_symbols_round_trip() {
+    for complex in Complex::ALL {
+        assert_eq!(Complex::try_from(complex.to_string().as_str()), Ok(*complex));
+        let tokens: Vec<_> = tokenize::<u32, i32>(complex.symbol())
+            .map(|token| token.unwrap().into_value())
+            .collect();
+        assert_eq!(tokens, [Token::Complex(*complex)], "Unexpected tokens for {complex}");
+    }
 }
@@ -0,0 +1,80 @@
+//! Submodule testing the atom counts of formulas, both overall and broken
+//! down by mixture component.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_number_of_atoms() {
+    let glucose: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    assert_eq!(glucose.number_of_atoms(), 24);
+
+    let hydrate: ChemicalFormula = ChemicalFormula::from_str("2Al(OH)3.3H2O").unwrap();
+    assert_eq!(hydrate.number_of_atoms(), 2 * 7 + 3 * 3);
+
+    let polymer = ChemicalFormula::<u32, i32>::from_str("((C2H4)4000000000)4000000000").unwrap();
+    assert_eq!(polymer.number_of_atoms(), u64::MAX);
+}
+
+#[test]
+fn test_atom_counts_exceeding_the_count_type() {
+    let formula = ChemicalFormula::<u8, i8>::from_str("C200H200").unwrap();
+    assert_eq!(formula.number_of_atoms(), 400);
+    assert_eq!(
+        formula.component_compositions(),
+        vec![(1, vec![(Element::C, 200), (Element::H, 200)])]
+    );
+
+    let formula = ChemicalFormula::<u8, i8>::from_str("(C100)3.200H2O").unwrap();
+    assert_eq!(formula.number_of_atoms(), 300 + 200 * 3);
+    assert_eq!(
+        formula.component_compositions(),
+        vec![(1, vec![(Element::C, 300)]), (200, vec![(Element::H, 2), (Element::O, 1)])]
+    );
+}
+
+#[test]
+fn test_component_compositions() {
+    let formula: ChemicalFormula = ChemicalFormula::from_str("CuSO4.5H2O").unwrap();
+    assert_eq!(
+        formula.component_compositions(),
+        vec![
+            (1, vec![(Element::Cu, 1), (Element::O, 4), (Element::S, 1)]),
+            (5, vec![(Element::H, 2), (Element::O, 1)]),
+        ]
+    );
+
+    // Isotopes are counted as their element, and repeated components are
+    // not merged.
+    let formula: ChemicalFormula = ChemicalFormula::from_str("[13C]H3OH.CH3OH").unwrap();
+    let compositions = formula.component_compositions();
+    assert_eq!(compositions[0], compositions[1]);
+    assert_eq!(compositions[0], (1, vec![(Element::C, 1), (Element::H, 4), (Element::O, 1)]));
+}
+
+#[test]
+fn test_component_compositions_agree_with_totals() {
+    let formula: ChemicalFormula = ChemicalFormula::from_str("2Fe(CN)6K4.3H2O.NaCl").unwrap();
+    let compositions = formula.component_compositions();
+    for element in formula.element_mask() {
+        let total: u64 = compositions
+            .iter()
+            .map(|(count, composition)| {
+                let atoms = composition
+                    .iter()
+                    .find_map(|(e, atoms)| (*e == element).then_some(*atoms))
+                    .unwrap_or(0);
+                u64::from(*count) * atoms
+            })
+            .sum();
+        assert_eq!(Some(total), formula.count_of_element::<u64>(element));
+    }
+    let atoms: u64 = compositions
+        .iter()
+        .map(|(count, composition)| {
+            u64::from(*count) * composition.iter().map(|(_, atoms)| atoms).sum::<u64>()
+        })
+        .sum();
+    assert_eq!(atoms, formula.number_of_atoms());
+}
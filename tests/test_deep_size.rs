@@ -0,0 +1,100 @@
+//! Submodule testing the deep sizes of formulas and trees, and pinning the
+//! sizes of the tree nodes so that regressions in their layout are caught,
+//! including their independence from the width of the counts and the
+//! packing of the repeats of single elements.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn test_node_sizes() {
+    assert_eq!(size_of::<ChemicalTree<u8, i8, Empty>>(), 24);
+    assert_eq!(size_of::<ChemicalTree<u16, i16, Empty>>(), 24);
+    assert_eq!(size_of::<ChemicalTree<u32, i32, Empty>>(), 24);
+    assert_eq!(size_of::<ChemicalTree<u64, i64, Empty>>(), 24);
+    assert_eq!(size_of::<(u8, ChemicalTree<u8, i8, Empty>)>(), 32);
+    assert_eq!(size_of::<ChemicalFormula<u16, i16>>(), 24);
+    assert_eq!(size_of::<InChIFormula<u16>>(), 24);
+}
+
+#[test]
+fn test_small_repeats_are_packed() {
+    let tree_size = size_of::<ChemicalTree<u16, i16, Empty>>();
+    let carbon = ChemicalFormula::<u16, i16>::from_str("C6").unwrap();
+    let Some(ChemicalTree::PackedRepeat(node)) = carbon.mixtures().next() else {
+        panic!("Expected a packed repeat");
+    };
+    assert_eq!((node.element(), node.count::<u16>()), (Element::C, 6));
+
+    let packed = ChemicalFormula::<u16, i16>::from_str("C6H12O6").unwrap();
+    assert_eq!(packed.to_string(), "C₆H₁₂O₆");
+
+    // Counts which do not fit in a byte fall back to a repeat allocating
+    // its element, along with the counters of shared trees.
+    let boxed = ChemicalFormula::<u16, i16>::from_str("C600H12O6").unwrap();
+    assert!(boxed.deep_size_of() >= packed.deep_size_of() + tree_size);
+    assert_eq!(boxed.to_string(), "C₆₀₀H₁₂O₆");
+
+    // Repeated groups still allocate their subtree.
+    let grouped = ChemicalFormula::<u16, i16>::from_str("(C6H12O6)2").unwrap();
+    assert!(grouped.deep_size_of() > packed.deep_size_of());
+    assert_eq!(grouped.count_of_element::<u32>(Element::C), Some(12));
+}
+
+#[test]
+fn test_packed_repeats_are_built_like_parsed_ones() {
+    let parsed = ChemicalFormula::<u8, i8>::from_str("C255H2").unwrap();
+    let built = ChemicalTree::<u8, i8, Empty>::from(Element::C)
+        .repeated(255)
+        .and_then(|tree| tree.followed_by(ChemicalTree::from(Element::H).repeated(2)?))
+        .unwrap();
+    assert_eq!(parsed, ChemicalFormula::from(built));
+
+    let widened = parsed.convert::<u32, i32>().unwrap();
+    assert_eq!(widened, ChemicalFormula::<u32, i32>::from_str("C255H2").unwrap());
+    assert_eq!(widened.convert::<u8, i8>(), Ok(parsed));
+
+    let boxed = ChemicalFormula::<u32, i32>::from_str("C256H2").unwrap();
+    assert!(boxed.convert::<u8, i8>().is_err());
+    assert_eq!(boxed.convert::<u16, i16>().unwrap().to_string(), "C₂₅₆H₂");
+}
+
+#[test]
+fn test_deep_size_grows_with_complexity() {
+    let formulas = ["H2O", "Ca(OH)2", "[Fe(CN)6]-4"];
+    let mut previous = size_of::<ChemicalFormula<u16, i16>>();
+    for formula in formulas {
+        let size = ChemicalFormula::<u16, i16>::from_str(formula).unwrap().deep_size_of();
+        assert!(size > previous, "{formula} uses {size} bytes");
+        previous = size;
+    }
+}
+
+#[test]
+fn test_deep_size_counts_capacity() {
+    let formula = ChemicalFormula::<u16, i16>::from_str("CuSO4.5H2O").unwrap();
+    let clone = formula.clone();
+    assert_eq!(formula, clone);
+    assert!(clone.deep_size_of() <= formula.deep_size_of());
+    assert!(clone.deep_size_of() > size_of::<ChemicalFormula<u16, i16>>());
+}
+
+#[test]
+fn test_deep_size_of_other_formulas() {
+    let mineral = MineralFormula::<u16, i16>::from_str("α-SiO2").unwrap();
+    assert!(mineral.deep_size_of_children() > 0);
+    assert!(mineral.deep_size_of() > size_of::<MineralFormula<u16, i16>>());
+
+    let inchi = InChIFormula::<u16>::from_str("C2H4O2.Na").unwrap();
+    let bigger = InChIFormula::<u16>::from_str("C20H25ClN2O5.C6H6O3S").unwrap();
+    assert!(inchi.deep_size_of_children() > 0);
+    assert!(bigger.deep_size_of() > inchi.deep_size_of());
+
+    let residual = ResidualFormula::<u16, i16>::from_str("RCH2OH").unwrap();
+    assert!(residual.deep_size_of() > size_of::<ResidualFormula<u16, i16>>());
+
+    let prefixed = Prefixed::<ChemicalFormula<u16, i16>>::from_str("β-C6H12O6").unwrap();
+    assert!(prefixed.deep_size_of() > size_of::<Prefixed<ChemicalFormula<u16, i16>>>());
+}
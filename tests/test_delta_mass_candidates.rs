@@ -0,0 +1,56 @@
+//! Submodule testing the suggestion of elemental compositions explaining
+//! the mass shifts of modifications.
+
+use molecular_formulas::prelude::*;
+
+fn candidates(delta: f64, tolerance: MassTolerance) -> Vec<String> {
+    FormulaDiff::delta_mass_to_candidates(delta, tolerance)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_common_modifications_are_suggested_first() {
+    let tolerance = MassTolerance::Dalton(5e-4);
+    assert_eq!(candidates(14.01565, tolerance)[0], "C H(2)");
+    assert_eq!(candidates(15.994915, tolerance)[0], "O");
+    assert_eq!(candidates(-18.010565, tolerance)[0], "H(-2) O(-1)");
+    assert_eq!(candidates(42.010565, tolerance)[0], "C(2) H(2) O");
+    assert_eq!(candidates(79.966331, tolerance)[0], "H O(3) P");
+    assert_eq!(candidates(-17.026549, tolerance)[0], "H(-3) N(-1)");
+}
+
+#[test]
+fn test_candidates_are_within_the_tolerance_and_sorted() {
+    let delta = 28.0313;
+    let tolerance = MassTolerance::Dalton(0.05);
+    let suggested = FormulaDiff::delta_mass_to_candidates(delta, tolerance);
+    assert!(suggested.len() > 1);
+    let names: Vec<String> = suggested.iter().map(ToString::to_string).collect();
+    assert!(names.contains(&"C(2) H(4)".to_string()));
+    assert!(names.contains(&"C O".to_string()));
+    let errors: Vec<f64> =
+        suggested.iter().map(|candidate| (candidate.isotopologue_mass() - delta).abs()).collect();
+    assert!(errors.iter().all(|error| *error <= 0.05));
+    assert!(errors.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn test_candidates_preserve_the_electron_parity() {
+    for candidate in FormulaDiff::delta_mass_to_candidates(15.0, MassTolerance::Dalton(0.05)) {
+        let odd = candidate.count_of_element(Element::H)
+            + candidate.count_of_element(Element::N)
+            + candidate.count_of_element(Element::P);
+        assert_eq!(odd % 2, 0, "{candidate}");
+        assert!(candidate.charge().abs() < f64::EPSILON);
+    }
+}
+
+#[test]
+fn test_empty_and_unexplained_shifts() {
+    for candidate in FormulaDiff::delta_mass_to_candidates(0.0, MassTolerance::Dalton(1e-3)) {
+        assert!(!candidate.is_empty());
+    }
+    assert!(candidates(2000.0, MassTolerance::Dalton(1e-4)).is_empty());
+}
@@ -0,0 +1,57 @@
+//! Submodule testing the dialects of the databases distributing molecular
+//! formulas against the conformance corpus of each dialect, found in the
+//! `tests/dialects` directory.
+
+use std::fmt::Debug;
+
+use molecular_formulas::prelude::*;
+
+/// Checks that each formula of the provided corpus is parsed by the
+/// provided dialect as its equivalent in the default notation, or rejected.
+fn check_corpus<M: FromStrWithOptions + PartialEq + Debug>(dialect: Dialect, corpus: &str) {
+    let mut checked = 0;
+    for line in corpus.lines().filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let (formula, reference) = line.split_once('\t').expect("Each entry has two columns");
+        let parsed = dialect.parse::<M>(formula);
+        if reference == "!" {
+            assert!(parsed.is_err(), "{} accepted {formula}", dialect.name());
+        } else {
+            let reference = M::from_str_with_options(reference, &ParserOptions::new()).unwrap();
+            assert_eq!(parsed, Ok(reference), "{} misread {formula}", dialect.name());
+        }
+        checked += 1;
+    }
+    assert!(checked > 0, "The corpus of {} is empty", dialect.name());
+}
+
+#[test]
+fn test_pubchem_corpus() {
+    check_corpus::<ChemicalFormula<u32, i32>>(
+        Dialect::PubChem,
+        include_str!("dialects/pubchem.tsv"),
+    );
+}
+
+#[test]
+fn test_chebi_corpus() {
+    check_corpus::<ResidualFormula<u32, i32>>(Dialect::Chebi, include_str!("dialects/chebi.tsv"));
+}
+
+#[test]
+fn test_kegg_corpus() {
+    check_corpus::<ResidualFormula<u32, i32>>(Dialect::Kegg, include_str!("dialects/kegg.tsv"));
+}
+
+#[test]
+fn test_inchi_corpus() {
+    check_corpus::<InChIFormula<u32>>(Dialect::InChI, include_str!("dialects/inchi.tsv"));
+}
+
+#[test]
+fn test_dialect_names() {
+    let names: Vec<&str> = [Dialect::PubChem, Dialect::Chebi, Dialect::Kegg, Dialect::InChI]
+        .iter()
+        .map(Dialect::name)
+        .collect();
+    assert_eq!(names, ["PubChem", "ChEBI", "KEGG", "InChI"]);
+}
@@ -0,0 +1,139 @@
+//! Submodule testing the display of formulas according to a `DisplayStyle`.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_default_style_matches_display() {
+    let style = DisplayStyle::default();
+    for formula in ["C6H12O6", "[Co(NH3)6]+3(Cl−)3", "[13C]H4", "•CH3", "CuSO4.5H2O", "Fe+3"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(formula.format_with(&style).to_string(), formula.to_string());
+    }
+}
+
+#[test]
+fn test_charge_styles() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("SO4-2").unwrap();
+    for (charge_style, number_style, expected) in [
+        (ChargeStyle::MagnitudeFirst, NumberStyle::Scripts, "SO₄²⁻"),
+        (ChargeStyle::SignFirst, NumberStyle::Scripts, "SO₄⁻²"),
+        (ChargeStyle::Repeated, NumberStyle::Scripts, "SO₄⁻⁻"),
+        (ChargeStyle::MagnitudeFirst, NumberStyle::Ascii, "SO42-"),
+        (ChargeStyle::SignFirst, NumberStyle::Ascii, "SO4-2"),
+        (ChargeStyle::Repeated, NumberStyle::Ascii, "SO4--"),
+    ] {
+        let style =
+            DisplayStyle::new().with_charge_style(charge_style).with_number_style(number_style);
+        assert_eq!(style.charge_style(), charge_style);
+        assert_eq!(style.number_style(), number_style);
+        assert_eq!(formula.format_with(&style).to_string(), expected);
+    }
+}
+
+#[test]
+fn test_bracket_styles() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("[Co(NH3)6]Cl3").unwrap();
+    for (bracket_style, expected) in [
+        (BracketStyle::Preserve, "[Co(NH₃)₆]Cl₃"),
+        (BracketStyle::Round, "(Co(NH₃)₆)Cl₃"),
+        (BracketStyle::Square, "[Co[NH₃]₆]Cl₃"),
+    ] {
+        let style = DisplayStyle::new().with_bracket_style(bracket_style);
+        assert_eq!(style.bracket_style(), bracket_style);
+        assert_eq!(formula.format_with(&style).to_string(), expected);
+    }
+}
+
+#[test]
+fn test_ascii_isotopes() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("[13C]H3[2H]").unwrap();
+    let style = DisplayStyle::new().with_number_style(NumberStyle::Ascii);
+    assert_eq!(formula.format_with(&style).to_string(), "[13C]H3[2H]");
+}
+
+#[test]
+fn test_mixture_separator() {
+    let style = DisplayStyle::new().with_mixture_separator(MixtureSeparator::MiddleDot);
+    assert_eq!(style.mixture_separator(), MixtureSeparator::MiddleDot);
+
+    let formula = MineralFormula::<u32, i32>::from_str("α-CaSO4.2H2O").unwrap();
+    assert_eq!(formula.format_with(&style).to_string(), "α-CaSO₄·2H₂O");
+
+    let formula = InChIFormula::<u32>::from_str("C2H6O.H2O").unwrap();
+    assert_eq!(formula.format_with(&style).to_string(), "C2H6O·H2O");
+
+    let formula = ResidualFormula::<u32, i32>::from_str("RCOO-.Na+").unwrap();
+    assert_eq!(formula.format_with(&style).to_string(), "RCOO⁻·Na⁺");
+}
+
+#[test]
+fn test_parsable_styles_round_trip() {
+    for style in [
+        DisplayStyle::new().with_charge_style(ChargeStyle::SignFirst),
+        DisplayStyle::new().with_charge_style(ChargeStyle::Repeated),
+        DisplayStyle::new()
+            .with_charge_style(ChargeStyle::SignFirst)
+            .with_number_style(NumberStyle::Ascii),
+    ] {
+        for formula in ["[Co(NH3)6]+3(Cl−)3", "SO4-2", "[13C]H4.Fe+3", "NH4+"] {
+            let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+            let displayed = formula.format_with(&style).to_string();
+            assert_eq!(
+                ChemicalFormula::<u32, i32>::from_str(&displayed).unwrap(),
+                formula,
+                "Failed to round-trip `{displayed}`"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_isotope_styles() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("[13C]H3O[2H].T2O").unwrap();
+    for (isotope_style, number_style, expected) in [
+        (IsotopeStyle::Bracketed, NumberStyle::Scripts, "[¹³C]H₃O[²H].[³H]₂O"),
+        (IsotopeStyle::Bracketed, NumberStyle::Ascii, "[13C]H3O[2H].[3H]2O"),
+        (IsotopeStyle::Superscript, NumberStyle::Scripts, "¹³CH₃O²H.³H₂O"),
+        (IsotopeStyle::Superscript, NumberStyle::Ascii, "¹³CH3O²H.³H2O"),
+        (IsotopeStyle::Suffixed, NumberStyle::Scripts, "C13H₃OH2.H3₂O"),
+        (IsotopeStyle::Suffixed, NumberStyle::Ascii, "C13H3OH2.H32O"),
+        (IsotopeStyle::HydrogenLetters, NumberStyle::Scripts, "[¹³C]H₃OD.T₂O"),
+        (IsotopeStyle::HydrogenLetters, NumberStyle::Ascii, "[13C]H3OD.T2O"),
+    ] {
+        let style =
+            DisplayStyle::new().with_isotope_style(isotope_style).with_number_style(number_style);
+        assert_eq!(style.isotope_style(), isotope_style);
+        assert_eq!(formula.format_with(&style).to_string(), expected);
+    }
+}
+
+#[test]
+fn test_isotope_styles_round_trip() {
+    for isotope_style in [IsotopeStyle::Bracketed, IsotopeStyle::HydrogenLetters] {
+        for number_style in [NumberStyle::Scripts, NumberStyle::Ascii] {
+            let style = DisplayStyle::new()
+                .with_isotope_style(isotope_style)
+                .with_number_style(number_style);
+            for formula in ["CH3OD", "[13C]H4", "T2O.[18O]H2", "[2H]Cl"] {
+                let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+                let displayed = formula.format_with(&style).to_string();
+                assert_eq!(
+                    ChemicalFormula::<u32, i32>::from_str(&displayed).unwrap(),
+                    formula,
+                    "Failed to round-trip {displayed}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_isotope_styles_across_formula_types() {
+    let style = DisplayStyle::new().with_isotope_style(IsotopeStyle::HydrogenLetters);
+    let formula = MineralFormula::<u32, i32>::from_str("Ca(OD)2").unwrap();
+    assert_eq!(formula.format_with(&style).to_string(), "Ca(OD)₂");
+    let formula = ResidualFormula::<u32, i32>::from_str("RCD3").unwrap();
+    assert_eq!(formula.format_with(&style).to_string(), "RCD₃");
+}
@@ -0,0 +1,83 @@
+//! Submodule testing the width of formula displays and their truncation at
+//! token boundaries.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+/// Formulas whose displays are truncated to every width in the tests.
+const FORMULAS: [&str; 8] = [
+    "C6H12O6.NaCl.H2O",
+    "[Co(NH3)6]+3(Cl−)3",
+    "¹³CH₄",
+    "CuSO4.5H2O",
+    "[Fe(CN)6]3-",
+    "•CH3",
+    "2C2H5OH.10H2O",
+    "Ca+2.2Cl-",
+];
+
+#[test]
+fn test_display_width() {
+    for (formula, width) in
+        [("C6H12O6", 7), ("[Co(NH3)6]+3(Cl−)3", 18), ("¹³CH₄", 7), ("Fe+3", 4), ("•CH3", 4)]
+    {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(formula.display_width(), width, "{formula}");
+    }
+}
+
+#[test]
+fn test_truncate_display_fits_within_width() {
+    for formula in FORMULAS {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let display = formula.to_string();
+        for max_width in 0..=formula.display_width() + 1 {
+            let truncated = formula.truncate_display(max_width);
+            assert!(truncated.chars().count() <= max_width, "{truncated} exceeds {max_width}");
+            if max_width >= formula.display_width() {
+                assert_eq!(truncated, display);
+            } else if max_width > 0 {
+                let prefix = truncated.strip_suffix('…').unwrap();
+                assert!(display.starts_with(prefix), "{truncated} is not a prefix of {display}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_truncate_display_cuts_at_token_ends() {
+    for formula in FORMULAS {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let display = formula.to_string();
+        let ends: Vec<usize> =
+            tokenize::<u32, i32>(&display).map(|token| token.unwrap().span().end).collect();
+        for max_width in 1..formula.display_width() {
+            let truncated = formula.truncate_display(max_width);
+            let cut = truncated.len() - '…'.len_utf8();
+            assert!(cut == 0 || ends.contains(&cut), "{truncated} splits a token of {display}");
+        }
+    }
+}
+
+#[test]
+fn test_truncate_display_keeps_tokens_whole() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("C6H12O6.NaCl.H2O").unwrap();
+    assert_eq!(formula.truncate_display(0), "");
+    assert_eq!(formula.truncate_display(1), "…");
+    assert_eq!(formula.truncate_display(4), "C₆…");
+    assert_eq!(formula.truncate_display(5), "C₆…");
+    assert_eq!(formula.truncate_display(6), "C₆H₁₂…");
+    assert_eq!(formula.truncate_display(10), "C₆H₁₂O₆.…");
+    assert_eq!(formula.truncate_display(11), "C₆H₁₂O₆.Na…");
+    assert_eq!(formula.truncate_display(13), "C₆H₁₂O₆.NaCl…");
+
+    let formula = ChemicalFormula::<u32, i32>::from_str("Fe+3.¹³CH₄").unwrap();
+    assert_eq!(formula.to_string(), "Fe³⁺.[¹³C]H₄");
+    assert_eq!(formula.truncate_display(4), "Fe…");
+    assert_eq!(formula.truncate_display(8), "Fe³⁺.…");
+    assert_eq!(formula.truncate_display(11), "Fe³⁺.[¹³C]…");
+
+    let formula = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    assert_eq!(formula.truncate_display(8), "CuSO₄.…");
+}
@@ -0,0 +1,87 @@
+//! Submodule testing the element constraints shared by the generation and the
+//! validation of formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_satisfies() {
+    let bounds = ElementBounds::<u32>::new()
+        .with_bounds(Element::C, 1, 12)
+        .with_bounds(Element::N, 0, 2)
+        .with_allowed_elements([Element::H, Element::O]);
+    for (formula, expected) in [
+        ("C6H12O6", true),
+        ("CH4N2O", true),
+        ("H2O", false),
+        ("C13H10", false),
+        ("C2H7N3", false),
+        ("CH3Cl", false),
+        ("[13C]H4", true),
+        ("NaCl.C2H6O", false),
+        ("C6H12O6.2C3H8", true),
+        ("C6H12O6.2C4H10", false),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(formula.satisfies(&bounds), expected, "{formula}");
+    }
+}
+
+#[test]
+fn test_satisfies_counts_beyond_the_count_type() {
+    let bounds = ElementBounds::<u8>::new().with_bounds(Element::C, 1, 255);
+    for (formula, expected) in [("(C100)2.50H2O", true), ("(C100)3", false), ("C.200H2O", true)] {
+        let formula = ChemicalFormula::<u8, i8>::from_str(formula).unwrap();
+        assert_eq!(formula.satisfies(&bounds), expected, "{formula}");
+    }
+}
+
+#[test]
+fn test_unrestricted_bounds() {
+    let bounds = ElementBounds::<u32>::default();
+    assert!(bounds.is_allowed(Element::U));
+    for formula in ["H2O", "UO2", "[Fe(CN)6]-4"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert!(formula.satisfies(&bounds), "{formula}");
+    }
+}
+
+#[test]
+fn test_bounds_are_replaced() {
+    let bounds =
+        ElementBounds::<u32>::new().with_bounds(Element::C, 1, 2).with_bounds(Element::C, 3, 4);
+    assert_eq!(bounds.bounds(Element::C), Some((3, 4)));
+    assert_eq!(bounds.iter().count(), 1);
+    assert_eq!(bounds.bounds(Element::H), None);
+}
+
+#[test]
+#[should_panic(expected = "must not exceed its maximum")]
+fn test_inverted_bounds() {
+    let _ = ElementBounds::<u32>::new().with_bounds(Element::C, 2, 1);
+}
+
+#[test]
+fn test_formulas_satisfy_bounds() {
+    let bounds = ElementBounds::<u32>::new()
+        .with_bounds(Element::O, 0, 3)
+        .with_bounds(Element::C, 0, 4)
+        .with_bounds(Element::H, 2, 10)
+        .with_bounds(Element::N, 0, 1);
+    let formulas: Vec<ChemicalFormula<u32, i32>> = bounds.formulas().collect();
+    assert_eq!(formulas.len(), 4 * 5 * 9 * 2);
+    for formula in &formulas {
+        assert!(formula.satisfies(&bounds), "{formula}");
+        assert!(formula.is_hill_sorted(), "{formula}");
+    }
+}
+
+#[test]
+fn test_formulas_skip_empty_composition() {
+    let bounds = ElementBounds::<u32>::new().with_bounds(Element::O, 0, 2);
+    let formulas: Vec<String> =
+        bounds.formulas::<i32>().map(|formula| formula.to_string()).collect();
+    assert_eq!(formulas, ["O", "O₂"]);
+    assert_eq!(ElementBounds::<u32>::new().formulas::<i32>().count(), 0);
+}
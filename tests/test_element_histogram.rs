@@ -0,0 +1,64 @@
+//! Submodule testing the element histograms of formulas, indexed by atomic
+//! number.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn histogram(formula: &str) -> Option<[u64; ELEMENT_HISTOGRAM_LENGTH]> {
+    ChemicalFormula::<u32, i32>::from_str(formula).unwrap().element_histogram()
+}
+
+fn index(element: Element) -> usize {
+    usize::from(u8::from(element))
+}
+
+#[test]
+fn test_histogram_counts_every_element() {
+    let glucose = histogram("C6H12O6").unwrap();
+    assert_eq!(glucose[index(Element::C)], 6);
+    assert_eq!(glucose[index(Element::H)], 12);
+    assert_eq!(glucose[index(Element::O)], 6);
+    assert_eq!(glucose.iter().filter(|count| **count > 0).count(), 3);
+    assert_eq!(glucose[0], 0);
+
+    let oganesson = histogram("Og").unwrap();
+    assert_eq!(oganesson[ELEMENT_HISTOGRAM_LENGTH - 1], 1);
+}
+
+#[test]
+fn test_histogram_merges_isotopes_and_mixtures() {
+    let labeled = histogram("[13C]CH3D.2H2O").unwrap();
+    assert_eq!(labeled[index(Element::C)], 2);
+    assert_eq!(labeled[index(Element::H)], 8);
+    assert_eq!(labeled[index(Element::O)], 2);
+
+    let ion = histogram("[Fe(CN)6]-4").unwrap();
+    assert_eq!(ion[index(Element::Fe)], 1);
+    assert_eq!(ion[index(Element::C)], 6);
+    assert_eq!(ion[index(Element::N)], 6);
+}
+
+#[test]
+fn test_histogram_matches_element_counts() {
+    for formula in ["C8H10N4O2", "CuSO4.5H2O", "Ca(OH)2", "C2H5OH.NaCl"] {
+        let parsed = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let histogram = parsed.element_histogram().unwrap();
+        for element in parsed.elements() {
+            assert_eq!(
+                Some(histogram[index(element)]),
+                parsed.count_of_element::<u64>(element),
+                "{formula}"
+            );
+        }
+        assert_eq!(histogram.iter().sum::<u64>(), parsed.number_of_atoms(), "{formula}");
+    }
+}
+
+#[test]
+fn test_histogram_of_inchi_formulas() {
+    let formula = InChIFormula::<u32>::from_str("C2H6O").unwrap();
+    let histogram = formula.element_histogram().unwrap();
+    assert_eq!(histogram[index(Element::C)], 2);
+    assert_eq!(histogram[index(Element::H)], 6);
+}
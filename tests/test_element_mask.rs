@@ -0,0 +1,64 @@
+//! Submodule testing the interconversion between formulas and element masks,
+//! and the prefiltering of formulas sharing elements.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ElementMaskError, prelude::*};
+
+#[test]
+fn test_element_mask() {
+    for (formula, expected) in [
+        ("H2O", &[Element::H, Element::O][..]),
+        ("C6H12O6", &[Element::H, Element::C, Element::O]),
+        ("CuSO4.5H2O", &[Element::H, Element::O, Element::S, Element::Cu]),
+        ("[2H]2O", &[Element::H, Element::O]),
+        ("C100000", &[Element::C]),
+        ("Ca(OH)2", &[Element::H, Element::O, Element::Ca]),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let elements: Vec<Element> = formula.element_mask().into_iter().collect();
+        assert_eq!(elements, expected, "{formula}");
+        for element in &elements {
+            assert!(formula.contains_element(*element));
+        }
+    }
+}
+
+#[test]
+fn test_round_trip_through_element_mask() {
+    for formula in ["C6H12O6", "H2O", "C2H5NO2", "Fe", "C12H22O11"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let mask = formula.element_mask();
+        let counts: Vec<u32> =
+            mask.into_iter().map(|element| formula.count_of_element(element).unwrap()).collect();
+        let rebuilt = ChemicalFormula::<u32, i32>::try_from((mask, counts.as_slice())).unwrap();
+        assert_eq!(rebuilt, formula);
+    }
+}
+
+#[test]
+fn test_invalid_element_mask_counts() {
+    let mask: ElementMask = [Element::H, Element::O].into_iter().collect();
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::try_from((mask, &[2, 1, 1][..])),
+        Err(ElementMaskError::MismatchedCounts)
+    );
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::try_from((mask, &[2, 0][..])),
+        Err(ElementMaskError::MismatchedCounts)
+    );
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::try_from((ElementMask::default(), &[][..])),
+        Err(ElementMaskError::EmptyMask)
+    );
+}
+
+#[test]
+fn test_shares_elements_with() {
+    let parse = |formula: &str| ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+    assert!(parse("H2O").shares_elements_with(&parse("CH4")));
+    assert!(parse("[13C]O2").shares_elements_with(&parse("C6H6")));
+    assert!(parse("NaCl.H2O").shares_elements_with(&parse("Cl2")));
+    assert!(!parse("H2O").shares_elements_with(&parse("NaCl")));
+    assert!(!parse("Fe2O3").shares_elements_with(&parse("CH4")));
+}
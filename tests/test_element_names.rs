@@ -0,0 +1,31 @@
+//! Tests for parsing full element names, enabled by the `names` feature.
+#![cfg(feature = "names")]
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_element_names() {
+    let by_name: ChemicalFormula = ChemicalFormula::from_str("Sodium2O").unwrap();
+    let by_symbol: ChemicalFormula = ChemicalFormula::from_str("Na2O").unwrap();
+    assert_eq!(by_name, by_symbol);
+
+    let lowercase: ChemicalFormula = ChemicalFormula::from_str("sodiumChlorine").unwrap();
+    let by_symbol: ChemicalFormula = ChemicalFormula::from_str("NaCl").unwrap();
+    assert_eq!(lowercase, by_symbol);
+
+    let deuterium: ChemicalFormula = ChemicalFormula::from_str("Deuterium2O").unwrap();
+    let by_symbol: ChemicalFormula = ChemicalFormula::from_str("D2O").unwrap();
+    assert_eq!(deuterium, by_symbol);
+}
+
+#[test]
+fn test_symbols_are_unaffected() {
+    for formula in ["NaCl", "CuSO4.5H2O", "Me2O", "HeH+", "CoCl2"] {
+        let parsed: ChemicalFormula = ChemicalFormula::from_str(formula).unwrap();
+        assert_eq!(parsed, ChemicalFormula::from_str(&parsed.to_string()).unwrap());
+    }
+    assert!(ChemicalFormula::<u16, i16>::from_str("Hz").is_err());
+    assert!(ChemicalFormula::<u16, i16>::from_str("Sodiu2O").is_err());
+}
@@ -0,0 +1,73 @@
+//! Submodule testing the empty formula, which is the identity of the
+//! addition of formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{CompositionError, ParserError},
+    prelude::*,
+};
+
+#[test]
+fn test_empty_formula_properties() {
+    let empty = ChemicalFormula::<u32, i32>::default();
+    assert!(empty.is_empty());
+    assert_eq!(empty.to_string(), "");
+    assert_eq!(empty.display_width(), 0);
+    assert!(empty.isotopologue_mass().abs() < f64::EPSILON);
+    assert!(empty.molar_mass().abs() < f64::EPSILON);
+    assert!(empty.charge().abs() < f64::EPSILON);
+    assert_eq!(empty.net_charge_integer(), Some(0));
+    assert_eq!(empty.number_of_mixtures(), 0);
+    assert_eq!(empty.count_of_element::<u32>(Element::C), Some(0));
+    assert_eq!(empty.element_mask().into_iter().count(), 0);
+    assert!(empty.is_hill_sorted());
+    assert_eq!(empty.neutralize(), empty);
+    assert_eq!(empty.checked_mul(3), Some(empty.clone()));
+    assert_eq!(empty.to_inchi_formula_layer(), None);
+    assert_eq!(empty.subformulas().count(), 0);
+    assert_eq!(empty.clone().into_elements().count(), 0);
+}
+
+#[test]
+fn test_empty_formula_is_the_identity_of_addition() {
+    let empty = ChemicalFormula::<u32, i32>::empty();
+    for formula in ["H2O", "CuSO4.5H2O", "[Fe(CN)6]-4.4K+", "•CH3"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(empty.clone() + formula.clone(), formula);
+        assert_eq!(formula.clone() + empty.clone(), formula);
+        let mut accumulated = empty.clone();
+        accumulated += formula.clone();
+        assert_eq!(accumulated, formula);
+    }
+    assert_eq!(empty.clone() + empty.clone(), empty);
+}
+
+#[test]
+fn test_empty_formula_is_not_parsed() {
+    let empty = ChemicalFormula::<u32, i32>::empty();
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::from_str(&empty.to_string()),
+        Err(ParserError::EmptyMolecularTree)
+    );
+    assert!(empty.verify_roundtrip().is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_empty_formula_serde_roundtrip() {
+    let empty = ChemicalFormula::<u32, i32>::default();
+    let serialized = serde_json::to_string(&empty).unwrap();
+    assert_eq!(serialized, "\"\"");
+    let deserialized: ChemicalFormula<u32, i32> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, empty);
+    assert!(deserialized.is_empty());
+}
+
+#[test]
+fn test_protonating_the_empty_formula() {
+    let empty = ChemicalFormula::<u32, i32>::empty();
+    assert_eq!(empty.protonate(0), Ok(empty.clone()));
+    assert_eq!(empty.protonate(1), Err(ParserError::EmptyMolecularTree.into()));
+    assert_eq!(empty.deprotonate(1), Err(CompositionError::InsufficientHydrogens));
+}
@@ -0,0 +1,58 @@
+//! Submodule testing the conversions of the errors of the crate into the
+//! top-level `Error` enumeration, and the sources they keep available.
+
+use core::{error::Error as _, str::FromStr};
+
+use molecular_formulas::{
+    Error,
+    errors::{FormulaPatternError, NumericError, ParserError},
+    prelude::*,
+};
+
+fn parse(formula: &str) -> Result<ChemicalFormula<u8, i8>, Error> {
+    Ok(ChemicalFormula::from_str(formula)?)
+}
+
+fn pattern(pattern: &str) -> Result<FormulaPattern, Error> {
+    Ok(FormulaPattern::from_str(pattern)?)
+}
+
+#[test]
+fn test_parser_errors_convert() {
+    let error = parse("H2O)").unwrap_err();
+    assert!(matches!(error, Error::Parser(_)));
+    assert!(error.to_string().starts_with("Parser error: "));
+    let source = error.source().unwrap();
+    assert_eq!(source.to_string(), ParserError::UnexpectedCharacter(')').to_string());
+
+    let error = parse("C300").unwrap_err();
+    assert_eq!(error, Error::Parser(ParserError::Numeric(NumericError::PositiveOverflow)));
+    assert!(error.source().unwrap().source().is_some());
+}
+
+#[test]
+fn test_other_errors_convert() {
+    let error = pattern("Xx1-2").unwrap_err();
+    assert_eq!(error, Error::FormulaPattern(FormulaPatternError::UnknownElement("Xx1-2".into())));
+    assert!(error.to_string().starts_with("Formula pattern error: "));
+
+    let range_error = ChemicalFormula::<u8, i8>::range_error("C300").unwrap();
+    let error = Error::from(range_error.clone());
+    assert_eq!(error.source().unwrap().to_string(), range_error.to_string());
+
+    let error = Error::from(NumericError::NegativeOverflow);
+    assert_eq!(
+        error.to_string(),
+        "Numeric error: Negative overflow occurred during numeric operation."
+    );
+}
+
+#[test]
+fn test_matches_need_a_wildcard() {
+    let category = match parse("H2O?").unwrap_err() {
+        Error::Parser(_) => "parser",
+        Error::Numeric(_) | Error::Range(_) => "numeric",
+        _ => "other",
+    };
+    assert_eq!(category, "parser");
+}
@@ -0,0 +1,79 @@
+//! Submodule testing the dense feature vectors of formulas, as specified by
+//! `FeatureSpec`.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn features(formula: &str, spec: &FeatureSpec) -> Vec<f32> {
+    ChemicalFormula::<u32, i32>::from_str(formula).unwrap().to_feature_vector(spec)
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-3, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn test_default_specification() {
+    let spec = FeatureSpec::default();
+    assert_eq!(
+        spec.feature_names(),
+        [
+            "C", "H", "N", "O", "P", "S", "F", "Cl", "Br", "I", "mass", "dbe", "charge", "H/C",
+            "N/C", "O/C", "P/C", "S/C"
+        ]
+    );
+    assert_eq!(spec.len(), 18);
+    assert!(!spec.is_empty());
+
+    let caffeine = features("C8H10N4O2", &spec);
+    assert_close(
+        &caffeine,
+        &[
+            8.0, 10.0, 4.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 194.080_38, 6.0, 0.0, 1.25, 0.5,
+            0.25, 0.0, 0.0,
+        ],
+    );
+}
+
+#[test]
+fn test_features_follow_the_specification() {
+    let spec = FeatureSpec::new()
+        .with_elements([Element::O, Element::C])
+        .with_other_elements(true)
+        .with_mass(false)
+        .with_double_bond_equivalent(false)
+        .with_ratios([Element::O]);
+    assert_eq!(spec.elements(), [Element::O, Element::C]);
+    assert_eq!(spec.ratios(), [Element::O]);
+    assert_eq!(spec.feature_names(), ["O", "C", "other", "charge", "O/C"]);
+    assert_close(&features("C2H3O2-", &spec), &[2.0, 2.0, 3.0, -1.0, 1.0]);
+    assert_close(&features("[13C]O2.2H2O", &spec), &[4.0, 1.0, 4.0, 0.0, 4.0]);
+}
+
+#[test]
+fn test_ratios_without_carbon_are_zero() {
+    let spec = FeatureSpec::new().with_elements([]).with_mass(false);
+    assert_close(&features("H2O", &spec), &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_empty_specification() {
+    let spec = FeatureSpec::new()
+        .with_elements([])
+        .with_mass(false)
+        .with_double_bond_equivalent(false)
+        .with_charge(false)
+        .with_ratios([]);
+    assert!(spec.is_empty());
+    assert!(features("C6H12O6", &spec).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "The element C is provided more than once")]
+fn test_repeated_elements_are_rejected() {
+    let _ = FeatureSpec::new().with_elements([Element::C, Element::H, Element::C]);
+}
@@ -0,0 +1,80 @@
+//! Submodule testing that the C header shipped with the crate declares
+//! exactly the functions exported by the `ffi` module, with matching
+//! signatures.
+#![cfg(feature = "ffi")]
+
+use std::collections::BTreeSet;
+
+/// The Rust source of the C interface.
+const FFI_SOURCE: &str = include_str!("../src/ffi.rs");
+
+/// The C header shipped with the crate.
+const HEADER: &str = include_str!("../include/molecular_formulas.h");
+
+/// Returns the C spelling of the provided Rust type of the C interface.
+fn c_type(rust_type: &str) -> String {
+    let (qualifier, pointee) = if let Some(pointee) = rust_type.strip_prefix("*const ") {
+        ("const ", Some(pointee))
+    } else if let Some(pointee) = rust_type.strip_prefix("*mut ") {
+        ("", Some(pointee))
+    } else {
+        ("", None)
+    };
+    let base = match pointee.unwrap_or(rust_type) {
+        "c_char" => "char",
+        "ChemicalFormula" => "ChemicalFormula",
+        "f64" => "double",
+        "i64" => "int64_t",
+        "" => "void",
+        other => panic!("The Rust type `{other}` has no C equivalent"),
+    };
+    if pointee.is_some() { format!("{qualifier}{base} *") } else { base.to_string() }
+}
+
+/// Returns the C declarations of the functions exported by the C interface,
+/// with normalized whitespace.
+fn declarations_from_source() -> BTreeSet<String> {
+    FFI_SOURCE
+        .split("pub unsafe extern \"C\" fn ")
+        .skip(1)
+        .map(|function| {
+            let signature = function.split_once('{').unwrap().0;
+            let signature = signature.split_whitespace().collect::<Vec<_>>().join(" ");
+            let (name, rest) = signature.split_once('(').unwrap();
+            let (parameters, return_type) = rest.rsplit_once(')').unwrap();
+            let return_type = return_type.trim().strip_prefix("->").unwrap_or_default().trim();
+            let parameters = parameters
+                .split(',')
+                .map(str::trim)
+                .filter(|parameter| !parameter.is_empty())
+                .map(|parameter| {
+                    let (name, rust_type) = parameter.split_once(':').unwrap();
+                    format!("{}{}", c_type(rust_type.trim()), name.trim())
+                })
+                .collect::<Vec<_>>();
+            let return_type = c_type(return_type);
+            let separator = if return_type.ends_with('*') { "" } else { " " };
+            format!("{return_type}{separator}{name}({});", parameters.join(", "))
+        })
+        .collect()
+}
+
+/// Returns the function declarations of the header, with normalized
+/// whitespace.
+fn declarations_from_header() -> BTreeSet<String> {
+    HEADER
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.ends_with(");") && !line.starts_with("/*"))
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .map(|declaration| declaration.replace("* ", "*"))
+        .collect()
+}
+
+#[test]
+fn test_header_matches_ffi_module() {
+    let expected = declarations_from_source();
+    assert_eq!(expected.len(), 7);
+    assert_eq!(declarations_from_header(), expected);
+    assert!(HEADER.contains("typedef struct ChemicalFormula ChemicalFormula;"));
+}
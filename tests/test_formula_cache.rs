@@ -0,0 +1,69 @@
+//! Submodule testing the cache of parsed formulas, which shares a single
+//! parsed formula between the occurrences of the same string.
+
+use std::sync::Arc;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+#[test]
+fn test_duplicated_strings_share_their_formula() {
+    let mut cache: FormulaCache<ChemicalFormula<u32, i32>> = FormulaCache::new(16);
+    let dataset = ["C6H12O6", "H2O", "C6H12O6", "NaCl", "H2O", "C6H12O6"];
+    let parsed: Vec<Arc<ChemicalFormula<u32, i32>>> =
+        dataset.iter().map(|formula| cache.parse(formula).unwrap()).collect();
+
+    assert!(Arc::ptr_eq(&parsed[0], &parsed[2]));
+    assert!(Arc::ptr_eq(&parsed[0], &parsed[5]));
+    assert!(Arc::ptr_eq(&parsed[1], &parsed[4]));
+    assert!(!Arc::ptr_eq(&parsed[0], &parsed[1]));
+    assert_eq!(cache.len(), 3);
+    assert_eq!((cache.hits(), cache.misses()), (3, 3));
+}
+
+#[test]
+fn test_least_recently_used_formulas_are_evicted() {
+    let mut cache: FormulaCache<ChemicalFormula<u32, i32>> = FormulaCache::new(2);
+    let water = cache.parse("H2O").unwrap();
+    cache.parse("NaCl").unwrap();
+    // Using water again makes the salt the least recently used formula.
+    cache.parse("H2O").unwrap();
+    cache.parse("CO2").unwrap();
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains("H2O"));
+    assert!(cache.contains("CO2"));
+    assert!(!cache.contains("NaCl"));
+    assert!(Arc::ptr_eq(&water, &cache.parse("H2O").unwrap()));
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!((cache.hits(), cache.misses()), (2, 3));
+}
+
+#[test]
+fn test_invalid_and_uncached_formulas() {
+    let mut cache: FormulaCache<ChemicalFormula<u32, i32>> = FormulaCache::new(4);
+    assert_eq!(cache.parse("H2O)"), Err(ParserError::UnexpectedCharacter(')')));
+    assert!(cache.is_empty());
+    assert_eq!(cache.misses(), 1);
+
+    let mut uncached: FormulaCache<ChemicalFormula<u32, i32>> = FormulaCache::new(0);
+    let first = uncached.parse("H2O").unwrap();
+    let second = uncached.parse("H2O").unwrap();
+    assert_eq!(first, second);
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert!(uncached.is_empty());
+}
+
+#[test]
+fn test_cache_with_options() {
+    let mut cache: FormulaCache<ChemicalFormula<u32, i32>> =
+        FormulaCache::with_options(8, Dialect::Chebi.parser_options());
+    assert_eq!(cache.options(), &Dialect::Chebi.parser_options());
+    assert_eq!(cache.capacity(), 8);
+    let sulfate = cache.parse("SO4(2-)").unwrap();
+    assert_eq!(sulfate.net_charge_integer(), Some(-2));
+
+    let mut residuals: FormulaCache<ResidualFormula<u32, i32>> = FormulaCache::new(8);
+    assert_eq!(residuals.parse("C2H4NO2R").unwrap().to_string(), "C₂H₄NO₂R");
+}
@@ -0,0 +1,80 @@
+//! Submodule testing the structured difference between two molecular
+//! formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn diff(formula: &str, reference: &str) -> FormulaDiff {
+    let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+    let reference = ChemicalFormula::<u32, i32>::from_str(reference).unwrap();
+    formula.diff(&reference)
+}
+
+#[test]
+fn test_diff_elements() {
+    for (formula, reference, expected) in [
+        ("C3H8NO6P", "C3H7NO3", "H O(3) P"),
+        ("C8H10N4O2", "C7H8N4O2", "C H(2)"),
+        ("C6H10O5", "C6H12O6", "H(-2) O(-1)"),
+        ("CuSO4.5H2O", "CuSO4", "H(10) O(5)"),
+        ("CH3Cl", "CH4", "H(-1) Cl"),
+        ("C6H12O6", "C6H12O6", ""),
+        ("C6H12O6", "(CH2O)6", ""),
+    ] {
+        assert_eq!(diff(formula, reference).to_string(), expected, "{formula} - {reference}");
+    }
+}
+
+#[test]
+fn test_diff_is_antisymmetric() {
+    let forward = diff("C2H6O", "C2H4O2");
+    let backward = diff("C2H4O2", "C2H6O");
+    assert_eq!(forward.elements().count(), backward.elements().count());
+    for (element, count) in forward.elements() {
+        assert_eq!(backward.count_of_element(element), -count);
+    }
+    assert!((forward.isotopologue_mass() + backward.isotopologue_mass()).abs() < 1e-9);
+    assert!((forward.molar_mass() + backward.molar_mass()).abs() < 1e-9);
+}
+
+#[test]
+fn test_diff_charge() {
+    let protonated = diff("C6H13O6+", "C6H12O6");
+    assert_eq!(protonated.to_string(), "H charge(1)");
+    assert!((protonated.charge() - 1.0).abs() < f64::EPSILON);
+    assert!(!protonated.is_empty());
+
+    let deprotonated = diff("Cl-", "Cl");
+    assert_eq!(deprotonated.elements().count(), 0);
+    assert_eq!(deprotonated.to_string(), "charge(-1)");
+    assert!(!deprotonated.is_empty());
+
+    assert!(diff("C6H12O6", "C6H12O6").is_empty());
+}
+
+#[test]
+fn test_diff_isotopes() {
+    // Isotopic labels do not change the elemental composition, but do change
+    // the mass.
+    let labelled = diff("[13C]H4", "CH4");
+    assert!(labelled.is_empty());
+    assert!((labelled.isotopologue_mass() - 1.003355).abs() < 1e-6);
+}
+
+#[test]
+fn test_diff_large_counts() {
+    // The totals are computed without overflowing the count type.
+    let formula = ChemicalFormula::<u8, i8>::from_str("(C200)200").unwrap();
+    let reference = ChemicalFormula::<u8, i8>::from_str("C").unwrap();
+    assert_eq!(formula.diff(&reference).count_of_element(Element::C), 39_999);
+}
+
+#[test]
+fn test_diff_of_large_counts() {
+    // The differences are computed from the element counts rather than by
+    // walking every atom, so that giant counts are diffed instantly.
+    let diff = diff("C4000000000H2(CH2)4000000000", "C");
+    assert_eq!(diff.count_of_element(Element::C), 7_999_999_999);
+    assert_eq!(diff.count_of_element(Element::H), 8_000_000_002);
+}
@@ -0,0 +1,59 @@
+//! Submodule testing the mass queries of formula indices.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn candidates() -> FormulaIndex<ChemicalFormula<u32, i32>> {
+    ElementBounds::<u32>::new()
+        .with_bounds(Element::C, 0, 12)
+        .with_bounds(Element::H, 0, 24)
+        .with_bounds(Element::N, 0, 4)
+        .with_bounds(Element::O, 0, 8)
+        .formulas()
+        .collect()
+}
+
+#[test]
+fn test_query_matches_linear_scan() {
+    let index = candidates();
+    for (target, tolerance) in [
+        (180.063388, MassTolerance::Ppm(5.0)),
+        (194.080376, MassTolerance::Ppm(1.0)),
+        (100.0, MassTolerance::Dalton(0.05)),
+        (12.0, MassTolerance::Dalton(0.0)),
+        (50.0, MassTolerance::Ppm(0.0)),
+    ] {
+        let (low, high) = tolerance.window(target);
+        let expected: Vec<f64> = index
+            .iter()
+            .map(|(mass, _)| mass)
+            .filter(|mass| low <= *mass && *mass <= high)
+            .collect();
+        let found: Vec<f64> = index.query_mass(target, tolerance).map(|(mass, _)| mass).collect();
+        assert_eq!(found, expected, "{target} {tolerance:?}");
+    }
+}
+
+#[test]
+fn test_ppm_window_is_relative_to_target() {
+    let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    let mass = glucose.isotopologue_mass();
+    let index: FormulaIndex<_> = [glucose].into_iter().collect();
+    // 3 ppm of the target mass.
+    let shifted = mass * (1.0 + 3e-6);
+    assert_eq!(index.query_mass(shifted, MassTolerance::Ppm(3.1)).len(), 1);
+    assert_eq!(index.query_mass(shifted, MassTolerance::Ppm(2.9)).len(), 0);
+}
+
+#[test]
+fn test_known_compositions() {
+    let index = candidates();
+    let caffeine = index
+        .query_mass(194.080376, MassTolerance::Ppm(1.0))
+        .map(|(_, formula)| formula.to_string())
+        .collect::<Vec<_>>();
+    assert!(caffeine.contains(&"C₈H₁₀N₄O₂".to_string()), "{caffeine:?}");
+    assert_eq!(index.len(), 13 * 25 * 5 * 9 - 1);
+    assert!(!index.is_empty());
+}
@@ -0,0 +1,56 @@
+//! Submodule testing the filtering of formulas with formula patterns.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::FormulaPatternError, prelude::*};
+
+#[test]
+fn test_matches() {
+    let pattern = FormulaPattern::from_str("C6-10 H* O2-4 !N").unwrap();
+    for (formula, expected) in [
+        ("C8H8O3", true),
+        ("C6H6O2", true),
+        ("C10O4", true),
+        ("C6H12O6", false),
+        ("C11H12O2", false),
+        ("C9H11NO3", false),
+        ("C7H8O2S", true),
+        ("C3H4O2.C3H4O2", true),
+        ("2C3H4O", true),
+        ("[13C]C5H6O2", true),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(pattern.matches(&formula), expected, "{formula}");
+    }
+}
+
+#[test]
+fn test_empty_pattern_matches_everything() {
+    let pattern = FormulaPattern::from_str("").unwrap();
+    for formula in ["H2O", "NaCl", "C6H12O6"] {
+        assert!(pattern.matches(&ChemicalFormula::<u8, i8>::from_str(formula).unwrap()));
+    }
+}
+
+#[test]
+fn test_filtering_candidates() {
+    let pattern = FormulaPattern::try_from("C1-2 H4+ !O").unwrap();
+    let bounds = ElementBounds::<u32>::new()
+        .with_bounds(Element::C, 0, 3)
+        .with_bounds(Element::H, 0, 8)
+        .with_bounds(Element::O, 0, 1);
+    let matching = bounds.formulas::<i32>().filter(|formula| pattern.matches(formula)).count();
+    assert_eq!(matching, 2 * 5);
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        FormulaPattern::from_str("C6 !"),
+        Err(FormulaPatternError::UnknownElement("!".to_string()))
+    );
+    assert_eq!(
+        FormulaPattern::from_str("C6--8"),
+        Err(FormulaPatternError::MalformedCount("C6--8".to_string()))
+    );
+}
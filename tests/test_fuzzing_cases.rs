@@ -31,7 +31,7 @@ fn test_fuzzing_case4() {
     let formula = "Cp-";
     let parsed: ChemicalFormula =
         ChemicalFormula::from_str(formula).expect("Failed to parse formula");
-    assert_eq!(parsed.to_string(), "(C₅H₅)²⁻", "Parsed formula was {parsed:?}");
+    assert_eq!(parsed.to_string(), "(C₅H₅)⁻", "Parsed formula was {parsed:?}");
 }
 
 #[test]
@@ -63,7 +63,7 @@ fn test_fuzzing_case8() {
     let formula = "Cp+";
     let parsed: ChemicalFormula =
         ChemicalFormula::from_str(formula).expect("Failed to parse formula");
-    assert_eq!(parsed.to_string(), "(C₅H₅)");
+    assert_eq!(parsed.to_string(), "(C₅H₅)⁺");
 }
 
 #[test]
@@ -71,13 +71,13 @@ fn test_fuzzing_case9() {
     let formula = "Cp+Cp+";
     let parsed: ChemicalFormula =
         ChemicalFormula::from_str(formula).expect("Failed to parse formula");
-    assert_eq!(parsed.to_string(), "(C₅H₅)(C₅H₅)");
+    assert_eq!(parsed.to_string(), "(C₅H₅)⁺(C₅H₅)⁺");
 }
 
 #[test]
 fn test_fuzzing_case10() {
     let formula2 = "Cp+Cp+";
-    let formula1 = "(C₅H₅)(C₅H₅)";
+    let formula1 = "(C₅H₅)⁺(C₅H₅)⁺";
     let parsed1: ChemicalFormula =
         ChemicalFormula::from_str(formula1).expect("Failed to parse formula");
     let parsed2: ChemicalFormula =
@@ -232,13 +232,13 @@ fn test_fuzzing_case24() {
         ChemicalFormula::from_str(formula).expect("Failed to parse formula");
 
     assert!(
-        (parsed.charge() + 32768.0).abs() < f64::EPSILON,
+        (parsed.charge() + 32767.0).abs() < f64::EPSILON,
         "Parsed formula had a charge of {}",
         parsed.charge()
     );
     assert_eq!(
         parsed.to_string(),
-        "[²⁶⁷Hs](C₄H₉)(C₅H₅)³²⁷⁶⁸⁻",
+        "[²⁶⁷Hs](C₄H₉)(C₅H₅)³²⁷⁶⁷⁻",
         "Parsed formula was {parsed:?} with a charge of {}",
         parsed.charge()
     );
@@ -0,0 +1,57 @@
+//! Submodule testing the A+2 isotope signature of chlorine, bromine and
+//! sulfur.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn signature(formula: &str) -> HalogenSignature {
+    ChemicalFormula::<u32, i32>::from_str(formula).unwrap().halogen_signature().unwrap()
+}
+
+#[test]
+fn test_signature_contributions() {
+    let bromobenzene = signature("C6H5Br");
+    assert_eq!(bromobenzene.number_of_atoms(Element::Br), 1);
+    assert!((bromobenzene.contribution(Element::Br) - 0.973).abs() < 0.001);
+    assert!(bromobenzene.a_plus_2() > bromobenzene.contribution(Element::Br));
+
+    let bromochloromethane = signature("CH2BrCl");
+    assert!((bromochloromethane.contribution(Element::Cl) - 0.320).abs() < 0.001);
+    assert!((bromochloromethane.contribution(Element::Br) - 0.973).abs() < 0.001);
+    // One heavy isotope of each element yields the A+4 peak.
+    assert!((bromochloromethane.a_plus_4() - 0.320 * 0.973).abs() < 0.001);
+}
+
+#[test]
+fn test_signature_absence() {
+    let glucose = signature("C6H12O6");
+    assert!(!glucose.is_present());
+    for element in [Element::Cl, Element::Br, Element::S, Element::C] {
+        assert_eq!(glucose.number_of_atoms(element), 0);
+        assert!(glucose.contribution(element).abs() < f64::EPSILON);
+    }
+    // Oxygen-18 and pairs of carbon-13 still produce a small A+2 peak.
+    assert!(glucose.a_plus_2() > 0.0 && glucose.a_plus_2() < 0.02);
+}
+
+#[test]
+fn test_explicit_isotopes_carry_no_signature() {
+    let labeled = signature("CH3[37Cl]");
+    assert!(!labeled.is_present());
+    assert!(labeled.a_plus_2() < 0.01);
+
+    let mixed = signature("CH2Cl[35Cl]");
+    assert_eq!(mixed.number_of_atoms(Element::Cl), 1);
+    assert!((mixed.contribution(Element::Cl) - mixed.a_plus_2()).abs() < 0.001);
+}
+
+#[test]
+fn test_signature_agrees_with_pattern() {
+    for formula in ["C6H4Cl2", "CH2Br2", "C5H11NO2S", "C2H6S2.HCl"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let signature = formula.halogen_signature().unwrap();
+        assert!((signature.a_plus_2() - formula.a_plus_n_abundance(2)).abs() < 1e-12);
+        assert!((signature.a_plus_4() - formula.a_plus_n_abundance(4)).abs() < 1e-12);
+    }
+}
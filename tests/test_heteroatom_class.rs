@@ -0,0 +1,50 @@
+//! Submodule testing the heteroatom classes and double bond equivalents
+//! used in petroleomics class plots.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_heteroatom_class() {
+    for (formula, expected) in [
+        ("C6H6", "HC"),
+        ("H2", "HC"),
+        ("C9H11NO3", "N1O3"),
+        ("C20H15NO2S", "N1O2S1"),
+        ("C12H8S2", "S2"),
+        ("C6H5Cl", "Cl1"),
+        ("CH3[15N]H2", "N1"),
+        ("C2H6O.H2O", "O2"),
+        ("2C2H6O", "O2"),
+        ("C33H34FeN4O4", "Fe1N4O4"),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(formula.heteroatom_class().as_deref(), Some(expected), "{formula}");
+    }
+}
+
+#[test]
+fn test_heteroatom_class_overflow() {
+    let formula = ChemicalFormula::<u8, i8>::from_str("200(C2H6O2)").unwrap();
+    assert_eq!(formula.heteroatom_class(), None);
+}
+
+#[test]
+fn test_double_bond_equivalent() {
+    for (formula, expected) in [
+        ("CH4", 0.0),
+        ("C2H4", 1.0),
+        ("C6H6", 4.0),
+        ("C10H8", 7.0),
+        ("C6H12O6", 1.0),
+        ("C8H10N4O2", 6.0),
+        ("C6H5Br", 4.0),
+        ("C2H6OS", 0.0),
+        ("CH3", 0.5),
+        ("C2H6O.H2O", -1.0),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert!((formula.double_bond_equivalent() - expected).abs() < f64::EPSILON, "{formula}");
+    }
+}
@@ -0,0 +1,70 @@
+//! Submodule testing the generation of homologous series from a base
+//! formula and a repeat unit.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{CompositionError, NumericError, ParserError},
+    prelude::*,
+};
+
+fn formula(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_members_are_evenly_spaced_in_mass() {
+    let series = HomologousSeries::new(formula("C2H4O2"), formula("CH2")).unwrap();
+    let members = series.members(0..=20).unwrap();
+    assert_eq!(members.len(), 21);
+    assert_eq!(members[0].formula(), &formula("C2H4O2"));
+    let methylene = formula("CH2").isotopologue_mass();
+    for (repeats, pair) in members.windows(2).enumerate() {
+        assert_eq!(pair[0].repeats(), repeats as u64);
+        assert!(
+            (pair[1].isotopologue_mass() - pair[0].isotopologue_mass() - methylene).abs() < 1e-9
+        );
+    }
+}
+
+#[test]
+fn test_series_of_charged_bases() {
+    // Protonated polyethylene glycols, as observed as contaminants in
+    // mass spectrometry.
+    let series = HomologousSeries::new(formula("H3O+"), formula("C2H4O")).unwrap();
+    let member = series.member(9).unwrap();
+    assert_eq!(member, formula("C18H39O10+"));
+    assert!((member.mz(false, true).unwrap() - 415.253_79).abs() < 1e-4);
+    assert_eq!(series.base().net_charge_integer(), Some(1));
+}
+
+#[test]
+fn test_members_merge_new_elements_in_hill_order() {
+    let series = HomologousSeries::new(formula("H2O"), formula("SiC2H6O")).unwrap();
+    assert_eq!(series.member(2).unwrap().to_string(), "C₄H₁₄O₃Si₂");
+    assert_eq!(series.member(0).unwrap().to_string(), "H₂O");
+    assert_eq!(series.repeat_unit(), &formula("SiC2H6O"));
+}
+
+#[test]
+fn test_overflowing_members() {
+    let series = HomologousSeries::new(
+        ChemicalFormula::<u8, i8>::from_str("C2H6").unwrap(),
+        ChemicalFormula::<u8, i8>::from_str("CH2").unwrap(),
+    )
+    .unwrap();
+    assert!(series.member(124).is_ok());
+    assert_eq!(series.member(125), Err(ParserError::Numeric(NumericError::PositiveOverflow)));
+    assert_eq!(series.member(300), Err(ParserError::Numeric(NumericError::PositiveOverflow)));
+    assert!(series.members(120..=130).is_err());
+}
+
+#[test]
+fn test_charged_repeat_unit() {
+    assert_eq!(
+        HomologousSeries::new(formula("H2O"), formula("CH2+")),
+        Err(CompositionError::ChargedRepeatUnit)
+    );
+    // A repeat unit whose charges cancel out is neutral.
+    assert!(HomologousSeries::new(formula("H2O"), formula("Na+.Cl-")).is_ok());
+}
@@ -0,0 +1,85 @@
+//! Submodule testing the modelling of hydrogen/deuterium exchange.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::CompositionError, prelude::*};
+
+fn formula(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_exchangeable_hydrogens() {
+    let policy = ExchangePolicy::default();
+    for (input, expected) in [
+        ("CH4", 0),
+        ("H2O", 1),
+        ("NH3", 2),
+        ("CH3SH", 1),
+        ("C6H12O6", 6),
+        ("MgCl2.6H2O", 6),
+        ("[2H]2O", 0),
+    ] {
+        assert_eq!(formula(input).exchangeable_hydrogens(&policy), expected, "{input}");
+    }
+    assert_eq!(formula("NH3").exchangeable_hydrogens(&ExchangePolicy::new()), 0);
+    let policy = ExchangePolicy::default().with_sites(Element::N, 3).with_sites(Element::O, 0);
+    assert_eq!(formula("NH3").exchangeable_hydrogens(&policy), 3);
+    assert_eq!(formula("H2O").exchangeable_hydrogens(&policy), 0);
+}
+
+#[test]
+fn test_exchange_preserves_structure() {
+    for (input, hydrogens, expected) in [
+        ("CH3COOH", 0, "CH3COOH"),
+        ("CH3OH", 1, "CH3OD"),
+        ("H2O", 1, "HDO"),
+        ("H2O", 2, "D2O"),
+        ("NH4+", 2, "NH2D2+"),
+        ("CH3CH2NH2", 3, "CH3CHDND2"),
+        ("C(CH2OH)4", 2, "C(CH2OH)3(CHDOD)"),
+        ("(CH3)3N", 1, "(CH3)2(CH2D)N"),
+        ("2H2O", 1, "H2O.HDO"),
+        ("CuSO4.5H2O", 5, "CuSO4.2H2O.HDO.2D2O"),
+    ] {
+        let exchanged = formula(input).exchange_h_for_d(hydrogens).unwrap();
+        assert_eq!(
+            exchanged.count_of_isotope::<u32>(Isotope::try_from((Element::H, 2_u16)).unwrap()),
+            Some(hydrogens),
+            "{input}"
+        );
+        assert_eq!(exchanged.to_string(), formula(expected).to_string(), "{input}");
+    }
+}
+
+#[test]
+fn test_exchange_mass_shift() {
+    let glucose = formula("C6H12O6");
+    let exchangeable = glucose.exchangeable_hydrogens(&ExchangePolicy::default());
+    let exchanged = glucose.exchange_h_for_d(u32::try_from(exchangeable).unwrap()).unwrap();
+    let shift = exchanged.isotopologue_mass() - glucose.isotopologue_mass();
+    assert!((shift - 6.0 * 1.006_276_746).abs() < 1e-6, "{shift}");
+}
+
+#[test]
+fn test_insufficient_hydrogens() {
+    assert_eq!(formula("CO2").exchange_h_for_d(1), Err(CompositionError::InsufficientHydrogens));
+    assert_eq!(formula("HDO").exchange_h_for_d(2), Err(CompositionError::InsufficientHydrogens));
+}
+
+#[test]
+fn test_tritium_exchange() {
+    let tritium = Isotope::try_from((Element::H, 3_u16)).unwrap();
+    let ethanolamine = formula("HOCH2CH2NH2");
+    let exchanged = ethanolamine.exchange_hydrogens(tritium, 2).unwrap();
+    assert_eq!(exchanged.count_of_isotope::<u32>(tritium), Some(2));
+    assert_eq!(exchanged.to_string(), formula("HOCH2CH2NT2").to_string());
+    let shift = exchanged.isotopologue_mass() - ethanolamine.isotopologue_mass();
+    assert!((shift - 2.0 * 2.008_224_246).abs() < 1e-6, "{shift}");
+
+    let oxygen18 = Isotope::try_from((Element::O, 18_u16)).unwrap();
+    assert_eq!(
+        ethanolamine.exchange_hydrogens(oxygen18, 1),
+        Err(CompositionError::NotAHydrogenIsotope(oxygen18))
+    );
+}
@@ -0,0 +1,87 @@
+//! Submodule testing the filling of skeleton compositions with implicit
+//! hydrogens under a valence model.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ValenceModelError, prelude::*};
+
+fn filled(skeleton: &str, model: &ValenceModel) -> Vec<String> {
+    ChemicalFormula::<u32, i32>::from_str(skeleton)
+        .unwrap()
+        .fill_hydrogens(model)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_saturated_skeletons() {
+    let saturated = ValenceModel::new().with_unsaturation(0, 0).unwrap();
+    assert_eq!(filled("C6O", &saturated), ["C₆H₁₄O"]);
+    assert_eq!(filled("C2N", &saturated), ["C₂H₇N"]);
+    assert_eq!(filled("CO+", &saturated), ["CH₄O"]);
+    // The hydrogens of the skeleton are kept, and mixtures are merged.
+    assert_eq!(filled("C2H2", &saturated), ["C₂H₆"]);
+    assert_eq!(filled("CH3.CH3", &saturated), ["C₂H₆"]);
+    assert_eq!(filled("[13C]Cl", &saturated), ["CH₃Cl"]);
+}
+
+#[test]
+fn test_unsaturations() {
+    let aromatic = ValenceModel::new().with_unsaturation(4, 4).unwrap();
+    assert_eq!(filled("C6", &aromatic), ["C₆H₆"]);
+
+    // Without an upper bound, candidates are generated down to the bare
+    // skeleton.
+    let candidates = filled("C2", &ValenceModel::new());
+    assert_eq!(candidates, ["C₂H₆", "C₂H₄", "C₂H₂", "C₂"]);
+
+    // Unsaturations exceeding what the skeleton may hold yield no candidate.
+    assert!(filled("C2", &ValenceModel::new().with_unsaturation(4, 8).unwrap()).is_empty());
+    assert!(filled("NH4+", &ValenceModel::new()).is_empty());
+}
+
+#[test]
+fn test_custom_valences() {
+    let model = ValenceModel::new()
+        .with_valences(Element::S, [6, 2, 4])
+        .and_then(|model| model.with_unsaturation(0, 0))
+        .unwrap();
+    assert_eq!(model.valences(Element::S), [2, 4, 6]);
+    assert_eq!(filled("CS", &model), ["CH₈S", "CH₆S", "CH₄S"]);
+    // Each atom takes its valence independently, and duplicates are merged.
+    assert_eq!(filled("S2", &model), ["H₁₀S₂", "H₈S₂", "H₆S₂", "H₄S₂", "H₂S₂"]);
+
+    let pentavalent = ValenceModel::new()
+        .with_valences(Element::P, [5])
+        .and_then(|model| model.with_unsaturation(1, 1))
+        .unwrap();
+    assert_eq!(filled("PO4", &pentavalent), ["H₃O₄P"]);
+    assert_eq!(ValenceModel::default(), ValenceModel::new());
+}
+
+#[test]
+fn test_large_skeletons() {
+    // The reachable hydrogens follow from the valence bounds, so that large
+    // counts do not enumerate the valences of every atom.
+    let model = ValenceModel::new()
+        .with_valences(Element::S, [2, 4, 6])
+        .and_then(|model| model.with_unsaturation(0, 0))
+        .unwrap();
+    let candidates = filled("C10000S10000", &model);
+    assert_eq!(candidates.len(), 20_001);
+    assert_eq!(candidates.first().unwrap(), "C₁₀₀₀₀H₆₀₀₀₂S₁₀₀₀₀");
+    assert_eq!(candidates.last().unwrap(), "C₁₀₀₀₀H₂₀₀₀₂S₁₀₀₀₀");
+}
+
+#[test]
+fn test_invalid_models() {
+    assert_eq!(
+        ValenceModel::new().with_valences(Element::S, []),
+        Err(ValenceModelError::MissingValences(Element::S))
+    );
+    assert_eq!(
+        ValenceModel::new().with_unsaturation(2, 1),
+        Err(ValenceModelError::EmptyUnsaturation { min: 2, max: 1 })
+    );
+}
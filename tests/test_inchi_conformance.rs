@@ -0,0 +1,72 @@
+//! Submodule testing that the canonical InChI formulas are displayed byte
+//! for byte as the formula layers of the standard InChIs of reference
+//! compounds, including their component order, multipliers and hydrogens.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+/// Formula layers of the standard InChIs of reference compounds, alongside
+/// the same species written in the default notation with its components in
+/// another order.
+const FORMULA_LAYERS: &[(&str, &str)] = &[
+    // Hydrogen is second in the presence of carbon, alphabetical otherwise.
+    ("C6H12O6", "C6H12O6"),
+    ("CH4", "CH4"),
+    ("CHCl3", "Cl3CH"),
+    ("H2O", "OH2"),
+    ("H3N", "NH3"),
+    ("ClH", "HCl"),
+    ("BrH", "HBr"),
+    ("HNO3", "HNO3"),
+    ("H2O4S", "H2SO4"),
+    // Components are sorted by their Hill formulas without hydrogen.
+    ("ClH.Na", "Na.HCl"),
+    ("Ca.2ClH", "HCl.Ca.HCl"),
+    ("2ClH.Mg", "Mg.2HCl"),
+    ("2Na.H2O4S", "H2SO4.2Na"),
+    ("Cu.H2O4S.5H2O", "5H2O.H2SO4.Cu"),
+    ("H3N.H2O", "H2O.NH3"),
+    ("K.Mn.4O", "4O.Mn.K"),
+    // Components containing carbon come first, by decreasing carbon count.
+    ("C2H4O2.Na", "Na.CH3COOH"),
+    ("2C2H4O2.Ca", "Ca.CH3COOH.CH3COOH"),
+    ("C6H8O7.3Na", "3Na.C6H8O7"),
+    ("C4H11N5.ClH", "HCl.C4H11N5"),
+    ("C20H25ClN2O5.C6H6O3S", "C6H5SO3H.C20H25ClN2O5"),
+    ("C16H19ClN2.C4H4O4", "C4H4O4.C16H19ClN2"),
+];
+
+#[test]
+fn test_formula_layers_are_canonical() {
+    for (layer, _) in FORMULA_LAYERS {
+        let formula = InChIFormula::<u32>::from_str(layer).unwrap();
+        assert_eq!(formula.to_string(), *layer);
+        assert!(formula.is_canonical(), "{layer} is not canonical");
+        assert_eq!(formula.canonical().unwrap().to_string(), *layer);
+    }
+}
+
+#[test]
+fn test_reordered_components_are_canonicalized() {
+    for (layer, _) in FORMULA_LAYERS {
+        let reversed: Vec<&str> = layer.split('.').rev().collect();
+        let formula = InChIFormula::<u32>::from_str(&reversed.join(".")).unwrap();
+        assert_eq!(formula.canonical().unwrap().to_string(), *layer);
+    }
+}
+
+#[test]
+fn test_chemical_formulas_match_formula_layers() {
+    for (layer, written) in FORMULA_LAYERS {
+        let formula = ChemicalFormula::<u32, i32>::from_str(written).unwrap();
+        assert_eq!(formula.to_inchi_formula_layer().unwrap(), *layer, "{written}");
+    }
+}
+
+#[test]
+fn test_canonical_overflow() {
+    let formula = InChIFormula::<u8>::from_str("200H2O.100H2O").unwrap();
+    assert_eq!(formula.canonical(), None);
+    assert!(!formula.is_canonical());
+}
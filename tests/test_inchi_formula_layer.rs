@@ -0,0 +1,36 @@
+//! Submodule testing the conversion of chemical formulas into InChI formula
+//! layers.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_inchi_formula_layer() {
+    for (formula, expected) in [
+        ("NaCl", "ClNa"),
+        ("H2SO4", "H2O4S"),
+        ("CHCl3", "CHCl3"),
+        ("C6H5Br", "C6H5Br"),
+        ("(CH3)3COH", "C4H10O"),
+        ("[Co(NH3)6]+3(Cl−)3", "Cl3CoH18N6"),
+        ("•CH3", "CH3"),
+        ("[2H]2O", "H2O"),
+        ("2H2O.NaCl.H2O", "ClNa.3H2O"),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let layer = formula.to_inchi_formula_layer().unwrap();
+        assert_eq!(layer, expected);
+        let inchi = InChIFormula::<u32>::from_str(&layer).unwrap();
+        assert_eq!(formula.to_inchi_formula().unwrap(), inchi);
+    }
+}
+
+#[test]
+fn test_inchi_formula_layer_overflow() {
+    let formula = ChemicalFormula::<u8, i8>::from_str("200H2O.100H2O").unwrap();
+    assert_eq!(formula.to_inchi_formula_layer(), None);
+
+    let formula = ChemicalFormula::<u8, i8>::from_str("(CH200)2").unwrap();
+    assert_eq!(formula.to_inchi_formula_layer(), None);
+}
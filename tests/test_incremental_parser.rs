@@ -0,0 +1,83 @@
+//! Submodule testing the validation of formulas typed one character at a
+//! time.
+
+use molecular_formulas::prelude::*;
+
+/// Pushes the characters of the provided input, and returns the state after
+/// each of them.
+fn states(input: &str) -> Vec<ValidationState> {
+    let mut parser = IncrementalParser::<u32, i32>::new();
+    input.chars().map(|c| parser.push_char(c)).collect()
+}
+
+#[test]
+fn test_valid_formulas_are_valid_prefixes() {
+    for formula in ["C6H12O6", "Ca(OH)2", "[13C]H4", "CuSO4.5H2O", "[Fe(CN)6]3-", "¹³CH₄", "Xe"]
+    {
+        let states = states(formula);
+        assert!(
+            states.iter().all(|state| !matches!(state, ValidationState::Invalid(_))),
+            "{formula}: {states:?}"
+        );
+        assert_eq!(states.last(), Some(&ValidationState::Complete), "{formula}");
+    }
+}
+
+#[test]
+fn test_expected_tokens() {
+    let states = states("K4[Fe(CN");
+    assert_eq!(states[2], ValidationState::Incomplete(ExpectedToken::Element));
+    assert_eq!(
+        states[4],
+        ValidationState::Incomplete(ExpectedToken::ClosingBracket(Bracket::Square))
+    );
+    assert_eq!(
+        states[7],
+        ValidationState::Incomplete(ExpectedToken::ClosingBracket(Bracket::Round))
+    );
+    assert_eq!(self::states("X"), [ValidationState::Incomplete(ExpectedToken::Element)]);
+    assert_eq!(self::states("¹³"), vec![ValidationState::Incomplete(ExpectedToken::Element); 2]);
+}
+
+#[test]
+fn test_invalid_inputs() {
+    for (input, span) in [("H2O)", 3..4), ("Q", 0..1), ("C?", 1..2), ("H₂2", 1..5)] {
+        let Some(ValidationState::Invalid(error)) = states(input).pop() else {
+            panic!("{input} should be invalid");
+        };
+        assert_eq!(error.span(), span, "{input}");
+    }
+}
+
+#[test]
+fn test_suggestions() {
+    let mut parser = IncrementalParser::<u32, i32>::new();
+    assert_eq!(parser.suggestions().len(), 118);
+    for c in "NaC".chars() {
+        parser.push_char(c);
+    }
+    assert_eq!(
+        parser.suggestions(),
+        [
+            Element::C,
+            Element::Cl,
+            Element::Ca,
+            Element::Cr,
+            Element::Co,
+            Element::Cu,
+            Element::Cd,
+            Element::Cs,
+            Element::Ce,
+            Element::Cm,
+            Element::Cf,
+            Element::Cn
+        ]
+    );
+    parser.push_char('l');
+    assert_eq!(parser.suggestions(), [Element::Cl]);
+    parser.push_char(')');
+    assert!(parser.suggestions().is_empty());
+    parser.clear();
+    assert_eq!(parser.input(), "");
+    assert_eq!(parser.state(), &ValidationState::Incomplete(ExpectedToken::Element));
+}
@@ -0,0 +1,50 @@
+//! Submodule testing the owned iteration over the elements of chemical
+//! formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_into_elements_matches_elements() {
+    for formula in [
+        "H2O",
+        "C6H12O6",
+        "CuSO4.5H2O",
+        "Ca(OH)2",
+        "K4[Fe(CN)6]",
+        "[13C]H4",
+        "D2O",
+        "NH4+",
+        "SO4-2",
+        "CH3•",
+        "(CH3)3C(CH2)2OH",
+        "2Na+.CO3-2",
+        "((CH2)2O)3",
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let expected: Vec<Element> = formula.elements().collect();
+        let owned: Vec<Element> = formula.clone().into_elements().collect();
+        assert_eq!(owned, expected, "{formula}");
+    }
+}
+
+#[test]
+fn test_into_iterator() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("Ca(OH)2").unwrap();
+    let mut hydrogens = 0;
+    for element in formula {
+        if element == Element::H {
+            hydrogens += 1;
+        }
+    }
+    assert_eq!(hydrogens, 2);
+}
+
+#[test]
+fn test_into_elements_is_lazy() {
+    let formula = ChemicalFormula::<u64, i64>::from_str("C18446744073709551615").unwrap();
+    let mut elements = formula.into_elements();
+    assert_eq!(elements.next(), Some(Element::C));
+    assert_eq!(elements.nth(1000), Some(Element::C));
+}
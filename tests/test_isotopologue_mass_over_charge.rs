@@ -20,3 +20,42 @@ fn test_isotopologue_mass_over_charge() {
         );
     }
 }
+
+#[test]
+/// Test to check that multiply charged anions gain the mass of their
+/// electrons, and that the polarity only affects the sign of the ratio
+fn test_mz_of_multiply_charged_anions() {
+    let electron_mass = 0.000548579909065;
+    for (formula, charge) in [("SO4-2", -2.0), ("PO4-3", -3.0), ("C6H10O6-2", -2.0), ("Fe+3", 3.0)]
+    {
+        let formula: ChemicalFormula = ChemicalFormula::from_str(formula).unwrap();
+        let neutral_mass = formula.isotopologue_mass();
+        let correction = formula.electron_mass_correction();
+        assert!((correction + charge * electron_mass).abs() < 1e-12, "{formula}: {correction}");
+        assert!(
+            (formula.isotopologue_mass_with_charge() - (neutral_mass + correction)).abs() < 1e-11
+        );
+
+        let expected = (neutral_mass - charge * electron_mass) / charge.abs();
+        let mz = formula.mz(false, true).unwrap();
+        assert!((mz - expected).abs() < 1e-11, "{formula}: {mz}");
+        let signed_mz = formula.mz(true, true).unwrap();
+        assert!((signed_mz - expected * charge.signum()).abs() < 1e-11);
+        assert!((signed_mz - formula.isotopologue_mass_over_charge()).abs() < 1e-11);
+
+        let uncorrected = formula.mz(false, false).unwrap();
+        assert!((uncorrected - neutral_mass / charge.abs()).abs() < 1e-11, "{formula}");
+        assert!((mz - uncorrected - correction / charge.abs()).abs() < 1e-11, "{formula}");
+    }
+}
+
+#[test]
+/// Test to check that neutral formulas need no electron mass correction
+fn test_neutral_electron_mass_correction() {
+    let formula: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    assert!(formula.electron_mass_correction().abs() < f64::EPSILON);
+    let formula: ChemicalFormula = ChemicalFormula::from_str("Na+.Cl-").unwrap();
+    assert!(formula.electron_mass_correction().abs() < f64::EPSILON);
+    assert_eq!(formula.mz(false, true), None);
+    assert_eq!(formula.mz(true, false), None);
+}
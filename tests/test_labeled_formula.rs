@@ -0,0 +1,132 @@
+//! Submodule testing the average masses and isotope patterns of formulas
+//! with partially enriched labelled positions.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn isotope(element: Element, mass_number: u16) -> Isotope {
+    Isotope::try_from((element, mass_number)).unwrap()
+}
+
+fn labeled(formula: &str) -> LabeledFormula<ChemicalFormula<u32, i32>> {
+    LabeledFormula::new(ChemicalFormula::from_str(formula).unwrap()).unwrap()
+}
+
+#[test]
+fn test_pattern_abundances_sum_to_one() {
+    for formula in ["C6H12O6", "[13C]C5H12O6", "CuSO4.5H2O", "[Co(NH3)6]Cl3", "C100H202"] {
+        let total: f64 = labeled(formula).isotope_pattern(0.0).iter().map(|(_, a)| a).sum();
+        assert!((total - 1.0).abs() < 1e-9, "{formula}: {total}");
+    }
+}
+
+#[test]
+fn test_pattern_is_sorted_and_pruned() {
+    let pattern = labeled("C254H377N65O75S6").isotope_pattern(1e-3);
+    assert!(pattern.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(pattern.iter().all(|(_, abundance)| *abundance >= 1e-3));
+}
+
+#[test]
+fn test_chlorine_pattern() {
+    let pattern = labeled("Cl2").isotope_pattern(0.0);
+    assert_eq!(pattern.len(), 3);
+    assert!((pattern[1].1 / pattern[0].1 - 0.6392).abs() < 1e-3);
+    assert!((pattern[2].1 / pattern[0].1 - 0.1021).abs() < 1e-3);
+}
+
+#[test]
+fn test_enrichment_shifts_pattern() {
+    let carbon_13 = isotope(Element::C, 13);
+    let fully = labeled("[13C]6H12O6");
+    let partially = labeled("[13C]6H12O6").with_enrichment(carbon_13, 0.5).unwrap();
+    assert!(fully.average_mass() > partially.average_mass());
+    assert!(partially.average_mass() > labeled("C6H12O6").average_mass());
+
+    let pattern = partially.isotope_pattern(1e-6);
+    let most_abundant = pattern.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+    assert!((most_abundant.0 - 183.07).abs() < 0.01);
+}
+
+#[test]
+fn test_mass_context_is_used() {
+    let boron_10 = isotope(Element::B, 10);
+    let boron_11 = isotope(Element::B, 11);
+    let context = MassContext::new()
+        .with_isotopic_composition(Element::B, [(boron_10, 1.0), (boron_11, 0.0)]);
+    let borane = labeled("BH3").with_mass_context(context);
+    assert!(borane.average_mass() < labeled("BH3").average_mass());
+    assert!(borane.isotope_pattern(1e-6).iter().all(|(mass, _)| *mass < 14.5));
+}
+
+#[test]
+fn test_a_plus_n_peaks_agree_with_pattern() {
+    for formula in ["C6H12O6", "CH2Br2", "[13C]C5H12O6", "C20H25N3O"] {
+        let labeled = labeled(formula);
+        let pattern = labeled.isotope_pattern(0.0);
+        for (n, (mass, abundance)) in pattern.iter().enumerate().take(4) {
+            let n = n as u64;
+            assert!((labeled.a_plus_n_mass(n).unwrap() - mass).abs() < 1e-6, "{formula} A+{n}");
+            let relative = abundance / pattern[0].1;
+            assert!((labeled.a_plus_n_abundance(n) - relative).abs() < 1e-9, "{formula} A+{n}");
+        }
+    }
+}
+
+#[test]
+fn test_a_plus_n_peaks() {
+    let chlorine = ChemicalFormula::<u32, i32>::from_str("Cl2").unwrap();
+    assert_eq!(chlorine.a_plus_n_mass(1), None);
+    assert!(chlorine.a_plus_n_abundance(1).abs() < f64::EPSILON);
+    assert!((chlorine.a_plus_n_abundance(2) - 0.64).abs() < 0.01);
+
+    // Partially enriched positions contribute their unlabelled fraction to
+    // the peaks lighter than the labelled isotopologue.
+    let carbon_13 = isotope(Element::C, 13);
+    let enriched = labeled("[13C]H4").with_enrichment(carbon_13, 0.9).unwrap();
+    assert!((enriched.a_plus_n_abundance(1) - 9.0).abs() < 0.01);
+
+    // The peaks of large formulas are computed without their whole pattern.
+    let polymer = ChemicalFormula::<u32, i32>::from_str("(C2H4)5000").unwrap();
+    assert!((polymer.a_plus_n_abundance(1) - 110.46).abs() < 0.01);
+}
+
+#[test]
+fn test_position_enrichment() {
+    let carbon_13 = isotope(Element::C, 13);
+    // Positions of the same isotope with the same enrichment are equivalent
+    // to the enrichment of the whole isotope.
+    let uniform = labeled("[13C]H3[13C]H2OH").with_enrichment(carbon_13, 0.5).unwrap();
+    let positions = labeled("[13C]H3[13C]H2OH")
+        .with_position_enrichment(carbon_13, 0, 0.5)
+        .unwrap()
+        .with_position_enrichment(carbon_13, 1, 0.5)
+        .unwrap();
+    assert!((uniform.average_mass() - positions.average_mass()).abs() < 1e-9);
+
+    // A single partially enriched position yields the doubly labelled
+    // isotopologue with its enrichment, and the singly labelled one with the
+    // complement.
+    let partial = labeled("[13C]H3[13C]H2OH").with_position_enrichment(carbon_13, 1, 0.25).unwrap();
+    assert_eq!(partial.enrichment(carbon_13, 0), Some(1.0));
+    assert_eq!(partial.enrichment(carbon_13, 1), Some(0.25));
+    let pattern = partial.isotope_pattern(0.0);
+    let heaviest_labelled = pattern.iter().find(|(mass, _)| (mass - 48.05).abs() < 0.01).unwrap();
+    let lightest_labelled = pattern.iter().find(|(mass, _)| (mass - 47.05).abs() < 0.01).unwrap();
+    assert!((heaviest_labelled.1 - 0.25).abs() < 0.01);
+    assert!((lightest_labelled.1 - 0.75).abs() < 0.01);
+
+    // Setting the enrichment of the isotope resets the one of its positions.
+    let reset = partial.with_enrichment(carbon_13, 0.9).unwrap();
+    assert_eq!(reset.enrichment(carbon_13, 1), Some(0.9));
+}
+
+#[test]
+fn test_large_counts() {
+    // The labelled formula is built from the element counts, and not by
+    // walking every atom of the formula.
+    let polymer = labeled("[13C]H3(CH2)4000000000CH3");
+    let expected = 12.011 * 4_000_000_001.0 + 1.008 * 8_000_000_006.0 + 13.003;
+    assert!((polymer.average_mass() / expected - 1.0).abs() < 1e-4);
+}
@@ -0,0 +1,35 @@
+//! Submodule testing the extraction of the largest mixture component of
+//! formulas, as used to desalt and dehydrate them.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn largest(formula: &str) -> Option<String> {
+    let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+    formula.largest_component().map(|component| component.to_string())
+}
+
+#[test]
+fn test_counter_ions_and_solvents_are_dropped() {
+    assert_eq!(largest("C20H18F3N4O8P-.Na+").as_deref(), Some("C₂₀H₁₈F₃N₄O₈P⁻"));
+    assert_eq!(largest("CuSO4.5H2O").as_deref(), Some("CuSO₄"));
+    assert_eq!(largest("2C7H15N2O+.SO4-2").as_deref(), Some("C₇H₁₅N₂O⁺"));
+    assert_eq!(largest("C6H12O6").as_deref(), Some("C₆H₁₂O₆"));
+}
+
+#[test]
+fn test_heavy_atoms_take_precedence_over_mass() {
+    assert_eq!(largest("CH6N+.I-").as_deref(), Some("CH₆N⁺"));
+    assert_eq!(largest("CH4.HI").as_deref(), Some("HI"));
+    assert_eq!(largest("C2H8N+.I-").as_deref(), Some("C₂H₈N⁺"));
+    assert_eq!(largest("[13C]H4.CH4").as_deref(), Some("[¹³C]H₄"));
+    assert_eq!(largest("CH4.[13C]H4").as_deref(), Some("[¹³C]H₄"));
+    assert_eq!(largest("H2O.H2O").as_deref(), Some("H₂O"));
+}
+
+#[test]
+fn test_empty_formulas_have_no_largest_component() {
+    assert_eq!(ChemicalFormula::<u32, i32>::empty().largest_component(), None);
+    assert_eq!(ChemicalFormula::<u32, i32>::default().largest_component(), None);
+}
@@ -0,0 +1,68 @@
+//! Submodule testing the computation of masses with custom isotope masses
+//! and isotopic compositions.
+
+use std::str::FromStr;
+
+use elements_rs::RelativeAtomicMass;
+use molecular_formulas::prelude::*;
+
+fn isotope(element: Element, mass_number: u16) -> Isotope {
+    Isotope::try_from((element, mass_number)).unwrap()
+}
+
+#[test]
+fn test_default_context_matches_default_masses() {
+    let context = MassContext::new();
+    for formula in ["C6H12O6", "[13C]6H12O6", "CuSO4.5H2O", "2Fe2O3.3H2O", "[Co(NH3)6]Cl3", "D2O"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert!(
+            (formula.isotopologue_mass_with(&context) - formula.isotopologue_mass()).abs() < 1e-9,
+            "{formula}"
+        );
+    }
+}
+
+#[test]
+fn test_isotope_mass_override_only_affects_labelled_atoms() {
+    let carbon_13 = isotope(Element::C, 13);
+    let context = MassContext::new().with_isotope_mass(carbon_13, 13.1);
+    let labelled = ChemicalFormula::<u32, i32>::from_str("[13C]CH6O").unwrap();
+    let shift = labelled.isotopologue_mass_with(&context) - labelled.isotopologue_mass();
+    assert!((shift - (13.1 - carbon_13.relative_atomic_mass())).abs() < 1e-9);
+
+    let unlabelled = ChemicalFormula::<u32, i32>::from_str("C2H6O").unwrap();
+    assert!(
+        (unlabelled.isotopologue_mass_with(&context) - unlabelled.isotopologue_mass()).abs() < 1e-9
+    );
+}
+
+#[test]
+fn test_enriched_composition_changes_unlabelled_atoms() {
+    let chlorine_35 = isotope(Element::Cl, 35);
+    let chlorine_37 = isotope(Element::Cl, 37);
+    let enriched = MassContext::new()
+        .with_isotopic_composition(Element::Cl, [(chlorine_35, 5.0), (chlorine_37, 95.0)]);
+    let formula = ChemicalFormula::<u32, i32>::from_str("CH2Cl2").unwrap();
+    let expected = formula.isotopologue_mass()
+        + 2.0 * (chlorine_37.relative_atomic_mass() - chlorine_35.relative_atomic_mass());
+    assert!((formula.isotopologue_mass_with(&enriched) - expected).abs() < 1e-9);
+
+    // Explicitly labelled atoms keep their isotope.
+    let labelled = ChemicalFormula::<u32, i32>::from_str("CH2[35Cl]2").unwrap();
+    assert!(
+        (labelled.isotopologue_mass_with(&enriched) - labelled.isotopologue_mass()).abs() < 1e-9
+    );
+}
+
+#[test]
+fn test_contexts_are_independent() {
+    let boron_10 = isotope(Element::B, 10);
+    let boron_11 = isotope(Element::B, 11);
+    let natural = MassContext::new();
+    let enriched = MassContext::new()
+        .with_isotopic_composition(Element::B, [(boron_10, 99.0), (boron_11, 1.0)]);
+    let formula = MineralFormula::<u32, i32>::from_str("B2O3").unwrap();
+    assert!(formula.isotopologue_mass_with(&enriched) < formula.isotopologue_mass_with(&natural));
+    assert!(enriched.average_mass(Element::B) < natural.average_mass(Element::B));
+    assert!((natural.average_mass(Element::B) - Element::B.standard_atomic_weight()).abs() < 1e-9);
+}
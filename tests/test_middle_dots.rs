@@ -0,0 +1,92 @@
+//! Submodule testing the reading of the middle dots, which may be either
+//! mixture separators or radicals depending on their position and on the
+//! parser options.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+/// Parses the provided formula with the provided middle dot handling.
+fn parse(
+    formula: &str,
+    handling: MiddleDotHandling,
+) -> Result<ChemicalFormula<u32, i32>, ParserError> {
+    ChemicalFormula::from_str_with_options(
+        formula,
+        &ParserOptions::new().with_middle_dot_handling(handling),
+    )
+}
+
+#[test]
+fn test_contextual_middle_dots() {
+    assert_eq!(ParserOptions::new().middle_dot_handling(), MiddleDotHandling::Contextual);
+    for (formula, expected) in [
+        ("CuSO4·5H2O", "CuSO4.5H2O"),
+        ("CuSO4∙5H2O", "CuSO4.5H2O"),
+        ("C2H6O·H2O", "C2H6O.H2O"),
+        ("Na+·Cl-", "Na+.Cl-"),
+        ("·CH3", "•CH3"),
+        ("CH3·", "CH3•"),
+        ("(CH3·)2", "(CH3•)2"),
+        ("CH4·+", "CH4•+"),
+        ("H2O.·OH", "H2O.•OH"),
+        ("·OH·H2O", "•OH.H2O"),
+    ] {
+        let parsed = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(parsed, ChemicalFormula::from_str(expected).unwrap(), "{formula}");
+    }
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::from_str("H2O··"),
+        Err(ParserError::UnexpectedCharacter('·'))
+    );
+}
+
+#[test]
+fn test_full_stops_and_bullets_are_unambiguous() {
+    for handling in
+        [MiddleDotHandling::Contextual, MiddleDotHandling::Radical, MiddleDotHandling::Separator]
+    {
+        let hydrate = parse("CuSO4.5H2O", handling).unwrap();
+        assert_eq!(hydrate.number_of_mixtures(), 6);
+        let methyl = parse("•CH3", handling).unwrap();
+        assert_eq!(methyl.to_string(), "•CH₃");
+    }
+}
+
+#[test]
+fn test_radical_middle_dots() {
+    let ethane = parse("CH3·CH3", MiddleDotHandling::Radical).unwrap();
+    assert_eq!(ethane.number_of_mixtures(), 1);
+    assert_eq!(ethane, ChemicalFormula::from_str("CH3•CH3").unwrap());
+    assert_eq!(parse("CuSO4·H2O", MiddleDotHandling::Radical).unwrap().number_of_mixtures(), 1);
+}
+
+#[test]
+fn test_separator_middle_dots() {
+    let hydrate = parse("CuSO4·5H2O", MiddleDotHandling::Separator).unwrap();
+    assert_eq!(hydrate, ChemicalFormula::from_str("CuSO4.5H2O").unwrap());
+    assert_eq!(parse("·CH3", MiddleDotHandling::Separator), Err(ParserError::EmptyMolecularTree));
+}
+
+#[test]
+fn test_middle_dots_in_other_formulas() {
+    let inchi = InChIFormula::<u32>::from_str("C2H6O·H2O").unwrap();
+    assert_eq!(inchi, InChIFormula::from_str("C2H6O.H2O").unwrap());
+    let options = ParserOptions::new().with_middle_dot_handling(MiddleDotHandling::Radical);
+    assert!(InChIFormula::<u32>::from_str_with_options("C2H6O·H2O", &options).is_err());
+
+    let residual = ResidualFormula::<u32, i32>::from_str("RCOO-·Na+").unwrap();
+    assert_eq!(residual, ResidualFormula::from_str("RCOO-.Na+").unwrap());
+    assert_eq!(ResidualFormula::<u32, i32>::from_str("·C8H18R").unwrap().to_string(), "•C₈H₁₈R");
+}
+
+#[test]
+fn test_middle_dot_separators_round_trip() {
+    let style = DisplayStyle::new().with_mixture_separator(MixtureSeparator::MiddleDot);
+    for formula in ["CuSO4.5H2O", "C2H6O.H2O", "[Fe(CN)6]-4.4K+", "NH4+.Cl-"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let display = formula.format_with(&style).to_string();
+        assert!(display.contains('·'), "{display}");
+        assert_eq!(ChemicalFormula::from_str(&display).unwrap(), formula, "{display}");
+    }
+}
@@ -0,0 +1,93 @@
+//! Submodule testing the library of common chemical modifications.
+#![cfg(feature = "modifications")]
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{CompositionError, ParserError},
+    modifications::Modification,
+    prelude::*,
+};
+
+fn formula(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_apply() {
+    for (modification, unmodified, expected) in [
+        (Modification::Acetylation, "C6H14N2O2", "C₈H₁₆N₂O₃"),
+        (Modification::Methylation, "C6H14N2O2", "C₇H₁₆N₂O₂"),
+        (Modification::Trimethylation, "C6H14N2O2", "C₉H₂₀N₂O₂"),
+        (Modification::Phosphorylation, "C3H7NO3", "C₃H₈NO₆P"),
+        (Modification::Oxidation, "C5H11NO2S", "C₅H₁₁NO₃S"),
+        (Modification::Deamidation, "C4H8N2O3", "C₄H₇NO₄"),
+        (Modification::Carbamidomethylation, "C3H7NO2S", "C₅H₁₀N₂O₃S"),
+        (Modification::Hexose, "C6H12O6", "C₁₂H₂₂O₁₁"),
+        (Modification::Dehydration, "C6H12O6", "C₆H₁₀O₅"),
+    ] {
+        let modified = modification.apply(&formula(unmodified)).unwrap();
+        assert_eq!(modified.to_string(), expected, "{modification:?} on {unmodified}");
+    }
+}
+
+#[test]
+fn test_apply_and_remove_are_inverse() {
+    let lysine = formula("C6H14N2O2");
+    for modification in Modification::ALL {
+        let modified = modification.apply(&lysine).unwrap();
+        assert_eq!(modification.remove(&modified).unwrap(), lysine, "{modification:?}");
+        assert!(modification.matches(&modified.diff(&lysine)), "{modification:?}");
+        assert!(
+            (modified.isotopologue_mass()
+                - lysine.isotopologue_mass()
+                - modification.isotopologue_mass())
+            .abs()
+                < 1e-9
+        );
+    }
+}
+
+#[test]
+fn test_charge_is_preserved() {
+    let protonated = formula("[C3H8NO3]+");
+    let modified = Modification::Phosphorylation.apply(&protonated).unwrap();
+    assert_eq!(modified.to_string(), "C₃H₉NO₆P⁺");
+    assert!((modified.charge() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        Modification::Phosphorylation.remove(&formula("C3H7NO3")),
+        Err(CompositionError::InsufficientAtoms(Element::P))
+    );
+    assert_eq!(
+        Modification::Deamidation.apply(&formula("C6H12O6")),
+        Err(CompositionError::InsufficientAtoms(Element::N))
+    );
+    assert_eq!(
+        Modification::Oxidation.remove(&formula("O")),
+        Err(ParserError::EmptyMolecularTree.into())
+    );
+}
+
+#[test]
+fn test_matches() {
+    let serine = formula("C3H7NO3");
+    let phosphoserine = formula("C3H8NO6P");
+    assert!(Modification::Phosphorylation.matches(&phosphoserine.diff(&serine)));
+    assert!(!Modification::Phosphorylation.matches(&serine.diff(&phosphoserine)));
+    assert!(!Modification::Sulfation.matches(&phosphoserine.diff(&serine)));
+}
+
+#[test]
+fn test_large_counts() {
+    // The counts are read per element, and not by walking every atom.
+    let polymer = formula("(CH2)2000000000");
+    let methylated = Modification::Methylation.apply(&polymer).unwrap();
+    assert_eq!(methylated.to_string(), "C₂₀₀₀₀₀₀₀₀₁H₄₀₀₀₀₀₀₀₀₂");
+    // Repeated units whose total fits the count type are accepted.
+    let small = ChemicalFormula::<u8, i8>::from_str("(C2)100").unwrap();
+    assert_eq!(Modification::Oxidation.apply(&small).unwrap().to_string(), "C₂₀₀O");
+}
@@ -2,6 +2,7 @@
 
 use std::str::FromStr;
 
+use elements_rs::RelativeAtomicMass;
 use molecular_formulas::prelude::*;
 
 const MOLAR_MASSES: &[(&str, f64)] = &[
@@ -24,3 +25,65 @@ fn test_molar_mass() {
         );
     }
 }
+
+#[test]
+/// Test to check that the `component_masses` method reports the molar mass
+/// and weight fraction of each mixture component
+fn test_component_masses() {
+    let formula: ChemicalFormula = ChemicalFormula::from_str("Na2CO3.10H2O").unwrap();
+    let masses = formula.component_masses();
+    assert_eq!(masses.len(), 2);
+    let (count, carbonate, carbonate_fraction) = masses[0];
+    assert_eq!(count, 1);
+    assert!((carbonate - 105.988).abs() < 1e-3);
+    let (count, water, water_fraction) = masses[1];
+    assert_eq!(count, 10);
+    assert!((water - 18.015).abs() < 1e-3);
+    assert!((carbonate + 10.0 * water - formula.molar_mass()).abs() < 1e-9);
+    assert!((carbonate_fraction + water_fraction - 1.0).abs() < 1e-12);
+    assert!((water_fraction - 0.6296).abs() < 1e-4);
+
+    // Repeated components are not merged.
+    let formula: ChemicalFormula = ChemicalFormula::from_str("NaCl.NaCl").unwrap();
+    let masses = formula.component_masses();
+    assert_eq!(masses.len(), 2);
+    assert!((masses[0].2 - 0.5).abs() < 1e-12);
+    assert!((masses[1].2 - 0.5).abs() < 1e-12);
+
+    let formula: ChemicalFormula = ChemicalFormula::from_str("C6H12O6").unwrap();
+    let masses = formula.component_masses();
+    assert_eq!(masses.len(), 1);
+    assert!((masses[0].2 - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+/// Test to check that the `molar_mass_with` method matches `molar_mass` with
+/// the default context, and follows the overridden compositions otherwise
+fn test_molar_mass_with() {
+    let natural = MassContext::new();
+    for formula in ["H2O", "C6H12O6", "CuSO4.5H2O", "[Fe(CN)6]-4", "NH4+", "•CH3"] {
+        let formula: ChemicalFormula = ChemicalFormula::from_str(formula).unwrap();
+        assert!((formula.molar_mass_with(&natural) - formula.molar_mass()).abs() < 1e-9);
+    }
+
+    let chlorine_35 = Isotope::try_from((Element::Cl, 35_u16)).unwrap();
+    let pure = MassContext::new().with_isotopic_composition(Element::Cl, [(chlorine_35, 1.0)]);
+    let salt: ChemicalFormula = ChemicalFormula::from_str("NaCl").unwrap();
+    let expected = Element::Na.standard_atomic_weight() + chlorine_35.relative_atomic_mass();
+    assert!((salt.molar_mass_with(&pure) - expected).abs() < 1e-9);
+}
+
+#[test]
+/// Test to check that explicit isotopes weigh as their isotope in
+/// `molar_mass_with`, as in the average mass of labelled formulas
+fn test_molar_mass_with_isotopes() {
+    let context = MassContext::new();
+    let labelled: ChemicalFormula<u32, i32> = ChemicalFormula::from_str("[13C]H4").unwrap();
+    let carbon_13 = Isotope::try_from((Element::C, 13_u16)).unwrap();
+    let expected = carbon_13.relative_atomic_mass() + 4.0 * Element::H.standard_atomic_weight();
+    assert!((labelled.molar_mass_with(&context) - expected).abs() < 1e-9);
+    assert!(labelled.molar_mass_with(&context) > labelled.molar_mass());
+
+    let average = LabeledFormula::new(labelled.clone()).unwrap().average_mass();
+    assert!((labelled.molar_mass_with(&context) - average).abs() < 1e-9);
+}
@@ -2,7 +2,10 @@
 
 use std::str::FromStr;
 
-use molecular_formulas::prelude::*;
+use molecular_formulas::{
+    errors::{NumericError, ParserError},
+    prelude::*,
+};
 
 #[test]
 fn test_charge() {
@@ -21,3 +24,89 @@ fn test_charge() {
     let formula: ChemicalFormula = ChemicalFormula::from_str("Ca²⁺").unwrap();
     assert!((formula.charge() - 2.0).abs() < f64::EPSILON);
 }
+
+#[test]
+fn test_zero_charge() {
+    for candidate in ["Na+0", "Na-0", "Na⁰⁺", "Na⁰⁻", "[Fe(CN)6]+0", "NH4+0.Cl-"] {
+        assert_eq!(
+            ChemicalFormula::<u32, i32>::from_str(candidate),
+            Err(ParserError::ZeroCharge),
+            "Expected `{candidate}` to be rejected as a zero charge"
+        );
+    }
+    // Leading zeros in non-zero charges are still numeric errors.
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::from_str("Na+02"),
+        Err(ParserError::Numeric(NumericError::LeadingZero))
+    );
+}
+
+#[test]
+fn test_neutralize() {
+    let formula: ChemicalFormula = ChemicalFormula::from_str("[Co(NH3)6]+3(Cl−)3").unwrap();
+    let neutral = formula.neutralize();
+    assert_eq!(neutral.to_string(), "[Co(NH₃)₆]Cl₃");
+    assert!(neutral.charge().abs() < f64::EPSILON);
+    assert_eq!(
+        neutral.count_of_element::<u32>(Element::Cl),
+        formula.count_of_element::<u32>(Element::Cl)
+    );
+
+    let formula: ChemicalFormula = ChemicalFormula::from_str("•CH3+").unwrap();
+    assert_eq!(formula.neutralize().to_string(), "•CH₃");
+}
+
+#[test]
+fn test_net_charge_integer() {
+    for (formula, expected) in [
+        ("H2O", 0),
+        ("H2O+2", 2),
+        ("[Co(NH3)6]+3(Cl−)3", 0),
+        ("(OH-)2", -2),
+        ("3SO4-2.2Fe+3", 0),
+        ("•CH3+", 1),
+        ("2[Fe(CN)6]-4", -8),
+    ] {
+        let formula: ChemicalFormula<u32, i32> = ChemicalFormula::from_str(formula).unwrap();
+        assert_eq!(formula.net_charge_integer(), Some(i64::from(expected)), "{formula}");
+        assert!((formula.charge() - f64::from(expected)).abs() < f64::EPSILON);
+    }
+
+    let overflowing: ChemicalFormula<u64, i64> =
+        ChemicalFormula::from_str("9223372036854775807Na+.K+").unwrap();
+    assert_eq!(overflowing.net_charge_integer(), None);
+}
+
+#[test]
+fn test_charged_groups_within_a_component() {
+    // Each bracketed group carries its own charge, and the charge of the
+    // component is their sum.
+    let formula: ChemicalFormula<u32, i32> = ChemicalFormula::from_str("(H3O)+(Cl)-").unwrap();
+    assert_eq!(formula.to_string(), "(H₃O)⁺Cl⁻");
+    assert!(formula.charge().abs() < f64::EPSILON);
+    assert_eq!(formula.net_charge_integer(), Some(0));
+    assert_eq!(formula.component_charges(), Some(vec![(1, 0)]));
+
+    let formula: ChemicalFormula<u32, i32> = ChemicalFormula::from_str("(OH-)2Fe+2").unwrap();
+    assert!(formula.charge().abs() < f64::EPSILON);
+    assert!((formula.isotopologue_mass_with_charge() - formula.isotopologue_mass()).abs() < 1e-12);
+
+    let formula: ChemicalFormula<u32, i32> = ChemicalFormula::from_str("(NH4)+(NH4)+").unwrap();
+    assert!((formula.charge() - 2.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_component_charges() {
+    let formula: ChemicalFormula<u32, i32> = ChemicalFormula::from_str("(H3O)+.(Cl)-").unwrap();
+    assert_eq!(formula.to_string(), "(H₃O)⁺.Cl⁻");
+    assert_eq!(formula.component_charges(), Some(vec![(1, 1), (1, -1)]));
+    assert!(formula.charge().abs() < f64::EPSILON);
+
+    let formula: ChemicalFormula<u32, i32> =
+        ChemicalFormula::from_str("[Co(NH3)6]+3.3Cl-.2H2O").unwrap();
+    assert_eq!(formula.component_charges(), Some(vec![(1, 3), (3, -1), (2, 0)]));
+    assert_eq!(formula.net_charge_integer(), Some(0));
+
+    let formula: ChemicalFormula<u32, i32> = ChemicalFormula::from_str("C6H12O6").unwrap();
+    assert_eq!(formula.component_charges(), Some(vec![(1, 0)]));
+}
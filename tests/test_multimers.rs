@@ -0,0 +1,100 @@
+//! Submodule testing the multimers of formulas and the adduct ions formed
+//! from them.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{CompositionError, NumericError, ParserError},
+    prelude::*,
+};
+
+fn formula(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+fn adducts(adducts: &[&str]) -> Vec<Adduct<u32, i32>> {
+    adducts.iter().map(|adduct| Adduct::from_str(adduct).unwrap()).collect()
+}
+
+#[test]
+fn test_multimers() {
+    let glucose = formula("C6H12O6");
+    let multimers = glucose.multimers(4).unwrap();
+    assert_eq!(multimers.len(), 4);
+    for (copies, multimer) in [1.0, 2.0, 3.0, 4.0].into_iter().zip(&multimers) {
+        assert!((multimer.isotopologue_mass() - copies * glucose.isotopologue_mass()).abs() < 1e-9);
+    }
+    assert_eq!(multimers[0], glucose);
+    assert!(glucose.multimers(0).unwrap().is_empty());
+    assert_eq!(glucose.multimer(0), Err(CompositionError::ZeroCopies));
+}
+
+#[test]
+fn test_multimers_of_ions_and_mixtures() {
+    let sulfate = formula("SO4-2").multimer(2).unwrap();
+    assert_eq!(sulfate.to_string(), "(SO₄²⁻)₂");
+    assert_eq!(sulfate.net_charge_integer(), Some(-4));
+    assert_eq!(sulfate.count_of_element::<u32>(Element::O), Some(8));
+    let mixture = formula("NaCl.2H2O").multimer(3).unwrap();
+    assert_eq!(mixture.to_string(), "(NaCl)₃.2(H₂O)₃");
+    assert_eq!(mixture.count_of_element::<u32>(Element::H), Some(12));
+}
+
+#[test]
+fn test_multimers_preserve_the_structure() {
+    for (molecule, expected) in [
+        ("Na", "Na₂"),
+        ("CH3(CH2)2CH3", "(CH₃(CH₂)₂CH₃)₂"),
+        ("[13C]H4", "([¹³C]H₄)₂"),
+        ("D2O", "([²H]₂O)₂"),
+        ("[Fe(CN)6]-4", "([Fe(CN)₆]⁴⁻)₂"),
+    ] {
+        let multimer = formula(molecule).multimer(2).unwrap();
+        assert_eq!(multimer.to_string(), expected, "{molecule}");
+        assert_eq!(ChemicalFormula::from_str(&multimer.to_string()).unwrap(), multimer);
+    }
+}
+
+#[test]
+fn test_multimer_overflows() {
+    let ethane = ChemicalFormula::<u8, i8>::from_str("C2H6").unwrap();
+    assert_eq!(ethane.multimer(42).unwrap().count_of_element::<u8>(Element::H), Some(252));
+    assert_eq!(
+        ethane.multimer(43),
+        Err(CompositionError::Parser(ParserError::Numeric(NumericError::PositiveOverflow)))
+    );
+    assert!(ethane.multimers(255).is_err());
+    let sulfate = ChemicalFormula::<u16, i8>::from_str("SO4-2").unwrap();
+    assert_eq!(sulfate.multimer(64).unwrap().net_charge_integer(), Some(-128));
+    assert_eq!(
+        sulfate.multimer(65),
+        Err(CompositionError::Parser(ParserError::Numeric(NumericError::NegativeOverflow)))
+    );
+}
+
+#[test]
+fn test_multimer_adducts_match_their_mass_over_charge() {
+    let caffeine = formula("C8H10N4O2");
+    let adducts = adducts(&["[M+H]1+", "[M+Na]1+", "[M+2H]2+", "[M-H]1-", "[M+H-H2O]1+"]);
+    let species = caffeine.multimer_adducts(3, &adducts).unwrap();
+    assert_eq!(species.len(), 15);
+    for (adduct, ion) in &species {
+        let expected = adduct.ion_mass_over_charge(caffeine.isotopologue_mass());
+        assert!(
+            (ion.mz(false, true).unwrap() - expected).abs() < 1e-9,
+            "Unexpected m/z for {adduct}"
+        );
+        assert_eq!(ion.net_charge_integer(), Some(i64::from(adduct.charge())));
+    }
+    assert_eq!(species[5].0.to_string(), "[2M+H]1+");
+    assert_eq!(species[14].0.multimer(), 3);
+}
+
+#[test]
+fn test_impossible_multimer_adducts_are_skipped() {
+    let salt = formula("NaCl");
+    let adducts = adducts(&["[M+H]1+", "[M+H-H2O]1+", "[M-Na]1-"]);
+    let species = salt.multimer_adducts(2, &adducts).unwrap();
+    let names: Vec<String> = species.iter().map(|(adduct, _)| adduct.to_string()).collect();
+    assert_eq!(names, ["[M+H]1+", "[M-Na]1-", "[2M+H]1+", "[2M-Na]1-"]);
+}
@@ -0,0 +1,89 @@
+//! Submodule testing the explicit normalization levels of chemical formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+const LEVELS: [NormalizationLevel; 4] = [
+    NormalizationLevel::None,
+    NormalizationLevel::Typography,
+    NormalizationLevel::Structural,
+    NormalizationLevel::Canonical,
+];
+
+#[test]
+fn test_normalization_levels() {
+    for (formula, expected) in [
+        ("Ca(OH)2", ["Ca(OH)2", "Ca(OH)₂", "CaO₂H₂", "CaH₂O₂"]),
+        ("[Fe(CN)6]-4.4K+", ["[Fe(CN)6]-4.4K+", "[Fe(CN)₆]⁴⁻.4K⁺", "FeC₆N₆⁴⁻.4K⁺", "C₆FeN₆⁴⁻.4K⁺"]),
+        ("CH3[13C]H2OH", ["CH3[13C]H2OH", "CH₃[¹³C]H₂OH", "CH₆[¹³C]O", "C[¹³C]H₆O"]),
+        ("[Co(NH3)6]Cl3", ["[Co(NH3)6]Cl3", "[Co(NH₃)₆]Cl₃", "CoN₆H₁₈Cl₃", "Cl₃CoH₁₈N₆"]),
+        ("NaCl.NaCl", ["NaCl.NaCl", "NaCl.NaCl", "NaCl.NaCl", "2ClNa"]),
+        ("CH3•", ["CH3•", "CH₃•", "CH₃•", "CH₃•"]),
+        ("C₆H₅CH₃", ["C6H5CH3", "C₆H₅CH₃", "C₇H₈", "C₇H₈"]),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        for (level, expected) in LEVELS.into_iter().zip(expected) {
+            assert_eq!(formula.normalize(level).unwrap(), expected, "{formula} at {level:?}");
+        }
+    }
+}
+
+#[test]
+fn test_typography_matches_display() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    assert_eq!(NormalizationLevel::default(), NormalizationLevel::Typography);
+    assert_eq!(formula.normalize(NormalizationLevel::default()).unwrap(), formula.to_string());
+}
+
+#[test]
+fn test_normalized_formulas_parse_back() {
+    for formula in ["K4[Fe(CN)6].3H2O", "SO4-2", "[NH4]+", "CH3COO-.Na+", "[2H]2O.H2O"] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let canonical = formula.normalize(NormalizationLevel::Canonical).unwrap();
+        for level in LEVELS {
+            let parsed =
+                ChemicalFormula::<u32, i32>::from_str(&formula.normalize(level).unwrap()).unwrap();
+            assert_eq!(parsed.normalize(NormalizationLevel::Canonical).unwrap(), canonical);
+        }
+    }
+}
+
+#[test]
+fn test_normalization_overflow() {
+    let formula = ChemicalFormula::<u8, i8>::from_str("(CH200)2").unwrap();
+    assert!(formula.normalize(NormalizationLevel::Typography).is_ok());
+    assert!(formula.normalize(NormalizationLevel::Structural).is_err());
+}
+
+#[test]
+fn test_formula_keys_are_stable() {
+    for (formula, key) in [
+        ("H2O", "CUN54TGHURR7P"),
+        ("C6H12O6", "BBALWKF6GDMI7"),
+        ("K4[Fe(CN)6].3H2O.H2O", "I4E6AIUIV2IBL"),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(formula.formula_key().unwrap(), key, "{formula}");
+    }
+}
+
+#[test]
+fn test_formula_keys_follow_the_canonical_form() {
+    let key = |formula: &str| {
+        ChemicalFormula::<u32, i32>::from_str(formula).unwrap().formula_key().unwrap()
+    };
+    for formula in ["H2O", "[Fe(CN)6]-4", "CuSO4.5H2O", "[13C]H4", "•CH3"] {
+        let key = key(formula);
+        assert_eq!(key.len(), 13);
+        assert!(key.chars().all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c)));
+    }
+    assert_eq!(key("K4[Fe(CN)6].3H2O.H2O"), key("C6FeK4N6.4H2O"));
+    assert_eq!(key("CH3CH2OH"), key("C2H6O"));
+    assert_ne!(key("NH4+"), key("NH4"));
+    assert_ne!(key("[13C]H4"), key("CH4"));
+    assert_ne!(key("NaCl.H2O"), key("ClH2NaO"));
+
+    let prefixed = Prefixed::<ChemicalFormula<u32, i32>>::from_str("α-C6H12O6").unwrap();
+    assert_eq!(prefixed.formula().formula_key().unwrap(), key("C6H12O6"));
+}
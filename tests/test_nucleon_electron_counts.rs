@@ -0,0 +1,40 @@
+//! Submodule testing the nucleon and electron bookkeeping of formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn formula(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_mass_number_sum() {
+    assert_eq!(formula("C6H12O6").mass_number_sum(), Some(180));
+    assert_eq!(formula("[13C]6H12O6").mass_number_sum(), Some(186));
+    assert_eq!(formula("CH2Cl2").mass_number_sum(), Some(84));
+    assert_eq!(formula("CH2[37Cl]Cl").mass_number_sum(), Some(86));
+    assert_eq!(formula("T2O").mass_number_sum(), Some(22));
+    assert_eq!(formula("2H2O.NaCl").mass_number_sum(), Some(94));
+    assert_eq!(formula("SO4-2").mass_number_sum(), Some(96));
+}
+
+#[test]
+fn test_electron_count() {
+    assert_eq!(formula("CH4").electron_count(), Some(10));
+    assert_eq!(formula("NH4+").electron_count(), Some(10));
+    assert_eq!(formula("SO4-2").electron_count(), Some(50));
+    assert_eq!(formula("[13C]H4").electron_count(), Some(10));
+    assert_eq!(formula("2H2O.Na+.Cl-").electron_count(), Some(48));
+    assert_eq!(formula("He+3").electron_count(), None);
+    assert_eq!(formula("He+2").electron_count(), Some(0));
+}
+
+#[test]
+fn test_electron_parity() {
+    assert_eq!(formula("C6H12O6").is_even_electron(), Some(true));
+    assert_eq!(formula("C6H13O6+").is_even_electron(), Some(true));
+    assert_eq!(formula("C6H12O6+").is_even_electron(), Some(false));
+    assert_eq!(formula("NO").is_even_electron(), Some(false));
+    assert_eq!(formula("He+3").is_even_electron(), None);
+}
@@ -0,0 +1,150 @@
+//! Submodule testing the support of the 64-bit count and charge types, the
+//! consistency of the overflow errors across notations and the conversions
+//! between formulas parametrized with different numeric types.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{NumericError, ParserError},
+    prelude::*,
+};
+
+#[test]
+fn test_64_bit_numbers() {
+    for (formula, expected) in [
+        ("C18446744073709551615", "C₁₈₄₄₆₇₄₄₀₇₃₇₀₉₅₅₁₆₁₅"),
+        ("Na+9223372036854775807", "Na⁹²²³³⁷²⁰³⁶⁸⁵⁴⁷⁷⁵⁸⁰⁷⁺"),
+        ("Na-9223372036854775808", "Na⁹²²³³⁷²⁰³⁶⁸⁵⁴⁷⁷⁵⁸⁰⁸⁻"),
+        ("Na⁹²²³³⁷²⁰³⁶⁸⁵⁴⁷⁷⁵⁸⁰⁸⁻", "Na⁹²²³³⁷²⁰³⁶⁸⁵⁴⁷⁷⁵⁸⁰⁸⁻"),
+    ] {
+        let parsed = ChemicalFormula::<u64, i64>::from_str(formula).unwrap();
+        assert_eq!(parsed.to_string(), expected);
+        assert_eq!(ChemicalFormula::<u64, i64>::from_str(expected).unwrap(), parsed);
+    }
+    assert_eq!(
+        ChemicalFormula::<u64, i64>::from_str("Na+9223372036854775808"),
+        Err(ParserError::Numeric(NumericError::PositiveOverflow))
+    );
+    assert_eq!(
+        ChemicalFormula::<u64, i64>::from_str("Na-9223372036854775809"),
+        Err(ParserError::Numeric(NumericError::NegativeOverflow))
+    );
+}
+
+#[test]
+fn test_overflow_consistency() {
+    // The extremes of the charge type are accepted in all notations, and
+    // the charges beyond them are rejected with an error matching their sign.
+    for (formula, expected) in [
+        ("Na-32768", Ok(-32768.0)),
+        ("Na³²⁷⁶⁸⁻", Ok(-32768.0)),
+        ("Na+32767", Ok(32767.0)),
+        ("Na³²⁷⁶⁷⁺", Ok(32767.0)),
+        ("Na-32769", Err(NumericError::NegativeOverflow)),
+        ("Na³²⁷⁶⁹⁻", Err(NumericError::NegativeOverflow)),
+        ("Na-99999999999999999999", Err(NumericError::NegativeOverflow)),
+        ("Na+32768", Err(NumericError::PositiveOverflow)),
+        ("Na³²⁷⁶⁸⁺", Err(NumericError::PositiveOverflow)),
+        ("Na⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁹⁺", Err(NumericError::PositiveOverflow)),
+    ] {
+        let parsed = ChemicalFormula::<u16, i16>::from_str(formula);
+        match expected {
+            Ok(charge) => {
+                let parsed = parsed.unwrap();
+                assert!((parsed.charge() - charge).abs() < f64::EPSILON, "{formula}");
+            }
+            Err(error) => assert_eq!(parsed, Err(ParserError::Numeric(error)), "{formula}"),
+        }
+    }
+}
+
+#[test]
+fn test_charge_wider_than_count() {
+    // Superscript numbers are only bound to the count type when they are not
+    // followed by a sign.
+    let formula = ChemicalFormula::<u8, i64>::from_str("Na³⁰⁰⁺").unwrap();
+    assert!((formula.charge() - 300.0).abs() < f64::EPSILON);
+    assert_eq!(
+        ChemicalFormula::<u8, i64>::from_str("[³⁰⁰Na]"),
+        Err(ParserError::Numeric(NumericError::PositiveOverflow))
+    );
+}
+
+#[test]
+fn test_widening_conversions() {
+    for formula in ["C6H12O6", "[Fe(CN)6]-4", "CuSO4.5H2O", "[13C]H4", "Na-128"] {
+        let narrow = ChemicalFormula::<u8, i8>::from_str(formula).unwrap();
+        let wide = ChemicalFormula::<u64, i64>::try_from(narrow.clone()).unwrap();
+        assert_eq!(wide.to_string(), narrow.to_string());
+        assert_eq!(ChemicalFormula::<u8, i8>::try_from(wide).unwrap(), narrow);
+    }
+
+    let mineral = MineralFormula::<u8, i8>::from_str("α-SiO4-4").unwrap();
+    let wide = MineralFormula::<u32, i64>::try_from(mineral.clone()).unwrap();
+    assert_eq!(wide.to_string(), mineral.to_string());
+
+    let residual = ResidualFormula::<u8, i8>::from_str("RNH3+").unwrap();
+    let wide = ResidualFormula::<u16, i32>::try_from(residual.clone()).unwrap();
+    assert_eq!(wide.to_string(), residual.to_string());
+
+    let inchi = InChIFormula::<u8>::from_str("C6H12O6").unwrap();
+    let wide = InChIFormula::<u64>::try_from(inchi.clone()).unwrap();
+    assert_eq!(wide.to_string(), inchi.to_string());
+}
+
+#[test]
+fn test_narrowing_conversions() {
+    for (formula, expected) in [
+        ("C256H4", NumericError::PositiveOverflow),
+        ("256H2O", NumericError::PositiveOverflow),
+        ("Na+128", NumericError::PositiveOverflow),
+        ("Cl-129", NumericError::NegativeOverflow),
+    ] {
+        let wide = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        assert_eq!(ChemicalFormula::<u8, i8>::try_from(wide), Err(expected), "{formula}");
+    }
+    let wide = InChIFormula::<u32>::from_str("C300H2").unwrap();
+    assert_eq!(InChIFormula::<u8>::try_from(wide), Err(NumericError::PositiveOverflow));
+}
+
+#[test]
+fn test_convert() {
+    for formula in ["C6H12O6", "[Cu(H2O)6]+2.2Cl-", "[13C]H4", "Na-32768", "CH3•"] {
+        let formula = ChemicalFormula::<u16, i16>::from_str(formula).unwrap();
+        let wide = formula.convert::<u64, i64>().unwrap();
+        assert_eq!(wide.to_string(), formula.to_string());
+        assert_eq!(wide.convert::<u16, i16>().unwrap(), formula);
+        assert_eq!(formula.convert::<u16, i16>().unwrap(), formula);
+    }
+    let formula = ChemicalFormula::<u16, i16>::from_str("Na-32768").unwrap();
+    assert_eq!(formula.convert::<u16, i8>(), Err(NumericError::NegativeOverflow));
+    let formula = ChemicalFormula::<u16, i16>::from_str("Na1000").unwrap();
+    assert_eq!(formula.convert::<u8, i16>(), Err(NumericError::PositiveOverflow));
+}
+
+#[test]
+fn test_checked_number_of_elements() {
+    let formula = ChemicalFormula::<u64, i64>::from_str("2(C18446744073709551615H)").unwrap();
+    assert_eq!(formula.try_number_of_elements::<u64>(), Err(NumericError::PositiveOverflow));
+    assert_eq!(formula.try_number_of_elements::<u128>(), Ok(36_893_488_147_419_103_232));
+
+    let formula = ChemicalFormula::<u8, i8>::from_str("C200H255.50H2O").unwrap();
+    assert_eq!(formula.try_number_of_elements::<u8>(), Err(NumericError::PositiveOverflow));
+    assert_eq!(formula.try_number_of_elements::<u16>(), Ok(605));
+    assert_eq!(formula.try_number_of_elements::<u64>(), Ok(formula.number_of_elements() as u64));
+
+    let inchi = InChIFormula::<u32>::from_str("C6H12O6").unwrap();
+    assert_eq!(inchi.try_number_of_elements::<u64>(), Ok(24));
+}
+
+#[test]
+fn test_saturating_mixture_counts() {
+    let formula = ChemicalFormula::<u8, i8>::from_str("200H2O.100NaCl").unwrap();
+    assert_eq!(formula.number_of_mixtures(), 300);
+    assert_eq!(formula.try_number_of_mixtures::<u8>(), Err(NumericError::PositiveOverflow));
+    assert_eq!(formula.try_number_of_mixtures::<u32>(), Ok(300));
+    assert_eq!(formula.number_of_elements(), 800);
+    assert_eq!(formula.number_of_non_hydrogens(), 400);
+    assert_eq!(formula.mixtures().count(), 300);
+    assert_eq!(formula.elements().count(), 800);
+}
@@ -40,3 +40,35 @@ fn test_ocr_variants() {
     assert_eq!(i1, i2);
     assert!((i1.charge() + 2.0).abs() < f64::EPSILON);
 }
+
+#[test]
+fn test_compatibility_forms() {
+    use std::str::FromStr;
+
+    use molecular_formulas::prelude::*;
+
+    let canonical: ChemicalFormula<u16, i16> = ChemicalFormula::from_str("C6H5(CH2)2OH").unwrap();
+
+    // Full-width digits, as found in formulas copied from East Asian documents.
+    let full_width_digits: ChemicalFormula<u16, i16> =
+        ChemicalFormula::from_str("C６H５(CH２)２OH").unwrap();
+    assert_eq!(canonical, full_width_digits);
+
+    // Full-width letters and brackets.
+    let full_width: ChemicalFormula<u16, i16> =
+        ChemicalFormula::from_str("Ｃ６Ｈ５（ＣＨ２）２ＯＨ").unwrap();
+    assert_eq!(canonical, full_width);
+
+    // Mathematical bold digits, as produced by some PDF exporters.
+    let bold: ChemicalFormula<u16, i16> =
+        ChemicalFormula::from_str("C\u{1D7D4}H\u{1D7D3}(CH\u{1D7D0})\u{1D7D0}OH").unwrap();
+    assert_eq!(canonical, bold);
+
+    // Combining marks are ignored.
+    let combining: ChemicalFormula<u16, i16> =
+        ChemicalFormula::from_str("C6\u{0332}H5(CH2)2\u{0301}OH").unwrap();
+    assert_eq!(canonical, combining);
+
+    // Full-width digits cannot be mixed with subscripts in the same number.
+    assert!(ChemicalFormula::<u16, i16>::from_str("C６₆").is_err());
+}
@@ -0,0 +1,49 @@
+//! Submodule testing the parsing of formulas from raw bytes, encoded either
+//! in UTF-8 or in Latin-1.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+#[test]
+fn test_utf8_bytes() {
+    for formula in ["C6H12O6", "SO₄²⁻", "CuSO4·5H2O", "[13C]H4", "α-D-C6H12O6"] {
+        let options = ParserOptions::new().with_prefix_handling(PrefixHandling::Skip);
+        assert_eq!(
+            ChemicalFormula::<u32, i32>::parse_bytes_with_options(formula.as_bytes(), &options),
+            ChemicalFormula::from_str_with_options(formula, &options),
+            "{formula}"
+        );
+    }
+}
+
+#[test]
+fn test_latin1_middle_dots() {
+    let hydrate = ChemicalFormula::<u32, i32>::parse_bytes(b"CuSO4\xB75H2O").unwrap();
+    assert_eq!(hydrate, ChemicalFormula::from_str("CuSO4.5H2O").unwrap());
+
+    let methyl = ChemicalFormula::<u32, i32>::parse_bytes(b"\xB7CH3").unwrap();
+    assert_eq!(methyl, ChemicalFormula::from_str("•CH3").unwrap());
+
+    let options = ParserOptions::new().with_middle_dot_handling(MiddleDotHandling::Radical);
+    let ethane =
+        ChemicalFormula::<u32, i32>::parse_bytes_with_options(b"CH3\xB7CH3", &options).unwrap();
+    assert_eq!(ethane.number_of_mixtures(), 1);
+}
+
+#[test]
+fn test_other_formula_types() {
+    let mineral = MineralFormula::<u32, i32>::parse_bytes(b"CaSO4\xB72H2O").unwrap();
+    assert_eq!(mineral, MineralFormula::from_str("CaSO4.2H2O").unwrap());
+    let inchi = InChIFormula::<u32>::parse_bytes(b"C2H6O").unwrap();
+    assert_eq!(inchi, InChIFormula::from_str("C2H6O").unwrap());
+}
+
+#[test]
+fn test_invalid_bytes() {
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::parse_bytes(b"C6H12\xE9O6"),
+        Err(ParserError::UnexpectedCharacter('é'))
+    );
+    assert_eq!(ChemicalFormula::<u32, i32>::parse_bytes(b""), ChemicalFormula::from_str(""));
+}
@@ -0,0 +1,90 @@
+//! Tests checking that the public parsing entry points of every formula type
+//! share the same parser stack and therefore agree on every input.
+//!
+//! All formula types are parsed by the `parsable` module: these tests guard
+//! against the `FromStr` and `TryFrom<&str>` implementations diverging, and
+//! against the Greek polymorph prefixes and residuals being handled
+//! differently depending on the entry point.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+const CASES: &[&str] = &[
+    "H2O",
+    "C6H12O6",
+    "NaCl.2H2O",
+    "[13C]H4",
+    "CH3COO-",
+    "Fe+3",
+    "(CH3)3C•",
+    "2Na+.SO4-2",
+    "C6H12O6)",
+    "",
+    "H2O..",
+    "h2o",
+];
+
+fn check_agreement<F>(input: &str)
+where
+    F: FromStr<Err = ParserError> + for<'a> TryFrom<&'a str, Error = ParserError> + PartialEq,
+    F: std::fmt::Debug + std::fmt::Display,
+{
+    let from_str = F::from_str(input);
+    let try_from = F::try_from(input);
+    let parse = input.parse::<F>();
+    assert_eq!(from_str, try_from, "`from_str` and `try_from` disagree on {input:?}");
+    assert_eq!(from_str, parse, "`from_str` and `parse` disagree on {input:?}");
+    if let Ok(formula) = from_str {
+        let reparsed = F::from_str(&formula.to_string())
+            .unwrap_or_else(|e| panic!("Failed to reparse {formula} from {input:?}: {e}"));
+        assert_eq!(formula, reparsed, "Round trip mismatch for {input:?}");
+    }
+}
+
+#[test]
+fn test_chemical_formula_entry_points() {
+    for input in CASES {
+        check_agreement::<ChemicalFormula>(input);
+    }
+}
+
+#[test]
+fn test_mineral_formula_entry_points() {
+    for input in CASES.iter().copied().chain(["α-SiO2", "β-Fe2O3", "γ-Al2O3"]) {
+        check_agreement::<MineralFormula>(input);
+    }
+}
+
+#[test]
+fn test_residual_formula_entry_points() {
+    for input in CASES.iter().copied().chain(["RCH3", "C6H5R", "R2O"]) {
+        check_agreement::<ResidualFormula>(input);
+    }
+}
+
+#[test]
+fn test_inchi_formula_entry_points() {
+    for input in ["C6H12O6", "C2H6O.H2O", "CH4", "", "H2C"] {
+        let from_str = InChIFormula::<u16>::from_str(input);
+        let parse = input.parse::<InChIFormula<u16>>();
+        assert_eq!(from_str, parse, "`from_str` and `parse` disagree on {input:?}");
+    }
+}
+
+#[test]
+fn test_extensions_agree_with_chemical_formulas() {
+    // Formulas without prefixes nor residuals must parse identically across
+    // formula types, since they are handled by the same tree parser.
+    for input in CASES {
+        let chemical = ChemicalFormula::<u16, i16>::from_str(input);
+        let mineral = MineralFormula::<u16, i16>::from_str(input);
+        let residual = ResidualFormula::<u16, i16>::from_str(input);
+        assert_eq!(chemical.is_ok(), mineral.is_ok(), "Mineral disagrees on {input:?}");
+        assert_eq!(chemical.is_ok(), residual.is_ok(), "Residual disagrees on {input:?}");
+        if let (Ok(chemical), Ok(mineral), Ok(residual)) = (chemical, mineral, residual) {
+            assert_eq!(chemical.to_string(), mineral.to_string());
+            assert_eq!(chemical.to_string(), residual.to_string());
+        }
+    }
+}
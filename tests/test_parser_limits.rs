@@ -0,0 +1,130 @@
+//! Tests for the limits which can be set through the `ParserOptions`.
+
+use molecular_formulas::{
+    errors::{ParserError, ParserLimit},
+    prelude::*,
+};
+
+#[test]
+fn test_default_options_have_no_limits() {
+    let options = ParserOptions::default();
+    assert_eq!(options.max_tokens(), None);
+    assert_eq!(options.max_depth(), None);
+    assert_eq!(options.max_count(), None);
+    let formula = ChemicalFormula::<u16, i16>::from_str_with_options(
+        "[Cu(C2H4(NH2)2)2(H2O)2]SO4.H2O",
+        &options,
+    )
+    .unwrap();
+    assert_eq!(formula, "[Cu(C2H4(NH2)2)2(H2O)2]SO4.H2O".parse().unwrap());
+}
+
+#[test]
+fn test_max_tokens() {
+    let options = ParserOptions::new().with_max_tokens(6);
+    assert!(ChemicalFormula::<u16, i16>::from_str_with_options("C6H12O6", &options).is_ok());
+    assert_eq!(
+        ChemicalFormula::<u16, i16>::from_str_with_options("C6H12O6Na", &options),
+        Err(ParserError::LimitExceeded(ParserLimit::Tokens))
+    );
+    assert_eq!(
+        InChIFormula::<u16>::from_str_with_options("C6H12O6Na", &options),
+        Err(ParserError::LimitExceeded(ParserLimit::Tokens))
+    );
+}
+
+#[test]
+fn test_max_depth() {
+    let options = ParserOptions::new().with_max_depth(1);
+    assert!(ChemicalFormula::<u16, i16>::from_str_with_options("H2O", &options).is_ok());
+    assert!(ChemicalFormula::<u16, i16>::from_str_with_options("(CH3)3N", &options).is_ok());
+    assert_eq!(
+        ChemicalFormula::<u16, i16>::from_str_with_options("[Cu(NH3)4]", &options),
+        Err(ParserError::LimitExceeded(ParserLimit::Depth))
+    );
+    assert_eq!(
+        MineralFormula::<u16, i16>::from_str_with_options("α-((SiO2))", &options),
+        Err(ParserError::LimitExceeded(ParserLimit::Depth))
+    );
+}
+
+#[test]
+fn test_max_count() {
+    let options = ParserOptions::new().with_max_count(100);
+    assert!(ChemicalFormula::<u16, i16>::from_str_with_options("C100H2", &options).is_ok());
+    assert_eq!(
+        ChemicalFormula::<u16, i16>::from_str_with_options("C101H2", &options),
+        Err(ParserError::LimitExceeded(ParserLimit::Count))
+    );
+    assert_eq!(
+        ResidualFormula::<u16, i16>::from_str_with_options("200RH", &options),
+        Err(ParserError::LimitExceeded(ParserLimit::Count))
+    );
+}
+
+/// Returns the provided formula nested within the provided number of
+/// bracket pairs.
+fn nested(opening: &str, formula: &str, closing: &str, depth: usize) -> String {
+    format!("{}{formula}{}", opening.repeat(depth), closing.repeat(depth))
+}
+
+#[test]
+fn test_nesting_depth_is_always_bounded() {
+    let depth = ParserOptions::MAX_NESTING_DEPTH;
+    for formula in [
+        nested("(", "H", ")", 10_000),
+        nested("[", "H", "]", 10_000),
+        nested("(H", "H", ")", 10_000),
+        nested("•(", "H", ")", 10_000),
+        "H+".repeat(10_000),
+        "H-".repeat(10_000),
+        "H•".repeat(10_000),
+        "H•+".repeat(10_000),
+        nested("(", "H", ")+", 10_000),
+        nested("(", "H", ")2•", 10_000),
+    ] {
+        assert_eq!(
+            ChemicalFormula::<u16, i16>::from_str_with_options(&formula, &ParserOptions::new()),
+            Err(ParserError::LimitExceeded(ParserLimit::Depth)),
+            "{}",
+            &formula[..16]
+        );
+        assert_eq!(
+            ResidualFormula::<u16, i16>::from_str_with_options(&formula, &ParserOptions::new()),
+            Err(ParserError::LimitExceeded(ParserLimit::Depth)),
+            "{}",
+            &formula[..16]
+        );
+    }
+    // A larger maximal depth does not lift the hard limit.
+    let options = ParserOptions::new().with_max_depth(usize::MAX);
+    assert_eq!(
+        ChemicalFormula::<u16, i16>::from_str_with_options(&nested("(", "H", ")", depth), &options),
+        Err(ParserError::LimitExceeded(ParserLimit::Depth))
+    );
+    assert_eq!(
+        MineralFormula::<u16, i16>::from_str_with_options(
+            &nested("(", "SiO2", ")", 10_000),
+            &options
+        ),
+        Err(ParserError::LimitExceeded(ParserLimit::Depth))
+    );
+}
+
+#[test]
+fn test_formulas_within_the_nesting_depth_are_traversable() {
+    let depth = ParserOptions::MAX_NESTING_DEPTH - 1;
+    let hydrogens = u32::try_from(depth).unwrap();
+    for (formula, expected) in [
+        (nested("(H", "H", ")", depth), hydrogens + 1),
+        (nested("[H", "H", "]", depth), hydrogens + 1),
+        (nested("(H", "H", ")+", depth), hydrogens + 1),
+        ("H+".repeat(depth), hydrogens),
+        ("H•".repeat(depth), hydrogens),
+    ] {
+        let parsed: ChemicalFormula<u32, i32> = formula.parse().unwrap();
+        assert_eq!(parsed.count_of_element::<u32>(Element::H), Some(expected));
+        assert!(parsed.isotopologue_mass() > 0.0);
+        assert!(!parsed.to_string().is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Submodule testing the greek letter prefixes carried by any formula type
+//! through the `Prefixed` wrapper.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+#[test]
+fn test_prefixed_formulas_roundtrip() {
+    for input in ["α-C₆H₁₂O₆", "β-C₆H₁₂O₆", "γ-Al₂O₃", "H₂O"] {
+        let formula = Prefixed::<ChemicalFormula<u32, i32>>::from_str(input).unwrap();
+        assert_eq!(formula.to_string(), input);
+    }
+    let inchi = Prefixed::<InChIFormula<u32>>::from_str("δ-C6H12O6").unwrap();
+    assert_eq!(inchi.to_string(), "δ-C6H12O6");
+    assert_eq!(inchi.formula(), &InChIFormula::from_str("C6H12O6").unwrap());
+}
+
+#[test]
+fn test_prefixes_are_followed_by_a_hyphen() {
+    assert_eq!(
+        Prefixed::<ChemicalFormula<u32, i32>>::from_str("αC6H12O6"),
+        Err(ParserError::UnexpectedCharacter('C'))
+    );
+    assert_eq!(
+        Prefixed::<ChemicalFormula<u32, i32>>::from_str("α"),
+        Err(ParserError::UnexpectedEndOfInput)
+    );
+    assert_eq!(
+        Prefixed::<ChemicalFormula<u32, i32>>::from_str("α-"),
+        Err(ParserError::EmptyMolecularTree)
+    );
+    let formula = Prefixed::<ChemicalFormula<u32, i32>>::from_str("α–SiO2").unwrap();
+    assert_eq!(formula.to_string(), "α-SiO₂");
+}
+
+#[test]
+fn test_prefixed_formulas_delegate_to_the_wrapped_formula() {
+    let anomer = Prefixed::<ChemicalFormula<u32, i32>>::from_str("β-C6H12O6").unwrap();
+    let glucose = ChemicalFormula::<u32, i32>::from_str("C6H12O6").unwrap();
+    assert_eq!(anomer.count_of_element::<u32>(Element::C), Some(6));
+    assert!((anomer.isotopologue_mass() - glucose.isotopologue_mass()).abs() < f64::EPSILON);
+    assert_eq!(anomer.net_charge_integer(), Some(0));
+    let style = DisplayStyle::new().with_mixture_separator(MixtureSeparator::MiddleDot);
+    assert_eq!(anomer.format_with(&style).to_string(), "β-C₆H₁₂O₆");
+
+    let (prefix, formula) = anomer.clone().with_prefix(None).into_parts();
+    assert_eq!((prefix, formula), (None, glucose));
+}
+
+#[test]
+fn test_mineral_formulas_convert_to_prefixed_formulas() {
+    let quartz = MineralFormula::<u32, i32>::from_str("α-SiO2").unwrap();
+    let prefixed: Prefixed<ChemicalFormula<u32, i32>> = quartz.clone().into();
+    assert_eq!(prefixed.polymorph_prefix(), Some(PolymorphPrefix::Alpha));
+    assert_eq!(prefixed.to_string(), quartz.to_string());
+    assert_eq!(MineralFormula::from(prefixed), quartz);
+
+    let thc = Prefixed::<ChemicalFormula<u32, i32>>::from_str("Δ9-C21H30O2").unwrap();
+    assert_eq!(MineralFormula::from(thc).to_string(), "C₂₁H₃₀O₂");
+}
+
+#[test]
+fn test_mineral_and_prefixed_formulas_read_the_same_prefixes() {
+    for input in
+        ["α-SiO2", "α-D-C6H12O6", "β-L-C6H12O6", "Δ9-C21H30O2", "α-La2O3", "β-LiAlO2", "α-D2O"]
+    {
+        let prefixed = Prefixed::<ChemicalFormula<u32, i32>>::from_str(input).unwrap();
+        let mineral = MineralFormula::<u32, i32>::from_str(input).unwrap();
+        assert_eq!(mineral, MineralFormula::from(prefixed), "{input}");
+    }
+    let anomer = MineralFormula::<u32, i32>::from_str("α-D-C6H12O6").unwrap();
+    assert_eq!(anomer.to_string(), "C₆H₁₂O₆");
+    let heavy_water = MineralFormula::<u32, i32>::from_str("α-D2O").unwrap();
+    assert_eq!(heavy_water.to_string(), "α-[²H]₂O");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_prefixed_formulas_serde_roundtrip() {
+    let anomer = Prefixed::<ChemicalFormula<u32, i32>>::from_str("β-C6H12O6").unwrap();
+    let serialized = serde_json::to_string(&anomer).unwrap();
+    assert_eq!(serialized, "\"β-C₆H₁₂O₆\"");
+    let deserialized: Prefixed<ChemicalFormula<u32, i32>> =
+        serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, anomer);
+}
@@ -0,0 +1,88 @@
+//! Submodule testing the handling of labeled prefixes, such as `Δ9-` or
+//! `α-D-`, at the start of chemical formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+fn parse(
+    formula: &str,
+    prefix_handling: PrefixHandling,
+) -> Result<ChemicalFormula<u32, i32>, ParserError> {
+    ChemicalFormula::from_str_with_options(
+        formula,
+        &ParserOptions::new().with_prefix_handling(prefix_handling),
+    )
+}
+
+fn preserve(formula: &str) -> Result<Prefixed<ChemicalFormula<u32, i32>>, ParserError> {
+    Prefixed::from_str(formula)
+}
+
+#[test]
+fn test_prefixes_are_rejected_by_default() {
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::from_str("α-D-C6H12O6"),
+        parse("α-D-C6H12O6", PrefixHandling::Reject)
+    );
+    assert!(ChemicalFormula::<u32, i32>::from_str("α-D-C6H12O6").is_err());
+}
+
+#[test]
+fn test_preserved_prefixes_roundtrip() {
+    for (formula, prefix, expected) in [
+        ("α-D-C6H12O6", "α-D", "α-D-C₆H₁₂O₆"),
+        ("β-L-C6H12O6", "β-L", "β-L-C₆H₁₂O₆"),
+        ("Δ9-C21H30O2", "Δ9", "Δ9-C₂₁H₃₀O₂"),
+        ("Δ9,12-C18H32O2", "Δ9,12", "Δ9,12-C₁₈H₃₂O₂"),
+        ("ω3-C18H30O2", "ω3", "ω3-C₁₈H₃₀O₂"),
+        ("α–C6H12O6", "α", "α-C₆H₁₂O₆"),
+    ] {
+        let parsed = preserve(formula).unwrap();
+        assert_eq!(parsed.prefix(), Some(prefix), "{formula}");
+        assert_eq!(parsed.to_string(), expected, "{formula}");
+        assert_eq!(preserve(expected).unwrap(), parsed, "{formula}");
+    }
+}
+
+#[test]
+fn test_skipped_prefixes() {
+    let skipped = parse("α-D-C6H12O6", PrefixHandling::Skip).unwrap();
+    assert_eq!(&skipped, preserve("α-D-C6H12O6").unwrap().formula());
+    assert_eq!(skipped, ChemicalFormula::from_str("C6H12O6").unwrap());
+}
+
+#[test]
+fn test_prefixes_do_not_affect_the_composition() {
+    let alpha = preserve("α-D-C6H12O6").unwrap();
+    let beta = preserve("β-D-C6H12O6").unwrap();
+    assert_ne!(alpha, beta);
+    assert!((alpha.isotopologue_mass() - beta.isotopologue_mass()).abs() < f64::EPSILON);
+    assert_eq!(alpha.clone().map(|formula| formula.neutralize()).prefix(), Some("α-D"));
+    let canonical = alpha.map(|formula| formula.normalize(NormalizationLevel::Canonical).unwrap());
+    assert_eq!(canonical.to_string(), "α-D-C₆H₁₂O₆");
+}
+
+#[test]
+fn test_malformed_prefixes() {
+    assert_eq!(preserve("αC6H12O6"), Err(ParserError::UnexpectedCharacter('C')));
+    assert_eq!(parse("α", PrefixHandling::Skip), Err(ParserError::UnexpectedEndOfInput));
+    assert_eq!(parse("α-D-", PrefixHandling::Skip), Err(ParserError::EmptyMolecularTree));
+}
+
+#[test]
+fn test_formulas_starting_with_d_or_l_after_a_prefix() {
+    for (formula, prefix, expected) in [
+        ("α-La2O3", "α", "La2O3"),
+        ("β-LiAlO2", "β", "LiAlO2"),
+        ("α-Dy2O3", "α", "Dy2O3"),
+        ("α-D", "α", "D"),
+        ("α-L-Lu2O3", "α-L", "Lu2O3"),
+    ] {
+        let expected = ChemicalFormula::<u32, i32>::from_str(expected).unwrap();
+        let preserved = preserve(formula).unwrap();
+        assert_eq!(preserved.prefix(), Some(prefix), "{formula}");
+        assert_eq!(preserved.formula(), &expected, "{formula}");
+        assert_eq!(parse(formula, PrefixHandling::Skip).unwrap(), expected, "{formula}");
+    }
+}
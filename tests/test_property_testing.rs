@@ -0,0 +1,37 @@
+//! Submodule testing the formulas generated by the `proptest` strategies,
+//! which must be valid and round-trip through their display.
+#![cfg(feature = "proptest")]
+
+use std::str::FromStr;
+
+use molecular_formulas::{prelude::*, property_testing::chemical_formulas};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_generated_formulas_roundtrip(formula in chemical_formulas::<u32, i32>()) {
+        let parsed = ChemicalFormula::<u32, i32>::from_str(&formula.to_string()).unwrap();
+        prop_assert_eq!(&parsed, &formula);
+        prop_assert_eq!(parsed.net_charge_integer(), formula.net_charge_integer());
+    }
+
+    #[test]
+    fn test_masses_are_additive(
+        left in any::<ChemicalFormula<u32, i32>>(),
+        right in any::<ChemicalFormula<u32, i32>>(),
+    ) {
+        let sum = left.clone() + right.clone();
+        let expected = left.isotopologue_mass() + right.isotopologue_mass();
+        prop_assert!((sum.isotopologue_mass() - expected).abs() < 1e-6 * expected.max(1.0));
+        prop_assert_eq!(
+            sum.net_charge_integer(),
+            Some(left.net_charge_integer().unwrap() + right.net_charge_integer().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_generated_mineral_formulas_roundtrip(formula in any::<MineralFormula<u16, i16>>()) {
+        let parsed = MineralFormula::<u16, i16>::from_str(&formula.to_string()).unwrap();
+        prop_assert_eq!(parsed.to_string(), formula.to_string());
+    }
+}
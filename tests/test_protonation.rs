@@ -0,0 +1,86 @@
+//! Submodule testing the protonation and deprotonation of chemical formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{CompositionError, NumericError, ParserError},
+    prelude::*,
+};
+
+#[test]
+fn test_protonate() {
+    for (formula, protons, expected) in [
+        ("C6H12O6", 2, "C₆H₁₄O₆²⁺"),
+        ("H2O", 1, "H₃O⁺"),
+        ("NaCl.H2O", 1, "NaCl.H₃O⁺"),
+        ("CH3COO-", 1, "CH₄COO"),
+        ("CO2", 1, "CO₂H⁺"),
+        ("[NH4]+", 1, "[NH₄]H²⁺"),
+        ("2H2O", 1, "H₂O.H₃O⁺"),
+        ("NaCl.3NH3", 2, "NaCl.2NH₃.NH₅²⁺"),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let protonated = formula.protonate(protons).unwrap();
+        assert_eq!(protonated.to_string(), expected);
+        assert_eq!(
+            protonated.count_of_element::<u32>(Element::H).unwrap(),
+            formula.count_of_element::<u32>(Element::H).unwrap() + protons
+        );
+        assert!((protonated.charge() - formula.charge() - f64::from(protons)).abs() < f64::EPSILON);
+    }
+}
+
+#[test]
+fn test_deprotonate() {
+    for (formula, protons, expected) in [
+        ("C6H12O6", 1, "C₆H₁₁O₆⁻"),
+        ("H3PO4", 3, "PO₄³⁻"),
+        ("[NH4]+", 1, "[NH₃]"),
+        ("NaCl.HCl", 1, "NaCl.Cl⁻"),
+        ("HOOCCOOH", 2, "OOCCOO²⁻"),
+        ("C2H4O2.2H2O", 1, "C₂H₄O₂.H₂O.HO⁻"),
+        ("2H2O", 1, "H₂O.HO⁻"),
+    ] {
+        let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let deprotonated = formula.deprotonate(protons).unwrap();
+        assert_eq!(deprotonated.to_string(), expected);
+        assert_eq!(
+            deprotonated.count_of_element::<u32>(Element::H).unwrap() + protons,
+            formula.count_of_element::<u32>(Element::H).unwrap()
+        );
+        assert!(
+            (formula.charge() - deprotonated.charge() - f64::from(protons)).abs() < f64::EPSILON
+        );
+        assert!(
+            (deprotonated.protonate(protons).unwrap().charge() - formula.charge()).abs()
+                < f64::EPSILON
+        );
+    }
+}
+
+#[test]
+fn test_deprotonate_errors() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("(CH3)2").unwrap();
+    assert_eq!(formula.deprotonate(1), Err(CompositionError::InsufficientHydrogens));
+
+    let formula = ChemicalFormula::<u32, i32>::from_str("H2O.NaCl").unwrap();
+    assert_eq!(formula.deprotonate(1), Err(CompositionError::InsufficientHydrogens));
+
+    let formula = ChemicalFormula::<u32, i32>::from_str("H+").unwrap();
+    assert_eq!(formula.deprotonate(1), Err(ParserError::EmptyMolecularTree.into()));
+}
+
+#[test]
+fn test_protonation_overflow() {
+    let formula = ChemicalFormula::<u8, i8>::from_str("CH255").unwrap();
+    assert_eq!(
+        formula.protonate(1),
+        Err(ParserError::Numeric(NumericError::PositiveOverflow).into())
+    );
+
+    let formula = ChemicalFormula::<u8, i8>::from_str("C").unwrap();
+    assert_eq!(
+        formula.protonate(200),
+        Err(ParserError::Numeric(NumericError::PositiveOverflow).into())
+    );
+}
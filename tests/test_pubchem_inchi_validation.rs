@@ -72,10 +72,18 @@ fn validate_pubchem_inchi(file_path: &Path) -> Result<(), Box<dyn std::error::Er
             continue;
         }
 
-        let _formula: InChIFormula = InChIFormula::from_str(formula_portion).map_err(|e| {
+        let formula: InChIFormula = InChIFormula::from_str(formula_portion).map_err(|e| {
             format!("Failed to parse formula `{}` for CID {}: {}", result.inchi, result.cid, e)
         })?;
 
+        if !formula.is_canonical() || formula.to_string() != formula_portion {
+            return Err(format!(
+                "Formula `{}` for CID {} is not displayed as its InChI formula layer",
+                result.inchi, result.cid
+            )
+            .into());
+        }
+
         parsed_count += 1;
     }
 
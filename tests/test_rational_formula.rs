@@ -0,0 +1,159 @@
+//! Submodule testing the formulas whose mixtures are repeated a rational
+//! number of times, such as hemihydrates.
+#![cfg(feature = "rational")]
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{NumericError, ParserError},
+    prelude::*,
+};
+use num_rational::Ratio;
+
+/// Parses the provided formula, reading fractional mixture multipliers.
+fn rational(formula: &str) -> Result<RationalFormula<u32, i32>, ParserError> {
+    RationalFormula::from_str_with_options(
+        formula,
+        &ParserOptions::new().with_fractional_multipliers(true),
+    )
+}
+
+#[test]
+fn test_parse_rational_formulas() {
+    for (formula, expected) in [
+        ("CaSO4.1/2H2O", "CaSO₄.1/2H₂O"),
+        ("CaSO4.2/4H2O", "CaSO₄.1/2H₂O"),
+        ("2Na2CO3.3/2H2O2", "2Na₂CO₃.3/2H₂O₂"),
+        ("CaSO4.1⁄2H2O", "CaSO₄.1/2H₂O"),
+        ("CuSO4.5H2O", "CuSO₄.5H₂O"),
+        ("CaSO4.4/2H2O", "CaSO₄.2H₂O"),
+        ("H2O", "H₂O"),
+    ] {
+        let parsed = rational(formula).unwrap();
+        assert_eq!(parsed.to_string(), expected, "{formula}");
+    }
+}
+
+#[test]
+fn test_invalid_rational_formulas() {
+    for (formula, expected) in [
+        ("CaSO4.1/H2O", ParserError::UnexpectedCharacter('H')),
+        ("CaSO4.1/", ParserError::UnexpectedEndOfInput),
+        ("CaSO4.0/2H2O", ParserError::Numeric(NumericError::LeadingZero)),
+        ("CaSO4.1/0H2O", ParserError::Numeric(NumericError::LeadingZero)),
+        ("CaSO4./2H2O", ParserError::UnexpectedCharacter('/')),
+    ] {
+        assert_eq!(rational(formula), Err(expected), "{formula}");
+    }
+}
+
+#[test]
+fn test_exact_counts_and_masses() {
+    let sesquihydrate = rational("Na2CO3.3/2H2O").unwrap();
+    assert_eq!(sesquihydrate.count_of_element(Element::H), Some(Ratio::from_integer(3)));
+    assert_eq!(sesquihydrate.count_of_element(Element::O), Some(Ratio::new(9, 2)));
+    assert_eq!(sesquihydrate.count_of_element(Element::N), Some(Ratio::from_integer(0)));
+
+    let (factor, integral) = sesquihydrate.to_integral().unwrap();
+    assert_eq!(factor, 2);
+    assert_eq!(integral, ChemicalFormula::from_str("2Na2CO3.3H2O").unwrap());
+    let expected = integral.isotopologue_mass() / 2.0;
+    assert!((sesquihydrate.isotopologue_mass() - expected).abs() < 1e-9);
+    let expected = integral.molar_mass() / 2.0;
+    assert!((sesquihydrate.molar_mass() - expected).abs() < 1e-9);
+}
+
+#[test]
+fn test_from_chemical_formula() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    let rational = RationalFormula::from(formula.clone());
+    assert_eq!(rational, RationalFormula::from_str("CuSO4.5H2O").unwrap());
+    assert_eq!(rational.to_integral(), Some((1, formula)));
+}
+
+#[test]
+fn test_charged_rational_formula() {
+    let formula = rational("Ca+2.1/2SO4-2").unwrap();
+    assert!((formula.charge() - 1.0).abs() < f64::EPSILON);
+    let electrons = formula.isotopologue_mass() - formula.isotopologue_mass_with_charge();
+    assert!((electrons - 0.000548579909065).abs() < 1e-12);
+}
+
+#[test]
+fn test_decimal_comma_multipliers() {
+    let options = ParserOptions::new().with_decimal_comma(true);
+    for (formula, expected) in [
+        ("CaSO4.2,5H2O", "CaSO₄.5/2H₂O"),
+        ("CaSO4.0,5H2O", "CaSO₄.1/2H₂O"),
+        ("Na2CO3.1,50H2O", "Na₂CO₃.3/2H₂O"),
+        ("CaSO4.2,0H2O", "CaSO₄.2H₂O"),
+        ("0,25C6H12O6", "1/4C₆H₁₂O₆"),
+        ("CaSO4·1,5H2O", "CaSO₄.3/2H₂O"),
+        ("CuSO4.5H2O", "CuSO₄.5H₂O"),
+    ] {
+        let parsed = RationalFormula::<u32, i32>::from_str_with_options(formula, &options).unwrap();
+        assert_eq!(parsed.to_string(), expected, "{formula}");
+    }
+    assert!(options.decimal_comma());
+    assert!(!ParserOptions::new().decimal_comma());
+}
+
+#[test]
+fn test_decimal_commas_require_the_option() {
+    assert_eq!(
+        RationalFormula::<u32, i32>::from_str("CaSO4.2,5H2O"),
+        Err(ParserError::UnexpectedCharacter(','))
+    );
+    let options = ParserOptions::new().with_decimal_comma(true);
+    assert_eq!(
+        ChemicalFormula::<u32, i32>::from_str_with_options("CaSO4.2,5H2O", &options),
+        Err(ParserError::UnexpectedCharacter(','))
+    );
+}
+
+#[test]
+fn test_invalid_decimal_comma_multipliers() {
+    let options = ParserOptions::new().with_decimal_comma(true);
+    for (formula, expected) in [
+        ("CaSO4.,5H2O", ParserError::UnexpectedCharacter(',')),
+        ("CaSO4.2,H2O", ParserError::UnexpectedCharacter('H')),
+        ("CaSO4.2,", ParserError::UnexpectedEndOfInput),
+        ("CaSO4.02,5H2O", ParserError::Numeric(NumericError::LeadingZero)),
+        ("CaSO4.0,0H2O", ParserError::Numeric(NumericError::LeadingZero)),
+        ("CaSO4.1,00000000000H2O", ParserError::Numeric(NumericError::PositiveOverflow)),
+    ] {
+        assert_eq!(
+            RationalFormula::<u32, i32>::from_str_with_options(formula, &options),
+            Err(expected),
+            "{formula}"
+        );
+    }
+}
+
+#[test]
+fn test_fractions_require_the_option() {
+    for formula in ["CaSO4.1/2H2O", "CaSO4.1⁄2H2O", "1/2H2O"] {
+        assert!(RationalFormula::<u32, i32>::from_str(formula).is_err(), "{formula}");
+        assert!(
+            ChemicalFormula::<u32, i32>::from_str_with_options(
+                formula,
+                &ParserOptions::new().with_fractional_multipliers(true)
+            )
+            .is_err()
+        );
+    }
+    assert_eq!(
+        RationalFormula::<u32, i32>::from_str("CaSO4.1/2H2O"),
+        Err(ParserError::UnexpectedCharacter('/'))
+    );
+    assert!(ParserOptions::new().with_fractional_multipliers(true).fractional_multipliers());
+    assert!(!ParserOptions::new().fractional_multipliers());
+
+    let options = ParserOptions::new().with_decimal_comma(true);
+    assert!(RationalFormula::<u32, i32>::from_str_with_options("CaSO4.1/2H2O", &options).is_err());
+    let options = options.with_fractional_multipliers(true);
+    assert_eq!(
+        RationalFormula::<u32, i32>::from_str_with_options("CaSO4.1/2H2O", &options),
+        RationalFormula::from_str_with_options("CaSO4.0,5H2O", &options)
+    );
+}
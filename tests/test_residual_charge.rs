@@ -0,0 +1,49 @@
+//! Submodule testing the charged API of residual formulas, whose residuals
+//! are neutral groups of unknown mass.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn residual(formula: &str) -> ResidualFormula<u32, i32> {
+    ResidualFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_residuals_are_neutral() {
+    for (formula, expected) in
+        [("RNH3+", 1), ("RCOO-", -1), ("R2PO4-2", -2), ("RNH3+.Cl-", 0), ("2RCOO-.Ca+2", 0)]
+    {
+        let formula = residual(formula);
+        assert_eq!(formula.net_charge_integer(), Some(i64::from(expected)), "{formula}");
+        assert!((formula.charge() - f64::from(expected)).abs() < f64::EPSILON);
+    }
+    assert_eq!(residual("RNH3+.Cl-").component_charges(), Some(vec![(1, 1), (1, -1)]));
+    assert!(residual("RNH3+").neutralize().charge().abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_residual_masses_are_unknown() {
+    let carboxylate = residual("RCOO-");
+    assert_eq!(carboxylate.checked_isotopologue_mass(), None);
+    assert_eq!(carboxylate.checked_isotopologue_mass_with_charge(), None);
+    assert_eq!(carboxylate.checked_molar_mass(), None);
+    // The unchecked masses only account for the atoms of the formula.
+    let atoms = ChemicalFormula::<u32, i32>::from_str("COO-").unwrap();
+    assert!((carboxylate.isotopologue_mass() - atoms.isotopologue_mass()).abs() < 1e-9);
+    assert!((carboxylate.molar_mass() - atoms.molar_mass()).abs() < 1e-9);
+}
+
+#[test]
+fn test_formulas_without_residuals_have_masses() {
+    let acetate = residual("CH3COO-");
+    let chemical = ChemicalFormula::<u32, i32>::from_str("CH3COO-").unwrap();
+    assert!(!acetate.contains_residuals());
+    assert_eq!(acetate.checked_isotopologue_mass(), Some(chemical.isotopologue_mass()));
+    assert_eq!(
+        acetate.checked_isotopologue_mass_with_charge(),
+        Some(chemical.isotopologue_mass_with_charge())
+    );
+    assert_eq!(acetate.checked_molar_mass(), Some(chemical.molar_mass()));
+    assert_eq!(acetate.count_of_element::<u32>(Element::C), Some(2));
+}
@@ -0,0 +1,69 @@
+//! Submodule testing the enumeration of the chemical formulas obtained by
+//! substituting the residuals of residual formulas with fragments.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::EnumerationError, prelude::*};
+
+/// Parses the provided fragments.
+fn fragments(fragments: &[&str]) -> Vec<ChemicalFormula<u32, i32>> {
+    fragments.iter().map(|fragment| ChemicalFormula::from_str(fragment).unwrap()).collect()
+}
+
+/// Enumerates the formulas of the provided residual formula and fragments.
+fn enumerate(formula: &str, library: &[&str]) -> Vec<String> {
+    let formula = ResidualFormula::<u32, i32>::from_str(formula).unwrap();
+    formula
+        .enumerate_with(&fragments(library))
+        .unwrap()
+        .map(|formula| formula.to_string())
+        .collect()
+}
+
+#[test]
+fn test_single_residual() {
+    assert_eq!(enumerate("C6H5R", &["CH3", "OH"]), ["C₆H₅CH₃", "C₆H₅OH"]);
+}
+
+#[test]
+fn test_several_residuals() {
+    assert_eq!(enumerate("RCH2R", &["H", "Cl"]), ["HCH₂H", "HCH₂Cl", "ClCH₂H", "ClCH₂Cl"]);
+    assert_eq!(enumerate("RCOO-.R+", &["H", "CH3"]).len(), 4);
+}
+
+#[test]
+fn test_enumerated_formulas_are_parsable() {
+    for formula in enumerate("R(CH2)2R", &["OH", "NH2", "COOH"]) {
+        let parsed = ChemicalFormula::<u32, i32>::from_str(&formula).unwrap();
+        assert_eq!(parsed.to_string(), formula);
+    }
+}
+
+#[test]
+fn test_degenerate_cases() {
+    assert_eq!(enumerate("H2O", &["CH3"]), ["H₂O"]);
+    assert_eq!(enumerate("H2O", &[]), ["H₂O"]);
+    assert!(enumerate("C6H5R", &[]).is_empty());
+}
+
+#[test]
+fn test_limit() {
+    let formula = ResidualFormula::<u32, i32>::from_str("RCRRR").unwrap();
+    let library = fragments(&["H", "F", "Cl", "Br", "I"]);
+    assert_eq!(formula.enumerate_with(&library).unwrap().count(), 625);
+    assert_eq!(formula.enumerate_with_limit(&library, 100).unwrap().count(), 100);
+    assert_eq!(formula.enumerate_with_limit(&library, 0).unwrap().count(), 0);
+}
+
+#[test]
+fn test_mixture_fragment() {
+    let formula = ResidualFormula::<u32, i32>::from_str("C6H5R").unwrap();
+    assert_eq!(
+        formula.enumerate_with(&fragments(&["CH3", "Na+.Cl-"])).err(),
+        Some(EnumerationError::MixtureFragment(1))
+    );
+    assert_eq!(
+        formula.enumerate_with(&fragments(&["2CH3"])).err(),
+        Some(EnumerationError::MixtureFragment(0))
+    );
+}
@@ -0,0 +1,72 @@
+//! Submodule testing that the display of formulas built by editing parsed
+//! formulas is parsed back into equal formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::RoundtripError, prelude::*};
+
+/// Formulas whose edited versions used to display to strings which were not
+/// parsed back into equal formulas.
+const EDGE_CASES: &[&str] = &[
+    "•Cl",
+    "·ClH",
+    "TD⁺3",
+    "Et++13",
+    "O•+•",
+    "Me2Cl+23H•",
+    "•CH3+",
+    "H•₃•",
+    "(N•)•",
+    "•(•O)",
+    "[Co(NH3)6]+3(Cl−)3",
+    "(CH3)3N.[Fe(CN)6]-3",
+    "(H2O+)2",
+    "Na+(H2O)3",
+    "C6H5O7-3.3Na+",
+    "(OH-)2Fe+2",
+];
+
+#[test]
+fn test_parsed_formulas_roundtrip() {
+    for case in EDGE_CASES {
+        let formula = ChemicalFormula::<u32, i32>::from_str(case).unwrap();
+        assert_eq!(formula.verify_roundtrip(), Ok(()), "{case}");
+    }
+}
+
+#[test]
+fn test_edited_formulas_roundtrip() {
+    for case in EDGE_CASES {
+        let formula = ChemicalFormula::<u32, i32>::from_str(case).unwrap();
+        assert_eq!(formula.neutralize().verify_roundtrip(), Ok(()), "{case}");
+        if let Ok(protonated) = formula.protonate(1) {
+            assert_eq!(protonated.verify_roundtrip(), Ok(()), "{case}");
+        }
+        if let Ok(deprotonated) = formula.deprotonate(1) {
+            assert_eq!(deprotonated.verify_roundtrip(), Ok(()), "{case}");
+        }
+        if let Ok(exchanged) = formula.exchange_h_for_d(1) {
+            assert_eq!(exchanged.verify_roundtrip(), Ok(()), "{case}");
+        }
+        let sum = formula.clone() + formula.neutralize();
+        assert_eq!(sum.verify_roundtrip(), Ok(()), "{case}");
+    }
+}
+
+#[test]
+fn test_edited_displays() {
+    let radical = ChemicalFormula::<u32, i32>::from_str("O•+•").unwrap();
+    assert_eq!(radical.neutralize().to_string(), "(O•)•");
+
+    let complex = ChemicalFormula::<u32, i32>::from_str("[Co(NH3)6]+3(Cl−)3").unwrap();
+    assert_eq!(complex.neutralize().to_string(), "[Co(NH₃)₆]Cl₃");
+}
+
+#[test]
+fn test_roundtrip_errors() {
+    let error = RoundtripError::Mismatch("H₂O".to_string());
+    assert_eq!(
+        error.to_string(),
+        "The display 'H₂O' of the formula is parsed into a different formula."
+    );
+}
@@ -0,0 +1,50 @@
+//! Submodule testing the splitting of neutral salts into their common
+//! counter-ions and their remaining core.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn split(formula: &str) -> Option<(String, Vec<String>)> {
+    let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+    let (core, ions) = formula.split_salt()?.into_parts();
+    Some((core.to_string(), ions.iter().map(ToString::to_string).collect()))
+}
+
+#[test]
+fn test_drug_salts_split_into_a_charged_core() {
+    assert_eq!(split("C20H18F3N4O8P.Na"), Some(("C₂₀H₁₈F₃N₄O₈P⁻".into(), vec!["Na⁺".into()])));
+    assert_eq!(split("C17H19NO3.HCl"), None);
+    assert_eq!(split("C17H19NO3.Cl"), Some(("C₁₇H₁₉NO₃⁺".into(), vec!["Cl⁻".into()])));
+    assert_eq!(split("2C7H15N2O.SO4"), Some(("2C₇H₁₅N₂O⁺".into(), vec!["SO₄²⁻".into()])));
+    assert_eq!(split("C6H5COO-.Na+"), Some(("C₆H₅COO⁻".into(), vec!["Na⁺".into()])));
+}
+
+#[test]
+fn test_counter_ions_balancing_each_other_leave_a_neutral_core() {
+    assert_eq!(split("C6H12O6.Na.Cl"), Some(("C₆H₁₂O₆".into(), vec!["Na⁺".into(), "Cl⁻".into()])));
+    assert_eq!(
+        split("C6H12O6.C2H6O.2K.CO3"),
+        Some(("C₆H₁₂O₆.C₂H₆O".into(), vec!["2K⁺".into(), "CO₃²⁻".into()]))
+    );
+    assert_eq!(split("CH3COO.NH4"), Some((String::new(), vec!["CH₃COO⁻".into(), "NH₄⁺".into()])));
+}
+
+#[test]
+fn test_unbalanced_or_charged_salts_are_not_split() {
+    assert_eq!(split("C6H12O6"), None);
+    assert_eq!(split("C6H12O6.C2H6O"), None);
+    assert_eq!(split("C6H5COO.Na+"), None);
+    assert_eq!(split("C6H12O6.C2H6O.Na"), None);
+}
+
+#[test]
+fn test_split_salts_preserve_their_composition() {
+    let salt = ChemicalFormula::<u32, i32>::from_str("C6H12O6.C2H6O.2K.CO3").unwrap();
+    let split = salt.split_salt().unwrap();
+    let total =
+        ChemicalFormula::total_composition(core::iter::once(split.core()).chain(split.ions()))
+            .unwrap();
+    assert_eq!(total, ChemicalFormula::total_composition([&salt]).unwrap());
+    assert_eq!(total.net_charge_integer(), Some(0));
+}
@@ -0,0 +1,140 @@
+//! Submodule testing the extraction of formulas from SD files.
+#![cfg(feature = "sdf")]
+
+use molecular_formulas::{
+    errors::ParserError,
+    sdf::{FormulaSource, SdfError, SdfReader},
+};
+
+/// Returns a V2000 molfile with the provided atoms, given as symbols and
+/// legacy charge codes, and without bonds.
+fn v2000_molfile(name: &str, atoms: &[(&str, u8)], properties: &[&str]) -> String {
+    let mut lines = vec![
+        name.to_string(),
+        "  -OEChem-01012600002D".to_string(),
+        String::new(),
+        format!("{:>3}{:>3}  0     0  0  0  0  0  0999 V2000", atoms.len(), 0),
+    ];
+    for (symbol, charge) in atoms {
+        lines.push(format!(
+            "{:>10.4}{:>10.4}{:>10.4} {symbol:<3} 0{charge:>3}  0  0  0  0  0  0  0  0  0  0",
+            0.0, 0.0, 0.0
+        ));
+    }
+    lines.extend(properties.iter().map(ToString::to_string));
+    lines.push("M  END".to_string());
+    lines.join("\n")
+}
+
+#[test]
+fn test_property_formula() {
+    let sdf = format!(
+        "{}\n> <PUBCHEM_COMPOUND_CID>\n5793\n\n> <PUBCHEM_MOLECULAR_FORMULA>\nC6H12O6\n\n$$$$\n",
+        v2000_molfile("5793", &[("C", 0), ("O", 0)], &[])
+    );
+    let records: Vec<_> = SdfReader::<_, u32, i32>::new(sdf.lines()).collect();
+    assert_eq!(records.len(), 1);
+    let record = records[0].as_ref().unwrap();
+    assert_eq!(record.name(), "5793");
+    assert_eq!(record.source(), FormulaSource::Property);
+    assert_eq!(record.formula().to_string(), "C₆H₁₂O₆");
+}
+
+#[test]
+fn test_atom_block_formula() {
+    let ethanol = v2000_molfile(
+        "ethanol",
+        &[("C", 0), ("C", 0), ("O", 0), ("H", 0), ("H", 0), ("H", 0), ("H", 0), ("H", 0), ("H", 0)],
+        &[],
+    );
+    // The legacy charge code 5 denotes a charge of -1.
+    let acetate_legacy = v2000_molfile("acetate", &[("C", 0), ("C", 0), ("O", 0), ("O", 5)], &[]);
+    // The charge property lines supersede the legacy charges.
+    let ammonium = v2000_molfile(
+        "ammonium",
+        &[("N", 5), ("H", 0), ("H", 0), ("H", 0), ("H", 0)],
+        &["M  CHG  1   1   1"],
+    );
+    let sdf = format!("{ethanol}\n$$$$\n{acetate_legacy}\n$$$$\n{ammonium}\n$$$$\n\n");
+    let formulas: Vec<(String, String, FormulaSource)> = SdfReader::<_, u32, i32>::new(sdf.lines())
+        .map(|record| {
+            let record = record.unwrap();
+            (record.name().to_string(), record.formula().to_string(), record.source())
+        })
+        .collect();
+    assert_eq!(
+        formulas,
+        vec![
+            ("ethanol".to_string(), "C₂H₆O".to_string(), FormulaSource::AtomBlock),
+            ("acetate".to_string(), "C₂O₂⁻".to_string(), FormulaSource::AtomBlock),
+            ("ammonium".to_string(), "H₄N⁺".to_string(), FormulaSource::AtomBlock),
+        ]
+    );
+}
+
+#[test]
+fn test_v3000_atom_block_formula() {
+    let sdf = "\
+methylammonium
+  RDKit          2D
+
+  0  0  0  0  0  0  0  0  0  0999 V3000
+M  V30 BEGIN CTAB
+M  V30 COUNTS 2 1 0 0 0
+M  V30 BEGIN ATOM
+M  V30 1 C 0.0 0.0 0.0 0
+M  V30 2 N 1.3 0.0 0.0 0 CHG=1
+M  V30 END ATOM
+M  V30 BEGIN BOND
+M  V30 1 1 1 2
+M  V30 END BOND
+M  V30 END CTAB
+M  END
+$$$$
+";
+    let record = SdfReader::<_, u32, i32>::new(sdf.lines()).next().unwrap().unwrap();
+    assert_eq!(record.formula().to_string(), "CN⁺");
+}
+
+#[test]
+fn test_custom_formula_properties() {
+    let sdf = format!(
+        "{}\n> <MF>\nC2H6O\n\n> <VENDOR_FORMULA>\nC2H5O-\n\n$$$$\n",
+        v2000_molfile("vendor", &[("C", 0)], &[])
+    );
+    let record = SdfReader::<_, u32, i32>::new(sdf.lines())
+        .with_formula_properties(["VENDOR_FORMULA", "MF"])
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.formula().to_string(), "C₂H₅O⁻");
+
+    let record = SdfReader::<_, u32, i32>::new(sdf.lines())
+        .with_formula_properties(Vec::<String>::new())
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.source(), FormulaSource::AtomBlock);
+    assert_eq!(record.into_formula().to_string(), "C");
+}
+
+#[test]
+fn test_sdf_errors() {
+    let invalid_property =
+        format!("{}\n> <FORMULA>\nC6H12O6)\n\n$$$$\n", v2000_molfile("invalid", &[("C", 0)], &[]));
+    let query_atom = v2000_molfile("query", &[("C", 0), ("R#", 0)], &[]);
+    let truncated = "truncated\n\n\n  3  0  0  0  0  0  0  0  0  0999 V2000\nM  END";
+    let sdf = format!("{invalid_property}{query_atom}\n$$$$\n{truncated}\n");
+    let records: Vec<_> = SdfReader::<_, u32, i32>::new(sdf.lines()).collect();
+    assert_eq!(records.len(), 3);
+    assert!(matches!(records[0], Err(SdfError::Formula { record: 0, .. })));
+    assert_eq!(records[1], Err(SdfError::UnknownAtom { record: 1, symbol: "R#".to_string() }));
+    assert_eq!(records[2], Err(SdfError::MalformedMolfile { record: 2 }));
+
+    let empty = v2000_molfile("empty", &[], &[]);
+    let record = SdfReader::<_, u32, i32>::new(empty.lines()).next().unwrap();
+    assert_eq!(
+        record,
+        Err(SdfError::Formula { record: 0, error: ParserError::EmptyMolecularTree })
+    );
+}
@@ -0,0 +1,121 @@
+//! Submodule testing that the public types of the crate, including its
+//! iterators and parsers, are `Send` and `Sync`, so that they can be shared
+//! across threads and asynchronous tasks.
+
+use std::{str::FromStr, thread};
+
+use molecular_formulas::{
+    errors::{FormulaPatternError, NumericError, ParserError, ParserLimit},
+    prelude::*,
+};
+
+/// Compiles only if the provided type is `Send` and `Sync`.
+const fn assert_send_sync<T: Send + Sync>() {}
+
+const _: () = {
+    // Formulas.
+    assert_send_sync::<ChemicalFormula>();
+    assert_send_sync::<ChemicalFormula<u64, i64>>();
+    assert_send_sync::<InChIFormula>();
+    assert_send_sync::<MineralFormula>();
+    assert_send_sync::<ResidualFormula>();
+    assert_send_sync::<LabeledFormula<ChemicalFormula>>();
+    assert_send_sync::<ByMass<ChemicalFormula>>();
+    assert_send_sync::<Adduct>();
+    assert_send_sync::<AdductTerm>();
+    assert_send_sync::<NeutralLoss>();
+    assert_send_sync::<FormulaDiff>();
+    assert_send_sync::<FormulaIndex<ChemicalFormula>>();
+    assert_send_sync::<FormulaPattern>();
+    assert_send_sync::<ElementBounds>();
+    assert_send_sync::<ExchangePolicy>();
+    assert_send_sync::<MassContext>();
+    assert_send_sync::<MassTolerance>();
+    assert_send_sync::<DisplayStyle>();
+    assert_send_sync::<Styled<'static, ChemicalFormula>>();
+
+    // Iterators.
+    assert_send_sync::<SubCompositions<u16, i16>>();
+    assert_send_sync::<EnumeratedFormulas<'static, u16, i16>>();
+    assert_send_sync::<IntoElements<u16, i16>>();
+
+    // Parsers and tokens.
+    assert_send_sync::<ParserOptions>();
+    assert_send_sync::<IncrementalParser>();
+    assert_send_sync::<SpannedTokens<'static, u16, i16>>();
+    assert_send_sync::<Spanned<Token<u16, i16, Empty>>>();
+    assert_send_sync::<Tokens<std::str::Chars<'static>, ChemicalFormula, Empty>>();
+    assert_send_sync::<SubTokens<std::str::Chars<'static>, ChemicalFormula, Empty>>();
+    assert_send_sync::<ValidationProfile>();
+    assert_send_sync::<ProfileViolation>();
+
+    // Errors.
+    assert_send_sync::<NumericError>();
+    assert_send_sync::<ParserError>();
+    assert_send_sync::<ParserLimit>();
+    assert_send_sync::<FormulaPatternError>();
+};
+
+#[cfg(feature = "rational")]
+const _: () = assert_send_sync::<RationalFormula>();
+
+#[cfg(feature = "sdf")]
+const _: () = {
+    use molecular_formulas::sdf::{FormulaSource, SdfError, SdfReader, SdfRecord};
+
+    assert_send_sync::<SdfReader<std::vec::IntoIter<String>>>();
+    assert_send_sync::<SdfRecord>();
+    assert_send_sync::<FormulaSource>();
+    assert_send_sync::<SdfError>();
+};
+
+#[cfg(feature = "modifications")]
+const _: () = assert_send_sync::<molecular_formulas::modifications::Modification>();
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use molecular_formulas::validation::{Mismatch, ValidationStats, Validator};
+
+    assert_send_sync::<Validator>();
+    assert_send_sync::<Mismatch>();
+    assert_send_sync::<ValidationStats>();
+};
+
+#[test]
+fn test_formulas_across_threads() {
+    let formulas: Vec<ChemicalFormula<u32, i32>> = ["C6H12O6", "CuSO4.5H2O", "NH4+"]
+        .into_iter()
+        .map(|formula| ChemicalFormula::from_str(formula).unwrap())
+        .collect();
+    let masses: Vec<f64> = thread::scope(|scope| {
+        let handles: Vec<_> = formulas
+            .iter()
+            .map(|formula| scope.spawn(move || formula.isotopologue_mass()))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    for (formula, mass) in formulas.iter().zip(masses) {
+        assert!((formula.isotopologue_mass() - mass).abs() < f64::EPSILON);
+    }
+}
+
+#[test]
+fn test_tokens_across_threads() {
+    let tokens = tokenize::<u32, i32>("C6H12O6");
+    let count = thread::scope(|scope| scope.spawn(move || tokens.count()).join().unwrap());
+    assert_eq!(count, 6);
+}
+
+#[test]
+fn test_iterators_across_threads() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    let elements = formula.elements();
+    let non_hydrogens = formula.non_hydrogens();
+    let (elements, non_hydrogens) = thread::scope(|scope| {
+        let elements = scope.spawn(move || elements.count());
+        let non_hydrogens = scope.spawn(move || non_hydrogens.count());
+        (elements.join().unwrap(), non_hydrogens.join().unwrap())
+    });
+    assert_eq!(elements, 21);
+    assert_eq!(non_hydrogens, 11);
+}
@@ -0,0 +1,120 @@
+//! Submodule testing the deserialization of formulas into narrower count and
+//! charge types, through the `TypeTagged` wrapper and the range errors of
+//! the plain serde implementations.
+#![cfg(feature = "serde")]
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::RangeError, prelude::*};
+
+#[test]
+fn test_type_tagged_roundtrip() {
+    let sulfate = ChemicalFormula::<u32, i32>::from_str("SO4-2").unwrap();
+    let serialized = serde_json::to_string(&TypeTagged::new(sulfate.clone())).unwrap();
+    assert_eq!(serialized, r#"{"count":"u32","charge":"i32","formula":"SO₄²⁻"}"#);
+    let same: TypeTagged<ChemicalFormula<u32, i32>> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(same.into_inner(), sulfate);
+    let narrower: TypeTagged<ChemicalFormula<u8, i8>> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(narrower.formula().to_string(), "SO₄²⁻");
+}
+
+#[test]
+fn test_type_tagged_sequence_and_field_order() {
+    let from_sequence: TypeTagged<ChemicalFormula<u16, i16>> =
+        serde_json::from_str(r#"["u64", "i64", "C6H12O6"]"#).unwrap();
+    assert_eq!(from_sequence.formula().to_string(), "C₆H₁₂O₆");
+    let reordered: TypeTagged<ChemicalFormula<u16, i16>> =
+        serde_json::from_str(r#"{"formula":"C6H12O6","charge":"i64","count":"u64"}"#).unwrap();
+    assert_eq!(reordered, from_sequence);
+}
+
+#[test]
+fn test_type_tagged_range_errors() {
+    let error = serde_json::from_str::<TypeTagged<ChemicalFormula<u16, i16>>>(
+        r#"{"count":"u32","charge":"i32","formula":"C2H6O70000"}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.starts_with(
+        "The formula serialized with `u32` counts and `i32` charges cannot be deserialized with \
+         `u16` counts and `i16` charges: The count 70000 at byte 5 of 'C2H6O70000' exceeds the \
+         range of `u16`."
+    ));
+
+    let error = serde_json::from_str::<TypeTagged<ChemicalFormula<u16, i8>>>(
+        r#"{"count":"u16","charge":"i32","formula":"SO4-200"}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.starts_with(
+        "The formula serialized with `u16` counts and `i32` charges cannot be deserialized with \
+         `u16` counts and `i8` charges: The charge -200 at byte 3 of 'SO4-200' exceeds the range \
+         of `i8`."
+    ));
+
+    let error = serde_json::from_str::<TypeTagged<InChIFormula<u8>>>(
+        r#"{"count":"u32","charge":null,"formula":"C300H602"}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.starts_with(
+        "The formula serialized with `u32` counts cannot be deserialized with `u8` counts: The \
+         count 300 at byte 1"
+    ));
+}
+
+#[test]
+fn test_type_tagged_missing_tags() {
+    let error = serde_json::from_str::<TypeTagged<ChemicalFormula<u16, i16>>>(
+        r#"{"count":"u32","formula":"H2O"}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.starts_with("missing field `charge`"));
+}
+
+#[test]
+fn test_plain_deserialization_names_the_count() {
+    let error = serde_json::from_str::<ChemicalFormula<u16, i16>>(r#""C₇₀₀₀₀H₁₄₀₀₀₂""#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.starts_with(
+            "The count 70000 at byte 1 of 'C₇₀₀₀₀H₁₄₀₀₀₂' exceeds the range of `u16`."
+        )
+    );
+
+    let error =
+        serde_json::from_str::<MineralFormula<u8, i8>>(r#""Mg2SiO300""#).unwrap_err().to_string();
+    assert!(error.starts_with("The count 300 at byte 6 of 'Mg2SiO300' exceeds the range of `u8`."));
+
+    let error = serde_json::from_str::<Prefixed<ChemicalFormula<u8, i8>>>(r#""β-C300H602""#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.starts_with("The count 300 at byte 4 of 'β-C300H602' exceeds the range of `u8`.")
+    );
+
+    let error = serde_json::from_str::<InChIFormula<u8>>(r#""C2H1000""#).unwrap_err().to_string();
+    assert!(error.starts_with("The count 1000 at byte 3 of 'C2H1000' exceeds the range of `u8`."));
+
+    let error =
+        serde_json::from_str::<ChemicalFormula<u16, i16>>(r#""H2O?""#).unwrap_err().to_string();
+    assert!(error.starts_with("Character '?' is not allowed"));
+}
+
+#[test]
+fn test_range_error() {
+    assert_eq!(
+        ResidualFormula::<u8, i8>::range_error("RC256"),
+        Some(RangeError::Count {
+            formula: "RC256".to_string(),
+            position: 2,
+            count: 256,
+            type_name: "u8",
+        })
+    );
+    assert_eq!(ResidualFormula::<u8, i8>::range_error("RC255"), None);
+    assert_eq!(ChemicalFormula::<u8, i8>::count_type_name(), "u8");
+    assert_eq!(InChIFormula::<u32>::charge_type_name(), None);
+}
@@ -0,0 +1,36 @@
+//! Submodule testing that the formulas whose subtrees are shared between
+//! clones are copied on write, so that editing a clone leaves the original
+//! formula untouched.
+#![cfg(feature = "shared-tree")]
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_edited_clones_leave_the_original_untouched() {
+    let complex = ChemicalFormula::<u32, i32>::from_str("[Cu(H2O)3(OH)]+").unwrap();
+    let clones: Vec<ChemicalFormula<u32, i32>> = (0..3).map(|_| complex.clone()).collect();
+
+    let protonated = clones[0].protonate(1).unwrap();
+    let deprotonated = clones[1].deprotonate(1).unwrap();
+    let exchanged = clones[2].exchange_h_for_d(2).unwrap();
+
+    assert_eq!(protonated.count_of_element::<u32>(Element::H), Some(8));
+    assert_eq!(deprotonated.count_of_element::<u32>(Element::H), Some(6));
+    assert_eq!(exchanged.count_of_element::<u32>(Element::H), Some(7));
+    assert!(exchanged.isotopologue_mass() > complex.isotopologue_mass());
+    for clone in &clones {
+        assert_eq!(clone, &complex);
+        assert_eq!(clone.to_string(), "[Cu(H₂O)₃(OH)]⁺");
+    }
+}
+
+#[test]
+fn test_consumed_clones_yield_the_elements_of_the_original() {
+    let formula = ChemicalFormula::<u32, i32>::from_str("(CH3)3N.[Fe(CN)6]-3").unwrap();
+    let elements: Vec<Element> = formula.clone().into_elements().collect();
+    assert_eq!(elements, formula.clone().into_elements().collect::<Vec<_>>());
+    assert_eq!(elements.len(), 26);
+    assert_eq!(formula.neutralize().to_string(), "(CH₃)₃N.[Fe(CN)₆]");
+}
@@ -0,0 +1,106 @@
+//! Submodule testing the solvent abbreviations which may be enabled in the
+//! parser options, as used in the notation of solvates.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn parse(formula: &str, options: &ParserOptions) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str_with_options(formula, options).unwrap()
+}
+
+#[test]
+fn test_solvents_are_off_by_default() {
+    assert!(ChemicalFormula::<u32, i32>::from_str("CuCl2.DMSO").is_err());
+    assert!(ChemicalFormula::<u32, i32>::from_str("NaCl.DMF").is_err());
+    assert_eq!(ParserOptions::new().solvent("DMSO"), None);
+}
+
+#[test]
+fn test_common_solvents() {
+    let options = ParserOptions::new().with_common_solvents();
+    assert_eq!(options.solvent("DMSO"), Some("(C2H6OS)"));
+
+    let quinine = parse("C20H24N2O2.2EtOH", &options);
+    assert_eq!(quinine.to_string(), "C₂₀H₂₄N₂O₂.2(C₂H₆O)");
+    assert_eq!(quinine.count_of_element::<u32>(Element::C), Some(24));
+
+    for (solvent, formula) in [
+        ("MeOH", "CH4O"),
+        ("DMSO", "C2H6OS"),
+        ("THF", "C4H8O"),
+        ("DMF", "C3H7NO"),
+        ("MeCN", "C2H3N"),
+        ("iPrOH", "C3H8O"),
+    ] {
+        let expected = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+        let solvate = parse(&format!("NaCl.{solvent}"), &options);
+        assert!(
+            (solvate.isotopologue_mass()
+                - expected.isotopologue_mass()
+                - parse("NaCl", &options).isotopologue_mass())
+            .abs()
+                < 1e-9,
+            "Unexpected mass for {solvent}"
+        );
+    }
+
+    // Solvents may also be written alone or after a middle dot.
+    assert_eq!(parse("DMSO", &options).to_string(), "(C₂H₆OS)");
+    assert_eq!(parse("CuSO4·THF", &options).count_of_element::<u32>(Element::O), Some(5));
+}
+
+#[test]
+fn test_solvents_require_whole_components() {
+    let options = ParserOptions::new().with_common_solvents();
+    // Element symbols within formulas are unaffected by the solvent names.
+    assert_eq!(parse("THFNa", &options), ChemicalFormula::from_str("THFNa").unwrap());
+    assert_eq!(parse("EtOAcNa", &options), ChemicalFormula::from_str("EtOAcNa").unwrap());
+    assert_eq!(parse("NaCl.EtOAc", &options).to_string(), "NaCl.(C₄H₈O₂)");
+    assert!(ChemicalFormula::<u32, i32>::from_str_with_options("DMSOX", &options).is_err());
+    assert_eq!(parse("NaCl.Py", &options).to_string(), "NaCl.(C₅H₅N)");
+}
+
+#[test]
+fn test_custom_solvent() {
+    let dioxane = ChemicalFormula::<u32, i32>::from_str("C4H8O2").unwrap();
+    let options = ParserOptions::new().with_solvent("Diox", &dioxane);
+    assert_eq!(parse("3Diox.H2O", &options).to_string(), "3(C₄H₈O₂).H₂O");
+    // Only the registered solvents are recognized.
+    assert!(ChemicalFormula::<u32, i32>::from_str_with_options("H2O.DMSO", &options).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Solvents must not start with a digit")]
+fn test_invalid_solvent_name() {
+    let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+    let _ = ParserOptions::new().with_solvent("2W", &water);
+}
+
+#[test]
+fn test_deuterated_solvents() {
+    let options = ParserOptions::new().with_common_solvents();
+    let dmso = ChemicalFormula::<u32, i32>::from_str("(C2D6OS)").unwrap();
+    assert_eq!(parse("d6-DMSO", &options), dmso);
+    assert_eq!(parse("DMSO-d6", &options), dmso);
+    assert_eq!(parse("d4-MeOH", &options), ChemicalFormula::from_str("(CD4O)").unwrap());
+    assert_eq!(parse("MeOH-d1", &options), ChemicalFormula::from_str("(CH3[2H]O)").unwrap());
+    assert_eq!(parse("CD3CN.d3-MeCN", &options), parse("CD3CN.(C2D3N)", &options));
+    assert_eq!(parse("NaCl.2THF-d8", &options).to_string(), "NaCl.2(C₄[²H]₈O)");
+
+    // The spelled out NMR solvents parse without any option.
+    for solvent in ["CDCl3", "CD3OD", "D2O", "C6D6", "CD2Cl2"] {
+        assert!(ChemicalFormula::<u32, i32>::from_str(solvent).is_ok(), "Failed on {solvent}");
+    }
+}
+
+#[test]
+fn test_invalid_deuteration_labels() {
+    let options = ParserOptions::new().with_common_solvents();
+    for invalid in ["d7-DMSO", "DMSO-d7", "d0-DMSO", "d6-DMSO-d6", "d6DMSO", "d6-Na"] {
+        assert!(
+            ChemicalFormula::<u32, i32>::from_str_with_options(invalid, &options).is_err(),
+            "Unexpectedly parsed {invalid}"
+        );
+    }
+}
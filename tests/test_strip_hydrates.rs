@@ -0,0 +1,40 @@
+//! Submodule testing the removal of the waters of crystallization and
+//! solvents of formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+fn strip(formula: &str, solvents: &[&str]) -> (String, u64) {
+    let formula = ChemicalFormula::<u32, i32>::from_str(formula).unwrap();
+    let solvents: Vec<ChemicalFormula<u32, i32>> =
+        solvents.iter().map(|solvent| ChemicalFormula::from_str(solvent).unwrap()).collect();
+    let (stripped, removed) = formula.strip_hydrates(&solvents);
+    (stripped.to_string(), removed)
+}
+
+#[test]
+fn test_waters_of_crystallization_are_removed() {
+    assert_eq!(strip("CuSO4.5H2O", &[]), ("CuSO₄".into(), 5));
+    assert_eq!(strip("K4[Fe(CN)6].3H2O.H2O", &[]), ("K₄[Fe(CN)₆]".into(), 4));
+    assert_eq!(strip("OH2.C6H12O6", &[]), ("C₆H₁₂O₆".into(), 1));
+    assert_eq!(strip("CaSO4.2D2O", &[]), ("CaSO₄".into(), 2));
+    assert_eq!(strip("C6H12O6", &[]), ("C₆H₁₂O₆".into(), 0));
+}
+
+#[test]
+fn test_solvents_are_removed() {
+    assert_eq!(strip("C22H19NO4.2C2H6OS", &["C2H6OS"]), ("C₂₂H₁₉NO₄".into(), 2));
+    assert_eq!(strip("C22H19NO4.CH4O.C2H3N", &["CH3OH.CH3CN"]), ("C₂₂H₁₉NO₄".into(), 2));
+    assert_eq!(strip("C22H19NO4.CH4O", &["C2H6O"]), ("C₂₂H₁₉NO₄.CH₄O".into(), 0));
+}
+
+#[test]
+fn test_charged_components_and_whole_formulas() {
+    assert_eq!(strip("H3O+.Cl-", &[]), ("H₃O⁺.Cl⁻".into(), 0));
+    assert_eq!(strip("H2O+.H2O", &[]), ("H₂O⁺".into(), 1));
+    let (stripped, removed) =
+        ChemicalFormula::<u32, i32>::from_str("3H2O").unwrap().strip_hydrates(&[]);
+    assert!(stripped.is_empty());
+    assert_eq!(removed, 3);
+}
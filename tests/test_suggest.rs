@@ -0,0 +1,50 @@
+//! Submodule testing the suggestions of valid formulas close to formulas
+//! which could not be parsed.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_suggestions_are_valid() {
+    for formula in ["NaCL", "C02", "Ca(OH2", "h2o", "Ca(OH]2", "[Fe(CN)6", "H2O)"] {
+        let suggestions = suggest::<u32, i32>(formula);
+        assert!(!suggestions.is_empty(), "{formula}");
+        for suggestion in suggestions {
+            assert!(ChemicalFormula::<u32, i32>::from_str(&suggestion).is_ok(), "{suggestion}");
+        }
+    }
+}
+
+#[test]
+fn test_suggestions_are_ranked_by_edits() {
+    assert_eq!(suggest::<u32, i32>("CL2"), ["Cl2"]);
+    assert_eq!(suggest::<u32, i32>("H2O)"), ["H2O", "H20"]);
+    assert!(suggest::<u32, i32>("h2o").contains(&"H2O".to_string()));
+
+    let suggestions = suggest::<u32, i32>("Ca(OH]2");
+    assert_eq!(suggestions[..2], ["Ca(OH)2", "Ca[OH]2"]);
+}
+
+#[test]
+fn test_no_suggestions() {
+    assert!(suggest::<u32, i32>("C6H12O6").is_empty());
+    assert!(suggest::<u32, i32>("Qq").is_empty());
+}
+
+#[test]
+fn test_long_formulas_get_single_edits() {
+    // Two edits away: the case of the chlorine and the zero.
+    let short = "CL2C0";
+    assert!(suggest::<u32, i32>(short).contains(&"Cl2CO".to_string()));
+
+    let padding = "CH2".repeat(MAX_TWO_EDITS_LENGTH / 3);
+    let long = format!("{padding}{short}");
+    assert!(long.len() > MAX_TWO_EDITS_LENGTH);
+    let suggestions = suggest::<u32, i32>(&long);
+    assert!(!suggestions.contains(&format!("{padding}Cl2CO")));
+
+    // Long formulas one edit away are still corrected.
+    let long = format!("{padding}NaCL");
+    assert_eq!(suggest::<u32, i32>(&long), [format!("{padding}NaCl")]);
+}
@@ -0,0 +1,44 @@
+//! Submodule testing the charges written with superscripts, whose
+//! magnitude may either precede or follow their sign.
+
+use std::str::FromStr;
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+fn parse(formula: &str) -> Result<ChemicalFormula<u32, i32>, ParserError> {
+    ChemicalFormula::from_str(formula)
+}
+
+#[test]
+fn test_sign_first_superscript_charges() {
+    for (sign_first, magnitude_first) in [
+        ("SO₄⁻²", "SO₄²⁻"),
+        ("SO4⁻²", "SO4²⁻"),
+        ("Fe⁺³", "Fe³⁺"),
+        ("[Fe(CN)₆]⁻⁴", "[Fe(CN)₆]⁴⁻"),
+        ("Ca⁺².2Cl⁻", "Ca²⁺.2Cl⁻"),
+        ("C₆H₅⁻¹²", "C₆H₅¹²⁻"),
+    ] {
+        assert_eq!(parse(sign_first), parse(magnitude_first), "{sign_first}");
+        assert!(parse(sign_first).is_ok(), "{sign_first}");
+    }
+    assert_eq!(parse("SO₄⁻²").unwrap().to_string(), "SO₄²⁻");
+    assert_eq!(parse("Fe⁺³").unwrap().net_charge_integer(), Some(3));
+    assert_eq!(parse("Fe⁺⁺").unwrap(), parse("Fe²⁺").unwrap());
+}
+
+#[test]
+fn test_malformed_sign_first_superscript_charges() {
+    assert_eq!(parse("Fe⁺⁰"), Err(ParserError::ZeroCharge));
+    assert_eq!(parse("Fe⁺³⁺"), Err(ParserError::UnexpectedCharacter('⁺')));
+    assert_eq!(parse("Fe⁺³-"), Err(ParserError::UnexpectedCharacter('-')));
+    assert!(parse("Fe⁺⁰³").is_err());
+    assert!(ChemicalFormula::<u32, i8>::from_str("Fe⁻²⁰⁰").is_err());
+}
+
+#[test]
+fn test_strict_profile_requires_magnitude_first_superscripts() {
+    assert!(ValidationProfile::IupacStrict.conforms::<u32, i32>("SO₄²⁻"));
+    assert!(!ValidationProfile::IupacStrict.conforms::<u32, i32>("SO₄⁻²"));
+    assert!(!ValidationProfile::IupacStrict.conforms::<u32, i32>("Fe⁺³"));
+}
@@ -0,0 +1,49 @@
+//! Submodule testing the spans of the tokens returned by the public
+//! tokenizer.
+
+use molecular_formulas::{errors::ParserError, prelude::*};
+
+/// Returns the substrings of the provided formula covered by its tokens.
+fn spans(formula: &str) -> Vec<&str> {
+    tokenize::<u32, i32>(formula).map(|token| &formula[token.unwrap().span()]).collect()
+}
+
+#[test]
+fn test_spans_cover_the_input() {
+    for formula in
+        ["H2O", "C₆H₁₂O₆", "[13C]H4", "¹³CH₄", "Fe³⁺", "CuSO4.5H2O", "[Fe(CN)6]3-", "C2H5•"]
+    {
+        assert_eq!(spans(formula).concat(), formula, "{formula}");
+    }
+}
+
+#[test]
+fn test_spans() {
+    assert_eq!(spans("H2O"), ["H", "2", "O"]);
+    assert_eq!(spans("C₆H₁₂O₆"), ["C", "₆", "H", "₁₂", "O", "₆"]);
+    assert_eq!(spans("¹³CH₄"), ["¹³C", "H", "₄"]);
+    assert_eq!(spans("Fe³⁺"), ["Fe", "³⁺"]);
+    assert_eq!(spans("[13C]H4"), ["[", "13", "C", "]", "H", "4"]);
+}
+
+#[test]
+fn test_tokens() {
+    let tokens: Vec<String> = tokenize::<u32, i32>("[Fe(CN)6]3-")
+        .map(|token| token.unwrap().into_value().to_string())
+        .collect();
+    assert_eq!(tokens, ["[", "Fe", "(", "C", "N", ")", "6", "]", "3", "⁻"]);
+}
+
+#[test]
+fn test_error_stops_the_iteration() {
+    let tokens: Vec<_> = tokenize::<u32, i32>("C₆X2").collect();
+    assert_eq!(tokens.len(), 3);
+    let error = tokens[2].clone().unwrap_err();
+    assert_eq!(error.value(), &ParserError::UnexpectedCharacter('X'));
+    assert_eq!(error.span(), 4..5);
+}
+
+#[test]
+fn test_empty_input() {
+    assert_eq!(tokenize::<u32, i32>("").count(), 0);
+}
@@ -0,0 +1,62 @@
+//! Submodule testing the sum of formulas and their total composition.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+/// Returns the formulas parsed from the provided strings.
+fn formulas(formulas: &[&str]) -> Vec<ChemicalFormula<u32, i32>> {
+    formulas.iter().map(|formula| ChemicalFormula::from_str(formula).unwrap()).collect()
+}
+
+#[test]
+fn test_sum_of_formulas() {
+    let fragments = formulas(&["H2O", "NaCl", "H2O", "C6H12O6"]);
+    let by_reference: ChemicalFormula<u32, i32> = fragments.iter().sum();
+    let by_value: ChemicalFormula<u32, i32> = fragments.clone().into_iter().sum();
+    assert_eq!(by_reference, by_value);
+    assert_eq!(by_reference.to_string(), "2H₂O.NaCl.C₆H₁₂O₆");
+
+    let empty: ChemicalFormula<u32, i32> = std::iter::empty::<ChemicalFormula<u32, i32>>().sum();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_total_composition() {
+    let fragments = formulas(&["C2H5", "OH", "CuSO4.5H2O"]);
+    let total = ChemicalFormula::total_composition(&fragments).unwrap();
+    assert_eq!(total, ChemicalFormula::from_str("C2H16CuO10S").unwrap());
+    assert_eq!(total.number_of_mixtures(), 1);
+    assert_eq!(ChemicalFormula::total_composition(fragments).unwrap(), total);
+
+    let sum: ChemicalFormula<u32, i32> = formulas(&["C2H5", "OH", "CuSO4.5H2O"]).iter().sum();
+    assert!((sum.isotopologue_mass() - total.isotopologue_mass()).abs() < 1e-9);
+}
+
+#[test]
+fn test_total_composition_charge() {
+    let ions = formulas(&["[Fe(CN)6]-4", "4K+", "Na+"]);
+    let total = ChemicalFormula::total_composition(&ions).unwrap();
+    assert_eq!(total.to_string(), "C₆FeK₄N₆Na⁺");
+    assert_eq!(total.net_charge_integer(), Some(1));
+
+    let neutral = ChemicalFormula::total_composition(formulas(&["NH4+", "Cl-"])).unwrap();
+    assert_eq!(neutral.to_string(), "ClH₄N");
+}
+
+#[test]
+fn test_total_composition_of_no_formulas() {
+    let none: [ChemicalFormula<u32, i32>; 0] = [];
+    assert_eq!(ChemicalFormula::total_composition(none), Some(ChemicalFormula::empty()));
+    let empties = [ChemicalFormula::<u32, i32>::empty(), ChemicalFormula::empty()];
+    assert_eq!(ChemicalFormula::total_composition(empties), Some(ChemicalFormula::empty()));
+}
+
+#[test]
+fn test_total_composition_overflow() {
+    let fragments: Vec<ChemicalFormula<u8, i8>> = ["C200", "C100"]
+        .iter()
+        .map(|formula| ChemicalFormula::from_str(formula).unwrap())
+        .collect();
+    assert_eq!(ChemicalFormula::total_composition(&fragments), None);
+}
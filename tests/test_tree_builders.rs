@@ -0,0 +1,110 @@
+//! Submodule testing the public builders of chemical trees, which must build
+//! the same trees as the parser does from their display.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{ParserError, ParserLimit},
+    prelude::*,
+};
+
+type Tree = ChemicalTree<u32, i32, Empty>;
+
+fn parsed(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+fn atoms(element: Element, count: u32) -> Tree {
+    Tree::from(element).repeated(count).unwrap()
+}
+
+#[test]
+fn test_built_trees_match_parsed_trees() {
+    let water = atoms(Element::H, 2).followed_by(Element::O.into()).unwrap();
+    assert_eq!(ChemicalFormula::from(water), parsed("H2O"));
+
+    let sulfate = Tree::from(Element::S)
+        .followed_by(atoms(Element::O, 4))
+        .and_then(|tree| tree.charged(-2))
+        .unwrap();
+    assert_eq!(ChemicalFormula::from(sulfate), parsed("SO4-2"));
+
+    let hydroxide = Tree::from(Element::O).followed_by(Element::H.into()).unwrap();
+    let complex = Tree::from(Element::Cu)
+        .followed_by(hydroxide.in_round_brackets().unwrap().repeated(2).unwrap())
+        .and_then(Tree::in_square_brackets)
+        .and_then(|tree| tree.charged(1))
+        .unwrap();
+    assert_eq!(ChemicalFormula::from(complex.clone()), parsed("[Cu(OH)2]+"));
+    assert_eq!(complex.to_string(), "[Cu(OH)₂]⁺");
+
+    let carbon = Tree::from(Isotope::try_from((Element::C, 13_u16)).unwrap());
+    let labeled = carbon.followed_by(atoms(Element::H, 4)).unwrap();
+    assert_eq!(ChemicalFormula::from(labeled), parsed("[13C]H4"));
+
+    let methyl = Tree::from(Element::C).followed_by(atoms(Element::H, 3)).unwrap();
+    assert_eq!(ChemicalFormula::from(methyl.clone().with_left_radical().unwrap()), parsed("·CH3"));
+    assert_eq!(ChemicalFormula::from(methyl.with_right_radical().unwrap()), parsed("CH3·"));
+}
+
+#[test]
+fn test_brackets_around_single_atoms_are_dropped() {
+    assert_eq!(Tree::from(Element::Na).in_round_brackets(), Ok(Tree::from(Element::Na)));
+    assert_eq!(Tree::from(Element::Na).in_square_brackets(), Ok(Tree::from(Element::Na)));
+    assert_eq!(Tree::from(Element::Na).repeated(1), Ok(Tree::from(Element::Na)));
+}
+
+#[test]
+fn test_repeated_units_are_bracketed() {
+    let hydrogen = atoms(Element::H, 2).repeated(3).unwrap();
+    assert_eq!(ChemicalFormula::from(hydrogen.clone()), parsed("(H2)3"));
+    assert_eq!(hydrogen.to_string(), "(H₂)₃");
+}
+
+#[test]
+fn test_charges_are_merged() {
+    let iron = Tree::from(Element::Fe).charged(2).unwrap().charged(1).unwrap();
+    assert_eq!(ChemicalFormula::from(iron.clone()), parsed("Fe+3"));
+    assert_eq!(iron.charged(-3), Ok(Tree::from(Element::Fe)));
+}
+
+#[test]
+fn test_builders_reject_invalid_trees() {
+    assert_eq!(Tree::from(Element::C).repeated(0), Err(ParserError::EmptyMolecularTree));
+    assert_eq!(Tree::from(Element::C).charged(0), Err(ParserError::ZeroCharge));
+    assert!(
+        ChemicalTree::<u8, i8, Empty>::from(Element::C).charged(120).unwrap().charged(10).is_err()
+    );
+}
+
+#[test]
+fn test_builders_enforce_the_nesting_limit() {
+    let depth_exceeded = Err(ParserError::LimitExceeded(ParserLimit::Depth));
+
+    // Nesting brackets stops at the limit instead of overflowing the stack.
+    let mut tree = Tree::from(Element::H);
+    let mut depth = 0;
+    loop {
+        match tree.clone().followed_by(Element::C.into()).and_then(Tree::in_round_brackets) {
+            Ok(nested) => tree = nested,
+            Err(error) => {
+                assert_eq!(Err::<Tree, _>(error), depth_exceeded);
+                break;
+            }
+        }
+        depth += 1;
+        assert!(depth <= 200_000, "The nesting limit was not enforced");
+    }
+    assert_eq!(depth, ParserOptions::MAX_NESTING_DEPTH);
+
+    // The limit applies to every builder nesting the tree.
+    let deep = tree.clone().followed_by(Element::O.into()).unwrap();
+    assert_eq!(deep.clone().in_square_brackets(), depth_exceeded);
+    assert_eq!(deep.clone().charged(1), depth_exceeded);
+    assert_eq!(deep.clone().with_left_radical(), depth_exceeded);
+    assert_eq!(deep.with_right_radical(), depth_exceeded);
+    assert_eq!(
+        Tree::from(Element::N).with_left_radical().unwrap().followed_by(tree),
+        depth_exceeded
+    );
+}
@@ -0,0 +1,88 @@
+//! Submodule testing the `Mass` and `Mz` newtypes and the typed mass APIs
+//! of the formulas.
+#![cfg(feature = "typed-units")]
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    prelude::*,
+    units::{Mass, Mz, TypedMasses},
+};
+
+fn formula(formula: &str) -> ChemicalFormula<u32, i32> {
+    ChemicalFormula::from_str(formula).unwrap()
+}
+
+#[test]
+fn test_typed_masses_match_the_untyped_ones() {
+    for name in ["C6H12O6", "SO4-2", "C6H13O6+", "H2O.NaCl"] {
+        let formula = formula(name);
+        assert!(
+            (formula.typed_isotopologue_mass().daltons() - formula.isotopologue_mass()).abs()
+                < f64::EPSILON
+        );
+        assert!(
+            (f64::from(formula.typed_isotopologue_mass_with_charge())
+                - formula.isotopologue_mass_with_charge())
+            .abs()
+                < f64::EPSILON
+        );
+        assert!((formula.typed_molar_mass().daltons() - formula.molar_mass()).abs() < f64::EPSILON);
+    }
+}
+
+#[test]
+fn test_conversion_between_mass_and_mz() {
+    let protonated = formula("C6H13O6+");
+    let mz = protonated.typed_mz(true, true).unwrap();
+    let mass = protonated.typed_isotopologue_mass_with_charge();
+    assert!((mass.over_charge(1.0) - mz).abs() < Mz::from_thomsons(1e-12));
+    assert!((mz.with_charge(1.0) - mass).abs() < Mass::from_daltons(1e-12));
+
+    let sulfate = formula("SO4-2");
+    let negative = sulfate.typed_mz(true, true).unwrap();
+    assert!(negative.thomsons() < 0.0);
+    assert!((negative + sulfate.typed_mz(false, true).unwrap()).abs() < Mz::from_thomsons(1e-12));
+    assert_eq!(formula("H2O").typed_mz(true, true), None);
+}
+
+#[test]
+fn test_arithmetic() {
+    let water = formula("H2O").typed_isotopologue_mass();
+    let glucose = formula("C6H12O6").typed_isotopologue_mass();
+    let sucrose = formula("C12H22O11").typed_isotopologue_mass();
+    assert!((glucose * 2.0 - water - sucrose).abs() < Mass::from_daltons(1e-9));
+    assert!((2.0 * glucose / 2.0 - glucose).abs() < Mass::from_daltons(1e-12));
+    assert!(([glucose, glucose].into_iter().sum::<Mass>() / glucose - 2.0).abs() < 1e-12);
+
+    let mut total = Mass::ZERO;
+    total += glucose;
+    total -= water;
+    assert!((total - (glucose + -water)).abs() < Mass::from_daltons(1e-12));
+    assert!(water < glucose);
+    assert!((Mass::from_kilodaltons(1.5).daltons() - 1500.0).abs() < f64::EPSILON);
+    assert!((Mass::from_daltons(66_500.0).kilodaltons() - 66.5).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_mass_accuracy() {
+    let theoretical = Mz::from_thomsons(181.070_665);
+    let observed = Mz::from_thomsons(181.071_027);
+    assert!((observed.ppm_from(theoretical) - 2.0).abs() < 0.01);
+    assert!((theoretical.ppm_from(observed) + 2.0).abs() < 0.01);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(Mass::from_daltons(18.0).to_string(), "18 Da");
+    assert_eq!(format!("{:.3}", Mass::from_daltons(18.010_565)), "18.011 Da");
+    assert_eq!(format!("{:.2}", Mz::from_thomsons(47.976_413)), "47.98 Th");
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_serde_is_transparent() {
+    let mass = Mass::from_daltons(18.5);
+    assert_eq!(serde_json::to_string(&mass).unwrap(), "18.5");
+    assert_eq!(serde_json::from_str::<Mz>("9.25").unwrap(), Mz::from_thomsons(9.25));
+}
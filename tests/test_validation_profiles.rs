@@ -0,0 +1,87 @@
+//! Submodule testing the validation profiles checking formula strings
+//! against the conventions of databases and naming authorities.
+
+use molecular_formulas::prelude::*;
+
+/// Returns the violations of the provided formula under the provided
+/// profile, without their spans.
+fn violations(profile: ValidationProfile, formula: &str) -> Vec<ProfileViolation> {
+    profile.check::<u32, i32>(formula).into_iter().map(Spanned::into_value).collect()
+}
+
+#[test]
+fn test_conforming_formulas() {
+    for formula in ["C6H12O6", "C2H3O2-", "O4S-2", "C5H5N.ClH", "CH4", "H2O", "[13C]H4", "BrK"] {
+        assert!(ValidationProfile::PubChem.conforms::<u32, i32>(formula), "{formula}");
+    }
+    for formula in ["C6H12O6", "C2H4O2.Na", "C5H5N.ClH", "CuO4S.5H2O"] {
+        assert!(ValidationProfile::Cas.conforms::<u32, i32>(formula), "{formula}");
+    }
+    for formula in ["H₂O", "SO₄²⁻", "Ca(OH)₂", "[¹³C]H₄", "¹³CH₄", "CuSO₄.5H₂O", "Na⁺", "CH₃•"]
+    {
+        assert!(ValidationProfile::IupacStrict.conforms::<u32, i32>(formula), "{formula}");
+    }
+}
+
+#[test]
+fn test_pubchem_violations() {
+    let profile = ValidationProfile::PubChem;
+    assert_eq!(violations(profile, "H₂O"), [ProfileViolation::NonAsciiCharacter]);
+    assert_eq!(violations(profile, "C1H4"), [ProfileViolation::ExplicitUnitCount]);
+    assert_eq!(
+        violations(profile, "Ca(OH)2"),
+        [ProfileViolation::RepeatedElement(Element::O), ProfileViolation::Grouping]
+    );
+    assert_eq!(violations(profile, "C2H6OS.HCl"), [ProfileViolation::NotHillOrdered]);
+    assert_eq!(
+        violations(profile, "MeOH"),
+        [
+            ProfileViolation::Abbreviation(Complex::Methyl),
+            ProfileViolation::RepeatedElement(Element::H)
+        ]
+    );
+    assert_eq!(violations(profile, "O4S++"), [ProfileViolation::ChargeNotation]);
+    assert_eq!(
+        violations(profile, "CH3•"),
+        [ProfileViolation::NonAsciiCharacter, ProfileViolation::Radical]
+    );
+}
+
+#[test]
+fn test_cas_violations() {
+    let profile = ValidationProfile::Cas;
+    assert_eq!(
+        violations(profile, "C2H3O2-.Na+"),
+        [ProfileViolation::Charged, ProfileViolation::Charged]
+    );
+    assert_eq!(violations(profile, "C6H12O6"), []);
+    assert_eq!(violations(profile, "O6C6H12"), [ProfileViolation::NotHillOrdered]);
+}
+
+#[test]
+fn test_iupac_violations() {
+    let profile = ValidationProfile::IupacStrict;
+    assert_eq!(violations(profile, "H2O"), [ProfileViolation::NumberTypography]);
+    assert_eq!(violations(profile, "₅H₂O"), [ProfileViolation::NumberTypography]);
+    assert_eq!(violations(profile, "[13C]H₄"), [ProfileViolation::NumberTypography]);
+    assert_eq!(violations(profile, "SO₄⁻²"), [ProfileViolation::ChargeNotation]);
+    assert_eq!(violations(profile, "H₂O₁"), [ProfileViolation::ExplicitUnitCount]);
+    assert_eq!(violations(profile, "CH₃COOH"), []);
+}
+
+#[test]
+fn test_unparsable_formulas() {
+    for profile in ValidationProfile::ALL {
+        let violations = profile.check::<u32, i32>("H2O)");
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(violations[0].value(), ProfileViolation::Unparsable(_)));
+    }
+}
+
+#[test]
+fn test_spans() {
+    let violations = ValidationProfile::PubChem.check::<u32, i32>("C2H6O.Cl1H");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].span(), 8..9);
+    assert_eq!(violations[0].value().to_string(), "Counts of one must be omitted.");
+}
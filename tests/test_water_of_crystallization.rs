@@ -0,0 +1,42 @@
+//! Submodule testing the counting and removal of the waters of
+//! crystallization of chemical and mineral formulas.
+
+use std::str::FromStr;
+
+use molecular_formulas::prelude::*;
+
+#[test]
+fn test_water_of_crystallization_of_chemical_formulas() {
+    let cases = [
+        ("CuSO4.5H2O", 5, "CuSO₄"),
+        ("K4[Fe(CN)6].3H2O.H2O", 4, "K₄[Fe(CN)₆]"),
+        ("C16H19N3O4S.3H2O", 3, "C₁₆H₁₉N₃O₄S"),
+        ("C6H12O6", 0, "C₆H₁₂O₆"),
+        ("H3O+.Cl-", 0, "H₃O⁺.Cl⁻"),
+        ("C22H19NO4.C2H6O", 0, "C₂₂H₁₉NO₄.C₂H₆O"),
+    ];
+    for (input, waters, anhydrous) in cases {
+        let formula = ChemicalFormula::<u32, i32>::from_str(input).unwrap();
+        assert_eq!(formula.water_of_crystallization(), waters, "{input}");
+        assert_eq!(formula.anhydrous().to_string(), anhydrous, "{input}");
+    }
+}
+
+#[test]
+fn test_anhydrous_basis_mass() {
+    let hydrate = ChemicalFormula::<u32, i32>::from_str("CuSO4.5H2O").unwrap();
+    let water = ChemicalFormula::<u32, i32>::from_str("H2O").unwrap();
+    let difference = hydrate.isotopologue_mass() - hydrate.anhydrous().isotopologue_mass();
+    assert!((difference - 5.0 * water.isotopologue_mass()).abs() < 1e-9);
+}
+
+#[test]
+fn test_water_of_crystallization_of_mineral_formulas() {
+    let gypsum = MineralFormula::<u32, i32>::from_str("CaSO4.2H2O").unwrap();
+    assert_eq!(gypsum.water_of_crystallization(), 2);
+    assert_eq!(gypsum.anhydrous().to_string(), "CaSO₄");
+
+    let hemihydrate = MineralFormula::<u32, i32>::from_str("α-2CaSO4.H2O").unwrap();
+    assert_eq!(hemihydrate.water_of_crystallization(), 1);
+    assert_eq!(hemihydrate.anhydrous().to_string(), "α-2CaSO₄");
+}
@@ -0,0 +1,54 @@
+//! Submodule testing the parser option dropping the units counted zero
+//! times and the leading zeros of counts, as emitted by some programs.
+
+use std::str::FromStr;
+
+use molecular_formulas::{
+    errors::{NumericError, ParserError},
+    prelude::*,
+};
+
+fn lenient() -> ParserOptions {
+    ParserOptions::new().with_zero_count_handling(ZeroCountHandling::Drop)
+}
+
+fn parse(formula: &str) -> Result<ChemicalFormula<u32, i32>, ParserError> {
+    ChemicalFormula::from_str_with_options(formula, &lenient())
+}
+
+#[test]
+fn test_zero_counts_are_rejected_by_default() {
+    assert_eq!(ParserOptions::new().zero_count_handling(), ZeroCountHandling::Reject);
+    for formula in ["C0H4", "C06H12O6", "(CH2)0"] {
+        assert_eq!(
+            ChemicalFormula::<u32, i32>::from_str(formula),
+            Err(ParserError::Numeric(NumericError::LeadingZero)),
+        );
+    }
+}
+
+#[test]
+fn test_zero_counts_are_dropped() {
+    for (formula, expected) in [
+        ("C0H4", "H4"),
+        ("CH4N0", "CH4"),
+        ("C6H12O6S0P0", "C6H12O6"),
+        ("C06H012O6", "C6H12O6"),
+        ("C₆H₁₂Cl₀O₆", "C6H12O6"),
+        ("CH3(CH2)0CH3", "CH3CH3"),
+        ("NaCl.2H2OD0", "NaCl.2H2O"),
+        ("[13C]H4Br0", "[13C]H4"),
+        ("Fe0Cu+2", "Cu+2"),
+    ] {
+        assert_eq!(parse(formula).unwrap(), ChemicalFormula::from_str(expected).unwrap());
+    }
+}
+
+#[test]
+fn test_zero_counts_edge_cases() {
+    assert_eq!(parse("C0"), Err(ParserError::EmptyMolecularTree));
+    assert!(parse("H2O.0NaCl").is_err());
+    assert!(parse("Fe+0").is_err());
+    let mineral = MineralFormula::<u32, i32>::from_str_with_options("Mg2Fe0SiO4", &lenient());
+    assert_eq!(mineral.unwrap(), MineralFormula::from_str("Mg2SiO4").unwrap());
+}